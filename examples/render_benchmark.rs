@@ -0,0 +1,122 @@
+//! Render Benchmark
+//!
+//! The stress benchmark's 1,000 entity world, drawn. Opens a window, runs
+//! the update and renderer together each frame and reports the tick, each
+//! render phase and the whole frame against the 60 FPS frame budget. Vsync
+//! is off so the frame is not padded out to the display's refresh. Build it
+//! in release, as the game ships:
+//!
+//! ```text
+//! cargo run --release --example render_benchmark
+//! ```
+//!
+//! Exits with an error if the average frame overruns the budget.
+
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use vampire_rpg::headless::HeadlessGame;
+use vampire_rpg::profiler::ProfileSection;
+use vampire_rpg::{AssetManager, NewGameSettings, Renderer};
+
+const ENTITY_COUNT: usize = 1000;
+const WARMUP_FRAMES: u32 = 30;
+const MEASURED_FRAMES: u32 = 600;
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+fn window_conf() -> Conf {
+    Conf {
+        window_title: "Vampire RPG Render Benchmark".to_owned(),
+        window_width: 1280,
+        window_height: 720,
+        platform: miniquad::conf::Platform {
+            swap_interval: Some(0),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[macroquad::main(window_conf)]
+async fn main() {
+    let mut game = HeadlessGame::new(NewGameSettings::default());
+    game.populate(ENTITY_COUNT);
+
+    // The infected would otherwise end the run long before it is measured
+    let player_id = game.game_state.player_id;
+    if let Some(health) = game
+        .game_state
+        .entities
+        .iter_mut()
+        .find(|entity| entity.id == player_id)
+        .and_then(|player| player.health.as_mut())
+    {
+        health.max = f32::MAX;
+        health.current = f32::MAX;
+    }
+
+    let mut assets = AssetManager::default();
+    let mut renderer = Renderer::new(None);
+    renderer.load_atlas(&mut assets);
+
+    for _ in 0..WARMUP_FRAMES {
+        game.tick();
+        renderer.render(&game.game_state);
+        renderer.take_phase_timings();
+        next_frame().await;
+    }
+
+    let mut tick_total = Duration::ZERO;
+    let mut phase_totals: HashMap<ProfileSection, Duration> = HashMap::new();
+    let mut frame_total = Duration::ZERO;
+    let mut worst_frame = Duration::ZERO;
+    for _ in 0..MEASURED_FRAMES {
+        let started = Instant::now();
+        game.tick();
+        tick_total += started.elapsed();
+        renderer.render(&game.game_state);
+        for (section, elapsed) in renderer.take_phase_timings() {
+            *phase_totals.entry(section).or_default() += elapsed;
+        }
+        // Presenting the frame is where queued draw calls reach the GPU
+        next_frame().await;
+        let elapsed = started.elapsed();
+        worst_frame = worst_frame.max(elapsed);
+        frame_total += elapsed;
+    }
+    let average_frame = frame_total / MEASURED_FRAMES;
+
+    println!("=== Vampire RPG Render Benchmark ===");
+    println!("Entities:       {}", game.game_state.entities.len());
+    println!("Frames:         {}", MEASURED_FRAMES);
+    println!(
+        "Average tick:   {:.3}ms",
+        milliseconds(tick_total / MEASURED_FRAMES)
+    );
+    for section in ProfileSection::ALL
+        .into_iter()
+        .filter(|section| section.is_render_phase())
+    {
+        let total = phase_totals.get(&section).copied().unwrap_or_default();
+        println!(
+            "{:<15} {:.3}ms",
+            format!("{}:", section.label()),
+            milliseconds(total / MEASURED_FRAMES)
+        );
+    }
+    println!("Average frame:  {:.3}ms", milliseconds(average_frame));
+    println!("Worst frame:    {:.3}ms", milliseconds(worst_frame));
+    println!(
+        "Frame budget:   {:.1}% used on average",
+        average_frame.as_secs_f64() / FRAME_BUDGET.as_secs_f64() * 100.0
+    );
+
+    if average_frame > FRAME_BUDGET {
+        eprintln!("Frames are too slow to hold 60 FPS with {ENTITY_COUNT} entities");
+        std::process::exit(1);
+    }
+}
@@ -257,6 +257,17 @@ fn spawn_test_shelter(
         shelter: Some(shelter),
         shelter_occupancy: None,
         color: WHITE,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
 
     entities.push(entity);
@@ -282,16 +293,30 @@ fn spawn_test_vampire(entities: &mut Vec<GameEntity>, next_id: &mut u32, x: f32,
             current: 50.0,
             maximum: 100.0,
             drain_rate: 1.0,
+            feeding: FeedingState::default(),
+            bottling: 0.0,
         }),
         vampire_abilities: Some(VampireAbilities {
             strength: 1.0,
             speed: 1.0,
             blood_sense: 0.0,
             shadow_movement: 0.0,
+            ability_state: AbilityState::default(),
         }),
         shelter: None,
         shelter_occupancy: Some(ShelterOccupancy::new()),
         color: PURPLE,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
 
     entities.push(entity);
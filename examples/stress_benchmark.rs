@@ -0,0 +1,71 @@
+//! Stress Benchmark
+//!
+//! Regression benchmark for the 1,000 entity target: fills the world with a
+//! mix of animals, infected and clan members, runs the full update headless
+//! and reports how long a tick takes against the 60 FPS frame budget. Only
+//! the update is timed; nothing is drawn, so the `render_benchmark` example
+//! covers whole frames. Build it in release, as the game ships:
+//!
+//! ```text
+//! cargo run --release --example stress_benchmark
+//! ```
+//!
+//! Exits with an error if the average tick overruns a quarter of the frame,
+//! leaving the rest of the frame for drawing.
+
+use std::time::{Duration, Instant};
+use vampire_rpg::headless::HeadlessGame;
+use vampire_rpg::NewGameSettings;
+
+const ENTITY_COUNT: usize = 1000;
+const WARMUP_TICKS: u64 = 30;
+const MEASURED_TICKS: u64 = 600;
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+fn main() {
+    let mut game = HeadlessGame::new(NewGameSettings::default());
+    game.populate(ENTITY_COUNT);
+
+    // The infected would otherwise end the run long before it is measured
+    let player_id = game.game_state.player_id;
+    if let Some(health) = game
+        .game_state
+        .entities
+        .iter_mut()
+        .find(|entity| entity.id == player_id)
+        .and_then(|player| player.health.as_mut())
+    {
+        health.max = f32::MAX;
+        health.current = f32::MAX;
+    }
+
+    game.simulate_ticks(WARMUP_TICKS);
+
+    let mut worst = Duration::ZERO;
+    let mut total = Duration::ZERO;
+    let mut ticks = 0u32;
+    for _ in 0..MEASURED_TICKS {
+        let started = Instant::now();
+        game.tick();
+        let elapsed = started.elapsed();
+        worst = worst.max(elapsed);
+        total += elapsed;
+        ticks += 1;
+    }
+    let average = total / ticks.max(1);
+
+    println!("=== Vampire RPG Stress Benchmark (update only) ===");
+    println!("Entities:      {}", game.game_state.entities.len());
+    println!("Ticks:         {}", ticks);
+    println!("Average tick:  {:.3}ms", average.as_secs_f64() * 1000.0);
+    println!("Worst tick:    {:.3}ms", worst.as_secs_f64() * 1000.0);
+    println!(
+        "Frame budget:  {:.1}% used by the update on average",
+        average.as_secs_f64() / FRAME_BUDGET.as_secs_f64() * 100.0
+    );
+
+    if average > FRAME_BUDGET / 4 {
+        eprintln!("Update is too slow to hold 60 FPS with {ENTITY_COUNT} entities");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,308 @@
+//! Achievements Module
+//!
+//! Lifetime stats kept across every run - kills, feedings, days survived and
+//! clans united - and the named achievements they earn. The stats are saved
+//! next to the game's saves, so they survive death and restarts; each run
+//! credits only what it adds while being played. Newly earned achievements
+//! queue a toast for the renderer.
+
+use crate::systems::SurvivalScore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Seconds an unlock toast stays on screen
+pub const TOAST_DURATION: f32 = 5.0;
+
+/// Errors that can occur while reading or writing lifetime stats
+#[derive(Debug, Error)]
+pub enum AchievementError {
+    #[error("could not access achievements file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("achievements file is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// A named goal earned once, across all runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstBlood,
+    FirstTaste,
+    SunWalker,
+    Undying,
+    Glutton,
+    Slayer,
+    FirstAlliance,
+    BloodSovereign,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 8] = [
+        Achievement::FirstBlood,
+        Achievement::FirstTaste,
+        Achievement::SunWalker,
+        Achievement::Undying,
+        Achievement::Glutton,
+        Achievement::Slayer,
+        Achievement::FirstAlliance,
+        Achievement::BloodSovereign,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Achievement::FirstBlood => "First Blood",
+            Achievement::FirstTaste => "First Taste",
+            Achievement::SunWalker => "Sun Walker",
+            Achievement::Undying => "Undying",
+            Achievement::Glutton => "Glutton",
+            Achievement::Slayer => "Slayer",
+            Achievement::FirstAlliance => "First Alliance",
+            Achievement::BloodSovereign => "Blood Sovereign",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstBlood => "Kill your first foe",
+            Achievement::FirstTaste => "Feed for the first time",
+            Achievement::SunWalker => "Survive 10 days in a single life",
+            Achievement::Undying => "Survive 50 days across all lives",
+            Achievement::Glutton => "Feed 100 times across all lives",
+            Achievement::Slayer => "Kill 100 foes across all lives",
+            Achievement::FirstAlliance => "Ally with a clan",
+            Achievement::BloodSovereign => "Unite every clan in a single life",
+        }
+    }
+
+    /// Whether the stats so far have earned this
+    pub fn is_earned(&self, stats: &LifetimeStats) -> bool {
+        match self {
+            Achievement::FirstBlood => stats.total_kills >= 1,
+            Achievement::FirstTaste => stats.total_feedings >= 1,
+            Achievement::SunWalker => stats.most_days >= 10,
+            Achievement::Undying => stats.days_survived >= 50,
+            Achievement::Glutton => stats.total_feedings >= 100,
+            Achievement::Slayer => stats.total_kills >= 100,
+            Achievement::FirstAlliance => stats.clans_united >= 1,
+            Achievement::BloodSovereign => {
+                stats.clans_total > 0 && stats.clans_united_in_one_life >= stats.clans_total
+            }
+        }
+    }
+}
+
+/// Counts for the run being played
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunStats {
+    pub kills: u32,
+    pub feedings: u32,
+    pub days: u32,
+    pub allied_clans: u32,
+    pub total_clans: u32,
+}
+
+/// Totals over every run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    pub total_kills: u32,
+    pub total_feedings: u32,
+    pub days_survived: u32,
+    /// Alliances made, counting each clan once per run
+    pub clans_united: u32,
+    pub runs_ended: u32,
+    /// Longest single life, in days
+    pub most_days: u32,
+    /// Most clans allied at once in a single life
+    pub clans_united_in_one_life: u32,
+    /// Clans in the world when that was reached
+    #[serde(default)]
+    pub clans_total: u32,
+    pub best_score: f32,
+}
+
+/// Lifetime stats and the achievements earned, as saved to disk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AchievementLog {
+    pub stats: LifetimeStats,
+    pub unlocked: Vec<Achievement>,
+}
+
+impl AchievementLog {
+    pub fn load(path: &Path) -> Result<Self, AchievementError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Read the log, starting afresh when there is none yet
+    pub fn load_or_default(path: &Path) -> Self {
+        Self::load(path).unwrap_or_default()
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), AchievementError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    /// Unlock everything the stats now earn, returning what is new
+    fn unlock_earned(&mut self) -> Vec<Achievement> {
+        let earned: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|achievement| {
+                !self.is_unlocked(*achievement) && achievement.is_earned(&self.stats)
+            })
+            .collect();
+        self.unlocked.extend(&earned);
+        earned
+    }
+}
+
+/// Credits a run's progress to the lifetime stats as it is made
+#[derive(Debug, Clone, Default)]
+pub struct AchievementTracker {
+    pub log: AchievementLog,
+    /// Run counts already credited
+    credited: RunStats,
+    /// Unlock toasts still to show, oldest first, with seconds left on the
+    /// one at the front
+    pub toasts: Vec<(Achievement, f32)>,
+    /// Changes not yet written to disk
+    pub dirty: bool,
+}
+
+impl AchievementTracker {
+    /// Start tracking a run whose counts are already at `run`
+    pub fn new(log: AchievementLog, run: RunStats) -> Self {
+        Self {
+            log,
+            credited: run,
+            toasts: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    /// Credit whatever the run has added since the last call and unlock any
+    /// achievements earned. Counts that went down, as when an earlier save is
+    /// loaded, are simply followed.
+    pub fn observe(&mut self, run: RunStats) -> Vec<Achievement> {
+        let credited = self.credited;
+        let stats = &mut self.log.stats;
+        stats.total_kills += run.kills.saturating_sub(credited.kills);
+        stats.total_feedings += run.feedings.saturating_sub(credited.feedings);
+        stats.days_survived += run.days.saturating_sub(credited.days);
+        stats.clans_united += run.allied_clans.saturating_sub(credited.allied_clans);
+        stats.most_days = stats.most_days.max(run.days);
+        if run.total_clans > 0 && run.allied_clans >= stats.clans_united_in_one_life {
+            stats.clans_united_in_one_life = run.allied_clans;
+            stats.clans_total = run.total_clans;
+        }
+        if run != credited {
+            self.dirty = true;
+        }
+        self.credited = RunStats {
+            allied_clans: credited.allied_clans.max(run.allied_clans),
+            ..run
+        };
+
+        let unlocked = self.log.unlock_earned();
+        self.toasts.extend(
+            unlocked
+                .iter()
+                .map(|achievement| (*achievement, TOAST_DURATION)),
+        );
+        unlocked
+    }
+
+    /// Record the end of a run and its final score
+    pub fn finish_run(&mut self, score: &SurvivalScore) {
+        let stats = &mut self.log.stats;
+        stats.runs_ended += 1;
+        stats.best_score = stats.best_score.max(score.overall_score);
+        self.dirty = true;
+    }
+
+    /// Count down the toast on screen, moving on to the next when it expires
+    pub fn update_toasts(&mut self, delta_time: f32) {
+        if let Some((_, remaining)) = self.toasts.first_mut() {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.toasts.remove(0);
+            }
+        }
+    }
+
+    /// Write the log if anything changed since it was last written
+    pub fn save(&mut self, path: &Path) -> Result<bool, AchievementError> {
+        if !self.dirty {
+            return Ok(false);
+        }
+        self.log.write(path)?;
+        self.dirty = false;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::BloodSystem;
+
+    #[test]
+    fn test_lifetime_stats_carry_across_runs_and_unlock_once() {
+        let path =
+            std::env::temp_dir().join(format!("vampire_achievements_{}.json", std::process::id()));
+        let run = |kills, feedings, days, allied_clans| RunStats {
+            kills,
+            feedings,
+            days,
+            allied_clans,
+            total_clans: 3,
+        };
+
+        // The first kill and feeding of a run unlock their achievements
+        let mut tracker = AchievementTracker::new(AchievementLog::default(), run(0, 0, 1, 0));
+        assert!(tracker.observe(run(0, 0, 1, 0)).is_empty());
+        assert_eq!(
+            tracker.observe(run(1, 1, 1, 0)),
+            vec![Achievement::FirstBlood, Achievement::FirstTaste]
+        );
+        assert!(tracker.observe(run(2, 1, 1, 0)).is_empty());
+        assert_eq!(tracker.toasts.len(), 2);
+        tracker.update_toasts(TOAST_DURATION);
+        assert_eq!(tracker.toasts.len(), 1);
+
+        // Loading an earlier save follows the counts down; kills made again
+        // count again
+        tracker.observe(run(1, 1, 1, 0));
+        tracker.observe(run(2, 1, 1, 0));
+        assert_eq!(tracker.log.stats.total_kills, 3);
+
+        tracker.observe(run(5, 4, 11, 1));
+        assert!(tracker.log.is_unlocked(Achievement::SunWalker));
+        assert!(tracker.log.is_unlocked(Achievement::FirstAlliance));
+        assert!(!tracker.log.is_unlocked(Achievement::BloodSovereign));
+        tracker.finish_run(&BloodSystem::calculate_survival_score(4, 11, 5));
+        assert!(tracker.save(&path).unwrap());
+        assert!(!tracker.save(&path).unwrap());
+
+        // The next life picks up the saved totals
+        let log = AchievementLog::load_or_default(&path);
+        assert_eq!(log.stats.days_survived, 10);
+        assert_eq!(log.stats.runs_ended, 1);
+        assert!(log.stats.best_score > 0.0);
+        let mut tracker = AchievementTracker::new(log, run(0, 0, 1, 0));
+        assert!(tracker.observe(run(3, 0, 1, 0)).is_empty());
+        assert_eq!(tracker.log.stats.total_kills, 9);
+        assert_eq!(
+            tracker.observe(run(3, 0, 1, 3)),
+            vec![Achievement::BloodSovereign]
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,515 @@
+//! Application Module
+//!
+//! The screens the game moves between outside of play itself: the title
+//! screen, the new game screen and the settings screen, and the in-game
+//! states (playing, paused, dead) that the main loop reads off the
+//! `GameState`. The main loop owns an `App` and switches on `App::state`.
+
+use crate::audio::AudioSettings;
+use crate::game_state::GameState;
+use crate::input::{key_name, InputAction, InputHandler};
+use crate::locale::Strings;
+use crate::save::DamagedSave;
+use crate::settings::{Difficulty, NewGameField, NewGameSettings, VideoSettings};
+use crate::systems::StartMode;
+use macroquad::prelude::KeyCode;
+
+/// Which screen the application is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppState {
+    MainMenu,
+    NewGame,
+    Settings,
+    Controls,
+    Diagnostics,
+    /// Damaged saves found at startup, offered for restoring from backup
+    RestoreSave,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+impl AppState {
+    /// The in-game state matching what the game is showing
+    pub fn of_game(game_state: &GameState) -> Self {
+        if game_state.game_over.is_some() {
+            AppState::GameOver
+        } else if game_state.paused && game_state.photo_mode.is_none() {
+            AppState::Paused
+        } else {
+            AppState::Playing
+        }
+    }
+
+    pub fn is_in_game(&self) -> bool {
+        matches!(
+            self,
+            AppState::Playing | AppState::Paused | AppState::GameOver
+        )
+    }
+}
+
+/// Entries on the title screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleOption {
+    NewGame,
+    Continue,
+    Settings,
+    Quit,
+}
+
+impl TitleOption {
+    /// Key of the option's label in the translation tables
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            TitleOption::NewGame => "title.new_game",
+            TitleOption::Continue => "title.continue",
+            TitleOption::Settings => "title.settings",
+            TitleOption::Quit => "title.quit",
+        }
+    }
+}
+
+/// Title screen selection. Continue is only offered when a save exists.
+#[derive(Debug, Clone)]
+pub struct TitleMenu {
+    options: Vec<TitleOption>,
+    selected: usize,
+}
+
+impl TitleMenu {
+    pub fn new(has_save: bool) -> Self {
+        let mut options = vec![TitleOption::NewGame];
+        if has_save {
+            options.push(TitleOption::Continue);
+        }
+        options.extend([TitleOption::Settings, TitleOption::Quit]);
+        // Returning players most likely want to carry on
+        let selected = if has_save { 1 } else { 0 };
+        Self { options, selected }
+    }
+
+    pub fn options(&self) -> &[TitleOption] {
+        &self.options
+    }
+
+    pub fn selected(&self) -> TitleOption {
+        self.options[self.selected]
+    }
+
+    /// Move the selection and return the option chosen this frame, if any
+    pub fn handle_input(&mut self, input_handler: &InputHandler) -> Option<TitleOption> {
+        let count = self.options.len();
+        if input_handler.is_action_just_pressed(InputAction::MenuDown)
+            || input_handler.is_action_just_pressed(InputAction::MoveDown)
+        {
+            self.selected = (self.selected + 1) % count;
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuUp)
+            || input_handler.is_action_just_pressed(InputAction::MoveUp)
+        {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        (input_handler.is_key_just_pressed(KeyCode::Enter)
+            || input_handler.is_action_just_pressed(InputAction::Interact))
+        .then(|| self.selected())
+    }
+}
+
+/// Rows on the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsRow {
+    GraphicsPreset,
+    MasterVolume,
+    MusicVolume,
+    EffectsVolume,
+    Mute,
+    Theme,
+    CameraLookAhead,
+    ScreenShake,
+    Sprites,
+    Vsync,
+    FrameCap,
+    UiScale,
+    Language,
+    Difficulty,
+    Controls,
+    Assets,
+}
+
+impl SettingsRow {
+    pub const ALL: [SettingsRow; 16] = [
+        SettingsRow::GraphicsPreset,
+        SettingsRow::MasterVolume,
+        SettingsRow::MusicVolume,
+        SettingsRow::EffectsVolume,
+        SettingsRow::Mute,
+        SettingsRow::Theme,
+        SettingsRow::CameraLookAhead,
+        SettingsRow::ScreenShake,
+        SettingsRow::Sprites,
+        SettingsRow::Vsync,
+        SettingsRow::FrameCap,
+        SettingsRow::UiScale,
+        SettingsRow::Language,
+        SettingsRow::Difficulty,
+        SettingsRow::Controls,
+        SettingsRow::Assets,
+    ];
+
+    /// Label and current value of the row, in the language of `strings`
+    pub fn describe(
+        &self,
+        strings: &Strings,
+        video: &VideoSettings,
+        audio: &AudioSettings,
+        difficulty: Difficulty,
+    ) -> (String, String) {
+        let percent = |volume: f32| format!("{:.0}%", volume * 100.0);
+        let (label, value) = match self {
+            SettingsRow::GraphicsPreset => {
+                ("settings.graphics", video.preset.display_name().to_string())
+            }
+            SettingsRow::MasterVolume => ("settings.master_volume", percent(audio.master)),
+            SettingsRow::MusicVolume => ("settings.music_volume", percent(audio.music)),
+            SettingsRow::EffectsVolume => ("settings.effects_volume", percent(audio.effects)),
+            SettingsRow::Mute => ("settings.sound", strings.on_off(!audio.muted).to_string()),
+            SettingsRow::Theme => ("settings.theme", video.theme.display_name().to_string()),
+            SettingsRow::CameraLookAhead => (
+                "settings.camera_look_ahead",
+                strings.on_off(video.camera_look_ahead).to_string(),
+            ),
+            SettingsRow::ScreenShake => (
+                "settings.screen_shake",
+                strings.on_off(video.screen_shake).to_string(),
+            ),
+            SettingsRow::Sprites => (
+                "settings.sprites",
+                strings
+                    .get(if video.textured_sprites {
+                        "settings.sprites_textured"
+                    } else {
+                        "settings.sprites_pixel_art"
+                    })
+                    .to_string(),
+            ),
+            SettingsRow::Vsync => (
+                "settings.vsync",
+                strings.on_off(video.frame_pacing.vsync).to_string(),
+            ),
+            SettingsRow::FrameCap => (
+                "settings.frame_cap",
+                video.frame_pacing.frame_cap.display_name(),
+            ),
+            SettingsRow::UiScale => ("settings.ui_scale", video.ui_scale.display_name()),
+            SettingsRow::Language => (
+                "settings.language",
+                video.language.display_name().to_string(),
+            ),
+            SettingsRow::Difficulty => {
+                ("settings.difficulty", difficulty.display_name().to_string())
+            }
+            SettingsRow::Controls => (
+                "settings.controls",
+                strings.get("settings.controls_value").to_string(),
+            ),
+            SettingsRow::Assets => (
+                "settings.assets",
+                strings.get("settings.assets_value").to_string(),
+            ),
+        };
+        (strings.get(label).to_string(), value)
+    }
+}
+
+/// Settings screen selection
+#[derive(Debug, Clone, Default)]
+pub struct SettingsMenu {
+    selected: usize,
+    /// Set when the player asks for the controls screen
+    open_controls: bool,
+    /// Set when the player asks for the asset diagnostics screen
+    open_diagnostics: bool,
+}
+
+impl SettingsMenu {
+    const VOLUME_STEP: f32 = 0.1;
+
+    pub fn selected(&self) -> SettingsRow {
+        SettingsRow::ALL[self.selected]
+    }
+
+    /// Move between rows and change the selected one with Left/Right.
+    /// `difficulty` is the one new games start at.
+    /// Returns true when the player leaves the screen.
+    pub fn handle_input(
+        &mut self,
+        input_handler: &InputHandler,
+        video: &mut VideoSettings,
+        audio: &mut AudioSettings,
+        difficulty: &mut Difficulty,
+    ) -> bool {
+        let count = SettingsRow::ALL.len();
+        if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+            self.selected = (self.selected + 1) % count;
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+
+        let step = if input_handler.is_action_just_pressed(InputAction::MenuRight) {
+            Self::VOLUME_STEP
+        } else if input_handler.is_action_just_pressed(InputAction::MenuLeft) {
+            -Self::VOLUME_STEP
+        } else {
+            0.0
+        };
+        if step != 0.0 {
+            let adjust = |volume: &mut f32| *volume = (*volume + step).clamp(0.0, 1.0);
+            match self.selected() {
+                SettingsRow::GraphicsPreset => {
+                    *video = video.with_preset(video.preset.toggled());
+                }
+                SettingsRow::Theme => video.theme = video.theme.next(),
+                SettingsRow::CameraLookAhead => {
+                    video.camera_look_ahead = !video.camera_look_ahead;
+                }
+                SettingsRow::ScreenShake => video.screen_shake = !video.screen_shake,
+                SettingsRow::Sprites => video.textured_sprites = !video.textured_sprites,
+                SettingsRow::Vsync => video.frame_pacing.vsync = !video.frame_pacing.vsync,
+                SettingsRow::FrameCap => {
+                    video.frame_pacing.frame_cap = video.frame_pacing.frame_cap.next();
+                }
+                SettingsRow::UiScale => video.ui_scale = video.ui_scale.next(),
+                SettingsRow::Language => video.language = video.language.next(),
+                SettingsRow::Difficulty if step > 0.0 => *difficulty = difficulty.next(),
+                SettingsRow::Difficulty => *difficulty = difficulty.previous(),
+                SettingsRow::Controls => self.open_controls = true,
+                SettingsRow::Assets => self.open_diagnostics = true,
+                SettingsRow::MasterVolume => audio.adjust_master(step),
+                SettingsRow::MusicVolume => adjust(&mut audio.music),
+                SettingsRow::EffectsVolume => adjust(&mut audio.effects),
+                SettingsRow::Mute => audio.toggle_mute(),
+            }
+        }
+
+        input_handler.is_action_just_pressed(InputAction::Pause)
+            || input_handler.is_key_just_pressed(KeyCode::Enter)
+    }
+
+    /// Whether the controls screen was asked for, clearing the request
+    pub fn take_open_controls(&mut self) -> bool {
+        std::mem::take(&mut self.open_controls)
+    }
+
+    /// Whether the asset diagnostics screen was asked for, clearing the
+    /// request
+    pub fn take_open_diagnostics(&mut self) -> bool {
+        std::mem::take(&mut self.open_diagnostics)
+    }
+}
+
+/// Controls screen: pick an action, then press the key to bind to it
+#[derive(Debug, Clone, Default)]
+pub struct ControlsMenu {
+    selected: usize,
+    /// Waiting for the key to bind to the selected action
+    capturing: bool,
+    /// Outcome of the last save, shown on the screen
+    pub status: Option<String>,
+}
+
+impl ControlsMenu {
+    pub fn selected(&self) -> InputAction {
+        InputAction::ALL[self.selected]
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    /// Move between actions, and rebind the selected one with Enter
+    /// followed by the new key. Esc cancels a rebind, or otherwise leaves
+    /// the screen, which returns true. Fixed keys are used here so a bad
+    /// binding can never lock the player out of fixing it.
+    pub fn handle_input(&mut self, input_handler: &mut InputHandler) -> bool {
+        if self.capturing {
+            let pressed: Vec<KeyCode> = input_handler.keys_just_pressed().collect();
+            if pressed.contains(&KeyCode::Escape) {
+                self.capturing = false;
+            } else if let Some(key) = pressed.into_iter().find(|key| key_name(*key).is_some()) {
+                let action = self.selected();
+                input_handler.action_map_mut().set_keys(action, &[key]);
+                self.capturing = false;
+            }
+            return false;
+        }
+
+        let count = InputAction::ALL.len();
+        if input_handler.is_key_just_pressed(KeyCode::Down) {
+            self.selected = (self.selected + 1) % count;
+        }
+        if input_handler.is_key_just_pressed(KeyCode::Up) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if input_handler.is_key_just_pressed(KeyCode::Enter) {
+            self.capturing = true;
+            self.status = None;
+        }
+        input_handler.is_key_just_pressed(KeyCode::Escape)
+    }
+}
+
+/// Application-level state: the current screen, the menus, and the options
+/// chosen outside of a game that every new or continued game starts with
+#[derive(Debug, Clone)]
+pub struct App {
+    pub state: AppState,
+    pub title_menu: TitleMenu,
+    pub settings_menu: SettingsMenu,
+    pub controls_menu: ControlsMenu,
+    pub new_game: NewGameSettings,
+    /// Which new game field the open text input fills in
+    pub new_game_field: NewGameField,
+    pub video_settings: VideoSettings,
+    pub audio_settings: AudioSettings,
+    /// Saves still waiting on the restore prompt
+    pub damaged_saves: Vec<DamagedSave>,
+}
+
+impl App {
+    pub fn new(has_save: bool) -> Self {
+        Self {
+            state: AppState::MainMenu,
+            title_menu: TitleMenu::new(has_save),
+            settings_menu: SettingsMenu::default(),
+            controls_menu: ControlsMenu::default(),
+            new_game: NewGameSettings {
+                start_mode: StartMode::Guided,
+                ..NewGameSettings::default()
+            },
+            new_game_field: NewGameField::default(),
+            video_settings: VideoSettings::default(),
+            audio_settings: AudioSettings::default(),
+            damaged_saves: Vec::new(),
+        }
+    }
+
+    /// Go back to the title screen, keeping the options chosen in game
+    pub fn return_to_title(&mut self, game_state: &GameState, has_save: bool) {
+        self.video_settings = game_state.video_settings.clone();
+        self.audio_settings = game_state.audio_settings.clone();
+        self.title_menu = TitleMenu::new(has_save);
+        self.state = AppState::MainMenu;
+    }
+
+    /// Hand the options chosen on the menus to a game about to start
+    pub fn apply_settings(&self, game_state: &mut GameState) {
+        game_state.video_settings = self.video_settings.clone();
+        game_state.audio_settings = self.audio_settings.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn press(input: &mut InputHandler, key: KeyCode) {
+        input.update_with(HashSet::new(), Vec::new());
+        input.update_with([key].into_iter().collect(), Vec::new());
+    }
+
+    #[test]
+    fn test_title_menu_offers_continue_only_with_a_save() {
+        let mut input = InputHandler::new();
+        let mut menu = TitleMenu::new(false);
+        assert!(!menu.options().contains(&TitleOption::Continue));
+
+        press(&mut input, KeyCode::Down);
+        assert_eq!(menu.handle_input(&input), None);
+        press(&mut input, KeyCode::Enter);
+        assert_eq!(menu.handle_input(&input), Some(TitleOption::Settings));
+
+        let menu = TitleMenu::new(true);
+        assert_eq!(menu.selected(), TitleOption::Continue);
+    }
+
+    #[test]
+    fn test_settings_menu_adjusts_and_closes() {
+        let mut input = InputHandler::new();
+        let mut menu = SettingsMenu::default();
+        let mut video = VideoSettings::default();
+        let mut audio = AudioSettings::default();
+        let mut difficulty = Difficulty::default();
+
+        press(&mut input, KeyCode::Right);
+        assert!(!menu.handle_input(&input, &mut video, &mut audio, &mut difficulty));
+        assert!(video.is_low_spec());
+
+        press(&mut input, KeyCode::Down);
+        menu.handle_input(&input, &mut video, &mut audio, &mut difficulty);
+        press(&mut input, KeyCode::Left);
+        menu.handle_input(&input, &mut video, &mut audio, &mut difficulty);
+        assert!((audio.master - 0.7).abs() < 1e-5);
+
+        let difficulty_row = SettingsRow::ALL
+            .iter()
+            .position(|row| *row == SettingsRow::Difficulty)
+            .unwrap();
+        for _ in 1..difficulty_row {
+            press(&mut input, KeyCode::Down);
+            menu.handle_input(&input, &mut video, &mut audio, &mut difficulty);
+        }
+        press(&mut input, KeyCode::Left);
+        menu.handle_input(&input, &mut video, &mut audio, &mut difficulty);
+        assert_eq!(difficulty, Difficulty::Easy);
+
+        for _ in 0..2 {
+            press(&mut input, KeyCode::Down);
+            menu.handle_input(&input, &mut video, &mut audio, &mut difficulty);
+        }
+        press(&mut input, KeyCode::Right);
+        menu.handle_input(&input, &mut video, &mut audio, &mut difficulty);
+        assert_eq!(menu.selected(), SettingsRow::Assets);
+        assert!(menu.take_open_diagnostics());
+        assert!(!menu.take_open_diagnostics());
+
+        press(&mut input, KeyCode::Escape);
+        assert!(menu.handle_input(&input, &mut video, &mut audio, &mut difficulty));
+    }
+
+    #[test]
+    fn test_in_game_state_follows_game() {
+        let mut game_state = GameState::new();
+        assert_eq!(AppState::of_game(&game_state), AppState::Playing);
+        game_state.paused = true;
+        assert_eq!(AppState::of_game(&game_state), AppState::Paused);
+        assert!(AppState::of_game(&game_state).is_in_game());
+        assert!(!AppState::MainMenu.is_in_game());
+    }
+
+    #[test]
+    fn test_controls_menu_rebinds_selected_action() {
+        let mut input = InputHandler::new();
+        let mut menu = ControlsMenu::default();
+
+        press(&mut input, KeyCode::Down);
+        menu.handle_input(&mut input);
+        assert_eq!(menu.selected(), InputAction::MoveDown);
+
+        press(&mut input, KeyCode::Enter);
+        menu.handle_input(&mut input);
+        assert!(menu.is_capturing());
+        press(&mut input, KeyCode::Down);
+        assert!(!menu.handle_input(&mut input));
+        assert!(!menu.is_capturing());
+        assert_eq!(
+            input.action_map().keys_for(InputAction::MoveDown),
+            vec![KeyCode::Down]
+        );
+
+        press(&mut input, KeyCode::Escape);
+        assert!(menu.handle_input(&mut input));
+    }
+}
@@ -0,0 +1,199 @@
+//! Assets Module
+//!
+//! Finds the font, sounds and sprite atlas at runtime instead of compiling
+//! them into the game, so a packaged build can ship its `assets` folder next
+//! to the executable, run from the repository, or pick up assets installed
+//! in the user's data directory. Each asset has a fallback (the default
+//! font, silence, pixel-art sprites), and the manager keeps a report of what
+//! was found where, shown on the asset diagnostics screen.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the assets folder in each search location
+pub const ASSETS_DIR: &str = "assets";
+/// Folder under the user's data directory holding installed assets
+const USER_DIR: &str = "vampire-rpg";
+
+/// What became of one asset
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetOutcome {
+    /// Read from this file
+    Loaded(PathBuf),
+    /// Not in any search location
+    Missing,
+    /// Found, but could not be used
+    Invalid(PathBuf, String),
+}
+
+/// One asset the game asked for and how that went
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetReport {
+    /// Path inside the assets folder, e.g. `sounds/attack_hit.wav`
+    pub name: String,
+    pub outcome: AssetOutcome,
+    /// What the game uses instead when the asset is unusable
+    pub fallback: String,
+}
+
+impl AssetReport {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.outcome, AssetOutcome::Loaded(_))
+    }
+
+    /// One line for the diagnostics screen
+    pub fn describe(&self) -> String {
+        match &self.outcome {
+            AssetOutcome::Loaded(path) => format!("{}: {}", self.name, path.display()),
+            AssetOutcome::Missing => format!("{}: missing, using {}", self.name, self.fallback),
+            AssetOutcome::Invalid(_, error) => {
+                format!("{}: {}, using {}", self.name, error, self.fallback)
+            }
+        }
+    }
+}
+
+/// Looks assets up in each search location in turn and records the results
+#[derive(Debug, Clone)]
+pub struct AssetManager {
+    search_paths: Vec<PathBuf>,
+    reports: Vec<AssetReport>,
+}
+
+impl Default for AssetManager {
+    fn default() -> Self {
+        Self::new(Self::default_search_paths())
+    }
+}
+
+impl AssetManager {
+    pub fn new(search_paths: Vec<PathBuf>) -> Self {
+        Self {
+            search_paths,
+            reports: Vec::new(),
+        }
+    }
+
+    /// Next to the executable, then the working directory, then the user's
+    /// data directory
+    pub fn default_search_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if let Some(exe_dir) = env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        {
+            paths.push(exe_dir.join(ASSETS_DIR));
+        }
+        if let Ok(cwd) = env::current_dir() {
+            paths.push(cwd.join(ASSETS_DIR));
+        }
+        let user_data = env::var_os("APPDATA")
+            .or_else(|| env::var_os("XDG_DATA_HOME"))
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")));
+        if let Some(user_data) = user_data {
+            paths.push(user_data.join(USER_DIR).join(ASSETS_DIR));
+        }
+        // Running from the executable's folder puts it in the list twice
+        paths.dedup();
+        paths
+    }
+
+    pub fn search_paths(&self) -> &[PathBuf] {
+        &self.search_paths
+    }
+
+    /// Every asset asked for so far
+    pub fn reports(&self) -> &[AssetReport] {
+        &self.reports
+    }
+
+    /// Assets that are missing or could not be used
+    pub fn problems(&self) -> impl Iterator<Item = &AssetReport> {
+        self.reports.iter().filter(|report| !report.is_ok())
+    }
+
+    /// The first search location holding the asset
+    pub fn find(&self, name: &str) -> Option<PathBuf> {
+        self.search_paths
+            .iter()
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// Read an asset, recording it as missing (with what is used instead)
+    /// when no search location has it
+    pub fn read(&mut self, name: &str, fallback: &str) -> Option<Vec<u8>> {
+        let found = self
+            .find(name)
+            .and_then(|path| fs::read(&path).ok().map(|bytes| (path, bytes)));
+        let (outcome, bytes) = match found {
+            Some((path, bytes)) => (AssetOutcome::Loaded(path), Some(bytes)),
+            None => (AssetOutcome::Missing, None),
+        };
+        self.record(name, outcome, fallback);
+        bytes
+    }
+
+    /// Mark an asset that was read as unusable, e.g. when it fails to decode
+    pub fn mark_invalid(&mut self, name: &str, error: impl ToString) {
+        if let Some(report) = self
+            .reports
+            .iter_mut()
+            .rev()
+            .find(|report| report.name == name)
+        {
+            let path = match &report.outcome {
+                AssetOutcome::Loaded(path) | AssetOutcome::Invalid(path, _) => path.clone(),
+                AssetOutcome::Missing => return,
+            };
+            report.outcome = AssetOutcome::Invalid(path, error.to_string());
+        }
+    }
+
+    fn record(&mut self, name: &str, outcome: AssetOutcome, fallback: &str) {
+        self.reports.retain(|report| report.name != name);
+        self.reports.push(AssetReport {
+            name: name.to_string(),
+            outcome,
+            fallback: fallback.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assets_searched_in_order_with_missing_reported() {
+        let root = env::temp_dir().join(format!("vampire-assets-test-{}", std::process::id()));
+        let (first, second) = (root.join("exe"), root.join("cwd"));
+        fs::create_dir_all(first.join("sounds")).unwrap();
+        fs::create_dir_all(second.join("sounds")).unwrap();
+        fs::write(first.join("sounds/hit.wav"), b"first").unwrap();
+        fs::write(second.join("sounds/hit.wav"), b"second").unwrap();
+        fs::write(second.join("sounds/moan.wav"), b"moan").unwrap();
+
+        let mut assets = AssetManager::new(vec![first.clone(), second.clone()]);
+        assert_eq!(assets.read("sounds/hit.wav", "silence").unwrap(), b"first");
+        assert_eq!(assets.read("sounds/moan.wav", "silence").unwrap(), b"moan");
+        assert!(assets
+            .read("fonts/default.ttf", "the default font")
+            .is_none());
+
+        assets.mark_invalid("sounds/moan.wav", "not a WAV file");
+        let problems: Vec<_> = assets.problems().map(|report| report.describe()).collect();
+        assert_eq!(
+            problems,
+            vec![
+                "sounds/moan.wav: not a WAV file, using silence",
+                "fonts/default.ttf: missing, using the default font"
+            ]
+        );
+        assert!(assets.reports()[0].is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
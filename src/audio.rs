@@ -0,0 +1,478 @@
+//! Audio Module
+//!
+//! Sound effects and ambient soundscapes. Systems never play sounds
+//! directly: GameState collects `SoundCue`s during the frame and the
+//! `AudioSystem` plays them afterwards, fading positional cues with distance
+//! from the camera. Sounds are read from `assets/sounds` and any that are
+//! missing stay silent. Playback needs the `audio` cargo feature, which
+//! turns on macroquad's audio backend; without it macroquad's audio calls
+//! are silent no-ops.
+//!
+//! The ambience follows where the player is: wind over open ground, dripping
+//! near caves and underpasses, crows around ruins and buildings, with the
+//! night bed and distant howls layered on after dark. Loops crossfade over a
+//! couple of seconds as the player moves between them.
+
+use crate::assets::AssetManager;
+use crate::components::{EntityFinder, GameEntity, Position, ShelterType};
+use macroquad::audio::{
+    load_sound_from_bytes, play_sound, set_sound_volume, stop_sound, PlaySoundParams, Sound,
+};
+use std::collections::HashMap;
+
+/// Distance from the camera beyond which positional sounds are inaudible
+pub const HEARING_RANGE: f32 = 700.0;
+
+/// Seconds an ambient loop takes to fade fully in or out
+pub const CROSSFADE_SECONDS: f32 = 2.0;
+
+/// Distance within which a shelter sets the soundscape around it
+pub const REGION_RADIUS: f32 = 300.0;
+
+/// Every sound the game can play
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    FeedingSquelch,
+    AttackHit,
+    SunlightSizzle,
+    AmbientNight,
+    DayTransition,
+    WindPlains,
+    CaveDrips,
+    RuinsCrows,
+    NightHowls,
+}
+
+impl SoundEffect {
+    pub const ALL: [SoundEffect; 9] = [
+        SoundEffect::FeedingSquelch,
+        SoundEffect::AttackHit,
+        SoundEffect::SunlightSizzle,
+        SoundEffect::AmbientNight,
+        SoundEffect::DayTransition,
+        SoundEffect::WindPlains,
+        SoundEffect::CaveDrips,
+        SoundEffect::RuinsCrows,
+        SoundEffect::NightHowls,
+    ];
+
+    /// Loops making up the soundscapes
+    pub const AMBIENT: [SoundEffect; 5] = [
+        SoundEffect::AmbientNight,
+        SoundEffect::WindPlains,
+        SoundEffect::CaveDrips,
+        SoundEffect::RuinsCrows,
+        SoundEffect::NightHowls,
+    ];
+
+    /// WAV file of the sound inside the assets folder
+    fn file_name(&self) -> &'static str {
+        match self {
+            SoundEffect::FeedingSquelch => "sounds/feeding_squelch.wav",
+            SoundEffect::AttackHit => "sounds/attack_hit.wav",
+            SoundEffect::SunlightSizzle => "sounds/sunlight_sizzle.wav",
+            SoundEffect::AmbientNight => "sounds/ambient_night.wav",
+            SoundEffect::DayTransition => "sounds/day_transition.wav",
+            SoundEffect::WindPlains => "sounds/wind_plains.wav",
+            SoundEffect::CaveDrips => "sounds/cave_drips.wav",
+            SoundEffect::RuinsCrows => "sounds/ruins_crows.wav",
+            SoundEffect::NightHowls => "sounds/night_howls.wav",
+        }
+    }
+
+    /// Music and ambience use the music volume; everything else is an effect
+    pub fn is_music(&self) -> bool {
+        Self::AMBIENT.contains(self)
+    }
+
+    /// Shortest gap between two plays, so per-frame triggers don't stack up
+    fn min_interval(&self) -> f64 {
+        match self {
+            SoundEffect::SunlightSizzle => 0.8,
+            SoundEffect::AttackHit => 0.05,
+            _ => 0.0,
+        }
+    }
+}
+
+/// A request to play a sound, optionally at a world position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoundCue {
+    pub effect: SoundEffect,
+    pub position: Option<Position>,
+}
+
+impl SoundCue {
+    /// A sound heard from a place in the world
+    pub fn at(effect: SoundEffect, position: Position) -> Self {
+        Self {
+            effect,
+            position: Some(position),
+        }
+    }
+
+    /// A sound heard at full volume wherever the camera is
+    pub fn global(effect: SoundEffect) -> Self {
+        Self {
+            effect,
+            position: None,
+        }
+    }
+}
+
+/// Kinds of place that sound different
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbientRegion {
+    Plains,
+    Cave,
+    Ruins,
+}
+
+impl AmbientRegion {
+    /// How the surroundings of a shelter sound
+    pub fn of_shelter(shelter_type: &ShelterType) -> Self {
+        match shelter_type {
+            ShelterType::Cave
+            | ShelterType::Underground
+            | ShelterType::BridgeUnderpass
+            | ShelterType::Lair => AmbientRegion::Cave,
+            ShelterType::Ruins | ShelterType::Building | ShelterType::Shed => AmbientRegion::Ruins,
+            ShelterType::TreeCover | ShelterType::Tent => AmbientRegion::Plains,
+        }
+    }
+
+    /// The region the player is in: the shelter they are inside, else the
+    /// nearest shelter within `REGION_RADIUS`, else open plains
+    pub fn around(entities: &[GameEntity], player_id: u32) -> Self {
+        let Some(player) = EntityFinder::by_id(entities, player_id) else {
+            return AmbientRegion::Plains;
+        };
+        let inside = player
+            .shelter_occupancy
+            .as_ref()
+            .and_then(|occupancy| occupancy.shelter_id)
+            .and_then(|id| EntityFinder::by_id(entities, id));
+        let nearest = || {
+            entities
+                .iter()
+                .filter(|entity| entity.shelter.is_some())
+                .map(|entity| (entity, entity.position.distance_to(&player.position)))
+                .filter(|(_, distance)| *distance <= REGION_RADIUS)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(entity, _)| entity)
+        };
+        inside
+            .or_else(nearest)
+            .and_then(|entity| entity.shelter.as_ref())
+            .map_or(AmbientRegion::Plains, |shelter| {
+                Self::of_shelter(&shelter.shelter_type)
+            })
+    }
+
+    /// Ambient loops to play here and how loud each is relative to the
+    /// others. Walls muffle the night.
+    pub fn layers(&self, is_night: bool) -> Vec<(SoundEffect, f32)> {
+        let mut layers = vec![match self {
+            AmbientRegion::Plains => (SoundEffect::WindPlains, 0.8),
+            AmbientRegion::Cave => (SoundEffect::CaveDrips, 1.0),
+            AmbientRegion::Ruins => (SoundEffect::RuinsCrows, 0.9),
+        }];
+        if is_night {
+            let night = if *self == AmbientRegion::Cave {
+                0.3
+            } else {
+                1.0
+            };
+            layers.push((SoundEffect::AmbientNight, night));
+            layers.push((SoundEffect::NightHowls, 0.6 * night));
+        }
+        layers
+    }
+}
+
+/// Current level of each ambient loop, eased toward the levels asked for so
+/// moving between regions crossfades instead of cutting
+#[derive(Debug, Clone, Default)]
+pub struct AmbientMixer {
+    gains: HashMap<SoundEffect, f32>,
+}
+
+impl AmbientMixer {
+    /// Move every loop toward its target level; loops not listed fade out
+    pub fn update(&mut self, targets: &[(SoundEffect, f32)], delta_time: f32) {
+        let step = delta_time / CROSSFADE_SECONDS;
+        for effect in SoundEffect::AMBIENT {
+            let target = targets
+                .iter()
+                .find(|(layer, _)| *layer == effect)
+                .map_or(0.0, |(_, gain)| *gain);
+            let gain = self.gains.entry(effect).or_insert(0.0);
+            *gain = if *gain < target {
+                (*gain + step).min(target)
+            } else {
+                (*gain - step).max(target)
+            };
+        }
+    }
+
+    pub fn gain(&self, effect: SoundEffect) -> f32 {
+        self.gains.get(&effect).copied().unwrap_or(0.0)
+    }
+}
+
+/// Player-adjustable volume levels, each 0.0 to 1.0
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            music: 0.6,
+            effects: 0.8,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Volume a sound plays at before distance falloff
+    pub fn volume_for(&self, effect: SoundEffect) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        let channel = if effect.is_music() {
+            self.music
+        } else {
+            self.effects
+        };
+        self.master * channel
+    }
+
+    /// Raise or lower the master volume
+    pub fn adjust_master(&mut self, delta: f32) {
+        self.master = (self.master + delta).clamp(0.0, 1.0);
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+    }
+}
+
+/// Volume falloff for a sound `distance` away from the listener
+pub fn attenuation(distance: f32) -> f32 {
+    let falloff = (1.0 - distance / HEARING_RANGE).clamp(0.0, 1.0);
+    falloff * falloff
+}
+
+/// Final volume for a cue heard from `listener`
+pub fn cue_volume(cue: &SoundCue, listener: Position, settings: &AudioSettings) -> f32 {
+    let falloff = cue
+        .position
+        .map_or(1.0, |position| attenuation(position.distance_to(&listener)));
+    settings.volume_for(cue.effect) * falloff
+}
+
+/// Owns the loaded sounds and plays cues and ambience
+pub struct AudioSystem {
+    sounds: HashMap<SoundEffect, Sound>,
+    last_played: HashMap<SoundEffect, f64>,
+    mixer: AmbientMixer,
+    /// Ambient loops playing now, with the volume last set on each
+    looping: HashMap<SoundEffect, f32>,
+}
+
+impl AudioSystem {
+    /// Load every sound from the assets folder. Sounds that are missing or
+    /// fail to decode are left silent and noted in the asset report.
+    pub async fn load(assets: &mut AssetManager) -> Self {
+        let mut sounds = HashMap::new();
+        for effect in SoundEffect::ALL {
+            let Some(bytes) = assets.read(effect.file_name(), "silence") else {
+                continue;
+            };
+            match load_sound_from_bytes(&bytes).await {
+                Ok(sound) => {
+                    sounds.insert(effect, sound);
+                }
+                Err(error) => assets.mark_invalid(effect.file_name(), error),
+            }
+        }
+
+        Self {
+            sounds,
+            last_played: HashMap::new(),
+            mixer: AmbientMixer::default(),
+            looping: HashMap::new(),
+        }
+    }
+
+    /// Play this frame's cues as heard from the camera position
+    pub fn play_cues(
+        &mut self,
+        cues: impl IntoIterator<Item = SoundCue>,
+        listener: Position,
+        settings: &AudioSettings,
+        now: f64,
+    ) {
+        for cue in cues {
+            let volume = cue_volume(&cue, listener, settings);
+            if volume <= 0.01 {
+                continue;
+            }
+            let Some(sound) = self.sounds.get(&cue.effect) else {
+                continue;
+            };
+            if self
+                .last_played
+                .get(&cue.effect)
+                .is_some_and(|last| now - last < cue.effect.min_interval())
+            {
+                continue;
+            }
+
+            play_sound(
+                sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume,
+                },
+            );
+            self.last_played.insert(cue.effect, now);
+        }
+    }
+
+    /// Crossfade the ambient loops toward the soundscape of the player's
+    /// region, starting loops as they fade in and stopping them once silent
+    pub fn update_ambient(
+        &mut self,
+        region: AmbientRegion,
+        is_night: bool,
+        settings: &AudioSettings,
+        delta_time: f32,
+    ) {
+        self.mixer.update(&region.layers(is_night), delta_time);
+        for effect in SoundEffect::AMBIENT {
+            let Some(sound) = self.sounds.get(&effect) else {
+                continue;
+            };
+            let volume = self.mixer.gain(effect) * settings.volume_for(effect);
+            match self.looping.get(&effect).copied() {
+                None if volume > 0.001 => {
+                    play_sound(
+                        sound,
+                        PlaySoundParams {
+                            looped: true,
+                            volume,
+                        },
+                    );
+                    self.looping.insert(effect, volume);
+                }
+                Some(_) if volume <= 0.001 => {
+                    stop_sound(sound);
+                    self.looping.remove(&effect);
+                }
+                Some(current) if (current - volume).abs() > 0.005 => {
+                    set_sound_volume(sound, volume);
+                    self.looping.insert(effect, volume);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positional_volume_falls_off() {
+        let settings = AudioSettings::default();
+        let listener = Position::new(0.0, 0.0);
+        let near = SoundCue::at(SoundEffect::AttackHit, Position::new(50.0, 0.0));
+        let far = SoundCue::at(SoundEffect::AttackHit, Position::new(500.0, 0.0));
+        let outside = SoundCue::at(SoundEffect::AttackHit, Position::new(HEARING_RANGE, 0.0));
+
+        assert!(cue_volume(&near, listener, &settings) > cue_volume(&far, listener, &settings));
+        assert_eq!(cue_volume(&outside, listener, &settings), 0.0);
+        assert_eq!(
+            cue_volume(
+                &SoundCue::global(SoundEffect::DayTransition),
+                listener,
+                &settings
+            ),
+            settings.master * settings.effects
+        );
+    }
+
+    #[test]
+    fn test_volume_settings() {
+        let mut settings = AudioSettings::default();
+        settings.adjust_master(0.5);
+        assert_eq!(settings.master, 1.0);
+        assert_eq!(
+            settings.volume_for(SoundEffect::AmbientNight),
+            settings.music
+        );
+
+        settings.toggle_mute();
+        assert_eq!(settings.volume_for(SoundEffect::FeedingSquelch), 0.0);
+    }
+
+    #[test]
+    fn test_every_sound_ships_as_wav() {
+        let mut assets = AssetManager::new(vec![
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+        ]);
+        for effect in SoundEffect::ALL {
+            let bytes = assets.read(effect.file_name(), "silence").unwrap();
+            assert_eq!(&bytes[..4], b"RIFF");
+        }
+    }
+
+    #[test]
+    fn test_soundscape_follows_region_and_crossfades() {
+        use crate::components::EntityAllocator;
+        use crate::systems::WorldSystem;
+
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let player_at = entities[0].position;
+        assert_eq!(
+            AmbientRegion::around(&entities, player_id),
+            AmbientRegion::Plains
+        );
+        let mut cave = entities[0].clone();
+        cave.id = 99;
+        cave.shelter = Some(crate::components::Shelter::new(ShelterType::Cave));
+        cave.position = Position::new(player_at.x + REGION_RADIUS - 10.0, player_at.y);
+        entities.push(cave);
+        assert_eq!(
+            AmbientRegion::around(&entities, player_id),
+            AmbientRegion::Cave
+        );
+
+        let mut mixer = AmbientMixer::default();
+        mixer.update(&AmbientRegion::Plains.layers(false), CROSSFADE_SECONDS);
+        assert_eq!(mixer.gain(SoundEffect::WindPlains), 0.8);
+
+        // Halfway through the fade the wind is going and the drips coming
+        let cave_night = AmbientRegion::Cave.layers(true);
+        mixer.update(&cave_night, CROSSFADE_SECONDS / 2.0);
+        assert!((mixer.gain(SoundEffect::WindPlains) - 0.3).abs() < 0.001);
+        assert_eq!(mixer.gain(SoundEffect::CaveDrips), 0.5);
+        assert_eq!(mixer.gain(SoundEffect::AmbientNight), 0.3);
+
+        mixer.update(&cave_night, CROSSFADE_SECONDS);
+        assert_eq!(mixer.gain(SoundEffect::WindPlains), 0.0);
+        assert_eq!(mixer.gain(SoundEffect::CaveDrips), 1.0);
+        assert!((mixer.gain(SoundEffect::NightHowls) - 0.18).abs() < 0.001);
+    }
+}
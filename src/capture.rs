@@ -0,0 +1,450 @@
+//! Capture Module
+//!
+//! Screenshots and short clips for sharing. A screenshot is written to the
+//! screenshots directory as a PNG named after the moment it was taken. The
+//! optional clip recorder is handed a scaled-down copy of the screen by the
+//! renderer ten times a second and keeps the last few seconds, which can be
+//! written out as a looping animated GIF on demand. The GIF encoder is a
+//! small one of our own: frames are mapped onto a fixed 256 colour palette
+//! and LZW-compressed.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Where screenshots and clips are written
+pub const SCREENSHOT_DIR: &str = "screenshots";
+
+/// Seconds of play a clip holds
+const CLIP_SECONDS: f32 = 5.0;
+/// Frames recorded per second of play
+const CLIP_FPS: f32 = 10.0;
+/// Clip frames are scaled down to at most this wide
+pub const CLIP_MAX_WIDTH: usize = 320;
+
+/// Levels per channel in the colour cube part of the palette
+const CUBE_LEVELS: usize = 6;
+/// Shades of grey after the colour cube, filling the palette to 256
+const GREY_LEVELS: usize = 256 - CUBE_LEVELS * CUBE_LEVELS * CUBE_LEVELS;
+/// Channels closer together than this are drawn from the grey ramp
+const GREY_TOLERANCE: u8 = 12;
+
+/// Widest LZW code a GIF may use
+const MAX_CODE_BITS: u32 = 12;
+const MAX_CODES: u16 = 1 << MAX_CODE_BITS;
+/// Palette indices are 8 bits wide
+const MIN_CODE_SIZE: u32 = 8;
+const CLEAR_CODE: u16 = 1 << MIN_CODE_SIZE;
+const END_CODE: u16 = CLEAR_CODE + 1;
+
+/// Errors that can occur while saving a capture
+#[derive(Debug, Error)]
+pub enum CaptureError {
+    #[error("could not write capture: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not encode screenshot: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("no clip has been recorded yet")]
+    EmptyClip,
+}
+
+/// One scaled-down frame of a clip, as palette indices
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClipFrame {
+    pub width: u16,
+    pub height: u16,
+    pub pixels: Vec<u8>,
+}
+
+impl ClipFrame {
+    /// Scale down RGBA screen pixels, stored bottom row first as they are
+    /// read back from the screen, and map them onto the palette
+    pub fn from_screen(width: usize, height: usize, rgba: &[u8], max_width: usize) -> Self {
+        let step = width.div_ceil(max_width.max(1)).max(1);
+        let (out_width, out_height) = (width / step, height / step);
+        let mut pixels = Vec::with_capacity(out_width * out_height);
+        for row in 0..out_height {
+            let source_row = height - 1 - row * step;
+            for column in 0..out_width {
+                let offset = (source_row * width + column * step) * 4;
+                pixels.push(palette_index(
+                    rgba[offset],
+                    rgba[offset + 1],
+                    rgba[offset + 2],
+                ));
+            }
+        }
+        Self {
+            width: out_width as u16,
+            height: out_height as u16,
+            pixels,
+        }
+    }
+}
+
+/// Rolling recorder keeping the last few seconds of frames
+#[derive(Debug, Clone, Default)]
+pub struct ClipRecorder {
+    pub enabled: bool,
+    frames: VecDeque<ClipFrame>,
+    /// Seconds since the last frame was recorded
+    since_frame: f32,
+}
+
+impl ClipRecorder {
+    /// Most frames kept at once
+    pub const MAX_FRAMES: usize = (CLIP_SECONDS * CLIP_FPS) as usize;
+
+    /// Switch recording on or off; switching off throws the clip away
+    pub fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        if !self.enabled {
+            self.frames.clear();
+        }
+        self.since_frame = 0.0;
+        self.enabled
+    }
+
+    /// Whether a frame should be recorded this frame
+    pub fn is_due(&mut self, delta_time: f32) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        self.since_frame += delta_time;
+        if self.since_frame < 1.0 / CLIP_FPS {
+            return false;
+        }
+        self.since_frame = 0.0;
+        true
+    }
+
+    /// Keep a frame, dropping the oldest once the clip is full
+    pub fn push(&mut self, frame: ClipFrame) {
+        // A resized window starts the clip over
+        if self
+            .frames
+            .back()
+            .is_some_and(|last| (last.width, last.height) != (frame.width, frame.height))
+        {
+            self.frames.clear();
+        }
+        self.frames.push_back(frame);
+        while self.frames.len() > Self::MAX_FRAMES {
+            self.frames.pop_front();
+        }
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The recorded frames as a looping animated GIF
+    pub fn encode_gif(&self) -> Result<Vec<u8>, CaptureError> {
+        let first = self.frames.front().ok_or(CaptureError::EmptyClip)?;
+        let delay = (100.0 / CLIP_FPS) as u16;
+
+        let mut gif = Vec::new();
+        gif.extend_from_slice(b"GIF89a");
+        gif.extend_from_slice(&first.width.to_le_bytes());
+        gif.extend_from_slice(&first.height.to_le_bytes());
+        // Global colour table of 256 entries at 8 bits per channel
+        gif.extend_from_slice(&[0xF7, 0, 0]);
+        gif.extend_from_slice(&palette());
+        // Loop forever
+        gif.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        gif.extend_from_slice(b"NETSCAPE2.0");
+        gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        for frame in &self.frames {
+            gif.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+            gif.extend_from_slice(&delay.to_le_bytes());
+            gif.extend_from_slice(&[0x00, 0x00]);
+
+            gif.extend_from_slice(&[0x2C, 0, 0, 0, 0]);
+            gif.extend_from_slice(&frame.width.to_le_bytes());
+            gif.extend_from_slice(&frame.height.to_le_bytes());
+            gif.push(0x00);
+
+            gif.push(MIN_CODE_SIZE as u8);
+            for block in lzw_compress(&frame.pixels).chunks(255) {
+                gif.push(block.len() as u8);
+                gif.extend_from_slice(block);
+            }
+            gif.push(0x00);
+        }
+        gif.push(0x3B);
+        Ok(gif)
+    }
+}
+
+/// Screenshots and the clip recorder, writing into one directory
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub recorder: ClipRecorder,
+    dir: PathBuf,
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self::new(SCREENSHOT_DIR)
+    }
+}
+
+impl Capture {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            recorder: ClipRecorder::default(),
+            dir: dir.into(),
+        }
+    }
+
+    /// Write RGBA screen pixels, stored bottom row first, as a PNG
+    pub fn save_screenshot(
+        &self,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+    ) -> Result<PathBuf, CaptureError> {
+        let row = width as usize * 4;
+        let flipped: Vec<u8> = rgba.chunks_exact(row).rev().flatten().copied().collect();
+        let path = self.timestamped_path("screenshot", "png")?;
+        image::save_buffer(&path, &flipped, width, height, image::ColorType::Rgba8)?;
+        Ok(path)
+    }
+
+    /// Write the recorded clip as an animated GIF
+    pub fn save_clip(&self) -> Result<PathBuf, CaptureError> {
+        let gif = self.recorder.encode_gif()?;
+        let path = self.timestamped_path("clip", "gif")?;
+        fs::write(&path, gif)?;
+        Ok(path)
+    }
+
+    /// A fresh file name in the capture directory, creating it if needed
+    fn timestamped_path(&self, prefix: &str, extension: &str) -> Result<PathBuf, CaptureError> {
+        fs::create_dir_all(&self.dir)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Ok(Self::unused_path(
+            &self.dir,
+            &format!("{}-{}-{:03}", prefix, now.as_secs(), now.subsec_millis()),
+            extension,
+        ))
+    }
+
+    /// `dir/stem.extension`, numbered if that file already exists
+    fn unused_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+        let mut path = dir.join(format!("{}.{}", stem, extension));
+        let mut copy = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.{}", stem, copy, extension));
+            copy += 1;
+        }
+        path
+    }
+}
+
+/// The fixed palette: a 6x6x6 colour cube followed by a grey ramp
+fn palette() -> Vec<u8> {
+    let mut colors = Vec::with_capacity(256 * 3);
+    for r in 0..CUBE_LEVELS {
+        for g in 0..CUBE_LEVELS {
+            for b in 0..CUBE_LEVELS {
+                for level in [r, g, b] {
+                    colors.push((level * 255 / (CUBE_LEVELS - 1)) as u8);
+                }
+            }
+        }
+    }
+    for grey in 0..GREY_LEVELS {
+        let shade = (grey * 255 / (GREY_LEVELS - 1)) as u8;
+        colors.extend_from_slice(&[shade, shade, shade]);
+    }
+    colors
+}
+
+/// Nearest palette entry for a colour
+fn palette_index(r: u8, g: u8, b: u8) -> u8 {
+    let (low, high) = (r.min(g).min(b), r.max(g).max(b));
+    if high - low <= GREY_TOLERANCE {
+        let luma = (r as usize + g as usize + b as usize) / 3;
+        let cube = CUBE_LEVELS * CUBE_LEVELS * CUBE_LEVELS;
+        return (cube + (luma * (GREY_LEVELS - 1) + 127) / 255) as u8;
+    }
+    let level = |channel: u8| (channel as usize * (CUBE_LEVELS - 1) + 127) / 255;
+    (level(r) * CUBE_LEVELS * CUBE_LEVELS + level(g) * CUBE_LEVELS + level(b)) as u8
+}
+
+/// Packs variable-width codes into bytes, least significant bit first
+struct CodeWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+    code_size: u32,
+}
+
+impl CodeWriter {
+    fn write(&mut self, code: u16) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += self.code_size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// GIF flavoured LZW: codes start one bit wider than the palette indices,
+/// grow as the table fills, and the table is cleared once it is full
+fn lzw_compress(pixels: &[u8]) -> Vec<u8> {
+    let mut writer = CodeWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bits: 0,
+        code_size: MIN_CODE_SIZE + 1,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = END_CODE + 1;
+    writer.write(CLEAR_CODE);
+
+    let Some((&first, rest)) = pixels.split_first() else {
+        writer.write(END_CODE);
+        return writer.finish();
+    };
+    let mut prefix = u16::from(first);
+    for &pixel in rest {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix);
+        // The decoder widens its codes once the table outgrows them
+        if next_code >= 1 << writer.code_size && writer.code_size < MAX_CODE_BITS {
+            writer.code_size += 1;
+        }
+        if next_code < MAX_CODES {
+            table.insert((prefix, pixel), next_code);
+            next_code += 1;
+        } else {
+            writer.write(CLEAR_CODE);
+            table.clear();
+            next_code = END_CODE + 1;
+            writer.code_size = MIN_CODE_SIZE + 1;
+        }
+        prefix = u16::from(pixel);
+    }
+    writer.write(prefix);
+    if next_code >= 1 << writer.code_size && writer.code_size < MAX_CODE_BITS {
+        writer.code_size += 1;
+    }
+    writer.write(END_CODE);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plain GIF LZW decoder to check the encoder against
+    fn lzw_decompress(data: &[u8]) -> Vec<u8> {
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..=END_CODE)
+                .map(|code| vec![code.min(255) as u8])
+                .collect();
+        };
+        reset(&mut table);
+        let (mut code_size, mut bit, mut previous) = (MIN_CODE_SIZE + 1, 0usize, None::<Vec<u8>>);
+        let mut output = Vec::new();
+        loop {
+            let mut code = 0usize;
+            for index in 0..code_size as usize {
+                let at = bit + index;
+                code |= ((data[at / 8] >> (at % 8)) as usize & 1) << index;
+            }
+            bit += code_size as usize;
+            if code == CLEAR_CODE as usize {
+                reset(&mut table);
+                code_size = MIN_CODE_SIZE + 1;
+                previous = None;
+                continue;
+            }
+            if code == END_CODE as usize {
+                return output;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = previous.clone();
+                    entry.push(previous[0]);
+                    entry
+                }
+                (None, None) => panic!("code {} before any output", code),
+            };
+            output.extend_from_slice(&entry);
+            if let Some(mut previous) = previous {
+                if table.len() < MAX_CODES as usize {
+                    previous.push(entry[0]);
+                    table.push(previous);
+                }
+            }
+            if table.len() == 1 << code_size && code_size < MAX_CODE_BITS {
+                code_size += 1;
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_clip_keeps_last_seconds_and_encodes_gif() {
+        // Enough varied pixels to fill the code table and clear it
+        let mut seed = 7u32;
+        let pixels: Vec<u8> = (0..20_000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                ((seed >> 16) % 40) as u8
+            })
+            .collect();
+        assert_eq!(lzw_decompress(&lzw_compress(&pixels)), pixels);
+        assert_eq!(lzw_decompress(&lzw_compress(&[])), Vec::<u8>::new());
+
+        // Screen pixels come bottom row first and are scaled down
+        let (width, height) = (640, 4);
+        let mut rgba = vec![0u8; width * height * 4];
+        rgba[(height - 1) * width * 4] = 255;
+        let frame = ClipFrame::from_screen(width, height, &rgba, CLIP_MAX_WIDTH);
+        assert_eq!((frame.width, frame.height), (320, 2));
+        assert_eq!(frame.pixels[0], palette_index(255, 0, 0));
+        assert_eq!(frame.pixels[1], palette_index(0, 0, 0));
+
+        let mut recorder = ClipRecorder::default();
+        assert!(matches!(
+            recorder.encode_gif(),
+            Err(CaptureError::EmptyClip)
+        ));
+        assert!(!recorder.is_due(1.0));
+        recorder.toggle();
+        assert!(!recorder.is_due(0.05));
+        assert!(recorder.is_due(0.05));
+        for _ in 0..ClipRecorder::MAX_FRAMES + 5 {
+            recorder.push(frame.clone());
+        }
+        assert_eq!(recorder.frame_count(), ClipRecorder::MAX_FRAMES);
+
+        let gif = recorder.encode_gif().unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(&gif[6..10], &[64, 1, 2, 0]);
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+}
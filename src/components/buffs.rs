@@ -0,0 +1,209 @@
+//! Blood quality and buff components
+//!
+//! Not all blood is equal. Animals give thin blood, infected a little
+//! better, clan vampires rich blood and their leaders the finest of all.
+//! Richer blood yields more and leaves the drinker stronger or faster for a
+//! while; those effects are tracked as timed buffs.
+
+use super::game_data::EntityType;
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How rich a creature's blood is, from poorest to finest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BloodQuality {
+    Animal,
+    Infected,
+    Clan,
+    Leader,
+}
+
+impl BloodQuality {
+    /// The quality of a creature's blood, if it has any worth drinking
+    pub fn of(entity_type: &EntityType) -> Option<Self> {
+        match entity_type {
+            EntityType::Animal => Some(BloodQuality::Animal),
+            EntityType::HostileInfected => Some(BloodQuality::Infected),
+            EntityType::ClanMember(_) => Some(BloodQuality::Clan),
+            EntityType::ClanLeader(_) => Some(BloodQuality::Leader),
+            EntityType::Player | EntityType::Shelter | EntityType::Pickup => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BloodQuality::Animal => "Animal",
+            BloodQuality::Infected => "Infected",
+            BloodQuality::Clan => "Clan",
+            BloodQuality::Leader => "Leader's",
+        }
+    }
+
+    /// Share of the prey's remaining health drunk as blood
+    pub fn yield_factor(&self) -> f32 {
+        match self {
+            BloodQuality::Animal => 0.6,
+            BloodQuality::Infected => 0.7,
+            BloodQuality::Clan => 0.8,
+            BloodQuality::Leader => 1.0,
+        }
+    }
+
+    /// Buffs granted by drinking this blood fresh
+    pub fn buffs(&self) -> Vec<Buff> {
+        match self {
+            BloodQuality::Animal => Vec::new(),
+            BloodQuality::Infected => vec![Buff::new(BuffKind::Speed, 1.1, 20.0)],
+            BloodQuality::Clan => vec![Buff::new(BuffKind::Strength, 1.25, 30.0)],
+            BloodQuality::Leader => vec![
+                Buff::new(BuffKind::Strength, 1.5, 45.0),
+                Buff::new(BuffKind::Speed, 1.25, 45.0),
+            ],
+        }
+    }
+}
+
+/// What a buff improves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuffKind {
+    Strength,
+    Speed,
+}
+
+impl BuffKind {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BuffKind::Strength => "Strength",
+            BuffKind::Speed => "Speed",
+        }
+    }
+
+    /// Letter shown on the HUD icon
+    pub fn icon(&self) -> &'static str {
+        match self {
+            BuffKind::Strength => "S",
+            BuffKind::Speed => "Q",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            BuffKind::Strength => Color::new(0.8, 0.15, 0.15, 1.0),
+            BuffKind::Speed => Color::new(0.2, 0.6, 0.9, 1.0),
+        }
+    }
+}
+
+/// A timed boost
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Buff {
+    pub kind: BuffKind,
+    /// Scale applied to the boosted stat
+    pub multiplier: f32,
+    /// Seconds left
+    pub remaining: f32,
+    /// Seconds it lasted when granted
+    pub duration: f32,
+}
+
+impl Buff {
+    pub fn new(kind: BuffKind, multiplier: f32, duration: f32) -> Self {
+        Self {
+            kind,
+            multiplier,
+            remaining: duration,
+            duration,
+        }
+    }
+
+    /// Time left as a fraction of the full duration
+    pub fn fraction_left(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.remaining / self.duration).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Buff component: the boosts an entity currently enjoys, at most one of
+/// each kind
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Buffs {
+    pub active: Vec<Buff>,
+}
+
+impl Buffs {
+    /// Grant a buff. One of the same kind is replaced when the new one is at
+    /// least as strong, and otherwise just topped up in time.
+    pub fn apply(&mut self, buff: Buff) {
+        match self
+            .active
+            .iter_mut()
+            .find(|active| active.kind == buff.kind)
+        {
+            Some(active) if buff.multiplier >= active.multiplier => *active = buff,
+            Some(active) => active.remaining = active.remaining.max(buff.remaining),
+            None => self.active.push(buff),
+        }
+    }
+
+    /// Scale the buffs of `kind` apply; 1.0 when there are none
+    pub fn multiplier(&self, kind: BuffKind) -> f32 {
+        self.active
+            .iter()
+            .filter(|buff| buff.kind == kind)
+            .map(|buff| buff.multiplier)
+            .product()
+    }
+
+    /// Count buffs down, dropping those that run out
+    pub fn tick(&mut self, delta_time: f32) {
+        for buff in &mut self.active {
+            buff.remaining -= delta_time;
+        }
+        self.active.retain(|buff| buff.remaining > 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_richer_blood_yields_more_and_grants_stronger_buffs() {
+        let qualities: Vec<BloodQuality> = [
+            EntityType::Animal,
+            EntityType::HostileInfected,
+            EntityType::ClanMember("Night-Bloods".to_string()),
+            EntityType::ClanLeader("Night-Bloods".to_string()),
+        ]
+        .iter()
+        .filter_map(BloodQuality::of)
+        .collect();
+        assert!(qualities
+            .windows(2)
+            .all(|pair| pair[0] < pair[1] && pair[0].yield_factor() < pair[1].yield_factor()));
+        assert!(BloodQuality::of(&EntityType::Player).is_none());
+        assert!(BloodQuality::Animal.buffs().is_empty());
+
+        let mut buffs = Buffs::default();
+        for buff in BloodQuality::Leader.buffs() {
+            buffs.apply(buff);
+        }
+        assert_eq!(buffs.multiplier(BuffKind::Strength), 1.5);
+        assert_eq!(buffs.multiplier(BuffKind::Speed), 1.25);
+
+        // Weaker blood only tops up the time left on a stronger buff
+        buffs.tick(40.0);
+        for buff in BloodQuality::Infected.buffs() {
+            buffs.apply(buff);
+        }
+        assert_eq!(buffs.multiplier(BuffKind::Speed), 1.25);
+        buffs.tick(10.0);
+        assert_eq!(buffs.multiplier(BuffKind::Strength), 1.0);
+        assert_eq!(buffs.multiplier(BuffKind::Speed), 1.25);
+        buffs.tick(10.0);
+        assert!(buffs.active.is_empty());
+    }
+}
@@ -11,6 +11,12 @@ pub struct CombatStats {
     pub defense: f32,
     pub last_attack_time: f32,
     pub attack_cooldown: f32,
+    pub crit_chance: f32,
+    pub crit_multiplier: f32,
+    pub dodge_chance: f32,
+    /// Resistance to being staggered, from 0.0 (none) to 1.0 (immovable)
+    #[serde(default)]
+    pub poise: f32,
 }
 
 impl CombatStats {
@@ -18,8 +24,7 @@ impl CombatStats {
         Self {
             attack_power,
             defense,
-            last_attack_time: 0.0,
-            attack_cooldown: 1.0,
+            ..Self::default()
         }
     }
 
@@ -35,6 +40,10 @@ impl Default for CombatStats {
             defense: 5.0,
             last_attack_time: 0.0,
             attack_cooldown: 1.0,
+            crit_chance: 0.1,
+            crit_multiplier: 1.5,
+            dodge_chance: 0.05,
+            poise: 0.0,
         }
     }
 }
@@ -45,7 +54,15 @@ pub enum AIState {
     Idle,
     Hostile,
     Fleeing,
+    /// Ambling and grazing around a home range (see `Wander`)
+    Wander,
     Dead,
+    /// Part of the player's retinue, carrying out an order
+    Follower(FollowerOrder),
+    /// Reeling from a heavy hit, unable to move or attack
+    Staggered(Stagger),
+    /// Marching with an allied clan's assault squad
+    Squad,
 }
 
 impl Default for AIState {
@@ -54,6 +71,144 @@ impl Default for AIState {
     }
 }
 
+/// A stagger or knockdown in progress, and what the entity was doing before
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stagger {
+    /// Seconds until the entity recovers
+    pub remaining: f32,
+    pub duration: f32,
+    /// Knocked off their feet rather than just reeling
+    pub knocked_down: bool,
+    /// State to return to once recovered
+    pub resume: Box<AIState>,
+}
+
+impl Stagger {
+    pub fn new(duration: f32, knocked_down: bool, resume: AIState) -> Self {
+        Self {
+            remaining: duration,
+            duration,
+            knocked_down,
+            resume: Box::new(resume),
+        }
+    }
+
+    /// Fraction of the stagger still to go, from 1.0 down to 0.0
+    pub fn fraction_left(&self) -> f32 {
+        (self.remaining / self.duration).clamp(0.0, 1.0)
+    }
+}
+
+/// A missile in flight, such as a blood lance
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projectile {
+    /// Who threw it; it never hits its owner
+    pub owner_id: u32,
+    pub position: super::entities::Position,
+    /// Unit direction of travel
+    pub direction: (f32, f32),
+    /// World units per second
+    pub speed: f32,
+    pub damage: f32,
+    /// Distance left before it falls apart
+    pub range_left: f32,
+}
+
+/// Orders the player can give to followers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FollowerOrder {
+    /// Stay close to the player
+    Follow,
+    /// Stand guard at a position
+    Hold(super::entities::Position),
+    /// Attack the given entity until it falls
+    Attack(u32),
+}
+
+impl FollowerOrder {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FollowerOrder::Follow => "Following",
+            FollowerOrder::Hold(_) => "Holding",
+            FollowerOrder::Attack(_) => "Attacking",
+        }
+    }
+}
+
+/// Where a wandering creature roams and what it is doing there
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wander {
+    /// Center of the home range, usually where the creature spawned
+    pub home: super::entities::Position,
+    pub range: f32,
+    /// Spot the creature is ambling towards
+    pub target: Option<super::entities::Position>,
+    /// Seconds left grazing before moving on
+    pub graze_remaining: f32,
+    /// Gone to ground in its den, out of sight until it comes out again
+    #[serde(default)]
+    pub hiding: bool,
+}
+
+impl Wander {
+    pub fn new(home: super::entities::Position, range: f32) -> Self {
+        Self {
+            home,
+            range,
+            target: None,
+            graze_remaining: 0.0,
+            hiding: false,
+        }
+    }
+
+    pub fn is_grazing(&self) -> bool {
+        self.graze_remaining > 0.0
+    }
+}
+
+/// How hungry a hostile infected is, and the animal it is hunting. Hunger
+/// climbs over time until the infected goes looking for prey.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hunger {
+    /// 0.0 when sated, 1.0 when ravenous
+    pub current: f32,
+    /// Hunger gained per second
+    pub rate: f32,
+    /// Animal being hunted, if any
+    pub prey: Option<u32>,
+}
+
+impl Hunger {
+    /// Hunger at which an infected starts hunting
+    pub const HUNT_THRESHOLD: f32 = 0.5;
+    /// Hunger infected gain per second; about two minutes from sated to
+    /// hunting
+    pub const INFECTED_RATE: f32 = 0.004;
+
+    pub fn new(current: f32, rate: f32) -> Self {
+        Self {
+            current: current.clamp(0.0, 1.0),
+            rate,
+            prey: None,
+        }
+    }
+
+    pub fn is_hungry(&self) -> bool {
+        self.current >= Self::HUNT_THRESHOLD
+    }
+
+    /// Grow hungrier with time
+    pub fn update(&mut self, delta_time: f32) {
+        self.current = (self.current + self.rate * delta_time).min(1.0);
+    }
+
+    /// Eat a kill, sating hunger by the share of a full meal it held
+    pub fn eat(&mut self, meal: f32) {
+        self.current = (self.current - meal).max(0.0);
+        self.prey = None;
+    }
+}
+
 /// AI behavior types for different entity personalities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AIBehavior {
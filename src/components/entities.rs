@@ -6,7 +6,7 @@ use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
 
 /// Position component for entities in 2D space
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -20,6 +20,28 @@ impl Position {
     pub fn distance_to(&self, other: &Position) -> f32 {
         ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
     }
+
+    /// Squared distance, for comparisons that can skip the square root
+    pub fn distance_squared_to(&self, other: &Position) -> f32 {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2)
+    }
+
+    /// Compass direction from this position to another (north is up)
+    pub fn compass_direction_to(&self, other: &Position) -> &'static str {
+        let dx = other.x - self.x;
+        let dy = other.y - self.y;
+        let octant = (dy.atan2(dx) / std::f32::consts::FRAC_PI_4).round() as i32;
+        match octant.rem_euclid(8) {
+            0 => "east",
+            1 => "southeast",
+            2 => "south",
+            3 => "southwest",
+            4 => "west",
+            5 => "northwest",
+            6 => "north",
+            _ => "northeast",
+        }
+    }
 }
 
 /// Velocity component for moving entities
@@ -71,8 +93,49 @@ impl Health {
     }
 }
 
+/// Collision shape, centred on the entity's position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Collider {
+    Circle {
+        radius: f32,
+    },
+    /// Axis-aligned box, used for solid shelter footprints
+    Box {
+        half_width: f32,
+        half_height: f32,
+    },
+}
+
+impl Collider {
+    /// Body of a creature, sized to its sprite; other entity types have none
+    pub fn for_creature(entity_type: &super::game_data::EntityType) -> Option<Self> {
+        use super::game_data::EntityType;
+        let radius = match entity_type {
+            EntityType::Player | EntityType::ClanMember(_) => 10.0,
+            EntityType::ClanLeader(_) => 12.0,
+            EntityType::HostileInfected => 9.0,
+            EntityType::Animal => 7.0,
+            EntityType::Shelter | EntityType::Pickup => return None,
+        };
+        Some(Collider::Circle { radius })
+    }
+
+    /// Solid footprint of a shelter, or `None` for shelters entities walk
+    /// straight into, like tree cover and underpasses
+    pub fn for_shelter(shelter_type: &super::shelter::ShelterType) -> Option<Self> {
+        if !shelter_type.is_solid() {
+            return None;
+        }
+        let (width, height) = shelter_type.visual_size();
+        Some(Collider::Box {
+            half_width: width / 2.0,
+            half_height: height / 2.0,
+        })
+    }
+}
+
 /// Main game entity containing all components
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameEntity {
     pub id: u32,
     pub position: Position,
@@ -85,7 +148,156 @@ pub struct GameEntity {
     pub vampire_abilities: Option<super::vampire::VampireAbilities>,
     pub shelter: Option<super::shelter::Shelter>,
     pub shelter_occupancy: Option<super::shelter::ShelterOccupancy>,
+    #[serde(with = "color_serde")]
     pub color: Color,
+    #[serde(skip)]
+    pub visual_state: VisualState,
+    pub corpse: Option<super::vampire::Corpse>,
+    pub inventory: Option<super::items::Inventory>,
+    pub pickup: Option<super::items::Pickup>,
+    pub schedule: Option<super::schedule::Schedule>,
+    pub wander: Option<super::combat::Wander>,
+    #[serde(default)]
+    pub collider: Option<Collider>,
+    #[serde(default)]
+    pub hunger: Option<super::combat::Hunger>,
+    #[serde(default)]
+    pub buffs: Option<super::buffs::Buffs>,
+    #[serde(default)]
+    pub progression: Option<super::progression::Progression>,
+    /// Which kind of animal it is
+    #[serde(default)]
+    pub species: Option<super::game_data::Species>,
+}
+
+impl GameEntity {
+    /// Gone to ground out of sight, where nothing can see, hunt or feed on it
+    pub fn is_hidden(&self) -> bool {
+        self.wander.as_ref().is_some_and(|wander| wander.hiding)
+    }
+}
+
+/// Serialize macroquad colors as `[r, g, b, a]`
+mod color_serde {
+    use macroquad::prelude::Color;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        [color.r, color.g, color.b, color.a].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let [r, g, b, a] = <[f32; 4]>::deserialize(deserializer)?;
+        Ok(Color::new(r, g, b, a))
+    }
+}
+
+/// Transient visual state used to tint entity sprites
+#[derive(Debug, Clone, Default)]
+pub struct VisualState {
+    /// Seconds left on the red damage flash
+    pub damage_flash: f32,
+    /// Seconds left on the blue charmed shimmer
+    pub charmed: f32,
+    /// Entity is running out of blood and looks pale
+    pub starving: bool,
+    /// Entity belongs to a clan allied with the player
+    pub allied: bool,
+    /// Death animation, started when the entity dies and kept once finished
+    pub death: Option<DeathAnimation>,
+}
+
+impl VisualState {
+    /// Duration of the red flash after taking damage
+    pub const DAMAGE_FLASH_DURATION: f32 = 0.2;
+
+    /// Start the damage flash
+    pub fn flash_damage(&mut self) {
+        self.damage_flash = Self::DAMAGE_FLASH_DURATION;
+    }
+
+    /// Start the charmed shimmer for the given duration
+    pub fn charm(&mut self, duration: f32) {
+        self.charmed = self.charmed.max(duration);
+    }
+
+    /// Count down timed effects
+    pub fn update(&mut self, delta_time: f32) {
+        self.damage_flash = (self.damage_flash - delta_time).max(0.0);
+        self.charmed = (self.charmed - delta_time).max(0.0);
+        if let Some(death) = &mut self.death {
+            death.elapsed = (death.elapsed + delta_time).min(DeathAnimation::DURATION);
+        }
+    }
+
+    /// Check if any tint should be drawn
+    pub fn has_tint(&self) -> bool {
+        self.damage_flash > 0.0 || self.charmed > 0.0 || self.starving || self.allied
+    }
+}
+
+/// How an entity's body goes when it dies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathStyle {
+    /// Infected flesh crumbles to ash
+    Crumble,
+    /// Vampires dissolve into mist and leave no body
+    Mist,
+    /// Animals and people collapse where they stood
+    Collapse,
+}
+
+impl DeathStyle {
+    pub fn for_entity(entity: &GameEntity) -> Self {
+        if entity.vampire_abilities.is_some() {
+            return DeathStyle::Mist;
+        }
+        match entity.entity_type {
+            super::game_data::EntityType::HostileInfected => DeathStyle::Crumble,
+            _ => DeathStyle::Collapse,
+        }
+    }
+
+    /// Whether a corpse is left once the animation ends
+    pub fn leaves_body(&self) -> bool {
+        !matches!(self, DeathStyle::Mist)
+    }
+}
+
+/// Progress of a death animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeathAnimation {
+    pub style: DeathStyle,
+    pub elapsed: f32,
+}
+
+impl DeathAnimation {
+    /// Seconds from the killing blow to the body coming to rest
+    pub const DURATION: f32 = 1.2;
+
+    pub fn new(style: DeathStyle) -> Self {
+        Self {
+            style,
+            elapsed: 0.0,
+        }
+    }
+
+    /// An animation that has already played, for bodies restored from a save
+    pub fn finished(style: DeathStyle) -> Self {
+        Self {
+            style,
+            elapsed: Self::DURATION,
+        }
+    }
+
+    /// Fraction of the animation played (0.0 to 1.0)
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / Self::DURATION).clamp(0.0, 1.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= Self::DURATION
+    }
 }
 
 /// Render component for visual representation
@@ -396,6 +396,17 @@ mod tests {
                 shelter: None,
                 shelter_occupancy: None,
                 color: WHITE,
+                visual_state: VisualState::default(),
+                corpse: None,
+                inventory: None,
+                pickup: None,
+                schedule: None,
+                wander: None,
+                collider: None,
+                hunger: None,
+                buffs: None,
+                progression: None,
+                species: None,
             })
             .collect()
     }
@@ -3,8 +3,10 @@
 //! This module contains components for environmental elements like stars, moon,
 //! ground tiles, and particle effects.
 
+use super::entities::Position;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Star component for background atmosphere
 #[derive(Debug, Clone)]
@@ -65,62 +67,75 @@ impl Default for Moon {
     }
 }
 
-/// Blood particle effect component
-#[derive(Debug, Clone)]
-pub struct BloodParticle {
-    pub x: f32,
-    pub y: f32,
-    pub velocity_x: f32,
-    pub velocity_y: f32,
-    pub life: f32,
-    pub max_life: f32,
-    pub size: f32,
+/// The weather over the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Overcast,
+    Rain,
+    Fog,
 }
 
-impl BloodParticle {
-    pub fn new(x: f32, y: f32) -> Self {
-        Self {
-            x,
-            y,
-            velocity_x: rand::gen_range(-60.0, 60.0),
-            velocity_y: rand::gen_range(-100.0, -20.0),
-            life: 100.0,
-            max_life: 100.0,
-            size: rand::gen_range(1.0, 3.0),
+impl Weather {
+    /// Chance of rain at each dawn and dusk
+    pub const RAIN_CHANCE: f32 = 0.25;
+    /// Chance of fog, after rain
+    pub const FOG_CHANCE: f32 = 0.15;
+    /// Chance of an overcast sky, after fog
+    pub const OVERCAST_CHANCE: f32 = 0.2;
+
+    /// Weather for the coming day or night given a roll in 0.0..1.0
+    pub fn roll(roll: f32) -> Self {
+        if roll < Self::RAIN_CHANCE {
+            Weather::Rain
+        } else if roll < Self::RAIN_CHANCE + Self::FOG_CHANCE {
+            Weather::Fog
+        } else if roll < Self::RAIN_CHANCE + Self::FOG_CHANCE + Self::OVERCAST_CHANCE {
+            Weather::Overcast
+        } else {
+            Weather::Clear
         }
     }
 
-    pub fn update(&mut self, delta_time: f32) -> bool {
-        self.x += self.velocity_x * delta_time;
-        self.y += self.velocity_y * delta_time;
-        self.velocity_y += 98.0 * delta_time; // Gravity
-        self.life -= delta_time * 0.8; // Fade over time (slower for longer effect)
-        self.life > 0.0
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Overcast => "Overcast",
+            Weather::Rain => "Rain",
+            Weather::Fog => "Fog",
+        }
     }
 
-    pub fn draw(&self, camera_offset_x: f32, camera_offset_y: f32) {
-        let zoom_level = 1.5;
-        let screen_x = self.x * zoom_level + camera_offset_x;
-        let screen_y = self.y * zoom_level + camera_offset_y;
-        let _alpha = self.life / self.max_life;
+    /// Share of the sun's strength that gets through
+    pub fn sunlight_factor(&self) -> f32 {
+        match self {
+            Weather::Clear => 1.0,
+            Weather::Overcast => 0.6,
+            Weather::Rain => 0.5,
+            Weather::Fog => 0.75,
+        }
+    }
 
-        // Make particles large and bright red for debugging visibility
-        draw_circle(
-            screen_x,
-            screen_y,
-            7.0,                            // Half the original size
-            Color::new(1.0, 0.0, 0.0, 1.0), // Bright red, fully opaque
-        );
+    /// Scale applied to how far creatures can see
+    pub fn sight_factor(&self) -> f32 {
+        match self {
+            Weather::Fog => 0.75,
+            Weather::Clear | Weather::Overcast | Weather::Rain => 1.0,
+        }
     }
 }
 
 /// Ground tile types for terrain variety
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TileType {
     Grass,
     Dirt,
     Stone,
     DeadGrass,
+    /// Rivers and lakes: slow to swim through, and running water weakens
+    /// vampires
+    Water,
 }
 
 /// Ground tile component for terrain system
@@ -132,6 +147,118 @@ pub struct GroundTile {
     pub texture_data: TileTextureData,
 }
 
+/// Coordinates of a square terrain chunk, counted in chunks from the origin
+pub type ChunkCoord = (i32, i32);
+
+/// Tracks which terrain chunks currently have their tiles in `ground_tiles`
+#[derive(Debug, Clone, Default)]
+pub struct TerrainChunks {
+    loaded: HashSet<ChunkCoord>,
+}
+
+impl TerrainChunks {
+    /// Width and height of a chunk in world units (8x8 tiles)
+    pub const SIZE: f32 = 512.0;
+
+    /// Rebuild the loaded set from tiles generated elsewhere
+    pub fn from_tiles(tiles: &[GroundTile]) -> Self {
+        Self {
+            loaded: tiles
+                .iter()
+                .map(|tile| Self::coord_of(tile.x, tile.y))
+                .collect(),
+        }
+    }
+
+    /// Chunk containing a world position
+    pub fn coord_of(x: f32, y: f32) -> ChunkCoord {
+        (
+            (x / Self::SIZE).floor() as i32,
+            (y / Self::SIZE).floor() as i32,
+        )
+    }
+
+    pub fn is_loaded(&self, coord: ChunkCoord) -> bool {
+        self.loaded.contains(&coord)
+    }
+
+    pub fn mark_loaded(&mut self, coord: ChunkCoord) {
+        self.loaded.insert(coord);
+    }
+
+    /// Forget every chunk for which `keep` returns false, returning the ones dropped
+    pub fn unload_where(&mut self, mut keep: impl FnMut(ChunkCoord) -> bool) -> Vec<ChunkCoord> {
+        let dropped: Vec<ChunkCoord> = self
+            .loaded
+            .iter()
+            .copied()
+            .filter(|&coord| !keep(coord))
+            .collect();
+        for coord in &dropped {
+            self.loaded.remove(coord);
+        }
+        dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.loaded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.loaded.is_empty()
+    }
+}
+
+/// How far the world reaches: the starting area, grown by every terrain
+/// chunk streamed in since. Movement is kept inside it, and nothing walks
+/// above the ground line.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorldBounds {
+    pub min_x: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+impl Default for WorldBounds {
+    /// The area generated before any streaming
+    fn default() -> Self {
+        Self {
+            min_x: 0.0,
+            max_x: 1600.0,
+            max_y: 1200.0,
+        }
+    }
+}
+
+impl WorldBounds {
+    /// Top of the ground; everything above is sky
+    pub const GROUND_LEVEL: f32 = 640.0;
+
+    pub fn width(&self) -> f32 {
+        self.max_x - self.min_x
+    }
+
+    /// Depth of the ground, from the ground line down
+    pub fn depth(&self) -> f32 {
+        self.max_y - Self::GROUND_LEVEL
+    }
+
+    /// Grow the bounds to cover a terrain chunk
+    pub fn include_chunk(&mut self, (chunk_x, chunk_y): ChunkCoord) {
+        self.min_x = self.min_x.min(chunk_x as f32 * TerrainChunks::SIZE);
+        self.max_x = self.max_x.max((chunk_x + 1) as f32 * TerrainChunks::SIZE);
+        self.max_y = self.max_y.max((chunk_y + 1) as f32 * TerrainChunks::SIZE);
+    }
+
+    /// Nearest position on the ground within the bounds
+    pub fn clamp(&self, position: Position) -> Position {
+        Position::new(
+            position.x.clamp(self.min_x, self.max_x),
+            position.y.clamp(Self::GROUND_LEVEL, self.max_y),
+        )
+    }
+}
+
 /// Pre-generated texture data for a ground tile to avoid per-frame random generation
 #[derive(Debug, Clone)]
 pub struct TileTextureData {
@@ -141,6 +268,9 @@ pub struct TileTextureData {
 }
 
 impl GroundTile {
+    /// Width and height of every ground tile in world units
+    pub const SIZE: f32 = 64.0;
+
     pub fn new(x: f32, y: f32, tile_type: TileType) -> Self {
         let texture_data = Self::generate_texture_data(&tile_type);
         Self {
@@ -197,6 +327,8 @@ impl GroundTile {
                     });
                 });
             }
+            // Water is animated as it is drawn
+            TileType::Water => {}
         }
 
         TileTextureData {
@@ -223,3 +355,162 @@ impl Default for TileTextureData {
         Self::new()
     }
 }
+
+/// Roadside signpost pointing toward a territory
+#[derive(Debug, Clone)]
+pub struct Signpost {
+    pub position: Position,
+    /// Name of the place the sign points to
+    pub destination_name: String,
+    pub destination: Position,
+    /// Whether the player has read this sign
+    pub read: bool,
+}
+
+impl Signpost {
+    pub fn new(position: Position, destination_name: &str, destination: Position) -> Self {
+        Self {
+            position,
+            destination_name: destination_name.to_string(),
+            destination,
+            read: false,
+        }
+    }
+
+    /// Text painted on the sign, e.g. "Bone Pit - 300m east".
+    /// One world unit is treated as a metre, rounded to the nearest ten.
+    pub fn text(&self) -> String {
+        let distance = (self.position.distance_to(&self.destination) / 10.0).round() * 10.0;
+        format!(
+            "{} - {}m {}",
+            self.destination_name,
+            distance,
+            self.direction()
+        )
+    }
+
+    /// Compass direction from the sign to its destination (north is up)
+    pub fn direction(&self) -> &'static str {
+        self.position.compass_direction_to(&self.destination)
+    }
+}
+
+/// Terrain that troubles vampires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HazardKind {
+    /// A stream or river; crossing it drains blood
+    RunningWater,
+    /// Rows of garlic a vampire cannot bring itself to walk into
+    GarlicField,
+}
+
+/// A rectangular patch of hazardous terrain (top-left corner and size)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HazardZone {
+    pub kind: HazardKind,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl HazardZone {
+    pub fn new(kind: HazardKind, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            kind,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.x >= self.x
+            && position.x <= self.x + self.width
+            && position.y >= self.y
+            && position.y <= self.y + self.height
+    }
+}
+
+/// Which parts of the ground the player has seen. The ground is split into
+/// square cells; everything outside it (the sky) counts as explored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FogOfWar {
+    columns: usize,
+    rows: usize,
+    explored: Vec<bool>,
+}
+
+impl FogOfWar {
+    pub const CELL_SIZE: f32 = 40.0;
+    /// Top of the ground; fog covers from here to the bottom of the world
+    pub const TOP: f32 = 640.0;
+    pub const WIDTH: f32 = 1600.0;
+    pub const HEIGHT: f32 = 560.0;
+    /// How far around the player the fog lifts
+    pub const PLAYER_SIGHT: f32 = 250.0;
+
+    pub fn new() -> Self {
+        let columns = (Self::WIDTH / Self::CELL_SIZE).ceil() as usize;
+        let rows = (Self::HEIGHT / Self::CELL_SIZE).ceil() as usize;
+        Self {
+            columns,
+            rows,
+            explored: vec![false; columns * rows],
+        }
+    }
+
+    fn cell_of(&self, position: &Position) -> Option<(usize, usize)> {
+        let column = (position.x / Self::CELL_SIZE).floor();
+        let row = ((position.y - Self::TOP) / Self::CELL_SIZE).floor();
+        (column >= 0.0
+            && row >= 0.0
+            && (column as usize) < self.columns
+            && (row as usize) < self.rows)
+            .then_some((column as usize, row as usize))
+    }
+
+    /// Lift the fog from every cell whose center lies within `radius`
+    pub fn reveal(&mut self, center: Position, radius: f32) {
+        let cells = (radius / Self::CELL_SIZE).ceil() as i32 + 1;
+        let column = (center.x / Self::CELL_SIZE).floor() as i32;
+        let row = ((center.y - Self::TOP) / Self::CELL_SIZE).floor() as i32;
+        for r in (row - cells).max(0)..=(row + cells).min(self.rows as i32 - 1) {
+            for c in (column - cells).max(0)..=(column + cells).min(self.columns as i32 - 1) {
+                let cell_center = Position::new(
+                    (c as f32 + 0.5) * Self::CELL_SIZE,
+                    Self::TOP + (r as f32 + 0.5) * Self::CELL_SIZE,
+                );
+                if cell_center.distance_to(&center) <= radius {
+                    self.explored[r as usize * self.columns + c as usize] = true;
+                }
+            }
+        }
+    }
+
+    pub fn is_explored(&self, position: &Position) -> bool {
+        self.cell_of(position)
+            .is_none_or(|(column, row)| self.explored[row * self.columns + column])
+    }
+
+    /// World-space top-left corners of the cells still under fog
+    pub fn unexplored_cells(&self) -> impl Iterator<Item = Position> + '_ {
+        self.explored
+            .iter()
+            .enumerate()
+            .filter(|(_, explored)| !**explored)
+            .map(|(index, _)| {
+                Position::new(
+                    (index % self.columns) as f32 * Self::CELL_SIZE,
+                    Self::TOP + (index / self.columns) as f32 * Self::CELL_SIZE,
+                )
+            })
+    }
+}
+
+impl Default for FogOfWar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -21,6 +21,26 @@ impl Default for GamePhase {
     }
 }
 
+/// Kinds of animal, each with its own stats and habits in the world data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Species {
+    Rat,
+    Deer,
+    Wolf,
+}
+
+impl Species {
+    pub const ALL: [Species; 3] = [Species::Rat, Species::Deer, Species::Wolf];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Species::Rat => "Rat",
+            Species::Deer => "Deer",
+            Species::Wolf => "Wolf",
+        }
+    }
+}
+
 /// Entity types for different character categories
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum EntityType {
@@ -30,6 +50,8 @@ pub enum EntityType {
     HostileInfected,
     Animal,
     Shelter,
+    /// Item lying in the world
+    Pickup,
 }
 
 /// Clan component for faction management
@@ -42,7 +64,17 @@ pub struct Clan {
     pub fear_of_player: f32,
     pub strength: f32,
     pub is_allied: bool,
+    /// Turned against the player by crimes against its members
+    #[serde(default)]
+    pub is_hostile: bool,
     pub is_defeated: bool,
+    pub tribute_level: TributeLevel,
+    pub unrest: f32,
+    /// Secret betrayal being plotted against the player, if any
+    pub plot: Option<RebellionPlot>,
+    /// Member count at the start of each recent day, oldest first
+    #[serde(default)]
+    pub population_history: Vec<u32>,
 }
 
 impl Clan {
@@ -55,7 +87,12 @@ impl Clan {
             fear_of_player: 0.0,
             strength: 1.0,
             is_allied: false,
+            is_hostile: false,
             is_defeated: false,
+            tribute_level: TributeLevel::None,
+            unrest: 0.0,
+            plot: None,
+            population_history: Vec::new(),
         }
     }
 
@@ -66,6 +103,178 @@ impl Clan {
     pub fn will_obey(&self) -> bool {
         self.loyalty_score() > 0.0 || self.fear_of_player > 0.7
     }
+
+    /// The clan saw the player commit a crime against one of their own:
+    /// they trust the player less and fear them more
+    pub fn witness_crime(&mut self, crime: Crime) {
+        self.trust_towards_player = (self.trust_towards_player - crime.trust_loss()).max(0.0);
+        self.fear_of_player = (self.fear_of_player + crime.fear_gain()).min(1.0);
+    }
+
+    /// Whether the clan can be asked for tribute (allied or subjugated)
+    pub fn owes_tribute(&self) -> bool {
+        self.is_allied || self.is_defeated
+    }
+
+    /// Highest tribute level the clan will pay without growing restless.
+    /// Allied clans pay out of trust, subjugated clans out of fear.
+    pub fn tolerated_tribute(&self) -> TributeLevel {
+        let willingness = self.trust_towards_player.max(self.fear_of_player);
+        TributeLevel::from_willingness(willingness)
+    }
+}
+
+/// Something done to a clan member that their clan will not forgive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crime {
+    Attack,
+    Feeding,
+    Murder,
+}
+
+impl Crime {
+    /// What the clan saw the player do
+    pub fn description(&self) -> &'static str {
+        match self {
+            Crime::Attack => "attack",
+            Crime::Feeding => "feed on",
+            Crime::Murder => "kill",
+        }
+    }
+
+    pub fn trust_loss(&self) -> f32 {
+        match self {
+            Crime::Attack => 0.05,
+            Crime::Feeding => 0.15,
+            Crime::Murder => 0.25,
+        }
+    }
+
+    pub fn fear_gain(&self) -> f32 {
+        match self {
+            Crime::Attack => 0.05,
+            Crime::Feeding => 0.05,
+            Crime::Murder => 0.15,
+        }
+    }
+}
+
+/// How heavily the player taxes a clan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TributeLevel {
+    #[default]
+    None,
+    Light,
+    Moderate,
+    Heavy,
+    Extortionate,
+}
+
+impl TributeLevel {
+    /// Map a 0.0-1.0 willingness score to the tribute level it supports
+    pub fn from_willingness(willingness: f32) -> Self {
+        match willingness {
+            w if w >= 0.9 => TributeLevel::Extortionate,
+            w if w >= 0.7 => TributeLevel::Heavy,
+            w if w >= 0.45 => TributeLevel::Moderate,
+            w if w >= 0.2 => TributeLevel::Light,
+            _ => TributeLevel::None,
+        }
+    }
+
+    /// Fraction of the clan's output taken as tribute
+    pub fn rate(&self) -> f32 {
+        match self {
+            TributeLevel::None => 0.0,
+            TributeLevel::Light => 0.1,
+            TributeLevel::Moderate => 0.2,
+            TributeLevel::Heavy => 0.35,
+            TributeLevel::Extortionate => 0.5,
+        }
+    }
+
+    /// Next heavier level (saturates at Extortionate)
+    pub fn raise(&self) -> Self {
+        match self {
+            TributeLevel::None => TributeLevel::Light,
+            TributeLevel::Light => TributeLevel::Moderate,
+            TributeLevel::Moderate => TributeLevel::Heavy,
+            TributeLevel::Heavy | TributeLevel::Extortionate => TributeLevel::Extortionate,
+        }
+    }
+
+    /// Next lighter level (saturates at None)
+    pub fn lower(&self) -> Self {
+        match self {
+            TributeLevel::None | TributeLevel::Light => TributeLevel::None,
+            TributeLevel::Moderate => TributeLevel::Light,
+            TributeLevel::Heavy => TributeLevel::Moderate,
+            TributeLevel::Extortionate => TributeLevel::Heavy,
+        }
+    }
+
+    /// Get the name as a display string
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TributeLevel::None => "None",
+            TributeLevel::Light => "Light",
+            TributeLevel::Moderate => "Moderate",
+            TributeLevel::Heavy => "Heavy",
+            TributeLevel::Extortionate => "Extortionate",
+        }
+    }
+}
+
+/// Form a clan's betrayal takes when its plot matures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BetrayalKind {
+    /// Quietly abandon the alliance
+    Defection,
+    /// Attack the player at the next meeting with the clan leader
+    Ambush,
+    /// Clan members across the territory rise up against the player
+    TerritoryRevolt,
+}
+
+/// A secret defection timer running inside a disloyal clan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebellionPlot {
+    pub kind: BetrayalKind,
+    /// Seconds left before the betrayal happens
+    pub remaining: f32,
+    /// Total length of the plot, used to time the emissary warning
+    pub duration: f32,
+    pub emissary_warned: bool,
+    /// Game time of the last overheard bark hinting at the plot
+    pub last_bark_time: f32,
+    /// Set once an ambush is waiting for the player's next visit
+    pub ambush_ready: bool,
+}
+
+impl RebellionPlot {
+    pub fn new(kind: BetrayalKind, duration: f32) -> Self {
+        Self {
+            kind,
+            remaining: duration,
+            duration,
+            emissary_warned: false,
+            last_bark_time: f32::NEG_INFINITY,
+            ambush_ready: false,
+        }
+    }
+
+    /// Fraction of the plot that has elapsed (0.0 to 1.0)
+    pub fn progress(&self) -> f32 {
+        (1.0 - self.remaining / self.duration).clamp(0.0, 1.0)
+    }
+}
+
+/// Resources collected from clans as tribute
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TributeStockpile {
+    pub blood_vials: u32,
+    pub materials: u32,
+    pub members: u32,
 }
 
 /// Player component - marks the player entity
@@ -90,51 +299,6 @@ impl Default for Player {
     }
 }
 
-/// Inventory component for items and resources
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Inventory {
-    pub items: HashMap<String, u32>,
-    pub capacity: u32,
-}
-
-impl Inventory {
-    pub fn new(capacity: u32) -> Self {
-        Self {
-            items: HashMap::new(),
-            capacity,
-        }
-    }
-
-    pub fn add_item(&mut self, item: String, quantity: u32) -> bool {
-        let current_count = self.items.values().sum::<u32>();
-        if current_count + quantity <= self.capacity {
-            *self.items.entry(item).or_insert(0) += quantity;
-            true
-        } else {
-            false
-        }
-    }
-
-    pub fn remove_item(&mut self, item: &str, quantity: u32) -> bool {
-        if let Some(count) = self.items.get_mut(item) {
-            if *count >= quantity {
-                *count -= quantity;
-                if *count == 0 {
-                    self.items.remove(item);
-                }
-                return true;
-            }
-        }
-        false
-    }
-
-    pub fn has_item(&self, item: &str, quantity: u32) -> bool {
-        self.items
-            .get(item)
-            .map_or(false, |&count| count >= quantity)
-    }
-}
-
 /// Dialogue component for interactive NPCs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dialogue {
@@ -0,0 +1,186 @@
+//! Item components for the player's inventory and pickups in the world
+//!
+//! This module contains the item definitions, the inventory that holds them,
+//! the pickup component for items lying on the ground, and what the slain
+//! leave behind.
+
+use super::game_data::EntityType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Kinds of items the player can carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItemKind {
+    /// Stoppered vial of blood - restores blood when drunk
+    BloodVial,
+    /// Strip of linen - binds wounds to restore health
+    Bandage,
+    /// Iron key - opens a locked shelter
+    Key,
+    /// Keepsake that clan leaders accept as a gift
+    Trinket,
+    /// A vampire hunter's field notes on the creatures of the night
+    HunterNotes,
+}
+
+impl ItemKind {
+    /// Blood held by one vial
+    pub const VIAL_BLOOD: f32 = 30.0;
+
+    /// Every item kind, in inventory display order
+    pub const ALL: [ItemKind; 5] = [
+        ItemKind::BloodVial,
+        ItemKind::Bandage,
+        ItemKind::Key,
+        ItemKind::Trinket,
+        ItemKind::HunterNotes,
+    ];
+
+    /// Get the name as a display string
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ItemKind::BloodVial => "Blood Vial",
+            ItemKind::Bandage => "Bandage",
+            ItemKind::Key => "Iron Key",
+            ItemKind::Trinket => "Trinket",
+            ItemKind::HunterNotes => "Hunter's Notes",
+        }
+    }
+
+    /// Most of this item the player can carry, if limited
+    pub fn carry_limit(&self) -> Option<u32> {
+        match self {
+            ItemKind::BloodVial => Some(5),
+            ItemKind::Bandage | ItemKind::Key | ItemKind::Trinket | ItemKind::HunterNotes => None,
+        }
+    }
+
+    /// What a slain creature drops, if anything, for a roll in `0.0..1.0`.
+    /// Leaders always carry a keepsake; their kin and the infected only
+    /// sometimes have anything worth taking.
+    pub fn loot(entity_type: &EntityType, roll: f32) -> Option<(ItemKind, u32)> {
+        match entity_type {
+            EntityType::ClanLeader(_) => Some((ItemKind::Trinket, 1)),
+            EntityType::ClanMember(_) if roll < 0.15 => Some((ItemKind::BloodVial, 1)),
+            EntityType::ClanMember(_) if roll < 0.25 => Some((ItemKind::Trinket, 1)),
+            EntityType::HostileInfected if roll < 0.2 => Some((ItemKind::Bandage, 1)),
+            EntityType::HostileInfected if roll < 0.25 => Some((ItemKind::HunterNotes, 1)),
+            _ => None,
+        }
+    }
+
+    /// What using the item does, shown in the inventory screen
+    pub fn description(&self) -> &'static str {
+        match self {
+            ItemKind::BloodVial => "Drink to restore 30 blood.",
+            ItemKind::Bandage => "Bind your wounds to restore 25 health.",
+            ItemKind::Key => "Opens a locked shelter you are standing beside.",
+            ItemKind::Trinket => "Offer to a nearby clan leader to earn their trust.",
+            ItemKind::HunterNotes => "Read to learn how a creature behaves for the bestiary.",
+        }
+    }
+}
+
+/// Inventory component for carried items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Inventory {
+    pub items: HashMap<ItemKind, u32>,
+    pub capacity: u32,
+}
+
+impl Inventory {
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            items: HashMap::new(),
+            capacity,
+        }
+    }
+
+    pub fn add_item(&mut self, item: ItemKind, quantity: u32) -> bool {
+        let within_limit = item
+            .carry_limit()
+            .is_none_or(|limit| self.count(item) + quantity <= limit);
+        if within_limit && self.total() + quantity <= self.capacity {
+            *self.items.entry(item).or_insert(0) += quantity;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn remove_item(&mut self, item: ItemKind, quantity: u32) -> bool {
+        if let Some(count) = self.items.get_mut(&item) {
+            if *count >= quantity {
+                *count -= quantity;
+                if *count == 0 {
+                    self.items.remove(&item);
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn has_item(&self, item: ItemKind, quantity: u32) -> bool {
+        self.count(item) >= quantity
+    }
+
+    /// Number of the given item carried
+    pub fn count(&self, item: ItemKind) -> u32 {
+        self.items.get(&item).copied().unwrap_or(0)
+    }
+
+    /// Total number of items carried, counted against capacity
+    pub fn total(&self) -> u32 {
+        self.items.values().sum()
+    }
+
+    /// Carried items with their counts, in display order
+    pub fn entries(&self) -> Vec<(ItemKind, u32)> {
+        ItemKind::ALL
+            .into_iter()
+            .map(|item| (item, self.count(item)))
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+}
+
+/// Pickup component - an item lying in the world waiting to be collected
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pickup {
+    pub item: ItemKind,
+    pub quantity: u32,
+}
+
+impl Pickup {
+    pub fn new(item: ItemKind, quantity: u32) -> Self {
+        Self { item, quantity }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inventory_capacity_and_order() {
+        let mut inventory = Inventory::new(5);
+        assert!(inventory.add_item(ItemKind::Trinket, 1));
+        assert!(inventory.add_item(ItemKind::BloodVial, 3));
+        assert!(!inventory.add_item(ItemKind::Bandage, 2));
+        // Vials are limited on their own, whatever room is left
+        let mut roomy = Inventory::new(20);
+        assert!(roomy.add_item(ItemKind::BloodVial, 5));
+        assert!(!roomy.add_item(ItemKind::BloodVial, 1));
+        assert_eq!(inventory.total(), 4);
+
+        assert_eq!(
+            inventory.entries(),
+            vec![(ItemKind::BloodVial, 3), (ItemKind::Trinket, 1)]
+        );
+
+        assert!(inventory.remove_item(ItemKind::Trinket, 1));
+        assert!(!inventory.has_item(ItemKind::Trinket, 1));
+        assert!(!inventory.remove_item(ItemKind::BloodVial, 4));
+    }
+}
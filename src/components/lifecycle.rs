@@ -0,0 +1,127 @@
+//! Entity lifecycle components
+//!
+//! Entity IDs are generational: the low bits name a slot and the high bits
+//! count how many times that slot has been handed out. When an entity is
+//! despawned its slot goes back on a free list and the next entity to take
+//! it gets a new generation, so an ID held by a shelter or a hunting
+//! infected never quietly comes to mean a different creature.
+
+use std::collections::HashSet;
+
+/// Bits of an ID that name its slot; the rest hold the generation
+const SLOT_BITS: u32 = 20;
+const SLOT_MASK: u32 = (1 << SLOT_BITS) - 1;
+/// Generations wrap after this many reuses of one slot
+const GENERATIONS: u32 = 1 << (32 - SLOT_BITS);
+
+/// The slot an entity ID refers to
+pub fn id_slot(id: u32) -> u32 {
+    id & SLOT_MASK
+}
+
+/// How many times the ID's slot had been reused when it was handed out
+pub fn id_generation(id: u32) -> u32 {
+    id >> SLOT_BITS
+}
+
+fn make_id(slot: u32, generation: u32) -> u32 {
+    (generation % GENERATIONS) << SLOT_BITS | slot
+}
+
+/// Hands out entity IDs, recycling the slots of despawned entities
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntityAllocator {
+    /// Current generation of each slot handed out so far
+    generations: Vec<u32>,
+    /// Whether each slot is in use
+    live: Vec<bool>,
+    /// Slots waiting to be reused, oldest first
+    free: Vec<u32>,
+}
+
+impl EntityAllocator {
+    /// Rebuild the allocator for entities restored from a save, where
+    /// `next_slot` slots had been handed out
+    pub fn from_ids(ids: impl IntoIterator<Item = u32>, next_slot: u32) -> Self {
+        let mut allocator = Self {
+            generations: vec![0; next_slot as usize],
+            live: vec![false; next_slot as usize],
+            free: Vec::new(),
+        };
+        for id in ids {
+            let slot = id_slot(id) as usize;
+            if slot >= allocator.live.len() {
+                allocator.generations.resize(slot + 1, 0);
+                allocator.live.resize(slot + 1, false);
+            }
+            allocator.generations[slot] = id_generation(id);
+            allocator.live[slot] = true;
+        }
+        allocator.free = (0..allocator.live.len() as u32)
+            .filter(|&slot| !allocator.live[slot as usize])
+            .collect();
+        allocator
+    }
+
+    /// A fresh ID, in a recycled slot when one is free
+    pub fn allocate(&mut self) -> u32 {
+        if !self.free.is_empty() {
+            let slot = self.free.remove(0);
+            let generation = (self.generations[slot as usize] + 1) % GENERATIONS;
+            self.generations[slot as usize] = generation;
+            self.live[slot as usize] = true;
+            return make_id(slot, generation);
+        }
+        let slot = self.generations.len() as u32;
+        self.generations.push(0);
+        self.live.push(true);
+        make_id(slot, 0)
+    }
+
+    /// Give an ID's slot back for reuse. Returns false for an ID that was
+    /// already released or belongs to an older generation.
+    pub fn release(&mut self, id: u32) -> bool {
+        if !self.is_live(id) {
+            return false;
+        }
+        let slot = id_slot(id);
+        self.live[slot as usize] = false;
+        self.free.push(slot);
+        true
+    }
+
+    /// Release every live ID not in `present`, for entities removed from
+    /// the world without going through `release`
+    pub fn release_missing(&mut self, present: &HashSet<u32>) -> Vec<u32> {
+        let missing: Vec<u32> = (0..self.live.len() as u32)
+            .filter(|&slot| self.live[slot as usize])
+            .map(|slot| make_id(slot, self.generations[slot as usize]))
+            .filter(|id| !present.contains(id))
+            .collect();
+        for id in &missing {
+            self.release(*id);
+        }
+        missing
+    }
+
+    /// Whether the ID is the one currently holding its slot
+    pub fn is_live(&self, id: u32) -> bool {
+        let slot = id_slot(id) as usize;
+        self.live.get(slot).copied().unwrap_or(false) && self.generations[slot] == id_generation(id)
+    }
+
+    /// Slots handed out so far, live or free; saved so a restored game
+    /// carries on where it left off
+    pub fn next_slot(&self) -> u32 {
+        self.generations.len() as u32
+    }
+
+    /// Number of live IDs
+    pub fn len(&self) -> usize {
+        self.live.iter().filter(|live| **live).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -3,19 +3,31 @@
 //! This module contains all the component types used in the vampire RPG.
 //! Components represent data that can be attached to entities.
 
+pub mod buffs;
 pub mod combat;
 pub mod entities;
 pub mod entity_iterator;
 pub mod environment;
 pub mod game_data;
+pub mod items;
+pub mod lifecycle;
+pub mod progression;
+pub mod schedule;
 pub mod shelter;
+pub mod territory;
 pub mod vampire;
 
 // Re-export all component types for easy access
+pub use buffs::*;
 pub use combat::*;
 pub use entities::*;
 pub use entity_iterator::*;
 pub use environment::*;
 pub use game_data::*;
+pub use items::*;
+pub use lifecycle::*;
+pub use progression::*;
+pub use schedule::*;
 pub use shelter::*;
+pub use territory::*;
 pub use vampire::*;
@@ -0,0 +1,239 @@
+//! Progression components
+//!
+//! The player grows stronger with every kill, feeding and finished
+//! objective. Experience raises their level, each level grants a skill
+//! point, and points are spent in a small skill tree of three branches.
+//! Combat, Blood and Shadow each open with a passive multiplier and lead on
+//! to the activatable abilities beyond Shadow Dash and Blood Sense.
+
+use super::buffs::BuffKind;
+use super::vampire::SpecialAbility;
+use serde::{Deserialize, Serialize};
+
+/// Experience the player gains for each kind of deed
+pub const KILL_XP: u32 = 25;
+pub const FEED_XP: u32 = 15;
+pub const OBJECTIVE_XP: u32 = 100;
+
+/// The three branches of the skill tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SkillBranch {
+    Combat,
+    Blood,
+    Shadow,
+}
+
+impl SkillBranch {
+    pub const ALL: [SkillBranch; 3] =
+        [SkillBranch::Combat, SkillBranch::Blood, SkillBranch::Shadow];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SkillBranch::Combat => "Combat",
+            SkillBranch::Blood => "Blood",
+            SkillBranch::Shadow => "Shadow",
+        }
+    }
+
+    /// The branch's skills, from the root down
+    pub fn skills(&self) -> [Skill; 3] {
+        match self {
+            SkillBranch::Combat => [Skill::Ferocity, Skill::LanceOfBlood, Skill::Savagery],
+            SkillBranch::Blood => [Skill::DeepDraught, Skill::ScentOfBlood, Skill::Veilsight],
+            SkillBranch::Shadow => [Skill::Fleetness, Skill::WingsOfNight, Skill::CrowsEye],
+        }
+    }
+}
+
+/// A skill learned with a skill point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Skill {
+    /// Stronger blows
+    Ferocity,
+    /// Unlocks Blood Lance
+    LanceOfBlood,
+    /// Stronger blows still
+    Savagery,
+    /// More blood from every feeding
+    DeepDraught,
+    /// Unlocks Blood Sight
+    ScentOfBlood,
+    /// Unlocks Spectral Vision
+    Veilsight,
+    /// Faster on foot
+    Fleetness,
+    /// Unlocks Bat Form
+    WingsOfNight,
+    /// Unlocks Crow Scout
+    CrowsEye,
+}
+
+impl Skill {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Skill::Ferocity => "Ferocity",
+            Skill::LanceOfBlood => "Lance of Blood",
+            Skill::Savagery => "Savagery",
+            Skill::DeepDraught => "Deep Draught",
+            Skill::ScentOfBlood => "Scent of Blood",
+            Skill::Veilsight => "Veilsight",
+            Skill::Fleetness => "Fleetness",
+            Skill::WingsOfNight => "Wings of Night",
+            Skill::CrowsEye => "Crow's Eye",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self.unlocks() {
+            Some(ability) => format!("Unlocks {}", ability.display_name()),
+            None => match self {
+                Skill::DeepDraught => "+20% blood from feeding".to_string(),
+                Skill::Fleetness => "+10% movement speed".to_string(),
+                _ => "+15% strength".to_string(),
+            },
+        }
+    }
+
+    pub fn branch(&self) -> SkillBranch {
+        match self {
+            Skill::Ferocity | Skill::LanceOfBlood | Skill::Savagery => SkillBranch::Combat,
+            Skill::DeepDraught | Skill::ScentOfBlood | Skill::Veilsight => SkillBranch::Blood,
+            Skill::Fleetness | Skill::WingsOfNight | Skill::CrowsEye => SkillBranch::Shadow,
+        }
+    }
+
+    /// The skill that must be learned first, if any
+    pub fn prerequisite(&self) -> Option<Skill> {
+        match self {
+            Skill::Ferocity | Skill::DeepDraught | Skill::Fleetness => None,
+            Skill::LanceOfBlood => Some(Skill::Ferocity),
+            Skill::Savagery => Some(Skill::LanceOfBlood),
+            Skill::ScentOfBlood => Some(Skill::DeepDraught),
+            Skill::Veilsight => Some(Skill::ScentOfBlood),
+            Skill::WingsOfNight => Some(Skill::Fleetness),
+            Skill::CrowsEye => Some(Skill::WingsOfNight),
+        }
+    }
+
+    /// The ability this skill makes available, if any
+    pub fn unlocks(&self) -> Option<SpecialAbility> {
+        match self {
+            Skill::LanceOfBlood => Some(SpecialAbility::BloodLance),
+            Skill::ScentOfBlood => Some(SpecialAbility::BloodSight),
+            Skill::Veilsight => Some(SpecialAbility::SpectralVision),
+            Skill::WingsOfNight => Some(SpecialAbility::BatForm),
+            Skill::CrowsEye => Some(SpecialAbility::CrowScout),
+            _ => None,
+        }
+    }
+
+    /// The skill that unlocks an ability; None for those known from the start
+    pub fn unlocking(ability: SpecialAbility) -> Option<Skill> {
+        SkillBranch::ALL
+            .iter()
+            .flat_map(|branch| branch.skills())
+            .find(|skill| skill.unlocks() == Some(ability))
+    }
+}
+
+/// Why a skill could not be learned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearnError {
+    AlreadyKnown,
+    NoSkillPoints,
+    MissingPrerequisite(Skill),
+}
+
+/// Experience, level and learned skills of a growing vampire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Progression {
+    /// Experience toward the next level
+    pub xp: u32,
+    pub level: u32,
+    pub skill_points: u32,
+    pub skills: Vec<Skill>,
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Self {
+            xp: 0,
+            level: 1,
+            skill_points: 0,
+            skills: Vec::new(),
+        }
+    }
+}
+
+impl Progression {
+    /// Experience needed to climb from the current level to the next
+    pub fn xp_to_next(&self) -> u32 {
+        100 * self.level
+    }
+
+    /// Add experience, returning how many levels were gained. Each level
+    /// grants a skill point.
+    pub fn gain_xp(&mut self, amount: u32) -> u32 {
+        self.xp += amount;
+        let mut levels = 0;
+        while self.xp >= self.xp_to_next() {
+            self.xp -= self.xp_to_next();
+            self.level += 1;
+            self.skill_points += 1;
+            levels += 1;
+        }
+        levels
+    }
+
+    pub fn has(&self, skill: Skill) -> bool {
+        self.skills.contains(&skill)
+    }
+
+    pub fn can_learn(&self, skill: Skill) -> Result<(), LearnError> {
+        if self.has(skill) {
+            return Err(LearnError::AlreadyKnown);
+        }
+        if let Some(required) = skill.prerequisite().filter(|required| !self.has(*required)) {
+            return Err(LearnError::MissingPrerequisite(required));
+        }
+        if self.skill_points == 0 {
+            return Err(LearnError::NoSkillPoints);
+        }
+        Ok(())
+    }
+
+    /// Spend a skill point on a skill
+    pub fn learn(&mut self, skill: Skill) -> Result<(), LearnError> {
+        self.can_learn(skill)?;
+        self.skill_points -= 1;
+        self.skills.push(skill);
+        Ok(())
+    }
+
+    /// Whether the ability has been learned; Shadow Dash and Blood Sense
+    /// are known from the start
+    pub fn knows(&self, ability: SpecialAbility) -> bool {
+        Skill::unlocking(ability).is_none_or(|skill| self.has(skill))
+    }
+
+    /// Passive multiplier from learned skills
+    pub fn multiplier(&self, kind: BuffKind) -> f32 {
+        self.skills
+            .iter()
+            .map(|skill| match (skill, kind) {
+                (Skill::Ferocity | Skill::Savagery, BuffKind::Strength) => 1.15,
+                (Skill::Fleetness, BuffKind::Speed) => 1.1,
+                _ => 1.0,
+            })
+            .product()
+    }
+
+    /// Multiplier on the blood drunk from each feeding
+    pub fn blood_multiplier(&self) -> f32 {
+        if self.has(Skill::DeepDraught) {
+            1.2
+        } else {
+            1.0
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Schedule components
+//!
+//! Daily routines for NPCs that live by the clock rather than reacting to the
+//! player: clan members sleep in a shelter through the day and patrol around
+//! their leader at night.
+
+use super::entities::Position;
+use serde::{Deserialize, Serialize};
+
+/// What a scheduled NPC is doing right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleActivity {
+    /// Circling their leader through the night
+    Patrol,
+    /// Heading for a shelter as dawn comes
+    ReturnToShelter,
+    /// Asleep inside a shelter until dusk
+    Sleep,
+}
+
+impl ScheduleActivity {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ScheduleActivity::Patrol => "Patrolling",
+            ScheduleActivity::ReturnToShelter => "Returning to shelter",
+            ScheduleActivity::Sleep => "Sleeping",
+        }
+    }
+}
+
+/// A day/night routine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub activity: ScheduleActivity,
+    /// Shelter the NPC is walking to or sleeping in
+    pub home_shelter: Option<u32>,
+    /// Where the NPC patrols when their leader is gone
+    pub post: Position,
+    /// Angle of the NPC's place on its patrol circle, in radians
+    pub patrol_angle: f32,
+    pub patrol_radius: f32,
+}
+
+impl Schedule {
+    pub fn new(post: Position, patrol_angle: f32, patrol_radius: f32) -> Self {
+        Self {
+            activity: ScheduleActivity::Patrol,
+            home_shelter: None,
+            post,
+            patrol_angle,
+            patrol_radius,
+        }
+    }
+}
@@ -3,8 +3,10 @@
 //! This module contains components for shelter structures that provide
 //! protection from sunlight during daytime, essential for vampire survival.
 
+use super::items::ItemKind;
 use macroquad::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Different types of shelters available in the world
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -23,6 +25,10 @@ pub enum ShelterType {
     Shed,
     /// Bridge underpass - medium protection, urban
     BridgeUnderpass,
+    /// Canvas tent pitched by the player - the first building tier
+    Tent,
+    /// The player's lair - the last building tier and their home
+    Lair,
 }
 
 impl ShelterType {
@@ -36,6 +42,8 @@ impl ShelterType {
             ShelterType::Ruins => 0.7,
             ShelterType::Shed => 0.6,
             ShelterType::BridgeUnderpass => 0.75,
+            ShelterType::Tent => 0.5,
+            ShelterType::Lair => 0.95,
         }
     }
 
@@ -49,6 +57,8 @@ impl ShelterType {
             ShelterType::Ruins => 4,
             ShelterType::Shed => 2,
             ShelterType::BridgeUnderpass => 6,
+            ShelterType::Tent => 1,
+            ShelterType::Lair => 4,
         }
     }
 
@@ -62,9 +72,24 @@ impl ShelterType {
             ShelterType::Ruins => 50.0,
             ShelterType::Shed => 45.0,
             ShelterType::BridgeUnderpass => 55.0,
+            ShelterType::Tent => 35.0,
+            ShelterType::Lair => 50.0,
         }
     }
 
+    /// Whether the shelter has walls that block movement. Solid footprints
+    /// stay inside the discovery range, so the shelter can still be entered.
+    pub fn is_solid(&self) -> bool {
+        matches!(
+            self,
+            ShelterType::Cave
+                | ShelterType::Building
+                | ShelterType::Ruins
+                | ShelterType::Shed
+                | ShelterType::Lair
+        )
+    }
+
     /// Get the visual size for rendering
     pub fn visual_size(&self) -> (f32, f32) {
         match self {
@@ -75,6 +100,32 @@ impl ShelterType {
             ShelterType::Ruins => (70.0, 45.0),
             ShelterType::Shed => (35.0, 25.0),
             ShelterType::BridgeUnderpass => (90.0, 40.0),
+            ShelterType::Tent => (30.0, 22.0),
+            ShelterType::Lair => (60.0, 45.0),
+        }
+    }
+
+    /// How tall the shelter stands, which sets the length of its shadow.
+    /// Underground shelters are flush with the ground and cast none.
+    pub fn shadow_height(&self) -> f32 {
+        match self {
+            ShelterType::Cave => 30.0,
+            ShelterType::Building => 60.0,
+            ShelterType::TreeCover => 45.0,
+            ShelterType::Underground => 0.0,
+            ShelterType::Ruins => 35.0,
+            ShelterType::Shed => 25.0,
+            ShelterType::BridgeUnderpass => 40.0,
+            ShelterType::Tent => 20.0,
+            ShelterType::Lair => 40.0,
+        }
+    }
+
+    /// How much sunlight the shadow blocks; leaves let some through
+    pub fn shade_density(&self) -> f32 {
+        match self {
+            ShelterType::TreeCover => 0.6,
+            _ => 1.0,
         }
     }
 
@@ -88,6 +139,8 @@ impl ShelterType {
             ShelterType::Ruins => Color::new(0.5, 0.4, 0.3, 1.0), // Tan
             ShelterType::Shed => Color::new(0.4, 0.2, 0.1, 1.0), // Dark brown
             ShelterType::BridgeUnderpass => Color::new(0.5, 0.5, 0.5, 1.0), // Medium gray
+            ShelterType::Tent => Color::new(0.6, 0.55, 0.4, 1.0), // Canvas
+            ShelterType::Lair => Color::new(0.25, 0.2, 0.22, 1.0), // Dark stone
         }
     }
 
@@ -101,6 +154,8 @@ impl ShelterType {
             ShelterType::Ruins => Color::new(0.7, 0.6, 0.4, 1.0), // Light tan
             ShelterType::Shed => Color::new(0.6, 0.3, 0.1, 1.0), // Orange-brown
             ShelterType::BridgeUnderpass => Color::new(0.7, 0.7, 0.7, 1.0), // Light gray
+            ShelterType::Tent => Color::new(0.4, 0.3, 0.2, 1.0), // Pole brown
+            ShelterType::Lair => Color::new(0.5, 0.05, 0.1, 1.0), // Blood red
         }
     }
 
@@ -114,10 +169,39 @@ impl ShelterType {
             ShelterType::Ruins => "Ancient Ruins",
             ShelterType::Shed => "Shed",
             ShelterType::BridgeUnderpass => "Bridge Underpass",
+            ShelterType::Tent => "Tent",
+            ShelterType::Lair => "Lair",
+        }
+    }
+
+    /// What the player pays to build this tier, if it is one they can build
+    pub fn build_cost(&self) -> Option<BuildCost> {
+        let (blood, seconds) = match self {
+            ShelterType::Tent => (20.0, 10.0),
+            ShelterType::Shed => (35.0, 20.0),
+            ShelterType::Lair => (60.0, 40.0),
+            _ => return None,
+        };
+        Some(BuildCost { blood, seconds })
+    }
+
+    /// The building tier a player-built shelter of this type grows into
+    pub fn next_tier(&self) -> Option<ShelterType> {
+        match self {
+            ShelterType::Tent => Some(ShelterType::Shed),
+            ShelterType::Shed => Some(ShelterType::Lair),
+            _ => None,
         }
     }
 }
 
+/// Blood and construction time for one building tier
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BuildCost {
+    pub blood: f32,
+    pub seconds: f32,
+}
+
 /// Shelter condition affecting protection effectiveness
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ShelterCondition {
@@ -145,6 +229,38 @@ impl ShelterCondition {
         }
     }
 
+    /// The next better condition, or None when already pristine
+    pub fn improved(&self) -> Option<ShelterCondition> {
+        match self {
+            ShelterCondition::Ruined => Some(ShelterCondition::Poor),
+            ShelterCondition::Poor => Some(ShelterCondition::Damaged),
+            ShelterCondition::Damaged => Some(ShelterCondition::Good),
+            ShelterCondition::Good => Some(ShelterCondition::Pristine),
+            ShelterCondition::Pristine => None,
+        }
+    }
+
+    /// The next worse condition, or None when already ruined
+    pub fn worsened(&self) -> Option<ShelterCondition> {
+        match self {
+            ShelterCondition::Pristine => Some(ShelterCondition::Good),
+            ShelterCondition::Good => Some(ShelterCondition::Damaged),
+            ShelterCondition::Damaged => Some(ShelterCondition::Poor),
+            ShelterCondition::Poor => Some(ShelterCondition::Ruined),
+            ShelterCondition::Ruined => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ShelterCondition::Pristine => "Pristine",
+            ShelterCondition::Good => "Good",
+            ShelterCondition::Damaged => "Damaged",
+            ShelterCondition::Poor => "Poor",
+            ShelterCondition::Ruined => "Ruined",
+        }
+    }
+
     /// Get the display color for this condition
     pub fn status_color(&self) -> Color {
         match self {
@@ -157,8 +273,34 @@ impl ShelterCondition {
     }
 }
 
+/// Work the player can do on a shelter from inside it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShelterWork {
+    /// Mend it up to this condition
+    Repair(ShelterCondition),
+    /// Reinforce it to this upgrade level
+    Reinforce(u32),
+}
+
+impl ShelterWork {
+    /// Seconds of work the job takes
+    pub fn seconds(&self) -> f32 {
+        match self {
+            ShelterWork::Repair(_) => Shelter::REPAIR_SECONDS,
+            ShelterWork::Reinforce(_) => Shelter::REINFORCE_SECONDS,
+        }
+    }
+
+    pub fn verb(&self) -> &'static str {
+        match self {
+            ShelterWork::Repair(_) => "repair",
+            ShelterWork::Reinforce(_) => "reinforce",
+        }
+    }
+}
+
 /// Main shelter component
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Shelter {
     /// Type of shelter
     pub shelter_type: ShelterType,
@@ -176,9 +318,42 @@ pub struct Shelter {
     pub enterable: bool,
     /// Time when shelter was last used (for cooldowns/degradation)
     pub last_used: f32,
+    /// A human family lives here; a vampire may not cross the threshold uninvited
+    #[serde(default)]
+    pub inhabited: bool,
+    /// The family has invited the player in
+    #[serde(default)]
+    pub invited: bool,
+    /// The door is locked and needs a key
+    #[serde(default)]
+    pub locked: bool,
+    /// Reinforcements added by the player, each raising protection
+    #[serde(default)]
+    pub upgrade_level: u32,
+    /// Progress (0.0 to 1.0) of the repair or reinforcement under way
+    #[serde(default)]
+    pub work_progress: f32,
+    /// Raised by the player in build mode
+    #[serde(default)]
+    pub built_by_player: bool,
+    /// Progress (0.0 to 1.0) while still being built; None once standing
+    #[serde(default)]
+    pub construction: Option<f32>,
+    /// Items the player has left here, by kind
+    #[serde(default)]
+    pub stash: HashMap<ItemKind, u32>,
 }
 
 impl Shelter {
+    /// Reinforcements a shelter can take
+    pub const MAX_UPGRADE_LEVEL: u32 = 3;
+    /// Protection each reinforcement adds before condition is applied
+    pub const UPGRADE_PROTECTION: f32 = 0.05;
+    /// Seconds of work to raise the condition one step
+    pub const REPAIR_SECONDS: f32 = 8.0;
+    /// Seconds of work for one reinforcement
+    pub const REINFORCE_SECONDS: f32 = 15.0;
+
     /// Create a new shelter of the specified type
     pub fn new(shelter_type: ShelterType) -> Self {
         Self {
@@ -190,6 +365,14 @@ impl Shelter {
             name: None,
             enterable: true,
             last_used: 0.0,
+            inhabited: false,
+            invited: false,
+            locked: false,
+            upgrade_level: 0,
+            work_progress: 0.0,
+            built_by_player: false,
+            construction: None,
+            stash: HashMap::new(),
         }
     }
 
@@ -211,12 +394,74 @@ impl Shelter {
 
     /// Calculate the effective protection this shelter provides
     pub fn effective_protection(&self) -> f32 {
-        self.shelter_type.base_protection() * self.condition.protection_multiplier()
+        let reinforced = self.shelter_type.base_protection()
+            + self.upgrade_level as f32 * Self::UPGRADE_PROTECTION;
+        reinforced.min(1.0) * self.condition.protection_multiplier()
+    }
+
+    /// What working on the shelter would do next: repairs come first, then
+    /// reinforcements, and a fully reinforced pristine shelter needs nothing
+    pub fn next_work(&self) -> Option<ShelterWork> {
+        if self.is_under_construction() {
+            return None;
+        }
+        match self.condition.improved() {
+            Some(condition) => Some(ShelterWork::Repair(condition)),
+            None if self.upgrade_level < Self::MAX_UPGRADE_LEVEL => {
+                Some(ShelterWork::Reinforce(self.upgrade_level + 1))
+            }
+            None => None,
+        }
+    }
+
+    /// Put `seconds` of work into the next job, returning it once finished
+    pub fn apply_work(&mut self, seconds: f32) -> Option<ShelterWork> {
+        let work = self.next_work()?;
+        self.work_progress += seconds / work.seconds();
+        if self.work_progress < 1.0 {
+            return None;
+        }
+        self.work_progress = 0.0;
+        match &work {
+            ShelterWork::Repair(condition) => self.condition = condition.clone(),
+            ShelterWork::Reinforce(level) => self.upgrade_level = *level,
+        }
+        Some(work)
+    }
+
+    /// Name of the reinforcement level, e.g. "Reinforced II"
+    pub fn upgrade_name(&self) -> Option<&'static str> {
+        match self.upgrade_level {
+            0 => None,
+            1 => Some("Reinforced I"),
+            2 => Some("Reinforced II"),
+            _ => Some("Reinforced III"),
+        }
+    }
+
+    /// Condition and reinforcement, e.g. "Good, Reinforced I"
+    pub fn state_text(&self) -> String {
+        match self.upgrade_name() {
+            Some(upgrade) => format!("{}, {}", self.condition.display_name(), upgrade),
+            None => self.condition.display_name().to_string(),
+        }
+    }
+
+    /// Whether the shelter is still being built and cannot be used yet
+    pub fn is_under_construction(&self) -> bool {
+        self.construction.is_some()
+    }
+
+    /// A finished lair of the player's own that can still take them in,
+    /// where they rise again after dying
+    pub fn is_home(&self) -> bool {
+        self.built_by_player && self.shelter_type == ShelterType::Lair && self.can_accommodate()
     }
 
     /// Check if this shelter can accommodate another occupant
     pub fn can_accommodate(&self) -> bool {
         self.enterable
+            && !self.is_under_construction()
             && !matches!(self.condition, ShelterCondition::Ruined)
             && self.occupants.len() < self.shelter_type.max_capacity() as usize
     }
@@ -260,6 +505,18 @@ impl Shelter {
             .saturating_sub(self.occupants.len() as u32)
     }
 
+    /// Whether an uninvited vampire is barred from entering
+    pub fn bars_vampires(&self) -> bool {
+        self.inhabited && !self.invited
+    }
+
+    /// Display name, falling back to the shelter type
+    pub fn display_name(&self) -> &str {
+        self.name
+            .as_deref()
+            .unwrap_or_else(|| self.shelter_type.display_name())
+    }
+
     /// Mark this shelter as discovered
     pub fn discover(&mut self) {
         self.discovered = true;
@@ -287,15 +544,17 @@ impl Shelter {
 
         match &self.name {
             Some(name) => format!(
-                "{} ({}): {}% protection, {} occupants",
+                "{} ({}, {}): {}% protection, {} occupants",
                 name,
                 self.shelter_type.display_name(),
+                self.state_text(),
                 protection_pct,
                 occupancy
             ),
             None => format!(
-                "{}: {}% protection, {} occupants",
+                "{} ({}): {}% protection, {} occupants",
                 self.shelter_type.display_name(),
+                self.state_text(),
                 protection_pct,
                 occupancy
             ),
@@ -309,7 +568,7 @@ impl Shelter {
 }
 
 /// Component to track shelter occupancy status for entities
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShelterOccupancy {
     /// ID of the shelter entity this entity is occupying (None if not in shelter)
     pub shelter_id: Option<u32>,
@@ -319,6 +578,10 @@ pub struct ShelterOccupancy {
     pub seeking_shelter: bool,
     /// Last time entity attempted to find shelter
     pub last_shelter_search: f32,
+    /// Inside the shelter's room rather than on its doorstep, out of reach
+    /// of sunlight and of everything outside. Saves load on the doorstep.
+    #[serde(skip)]
+    pub indoors: bool,
 }
 
 impl ShelterOccupancy {
@@ -329,6 +592,7 @@ impl ShelterOccupancy {
             entered_at: 0.0,
             seeking_shelter: false,
             last_shelter_search: 0.0,
+            indoors: false,
         }
     }
 
@@ -348,6 +612,7 @@ impl ShelterOccupancy {
     pub fn leave_shelter(&mut self) {
         self.shelter_id = None;
         self.entered_at = 0.0;
+        self.indoors = false;
     }
 
     /// Start seeking shelter
@@ -0,0 +1,89 @@
+//! Territory components
+//!
+//! Circular stretches of land the player can take for their own. A territory
+//! is captured by clearing it of hostiles and holding it for a few in-game
+//! hours; every captured territory then yields blood each day.
+
+use super::entities::Position;
+use serde::{Deserialize, Serialize};
+
+/// A named circle of land that can be captured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Territory {
+    pub name: String,
+    pub center: Position,
+    pub radius: f32,
+    /// Blood granted to the player each day once captured
+    pub blood_income: f32,
+    pub captured: bool,
+    /// In-game hours the player has held the territory towards capture
+    pub hold_hours: f32,
+}
+
+impl Territory {
+    /// In-game hours a cleared territory must be held before it is captured
+    pub const HOURS_TO_CAPTURE: f32 = 3.0;
+
+    pub fn new(name: &str, x: f32, y: f32, radius: f32, blood_income: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            center: Position::new(x, y),
+            radius,
+            blood_income,
+            captured: false,
+            hold_hours: 0.0,
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.center.distance_to(position) <= self.radius
+    }
+
+    /// How far the current hold has gone towards capture (0.0 to 1.0)
+    pub fn capture_progress(&self) -> f32 {
+        if self.captured {
+            1.0
+        } else {
+            (self.hold_hours / Self::HOURS_TO_CAPTURE).min(1.0)
+        }
+    }
+}
+
+/// All territories in the world and the day their income was last paid
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TerritoryManager {
+    pub territories: Vec<Territory>,
+    pub last_income_day: u32,
+}
+
+impl TerritoryManager {
+    pub fn new(territories: Vec<Territory>) -> Self {
+        Self {
+            territories,
+            last_income_day: 0,
+        }
+    }
+
+    /// Index of the territory containing a position
+    pub fn territory_at(&self, position: &Position) -> Option<usize> {
+        self.territories
+            .iter()
+            .position(|territory| territory.contains(position))
+    }
+
+    pub fn captured_count(&self) -> usize {
+        self.territories
+            .iter()
+            .filter(|territory| territory.captured)
+            .count()
+    }
+
+    /// Blood paid each day by all captured territories together
+    pub fn daily_income(&self) -> f32 {
+        self.territories
+            .iter()
+            .filter(|territory| territory.captured)
+            .map(|territory| territory.blood_income)
+            .sum()
+    }
+}
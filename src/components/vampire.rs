@@ -10,6 +10,11 @@ pub struct BloodMeter {
     pub current: f32,
     pub maximum: f32,
     pub drain_rate: f32,
+    #[serde(default)]
+    pub feeding: FeedingState,
+    /// Overflow from feeding at a full meter, collecting toward the next vial
+    #[serde(default)]
+    pub bottling: f32,
 }
 
 impl BloodMeter {
@@ -18,6 +23,8 @@ impl BloodMeter {
             current: maximum * 0.5, // Start at half blood
             maximum,
             drain_rate: 1.0,
+            feeding: FeedingState::default(),
+            bottling: 0.0,
         }
     }
 
@@ -30,8 +37,11 @@ impl BloodMeter {
         }
     }
 
-    pub fn add_blood(&mut self, amount: f32) {
-        self.current = (self.current + amount).min(self.maximum);
+    /// Add blood up to the maximum, returning the overflow that did not fit
+    pub fn add_blood(&mut self, amount: f32) -> f32 {
+        let total = self.current + amount;
+        self.current = total.min(self.maximum);
+        (total - self.maximum).max(0.0)
     }
 
     pub fn is_starving(&self) -> bool {
@@ -43,6 +53,90 @@ impl BloodMeter {
     }
 }
 
+/// Feeding pace tracking: a short cooldown between feedings and satiation
+/// that builds with rapid consecutive feedings and reduces blood gained
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedingState {
+    pub last_feed_time: Option<f32>,
+    /// 0.0 (hungry) to 1.0 (gorged)
+    pub satiation: f32,
+}
+
+impl FeedingState {
+    /// Seconds between feedings
+    pub const COOLDOWN: f32 = 2.0;
+    /// Satiation added by each feeding
+    pub const SATIATION_PER_FEED: f32 = 0.3;
+    /// Satiation lost per second
+    pub const SATIATION_DECAY: f32 = 0.02;
+    /// Smallest fraction of blood gained when fully gorged
+    pub const MIN_GAIN: f32 = 0.25;
+
+    pub fn cooldown_remaining(&self, game_time: f32) -> f32 {
+        self.last_feed_time
+            .map_or(0.0, |last| (Self::COOLDOWN - (game_time - last)).max(0.0))
+    }
+
+    pub fn can_feed(&self, game_time: f32) -> bool {
+        self.cooldown_remaining(game_time) <= 0.0
+    }
+
+    /// Fraction of a meal's blood actually absorbed at the current satiation
+    pub fn gain_multiplier(&self) -> f32 {
+        (1.0 - self.satiation).max(Self::MIN_GAIN)
+    }
+
+    pub fn record_feed(&mut self, game_time: f32) {
+        self.last_feed_time = Some(game_time);
+        self.satiation = (self.satiation + Self::SATIATION_PER_FEED).min(1.0);
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.satiation = (self.satiation - Self::SATIATION_DECAY * delta_time).max(0.0);
+    }
+}
+
+/// Blood left in a body; it drains away the longer the body lies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Corpse {
+    pub blood: f32,
+    pub initial_blood: f32,
+}
+
+impl Corpse {
+    /// Seconds for a corpse to lose half its remaining blood
+    pub const BLOOD_HALF_LIFE: f32 = 20.0;
+    /// Below this a corpse is considered dry
+    pub const DRY_THRESHOLD: f32 = 1.0;
+
+    pub fn new(blood: f32) -> Self {
+        Self {
+            blood,
+            initial_blood: blood,
+        }
+    }
+
+    pub fn decay(&mut self, delta_time: f32) {
+        self.blood *= 0.5_f32.powf(delta_time / Self::BLOOD_HALF_LIFE);
+        if self.blood < Self::DRY_THRESHOLD {
+            self.blood = 0.0;
+        }
+    }
+
+    pub fn is_dry(&self) -> bool {
+        self.blood <= 0.0
+    }
+
+    /// Remaining blood as a fraction of what the body started with
+    pub fn freshness(&self) -> f32 {
+        if self.initial_blood > 0.0 {
+            self.blood / self.initial_blood
+        } else {
+            0.0
+        }
+    }
+}
+
 /// Vampire abilities component
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VampireAbilities {
@@ -50,6 +144,8 @@ pub struct VampireAbilities {
     pub speed: f32,
     pub blood_sense: f32,
     pub shadow_movement: f32,
+    #[serde(default)]
+    pub ability_state: AbilityState,
 }
 
 impl Default for VampireAbilities {
@@ -59,8 +155,266 @@ impl Default for VampireAbilities {
             speed: 1.0,
             blood_sense: 0.0,
             shadow_movement: 0.0,
+            ability_state: AbilityState::default(),
+        }
+    }
+}
+
+impl VampireAbilities {
+    /// Distance (world units) at which blood sense reveals hidden creatures
+    pub fn blood_sense_range(&self) -> f32 {
+        150.0 + self.blood_sense * 100.0
+    }
+
+    /// Whether the vampire's blood sense is developed enough for an ability
+    pub fn has_unlocked(&self, ability: SpecialAbility) -> bool {
+        self.blood_sense >= ability.required_blood_sense()
+    }
+}
+
+/// Special abilities the player can activate for a blood cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SpecialAbility {
+    ShadowDash,
+    BloodSensePulse,
+    BatForm,
+    SpectralVision,
+    /// Send out a crow to pilot as a scout
+    CrowScout,
+    /// Toggle scent trails toward living prey, draining blood while on
+    BloodSight,
+    /// Hurl a lance of blood at the cursor
+    BloodLance,
+}
+
+impl SpecialAbility {
+    pub const ALL: [SpecialAbility; 7] = [
+        SpecialAbility::ShadowDash,
+        SpecialAbility::BloodSensePulse,
+        SpecialAbility::BatForm,
+        SpecialAbility::SpectralVision,
+        SpecialAbility::CrowScout,
+        SpecialAbility::BloodSight,
+        SpecialAbility::BloodLance,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SpecialAbility::ShadowDash => "Shadow Dash",
+            SpecialAbility::BloodSensePulse => "Blood Sense",
+            SpecialAbility::BatForm => "Bat Form",
+            SpecialAbility::SpectralVision => "Spectral Vision",
+            SpecialAbility::CrowScout => "Crow Scout",
+            SpecialAbility::BloodSight => "Blood Sight",
+            SpecialAbility::BloodLance => "Blood Lance",
+        }
+    }
+
+    /// Blood consumed on activation
+    pub fn blood_cost(&self) -> f32 {
+        match self {
+            SpecialAbility::ShadowDash => 8.0,
+            SpecialAbility::BloodSensePulse => 5.0,
+            SpecialAbility::BatForm => 15.0,
+            SpecialAbility::SpectralVision => 25.0,
+            SpecialAbility::CrowScout => 10.0,
+            SpecialAbility::BloodSight => 3.0,
+            SpecialAbility::BloodLance => 6.0,
+        }
+    }
+
+    /// Blood consumed each second while a toggled ability stays on
+    pub fn upkeep(&self) -> f32 {
+        match self {
+            SpecialAbility::BloodSight => 0.5,
+            _ => 0.0,
+        }
+    }
+
+    /// Seconds before the ability can be used again
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            SpecialAbility::ShadowDash => 3.0,
+            SpecialAbility::BloodSensePulse => 10.0,
+            SpecialAbility::BatForm => 20.0,
+            SpecialAbility::SpectralVision => 30.0,
+            SpecialAbility::CrowScout => 25.0,
+            SpecialAbility::BloodSight => 1.0,
+            SpecialAbility::BloodLance => 1.5,
         }
     }
+
+    /// How long the effect lasts (zero for instant abilities)
+    pub fn duration(&self) -> f32 {
+        match self {
+            SpecialAbility::ShadowDash => 0.0,
+            SpecialAbility::BloodSensePulse => 5.0,
+            SpecialAbility::BatForm => 6.0,
+            SpecialAbility::SpectralVision => 4.0,
+            SpecialAbility::CrowScout => 12.0,
+            // Lasts until switched off or the blood runs out
+            SpecialAbility::BloodSight => 0.0,
+            SpecialAbility::BloodLance => 0.0,
+        }
+    }
+
+    /// Blood sense the vampire must have developed before the ability can be used
+    pub fn required_blood_sense(&self) -> f32 {
+        match self {
+            SpecialAbility::SpectralVision => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            SpecialAbility::ShadowDash => 0,
+            SpecialAbility::BloodSensePulse => 1,
+            SpecialAbility::BatForm => 2,
+            SpecialAbility::SpectralVision => 3,
+            SpecialAbility::CrowScout => 4,
+            SpecialAbility::BloodSight => 5,
+            SpecialAbility::BloodLance => 6,
+        }
+    }
+}
+
+/// Cooldowns and active effects of special abilities
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AbilityState {
+    #[serde(deserialize_with = "deserialize_cooldowns")]
+    cooldowns: [f32; SpecialAbility::ALL.len()],
+    pub sense_pulse_remaining: f32,
+    pub bat_form_remaining: f32,
+    #[serde(default)]
+    pub spectral_remaining: f32,
+    /// Blood Sight stays on until toggled off
+    #[serde(default)]
+    pub blood_sight: bool,
+    /// Creeping along with the sneak key held
+    #[serde(default)]
+    pub sneaking: bool,
+    /// Strengthened by a blood moon, which is reckoned afresh every frame
+    #[serde(skip)]
+    pub empowered: bool,
+}
+
+impl AbilityState {
+    /// Speed multiplier while in bat form
+    pub const BAT_FORM_SPEED: f32 = 1.6;
+    /// Hitbox scale while in bat form
+    pub const BAT_FORM_HITBOX: f32 = 0.5;
+    /// How much stronger, longer lasting and quicker to recover abilities
+    /// are under a blood moon
+    pub const EMPOWERED_POWER: f32 = 1.5;
+
+    pub fn cooldown_remaining(&self, ability: SpecialAbility) -> f32 {
+        self.cooldowns[ability.index()]
+    }
+
+    pub fn is_ready(&self, ability: SpecialAbility) -> bool {
+        self.cooldown_remaining(ability) <= 0.0
+    }
+
+    /// Scale on ability strength, duration and recovery
+    pub fn power(&self) -> f32 {
+        if self.empowered {
+            Self::EMPOWERED_POWER
+        } else {
+            1.0
+        }
+    }
+
+    /// Seconds an ability takes to recover once used
+    pub fn cooldown(&self, ability: SpecialAbility) -> f32 {
+        ability.cooldown() / self.power()
+    }
+
+    /// Seconds an ability's effect lasts once used
+    pub fn duration(&self, ability: SpecialAbility) -> f32 {
+        ability.duration() * self.power()
+    }
+
+    /// Put an ability on cooldown and start its effect
+    pub fn trigger(&mut self, ability: SpecialAbility) {
+        self.cooldowns[ability.index()] = self.cooldown(ability);
+        let duration = self.duration(ability);
+        match ability {
+            SpecialAbility::ShadowDash => {}
+            SpecialAbility::BloodSensePulse => self.sense_pulse_remaining = duration,
+            SpecialAbility::BatForm => self.bat_form_remaining = duration,
+            SpecialAbility::SpectralVision => self.spectral_remaining = duration,
+            // The crow itself keeps track of how long it stays out
+            SpecialAbility::CrowScout => {}
+            SpecialAbility::BloodSight => self.blood_sight = true,
+            // The lance flies on its own once thrown (see `ProjectileSystem`)
+            SpecialAbility::BloodLance => {}
+        }
+    }
+
+    pub fn is_bat_form(&self) -> bool {
+        self.bat_form_remaining > 0.0
+    }
+
+    pub fn is_sensing(&self) -> bool {
+        self.sense_pulse_remaining > 0.0
+    }
+
+    pub fn is_spectral(&self) -> bool {
+        self.spectral_remaining > 0.0
+    }
+
+    pub fn is_blood_sight(&self) -> bool {
+        self.blood_sight
+    }
+
+    /// Movement speed multiplier from active effects
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_bat_form() {
+            Self::BAT_FORM_SPEED
+        } else if self.is_sneaking() {
+            crate::systems::stealth::SNEAK_SPEED
+        } else {
+            1.0
+        }
+    }
+
+    /// Sneaking, which a bat cannot do
+    pub fn is_sneaking(&self) -> bool {
+        self.sneaking && !self.is_bat_form()
+    }
+
+    /// Hitbox scale from active effects
+    pub fn hitbox_scale(&self) -> f32 {
+        if self.is_bat_form() {
+            Self::BAT_FORM_HITBOX
+        } else {
+            1.0
+        }
+    }
+
+    /// Count down cooldowns and effect timers
+    pub fn update(&mut self, delta_time: f32) {
+        for cooldown in &mut self.cooldowns {
+            *cooldown = (*cooldown - delta_time).max(0.0);
+        }
+        self.sense_pulse_remaining = (self.sense_pulse_remaining - delta_time).max(0.0);
+        self.bat_form_remaining = (self.bat_form_remaining - delta_time).max(0.0);
+        self.spectral_remaining = (self.spectral_remaining - delta_time).max(0.0);
+    }
+}
+
+/// Read saved cooldowns, which older saves wrote before every ability existed;
+/// abilities missing from the save start ready
+fn deserialize_cooldowns<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[f32; SpecialAbility::ALL.len()], D::Error> {
+    let saved = Vec::<f32>::deserialize(deserializer)?;
+    let mut cooldowns = [0.0; SpecialAbility::ALL.len()];
+    for (cooldown, saved) in cooldowns.iter_mut().zip(saved) {
+        *cooldown = saved;
+    }
+    Ok(cooldowns)
 }
 
 /// Sunlight vulnerability component
@@ -0,0 +1,327 @@
+//! Console Module
+//!
+//! The developer console, opened in game with the ~ key. Each line typed is
+//! parsed into a `ConsoleCommand` and run against the game: spawning
+//! creatures, setting the hour, topping up blood, teleporting the player
+//! and toggling god mode. Results and errors are kept in the console's own
+//! scrollback rather than the message log. Only debug builds can open the
+//! console, and a run it has changed is no longer signed for leaderboards.
+
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::WorldSystem;
+use macroquad::rand;
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// Whether this build lets the console be opened
+pub const CONSOLE_ENABLED: bool = cfg!(debug_assertions);
+
+/// Longest line that can be typed into the console
+pub const CONSOLE_LINE_LEN: usize = 80;
+
+/// Scrollback lines the console keeps
+const SCROLLBACK: usize = 12;
+
+/// Most creatures one spawn command may create
+const MAX_SPAWN: u32 = 50;
+
+/// Spawned creatures land this far from the player, in a random direction
+const SPAWN_DISTANCE: (f32, f32) = (80.0, 160.0);
+
+/// Creatures the `spawn` command knows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnKind {
+    Infected,
+    /// An animal of whatever species suits the ground
+    Animal,
+    Species(Species),
+}
+
+impl SpawnKind {
+    fn parse(word: &str) -> Result<Self, ConsoleError> {
+        Ok(match word {
+            "infected" => SpawnKind::Infected,
+            "animal" => SpawnKind::Animal,
+            "rat" => SpawnKind::Species(Species::Rat),
+            "deer" => SpawnKind::Species(Species::Deer),
+            "wolf" => SpawnKind::Species(Species::Wolf),
+            other => return Err(ConsoleError::UnknownCreature(other.to_string())),
+        })
+    }
+}
+
+/// A parsed console line
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleCommand {
+    Help,
+    Spawn { kind: SpawnKind, count: u32 },
+    SetTime(f32),
+    GiveBlood(f32),
+    Teleport(Position),
+    ToggleGodMode,
+}
+
+/// Why a console line could not be run
+#[derive(Debug, Error, PartialEq)]
+pub enum ConsoleError {
+    #[error("unknown command '{0}' (try 'help')")]
+    UnknownCommand(String),
+    #[error("{0} needs {1}")]
+    MissingArgument(&'static str, &'static str),
+    #[error("'{0}' is not a number")]
+    InvalidNumber(String),
+    #[error("no creature called '{0}' (infected, animal, rat, deer, wolf)")]
+    UnknownCreature(String),
+    #[error("there is no player to do that to")]
+    NoPlayer,
+}
+
+impl ConsoleCommand {
+    /// One line of usage for every command
+    pub const HELP: [&'static str; 6] = [
+        "spawn <infected|animal|rat|deer|wolf> [count]",
+        "set_time <hour 0-24>",
+        "give_blood <amount>",
+        "tp <x> <y>",
+        "toggle_godmode",
+        "help",
+    ];
+
+    /// Parse a line such as `spawn infected 5` or `tp 800 700`
+    pub fn parse(line: &str) -> Result<Self, ConsoleError> {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default().to_lowercase();
+
+        Ok(match name.as_str() {
+            "help" => ConsoleCommand::Help,
+            "spawn" => {
+                let kind = SpawnKind::parse(
+                    &words
+                        .next()
+                        .ok_or(ConsoleError::MissingArgument("spawn", "a creature"))?
+                        .to_lowercase(),
+                )?;
+                let count = match words.next() {
+                    Some(word) => word
+                        .parse::<u32>()
+                        .map_err(|_| ConsoleError::InvalidNumber(word.to_string()))?,
+                    None => 1,
+                };
+                ConsoleCommand::Spawn {
+                    kind,
+                    count: count.clamp(1, MAX_SPAWN),
+                }
+            }
+            "set_time" => {
+                ConsoleCommand::SetTime(Self::number(words.next(), "set_time", "an hour")?)
+            }
+            "give_blood" => {
+                ConsoleCommand::GiveBlood(Self::number(words.next(), "give_blood", "an amount")?)
+            }
+            "tp" => {
+                let x = Self::number(words.next(), "tp", "x and y")?;
+                let y = Self::number(words.next(), "tp", "x and y")?;
+                ConsoleCommand::Teleport(Position::new(x, y))
+            }
+            "toggle_godmode" | "god" => ConsoleCommand::ToggleGodMode,
+            other => return Err(ConsoleError::UnknownCommand(other.to_string())),
+        })
+    }
+
+    /// A word read as a number for `command`, which needs `what` there
+    fn number(
+        word: Option<&str>,
+        command: &'static str,
+        what: &'static str,
+    ) -> Result<f32, ConsoleError> {
+        let word = word.ok_or(ConsoleError::MissingArgument(command, what))?;
+        word.parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| ConsoleError::InvalidNumber(word.to_string()))
+    }
+
+    /// Run the command against the game, returning what happened
+    pub fn execute(self, game: &mut GameState) -> Result<String, ConsoleError> {
+        let player_position = EntityFinder::by_id(&game.entities, game.player_id)
+            .map(|player| player.position)
+            .ok_or(ConsoleError::NoPlayer)?;
+
+        Ok(match self {
+            ConsoleCommand::Help => ConsoleCommand::HELP.join(" | "),
+            ConsoleCommand::Spawn { kind, count } => {
+                for _ in 0..count {
+                    let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+                    let distance = rand::gen_range(SPAWN_DISTANCE.0, SPAWN_DISTANCE.1);
+                    let Position { x, y } = game.world_bounds.clamp(Position::new(
+                        player_position.x + angle.cos() * distance,
+                        player_position.y + angle.sin() * distance,
+                    ));
+                    match kind {
+                        SpawnKind::Infected => {
+                            game.spawn_entity(EntityType::HostileInfected, x, y);
+                        }
+                        SpawnKind::Animal => {
+                            game.spawn_entity(EntityType::Animal, x, y);
+                        }
+                        SpawnKind::Species(species) => {
+                            WorldSystem::spawn_animal(
+                                &mut game.entities,
+                                &mut game.entity_ids,
+                                species,
+                                x,
+                                y,
+                            );
+                        }
+                    }
+                }
+                format!("Spawned {} x {:?}", count, kind)
+            }
+            ConsoleCommand::SetTime(hour) => {
+                game.time.set_time(hour);
+                format!("Time set to {:.1}h", game.time.current_time())
+            }
+            ConsoleCommand::GiveBlood(amount) => {
+                let blood_meter = game
+                    .entities
+                    .iter_mut()
+                    .find(|entity| entity.id == game.player_id)
+                    .and_then(|player| player.blood_meter.as_mut())
+                    .ok_or(ConsoleError::NoPlayer)?;
+                blood_meter.current =
+                    (blood_meter.current + amount).clamp(0.0, blood_meter.maximum);
+                format!(
+                    "Blood now {:.0}/{:.0}",
+                    blood_meter.current, blood_meter.maximum
+                )
+            }
+            ConsoleCommand::Teleport(position) => {
+                // Anywhere on the ground goes: the terrain there is streamed
+                // in, growing the world bounds to take it in
+                WorldSystem::stream_terrain(
+                    &mut game.ground_tiles,
+                    &mut game.terrain_chunks,
+                    &mut game.world_bounds,
+                    position,
+                    game.world_seed,
+                );
+                let target = game.world_bounds.clamp(position);
+                if let Some(player) = game
+                    .entities
+                    .iter_mut()
+                    .find(|entity| entity.id == game.player_id)
+                {
+                    player.position = target;
+                }
+                format!("Teleported to ({:.0}, {:.0})", target.x, target.y)
+            }
+            ConsoleCommand::ToggleGodMode => {
+                game.god_mode = !game.god_mode;
+                format!("God mode {}", if game.god_mode { "on" } else { "off" })
+            }
+        })
+    }
+}
+
+/// The console's open state, the line being typed and its scrollback
+#[derive(Debug, Clone, Default)]
+pub struct DevConsole {
+    pub open: bool,
+    /// The line being typed, mirrored from the input handler for drawing
+    pub input: String,
+    scrollback: VecDeque<String>,
+}
+
+impl DevConsole {
+    /// Earlier commands and their results, oldest first
+    pub fn scrollback(&self) -> impl Iterator<Item = &str> {
+        self.scrollback.iter().map(String::as_str)
+    }
+
+    pub fn print(&mut self, line: impl Into<String>) {
+        if self.scrollback.len() == SCROLLBACK {
+            self.scrollback.pop_front();
+        }
+        self.scrollback.push_back(line.into());
+    }
+
+    /// Parse and run one typed line, echoing it and its result
+    pub fn run(game: &mut GameState, line: &str) {
+        game.console.print(format!("> {}", line));
+        let result = ConsoleCommand::parse(line).and_then(|command| {
+            if command != ConsoleCommand::Help {
+                game.score_ledger.console_used = true;
+            }
+            command.execute(game)
+        });
+        match result {
+            Ok(output) => game.console.print(output),
+            Err(error) => game.console.print(format!("Error: {}", error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::NewGameSettings;
+
+    #[test]
+    fn test_console_parses_and_runs_commands() {
+        assert_eq!(
+            ConsoleCommand::parse("spawn infected 5"),
+            Ok(ConsoleCommand::Spawn {
+                kind: SpawnKind::Infected,
+                count: 5
+            })
+        );
+        assert_eq!(
+            ConsoleCommand::parse("  TP 800 700 "),
+            Ok(ConsoleCommand::Teleport(Position::new(800.0, 700.0)))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("set_time noon"),
+            Err(ConsoleError::InvalidNumber("noon".to_string()))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("give_blood"),
+            Err(ConsoleError::MissingArgument("give_blood", "an amount"))
+        );
+        assert!(matches!(
+            ConsoleCommand::parse("spawn dragon"),
+            Err(ConsoleError::UnknownCreature(_))
+        ));
+        assert!(matches!(
+            ConsoleCommand::parse("fly"),
+            Err(ConsoleError::UnknownCommand(_))
+        ));
+
+        // Asking for help leaves the run fit for the leaderboard; cheats do not
+        let mut game = GameState::new_game(NewGameSettings::default());
+        DevConsole::run(&mut game, "help");
+        assert!(!game.score_ledger.console_used);
+        let before = game.entities.len();
+        DevConsole::run(&mut game, "spawn wolf 3");
+        assert!(game.score_ledger.console_used);
+        assert_eq!(game.entities.len(), before + 3);
+        DevConsole::run(&mut game, "tp 800 700");
+        let player = EntityFinder::by_id(&game.entities, game.player_id).unwrap();
+        assert_eq!(player.position, Position::new(800.0, 700.0));
+        // Beyond the starting area too, though never into the sky
+        DevConsole::run(&mut game, "tp 5000 100");
+        let player = EntityFinder::by_id(&game.entities, game.player_id).unwrap();
+        assert_eq!(
+            player.position,
+            Position::new(5000.0, WorldBounds::GROUND_LEVEL)
+        );
+        assert!(game.world_bounds.max_x > 5000.0);
+        DevConsole::run(&mut game, "toggle_godmode");
+        assert!(game.god_mode);
+        DevConsole::run(&mut game, "fly");
+        assert_eq!(
+            game.console.scrollback().last(),
+            Some("Error: unknown command 'fly' (try 'help')")
+        );
+    }
+}
@@ -4,18 +4,134 @@
 //! The GameState is now a lean coordinator that delegates specific responsibilities
 //! to focused systems, following the Single Responsibility Principle.
 
+use crate::achievements::{AchievementLog, AchievementTracker, RunStats};
+use crate::audio::{AudioSettings, SoundCue, SoundEffect};
 use crate::components::*;
+use crate::console::DevConsole;
+use crate::input::{InputAction, InputHandler};
+use crate::leaderboard::{ScoreClaims, ScoreExport, ScoreLedger};
+use crate::profiler::{ProfileSection, Profiler};
+use crate::save::{Milestone, MilestoneTracker, SaveData, SaveManager, SaveReason};
+use crate::settings::{Difficulty, FpsMonitor, NewGameSettings, VideoSettings, WeaknessRules};
+use crate::systems::status::RECRUIT_CHARM_SECONDS;
 use crate::systems::*;
-use crate::InputHandler;
 use macroquad::prelude::*;
 use std::collections::HashMap;
+use std::time::Instant;
+
+/// Infected feeding on prey within this distance are heard by the player
+const HUNT_HEARING_RANGE: f32 = 500.0;
+
+/// Hostiles within this distance make sneaking past them worth practice
+const STEALTH_PRACTICE_RANGE: f32 = 200.0;
+
+/// Holding feed this long drains the victim instead of killing them
+const DRAIN_HOLD_SECONDS: f32 = 0.6;
+
+/// Anything that moved farther than this in one step jumped there (a dash,
+/// a load) and is drawn where it landed instead of sliding across
+const MAX_BLEND_DISTANCE: f32 = 50.0;
+
+/// Options offered on the game over screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOverChoice {
+    Restart,
+    Quit,
+}
+
+impl GameOverChoice {
+    pub const ALL: [GameOverChoice; 2] = [GameOverChoice::Restart, GameOverChoice::Quit];
+
+    /// Key of the option's label in the translation tables
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            GameOverChoice::Restart => "game_over.rise_again",
+            GameOverChoice::Quit => "game_over.return_to_title",
+        }
+    }
+}
+
+/// Pages of the codex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CodexPage {
+    /// Visions recovered in dreams
+    #[default]
+    Lore,
+    /// What the player has learned about creatures
+    Bestiary,
+}
+
+impl CodexPage {
+    pub fn toggled(&self) -> Self {
+        match self {
+            CodexPage::Lore => CodexPage::Bestiary,
+            CodexPage::Bestiary => CodexPage::Lore,
+        }
+    }
+}
+
+/// The player has met their final death. Systems stay frozen until the main
+/// loop acts on the chosen option.
+#[derive(Debug, Clone)]
+pub struct GameOver {
+    pub score: SurvivalScore,
+    pub selected: GameOverChoice,
+    /// Seconds since death; the screen fades in and ignores input until
+    /// `INPUT_DELAY` has passed so a held key does not skip it
+    pub elapsed: f32,
+    /// Confirmed choice waiting for the main loop
+    pub chosen: Option<GameOverChoice>,
+}
+
+impl GameOver {
+    pub const FADE_IN: f32 = 1.5;
+    pub const INPUT_DELAY: f32 = 1.0;
+
+    pub fn new(score: SurvivalScore) -> Self {
+        Self {
+            score,
+            selected: GameOverChoice::Restart,
+            elapsed: 0.0,
+            chosen: None,
+        }
+    }
+
+    /// How far the screen has faded in (0.0 to 1.0)
+    pub fn fade(&self) -> f32 {
+        (self.elapsed / Self::FADE_IN).min(1.0)
+    }
+
+    fn update(&mut self, input_handler: &InputHandler, delta_time: f32) {
+        self.elapsed += delta_time;
+        if self.elapsed < Self::INPUT_DELAY {
+            return;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::MenuUp)
+            || input_handler.is_action_just_pressed(InputAction::MenuDown)
+        {
+            self.selected = match self.selected {
+                GameOverChoice::Restart => GameOverChoice::Quit,
+                GameOverChoice::Quit => GameOverChoice::Restart,
+            };
+        }
+        if input_handler.is_action_just_pressed(InputAction::Interact)
+            || input_handler.is_key_just_pressed(KeyCode::Enter)
+        {
+            self.chosen = Some(self.selected);
+        }
+    }
+}
 
 /// Core game state that coordinates all systems and manages game data
 pub struct GameState {
     // Entity management
     pub entities: Vec<GameEntity>,
-    pub next_entity_id: u32,
+    /// Hands out entity IDs and takes back those of despawned entities
+    pub entity_ids: EntityAllocator,
     pub player_id: u32,
+    /// Proximity index over `entities`, rebuilt at the start of each frame
+    pub spatial_grid: SpatialGrid,
 
     // Game systems
     pub time: TimeSystem,
@@ -25,56 +141,308 @@ pub struct GameState {
     pub clans: HashMap<String, Clan>,
     pub camera_x: f32,
     pub camera_y: f32,
+    pub camera_rig: CameraRig,
+    /// Photo mode: the world is frozen and the camera moves by hand
+    pub photo_mode: Option<PhotoMode>,
+    /// Entity positions and camera at the start of the latest simulation
+    /// step, blended toward the current ones when drawing between steps
+    pub previous_positions: HashMap<u32, Position>,
+    pub previous_camera: (f32, f32),
+    /// How far from the previous step toward the current one to draw
+    pub render_blend: f32,
     pub phase_objectives: Vec<String>,
     pub completed_objectives: Vec<String>,
+    /// In-game day each objective was completed on
+    pub objective_completion_days: HashMap<String, u32>,
     pub game_time: f32,
     pub kills: u32,
     pub feeding_count: u32,
+    pub tribute_stockpile: TributeStockpile,
+    pub last_tribute_day: u32,
 
     // Environment
     pub stars: Vec<Star>,
     pub moon: Moon,
-    pub blood_particles: Vec<BloodParticle>,
+    /// Special nights such as the blood moon, and the banner announcing them
+    pub calendar: Calendar,
+    pub particles: ParticlePool,
+    /// Damage numbers, recoil and screen shake from recent blows
+    pub hit_effects: HitEffects,
+    /// Blood dripped and splashed by the wounded
+    pub blood_trails: BloodTrails,
+    /// Speech bubbles above creatures that just spoke up
+    pub barks: Barks,
+    /// How long each body has lain dead, to clear it once it decays
+    pub decay: Decay,
+    /// Blood lances and other missiles in flight
+    pub projectiles: Vec<Projectile>,
+    /// Creature under the mouse cursor, outlined and described on screen
+    pub hovered_entity: Option<u32>,
     pub ground_tiles: Vec<GroundTile>,
+    /// Chunks whose tiles are currently in `ground_tiles`
+    pub terrain_chunks: TerrainChunks,
+    /// How far the world has been streamed out; movement stays inside it
+    pub world_bounds: WorldBounds,
+    /// Where creatures can walk, kept until a shelter goes up or comes down
+    /// or the world grows
+    pub nav_grid: NavGrid,
+    /// Seed the world was laid out from; tiles streamed in later use it too
+    pub world_seed: u64,
+    pub signposts: Vec<Signpost>,
+    pub hazards: Vec<HazardZone>,
+    pub territories: TerritoryManager,
+    pub fog_of_war: FogOfWar,
+    pub spawn_director: SpawnDirector,
+
+    // Travel
+    pub waypoint: Option<Waypoint>,
+    pub auto_walk: Option<AutoWalk>,
+
+    /// The room the player has stepped into, while inside a building or cave
+    pub interior: Option<InteriorScene>,
+
+    // Crow scouting
+    pub crow_scout: Option<CrowScout>,
+    pub scout_marks: Vec<ScoutMark>,
+
+    /// Where the player means to build, while in build mode
+    pub blueprint: Option<Blueprint>,
+
+    // Combat feedback (recent hits, consumed by rendering and objectives)
+    pub damage_events: Vec<DamageEvent>,
+    /// Things that happened, delivered to interested systems next frame
+    pub events: EventBus,
 
     // Debug message log
     pub debug_messages: Vec<String>,
+    /// Developer console opened with ~
+    pub console: DevConsole,
+    /// Keeps the player's health and blood full, from the console
+    pub god_mode: bool,
 
     // UI state
     pub paused: bool,
     pub show_clan_menu: bool,
+    pub selected_clan: usize,
     pub show_legend: bool,
+    /// Ability tooltips are shown while the info key is held
+    pub show_ability_tooltips: bool,
     pub show_quick_start: bool,
+    pub show_minimap: bool,
+    /// Territory borders on the ground and the minimap
+    pub show_territories: bool,
+    pub show_video_settings: bool,
+    pub show_inventory: bool,
+    /// Row highlighted in the inventory screen
+    pub selected_item: usize,
+    /// Conversation with a clan leader currently on screen
+    pub active_dialogue: Option<ActiveDialogue>,
+    /// Tasks taken on from clan leaders, and those already done
+    pub quests: QuestLog,
+    /// A vision dreamt while resting; freezes the world until it ends
+    pub active_vision: Option<ActiveVision>,
+    /// Scripted scene currently suspending systems or holding the camera
+    pub cutscene: Option<Cutscene>,
+    /// Fights between clans and infected away from the player
+    pub skirmishes: SkirmishTracker,
+    /// Clan member entities standing for each clan's rolls
+    pub clan_roster: ClanRoster,
+    /// Standings, wars and raids between the clans themselves
+    pub clan_relations: ClanRelations,
+    /// Assault called in with an allied clan
+    pub assaults: AssaultTracker,
+    /// Escalating hints for objectives the player is stuck on
+    pub objective_hints: HintTracker,
+    pub show_codex: bool,
+    pub codex_page: CodexPage,
+    pub selected_codex_entry: usize,
+    pub show_journal: bool,
+    /// Lines the quest journal is scrolled down by
+    pub journal_scroll: usize,
+    pub show_skill_tree: bool,
+    /// Highlighted skill as (branch, row)
+    pub selected_skill: (usize, usize),
+
+    // Rest, dreams and what they teach
+    pub lore_codex: LoreCodex,
+    /// Creatures seen, fought and read about
+    pub bestiary: Bestiary,
+    /// Seconds the player has rested in a shelter through the current day
+    pub rest_time: f32,
+    /// Sleeping through the day, skipping time until sunset
+    pub hibernation: Option<Hibernation>,
+    /// Seconds the hibernate key has been held
+    pub hibernate_hold: f32,
+    /// Seconds the feed key has been held since it was pressed, until it
+    /// is released or a drain goes through
+    pub feed_hold: Option<f32>,
+    /// Seconds spent sneaking unseen near hostiles, towards the next lesson
+    /// in shadow movement
+    pub stealth_practice: f32,
+    /// Day that last had its chance of a vision
+    pub last_dream_roll_day: Option<u32>,
+    /// Set when the player dies; freezes the world behind the death screen
+    pub game_over: Option<GameOver>,
+
+    // Guided first night, present until its dawn for a guided start
+    pub start_mode: StartMode,
+    pub first_night: Option<FirstNight>,
+
+    /// Vampire weaknesses enforced in this game
+    pub weakness_rules: WeaknessRules,
+    /// Scales blood drain, sunlight damage and infected numbers
+    pub difficulty: Difficulty,
+    /// Name the player gave their vampire
+    pub player_name: String,
+
+    // Video settings and low-FPS detection
+    pub video_settings: VideoSettings,
+    pub fps_monitor: FpsMonitor,
+    pub low_spec_suggested: bool,
+    /// Per-system and render timings for the profiling overlay
+    pub profiler: Profiler,
+
+    // Audio: volume levels and sounds triggered this frame
+    pub audio_settings: AudioSettings,
+    pub sound_cues: Vec<SoundCue>,
+
+    // Autosaves: timed plus rotating milestone slots
+    pub save_manager: SaveManager,
+    pub milestone_tracker: MilestoneTracker,
+    /// Saves requested this frame, written by `process_saves`
+    pub pending_saves: Vec<SaveReason>,
+
+    // Leaderboard: the run's signed daily snapshots and, after death, its score
+    pub score_ledger: ScoreLedger,
+    pub pending_score_export: Option<ScoreExport>,
+
+    /// Lifetime stats and achievements, carried across runs
+    pub achievements: AchievementTracker,
 }
 
 impl GameState {
     /// Create a new game state with all systems initialized
     pub fn new() -> Self {
+        Self::new_game(NewGameSettings::default())
+    }
+
+    /// Create a new game with the options chosen on the new game screen
+    pub fn new_game(settings: NewGameSettings) -> Self {
+        let start_mode = settings.start_mode;
+        let score_claims = ScoreClaims::from_settings(&settings);
         let mut state = Self {
             entities: Vec::new(),
-            next_entity_id: 0,
+            entity_ids: EntityAllocator::default(),
             player_id: 0,
+            spatial_grid: SpatialGrid::default(),
             time: TimeSystem::new(),
             phase: GamePhase::SurvivalAndDiscovery,
             clans: HashMap::new(),
             camera_x: 0.0,
             camera_y: 0.0,
+            previous_positions: HashMap::new(),
+            previous_camera: (0.0, 0.0),
+            render_blend: 1.0,
+            camera_rig: CameraRig::default(),
+            photo_mode: None,
             phase_objectives: ObjectivesSystem::get_initial_objectives(
                 &GamePhase::SurvivalAndDiscovery,
             ),
             completed_objectives: Vec::new(),
+            objective_completion_days: HashMap::new(),
             paused: false,
             show_clan_menu: false,
+            selected_clan: 0,
             show_legend: false,
+            show_ability_tooltips: false,
             show_quick_start: true,
+            show_minimap: true,
+            show_territories: true,
+            show_video_settings: false,
+            show_inventory: false,
+            selected_item: 0,
+            active_dialogue: None,
+            quests: QuestLog::default(),
+            cutscene: None,
+            skirmishes: SkirmishTracker::default(),
+            clan_roster: ClanRoster::default(),
+            clan_relations: ClanRelations::default(),
+            assaults: AssaultTracker::default(),
+            objective_hints: HintTracker::default(),
+            active_vision: None,
+            show_codex: false,
+            codex_page: CodexPage::Lore,
+            selected_codex_entry: 0,
+            show_journal: false,
+            journal_scroll: 0,
+            show_skill_tree: false,
+            selected_skill: (0, 0),
+            lore_codex: LoreCodex::default(),
+            bestiary: Bestiary::default(),
+            rest_time: 0.0,
+            hibernation: None,
+            hibernate_hold: 0.0,
+            feed_hold: None,
+            stealth_practice: 0.0,
+            last_dream_roll_day: None,
+            game_over: None,
+            start_mode,
+            first_night: None,
+            weakness_rules: settings.weaknesses,
+            difficulty: settings.difficulty,
+            player_name: settings.player_name,
+            video_settings: VideoSettings::default(),
+            fps_monitor: FpsMonitor::default(),
+            profiler: Profiler::default(),
+            low_spec_suggested: false,
+            audio_settings: AudioSettings::default(),
+            sound_cues: Vec::new(),
+            save_manager: SaveManager::default(),
+            milestone_tracker: MilestoneTracker::new(
+                &GamePhase::SurvivalAndDiscovery,
+                &HashMap::new(),
+                &[],
+            ),
+            pending_saves: Vec::new(),
+            score_ledger: ScoreLedger::new(score_claims),
+            pending_score_export: None,
+            achievements: AchievementTracker::default(),
             game_time: 0.0,
             kills: 0,
             feeding_count: 0,
+            tribute_stockpile: TributeStockpile::default(),
+            last_tribute_day: 0,
             stars: Vec::new(),
             moon: Moon::new(),
-            blood_particles: Vec::new(),
+            calendar: Calendar::default(),
+            particles: ParticlePool::default(),
+            hit_effects: HitEffects::default(),
+            blood_trails: BloodTrails::default(),
+            barks: Barks::default(),
+            decay: Decay::default(),
+            projectiles: Vec::new(),
+            hovered_entity: None,
             ground_tiles: Vec::new(),
+            terrain_chunks: TerrainChunks::default(),
+            world_bounds: WorldBounds::default(),
+            nav_grid: NavGrid::build(&[], &WorldBounds::default(), settings.seed),
+            world_seed: settings.seed,
+            signposts: Vec::new(),
+            hazards: Vec::new(),
+            territories: TerritoryManager::default(),
+            fog_of_war: FogOfWar::new(),
+            spawn_director: SpawnDirector::new(settings.max_hostiles),
+            waypoint: None,
+            auto_walk: None,
+            interior: None,
+            crow_scout: None,
+            blueprint: None,
+            scout_marks: Vec::new(),
+            damage_events: Vec::new(),
+            events: EventBus::default(),
             debug_messages: Vec::new(),
+            console: DevConsole::default(),
+            god_mode: false,
         };
 
         // Initialize the world using the world system
@@ -84,7 +452,28 @@ impl GameState {
             &mut state.stars,
             &mut state.moon,
             &mut state.ground_tiles,
-            &mut state.next_entity_id,
+            &mut state.entity_ids,
+            settings.difficulty,
+            settings.seed,
+        );
+        state.terrain_chunks = TerrainChunks::from_tiles(&state.ground_tiles);
+        state.signposts = WorldSystem::place_signposts(&state.entities);
+        state.hazards = WorldSystem::place_hazards();
+        state.territories = WorldSystem::place_territories();
+        if start_mode == StartMode::Guided {
+            // Begin at dusk so the guided night runs its full length
+            state.time.set_time(18.0);
+            state.first_night = Some(OnboardingSystem::begin_guided(
+                &mut state.entities,
+                &mut state.entity_ids,
+                state.player_id,
+            ));
+        }
+        state.milestone_tracker =
+            MilestoneTracker::new(&state.phase, &state.clans, &state.entities);
+        state.achievements = AchievementTracker::new(
+            AchievementLog::load_or_default(&state.save_manager.achievements_path()),
+            state.run_stats(),
         );
 
         state
@@ -92,201 +481,2118 @@ impl GameState {
 
     /// Main update loop that coordinates all systems
     pub fn update(&mut self, input_handler: &InputHandler, delta_time: f32) {
-        // Handle UI input first
-        self.handle_ui_input(input_handler);
+        // Death stops the world; only the game over screen takes input
+        if let Some(game_over) = self.game_over.as_mut() {
+            game_over.update(input_handler, delta_time);
+            return;
+        }
+
+        // So does a vision
+        if let Some(vision) = self.active_vision.as_mut() {
+            if let Some(outcome) = DreamSystem::update(vision, input_handler, delta_time) {
+                self.finish_vision(outcome);
+            }
+            return;
+        }
+
+        // A conversation takes over input; everything else goes to the UI
+        if self.active_dialogue.is_some() {
+            self.handle_dialogue_input(input_handler);
+        } else {
+            self.handle_ui_input(input_handler);
+        }
+
+        // Photo mode freezes the world but the camera still moves
+        if self.photo_mode.is_some() {
+            self.update_photo_camera(input_handler, delta_time);
+        }
 
-        // Skip game updates if paused or showing menus
-        if self.paused || self.show_clan_menu || self.show_legend || self.show_quick_start {
+        let pause = self.system_pause();
+        if pause.is_all() {
             return;
         }
 
-        // Update game time
-        self.game_time += delta_time;
+        // Index entity positions for this frame's proximity queries
+        self.spatial_grid.rebuild(&self.entities);
+
+        // Hand last frame's events to the systems that follow them
+        self.events.advance();
+        self.dispatch_events(delta_time);
 
-        // Entity debugging removed - now handled by in-game debug log
+        // Damage events produced this frame start here
+        let first_new_event = self.damage_events.len();
 
         // System updates in order of dependency
-        self.update_time_system(delta_time);
-        self.update_environment(delta_time);
-        self.update_player_system(input_handler, delta_time);
-        self.update_ai_system(delta_time);
-        self.update_shelter_system(delta_time);
-        self.update_blood_system(delta_time);
-        self.update_objectives_system();
-        self.update_camera();
+        if !pause.ambient {
+            // Game time drives animations, so it keeps running behind dialogue
+            self.game_time += delta_time;
+            self.update_environment(delta_time);
+            PauseSystem::tick_cutscene(&mut self.cutscene, delta_time);
+        }
+        if !pause.simulation {
+            self.update_time_system(delta_time);
+        }
+        if !pause.player_input {
+            let started = Instant::now();
+            if self.interior.is_some() {
+                self.update_interior(input_handler, delta_time);
+            } else if self.crow_scout.is_some() {
+                self.update_crow_scout(input_handler, delta_time);
+            } else if self.blueprint.is_some() {
+                self.update_build_mode(input_handler, delta_time);
+            } else {
+                self.update_player_system(input_handler, delta_time);
+            }
+            self.profiler
+                .record(ProfileSection::Player, started.elapsed());
+        }
+        if !pause.simulation {
+            self.update_fog_of_war(delta_time);
+            AbilitySystem::update(&mut self.entities, delta_time);
+            self.update_projectiles(delta_time);
+        }
+        if !pause.ai {
+            let started = Instant::now();
+            self.update_ai_system(delta_time);
+            BarkSystem::update(
+                &mut self.barks,
+                &self.entities,
+                &self.clans,
+                self.player_id,
+                self.game_time,
+                delta_time,
+            );
+            self.update_skirmishes(delta_time);
+            self.update_clan_warfare(delta_time);
+            self.update_assault(delta_time);
+            self.update_schedules(delta_time);
+            self.profiler.record(ProfileSection::Ai, started.elapsed());
+        }
+        if !(pause.player_input && pause.ai) {
+            // Movement is done for the frame; keep bodies and walls apart
+            CollisionSystem::resolve(&mut self.entities, &self.spatial_grid);
+        }
+        if !pause.simulation {
+            self.apply_god_mode();
+            let started = Instant::now();
+            self.update_shelter_system(delta_time);
+            self.profiler
+                .record(ProfileSection::Shelter, started.elapsed());
+            self.update_construction(delta_time);
+            self.update_rest(delta_time);
+            let started = Instant::now();
+            self.update_blood_system(delta_time);
+            self.profiler
+                .record(ProfileSection::Blood, started.elapsed());
+            self.update_hibernation(first_new_event, delta_time);
+            self.update_combat_events(first_new_event);
+            TrailSystem::update(
+                &mut self.blood_trails,
+                &self.entities,
+                self.player_id,
+                delta_time,
+            );
+            self.update_bestiary();
+            self.update_status_system(delta_time);
+            self.update_tribute_system();
+            self.update_clan_population();
+            self.update_territory_system(delta_time);
+            self.score_ledger
+                .record(self.time.day_count(), self.feeding_count, self.kills);
+            self.update_achievements();
+            self.update_waves(delta_time);
+            self.update_rebellion_system(delta_time);
+            let started = Instant::now();
+            self.update_objectives_system();
+            self.profiler
+                .record(ProfileSection::Objectives, started.elapsed());
+            self.update_onboarding(None);
+            LifecycleSystem::update(
+                &mut self.entities,
+                &mut self.entity_ids,
+                &mut self.decay,
+                self.player_id,
+                self.time.seconds_in(BODY_DECAY_HOURS),
+                delta_time,
+            );
+        }
+        if !pause.ambient {
+            if let Some(first_night) = self.first_night.as_mut() {
+                first_night.tick_banner(delta_time);
+            }
+            self.calendar.tick_banner(delta_time);
+            self.objective_hints.tick_toast(delta_time);
+            self.achievements.update_toasts(delta_time);
+            EffectsSystem::update(&mut self.hit_effects, delta_time);
+            self.update_camera(delta_time);
+            WorldSystem::stream_terrain(
+                &mut self.ground_tiles,
+                &mut self.terrain_chunks,
+                &mut self.world_bounds,
+                Position::new(self.camera_x, self.camera_y),
+                self.world_seed,
+            );
+        }
+        if pause.simulation {
+            return;
+        }
         self.update_phase_progression();
+
+        // A vampire with a lair rises there again instead of dying
+        if self.is_game_over() {
+            if let Some(event) =
+                ConstructionSystem::rise_in_lair(&mut self.entities, self.player_id, self.game_time)
+            {
+                self.add_debug_message(event.get_message());
+            }
+        }
+
+        // A dead vampire is not autosaved
+        if self.is_game_over() {
+            let score = self.get_survival_stats();
+            self.add_debug_message(format!(
+                "{} has met the final death after {} days.",
+                self.player_name, score.days_survived
+            ));
+            self.pending_score_export =
+                ScoreExport::sign(&self.player_name, &score, &self.score_ledger);
+            if self.pending_score_export.is_none() {
+                self.add_debug_message(
+                    "The console was used, so this run is not exported.".to_string(),
+                );
+            }
+            self.achievements.finish_run(&score);
+            self.game_over = Some(GameOver::new(score));
+            return;
+        }
+
+        self.check_milestones();
+        if self.save_manager.tick(delta_time) {
+            self.pending_saves.push(SaveReason::Timed);
+        }
+    }
+
+    /// Remember where everything stands before a simulation step moves it
+    pub fn begin_step(&mut self) {
+        self.previous_positions.clear();
+        self.previous_positions.extend(
+            self.entities
+                .iter()
+                .map(|entity| (entity.id, entity.position)),
+        );
+        self.previous_camera = (self.camera_x, self.camera_y);
+    }
+
+    /// Where to draw an entity, between its last two simulated positions
+    pub fn render_position(&self, entity: &GameEntity) -> Position {
+        match self.previous_positions.get(&entity.id) {
+            Some(previous) => Self::blend(*previous, entity.position, self.render_blend),
+            None => entity.position,
+        }
+    }
+
+    /// Where to centre the camera, between its last two simulated positions
+    pub fn render_camera(&self) -> (f32, f32) {
+        let (x, y) = self.previous_camera;
+        let camera = Self::blend(
+            Position::new(x, y),
+            Position::new(self.camera_x, self.camera_y),
+            self.render_blend,
+        );
+        (camera.x, camera.y)
+    }
+
+    fn blend(previous: Position, current: Position, t: f32) -> Position {
+        if previous.distance_to(&current) > MAX_BLEND_DISTANCE {
+            return current;
+        }
+        Position::new(
+            previous.x + (current.x - previous.x) * t,
+            previous.y + (current.y - previous.y) * t,
+        )
+    }
+
+    /// Systems suspended this frame by menus, dialogue or a cutscene
+    pub fn system_pause(&self) -> SystemPause {
+        let menu_open = self.paused
+            || self.photo_mode.is_some()
+            || self.show_clan_menu
+            || self.show_legend
+            || self.show_quick_start
+            || self.show_video_settings
+            || self.show_inventory
+            || self.show_codex
+            || self.show_journal
+            || self.show_skill_tree;
+        PauseSystem::for_frame(
+            menu_open,
+            self.active_dialogue.is_some(),
+            self.cutscene.as_ref(),
+        )
+    }
+
+    /// Handle UI-related input (menus, pause, etc.)
+    fn handle_ui_input(&mut self, input_handler: &InputHandler) {
+        // Photo mode keeps every key for the camera until it is left,
+        // back to whatever menu it was opened from
+        if self.photo_mode.is_some() {
+            if input_handler.is_action_just_pressed(InputAction::PhotoMode)
+                || input_handler.is_action_just_pressed(InputAction::Pause)
+            {
+                self.photo_mode = None;
+            } else {
+                self.handle_zoom_input(input_handler);
+            }
+            return;
+        }
+        if input_handler.is_action_just_pressed(InputAction::PhotoMode) {
+            self.photo_mode = Some(PhotoMode::default());
+            return;
+        }
+
+        // Menu toggles
+        if input_handler.is_action_just_pressed(InputAction::Pause) {
+            self.paused = !self.paused;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::ClanMenu) {
+            self.show_clan_menu = !self.show_clan_menu;
+        }
+
+        if self.show_clan_menu {
+            self.handle_clan_menu_input(input_handler);
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Legend) {
+            self.show_legend = !self.show_legend;
+        }
+
+        self.show_ability_tooltips = input_handler.is_action_pressed(InputAction::AbilityInfo);
+
+        if input_handler.is_action_just_pressed(InputAction::Help) {
+            self.show_quick_start = !self.show_quick_start;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Map) {
+            self.show_minimap = !self.show_minimap;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::TerritoryBorders) {
+            self.show_territories = !self.show_territories;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::WatchSkirmish) && self.show_minimap {
+            self.watch_latest_skirmish();
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Inventory) {
+            self.show_inventory = !self.show_inventory;
+            self.selected_item = 0;
+        }
+
+        if self.show_inventory {
+            self.handle_inventory_input(input_handler);
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Journal) {
+            self.show_journal = !self.show_journal;
+            self.journal_scroll = 0;
+        }
+
+        if self.show_journal {
+            if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+                // The renderer stops at the last page; this only keeps the
+                // count from running away past the end of the list
+                let rows = self.phase_objectives.len() + self.completed_objectives.len() + 2;
+                self.journal_scroll = (self.journal_scroll + 1).min(rows);
+            }
+            if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+                self.journal_scroll = self.journal_scroll.saturating_sub(1);
+            }
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::SkillTree) {
+            self.show_skill_tree = !self.show_skill_tree;
+        }
+
+        if self.show_skill_tree {
+            self.handle_skill_tree_input(input_handler);
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Codex) {
+            self.show_codex = !self.show_codex;
+            self.selected_codex_entry = 0;
+        }
+
+        if self.show_codex {
+            if input_handler.is_action_just_pressed(InputAction::MenuLeft)
+                || input_handler.is_action_just_pressed(InputAction::MenuRight)
+            {
+                self.codex_page = self.codex_page.toggled();
+                self.selected_codex_entry = 0;
+            }
+            let count = match self.codex_page {
+                CodexPage::Lore => self.lore_codex.unlocked.len(),
+                CodexPage::Bestiary => self.bestiary.entries().len(),
+            };
+            if count > 0 {
+                if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+                    self.selected_codex_entry = (self.selected_codex_entry + 1) % count;
+                }
+                if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+                    self.selected_codex_entry = (self.selected_codex_entry + count - 1) % count;
+                }
+            }
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::VideoSettings) {
+            self.show_video_settings = !self.show_video_settings;
+            self.low_spec_suggested = false;
+        }
+
+        if self.show_video_settings
+            && (input_handler.is_action_just_pressed(InputAction::MenuLeft)
+                || input_handler.is_action_just_pressed(InputAction::MenuRight))
+        {
+            let preset = self.video_settings.preset.toggled();
+            self.video_settings = self.video_settings.with_preset(preset);
+            self.add_debug_message(format!("Graphics preset set to {}", preset.display_name()));
+        }
+
+        if self.show_video_settings {
+            let louder = input_handler.is_action_just_pressed(InputAction::MenuUp);
+            let quieter = input_handler.is_action_just_pressed(InputAction::MenuDown);
+            if louder || quieter {
+                self.audio_settings
+                    .adjust_master(if louder { 0.1 } else { -0.1 });
+                self.add_debug_message(format!(
+                    "Master volume {:.0}%",
+                    self.audio_settings.master * 100.0
+                ));
+            }
+        }
+
+        self.handle_zoom_input(input_handler);
+
+        // Close quick start guide on any movement
+        if self.show_quick_start && input_handler.movement_vector() != (0.0, 0.0) {
+            self.show_quick_start = false;
+        }
+    }
+
+    /// Zoom with +/- or the mouse wheel
+    fn handle_zoom_input(&mut self, input_handler: &InputHandler) {
+        let mut zoom_steps = input_handler.wheel_steps();
+        if input_handler.is_action_just_pressed(InputAction::ZoomIn) {
+            zoom_steps += 1.0;
+        }
+        if input_handler.is_action_just_pressed(InputAction::ZoomOut) {
+            zoom_steps -= 1.0;
+        }
+        if zoom_steps != 0.0 {
+            CameraSystem::zoom_by(&mut self.camera_rig, zoom_steps);
+        }
+    }
+
+    /// Pan the photo mode camera with the movement keys or by dragging
+    /// with the left mouse button
+    fn update_photo_camera(&mut self, input_handler: &InputHandler, delta_time: f32) {
+        let Some(photo_mode) = self.photo_mode.as_mut() else {
+            return;
+        };
+        let dragging = input_handler
+            .is_mouse_down(MouseButton::Left)
+            .then(|| input_handler.mouse_position());
+        let mut camera = Position::new(self.camera_x, self.camera_y);
+        CameraSystem::pan(
+            photo_mode,
+            &mut self.camera_rig,
+            &mut camera,
+            input_handler.movement_vector(),
+            dragging,
+            delta_time,
+        );
+        self.camera_x = camera.x;
+        self.camera_y = camera.y;
+    }
+
+    /// Move through the inventory screen and use the highlighted item
+    fn handle_inventory_input(&mut self, input_handler: &InputHandler) {
+        let entries = self.player_inventory();
+        if entries.is_empty() {
+            return;
+        }
+        self.selected_item = self.selected_item.min(entries.len() - 1);
+
+        if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+            self.selected_item = (self.selected_item + 1) % entries.len();
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+            self.selected_item = (self.selected_item + entries.len() - 1) % entries.len();
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            let (item, _) = entries[self.selected_item];
+            let event =
+                PlayerSystem::use_item(&mut self.entities, &mut self.clans, self.player_id, item);
+            self.add_debug_message(event.get_message());
+            if event == ItemEvent::NotesRead {
+                let learned = BestiarySystem::read_notes(&mut self.bestiary);
+                self.add_debug_message(learned.get_message());
+            }
+        }
+    }
+
+    /// Move between the skill tree's branches and learn the highlighted skill
+    fn handle_skill_tree_input(&mut self, input_handler: &InputHandler) {
+        let branches = SkillBranch::ALL.len();
+        let rows = SkillBranch::ALL[0].skills().len();
+        let (branch, row) = &mut self.selected_skill;
+        if input_handler.is_action_just_pressed(InputAction::MenuRight) {
+            *branch = (*branch + 1) % branches;
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuLeft) {
+            *branch = (*branch + branches - 1) % branches;
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+            *row = (*row + 1) % rows;
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+            *row = (*row + rows - 1) % rows;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            let skill = SkillBranch::ALL[*branch].skills()[*row];
+            let event = ProgressionSystem::learn(&mut self.entities, self.player_id, skill);
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// The player's level, experience and skills
+    pub fn player_progression(&self) -> Option<&Progression> {
+        EntityFinder::by_id(&self.entities, self.player_id)
+            .and_then(|player| player.progression.as_ref())
+    }
+
+    /// Items the player carries, in display order
+    pub fn player_inventory(&self) -> Vec<(ItemKind, u32)> {
+        EntityFinder::by_id(&self.entities, self.player_id)
+            .and_then(|player| player.inventory.as_ref())
+            .map(Inventory::entries)
+            .unwrap_or_default()
+    }
+
+    /// Navigate and choose replies in the active clan leader conversation
+    fn handle_dialogue_input(&mut self, input_handler: &InputHandler) {
+        let Some(dialogue) = self.active_dialogue.as_mut() else {
+            return;
+        };
+
+        if input_handler.is_action_just_pressed(InputAction::Pause) {
+            let leader_name = dialogue.leader_name.clone();
+            self.active_dialogue = None;
+            self.add_debug_message(format!("You walk away from {}.", leader_name));
+            return;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+            DialogueSystem::navigate(dialogue, true);
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+            DialogueSystem::navigate(dialogue, false);
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Interact)
+            || input_handler.is_action_just_pressed(InputAction::Attack)
+        {
+            let clan_name = dialogue.clan_name.clone();
+            let asks_for_work = dialogue
+                .current()
+                .options
+                .get(dialogue.selected_option)
+                .is_some_and(|option| option.effect.asks_for_work);
+            let was_allied = self
+                .clans
+                .get(&clan_name)
+                .is_some_and(|clan| clan.is_allied);
+            let messages = match DialogueSystem::choose(dialogue, &mut self.clans) {
+                DialogueStep::Continue { messages } => messages,
+                DialogueStep::Ended { messages } => {
+                    self.active_dialogue = None;
+                    messages
+                }
+            };
+            if !was_allied
+                && self
+                    .clans
+                    .get(&clan_name)
+                    .is_some_and(|clan| clan.is_allied)
+            {
+                self.events.push(GameEvent::ClanAllied {
+                    clan_name: clan_name.clone(),
+                });
+            }
+            for message in messages {
+                self.add_debug_message(message);
+            }
+            if asks_for_work {
+                let event = QuestSystem::request(&mut self.quests, &self.clans, &clan_name);
+                self.add_debug_message(event.get_message());
+            }
+            self.check_milestones();
+        }
+    }
+
+    /// Queue a milestone autosave for anything pivotal that happened since the last check
+    fn check_milestones(&mut self) {
+        let milestones = self
+            .milestone_tracker
+            .observe(&self.phase, &self.clans, &self.entities);
+        for milestone in milestones {
+            self.record_milestone(milestone);
+        }
+    }
+
+    /// Queue a milestone autosave
+    pub fn record_milestone(&mut self, milestone: Milestone) {
+        self.pending_saves.push(SaveReason::Milestone(milestone));
+    }
+
+    /// Write any queued autosaves to disk
+    pub fn process_saves(&mut self) {
+        for reason in std::mem::take(&mut self.pending_saves) {
+            let label = match &reason {
+                SaveReason::Timed => "Autosave".to_string(),
+                SaveReason::Milestone(milestone) => milestone.description(),
+            };
+            let data = SaveData::capture(self, reason);
+            match self.save_manager.write(&data) {
+                Ok(_) => self.add_debug_message(format!("Game saved: {}", label)),
+                Err(error) => self.add_debug_message(format!("Autosave failed: {}", error)),
+            }
+        }
+
+        if let Some(export) = self.pending_score_export.take() {
+            match export.write(&self.save_manager.leaderboard_path()) {
+                Ok(()) => self.add_debug_message("Score exported for the leaderboard.".to_string()),
+                Err(error) => self.add_debug_message(format!("Score export failed: {}", error)),
+            }
+        }
+
+        let path = self.save_manager.achievements_path();
+        if let Err(error) = self.achievements.save(&path) {
+            self.add_debug_message(format!("Could not save achievements: {}", error));
+        }
+    }
+
+    /// This run's counts, as credited to the lifetime stats
+    fn run_stats(&self) -> RunStats {
+        RunStats {
+            kills: self.kills,
+            feedings: self.feeding_count,
+            days: self.time.day_count(),
+            allied_clans: self.clans.values().filter(|clan| clan.is_allied).count() as u32,
+            total_clans: self.clans.len() as u32,
+        }
+    }
+
+    /// Credit this frame's progress to the lifetime stats, announcing any
+    /// achievements it earns
+    fn update_achievements(&mut self) {
+        for achievement in self.achievements.observe(self.run_stats()) {
+            self.add_debug_message(format!(
+                "Achievement unlocked: {} - {}",
+                achievement.display_name(),
+                achievement.description()
+            ));
+        }
+    }
+
+    /// Return to the most recent milestone save
+    pub fn load_latest_milestone(&mut self) {
+        match self.save_manager.latest_milestone() {
+            Some(data) => {
+                let label = match &data.reason {
+                    SaveReason::Milestone(milestone) => milestone.description(),
+                    SaveReason::Timed => "Autosave".to_string(),
+                };
+                data.restore(self);
+                self.events = EventBus::default();
+                self.projectiles.clear();
+                self.game_over = None;
+                self.interior = None;
+                self.crow_scout = None;
+                self.blueprint = None;
+                self.hibernation = None;
+                self.scout_marks.clear();
+                self.active_vision = None;
+                self.auto_walk = None;
+                self.camera_rig.settled = false;
+                self.add_debug_message(format!("Loaded milestone: {}", label));
+            }
+            None => self.add_debug_message("No milestone saves found".to_string()),
+        }
+    }
+
+    /// Feed a measured frame rate to the low-FPS monitor, suggesting the
+    /// low-spec preset once if performance stays poor
+    pub fn report_fps(&mut self, fps: f32, elapsed: f32) {
+        if self.video_settings.is_low_spec() {
+            return;
+        }
+
+        if self.fps_monitor.record(fps, elapsed) {
+            self.low_spec_suggested = true;
+            self.add_debug_message(format!(
+                "Low frame rate detected ({:.0} FPS). Press V to switch to the Low-Spec preset.",
+                fps
+            ));
+        }
+    }
+
+    /// Handle clan selection and tribute adjustment while the clan menu is open
+    fn handle_clan_menu_input(&mut self, input_handler: &InputHandler) {
+        let clan_count = self.clans.len();
+        if clan_count == 0 {
+            return;
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::MenuDown) {
+            self.selected_clan = (self.selected_clan + 1) % clan_count;
+        }
+        if input_handler.is_action_just_pressed(InputAction::MenuUp) {
+            self.selected_clan = (self.selected_clan + clan_count - 1) % clan_count;
+        }
+
+        let raise = input_handler.is_action_just_pressed(InputAction::MenuRight);
+        let lower = input_handler.is_action_just_pressed(InputAction::MenuLeft);
+        if raise || lower {
+            let clan_name =
+                self.sorted_clan_names()[self.selected_clan.min(clan_count - 1)].clone();
+            if let Some(clan) = self.clans.get_mut(&clan_name) {
+                if TributeSystem::adjust_tribute(clan, raise) {
+                    let message = format!(
+                        "Tribute from {} set to {}",
+                        clan_name,
+                        clan.tribute_level.display_name()
+                    );
+                    self.add_debug_message(message);
+                } else {
+                    self.add_debug_message(format!(
+                        "{} must be allied or subjugated before paying tribute",
+                        clan_name
+                    ));
+                }
+            }
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            let clan_name =
+                self.sorted_clan_names()[self.selected_clan.min(clan_count - 1)].clone();
+            let event = AssaultSystem::call(
+                &mut self.assaults,
+                &self.clans,
+                &mut self.entities,
+                &self.territories,
+                &self.world_bounds,
+                self.player_id,
+                &clan_name,
+                self.time.day_count(),
+            );
+            if matches!(event, AssaultEvent::Called { .. }) {
+                self.show_clan_menu = false;
+            }
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Clan names in a stable order for menus
+    pub fn sorted_clan_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.clans.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Update the time system
+    fn update_time_system(&mut self, delta_time: f32) {
+        let scale = HibernationSystem::time_scale(self.hibernation.as_ref());
+        if self.time.update(delta_time * scale).is_some() {
+            self.sound_cues
+                .push(SoundCue::global(SoundEffect::DayTransition));
+
+            // The weather turns at every dawn and dusk
+            if let Some(change) = WeatherSystem::turn(&mut self.time, rand::gen_range(0.0, 1.0)) {
+                self.add_debug_message(change.get_message());
+            }
+        }
+        if let Some(event) = CalendarSystem::update(
+            &mut self.calendar,
+            &mut self.entities,
+            &self.time,
+            self.player_id,
+        ) {
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Point at creatures with the mouse: left click strikes the one under
+    /// the cursor and right click feeds on it, when it is within reach
+    fn update_mouse_targeting(&mut self, input_handler: &InputHandler) {
+        let Some(screen_size) = input_handler.screen_size() else {
+            self.hovered_entity = None;
+            return;
+        };
+        let cursor = CameraSystem::screen_to_world(
+            &self.camera_rig,
+            Position::new(self.camera_x, self.camera_y),
+            input_handler.mouse_position(),
+            screen_size,
+        );
+        self.hovered_entity =
+            PlayerSystem::pick_entity(&self.entities, &self.spatial_grid, self.player_id, cursor);
+        let Some(target_id) = self.hovered_entity else {
+            return;
+        };
+
+        if input_handler.is_mouse_clicked(MouseButton::Left) {
+            if let Some(event) = PlayerSystem::attempt_attack_on(
+                &mut self.entities,
+                self.player_id,
+                target_id,
+                self.game_time,
+                &mut self.damage_events,
+            ) {
+                if !event.dodged && self.video_settings.particles {
+                    ParticleSystem::emit(
+                        &mut self.particles,
+                        ParticleKind::Blood,
+                        event.position,
+                        12,
+                    );
+                }
+            }
+        }
+        if input_handler.is_mouse_clicked(MouseButton::Right) {
+            let in_reach = PlayerSystem::inspect(&self.entities, self.player_id, target_id)
+                .is_some_and(|inspection| inspection.can_feed);
+            if !in_reach {
+                self.add_debug_message("Too far away to feed.".to_string());
+                return;
+            }
+            let mut debug_messages = Vec::new();
+            let fed = PlayerSystem::attempt_feeding_on(
+                &mut self.entities,
+                &self.spatial_grid,
+                self.player_id,
+                target_id,
+                self.game_time,
+                &mut debug_messages,
+            );
+            for message in debug_messages {
+                self.add_debug_message(message);
+            }
+            if let Some(event) = fed {
+                if let GameEvent::FedOnTarget { position, .. } = event {
+                    self.report_crime(Crime::Feeding, target_id, position);
+                }
+                self.events.push(event);
+            }
+        }
+    }
+
+    /// From the player toward the world position under the cursor; no
+    /// direction at all without a window to point in
+    fn aim_direction(&self, input_handler: &InputHandler) -> (f32, f32) {
+        let (Some(player), Some(screen_size)) = (
+            EntityFinder::by_id(&self.entities, self.player_id),
+            input_handler.screen_size(),
+        ) else {
+            return (0.0, 0.0);
+        };
+        let target = CameraSystem::screen_to_world(
+            &self.camera_rig,
+            Position::new(self.camera_x, self.camera_y),
+            input_handler.mouse_position(),
+            screen_size,
+        );
+        (target.x - player.position.x, target.y - player.position.y)
+    }
+
+    /// Fly projectiles and resolve what they hit
+    fn update_projectiles(&mut self, delta_time: f32) {
+        if self.projectiles.is_empty() {
+            return;
+        }
+        let blockers = StealthSystem::sight_blockers(&self.entities);
+        ProjectileSystem::update(
+            &mut self.projectiles,
+            &mut self.entities,
+            &self.spatial_grid,
+            &blockers,
+            self.game_time,
+            &mut self.damage_events,
+            delta_time,
+        );
+    }
+
+    /// Update environmental elements
+    fn update_environment(&mut self, delta_time: f32) {
+        // Update stars
+        for star in &mut self.stars {
+            star.update(self.game_time);
+        }
+
+        // Update moon
+        self.moon.update(self.game_time);
+
+        // Update particles
+        ParticleSystem::update(&mut self.particles, delta_time);
+    }
+
+    /// Steer the crow scout while the vampire stands still, and call it
+    /// back when the Crow Scout key is pressed again
+    fn update_crow_scout(&mut self, input_handler: &InputHandler, delta_time: f32) {
+        if let Some(velocity) = self
+            .entities
+            .iter_mut()
+            .find(|entity| entity.id == self.player_id)
+            .and_then(|player| player.velocity.as_mut())
+        {
+            *velocity = Velocity::zero();
+        }
+
+        let Some(scout) = self.crow_scout.as_mut() else {
+            return;
+        };
+        let event = if input_handler.is_action_just_pressed(InputAction::CrowScout) {
+            Some(ScoutEvent::Dismissed)
+        } else {
+            ScoutSystem::fly(
+                scout,
+                &self.world_bounds,
+                input_handler.movement_vector(),
+                delta_time,
+            )
+        };
+        ScoutSystem::observe(
+            scout,
+            &self.entities,
+            &self.spatial_grid,
+            self.player_id,
+            &mut self.fog_of_war,
+            &mut self.scout_marks,
+        );
+
+        if let Some(event) = event {
+            self.crow_scout = None;
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Enter the nearest shelter, stepping into its room if it has one, or
+    /// leave the one the player is in
+    fn toggle_shelter(&mut self) {
+        let player_id = self.player_id;
+        let sheltered_in = |entities: &[GameEntity]| {
+            EntityFinder::by_id(entities, player_id)
+                .and_then(|player| player.shelter_occupancy.as_ref())
+                .and_then(|occupancy| occupancy.shelter_id)
+        };
+        let before = sheltered_in(&self.entities);
+        let interaction = ShelterSystem::handle_player_shelter_interaction(
+            &mut self.entities,
+            &self.spatial_grid,
+            self.player_id,
+            self.game_time,
+            self.weakness_rules.thresholds,
+        );
+        if let Some(message) = interaction {
+            self.add_debug_message(format!("Shelter: {}", message));
+        }
+        match (before, sheltered_in(&self.entities)) {
+            (None, Some(shelter_id)) => {
+                self.events.push(GameEvent::ShelterEntered {
+                    entity_id: self.player_id,
+                    shelter_id,
+                });
+                if let Some((scene, event)) =
+                    InteriorSystem::enter(&mut self.entities, self.player_id, shelter_id)
+                {
+                    self.interior = Some(scene);
+                    self.hovered_entity = None;
+                    self.add_debug_message(event.get_message());
+                }
+            }
+            (Some(shelter_id), None) => {
+                self.interior = None;
+                self.events.push(GameEvent::ShelterLeft {
+                    entity_id: self.player_id,
+                    shelter_id,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Walk the room inside a shelter while the vampire's body waits out of
+    /// sight; interact uses whatever is in reach
+    fn update_interior(&mut self, input_handler: &InputHandler, delta_time: f32) {
+        if let Some(velocity) = self
+            .entities
+            .iter_mut()
+            .find(|entity| entity.id == self.player_id)
+            .and_then(|player| player.velocity.as_mut())
+        {
+            *velocity = Velocity::zero();
+        }
+
+        // The shelter is gone from around the player
+        let indoors = EntityFinder::by_id(&self.entities, self.player_id)
+            .and_then(|player| player.shelter_occupancy.as_ref())
+            .is_some_and(|occupancy| occupancy.indoors);
+        let Some(scene) = self.interior.as_mut().filter(|_| indoors) else {
+            self.interior = None;
+            return;
+        };
+        if self.hibernation.is_none() {
+            InteriorSystem::walk(scene, input_handler.movement_vector(), delta_time);
+        }
+        let shelter_id = scene.shelter_id;
+        let fixture = InteriorSystem::fixture_in_reach(scene);
+
+        if input_handler.is_action_just_pressed(InputAction::Shelter) {
+            self.toggle_shelter();
+            return;
+        }
+        if !input_handler.is_action_just_pressed(InputAction::Interact) {
+            return;
+        }
+        let event = match fixture {
+            Some(Fixture::Door) => {
+                self.toggle_shelter();
+                None
+            }
+            Some(Fixture::Bed) if self.hibernation.is_none() => {
+                if self.time.is_day() {
+                    self.hibernation = Some(HibernationSystem::sleep(shelter_id));
+                    self.add_debug_message(HibernationEvent::FellAsleep.get_message());
+                    None
+                } else {
+                    Some(InteriorEvent::CannotSleepAtNight)
+                }
+            }
+            Some(Fixture::Stash) => {
+                InteriorSystem::use_stash(&mut self.entities, self.player_id, shelter_id)
+            }
+            _ => None,
+        };
+        if let Some(event) = event {
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Steer the blueprint while the vampire stands still, build it with
+    /// interact, and leave build mode when the Build key is pressed again
+    fn update_build_mode(&mut self, input_handler: &InputHandler, delta_time: f32) {
+        let Some(player) = self
+            .entities
+            .iter_mut()
+            .find(|entity| entity.id == self.player_id)
+        else {
+            return;
+        };
+        if let Some(velocity) = player.velocity.as_mut() {
+            *velocity = Velocity::zero();
+        }
+        let player_position = player.position;
+
+        let Some(blueprint) = self.blueprint.as_mut() else {
+            return;
+        };
+        if input_handler.is_action_just_pressed(InputAction::Build) {
+            self.blueprint = None;
+            self.add_debug_message("You put away your plans.".to_string());
+            return;
+        }
+        ConstructionSystem::move_blueprint(
+            blueprint,
+            player_position,
+            input_handler.movement_vector(),
+            delta_time,
+        );
+
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            let blueprint = blueprint.clone();
+            let message = match ConstructionSystem::build(
+                &mut self.entities,
+                &mut self.entity_ids,
+                self.player_id,
+                &blueprint,
+            ) {
+                Ok(event) => {
+                    self.blueprint = None;
+                    self.spatial_grid.rebuild(&self.entities);
+                    event.get_message()
+                }
+                Err(error) => error.get_message(),
+            };
+            self.add_debug_message(message);
+        }
+    }
+
+    /// Raise whatever the player is building, saving when a lair is done
+    fn update_construction(&mut self, delta_time: f32) {
+        for event in ConstructionSystem::update(&mut self.entities, delta_time) {
+            if event
+                == (ConstructionEvent::Completed {
+                    shelter_type: ShelterType::Lair,
+                })
+            {
+                self.record_milestone(Milestone::LairBuilt);
+            }
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Lift the fog around the player and fade the crow's marks
+    fn update_fog_of_war(&mut self, delta_time: f32) {
+        if let Some(player) = EntityFinder::by_id(&self.entities, self.player_id) {
+            self.fog_of_war
+                .reveal(player.position, FogOfWar::PLAYER_SIGHT);
+        }
+        ScoutSystem::update_marks(&mut self.scout_marks, &self.entities, delta_time);
+    }
+
+    /// Update player-related systems
+    fn update_player_system(&mut self, input_handler: &InputHandler, delta_time: f32) {
+        // Creep while the sneak key is held
+        if let Some(abilities) = self
+            .entities
+            .iter_mut()
+            .find(|entity| entity.id == self.player_id)
+            .and_then(|player| player.vampire_abilities.as_mut())
+        {
+            abilities.ability_state.sneaking = input_handler.is_action_pressed(InputAction::Sneak);
+        }
+
+        // Handle player input and actions
+        PlayerSystem::handle_input(
+            &mut self.entities,
+            input_handler,
+            &self.spatial_grid,
+            self.player_id,
+            self.game_time,
+        );
+
+        if input_handler.is_action_just_pressed(InputAction::Waypoint) {
+            self.cycle_waypoint();
+        }
+        if input_handler.is_action_just_pressed(InputAction::AutoWalk) {
+            self.toggle_auto_walk();
+        }
+        if input_handler.is_action_just_pressed(InputAction::Build) {
+            self.enter_build_mode();
+        }
+
+        // Update player movement; any movement input takes back control
+        let previous_position =
+            EntityFinder::by_id(&self.entities, self.player_id).map(|player| player.position);
+        if input_handler.movement_vector() != (0.0, 0.0) && self.auto_walk.take().is_some() {
+            self.add_debug_message("Auto-walk cancelled.".to_string());
+        }
+        let swimming = previous_position
+            .is_some_and(|position| WorldSystem::is_water_at(&position, self.world_seed));
+        match (self.auto_walk.as_mut(), previous_position) {
+            (Some(auto_walk), Some(position)) => match TravelSystem::steer(auto_walk, position) {
+                Some(direction) => PlayerSystem::move_player(
+                    &mut self.entities,
+                    self.player_id,
+                    &self.world_bounds,
+                    direction,
+                    self.time.is_day(),
+                    swimming,
+                    delta_time,
+                ),
+                None => {
+                    let label = auto_walk.destination.label.clone();
+                    self.auto_walk = None;
+                    self.add_debug_message(format!("You have arrived at {}.", label));
+                }
+            },
+            _ => PlayerSystem::update_movement(
+                &mut self.entities,
+                input_handler,
+                self.player_id,
+                &self.world_bounds,
+                self.time.is_day(),
+                swimming,
+                delta_time,
+            ),
+        }
+
+        // Pick up any items the player walked over
+        let item_events = previous_position
+            .map(|previous| {
+                PlayerSystem::collect_pickups(&mut self.entities, self.player_id, previous)
+            })
+            .unwrap_or_default();
+        if item_events
+            .iter()
+            .any(|event| matches!(event, ItemEvent::PickedUp { .. }))
+        {
+            self.spatial_grid.rebuild(&self.entities);
+        }
+        for event in item_events {
+            self.add_debug_message(event.get_message());
+        }
+
+        // Handle special abilities
+        let ability_actions = [
+            (InputAction::ShadowDash, SpecialAbility::ShadowDash),
+            (InputAction::BloodSense, SpecialAbility::BloodSensePulse),
+            (InputAction::BatForm, SpecialAbility::BatForm),
+            (InputAction::SpectralVision, SpecialAbility::SpectralVision),
+            (InputAction::CrowScout, SpecialAbility::CrowScout),
+            (InputAction::BloodSight, SpecialAbility::BloodSight),
+            (InputAction::BloodLance, SpecialAbility::BloodLance),
+        ];
+        for (action, ability) in ability_actions {
+            if input_handler.is_action_just_pressed(action) {
+                // Blood Lance flies at the cursor; Shadow Dash follows movement
+                let direction = if ability == SpecialAbility::BloodLance {
+                    self.aim_direction(input_handler)
+                } else {
+                    input_handler.movement_vector()
+                };
+                let result = AbilitySystem::activate(
+                    &mut self.entities,
+                    self.player_id,
+                    &self.world_bounds,
+                    ability,
+                    direction,
+                );
+                if result
+                    == (AbilityResult::Activated {
+                        ability: SpecialAbility::BloodLance,
+                    })
+                {
+                    ProjectileSystem::fire_lance(
+                        &mut self.projectiles,
+                        &self.entities,
+                        self.player_id,
+                        direction,
+                    );
+                }
+                if result
+                    == (AbilityResult::Activated {
+                        ability: SpecialAbility::CrowScout,
+                    })
+                {
+                    self.crow_scout = EntityFinder::by_id(&self.entities, self.player_id)
+                        .map(|player| ScoutSystem::launch(player.position));
+                }
+                if matches!(result, AbilityResult::Activated { .. })
+                    && self.video_settings.particles
+                {
+                    if let Some(player) = EntityFinder::by_id(&self.entities, self.player_id) {
+                        ParticleSystem::emit(
+                            &mut self.particles,
+                            ParticleKind::Sparkle,
+                            player.position,
+                            10,
+                        );
+                    }
+                }
+                self.add_debug_message(result.get_message());
+            }
+        }
+
+        // Footsteps kick up dust
+        if self.video_settings.particles {
+            if let Some(player) = EntityFinder::by_id(&self.entities, self.player_id) {
+                ParticleSystem::emit_dust(&mut self.particles, player, delta_time);
+            }
+        }
+
+        // Running water and garlic act on wherever the player ended up
+        if let Some(previous) = previous_position {
+            if let Some(event) = WeaknessSystem::update(
+                &mut self.entities,
+                self.player_id,
+                &self.hazards,
+                &self.weakness_rules,
+                previous,
+                delta_time,
+            ) {
+                self.add_debug_message(event.get_message());
+            }
+        }
+
+        // Drink a bottled blood vial
+        if input_handler.is_action_just_pressed(InputAction::DrinkVial) {
+            let event = PlayerSystem::use_item(
+                &mut self.entities,
+                &mut self.clans,
+                self.player_id,
+                ItemKind::BloodVial,
+            );
+            self.add_debug_message(event.get_message());
+        }
+
+        // Recruit clan members and command the retinue
+        if input_handler.is_action_just_pressed(InputAction::Recruit) {
+            self.recruit_follower();
+        }
+        let follower_commands = [
+            (InputAction::CommandFollow, FollowerCommand::Follow),
+            (InputAction::CommandHold, FollowerCommand::Hold),
+            (InputAction::CommandAttack, FollowerCommand::AttackTarget),
+        ];
+        for (action, command) in follower_commands {
+            if input_handler.is_action_just_pressed(action) {
+                let message = match PlayerSystem::command_followers(
+                    &mut self.entities,
+                    self.player_id,
+                    command,
+                ) {
+                    Some(0) => "You have no followers to command.".to_string(),
+                    Some(count) => {
+                        format!("{} ({} followers)", command.display_name(), count)
+                    }
+                    None => "No enemy nearby to attack.".to_string(),
+                };
+                self.add_debug_message(message);
+            }
+        }
+
+        // Handle shelter interaction
+        if input_handler.is_action_just_pressed(InputAction::Shelter) {
+            self.toggle_shelter();
+        }
+
+        self.update_mouse_targeting(input_handler);
+
+        // Tapping feed drinks a victim dry; holding it drains them and
+        // leaves them unconscious. Inside a shelter the hold is left to
+        // hibernation instead. The feeding counter follows the event next
+        // frame.
+        let shelter_id = EntityFinder::by_id(&self.entities, self.player_id)
+            .and_then(|player| player.shelter_occupancy.as_ref())
+            .and_then(|occupancy| occupancy.shelter_id);
+        if input_handler.is_action_just_pressed(InputAction::Feed) {
+            self.feed_hold = Some(0.0);
+        }
+        match self.feed_hold {
+            Some(held) if input_handler.is_action_pressed(InputAction::Feed) => {
+                let held = held + delta_time;
+                self.feed_hold = Some(held);
+                // One drain attempt per hold
+                if held >= DRAIN_HOLD_SECONDS && shelter_id.is_none() {
+                    self.feed_hold = None;
+                    let mut debug_messages = Vec::new();
+                    let drained = PlayerSystem::attempt_drain(
+                        &mut self.entities,
+                        &self.spatial_grid,
+                        self.player_id,
+                        self.game_time,
+                        &mut debug_messages,
+                    );
+                    for message in debug_messages {
+                        self.add_debug_message(message);
+                    }
+                    if let Some(event) = drained {
+                        self.hibernate_hold = 0.0;
+                        if let GameEvent::FedOnTarget {
+                            target_id,
+                            position,
+                            ..
+                        } = event
+                        {
+                            self.report_crime(Crime::Feeding, target_id, position);
+                        }
+                        self.events.push(event);
+                    }
+                }
+            }
+            Some(held) => {
+                self.feed_hold = None;
+                if held < DRAIN_HOLD_SECONDS {
+                    let mut debug_messages = Vec::new();
+                    if let Some(event) = PlayerSystem::attempt_feeding(
+                        &mut self.entities,
+                        &self.spatial_grid,
+                        self.player_id,
+                        self.game_time,
+                        &mut debug_messages,
+                    ) {
+                        debug_messages.push("FEEDING SUCCESS!".to_string());
+                        if let GameEvent::FedOnTarget {
+                            target_id,
+                            position,
+                            ..
+                        } = event
+                        {
+                            self.report_crime(Crime::Feeding, target_id, position);
+                        }
+                        self.events.push(event);
+                    } else {
+                        debug_messages
+                            .push("FEEDING FAILED - no target position returned".to_string());
+                    }
+
+                    // Add all debug messages after the feeding attempt
+                    for message in debug_messages {
+                        self.add_debug_message(message);
+                    }
+                }
+            }
+            None => {}
+        }
+
+        // Holding feed inside a shelter by day sleeps through to sunset
+        if self.hibernation.is_none() {
+            if let Some(hibernation) = HibernationSystem::hold(
+                &mut self.hibernate_hold,
+                input_handler.is_action_pressed(InputAction::Feed),
+                shelter_id,
+                &self.time,
+                delta_time,
+            ) {
+                self.hibernation = Some(hibernation);
+                self.add_debug_message(HibernationEvent::FellAsleep.get_message());
+            }
+        }
+
+        // Handle attack attempts through the combat system
+        if input_handler.is_action_just_pressed(InputAction::Attack) {
+            if let Some(event) = PlayerSystem::attempt_attack(
+                &mut self.entities,
+                self.player_id,
+                self.game_time,
+                &mut self.damage_events,
+            ) {
+                if !event.dodged && self.video_settings.particles {
+                    // Create blood particle effects at the attacked entity's position
+                    ParticleSystem::emit(
+                        &mut self.particles,
+                        ParticleKind::Blood,
+                        event.position,
+                        12, // More particles for combat
+                    );
+                }
+            }
+        }
+
+        // Holding interact inside a shelter mends and then reinforces it
+        if input_handler.is_action_pressed(InputAction::Interact) {
+            if let Some(event) =
+                ShelterSystem::work_on_shelter(&mut self.entities, self.player_id, delta_time)
+            {
+                self.add_debug_message(event.get_message());
+            }
+        }
+
+        // Handle clan interactions, or read a signpost when no leader is near
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            if let Some(clan_name) = PlayerSystem::attempt_interaction(
+                &mut self.entities,
+                &self.spatial_grid,
+                self.player_id,
+            ) {
+                self.interact_with_clan(&clan_name);
+            } else if let Some(message) =
+                WorldSystem::read_signpost(&mut self.signposts, &mut self.entities, self.player_id)
+            {
+                self.add_debug_message(message);
+            } else if self.weakness_rules.thresholds {
+                if let Some(event) = WeaknessSystem::knock(
+                    &mut self.entities,
+                    &self.spatial_grid,
+                    self.player_id,
+                    self.time.is_night(),
+                ) {
+                    self.add_debug_message(event.get_message());
+                }
+            }
+        }
+    }
+
+    /// Update AI system for all NPCs and resolve their attacks
+    fn update_ai_system(&mut self, delta_time: f32) {
+        CombatSystem::update_staggers(&mut self.entities, delta_time);
+        let stealth = self.stealth_profile();
+        let hearing_scale = self
+            .noise_profile()
+            .map_or(1.0, |profile| profile.multiplier)
+            * stealth.map_or(1.0, |profile| profile.multiplier)
+            * self.time.weather().sight_factor();
+        let blockers = StealthSystem::sight_blockers(&self.entities);
+        if !self
+            .nav_grid
+            .is_current(&self.entities, &self.world_bounds, self.world_seed)
+        {
+            self.nav_grid = NavGrid::build(&self.entities, &self.world_bounds, self.world_seed);
+        }
+        let attackers = AISystem::update_all_ai(
+            &mut self.entities,
+            &self.spatial_grid,
+            self.player_id,
+            hearing_scale,
+            &blockers,
+            self.time.is_night(),
+            &self.world_bounds,
+            &mut self.nav_grid,
+            self.world_seed,
+            self.video_settings.active_entity_radius,
+            delta_time,
+        );
+        if stealth.is_some_and(|profile| profile.sneaking) {
+            self.practice_stealth(attackers.is_empty(), delta_time);
+        }
+
+        // Nothing outside can reach a player indoors
+        for attacker_id in attackers.into_iter().filter(|_| self.interior.is_none()) {
+            CombatSystem::resolve_attack(
+                &mut self.entities,
+                attacker_id,
+                self.player_id,
+                self.game_time,
+                &mut self.damage_events,
+            );
+        }
+
+        let follower_attacks = AISystem::update_followers(
+            &mut self.entities,
+            self.player_id,
+            &self.world_bounds,
+            &mut self.nav_grid,
+            delta_time,
+        );
+        for (attacker_id, target_id) in follower_attacks {
+            CombatSystem::resolve_attack(
+                &mut self.entities,
+                attacker_id,
+                target_id,
+                self.game_time,
+                &mut self.damage_events,
+            );
+        }
+
+        self.update_hunters(hearing_scale, delta_time);
+    }
+
+    /// Hungry infected stalk, kill and devour animals away from the player
+    fn update_hunters(&mut self, hearing_scale: f32, delta_time: f32) {
+        let hunts = AISystem::update_hunters(
+            &mut self.entities,
+            &self.spatial_grid,
+            self.player_id,
+            hearing_scale,
+            self.video_settings.active_entity_radius,
+            delta_time,
+        );
+        for (hunter_id, prey_id) in hunts {
+            let prey_alive = EntityFinder::by_id(&self.entities, prey_id)
+                .is_some_and(|prey| !matches!(prey.ai_state, AIState::Dead));
+            if prey_alive {
+                let killed = CombatSystem::resolve_attack(
+                    &mut self.entities,
+                    hunter_id,
+                    prey_id,
+                    self.game_time,
+                    &mut self.damage_events,
+                )
+                .is_some_and(|blow| blow.killed);
+                if !killed {
+                    continue;
+                }
+            }
+
+            let kill_site = EntityFinder::by_id(&self.entities, prey_id).map(|prey| prey.position);
+            if BloodSystem::devour(&mut self.entities, hunter_id, prey_id) {
+                let player_pos =
+                    EntityFinder::by_id(&self.entities, self.player_id).map(|p| p.position);
+                if let Some((player_pos, kill_site)) = player_pos.zip(kill_site) {
+                    if player_pos.distance_to(&kill_site) <= HUNT_HEARING_RANGE {
+                        self.add_debug_message(format!(
+                            "Something is feeding on an animal {} of you.",
+                            SkirmishSystem::bearing(player_pos, kill_site)
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Recruit a clan member into the retinue, calling one out of the clan
+    /// when recruiting from its leader
+    fn recruit_follower(&mut self) {
+        let result = PlayerSystem::attempt_recruitment(
+            &mut self.entities,
+            &self.spatial_grid,
+            &mut self.clans,
+            self.player_id,
+        );
+
+        if let RecruitResult::Recruited { entity_id, .. } = &result {
+            StatusSystem::apply_charm(&mut self.entities, *entity_id, RECRUIT_CHARM_SECONDS);
+        }
+
+        if let RecruitResult::Summoned { clan_name } = &result {
+            if let Some(player_pos) =
+                EntityFinder::by_id(&self.entities, self.player_id).map(|p| p.position)
+            {
+                let color = WorldSystem::clan_member_color(clan_name);
+                let id = WorldSystem::spawn_clan_member(
+                    &mut self.entities,
+                    &mut self.entity_ids,
+                    clan_name,
+                    player_pos.x + 30.0,
+                    player_pos.y.max(650.0),
+                    color,
+                );
+                if let Some(follower) = self.entities.iter_mut().find(|e| e.id == id) {
+                    follower.ai_state = AIState::Follower(FollowerOrder::Follow);
+                }
+                StatusSystem::apply_charm(&mut self.entities, id, RECRUIT_CHARM_SECONDS);
+            }
+        }
+
+        self.add_debug_message(result.get_message());
+    }
+
+    /// Report kills from this frame's damage events, credit them to the
+    /// bestiary, and expire old ones
+    fn update_combat_events(&mut self, first_new_event: usize) {
+        let new_events = &self.damage_events[first_new_event.min(self.damage_events.len())..];
+        for event in new_events.iter().filter(|event| event.killed) {
+            self.events.push(GameEvent::EntityDied {
+                entity_id: event.target_id,
+                killer_id: event.attacker_id,
+                position: event.position,
+            });
+        }
+        let crimes: Vec<(Crime, u32, Position)> = new_events
+            .iter()
+            .filter(|event| event.attacker_id == self.player_id && !event.dodged)
+            .map(|event| {
+                let crime = if event.killed {
+                    Crime::Murder
+                } else {
+                    Crime::Attack
+                };
+                (crime, event.target_id, event.position)
+            })
+            .collect();
+        EffectsSystem::spawn(
+            &mut self.hit_effects,
+            new_events,
+            &self.entities,
+            self.player_id,
+            self.video_settings.screen_shake,
+        );
+        TrailSystem::splash(&mut self.blood_trails, new_events, self.player_id);
+        let mut loot = Vec::new();
+        for event in new_events {
+            if !event.killed || event.target_id == self.player_id {
+                continue;
+            }
+            let roll = rand::gen_range(0.0, 1.0);
+            if let Some(drop) = LifecycleSystem::drop_loot(
+                &mut self.entities,
+                &mut self.entity_ids,
+                event.target_id,
+                roll,
+            ) {
+                if event.attacker_id == self.player_id {
+                    loot.push(drop);
+                }
+            }
+        }
+        let mut learned = Vec::new();
+        for event in new_events {
+            if !event.killed || event.attacker_id != self.player_id {
+                continue;
+            }
+            if let Some(victim) = EntityFinder::by_id(&self.entities, event.target_id) {
+                learned.extend(BestiarySystem::record_kill(&mut self.bestiary, victim));
+            }
+        }
+
+        let player_hurt = new_events
+            .iter()
+            .any(|event| event.target_id == self.player_id && !event.dodged);
+        self.sound_cues.extend(
+            new_events
+                .iter()
+                .filter(|event| !event.dodged)
+                .map(|event| SoundCue::at(SoundEffect::AttackHit, event.position)),
+        );
+
+        CombatSystem::prune_events(&mut self.damage_events, self.game_time);
+        for (crime, victim_id, position) in crimes {
+            self.report_crime(crime, victim_id, position);
+        }
+
+        for event in learned {
+            self.add_debug_message(event.get_message());
+        }
+        for drop in loot {
+            self.add_debug_message(drop.get_message());
+        }
+
+        if player_hurt && self.auto_walk.take().is_some() {
+            self.add_debug_message("You are struck and stop walking.".to_string());
+        }
+    }
+
+    /// Add creatures the player gets a good look at to the bestiary
+    fn update_bestiary(&mut self) {
+        let events = BestiarySystem::observe(
+            &mut self.bestiary,
+            &self.entities,
+            &self.spatial_grid,
+            self.player_id,
+        );
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Places the player can pick as a waypoint: the current objective,
+    /// any hint markers, every territory, then every discovered shelter
+    pub fn waypoint_destinations(&self) -> Vec<Waypoint> {
+        let objective = self.first_night.as_ref().and_then(|first_night| {
+            OnboardingSystem::marker_position(first_night, &self.entities, self.player_id)
+        });
+        let territories = self
+            .territories
+            .territories
+            .iter()
+            .map(|territory| Waypoint::new(territory.name.clone(), territory.center));
+        let shelters = self.entities.iter().filter_map(|entity| {
+            let shelter = entity
+                .shelter
+                .as_ref()
+                .filter(|shelter| shelter.discovered)?;
+            Some(Waypoint::new(shelter.display_name(), entity.position))
+        });
+
+        let hints = self
+            .hint_marker_positions()
+            .into_iter()
+            .map(|position| Waypoint::new("Hint", position));
+
+        objective
+            .map(|position| Waypoint::new("Objective", position))
+            .into_iter()
+            .chain(hints)
+            .chain(territories)
+            .chain(shelters)
+            .collect()
+    }
+
+    /// Move the waypoint on to the next destination, clearing it after the
+    /// last one
+    fn cycle_waypoint(&mut self) {
+        let destinations = self.waypoint_destinations();
+        let next = match &self.waypoint {
+            Some(current) => destinations
+                .iter()
+                .position(|destination| destination.label == current.label)
+                .map_or(0, |index| index + 1),
+            None => 0,
+        };
+        self.waypoint = destinations.get(next).cloned();
+        self.auto_walk = None;
+        let message = match &self.waypoint {
+            Some(waypoint) => format!("Waypoint set: {}", waypoint.label),
+            None => "Waypoint cleared.".to_string(),
+        };
+        self.add_debug_message(message);
+    }
+
+    fn toggle_auto_walk(&mut self) {
+        if self.auto_walk.take().is_some() {
+            self.add_debug_message("Auto-walk stopped.".to_string());
+            return;
+        }
+        let Some(waypoint) = self.waypoint.clone() else {
+            self.add_debug_message("Set a waypoint first (N).".to_string());
+            return;
+        };
+        let Some(position) =
+            EntityFinder::by_id(&self.entities, self.player_id).map(|player| player.position)
+        else {
+            return;
+        };
+        self.add_debug_message(format!("Walking to {}...", waypoint.label));
+        self.auto_walk = Some(TravelSystem::start(position, waypoint));
+    }
+
+    /// Update shelter system
+    fn update_shelter_system(&mut self, delta_time: f32) {
+        ShelterSystem::update_shelters(
+            &mut self.entities,
+            self.game_time,
+            self.time.get_sunlight_intensity(),
+            delta_time,
+        );
+    }
+
+    /// Send the night's waves of infected
+    fn update_waves(&mut self, delta_time: f32) {
+        if let Some(event) = WaveSystem::update(
+            &mut self.spawn_director,
+            &mut self.entities,
+            &mut self.entity_ids,
+            self.time.is_night(),
+            self.time.day_count(),
+            self.calendar.spawn_rate(),
+            WaveSystem::keep_clear(
+                Position::new(self.camera_x, self.camera_y),
+                self.camera_rig.zoom,
+            ),
+            delta_time,
+        ) {
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Keep clan members to their day and night routines
+    fn update_schedules(&mut self, delta_time: f32) {
+        ScheduleSystem::update(
+            &mut self.entities,
+            &self.world_bounds,
+            self.time.is_day(),
+            self.game_time,
+            delta_time,
+        );
+    }
+
+    /// Count daytime rest in a shelter and, once a day, maybe drift into a
+    /// vision of the first vampire's past
+    fn update_rest(&mut self, delta_time: f32) {
+        if !self.is_player_in_shelter() || self.time.is_night() {
+            self.rest_time = 0.0;
+            return;
+        }
+        self.rest_time += delta_time;
+
+        let Some(script) = DreamSystem::next_vision(&self.lore_codex) else {
+            return;
+        };
+        if DreamSystem::rest_brings_dream(
+            self.rest_time,
+            &mut self.last_dream_roll_day,
+            self.time.day_count(),
+            rand::gen_range(0.0, 1.0),
+        ) {
+            self.add_debug_message(format!("You sink into a vision: {}", script.title));
+            self.active_vision = Some(DreamSystem::load(script));
+        }
+    }
+
+    /// Drain blood while the player sleeps and wake them at sunset or when
+    /// disturbed; hits taken this frame start at `first_new_event`
+    fn update_hibernation(&mut self, first_new_event: usize, delta_time: f32) {
+        let Some(hibernation) = self.hibernation.as_mut() else {
+            return;
+        };
+        let attacked = self.damage_events[first_new_event..]
+            .iter()
+            .any(|event| event.target_id == self.player_id);
+        let event = HibernationSystem::update(
+            hibernation,
+            &self.time,
+            &mut self.entities,
+            self.player_id,
+            attacked,
+            self.difficulty,
+            delta_time,
+        );
+        if hibernation.waking && hibernation.fade <= 0.0 {
+            self.hibernation = None;
+        }
+        if let Some(event) = event {
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Wake from a vision, keeping what it taught
+    fn finish_vision(&mut self, outcome: VisionOutcome) {
+        if let Some(vision) = self.active_vision.take() {
+            if let VisionOutcome::Completed { skill_point, .. } = &outcome {
+                self.lore_codex.unlocked.push(vision.script.id.to_string());
+                if let Some(progression) = self
+                    .entities
+                    .iter_mut()
+                    .find(|entity| entity.id == self.player_id)
+                    .and_then(|player| player.progression.as_mut())
+                    .filter(|_| *skill_point)
+                {
+                    progression.skill_points += 1;
+                }
+            }
+        }
+        self.rest_time = 0.0;
+        self.add_debug_message(outcome.get_message());
+    }
+
+    /// Update blood system and related mechanics
+    fn update_blood_system(&mut self, delta_time: f32) {
+        let shadows = ExposureSystem::cast_shadows(&self.entities, &self.time);
+        BloodSystem::update_blood_system(
+            &mut self.entities,
+            self.time.is_day(),
+            self.time.get_sunlight_intensity(),
+            &shadows,
+            self.difficulty,
+            &mut self.events,
+            delta_time,
+        );
+        BloodSystem::apply_running_water(
+            &mut self.entities,
+            self.world_seed,
+            self.difficulty,
+            delta_time,
+        );
     }
 
-    /// Handle UI-related input (menus, pause, etc.)
-    fn handle_ui_input(&mut self, input_handler: &InputHandler) {
-        // Menu toggles
-        if input_handler.is_key_just_pressed(KeyCode::Escape) {
-            self.paused = !self.paused;
+    /// Act on the events delivered this frame: count the player's kills and
+    /// feedings, play their sounds and particles, and announce the rest
+    fn dispatch_events(&mut self, delta_time: f32) {
+        ObjectivesSystem::record_events(
+            self.events.events(),
+            self.player_id,
+            &mut self.kills,
+            &mut self.feeding_count,
+        );
+        if let Some(level) = ProgressionSystem::record_events(
+            self.events.events(),
+            &mut self.entities,
+            self.player_id,
+        ) {
+            self.add_debug_message(format!(
+                "You reach level {}. Spend your skill points in the skill tree (O).",
+                level
+            ));
         }
-
-        if input_handler.is_key_just_pressed(KeyCode::Tab) {
-            self.show_clan_menu = !self.show_clan_menu;
+        let quest_events = QuestSystem::record_events(
+            &mut self.quests,
+            self.events.events(),
+            &mut self.entities,
+            &mut self.clans,
+            &mut self.territories,
+            self.player_id,
+            self.time.day_count(),
+        );
+        for event in quest_events {
+            self.add_debug_message(event.get_message());
         }
 
-        if input_handler.is_key_just_pressed(KeyCode::L) {
-            self.show_legend = !self.show_legend;
+        let mut burning = Vec::new();
+        let mut messages = Vec::new();
+        for event in self.events.events() {
+            match event {
+                GameEvent::FedOnTarget { position, .. } => {
+                    self.sound_cues
+                        .push(SoundCue::at(SoundEffect::FeedingSquelch, *position));
+                    if self.video_settings.particles {
+                        ParticleSystem::emit(
+                            &mut self.particles,
+                            ParticleKind::Blood,
+                            *position,
+                            8,
+                        );
+                    }
+                }
+                GameEvent::SunlightBurned {
+                    entity_id,
+                    position,
+                } => {
+                    if *entity_id == self.player_id {
+                        self.sound_cues
+                            .push(SoundCue::at(SoundEffect::SunlightSizzle, *position));
+                    }
+                    burning.push(*position);
+                }
+                _ => messages.extend(event.get_message()),
+            }
         }
 
-        if input_handler.is_key_just_pressed(KeyCode::H) {
-            self.show_quick_start = !self.show_quick_start;
+        if self.video_settings.particles {
+            ParticleSystem::emit_embers(&mut self.particles, &burning, delta_time);
         }
-
-        // Close quick start guide on any movement
-        if self.show_quick_start
-            && (input_handler.is_key_pressed(KeyCode::W)
-                || input_handler.is_key_pressed(KeyCode::A)
-                || input_handler.is_key_pressed(KeyCode::S)
-                || input_handler.is_key_pressed(KeyCode::D))
-        {
-            self.show_quick_start = false;
+        for message in messages {
+            self.add_debug_message(message);
         }
     }
 
-    /// Update the time system
-    fn update_time_system(&mut self, delta_time: f32) {
-        self.time.update(delta_time);
+    /// Let the victim's clanmates judge a crime they may have seen
+    fn report_crime(&mut self, crime: Crime, victim_id: u32, position: Position) {
+        let blockers = StealthSystem::sight_blockers(&self.entities);
+        CrimeSystem::report(
+            &mut self.clans,
+            &mut self.entities,
+            &self.spatial_grid,
+            crime,
+            self.player_id,
+            victim_id,
+            position,
+            self.time.weather().sight_factor(),
+            &blockers,
+            &mut self.events,
+        );
     }
 
-    /// Update environmental elements
-    fn update_environment(&mut self, delta_time: f32) {
-        // Update stars
-        for star in &mut self.stars {
-            star.update(self.game_time);
+    /// Hand this frame's sound cues to the audio system
+    pub fn take_sound_cues(&mut self) -> Vec<SoundCue> {
+        std::mem::take(&mut self.sound_cues)
+    }
+
+    /// Update per-entity visual status (damage flashes, allied outlines, charms)
+    fn update_status_system(&mut self, delta_time: f32) {
+        StatusSystem::update_visual_states(&mut self.entities, &self.clans, delta_time);
+    }
+
+    /// Collect clan tribute once for each new day
+    fn update_tribute_system(&mut self) {
+        let current_day = self.time.day_count();
+        if current_day <= self.last_tribute_day {
+            return;
         }
+        self.last_tribute_day = current_day;
 
-        // Update moon
-        self.moon.update(self.game_time);
+        let events =
+            TributeSystem::collect_daily_tribute(&mut self.clans, &mut self.tribute_stockpile);
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
+    }
 
-        // Update blood particles
-        BloodSystem::update_blood_particles(&mut self.blood_particles, delta_time);
+    /// Keep each clan's members in the world in step with its rolls
+    fn update_clan_population(&mut self) {
+        let events = PopulationSystem::update(
+            &mut self.clan_roster,
+            &mut self.clans,
+            &mut self.entities,
+            &mut self.entity_ids,
+            self.time.day_count(),
+        );
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
     }
 
-    /// Update player-related systems
-    fn update_player_system(&mut self, input_handler: &InputHandler, delta_time: f32) {
-        // Handle player input and actions
-        PlayerSystem::handle_input(
+    /// Advance secret clan plots and surface their warnings
+    /// Advance territory capture and pay each new day's blood income
+    fn update_territory_system(&mut self, delta_time: f32) {
+        let events = TerritorySystem::update(
+            &mut self.territories,
+            &self.entities,
+            self.player_id,
+            self.time.hours_in(delta_time),
+        );
+        let income = TerritorySystem::collect_income(
+            &mut self.territories,
             &mut self.entities,
-            input_handler,
             self.player_id,
-            self.game_time,
+            self.time.day_count(),
         );
+        for event in events.into_iter().chain(income) {
+            self.add_debug_message(event.get_message());
+        }
+    }
 
-        // Update player movement
-        PlayerSystem::update_movement(
+    fn update_skirmishes(&mut self, delta_time: f32) {
+        let events = SkirmishSystem::update(
+            &mut self.skirmishes,
             &mut self.entities,
-            input_handler,
+            &self.spatial_grid,
             self.player_id,
-            self.time.is_day(),
+            self.game_time,
+            &mut self.damage_events,
             delta_time,
         );
-
-        // Handle shelter interaction
-        if input_handler.is_key_just_pressed(KeyCode::F) {
-            if let Some(message) = ShelterSystem::handle_player_shelter_interaction(
-                &mut self.entities,
-                self.player_id,
-                self.game_time,
-            ) {
-                self.add_debug_message(format!("Shelter: {}", message));
-            }
-        }
-
-        // Handle feeding attempts and update feeding counter
-        if input_handler.is_key_just_pressed(KeyCode::R) {
-            let mut debug_messages = Vec::new();
-            if let Some(feed_pos) = PlayerSystem::attempt_feeding(
-                &mut self.entities,
-                self.player_id,
-                &mut debug_messages,
-            ) {
-                self.feeding_count += 1;
-                debug_messages.push(format!(
-                    "FEEDING SUCCESS! Creating blood particles at ({}, {})",
-                    feed_pos.x, feed_pos.y
-                ));
-
-                // Create blood particle effects at the fed-upon entity's position
-                BloodSystem::create_blood_particles(
-                    &mut self.blood_particles,
-                    feed_pos.x,
-                    feed_pos.y,
-                    8,
-                    &mut debug_messages,
-                );
-            } else {
-                debug_messages.push("FEEDING FAILED - no target position returned".to_string());
-            }
-
-            // Add all debug messages after the feeding attempt
-            for message in debug_messages {
-                self.add_debug_message(message);
-            }
-        }
-
-        // Handle attack attempts and update kill counter
-        if input_handler.is_key_just_pressed(KeyCode::Space) {
-            if let Some(target_pos) =
-                PlayerSystem::attempt_attack(&mut self.entities, self.player_id, self.game_time)
-            {
-                self.kills += 1;
-
-                // Create blood particle effects at the attacked entity's position
-                let mut attack_debug_messages = Vec::new();
-                BloodSystem::create_blood_particles(
-                    &mut self.blood_particles,
-                    target_pos.x,
-                    target_pos.y,
-                    12, // More particles for combat
-                    &mut attack_debug_messages,
-                );
-                for message in attack_debug_messages {
-                    self.add_debug_message(message);
-                }
-            }
-        }
-
-        // Handle clan interactions
-        if input_handler.is_key_just_pressed(KeyCode::E) {
-            if let Some(clan_name) =
-                PlayerSystem::attempt_interaction(&mut self.entities, self.player_id)
-            {
-                self.interact_with_clan(&clan_name);
-            }
+        for event in events {
+            self.add_debug_message(event.get_message());
         }
     }
 
-    /// Update AI system for all NPCs
-    fn update_ai_system(&mut self, delta_time: f32) {
-        AISystem::update_all_ai(&mut self.entities, self.player_id, delta_time);
+    /// Carry on the clans' diplomacy and the raids between clans at war
+    fn update_clan_warfare(&mut self, delta_time: f32) {
+        let mut events = WarfareSystem::update_diplomacy(
+            &mut self.clan_relations,
+            &self.clans,
+            self.time.day_count(),
+        );
+        events.extend(WarfareSystem::update_clashes(
+            &mut self.clan_relations,
+            &mut self.clans,
+            &mut self.entities,
+            &self.territories,
+            self.player_id,
+            self.game_time,
+            &mut self.damage_events,
+            delta_time,
+        ));
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
     }
 
-    /// Update shelter system
-    fn update_shelter_system(&mut self, delta_time: f32) {
-        ShelterSystem::update_shelters(
+    /// Lead the assault under way, if any
+    fn update_assault(&mut self, delta_time: f32) {
+        let events = AssaultSystem::update(
+            &mut self.assaults,
+            &mut self.clans,
             &mut self.entities,
+            &mut self.territories,
+            &self.world_bounds,
+            self.player_id,
             self.game_time,
-            self.time.get_sunlight_intensity(),
+            &mut self.damage_events,
             delta_time,
         );
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
     }
 
-    /// Update blood system and related mechanics
-    fn update_blood_system(&mut self, delta_time: f32) {
-        BloodSystem::update_blood_system(
+    /// Pan the camera over to the latest skirmish on the minimap for a few
+    /// seconds, holding the player still meanwhile
+    fn watch_latest_skirmish(&mut self) {
+        let Some(skirmish) = self.skirmishes.latest() else {
+            self.add_debug_message("No fighting to watch.".to_string());
+            return;
+        };
+        self.cutscene = Some(Cutscene::new(
+            SystemPause {
+                player_input: true,
+                ..SystemPause::NONE
+            },
+            Some(skirmish.position),
+            3.0,
+        ));
+    }
+
+    fn update_rebellion_system(&mut self, delta_time: f32) {
+        let events = RebellionSystem::update(
+            &mut self.clans,
             &mut self.entities,
-            self.time.is_day(),
-            self.time.get_sunlight_intensity(),
+            self.player_id,
+            self.game_time,
             delta_time,
         );
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
     }
 
     /// Update objectives and check for completions
     fn update_objectives_system(&mut self) {
+        let already_completed = self.completed_objectives.len();
         ObjectivesSystem::check_objectives(
             &self.entities,
             self.player_id,
@@ -297,14 +2603,115 @@ impl GameState {
             &mut self.phase_objectives,
             &mut self.completed_objectives,
         );
+
+        for objective in &self.completed_objectives[already_completed..] {
+            self.events.push(GameEvent::ObjectiveCompleted {
+                objective: objective.clone(),
+            });
+        }
+
+        let day = self.time.day_count();
+        for objective in &self.completed_objectives {
+            self.objective_completion_days
+                .entry(objective.clone())
+                .or_insert(day);
+        }
+
+        let stats = self.objective_stats();
+        let events = HintSystem::update(
+            &mut self.objective_hints,
+            &self.phase_objectives,
+            &stats,
+            day,
+        );
+        for event in events {
+            self.add_debug_message(event.get_message());
+        }
+    }
+
+    /// Where each hint marker currently points
+    pub fn hint_marker_positions(&self) -> Vec<Position> {
+        self.objective_hints
+            .active_markers()
+            .into_iter()
+            .filter_map(|marker| {
+                HintSystem::marker_position(
+                    marker,
+                    &self.entities,
+                    self.player_id,
+                    &self.territories,
+                )
+            })
+            .collect()
+    }
+
+    /// Counts the quest journal measures objectives against
+    pub fn objective_stats(&self) -> ObjectiveStats {
+        ObjectiveStats {
+            day_count: self.time.day_count(),
+            kills: self.kills,
+            feeding_count: self.feeding_count,
+            allied_clans: self.clans.values().filter(|clan| clan.is_allied).count(),
+            defeated_clans: self.clans.values().filter(|clan| clan.is_defeated).count(),
+            total_clans: self.clans.len(),
+        }
+    }
+
+    /// Advance the guided first night, if one is running
+    fn update_onboarding(&mut self, met_leader: Option<&str>) {
+        let Some(first_night) = self.first_night.as_mut() else {
+            return;
+        };
+        let events = OnboardingSystem::update(
+            first_night,
+            &self.entities,
+            self.player_id,
+            &self.time,
+            self.feeding_count,
+            met_leader,
+        );
+
+        for event in events {
+            if event == OnboardingEvent::NightSurvived {
+                self.first_night = None;
+            }
+            let message = event.get_message();
+            if !message.is_empty() {
+                self.add_debug_message(message);
+            }
+        }
     }
 
     /// Update camera to follow player
-    fn update_camera(&mut self) {
-        if let Some(player) = EntityFinder::by_id(&self.entities, self.player_id) {
-            self.camera_x = player.position.x;
-            self.camera_y = player.position.y;
+    fn update_camera(&mut self, delta_time: f32) {
+        // The photo mode camera goes only where it is moved
+        if self.photo_mode.is_some() {
+            return;
         }
+        // A cutscene holds the camera; otherwise it rides with the crow
+        // while scouting
+        let (target, velocity) =
+            if let Some(focus) = self.cutscene.as_ref().and_then(|cutscene| cutscene.focus) {
+                (focus, None)
+            } else if let Some(scout) = &self.crow_scout {
+                (scout.position, None)
+            } else if let Some(player) = EntityFinder::by_id(&self.entities, self.player_id) {
+                (player.position, player.velocity)
+            } else {
+                return;
+            };
+
+        self.camera_rig.look_ahead = self.video_settings.camera_look_ahead;
+        let mut camera = Position::new(self.camera_x, self.camera_y);
+        CameraSystem::follow(
+            &mut self.camera_rig,
+            &mut camera,
+            target,
+            velocity.as_ref(),
+            delta_time,
+        );
+        self.camera_x = camera.x;
+        self.camera_y = camera.y;
     }
 
     /// Check for and handle phase progression
@@ -332,6 +2739,9 @@ impl GameState {
     /// Advance to the next game phase
     fn advance_to_phase(&mut self, new_phase: GamePhase) {
         self.phase = new_phase.clone();
+        self.events.push(GameEvent::PhaseAdvanced {
+            phase: new_phase.clone(),
+        });
 
         // Add new objectives for the new phase
         let mut new_objectives = ObjectivesSystem::get_initial_objectives(&new_phase);
@@ -340,15 +2750,63 @@ impl GameState {
 
     /// Handle clan interaction logic
     fn interact_with_clan(&mut self, clan_name: &str) {
-        if let Some(clan) = self.clans.get_mut(clan_name) {
-            clan.trust_towards_player += 0.1;
-            clan.trust_towards_player = clan.trust_towards_player.min(1.0);
-
-            // Check if clan should become allied
-            if clan.trust_towards_player > 0.7 && !clan.is_allied {
-                clan.is_allied = true;
+        // A clan plotting an ambush springs it at the meeting
+        if let (Some(clan), Some(player)) = (
+            self.clans.get_mut(clan_name),
+            EntityFinder::by_id(&self.entities, self.player_id),
+        ) {
+            let player_pos = player.position;
+            let ambushers = RebellionSystem::spring_ambush(
+                clan,
+                &mut self.entities,
+                &mut self.entity_ids,
+                player_pos,
+                WorldSystem::clan_member_color(clan_name),
+            );
+            if let Some(ambusher) = ambushers
+                .first()
+                .and_then(|id| EntityFinder::by_id(&self.entities, *id))
+            {
+                // Hold the player still while the camera shows who sprang it
+                self.cutscene = Some(Cutscene::new(
+                    SystemPause {
+                        player_input: true,
+                        ai: true,
+                        ..SystemPause::NONE
+                    },
+                    Some(ambusher.position),
+                    1.5,
+                ));
+                self.add_debug_message(format!(
+                    "AMBUSH! {} warriors of the {} attack at the meeting!",
+                    ambushers.len(),
+                    clan_name
+                ));
+                return;
             }
         }
+
+        // Otherwise the leader talks, first taking anything brought for them
+        let deliveries = QuestSystem::deliver(
+            &mut self.quests,
+            &mut self.entities,
+            &mut self.clans,
+            &mut self.territories,
+            self.player_id,
+            clan_name,
+            self.time.day_count(),
+        );
+        for event in deliveries {
+            self.add_debug_message(event.get_message());
+        }
+        self.active_dialogue = self.clans.get(clan_name).and_then(DialogueSystem::start);
+        if let Some(leader) = self
+            .active_dialogue
+            .as_ref()
+            .map(|dialogue| dialogue.leader_name.clone())
+        {
+            self.update_onboarding(Some(&leader));
+        }
     }
 
     /// Get player status for UI display
@@ -372,31 +2830,45 @@ impl GameState {
         )
     }
 
+    /// Top the player's health and blood back up while god mode is on
+    fn apply_god_mode(&mut self) {
+        if !self.god_mode {
+            return;
+        }
+        if let Some(player) = self.entities.iter_mut().find(|e| e.id == self.player_id) {
+            if let Some(health) = &mut player.health {
+                health.current = health.max;
+            }
+            if let Some(blood_meter) = &mut player.blood_meter {
+                blood_meter.current = blood_meter.maximum;
+            }
+        }
+    }
+
     /// Spawn a new entity using the world system
     pub fn spawn_entity(&mut self, entity_type: EntityType, x: f32, y: f32) -> Option<u32> {
         match entity_type {
             EntityType::HostileInfected => Some(WorldSystem::spawn_hostile_infected(
                 &mut self.entities,
-                &mut self.next_entity_id,
-                x,
-                y,
-            )),
-            EntityType::Animal => Some(WorldSystem::spawn_animal(
-                &mut self.entities,
-                &mut self.next_entity_id,
+                &mut self.entity_ids,
                 x,
                 y,
             )),
+            EntityType::Animal => {
+                let ground = WorldSystem::tile_type_at(&Position::new(x, y), self.world_seed);
+                Some(WorldSystem::spawn_animal(
+                    &mut self.entities,
+                    &mut self.entity_ids,
+                    WorldSystem::species_for_ground(ground, rand::gen_range(0.0, 1.0)),
+                    x,
+                    y,
+                ))
+            }
             EntityType::ClanMember(clan_name) => {
-                let color = match clan_name.as_str() {
-                    "Bone-Eaters" => LIGHTGRAY,
-                    "Flame-Haters" => VIOLET,
-                    "Night-Bloods" => BLUE,
-                    _ => WHITE,
-                };
+                let color = WorldSystem::clan_member_color(&clan_name);
                 Some(WorldSystem::spawn_clan_member(
                     &mut self.entities,
-                    &mut self.next_entity_id,
+                    &mut self.entity_ids,
                     &clan_name,
                     x,
                     y,
@@ -414,6 +2886,11 @@ impl GameState {
             .map_or(true, |health| health.current <= 0.0)
     }
 
+    /// Take the option confirmed on the game over screen, if any
+    pub fn take_game_over_choice(&mut self) -> Option<GameOverChoice> {
+        self.game_over.as_mut()?.chosen.take()
+    }
+
     /// Get survival statistics
     pub fn get_survival_stats(&self) -> SurvivalScore {
         BloodSystem::calculate_survival_score(self.feeding_count, self.time.day_count(), self.kills)
@@ -424,6 +2901,75 @@ impl GameState {
         ShelterSystem::get_nearby_shelter_info(&self.entities, self.player_id, 200.0)
     }
 
+    /// Everything crossed by the segment from `from` to `to`, nearest first
+    pub fn raycast(&self, from: Position, to: Position) -> Vec<RaycastHit> {
+        WorldQuery::raycast(
+            &self.entities,
+            &self.spatial_grid,
+            &self.ground_tiles,
+            from,
+            to,
+        )
+    }
+
+    /// Entities, shelters and ground tiles inside a circle or rectangle
+    pub fn query_area(&self, shape: QueryShape) -> AreaQuery {
+        WorldQuery::query_area(
+            &self.entities,
+            &self.spatial_grid,
+            &self.ground_tiles,
+            shape,
+        )
+    }
+
+    /// How far the player's noise carries where they stand
+    pub fn noise_profile(&self) -> Option<NoiseProfile> {
+        let player = EntityFinder::by_id(&self.entities, self.player_id)?;
+        Some(NoiseSystem::profile(
+            &player.position,
+            self.world_seed,
+            self.time.weather(),
+            self.time.is_night(),
+        ))
+    }
+
+    /// How hidden the player is from hostile eyes where they stand
+    pub fn stealth_profile(&self) -> Option<StealthProfile> {
+        let player = EntityFinder::by_id(&self.entities, self.player_id)?;
+        Some(StealthSystem::profile(
+            player,
+            self.time.is_night(),
+            self.get_player_sun_exposure(),
+        ))
+    }
+
+    /// Sneaking past hostiles without being set upon teaches shadow movement
+    fn practice_stealth(&mut self, unnoticed: bool, delta_time: f32) {
+        let Some(player_position) =
+            EntityFinder::by_id(&self.entities, self.player_id).map(|player| player.position)
+        else {
+            return;
+        };
+        let hostiles_near = self.entities.iter().any(|entity| {
+            entity.entity_type == EntityType::HostileInfected
+                && entity.health.as_ref().is_some_and(Health::is_alive)
+                && entity.position.distance_to(&player_position) <= STEALTH_PRACTICE_RANGE
+        });
+        if !unnoticed || !hostiles_near {
+            return;
+        }
+        self.stealth_practice += delta_time;
+        if self.stealth_practice >= stealth::PRACTICE_SECONDS {
+            self.stealth_practice = 0.0;
+            PlayerSystem::level_up_abilities(
+                &mut self.entities,
+                self.player_id,
+                ExperienceType::Stealth,
+            );
+            self.add_debug_message("You move more surely through the shadows.".to_string());
+        }
+    }
+
     /// Check if player is currently in shelter
     pub fn is_player_in_shelter(&self) -> bool {
         EntityFinder::by_id(&self.entities, self.player_id)
@@ -432,6 +2978,33 @@ impl GameState {
     }
 
     /// Get current shelter protection level for player
+    /// The shelter the player is inside, if any
+    /// Open build mode, unless the player is inside a shelter
+    fn enter_build_mode(&mut self) {
+        if self.player_shelter().is_some() {
+            self.add_debug_message("Step outside to build.".to_string());
+            return;
+        }
+        let Some(player) = EntityFinder::by_id(&self.entities, self.player_id) else {
+            return;
+        };
+        self.blueprint = Some(ConstructionSystem::begin(player.position));
+        self.auto_walk = None;
+        self.add_debug_message(
+            "Build mode: move the plan, E to build, U to put it away.".to_string(),
+        );
+    }
+
+    pub fn player_shelter(&self) -> Option<&Shelter> {
+        let shelter_id = EntityFinder::by_id(&self.entities, self.player_id)?
+            .shelter_occupancy
+            .as_ref()?
+            .shelter_id?;
+        EntityFinder::by_id(&self.entities, shelter_id)?
+            .shelter
+            .as_ref()
+    }
+
     pub fn get_player_shelter_protection(&self) -> f32 {
         let sunlight_damage = self.time.get_sunlight_intensity() * 100.0;
         let protected_damage = ShelterSystem::calculate_shelter_protection(
@@ -442,37 +3015,11 @@ impl GameState {
         1.0 - (protected_damage / sunlight_damage.max(1.0))
     }
 
-    /// Find the position of the target entity that would be attacked
-    fn find_attack_target_position(&self) -> Option<Position> {
-        let player_pos = self
-            .entities
-            .iter()
-            .find(|e| e.id == self.player_id)?
-            .position;
-
-        let attack_range = 60.0;
-
-        for entity in &self.entities {
-            if entity.id == self.player_id {
-                continue;
-            }
-
-            if matches!(entity.ai_state, AIState::Hostile) {
-                let distance = ((player_pos.x - entity.position.x).powi(2)
-                    + (player_pos.y - entity.position.y).powi(2))
-                .sqrt();
-
-                if distance <= attack_range {
-                    if let Some(health) = &entity.health {
-                        if health.current > 0.0 {
-                            return Some(entity.position);
-                        }
-                    }
-                }
-            }
-        }
-
-        None
+    /// Share of full sunlight reaching the player where they stand
+    pub fn get_player_sun_exposure(&self) -> f32 {
+        let shadows = ExposureSystem::cast_shadows(&self.entities, &self.time);
+        EntityFinder::by_id(&self.entities, self.player_id)
+            .map_or(1.0, |player| ExposureSystem::exposure(&shadows, player))
     }
 
     /// Add a debug message to the log
@@ -486,7 +3033,19 @@ impl GameState {
 
     /// Reset game to initial state
     pub fn reset(&mut self) {
-        *self = Self::new();
+        let video_settings = self.video_settings.clone();
+        let audio_settings = self.audio_settings.clone();
+        *self = Self::new_game(NewGameSettings {
+            start_mode: self.start_mode,
+            weaknesses: self.weakness_rules,
+            difficulty: self.difficulty,
+            player_name: std::mem::take(&mut self.player_name),
+            max_hostiles: self.spawn_director.max_hostiles,
+            seed: self.world_seed,
+        });
+        // Settings chosen in this session carry over to the next life
+        self.video_settings = video_settings;
+        self.audio_settings = audio_settings;
     }
 }
 
@@ -518,6 +3077,14 @@ mod tests {
             .trust_towards_player;
 
         game_state.interact_with_clan("Bone-Eaters");
+        let dialogue = game_state.active_dialogue.as_mut().unwrap();
+        assert_eq!(dialogue.leader_name, "Grimjaw");
+
+        // Offer an alliance, then show proof of strength
+        DialogueSystem::choose(dialogue, &mut game_state.clans);
+        let dialogue = game_state.active_dialogue.as_mut().unwrap();
+        let step = DialogueSystem::choose(dialogue, &mut game_state.clans);
+        assert!(matches!(step, DialogueStep::Ended { .. }));
 
         let new_trust = game_state
             .clans
@@ -545,4 +3112,45 @@ mod tests {
 
         assert!(game_state.is_game_over());
     }
+
+    #[test]
+    fn test_death_freezes_game_until_restart() {
+        let mut game_state = GameState::new();
+        game_state.show_quick_start = false;
+        let mut input = InputHandler::new();
+        input.update_with(Default::default(), Vec::new());
+
+        let player = game_state
+            .entities
+            .iter_mut()
+            .find(|e| e.id == game_state.player_id)
+            .unwrap();
+        player.health.as_mut().unwrap().current = 0.0;
+        game_state.update(&input, 0.016);
+        assert!(game_state.game_over.is_some());
+
+        // The world stays frozen behind the death screen
+        let game_time = game_state.game_time;
+        game_state.update(&input, 0.5);
+        assert_eq!(game_state.game_time, game_time);
+
+        // Input is ignored until the screen has faded in, then Down and E quit
+        input.update_with([KeyCode::E].into_iter().collect(), Vec::new());
+        game_state.update(&input, 0.016);
+        assert_eq!(game_state.take_game_over_choice(), None);
+
+        game_state.update(&input, 1.0);
+        input.update_with([KeyCode::Down].into_iter().collect(), Vec::new());
+        game_state.update(&input, 0.016);
+        input.update_with([KeyCode::E].into_iter().collect(), Vec::new());
+        game_state.update(&input, 0.016);
+        assert_eq!(
+            game_state.take_game_over_choice(),
+            Some(GameOverChoice::Quit)
+        );
+
+        game_state.reset();
+        assert!(game_state.game_over.is_none());
+        assert!(!game_state.is_game_over());
+    }
 }
@@ -0,0 +1,235 @@
+//! Headless Module
+//!
+//! Runs the game without a window: a scripted list of key events stands in
+//! for the keyboard, `GameState::update` is called at a fixed tick, and
+//! nothing is drawn. Integration tests use it to play whole days through
+//! and check what happened, e.g. that a vampire who never feeds starves.
+
+use crate::components::{GameEntity, Species};
+use crate::game_state::GameState;
+use crate::input::InputHandler;
+use crate::settings::NewGameSettings;
+use crate::systems::WorldSystem;
+use macroquad::prelude::KeyCode;
+use std::collections::HashSet;
+
+/// Seconds of game time per simulated tick
+pub const TICK_SECONDS: f32 = 1.0 / 60.0;
+
+/// A key going down or coming up on a given tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptEvent {
+    pub tick: u64,
+    pub key: KeyCode,
+    pub down: bool,
+}
+
+/// Key events to replay, in tick order
+#[derive(Debug, Clone, Default)]
+pub struct InputScript {
+    events: Vec<ScriptEvent>,
+}
+
+impl InputScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tap a key: down on `tick`, up on the next one
+    pub fn press(self, tick: u64, key: KeyCode) -> Self {
+        self.hold(tick, tick + 1, key)
+    }
+
+    /// Hold a key from `from` until it is released on `until`
+    pub fn hold(mut self, from: u64, until: u64, key: KeyCode) -> Self {
+        self.events.push(ScriptEvent {
+            tick: from,
+            key,
+            down: true,
+        });
+        self.events.push(ScriptEvent {
+            tick: until.max(from + 1),
+            key,
+            down: false,
+        });
+        // Stable, so a release and press on the same tick keep their order
+        self.events.sort_by_key(|event| event.tick);
+        self
+    }
+
+    pub fn events(&self) -> &[ScriptEvent] {
+        &self.events
+    }
+}
+
+/// A game run tick by tick without a window
+pub struct HeadlessGame {
+    pub game_state: GameState,
+    pub input: InputHandler,
+    script: InputScript,
+    /// Index of the next script event to apply
+    next_event: usize,
+    keys_down: HashSet<KeyCode>,
+    tick: u64,
+}
+
+impl HeadlessGame {
+    /// A new game with the quick start guide already dismissed, since it
+    /// pauses the world until a key is pressed
+    pub fn new(settings: NewGameSettings) -> Self {
+        let mut game_state = GameState::new_game(settings);
+        game_state.show_quick_start = false;
+        Self {
+            game_state,
+            input: InputHandler::new(),
+            script: InputScript::default(),
+            next_event: 0,
+            keys_down: HashSet::new(),
+            tick: 0,
+        }
+    }
+
+    pub fn with_script(mut self, script: InputScript) -> Self {
+        self.script = script;
+        self.next_event = 0;
+        self
+    }
+
+    /// Ticks simulated so far
+    pub fn ticks(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn player(&self) -> Option<&GameEntity> {
+        self.game_state
+            .entities
+            .iter()
+            .find(|entity| entity.id == self.game_state.player_id)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game_state.game_over.is_some()
+    }
+
+    /// Add creatures until `count` entities are alive, a mix of animals,
+    /// infected and clan members spread over the ground, for stress tests
+    pub fn populate(&mut self, count: usize) {
+        let state = &mut self.game_state;
+        let clans: Vec<String> = state.clans.keys().cloned().collect();
+        let mut spawned = 0usize;
+        while state.entities.len() < count {
+            // A spread that stays put between runs, so timings compare
+            let x = 40.0 + (spawned * 97 % 1520) as f32;
+            let y = 660.0 + (spawned * 53 % 520) as f32;
+            match spawned % 4 {
+                0 | 1 => {
+                    WorldSystem::spawn_animal(
+                        &mut state.entities,
+                        &mut state.entity_ids,
+                        Species::ALL[spawned % Species::ALL.len()],
+                        x,
+                        y,
+                    );
+                }
+                2 => {
+                    WorldSystem::spawn_hostile_infected(
+                        &mut state.entities,
+                        &mut state.entity_ids,
+                        x,
+                        y,
+                    );
+                }
+                _ => {
+                    // On the rolls and counted, or the clan would send them away
+                    let clan = &clans[spawned / 4 % clans.len()];
+                    let id = WorldSystem::spawn_clan_member(
+                        &mut state.entities,
+                        &mut state.entity_ids,
+                        clan,
+                        x,
+                        y,
+                        WorldSystem::clan_member_color(clan),
+                    );
+                    state.clan_roster.members.insert(id, clan.clone());
+                    if let Some(clan) = state.clans.get_mut(clan) {
+                        clan.member_count += 1;
+                    }
+                }
+            }
+            spawned += 1;
+        }
+        state.spatial_grid.rebuild(&state.entities);
+    }
+
+    /// Apply this tick's script events and advance the game by one tick
+    pub fn tick(&mut self) {
+        let events = self.script.events();
+        while let Some(event) = events.get(self.next_event) {
+            if event.tick > self.tick {
+                break;
+            }
+            if event.down {
+                self.keys_down.insert(event.key);
+            } else {
+                self.keys_down.remove(&event.key);
+            }
+            self.next_event += 1;
+        }
+        self.input.update_with(self.keys_down.clone(), Vec::new());
+        self.game_state.update(&self.input, TICK_SECONDS);
+        self.tick += 1;
+    }
+
+    /// Run up to `count` ticks, stopping early if the player dies.
+    /// Returns how many ticks ran.
+    pub fn simulate_ticks(&mut self, count: u64) -> u64 {
+        let start = self.tick;
+        while self.tick - start < count && !self.is_over() {
+            self.tick();
+        }
+        self.tick - start
+    }
+
+    /// Run until `days` more in-game days have passed or the player dies.
+    /// The clock stands still during a vision, so this gives up after
+    /// twice the ticks the days should take. Returns how many ticks ran.
+    pub fn simulate_days(&mut self, days: u32) -> u64 {
+        let start = self.tick;
+        let target = self.game_state.time.day_count() + days;
+        let ticks_per_day = (self.game_state.time.day_length() / TICK_SECONDS).ceil() as u64;
+        let limit = 2 * ticks_per_day * u64::from(days);
+        while self.game_state.time.day_count() < target
+            && !self.is_over()
+            && self.tick - start < limit
+        {
+            self.tick();
+        }
+        self.tick - start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_replays_key_events_on_their_ticks() {
+        let script = InputScript::new()
+            .press(2, KeyCode::L)
+            .hold(1, 4, KeyCode::D);
+        let mut game = HeadlessGame::new(NewGameSettings::default()).with_script(script);
+        let start_x = game.player().unwrap().position.x;
+
+        assert_eq!(game.simulate_ticks(2), 2);
+        assert!(game.input.is_key_pressed(KeyCode::D));
+        assert!(!game.game_state.show_legend);
+        game.tick();
+        assert!(game.input.is_key_just_pressed(KeyCode::L));
+        assert!(game.game_state.show_legend);
+
+        game.simulate_ticks(2);
+        assert!(!game.input.is_key_pressed(KeyCode::D));
+        assert_eq!(game.ticks(), 5);
+        assert!(game.player().unwrap().position.x > start_x);
+    }
+}
@@ -0,0 +1,419 @@
+//! Input Actions
+//!
+//! Maps abstract game actions to keyboard keys and gamepad buttons so that
+//! gameplay code asks "is Attack pressed?" instead of checking specific keys.
+
+use macroquad::prelude::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Abstract actions the player can perform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Sneak,
+    Feed,
+    Attack,
+    Interact,
+    Shelter,
+    Pause,
+    ClanMenu,
+    Legend,
+    Help,
+    Map,
+    TerritoryBorders,
+    WatchSkirmish,
+    Inventory,
+    Codex,
+    Journal,
+    SkillTree,
+    Waypoint,
+    AutoWalk,
+    DrinkVial,
+    VideoSettings,
+    PhotoMode,
+    ZoomIn,
+    ZoomOut,
+    ShadowDash,
+    BloodSense,
+    BatForm,
+    SpectralVision,
+    CrowScout,
+    BloodSight,
+    BloodLance,
+    Build,
+    AbilityInfo,
+    Recruit,
+    CommandFollow,
+    CommandHold,
+    CommandAttack,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+}
+
+impl InputAction {
+    /// Every action, in a stable order
+    pub const ALL: [InputAction; 44] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Sneak,
+        InputAction::Feed,
+        InputAction::Attack,
+        InputAction::Interact,
+        InputAction::Shelter,
+        InputAction::Pause,
+        InputAction::ClanMenu,
+        InputAction::Legend,
+        InputAction::Help,
+        InputAction::Map,
+        InputAction::TerritoryBorders,
+        InputAction::WatchSkirmish,
+        InputAction::Inventory,
+        InputAction::Codex,
+        InputAction::Journal,
+        InputAction::SkillTree,
+        InputAction::Waypoint,
+        InputAction::AutoWalk,
+        InputAction::DrinkVial,
+        InputAction::VideoSettings,
+        InputAction::PhotoMode,
+        InputAction::ZoomIn,
+        InputAction::ZoomOut,
+        InputAction::ShadowDash,
+        InputAction::BloodSense,
+        InputAction::BatForm,
+        InputAction::SpectralVision,
+        InputAction::CrowScout,
+        InputAction::BloodSight,
+        InputAction::BloodLance,
+        InputAction::Build,
+        InputAction::AbilityInfo,
+        InputAction::Recruit,
+        InputAction::CommandFollow,
+        InputAction::CommandHold,
+        InputAction::CommandAttack,
+        InputAction::MenuUp,
+        InputAction::MenuDown,
+        InputAction::MenuLeft,
+        InputAction::MenuRight,
+    ];
+
+    /// Name shown on the controls screen
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move up",
+            InputAction::MoveDown => "Move down",
+            InputAction::MoveLeft => "Move left",
+            InputAction::MoveRight => "Move right",
+            InputAction::Sneak => "Sneak (hold)",
+            InputAction::Feed => "Feed",
+            InputAction::Attack => "Attack",
+            InputAction::Interact => "Interact",
+            InputAction::Shelter => "Shelter",
+            InputAction::Pause => "Pause / Back",
+            InputAction::ClanMenu => "Clan menu",
+            InputAction::Legend => "Legend",
+            InputAction::Help => "Help",
+            InputAction::Map => "Map",
+            InputAction::TerritoryBorders => "Territory borders",
+            InputAction::WatchSkirmish => "Watch skirmish",
+            InputAction::Inventory => "Inventory",
+            InputAction::Codex => "Lore codex",
+            InputAction::Journal => "Quest journal",
+            InputAction::SkillTree => "Skill tree",
+            InputAction::Waypoint => "Cycle waypoint",
+            InputAction::AutoWalk => "Auto-walk",
+            InputAction::DrinkVial => "Drink vial",
+            InputAction::VideoSettings => "Video settings",
+            InputAction::PhotoMode => "Photo mode",
+            InputAction::ZoomIn => "Zoom in",
+            InputAction::ZoomOut => "Zoom out",
+            InputAction::ShadowDash => "Shadow Dash",
+            InputAction::BloodSense => "Blood Sense",
+            InputAction::BatForm => "Bat Form",
+            InputAction::SpectralVision => "Spectral Vision",
+            InputAction::CrowScout => "Crow Scout",
+            InputAction::BloodSight => "Blood Sight",
+            InputAction::BloodLance => "Blood Lance (aim with mouse)",
+            InputAction::Build => "Build mode",
+            InputAction::AbilityInfo => "Ability info (hold)",
+            InputAction::Recruit => "Recruit",
+            InputAction::CommandFollow => "Order: follow",
+            InputAction::CommandHold => "Order: hold",
+            InputAction::CommandAttack => "Order: attack",
+            InputAction::MenuUp => "Menu up",
+            InputAction::MenuDown => "Menu down",
+            InputAction::MenuLeft => "Menu left",
+            InputAction::MenuRight => "Menu right",
+        }
+    }
+}
+
+/// Buttons on a standard (Xbox-style layout) gamepad
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// A / Cross
+    South,
+    /// B / Circle
+    East,
+    /// X / Square
+    West,
+    /// Y / Triangle
+    North,
+    LeftBumper,
+    RightBumper,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// A single physical input that can trigger an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Button(GamepadButton),
+}
+
+/// Snapshot of a gamepad's state for the current frame.
+///
+/// Macroquad has no gamepad API, so a platform backend (`GamepadBackend`,
+/// with the `gamepad` feature) polls the controller and hands the snapshot
+/// to `InputHandler::set_gamepad_state` each frame.
+#[derive(Debug, Clone, Default)]
+pub struct GamepadState {
+    pub connected: bool,
+    pub buttons: HashSet<GamepadButton>,
+    /// Left analog stick, each axis in -1.0..=1.0 (positive y is down)
+    pub left_stick: (f32, f32),
+}
+
+/// Bindings from actions to keys and gamepad buttons
+#[derive(Debug, Clone)]
+pub struct ActionMap {
+    bindings: HashMap<InputAction, Vec<InputBinding>>,
+    /// Analog stick magnitude below which input is ignored
+    pub dead_zone: f32,
+}
+
+impl ActionMap {
+    /// Create an empty action map
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+            dead_zone: 0.2,
+        }
+    }
+
+    /// Add a binding for an action
+    pub fn bind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Remove all bindings for an action
+    pub fn clear(&mut self, action: InputAction) {
+        self.bindings.remove(&action);
+    }
+
+    /// Replace an action's keyboard keys, keeping its gamepad buttons
+    pub fn set_keys(&mut self, action: InputAction, keys: &[KeyCode]) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|binding| matches!(binding, InputBinding::Button(_)));
+        for key in keys {
+            if !bindings.contains(&InputBinding::Key(*key)) {
+                bindings.push(InputBinding::Key(*key));
+            }
+        }
+    }
+
+    /// Keyboard keys bound to an action
+    pub fn keys_for(&self, action: InputAction) -> Vec<KeyCode> {
+        self.bindings_for(action)
+            .iter()
+            .filter_map(|binding| match binding {
+                InputBinding::Key(key) => Some(*key),
+                InputBinding::Button(_) => None,
+            })
+            .collect()
+    }
+
+    /// Actions other than `action` that share one of its keys
+    pub fn conflicts_with(&self, action: InputAction) -> Vec<InputAction> {
+        let keys = self.keys_for(action);
+        InputAction::ALL
+            .into_iter()
+            .filter(|&other| {
+                other != action && self.keys_for(other).iter().any(|key| keys.contains(key))
+            })
+            .collect()
+    }
+
+    /// Get the bindings for an action
+    pub fn bindings_for(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings.get(&action).map_or(&[], |b| b.as_slice())
+    }
+
+    /// All keyboard keys referenced by any binding
+    pub fn bound_keys(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        self.bindings
+            .values()
+            .flatten()
+            .filter_map(|binding| match binding {
+                InputBinding::Key(key) => Some(*key),
+                InputBinding::Button(_) => None,
+            })
+    }
+
+    /// Check whether an action is active given the held keys and gamepad buttons
+    pub fn is_active(
+        &self,
+        action: InputAction,
+        keys: &HashSet<KeyCode>,
+        gamepad: &GamepadState,
+    ) -> bool {
+        self.bindings_for(action)
+            .iter()
+            .any(|binding| match binding {
+                InputBinding::Key(key) => keys.contains(key),
+                InputBinding::Button(button) => {
+                    gamepad.connected && gamepad.buttons.contains(button)
+                }
+            })
+    }
+
+    /// Apply a radial dead zone to an analog stick, rescaling the remaining
+    /// range so movement ramps smoothly from zero
+    pub fn apply_dead_zone(&self, stick: (f32, f32)) -> (f32, f32) {
+        let magnitude = (stick.0 * stick.0 + stick.1 * stick.1).sqrt();
+        if magnitude <= self.dead_zone || magnitude == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let scaled = ((magnitude - self.dead_zone) / (1.0 - self.dead_zone)).min(1.0);
+        (stick.0 / magnitude * scaled, stick.1 / magnitude * scaled)
+    }
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        use GamepadButton::*;
+        use InputAction::*;
+
+        let mut map = Self::empty();
+        let defaults = [
+            (MoveUp, KeyCode::W, Some(DPadUp)),
+            (MoveDown, KeyCode::S, Some(DPadDown)),
+            (MoveLeft, KeyCode::A, Some(DPadLeft)),
+            (MoveRight, KeyCode::D, Some(DPadRight)),
+            (Sneak, KeyCode::LeftShift, None),
+            (Feed, KeyCode::R, Some(West)),
+            (Attack, KeyCode::Space, Some(South)),
+            (Interact, KeyCode::E, Some(North)),
+            (Shelter, KeyCode::F, Some(East)),
+            (Pause, KeyCode::Escape, Some(Start)),
+            (ClanMenu, KeyCode::Tab, Some(Select)),
+            (Legend, KeyCode::L, None),
+            (Help, KeyCode::H, None),
+            (Map, KeyCode::M, Some(RightBumper)),
+            (TerritoryBorders, KeyCode::F4, None),
+            (WatchSkirmish, KeyCode::Y, None),
+            (Inventory, KeyCode::I, None),
+            (Codex, KeyCode::K, None),
+            (Journal, KeyCode::J, None),
+            (SkillTree, KeyCode::O, None),
+            (Waypoint, KeyCode::N, None),
+            (AutoWalk, KeyCode::T, None),
+            (DrinkVial, KeyCode::B, None),
+            (VideoSettings, KeyCode::V, Some(LeftBumper)),
+            (PhotoMode, KeyCode::F2, None),
+            (ZoomIn, KeyCode::Equal, None),
+            (ZoomIn, KeyCode::KpAdd, None),
+            (ZoomOut, KeyCode::Minus, None),
+            (ZoomOut, KeyCode::KpSubtract, None),
+            (ShadowDash, KeyCode::Key1, None),
+            (BloodSense, KeyCode::Key2, None),
+            (BatForm, KeyCode::Key3, None),
+            (SpectralVision, KeyCode::Key4, None),
+            (CrowScout, KeyCode::Key5, None),
+            (BloodSight, KeyCode::Key6, None),
+            (BloodLance, KeyCode::Key7, None),
+            (Build, KeyCode::U, None),
+            (AbilityInfo, KeyCode::LeftAlt, None),
+            (Recruit, KeyCode::G, None),
+            (CommandFollow, KeyCode::Z, None),
+            (CommandHold, KeyCode::X, None),
+            (CommandAttack, KeyCode::C, None),
+            (MenuUp, KeyCode::Up, Some(DPadUp)),
+            (MenuDown, KeyCode::Down, Some(DPadDown)),
+            (MenuLeft, KeyCode::Left, Some(DPadLeft)),
+            (MenuRight, KeyCode::Right, Some(DPadRight)),
+        ];
+
+        for (action, key, button) in defaults {
+            map.bind(action, InputBinding::Key(key));
+            if let Some(button) = button {
+                map.bind(action, InputBinding::Button(button));
+            }
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings_cover_keys_and_buttons() {
+        let map = ActionMap::default();
+        let attack = map.bindings_for(InputAction::Attack);
+        assert!(attack.contains(&InputBinding::Key(KeyCode::Space)));
+        assert!(attack.contains(&InputBinding::Button(GamepadButton::South)));
+    }
+
+    #[test]
+    fn test_action_active_from_either_device() {
+        let map = ActionMap::default();
+        let mut keys = HashSet::new();
+        let mut gamepad = GamepadState::default();
+
+        assert!(!map.is_active(InputAction::Feed, &keys, &gamepad));
+
+        keys.insert(KeyCode::R);
+        assert!(map.is_active(InputAction::Feed, &keys, &gamepad));
+
+        keys.clear();
+        gamepad.buttons.insert(GamepadButton::West);
+        // Buttons only count while a pad is connected
+        assert!(!map.is_active(InputAction::Feed, &keys, &gamepad));
+        gamepad.connected = true;
+        assert!(map.is_active(InputAction::Feed, &keys, &gamepad));
+    }
+
+    #[test]
+    fn test_dead_zone() {
+        let map = ActionMap::default();
+        assert_eq!(map.apply_dead_zone((0.1, 0.1)), (0.0, 0.0));
+
+        let (x, y) = map.apply_dead_zone((1.0, 0.0));
+        assert!((x - 1.0).abs() < 0.001);
+        assert_eq!(y, 0.0);
+
+        // Just past the dead zone movement starts near zero
+        let (x, _) = map.apply_dead_zone((0.3, 0.0));
+        assert!(x > 0.0 && x < 0.2);
+    }
+}
@@ -0,0 +1,147 @@
+//! Key Binding Config
+//!
+//! Reads and writes the keyboard half of the `ActionMap` as a JSON file so
+//! players can rebind actions and keep their layout between sessions.
+//! Gamepad buttons keep their defaults. Keys are written by name, e.g.
+//! `"Attack": ["Space"]`.
+
+use super::actions::{ActionMap, InputAction};
+use macroquad::prelude::KeyCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Where the key bindings are kept
+pub const BINDINGS_PATH: &str = "keybindings.json";
+
+/// Errors that can occur while reading or writing key bindings
+#[derive(Debug, Error)]
+pub enum BindingsError {
+    #[error("could not access key bindings: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("key bindings are not valid: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+macro_rules! key_names {
+    ($($key:ident),* $(,)?) => {
+        /// Keys that can be bound, with the names used in the config file
+        const KEY_NAMES: &[(KeyCode, &str)] = &[$((KeyCode::$key, stringify!($key))),*];
+    };
+}
+
+key_names! {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Key0, Key1, Key2,
+    Key3, Key4, Key5, Key6, Key7, Key8, Key9, Space, Enter, Escape, Tab, Backspace, Insert, Delete,
+    Up, Down, Left, Right, PageUp, PageDown, Home, End, Apostrophe, Comma, Minus, Period, Slash,
+    Semicolon, Equal, LeftBracket, Backslash, RightBracket, GraveAccent, F1, F2, F3, F4, F5, F6,
+    F7, F8, F9, F10, F11, F12, Kp0, Kp1, Kp2, Kp3, Kp4, Kp5, Kp6, Kp7, Kp8, Kp9, KpDecimal,
+    KpDivide, KpMultiply, KpSubtract, KpAdd, KpEnter, LeftShift, LeftControl, LeftAlt, RightShift,
+    RightControl, RightAlt,
+}
+
+/// Name of a bindable key, or None for keys that cannot be bound
+pub fn key_name(key: KeyCode) -> Option<&'static str> {
+    KEY_NAMES
+        .iter()
+        .find(|(code, _)| *code == key)
+        .map(|(_, name)| *name)
+}
+
+/// The key with the given config file name
+pub fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEY_NAMES
+        .iter()
+        .find(|(_, key_name)| key_name.eq_ignore_ascii_case(name))
+        .map(|(code, _)| *code)
+}
+
+/// The keyboard bindings of every action, as stored on disk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyBindings {
+    pub keys: BTreeMap<InputAction, Vec<String>>,
+}
+
+impl KeyBindings {
+    /// Record the keys currently bound in an action map
+    pub fn from_action_map(map: &ActionMap) -> Self {
+        let keys = InputAction::ALL
+            .into_iter()
+            .map(|action| {
+                let names = map
+                    .keys_for(action)
+                    .into_iter()
+                    .filter_map(key_name)
+                    .map(str::to_string)
+                    .collect();
+                (action, names)
+            })
+            .collect();
+        Self { keys }
+    }
+
+    /// Bind the recorded keys in an action map. Actions missing from the
+    /// file keep their current keys. Returns a warning for every key name
+    /// that was not recognised.
+    pub fn apply(&self, map: &mut ActionMap) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for (action, names) in &self.keys {
+            let keys: Vec<KeyCode> = names
+                .iter()
+                .filter_map(|name| {
+                    let key = key_from_name(name);
+                    if key.is_none() {
+                        warnings.push(format!(
+                            "Unknown key '{}' for {}",
+                            name,
+                            action.display_name()
+                        ));
+                    }
+                    key
+                })
+                .collect();
+            map.set_keys(*action, &keys);
+        }
+        warnings
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BindingsError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BindingsError> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_round_trip_through_json() {
+        let mut map = ActionMap::default();
+        map.set_keys(InputAction::Attack, &[KeyCode::LeftControl]);
+        let json = serde_json::to_string(&KeyBindings::from_action_map(&map)).unwrap();
+        assert!(json.contains(r#""Attack":["LeftControl"]"#));
+
+        let mut loaded: KeyBindings = serde_json::from_str(&json).unwrap();
+        loaded
+            .keys
+            .insert(InputAction::Feed, vec!["q".into(), "NoSuchKey".into()]);
+        let mut fresh = ActionMap::default();
+        let warnings = loaded.apply(&mut fresh);
+
+        assert_eq!(
+            fresh.keys_for(InputAction::Attack),
+            vec![KeyCode::LeftControl]
+        );
+        assert_eq!(fresh.keys_for(InputAction::Feed), vec![KeyCode::Q]);
+        assert_eq!(warnings.len(), 1);
+        // Rebinding a key leaves the gamepad button in place
+        assert_eq!(fresh.bindings_for(InputAction::Attack).len(), 2);
+    }
+}
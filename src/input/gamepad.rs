@@ -0,0 +1,72 @@
+//! Gamepad Backend
+//!
+//! Macroquad has no gamepad API, so controllers are read through gilrs.
+//! The backend is polled once per frame and its snapshot handed to
+//! `InputHandler::set_gamepad_state`. Only built with the `gamepad` cargo
+//! feature.
+
+use super::actions::{GamepadButton, GamepadState};
+use gilrs::{Axis, Button, Gilrs};
+
+/// Every button the action map can bind, with gilrs' name for it
+const BUTTONS: [(Button, GamepadButton); 12] = [
+    (Button::South, GamepadButton::South),
+    (Button::East, GamepadButton::East),
+    (Button::West, GamepadButton::West),
+    (Button::North, GamepadButton::North),
+    (Button::LeftTrigger, GamepadButton::LeftBumper),
+    (Button::RightTrigger, GamepadButton::RightBumper),
+    (Button::Select, GamepadButton::Select),
+    (Button::Start, GamepadButton::Start),
+    (Button::DPadUp, GamepadButton::DPadUp),
+    (Button::DPadDown, GamepadButton::DPadDown),
+    (Button::DPadLeft, GamepadButton::DPadLeft),
+    (Button::DPadRight, GamepadButton::DPadRight),
+];
+
+/// Reads the first connected controller
+pub struct GamepadBackend {
+    /// None when the platform's gamepad support could not be started
+    gilrs: Option<Gilrs>,
+}
+
+impl GamepadBackend {
+    pub fn new() -> Result<Self, Box<gilrs::Error>> {
+        Gilrs::new()
+            .map(|gilrs| Self { gilrs: Some(gilrs) })
+            .map_err(Box::new)
+    }
+
+    /// A backend that never reports a controller, for when `new` failed
+    pub fn disconnected() -> Self {
+        Self { gilrs: None }
+    }
+
+    /// Snapshot of the controller for this frame; disconnected when there
+    /// is none
+    pub fn poll(&mut self) -> GamepadState {
+        let Some(gilrs) = self.gilrs.as_mut() else {
+            return GamepadState::default();
+        };
+        // Pumping the events is what updates the cached button and axis state
+        while gilrs.next_event().is_some() {}
+
+        let Some((_, gamepad)) = gilrs.gamepads().find(|(_, gamepad)| gamepad.is_connected())
+        else {
+            return GamepadState::default();
+        };
+        GamepadState {
+            connected: true,
+            buttons: BUTTONS
+                .iter()
+                .filter(|(button, _)| gamepad.is_pressed(*button))
+                .map(|&(_, button)| button)
+                .collect(),
+            // gilrs points the stick's y axis up; the game's points down
+            left_stick: (
+                gamepad.value(Axis::LeftStickX),
+                -gamepad.value(Axis::LeftStickY),
+            ),
+        }
+    }
+}
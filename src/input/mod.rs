@@ -1,6 +1,23 @@
 //! Input Handler Module
 //!
 //! This module provides centralized input handling for the Vampire RPG.
+//! Gameplay code queries abstract actions, which are bound to both keyboard
+//! keys and gamepad buttons through an `ActionMap`. Every key macroquad
+//! reports is tracked, and a text input mode captures typed characters for
+//! text fields. Controllers are read by the `GamepadBackend` when the
+//! `gamepad` feature is enabled.
+
+pub mod actions;
+pub mod config;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod text;
+
+pub use actions::{ActionMap, GamepadButton, GamepadState, InputAction, InputBinding};
+pub use config::{key_name, BindingsError, KeyBindings, BINDINGS_PATH};
+#[cfg(feature = "gamepad")]
+pub use gamepad::GamepadBackend;
+pub use text::{TextInput, TextInputStatus};
 
 use macroquad::prelude::*;
 use std::collections::HashSet;
@@ -10,6 +27,30 @@ pub struct InputHandler {
     keys_just_pressed: HashSet<KeyCode>,
     keys_just_released: HashSet<KeyCode>,
     previous_keys: HashSet<KeyCode>,
+    action_map: ActionMap,
+    gamepad: GamepadState,
+    actions_pressed: HashSet<InputAction>,
+    actions_just_pressed: HashSet<InputAction>,
+    /// Actions whose keys are down, even while a text field suspends them
+    held_actions: HashSet<InputAction>,
+    /// Characters typed this frame, in order
+    frame_chars: Vec<char>,
+    /// Text field being edited; actions are suspended while it is open
+    text_input: Option<TextInput>,
+    /// Mouse wheel movement this frame
+    wheel: f32,
+    /// Cursor position in screen pixels
+    mouse: (f32, f32),
+    /// Window size in pixels, unknown (zero) until polled from macroquad
+    screen_size: (f32, f32),
+    /// Mouse buttons clicked this frame
+    clicks: HashSet<MouseButton>,
+    /// Mouse buttons held down
+    mouse_down: HashSet<MouseButton>,
+    /// Keep this frame's presses into the next, as nothing has read them yet
+    carry: bool,
+    /// Keys whose presses were carried over from an earlier frame
+    carried_keys: HashSet<KeyCode>,
 }
 
 impl InputHandler {
@@ -19,58 +60,269 @@ impl InputHandler {
             keys_just_pressed: HashSet::new(),
             keys_just_released: HashSet::new(),
             previous_keys: HashSet::new(),
+            action_map: ActionMap::default(),
+            gamepad: GamepadState::default(),
+            actions_pressed: HashSet::new(),
+            actions_just_pressed: HashSet::new(),
+            held_actions: HashSet::new(),
+            frame_chars: Vec::new(),
+            text_input: None,
+            wheel: 0.0,
+            mouse: (0.0, 0.0),
+            screen_size: (0.0, 0.0),
+            clicks: HashSet::new(),
+            mouse_down: HashSet::new(),
+            carry: false,
+            carried_keys: HashSet::new(),
         }
     }
 
+    /// Poll macroquad for this frame's keys and typed characters
     pub fn update(&mut self) {
-        // Clear just pressed/released from previous frame
-        self.keys_just_pressed.clear();
-        self.keys_just_released.clear();
+        let carry = self.carry;
+        let mut chars = Vec::new();
+        while let Some(character) = get_char_pressed() {
+            chars.push(character);
+        }
+        self.update_with(get_keys_down(), chars);
+        let wheel = mouse_wheel().1;
+        let buttons = [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+        let clicks = buttons
+            .into_iter()
+            .filter(|&button| is_mouse_button_pressed(button));
+        if carry {
+            self.wheel += wheel;
+            self.clicks.extend(clicks);
+        } else {
+            self.wheel = wheel;
+            self.clicks = clicks.collect();
+        }
+        self.mouse_down = buttons
+            .into_iter()
+            .filter(|&button| is_mouse_button_down(button))
+            .collect();
+        self.mouse = mouse_position();
+        self.screen_size = (screen_width(), screen_height());
+    }
+
+    /// Advance one frame from the given held keys and typed characters.
+    /// `update` feeds this from macroquad; it can also be driven directly.
+    pub fn update_with(&mut self, keys_down: HashSet<KeyCode>, chars: Vec<char>) {
+        let carried = std::mem::take(&mut self.carry).then(|| {
+            (
+                std::mem::take(&mut self.keys_just_pressed),
+                std::mem::take(&mut self.keys_just_released),
+                std::mem::take(&mut self.actions_just_pressed),
+            )
+        });
+
+        // Determine just pressed keys (in current but not in previous)
+        self.keys_just_pressed = keys_down.difference(&self.previous_keys).copied().collect();
+
+        // Determine just released keys (in previous but not in current)
+        self.keys_just_released = self.previous_keys.difference(&keys_down).copied().collect();
+
+        // Update state
+        self.keys_pressed = keys_down.clone();
+        self.previous_keys = keys_down;
+        self.frame_chars = chars;
 
-        // Get currently pressed keys
-        let mut current_keys = HashSet::new();
-
-        // Check all relevant keys
-        let keys_to_check = [
-            KeyCode::W,
-            KeyCode::A,
-            KeyCode::S,
-            KeyCode::D,
-            KeyCode::Space,
-            KeyCode::R,
-            KeyCode::E,
-            KeyCode::F,
-            KeyCode::Escape,
-            KeyCode::Tab,
-            KeyCode::L,
-            KeyCode::H,
-            KeyCode::Q,
-            KeyCode::LeftControl,
-        ];
-
-        for &key in &keys_to_check {
-            if is_key_down(key) {
-                current_keys.insert(key);
+        if let Some(text_input) = self.text_input.as_mut() {
+            text_input.type_chars(&self.frame_chars);
+            if self.keys_just_pressed.contains(&KeyCode::Backspace) {
+                text_input.backspace();
+            }
+            if self.keys_just_pressed.contains(&KeyCode::Enter)
+                || self.keys_just_pressed.contains(&KeyCode::KpEnter)
+            {
+                text_input.submit();
+            } else if self.keys_just_pressed.contains(&KeyCode::Escape) {
+                text_input.cancel();
             }
         }
 
-        // Determine just pressed keys (in current but not in previous)
-        for &key in &current_keys {
-            if !self.previous_keys.contains(&key) {
-                self.keys_just_pressed.insert(key);
+        self.update_actions();
+
+        self.carried_keys.clear();
+        if let Some((pressed, released, actions)) = carried {
+            self.carried_keys
+                .extend(pressed.difference(&self.keys_just_pressed));
+            self.keys_just_pressed.extend(pressed);
+            self.keys_just_released.extend(released);
+            if self.text_input.is_none() {
+                self.actions_just_pressed.extend(actions);
             }
         }
+    }
 
-        // Determine just released keys (in previous but not in current)
-        for &key in &self.previous_keys {
-            if !current_keys.contains(&key) {
-                self.keys_just_released.insert(key);
+    /// Hold this frame's presses, releases, clicks and wheel turns over to
+    /// the next frame. A fixed-timestep loop calls this on frames that ran
+    /// no simulation step, so quick taps between steps are not lost.
+    pub fn carry_presses(&mut self) {
+        self.carry = true;
+    }
+
+    /// Forget this frame's presses, releases, clicks and wheel turns once a
+    /// simulation step has seen them, so further steps in the same frame do
+    /// not act on them again. Held keys stay held.
+    pub fn consume_presses(&mut self) {
+        self.keys_just_pressed.clear();
+        self.keys_just_released.clear();
+        self.actions_just_pressed.clear();
+        self.clicks.clear();
+        self.wheel = 0.0;
+    }
+
+    /// Resolve which actions are held and newly pressed this frame
+    fn update_actions(&mut self) {
+        let current_actions: HashSet<InputAction> = InputAction::ALL
+            .into_iter()
+            .filter(|&action| {
+                self.action_map
+                    .is_active(action, &self.keys_pressed, &self.gamepad)
+            })
+            .collect();
+        let newly_pressed = current_actions
+            .difference(&self.held_actions)
+            .copied()
+            .collect();
+        self.held_actions = current_actions;
+
+        // Typing into a text field must not also move or attack. Held keys
+        // are still tracked so closing the field does not fire them.
+        if self.text_input.is_some() {
+            self.actions_pressed.clear();
+            self.actions_just_pressed.clear();
+        } else {
+            self.actions_pressed = self.held_actions.clone();
+            self.actions_just_pressed = newly_pressed;
+        }
+    }
+
+    /// Provide this frame's gamepad snapshot from the platform backend.
+    /// Call before `update` so button presses resolve into actions.
+    pub fn set_gamepad_state(&mut self, gamepad: GamepadState) {
+        self.gamepad = gamepad;
+    }
+
+    pub fn action_map(&self) -> &ActionMap {
+        &self.action_map
+    }
+
+    pub fn action_map_mut(&mut self) -> &mut ActionMap {
+        &mut self.action_map
+    }
+
+    pub fn is_action_pressed(&self, action: InputAction) -> bool {
+        self.actions_pressed.contains(&action)
+    }
+
+    pub fn is_action_just_pressed(&self, action: InputAction) -> bool {
+        self.actions_just_pressed.contains(&action)
+    }
+
+    /// Movement direction for this frame, each axis in -1.0..=1.0.
+    /// The analog stick wins when pushed past its dead zone; otherwise the
+    /// digital movement actions are used, normalized on diagonals.
+    pub fn movement_vector(&self) -> (f32, f32) {
+        if self.gamepad.connected {
+            let stick = self.action_map.apply_dead_zone(self.gamepad.left_stick);
+            if stick != (0.0, 0.0) {
+                return stick;
             }
         }
 
-        // Update state
-        self.keys_pressed = current_keys.clone();
-        self.previous_keys = current_keys;
+        let mut move_x: f32 = 0.0;
+        let mut move_y: f32 = 0.0;
+        if self.is_action_pressed(InputAction::MoveUp) {
+            move_y = -1.0;
+        }
+        if self.is_action_pressed(InputAction::MoveDown) {
+            move_y = 1.0;
+        }
+        if self.is_action_pressed(InputAction::MoveLeft) {
+            move_x = -1.0;
+        }
+        if self.is_action_pressed(InputAction::MoveRight) {
+            move_x = 1.0;
+        }
+
+        // Normalize diagonal movement
+        if move_x != 0.0 && move_y != 0.0 {
+            move_x *= 0.707; // 1/sqrt(2)
+            move_y *= 0.707;
+        }
+
+        (move_x, move_y)
+    }
+
+    /// Mouse wheel notches turned this frame: positive away from the player,
+    /// negative towards them. Platforms report different units per notch, so
+    /// only the direction is kept.
+    pub fn wheel_steps(&self) -> f32 {
+        if self.text_input.is_some() || self.wheel == 0.0 {
+            0.0
+        } else {
+            self.wheel.signum()
+        }
+    }
+
+    /// Where the cursor is on screen, in pixels
+    pub fn mouse_position(&self) -> (f32, f32) {
+        self.mouse
+    }
+
+    /// Window size in pixels, or `None` when driven without a window
+    pub fn screen_size(&self) -> Option<(f32, f32)> {
+        Some(self.screen_size).filter(|(width, height)| *width > 0.0 && *height > 0.0)
+    }
+
+    /// Whether a mouse button was clicked this frame; clicks are ignored
+    /// while a text field is open
+    pub fn is_mouse_clicked(&self, button: MouseButton) -> bool {
+        self.text_input.is_none() && self.clicks.contains(&button)
+    }
+
+    /// Whether a mouse button is held down, as when dragging
+    pub fn is_mouse_down(&self, button: MouseButton) -> bool {
+        self.text_input.is_none() && self.mouse_down.contains(&button)
+    }
+
+    /// Characters typed this frame, in order, whether or not a text field
+    /// is open
+    pub fn frame_chars(&self) -> &[char] {
+        &self.frame_chars
+    }
+
+    /// Open a text field, capturing typed characters until Enter submits or
+    /// Escape cancels. Actions are suspended while it is open.
+    pub fn begin_text_input(&mut self, initial: &str, max_len: usize) {
+        self.text_input = Some(TextInput::new(initial, max_len));
+        self.actions_pressed.clear();
+        self.actions_just_pressed.clear();
+    }
+
+    /// The text field being edited, if one is open
+    pub fn text_input(&self) -> Option<&TextInput> {
+        self.text_input.as_ref()
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        self.text_input.is_some()
+    }
+
+    /// Close the text field once it has been submitted or cancelled,
+    /// returning it so the caller can read the result
+    pub fn take_finished_text_input(&mut self) -> Option<TextInput> {
+        if self
+            .text_input
+            .as_ref()
+            .is_some_and(|text_input| text_input.status() != TextInputStatus::Editing)
+        {
+            self.text_input.take()
+        } else {
+            None
+        }
     }
 
     pub fn is_key_pressed(&self, key: KeyCode) -> bool {
@@ -81,6 +333,18 @@ impl InputHandler {
         self.keys_just_pressed.contains(&key)
     }
 
+    /// Keys first pressed this frame, in no particular order
+    pub fn keys_just_pressed(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        self.keys_just_pressed.iter().copied()
+    }
+
+    /// Whether a key went down this very frame, leaving out presses carried
+    /// over from frames that ran no simulation step. For keys handled once
+    /// per frame outside the simulation.
+    pub fn is_key_pressed_this_frame(&self, key: KeyCode) -> bool {
+        self.is_key_just_pressed(key) && !self.carried_keys.contains(&key)
+    }
+
     pub fn is_key_just_released(&self, key: KeyCode) -> bool {
         self.keys_just_released.contains(&key)
     }
@@ -95,3 +359,62 @@ impl Default for InputHandler {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(keys: &[KeyCode]) -> HashSet<KeyCode> {
+        keys.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_any_key_is_tracked() {
+        let mut input = InputHandler::new();
+        input.update_with(keys(&[KeyCode::F5, KeyCode::W]), Vec::new());
+        assert!(input.is_key_just_pressed(KeyCode::F5));
+        assert!(input.is_action_pressed(InputAction::MoveUp));
+
+        input.update_with(keys(&[KeyCode::W]), Vec::new());
+        assert!(input.is_key_just_released(KeyCode::F5));
+        assert!(!input.is_action_just_pressed(InputAction::MoveUp));
+    }
+
+    #[test]
+    fn test_text_input_suspends_actions() {
+        let mut input = InputHandler::new();
+        input.begin_text_input("", 10);
+
+        input.update_with(keys(&[KeyCode::W]), vec!['w']);
+        assert!(!input.is_action_pressed(InputAction::MoveUp));
+        assert_eq!(input.frame_chars(), &['w']);
+        assert_eq!(input.text_input().unwrap().text(), "w");
+        assert!(input.take_finished_text_input().is_none());
+
+        // Escape cancels without also pausing, even on the following frame
+        input.update_with(keys(&[KeyCode::Escape]), Vec::new());
+        let finished = input.take_finished_text_input().unwrap();
+        assert_eq!(finished.status(), TextInputStatus::Cancelled);
+        input.update_with(keys(&[KeyCode::Escape]), Vec::new());
+        assert!(!input.is_action_just_pressed(InputAction::Pause));
+        assert!(input.is_action_pressed(InputAction::Pause));
+    }
+
+    #[test]
+    fn test_presses_carry_over_until_a_step_consumes_them() {
+        let mut input = InputHandler::new();
+        input.update_with(keys(&[KeyCode::I]), Vec::new());
+        input.carry_presses();
+
+        // Still held next frame: the press is kept for the step that runs
+        input.update_with(keys(&[KeyCode::I]), Vec::new());
+        assert!(input.is_action_just_pressed(InputAction::Inventory));
+
+        // A second step in the same frame no longer sees it
+        input.consume_presses();
+        assert!(!input.is_action_just_pressed(InputAction::Inventory));
+        assert!(input.is_action_pressed(InputAction::Inventory));
+        input.update_with(keys(&[KeyCode::I]), Vec::new());
+        assert!(!input.is_action_just_pressed(InputAction::Inventory));
+    }
+}
@@ -0,0 +1,105 @@
+//! Text Input
+//!
+//! A single line text field fed by the characters typed each frame, used
+//! for naming characters and saves.
+
+/// Whether the player is still typing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextInputStatus {
+    Editing,
+    /// Enter was pressed
+    Submitted,
+    /// Escape was pressed
+    Cancelled,
+}
+
+/// A line of text being typed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextInput {
+    text: String,
+    max_len: usize,
+    status: TextInputStatus,
+}
+
+impl TextInput {
+    pub fn new(initial: &str, max_len: usize) -> Self {
+        Self {
+            text: initial.chars().take(max_len).collect(),
+            max_len,
+            status: TextInputStatus::Editing,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn status(&self) -> TextInputStatus {
+        self.status
+    }
+
+    /// Append typed characters, ignoring control characters and anything
+    /// past the length limit
+    pub fn type_chars(&mut self, chars: &[char]) {
+        if self.status != TextInputStatus::Editing {
+            return;
+        }
+        for &character in chars {
+            if character.is_control() || self.text.chars().count() >= self.max_len {
+                continue;
+            }
+            self.text.push(character);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if self.status == TextInputStatus::Editing {
+            self.text.pop();
+        }
+    }
+
+    pub fn submit(&mut self) {
+        if self.status == TextInputStatus::Editing {
+            self.status = TextInputStatus::Submitted;
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if self.status == TextInputStatus::Editing {
+            self.status = TextInputStatus::Cancelled;
+        }
+    }
+
+    /// The submitted text with surrounding spaces trimmed, or None if the
+    /// field was cancelled, is still open or was left blank
+    pub fn submitted_text(&self) -> Option<&str> {
+        let text = self.text.trim();
+        (self.status == TextInputStatus::Submitted && !text.is_empty()).then_some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_input_editing() {
+        let mut text_input = TextInput::new("Vlad", 6);
+        text_input.type_chars(&['i', 'm', 'i', 'r']);
+        assert_eq!(text_input.text(), "Vladim");
+
+        // Control characters never end up in the text
+        text_input.backspace();
+        text_input.type_chars(&['\r', '\u{8}', 'x']);
+        assert_eq!(text_input.text(), "Vladix");
+        assert_eq!(text_input.submitted_text(), None);
+
+        text_input.submit();
+        text_input.type_chars(&['y']);
+        assert_eq!(text_input.submitted_text(), Some("Vladix"));
+
+        let mut blank = TextInput::new("  ", 10);
+        blank.submit();
+        assert_eq!(blank.submitted_text(), None);
+    }
+}
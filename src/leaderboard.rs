@@ -0,0 +1,342 @@
+//! Leaderboard Module
+//!
+//! Score exports for community leaderboards. When the vampire meets the final
+//! death the run is written out as a signed payload: the final score, the
+//! settings the run was played under, and a hash chain of the snapshots taken
+//! at the start of every in-game day. Runs touched by the developer console
+//! are never signed. None of this is cryptographically
+//! strong, since the signing key ships inside the game, but it does stop
+//! hand-edited scores and settings from passing validation.
+
+use crate::settings::{Difficulty, NewGameSettings, WeaknessRules};
+use crate::systems::{BloodSystem, SpawnDirector, StartMode, SurvivalScore};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Score export format version
+pub const LEADERBOARD_VERSION: u32 = 2;
+
+/// Key the payload is signed with. Release builds set
+/// `VAMPIRE_LEADERBOARD_KEY` so their exports cannot be signed by dev builds.
+const SIGNING_KEY: &str = match option_env!("VAMPIRE_LEADERBOARD_KEY") {
+    Some(key) => key,
+    None => "vampire-rpg-development-key",
+};
+
+/// Highest hostile cap a run may claim; the new game screen never offers more
+pub const MAX_HOSTILES_LIMIT: usize = SpawnDirector::DEFAULT_MAX_HOSTILES * 4;
+
+/// Reasons an exported score fails validation
+#[derive(Debug, Error)]
+pub enum LeaderboardError {
+    #[error("could not access score file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("score file is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("score was exported by an incompatible version ({0})")]
+    Version(u32),
+    #[error("score signature does not match")]
+    Signature,
+    #[error("daily snapshot chain is broken at day {0}")]
+    Chain(u32),
+    #[error("claimed settings are not possible: {0}")]
+    Claims(String),
+    #[error("final score does not match the recorded run")]
+    Score,
+}
+
+/// FNV-1a over a sequence of byte slices, continuing from `seed`
+fn fnv1a(seed: u64, parts: &[&[u8]]) -> u64 {
+    let mut hash = seed;
+    for part in parts {
+        for byte in *part {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The settings a run claims to have been played under
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreClaims {
+    pub start_mode: StartMode,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    pub weaknesses: WeaknessRules,
+    pub max_hostiles: usize,
+}
+
+impl ScoreClaims {
+    pub fn from_settings(settings: &NewGameSettings) -> Self {
+        Self {
+            start_mode: settings.start_mode,
+            difficulty: settings.difficulty,
+            weaknesses: settings.weaknesses,
+            max_hostiles: settings.max_hostiles,
+        }
+    }
+
+    /// Start of the snapshot chain, so claims cannot be swapped after the fact
+    fn genesis(&self) -> u64 {
+        let claims = serde_json::to_vec(self).unwrap_or_default();
+        fnv1a(FNV_OFFSET, &[&claims])
+    }
+
+    fn validate(&self) -> Result<(), LeaderboardError> {
+        if self.max_hostiles == 0 || self.max_hostiles > MAX_HOSTILES_LIMIT {
+            return Err(LeaderboardError::Claims(format!(
+                "hostile cap of {} is outside 1-{}",
+                self.max_hostiles, MAX_HOSTILES_LIMIT
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Counts taken at the start of an in-game day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaySnapshot {
+    pub day: u32,
+    pub feedings: u32,
+    pub kills: u32,
+}
+
+/// The run's claims and its chain of daily snapshots, kept while playing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreLedger {
+    pub claims: ScoreClaims,
+    pub snapshots: Vec<DaySnapshot>,
+    /// Hash of the claims followed by every snapshot in order
+    pub chain_head: u64,
+    /// The developer console changed the run, so it cannot be exported
+    #[serde(default)]
+    pub console_used: bool,
+}
+
+impl ScoreLedger {
+    pub fn new(claims: ScoreClaims) -> Self {
+        let chain_head = claims.genesis();
+        Self {
+            claims,
+            snapshots: Vec::new(),
+            chain_head,
+            console_used: false,
+        }
+    }
+
+    fn link(previous: u64, snapshot: &DaySnapshot) -> u64 {
+        let snapshot = serde_json::to_vec(snapshot).unwrap_or_default();
+        fnv1a(previous, &[&snapshot])
+    }
+
+    /// Add the day's snapshot to the chain. Does nothing if the day has
+    /// already been recorded.
+    pub fn record(&mut self, day: u32, feedings: u32, kills: u32) {
+        if self
+            .snapshots
+            .last()
+            .is_some_and(|snapshot| snapshot.day >= day)
+        {
+            return;
+        }
+        let snapshot = DaySnapshot {
+            day,
+            feedings,
+            kills,
+        };
+        self.chain_head = Self::link(self.chain_head, &snapshot);
+        self.snapshots.push(snapshot);
+    }
+
+    /// Check the snapshots still hash to the recorded head and never go
+    /// backwards
+    fn validate(&self) -> Result<(), LeaderboardError> {
+        let mut head = self.claims.genesis();
+        let mut previous: Option<&DaySnapshot> = None;
+        for snapshot in &self.snapshots {
+            if let Some(previous) = previous {
+                if snapshot.day != previous.day + 1
+                    || snapshot.feedings < previous.feedings
+                    || snapshot.kills < previous.kills
+                {
+                    return Err(LeaderboardError::Chain(snapshot.day));
+                }
+            }
+            head = Self::link(head, snapshot);
+            previous = Some(snapshot);
+        }
+        if head != self.chain_head {
+            return Err(LeaderboardError::Chain(previous.map_or(0, |last| last.day)));
+        }
+        Ok(())
+    }
+}
+
+/// What a leaderboard receives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScorePayload {
+    pub version: u32,
+    pub player_name: String,
+    pub days_survived: u32,
+    pub total_feedings: u32,
+    pub total_kills: u32,
+    pub overall_score: f32,
+    pub ledger: ScoreLedger,
+}
+
+/// A score payload with its signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExport {
+    pub payload: ScorePayload,
+    pub signature: String,
+}
+
+impl ScoreExport {
+    /// Sign a finished run's score, unless the console was used on it
+    pub fn sign(player_name: &str, score: &SurvivalScore, ledger: &ScoreLedger) -> Option<Self> {
+        if ledger.console_used {
+            return None;
+        }
+        let payload = ScorePayload {
+            version: LEADERBOARD_VERSION,
+            player_name: player_name.to_string(),
+            days_survived: score.days_survived,
+            total_feedings: score.total_feedings,
+            total_kills: score.total_kills,
+            overall_score: score.overall_score,
+            ledger: ledger.clone(),
+        };
+        let signature = Self::signature_of(&payload);
+        Some(Self { payload, signature })
+    }
+
+    fn signature_of(payload: &ScorePayload) -> String {
+        let body = serde_json::to_vec(payload).unwrap_or_default();
+        let key = SIGNING_KEY.as_bytes();
+        format!("{:016x}", fnv1a(FNV_OFFSET, &[key, &body, key]))
+    }
+
+    /// Check the signature, the snapshot chain, the claimed settings, and
+    /// that the final score follows from the recorded counts
+    pub fn validate(&self) -> Result<(), LeaderboardError> {
+        let payload = &self.payload;
+        if payload.version != LEADERBOARD_VERSION {
+            return Err(LeaderboardError::Version(payload.version));
+        }
+        if Self::signature_of(payload) != self.signature {
+            return Err(LeaderboardError::Signature);
+        }
+        if payload.ledger.console_used {
+            return Err(LeaderboardError::Claims(
+                "the developer console was used".to_string(),
+            ));
+        }
+        payload.ledger.claims.validate()?;
+        payload.ledger.validate()?;
+
+        let expected = BloodSystem::calculate_survival_score(
+            payload.total_feedings,
+            payload.days_survived,
+            payload.total_kills,
+        );
+        let outran_snapshots = payload.ledger.snapshots.last().is_some_and(|last| {
+            last.day > payload.days_survived
+                || last.feedings > payload.total_feedings
+                || last.kills > payload.total_kills
+        });
+        if expected.overall_score != payload.overall_score || outran_snapshots {
+            return Err(LeaderboardError::Score);
+        }
+        Ok(())
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), LeaderboardError> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Read an exported score and validate it
+    pub fn load(path: &Path) -> Result<Self, LeaderboardError> {
+        let export: Self = serde_json::from_slice(&fs::read(path)?)?;
+        export.validate()?;
+        Ok(export)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tampered_exports_fail_validation() {
+        let mut ledger = ScoreLedger::new(ScoreClaims::from_settings(&NewGameSettings::default()));
+        ledger.record(1, 0, 0);
+        ledger.record(2, 3, 4);
+        ledger.record(2, 5, 5); // already recorded
+        ledger.record(3, 6, 9);
+        assert_eq!(ledger.snapshots.len(), 3);
+
+        let score = BloodSystem::calculate_survival_score(7, 3, 10);
+        let export = ScoreExport::sign("Vlad", &score, &ledger).unwrap();
+        assert!(export.validate().is_ok());
+
+        let mut inflated = export.clone();
+        inflated.payload.total_kills = 500;
+        assert!(matches!(
+            inflated.validate(),
+            Err(LeaderboardError::Signature)
+        ));
+
+        // Re-signing with edited snapshots still breaks the chain
+        let mut rewritten = ledger.clone();
+        rewritten.snapshots[1].kills = 1;
+        let rewritten = ScoreExport::sign("Vlad", &score, &rewritten).unwrap();
+        assert!(matches!(
+            rewritten.validate(),
+            Err(LeaderboardError::Chain(_))
+        ));
+
+        // So does claiming easier settings than the run started with
+        let mut easier = ledger.clone();
+        easier.claims.max_hostiles = 5;
+        let easier = ScoreExport::sign("Vlad", &score, &easier).unwrap();
+        assert!(matches!(easier.validate(), Err(LeaderboardError::Chain(_))));
+
+        // Or a harder difficulty than the run was played on
+        let mut harder = ledger.clone();
+        harder.claims.difficulty = Difficulty::Nightmare;
+        let harder = ScoreExport::sign("Vlad", &score, &harder).unwrap();
+        assert!(matches!(harder.validate(), Err(LeaderboardError::Chain(_))));
+
+        let mut impossible = ScoreLedger::new(ScoreClaims {
+            max_hostiles: MAX_HOSTILES_LIMIT + 1,
+            ..ledger.claims.clone()
+        });
+        impossible.record(1, 0, 0);
+        let impossible = ScoreExport::sign("Vlad", &score, &impossible).unwrap();
+        assert!(matches!(
+            impossible.validate(),
+            Err(LeaderboardError::Claims(_))
+        ));
+
+        // Console runs are not signed, nor accepted if marked afterwards
+        let mut cheated = ledger.clone();
+        cheated.console_used = true;
+        assert!(ScoreExport::sign("Vlad", &score, &cheated).is_none());
+        let mut marked = export.clone();
+        marked.payload.ledger.console_used = true;
+        marked.signature = ScoreExport::signature_of(&marked.payload);
+        assert!(matches!(
+            marked.validate(),
+            Err(LeaderboardError::Claims(_))
+        ));
+    }
+}
@@ -3,28 +3,71 @@
 //! This crate implements a complete vampire RPG with pixel art graphics,
 //! atmospheric environments, and survival mechanics.
 
+pub mod achievements;
+pub mod app;
+pub mod assets;
+pub mod audio;
+pub mod capture;
 pub mod components;
+pub mod console;
 pub mod game_state;
+pub mod headless;
 pub mod input;
+pub mod leaderboard;
+pub mod locale;
+pub mod profiler;
 pub mod rendering;
+pub mod save;
+pub mod settings;
+pub mod spectate;
 pub mod systems;
+pub mod theme;
+pub mod world_data;
 
 // Re-export commonly used types for convenience
+pub use achievements::{Achievement, AchievementError, AchievementLog, AchievementTracker};
+pub use app::{App, AppState, ControlsMenu, SettingsMenu, SettingsRow, TitleMenu, TitleOption};
+pub use assets::{AssetManager, AssetOutcome, AssetReport};
+pub use audio::{AmbientMixer, AmbientRegion, AudioSettings, AudioSystem, SoundCue, SoundEffect};
+pub use capture::{Capture, CaptureError, ClipFrame, ClipRecorder};
 pub use components::{
-    combat::{AIState, CombatStats},
-    entities::{GameEntity, Health, Position, Velocity},
-    environment::{BloodParticle, GroundTile, Moon, Star, TileType},
-    game_data::{Clan, EntityType, GamePhase},
+    combat::{AIState, CombatStats, FollowerOrder},
+    entities::{GameEntity, Health, Position, Velocity, VisualState},
+    environment::{FogOfWar, GroundTile, Moon, Star, TileType},
+    game_data::{Clan, EntityType, GamePhase, TributeLevel, TributeStockpile},
+    items::{Inventory, ItemKind, Pickup},
+    lifecycle::EntityAllocator,
     shelter::{Shelter, ShelterCondition, ShelterOccupancy, ShelterType},
-    vampire::{BloodMeter, VampireAbilities},
+    territory::{Territory, TerritoryManager},
+    vampire::{AbilityState, BloodMeter, Corpse, FeedingState, SpecialAbility, VampireAbilities},
 };
-pub use game_state::GameState;
-pub use input::InputHandler;
-pub use rendering::Renderer;
+pub use console::{ConsoleCommand, ConsoleError, DevConsole, SpawnKind};
+pub use game_state::{GameOver, GameOverChoice, GameState};
+pub use headless::{HeadlessGame, InputScript, ScriptEvent};
+pub use input::{ActionMap, GamepadButton, GamepadState, InputAction, InputHandler};
+pub use leaderboard::{LeaderboardError, ScoreExport, ScoreLedger};
+pub use locale::{Language, Strings};
+pub use profiler::{ProfileSection, Profiler, SectionStats};
+pub use rendering::{DrawCommand, DrawQueue, RenderLayer, Renderer};
+pub use save::{DamagedSave, Milestone, SaveData, SaveError, SaveManager, SaveReason};
+pub use settings::{
+    Difficulty, FpsMonitor, GraphicsPreset, NewGameSettings, UiScale, VideoSettings, WeaknessRules,
+};
+pub use spectate::{SpectateError, SpectatorClient, SpectatorFrame, SpectatorHost};
 pub use systems::{
-    AISystem, BloodStatus, BloodSystem, ObjectiveProgress, ObjectivesSystem, PlayerStatus,
-    PlayerSystem, ShelterInfo, ShelterSystem, TimeSystem, WorldSystem,
+    AISystem, AbilityEffect, AbilityResult, AbilitySystem, AbilityTooltip, ActiveDialogue,
+    AssaultSystem, BarkSystem, BestiarySystem, BloodStatus, BloodSystem, Blueprint, CalendarSystem,
+    CollisionSystem, CombatSystem, ConstructionEvent, ConstructionSystem, CrimeSystem, DamageEvent,
+    DialogueStep, DialogueSystem, Drain, DreamSystem, EffectsSystem, EventBus, GameEvent,
+    HintSystem, InteriorSystem, LifecycleSystem, LoreCodex, NavGrid, NoiseProfile, NoiseSystem,
+    ObjectiveProgress, ObjectivesSystem, OnboardingSystem, PathfindingSystem, PauseSystem,
+    PlayerStatus, PlayerSystem, PopulationSystem, ProgressionSystem, ProjectileSystem, QuestSystem,
+    ScheduleSystem, ScoutSystem, ShelterInfo, ShelterSystem, SpatialGrid, StartMode, StatusSystem,
+    StealthSystem, SystemPause, TerritoryEvent, TerritorySystem, TimeSystem, TrailSystem,
+    TributeEvent, TributeSystem, WarfareSystem, WeatherSystem, WorldQuery, WorldSystem,
 };
+pub use theme::{Palette, PaletteTheme, ThemeError};
+pub use world_data::{WorldData, WorldDataError};
 
 // Common imports for external use
 pub use macroquad::prelude::*;
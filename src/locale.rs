@@ -0,0 +1,148 @@
+//! Locale Module
+//!
+//! Translations of the menu and HUD text. Each language is a JSON table in
+//! `assets/locale/` mapping string keys such as `"pause.title"` to text,
+//! compiled into the game. Text may name placeholders in braces, like
+//! `"Day {day}"`, filled in by `Strings::format` so translations can put
+//! them wherever their grammar needs. A key missing from a table falls back
+//! to English, and a key missing from English shows as the key itself so
+//! the gap is easy to spot.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Languages selectable in settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The language's name in itself, so it can be found whatever the
+    /// current language is
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// The next language in `ALL`, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn embedded(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locale/en.json"),
+            Language::Spanish => include_str!("../assets/locale/es.json"),
+        }
+    }
+
+    /// The translation table as compiled into the game
+    pub fn table(&self) -> HashMap<String, String> {
+        serde_json::from_str(self.embedded()).expect("built-in translation table is valid")
+    }
+}
+
+/// Text for one language, with English behind it for anything untranslated
+#[derive(Debug, Clone)]
+pub struct Strings {
+    language: Language,
+    table: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Strings {
+    pub fn new(language: Language) -> Self {
+        let fallback = if language == Language::English {
+            HashMap::new()
+        } else {
+            Language::English.table()
+        };
+        Self {
+            language,
+            table: language.table(),
+            fallback,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The text for `key`
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.table
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// The text for `key` with each `{name}` placeholder replaced by its
+    /// value from `args`
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        args.iter()
+            .fold(self.get(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), &value.to_string())
+            })
+    }
+
+    /// "On" or "Off" in the current language
+    pub fn on_off(&self, enabled: bool) -> &str {
+        self.get(if enabled { "common.on" } else { "common.off" })
+    }
+}
+
+impl Default for Strings {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translations_cover_english_and_fill_placeholders() {
+        let english = Language::English.table();
+        for language in Language::ALL {
+            let table = language.table();
+            for (key, text) in &english {
+                let translated = table.get(key);
+                assert!(translated.is_some(), "{:?} is missing {}", language, key);
+                // Every placeholder English fills in must survive translation
+                for placeholder in text.split('{').skip(1).filter_map(|s| s.split_once('}')) {
+                    assert!(
+                        translated
+                            .unwrap()
+                            .contains(&format!("{{{}}}", placeholder.0)),
+                        "{:?} drops {{{}}} from {}",
+                        language,
+                        placeholder.0,
+                        key
+                    );
+                }
+            }
+        }
+
+        let spanish = Strings::new(Language::Spanish);
+        assert_eq!(spanish.get("pause.title"), "PAUSA");
+        assert_eq!(
+            spanish.format("clans.members", &[("count", &3)]),
+            "Miembros: 3".to_string()
+        );
+        assert_eq!(spanish.get("no.such.key"), "no.such.key");
+        assert_eq!(Language::Spanish.next(), Language::English);
+    }
+}
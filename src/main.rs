@@ -3,8 +3,29 @@
 //! A 2D vampire survival RPG built with Rust and Macroquad.
 
 use macroquad::prelude::*;
+use std::path::Path;
 
-use vampire_rpg::{GameState, InputHandler, Renderer};
+use vampire_rpg::components::EntityFinder;
+use vampire_rpg::console::{DevConsole, CONSOLE_ENABLED, CONSOLE_LINE_LEN};
+#[cfg(feature = "gamepad")]
+use vampire_rpg::input::GamepadBackend;
+use vampire_rpg::input::{KeyBindings, BINDINGS_PATH};
+use vampire_rpg::settings::{
+    random_seed, seed_from_text, FixedTimestep, FrameLimiter, FramePacing, NewGameField,
+    FRAME_PACING_PATH, MAX_PLAYER_NAME_LEN, MAX_SEED_LEN,
+};
+use vampire_rpg::spectate::DEFAULT_SPECTATE_PORT;
+use vampire_rpg::{
+    AmbientRegion, App, AppState, AssetManager, AudioSystem, GameOverChoice, GameState,
+    InputAction, InputHandler, NewGameSettings, Position, Renderer, SaveManager, SaveReason,
+    SpectatorClient, SpectatorHost, TitleMenu, TitleOption, WorldData, WorldSystem,
+};
+
+/// Free camera speed while spectating, in world units per second
+const SPECTATOR_CAMERA_SPEED: f32 = 600.0;
+
+/// The UI font inside the assets folder
+const FONT_PATH: &str = "fonts/default.ttf";
 
 /// Window configuration for the game
 fn window_conf() -> Conf {
@@ -15,10 +36,203 @@ fn window_conf() -> Conf {
         window_resizable: false,
         fullscreen: true,
         sample_count: 4,
+        // Vsync has to be chosen before the window opens
+        platform: miniquad::conf::Platform {
+            swap_interval: Some(
+                if FramePacing::load_or_default(Path::new(FRAME_PACING_PATH)).vsync {
+                    1
+                } else {
+                    0
+                },
+            ),
+            ..Default::default()
+        },
         ..Default::default()
     }
 }
 
+/// What the player did on the new game screen this frame
+enum NewGameInput {
+    None,
+    Start,
+    Back,
+}
+
+/// Handle one frame of the new game screen.
+/// Up/Down switches the start mode, Left/Right the difficulty, 1-3 toggle
+/// the vampire weaknesses, N names the vampire, T types a world seed and R
+/// rolls a new one, and Esc goes back to the title screen.
+fn update_new_game_screen(
+    settings: &mut NewGameSettings,
+    field: &mut NewGameField,
+    input_handler: &mut InputHandler,
+) -> NewGameInput {
+    // While a text field is open it takes all typing; the Enter or
+    // Esc that closes it must not also start the game
+    if let Some(text_input) = input_handler.take_finished_text_input() {
+        if let Some(text) = text_input.submitted_text() {
+            match field {
+                NewGameField::Name => settings.player_name = text.to_string(),
+                NewGameField::Seed => settings.seed = seed_from_text(text),
+            }
+        }
+        return NewGameInput::None;
+    }
+    if input_handler.is_text_input_active() {
+        return NewGameInput::None;
+    }
+    if input_handler.is_key_just_pressed(KeyCode::N) {
+        *field = NewGameField::Name;
+        input_handler.begin_text_input(&settings.player_name, MAX_PLAYER_NAME_LEN);
+        return NewGameInput::None;
+    }
+    if input_handler.is_key_just_pressed(KeyCode::T) {
+        *field = NewGameField::Seed;
+        input_handler.begin_text_input(&settings.seed.to_string(), MAX_SEED_LEN);
+        return NewGameInput::None;
+    }
+    if input_handler.is_key_just_pressed(KeyCode::R) {
+        settings.seed = random_seed();
+    }
+
+    if [
+        InputAction::MenuUp,
+        InputAction::MenuDown,
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+    ]
+    .into_iter()
+    .any(|action| input_handler.is_action_just_pressed(action))
+    {
+        settings.start_mode = settings.start_mode.toggled();
+    }
+    if input_handler.is_action_just_pressed(InputAction::MenuRight) {
+        settings.difficulty = settings.difficulty.next();
+    }
+    if input_handler.is_action_just_pressed(InputAction::MenuLeft) {
+        settings.difficulty = settings.difficulty.previous();
+    }
+    for (index, key) in [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3]
+        .into_iter()
+        .enumerate()
+    {
+        if input_handler.is_key_just_pressed(key) {
+            settings.weaknesses.toggle(index);
+        }
+    }
+    if input_handler.is_action_just_pressed(InputAction::Pause) {
+        return NewGameInput::Back;
+    }
+    if input_handler.is_key_just_pressed(KeyCode::Enter)
+        || input_handler.is_action_just_pressed(InputAction::Interact)
+    {
+        return NewGameInput::Start;
+    }
+    NewGameInput::None
+}
+
+/// How this instance takes part in a stream, chosen on the command line:
+/// `--host [addr]` streams the run, `--spectate <addr>` watches one
+enum NetworkRole {
+    Solo,
+    Host(String),
+    Spectate(String),
+}
+
+impl NetworkRole {
+    fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let value_after = |index: usize| args.get(index + 1).filter(|arg| !arg.starts_with("--"));
+        for (index, arg) in args.iter().enumerate() {
+            match arg.as_str() {
+                "--host" => {
+                    let addr = value_after(index)
+                        .cloned()
+                        .unwrap_or_else(|| format!("0.0.0.0:{}", DEFAULT_SPECTATE_PORT));
+                    return NetworkRole::Host(addr);
+                }
+                "--spectate" => {
+                    let addr = value_after(index)
+                        .cloned()
+                        .unwrap_or_else(|| format!("127.0.0.1:{}", DEFAULT_SPECTATE_PORT));
+                    return NetworkRole::Spectate(addr);
+                }
+                _ => {}
+            }
+        }
+        NetworkRole::Solo
+    }
+}
+
+/// Watch a friend's run: show the latest snapshot the host sent, with a
+/// free camera and no way to affect the game
+async fn run_spectator(addr: &str, renderer: &mut Renderer, input_handler: &mut InputHandler) {
+    let mut game_state = GameState::new();
+    game_state.show_quick_start = false;
+    let mut client = match SpectatorClient::connect(addr) {
+        Ok(client) => Some(client),
+        Err(error) => {
+            game_state.add_debug_message(format!("Could not connect to {}: {}", addr, error));
+            None
+        }
+    };
+    let mut status = match client {
+        Some(_) => format!("waiting for {}", addr),
+        None => format!("could not connect to {}", addr),
+    };
+    let mut following = true;
+
+    loop {
+        let delta_time = get_frame_time().min(0.1);
+        input_handler.update();
+        if input_handler.is_quit_requested() {
+            break;
+        }
+
+        if let Some(connection) = client.as_mut() {
+            match connection.poll() {
+                Ok(Some(frame)) => {
+                    frame.apply(&mut game_state);
+                    status = format!("watching {}", addr);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    status = format!("{} ({})", error, addr);
+                    client = None;
+                }
+            }
+        }
+
+        // Free camera; Space snaps back to following the host's player
+        let (move_x, move_y) = input_handler.movement_vector();
+        if (move_x, move_y) != (0.0, 0.0) {
+            following = false;
+            game_state.camera_x += move_x * SPECTATOR_CAMERA_SPEED * delta_time;
+            game_state.camera_y += move_y * SPECTATOR_CAMERA_SPEED * delta_time;
+        }
+        if input_handler.is_action_just_pressed(InputAction::Attack) {
+            following = true;
+        }
+        if following {
+            if let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) {
+                game_state.camera_x = player.position.x;
+                game_state.camera_y = player.position.y;
+            }
+        }
+        WorldSystem::stream_terrain(
+            &mut game_state.ground_tiles,
+            &mut game_state.terrain_chunks,
+            &mut game_state.world_bounds,
+            Position::new(game_state.camera_x, game_state.camera_y),
+            game_state.world_seed,
+        );
+
+        renderer.render(&game_state);
+        renderer.draw_spectator_overlay(&status, following);
+        next_frame().await;
+    }
+}
+
 #[macroquad::main(window_conf)]
 async fn main() {
     // Initialize random seed
@@ -26,41 +240,100 @@ async fn main() {
 
     // Removed "Initializing..." screen for faster startup
 
-    // Create game state and systems
-    let mut game_state = GameState::new();
     let mut input_handler = InputHandler::new();
+    let mut startup_messages = Vec::new();
+
+    // Player key bindings replace the defaults when present
+    let bindings_path = Path::new(BINDINGS_PATH);
+    if bindings_path.exists() {
+        match KeyBindings::load(bindings_path) {
+            Ok(bindings) => {
+                startup_messages.extend(bindings.apply(input_handler.action_map_mut()));
+                startup_messages.push(format!("Key bindings loaded from {}", BINDINGS_PATH));
+            }
+            Err(error) => startup_messages.push(format!("Using default keys: {}", error)),
+        }
+    }
+
+    #[cfg(feature = "gamepad")]
+    let mut gamepad = GamepadBackend::new().unwrap_or_else(|error| {
+        startup_messages.push(format!("Gamepad support unavailable: {}", error));
+        GamepadBackend::disconnected()
+    });
 
     // Track fullscreen state (starts as true, using macroquad's native fullscreen)
     let mut is_fullscreen = true;
 
-    // Embed font data directly in binary for reliable loading
-    let font_data: &[u8] = include_bytes!("../assets/fonts/default.ttf");
-    let font = match load_ttf_font_from_bytes(font_data) {
-        Ok(font) => {
-            game_state.add_debug_message("Font loaded successfully from embedded data".to_string());
-            Some(font)
-        }
-        Err(e) => {
-            game_state.add_debug_message(format!("Warning: Could not load embedded font: {}", e));
-            game_state.add_debug_message("Using default system font".to_string());
-            None
-        }
-    };
+    // Fonts, sounds and sprites are read from the assets folder, falling
+    // back to built-in defaults for anything that is missing
+    let mut assets = AssetManager::default();
+    let font = assets
+        .read(FONT_PATH, "the default font")
+        .and_then(|font_data| match load_ttf_font_from_bytes(&font_data) {
+            Ok(font) => Some(font),
+            Err(error) => {
+                assets.mark_invalid(FONT_PATH, error);
+                None
+            }
+        });
 
     let mut renderer = Renderer::new(font);
+    renderer.load_atlas(&mut assets);
+
+    // Creature, clan and shelter tables, replaceable by mods
+    WorldData::load(&mut assets).install();
+
+    let network_role = NetworkRole::from_args();
+    if let NetworkRole::Spectate(addr) = &network_role {
+        run_spectator(addr, &mut renderer, &mut input_handler).await;
+        return;
+    }
+
+    let mut audio = AudioSystem::load(&mut assets).await;
+    let missing_assets = assets.problems().count();
+    if missing_assets > 0 {
+        startup_messages.push(format!(
+            "{} asset(s) could not be loaded - see Settings > Assets",
+            missing_assets
+        ));
+    }
+
+    // Stream the run to spectators when hosting
+    let mut spectator_host = match &network_role {
+        NetworkRole::Host(addr) => match SpectatorHost::bind(addr.as_str()) {
+            Ok(host) => {
+                startup_messages.push(format!("Streaming to spectators on {}", addr));
+                Some(host)
+            }
+            Err(error) => {
+                startup_messages.push(format!("Could not host on {}: {}", addr, error));
+                None
+            }
+        },
+        NetworkRole::Solo | NetworkRole::Spectate(_) => None,
+    };
 
     // Add debug message about fullscreen mode
-    game_state
-        .add_debug_message("Game started in fullscreen mode (F11 to toggle windowed)".to_string());
+    startup_messages.push("Game started in fullscreen mode (F11 to toggle windowed)".to_string());
+
+    // Start on the title screen; the game itself exists once one is started
+    let save_manager = SaveManager::default();
+    let mut app = App::new(save_manager.latest_save().is_some());
+    app.damaged_saves = save_manager.damaged_saves();
+    if !app.damaged_saves.is_empty() {
+        app.state = AppState::RestoreSave;
+    }
+    app.video_settings.frame_pacing = FramePacing::load_or_default(Path::new(FRAME_PACING_PATH));
+    let mut frame_limiter = FrameLimiter::new();
+    let mut timestep = FixedTimestep::default();
+    let mut game_state: Option<GameState> = None;
 
     let mut last_time = get_time();
     let mut frame_count = 0;
     let mut fps_timer = 0.0;
 
-    // Main game loop
+    // Main loop
     loop {
-        let frame_start = get_time();
-
         // Calculate delta time
         let current_time = get_time();
         let delta_time = (current_time - last_time) as f32;
@@ -69,80 +342,398 @@ async fn main() {
         // Cap delta time to prevent large jumps (allow for frame drops/pauses)
         let delta_time = delta_time.min(0.1); // Max 100ms to handle pauses gracefully
 
-        // Update FPS counter and delta time monitoring
-        frame_count += 1;
-        fps_timer += delta_time;
-        if fps_timer >= 1.0 {
-            let fps = frame_count as f32 / fps_timer;
-            let perf_mode = if renderer.performance_mode() {
-                "PERF"
-            } else {
-                "NORM"
-            };
-
-            // Get player speed for monitoring
-            let player_speed = game_state
-                .entities
-                .iter()
-                .find(|e| {
-                    matches!(
-                        e.entity_type,
-                        vampire_rpg::components::game_data::EntityType::Player
-                    )
-                })
-                .and_then(|p| p.velocity.as_ref())
-                .map(|v| (v.x.powi(2) + v.y.powi(2)).sqrt())
-                .unwrap_or(0.0);
-
-            game_state.add_debug_message(format!(
-                "FPS: {:.1} | DT: {:.4}s | {} | Speed: {:.0}",
-                fps, delta_time, perf_mode, player_speed
-            ));
-            frame_count = 0;
-            fps_timer = 0.0;
-        }
-
         // Handle input
+        #[cfg(feature = "gamepad")]
+        input_handler.set_gamepad_state(gamepad.poll());
         input_handler.update();
 
+        // Handle window close
+        if input_handler.is_quit_requested() {
+            break;
+        }
+
         // Handle fullscreen toggle with F11
-        if is_key_pressed(KeyCode::F11) {
+        if input_handler.is_key_pressed_this_frame(KeyCode::F11) {
             is_fullscreen = !is_fullscreen;
             set_fullscreen(is_fullscreen);
 
-            if is_fullscreen {
-                game_state.add_debug_message("Switched to fullscreen mode".to_string());
-            } else {
-                game_state.add_debug_message("Switched to windowed mode".to_string());
+            if let Some(game_state) = game_state.as_mut() {
+                if is_fullscreen {
+                    game_state.add_debug_message("Switched to fullscreen mode".to_string());
+                } else {
+                    game_state.add_debug_message("Switched to windowed mode".to_string());
+                }
             }
         }
 
-        // Handle performance mode toggle with P key
-        if is_key_pressed(KeyCode::P) {
-            let current_mode = renderer.performance_mode();
-            renderer.set_performance_mode(!current_mode);
-            if !current_mode {
-                game_state.add_debug_message("Performance mode enabled".to_string());
-            } else {
-                game_state.add_debug_message("Performance mode disabled".to_string());
+        // Menus follow the settings screen; a game in progress scales and
+        // translates by its own copy when rendered
+        renderer.update_ui_scaling(app.video_settings.ui_scale);
+        renderer.update_language(app.video_settings.language);
+
+        match app.state {
+            AppState::MainMenu => {
+                match app.title_menu.handle_input(&input_handler) {
+                    Some(TitleOption::NewGame) => app.state = AppState::NewGame,
+                    Some(TitleOption::Continue) => match save_manager.latest_save() {
+                        Some(data) => {
+                            // Continued games skip the guided start; the save
+                            // carries its own weaknesses and name
+                            let mut continued = GameState::new_game(NewGameSettings::default());
+                            data.restore(&mut continued);
+                            continued.show_quick_start = false;
+                            app.apply_settings(&mut continued);
+                            for message in startup_messages.drain(..) {
+                                continued.add_debug_message(message);
+                            }
+                            continued
+                                .add_debug_message("Continuing from the last save".to_string());
+                            game_state = Some(continued);
+                            app.state = AppState::Playing;
+                        }
+                        None => app.title_menu = TitleMenu::new(false),
+                    },
+                    Some(TitleOption::Settings) => app.state = AppState::Settings,
+                    Some(TitleOption::Quit) => break,
+                    None => {}
+                }
+                renderer.draw_title_screen(&app.title_menu, missing_assets);
+            }
+            AppState::NewGame => {
+                match update_new_game_screen(
+                    &mut app.new_game,
+                    &mut app.new_game_field,
+                    &mut input_handler,
+                ) {
+                    NewGameInput::Start => {
+                        let mut new_game = GameState::new_game(app.new_game.clone());
+                        // The next new game gets a world of its own
+                        app.new_game.seed = random_seed();
+                        app.apply_settings(&mut new_game);
+                        for message in startup_messages.drain(..) {
+                            new_game.add_debug_message(message);
+                        }
+                        game_state = Some(new_game);
+                        app.state = AppState::Playing;
+                    }
+                    NewGameInput::Back => app.state = AppState::MainMenu,
+                    NewGameInput::None => {}
+                }
+                renderer.draw_start_menu(
+                    &app.new_game,
+                    input_handler
+                        .text_input()
+                        .map(|text_input| (app.new_game_field, text_input.text())),
+                );
+            }
+            AppState::Settings => {
+                if app.settings_menu.handle_input(
+                    &input_handler,
+                    &mut app.video_settings,
+                    &mut app.audio_settings,
+                    &mut app.new_game.difficulty,
+                ) {
+                    let pacing = app.video_settings.frame_pacing;
+                    if let Err(error) = pacing.save(Path::new(FRAME_PACING_PATH)) {
+                        eprintln!("Could not save frame pacing: {}", error);
+                    }
+                    app.state = AppState::MainMenu;
+                }
+                if app.settings_menu.take_open_controls() {
+                    app.state = AppState::Controls;
+                }
+                if app.settings_menu.take_open_diagnostics() {
+                    app.state = AppState::Diagnostics;
+                }
+                renderer.draw_settings_screen(
+                    &app.settings_menu,
+                    &app.video_settings,
+                    &app.audio_settings,
+                    app.new_game.difficulty,
+                );
+            }
+            AppState::Controls => {
+                if app.controls_menu.handle_input(&mut input_handler) {
+                    let bindings = KeyBindings::from_action_map(input_handler.action_map());
+                    app.controls_menu.status =
+                        Some(match bindings.save(Path::new(BINDINGS_PATH)) {
+                            Ok(()) => format!("Key bindings saved to {}", BINDINGS_PATH),
+                            Err(error) => format!("Could not save key bindings: {}", error),
+                        });
+                    app.state = AppState::Settings;
+                }
+                renderer.draw_controls_screen(&app.controls_menu, input_handler.action_map());
+            }
+            AppState::Diagnostics => {
+                if input_handler.is_key_just_pressed(KeyCode::Escape)
+                    || input_handler.is_key_just_pressed(KeyCode::Enter)
+                {
+                    app.state = AppState::Settings;
+                }
+                renderer.draw_diagnostics_screen(&assets);
+            }
+            AppState::RestoreSave => {
+                let restore = input_handler.is_key_just_pressed(KeyCode::Enter);
+                if restore || input_handler.is_key_just_pressed(KeyCode::Escape) {
+                    let damaged = app.damaged_saves.remove(0);
+                    if restore && damaged.backup.is_some() {
+                        startup_messages.push(match save_manager.restore_backup(&damaged) {
+                            Ok(_) => format!("Restored {} from its backup", damaged.slot_name()),
+                            Err(error) => {
+                                format!("Could not restore {}: {}", damaged.slot_name(), error)
+                            }
+                        });
+                    }
+                }
+                match app.damaged_saves.first() {
+                    Some(damaged) => {
+                        renderer.draw_restore_save_screen(damaged, app.damaged_saves.len())
+                    }
+                    None => {
+                        app.title_menu = TitleMenu::new(save_manager.latest_save().is_some());
+                        app.state = AppState::MainMenu;
+                    }
+                }
+            }
+            AppState::Playing | AppState::Paused | AppState::GameOver => {
+                let Some(game) = game_state.as_mut() else {
+                    app.state = AppState::MainMenu;
+                    next_frame().await;
+                    continue;
+                };
+                if run_game_frame(
+                    game,
+                    &mut app,
+                    &mut renderer,
+                    &mut audio,
+                    spectator_host.as_mut(),
+                    &mut input_handler,
+                    &mut timestep,
+                    delta_time,
+                    &mut frame_count,
+                    &mut fps_timer,
+                ) {
+                    app.return_to_title(game, save_manager.latest_save().is_some());
+                    game_state = None;
+                }
             }
         }
 
-        // Handle window close
-        if is_key_pressed(KeyCode::Q) && is_key_down(KeyCode::LeftControl) {
-            break;
+        // Hold to the frame cap, if any; vsync also paces next_frame()
+        let frame_cap = game_state
+            .as_ref()
+            .map_or(app.video_settings.frame_pacing, |game| {
+                game.video_settings.frame_pacing
+            })
+            .frame_cap;
+        frame_limiter.wait(frame_cap);
+
+        // Present frame
+        next_frame().await;
+    }
+}
+
+/// Run and draw one frame of a game in progress. The game simulates in
+/// fixed steps, as many as the frame's time pays for, and is drawn blended
+/// between its last two steps. Returns true when the player leaves for the
+/// title screen.
+#[allow(clippy::too_many_arguments)]
+fn run_game_frame(
+    game_state: &mut GameState,
+    app: &mut App,
+    renderer: &mut Renderer,
+    audio: &mut AudioSystem,
+    spectator_host: Option<&mut SpectatorHost>,
+    input_handler: &mut InputHandler,
+    timestep: &mut FixedTimestep,
+    delta_time: f32,
+    frame_count: &mut u32,
+    fps_timer: &mut f32,
+) -> bool {
+    // Update FPS counter and delta time monitoring
+    *frame_count += 1;
+    *fps_timer += delta_time;
+    if *fps_timer >= 1.0 {
+        let fps = *frame_count as f32 / *fps_timer;
+        let perf_mode = if renderer.performance_mode() {
+            "PERF"
+        } else {
+            "NORM"
+        };
+
+        // Get player speed for monitoring
+        let player_speed = game_state
+            .entities
+            .iter()
+            .find(|e| {
+                matches!(
+                    e.entity_type,
+                    vampire_rpg::components::game_data::EntityType::Player
+                )
+            })
+            .and_then(|p| p.velocity.as_ref())
+            .map(|v| (v.x.powi(2) + v.y.powi(2)).sqrt())
+            .unwrap_or(0.0);
+
+        game_state.add_debug_message(format!(
+            "FPS: {:.1} | DT: {:.4}s | {} | Speed: {:.0}",
+            fps, delta_time, perf_mode, player_speed
+        ));
+        game_state.report_fps(fps, *fps_timer);
+        *frame_count = 0;
+        *fps_timer = 0.0;
+    }
+
+    // ~ opens the developer console in debug builds. While it is open it
+    // takes all typing; Enter runs the line and Esc closes it.
+    let console_closed = match input_handler.take_finished_text_input() {
+        Some(text_input) => {
+            if let Some(line) = text_input.submitted_text() {
+                DevConsole::run(game_state, line);
+            }
+            game_state.console.open = false;
+            true
         }
+        None => false,
+    };
+    if CONSOLE_ENABLED
+        && !console_closed
+        && !input_handler.is_text_input_active()
+        && input_handler.is_key_just_pressed(KeyCode::GraveAccent)
+    {
+        input_handler.begin_text_input("", CONSOLE_LINE_LEN);
+        game_state.console.open = true;
+    }
+    game_state.console.input = input_handler
+        .text_input()
+        .map_or_else(String::new, |text_input| text_input.text().to_string());
 
-        // Update game state
-        game_state.update(&input_handler, delta_time);
+    // Presses wait for a frame that runs a step, so a quick tap on a fast
+    // display is neither lost nor acted on twice
+    let steps = timestep.advance(delta_time);
+    if steps == 0 {
+        input_handler.carry_presses();
+    }
+    let typing = console_closed || input_handler.is_text_input_active();
+    let pressed = |key| steps > 0 && !typing && input_handler.is_key_just_pressed(key);
+    let (screenshot, toggle_clip, save_clip) = (
+        pressed(KeyCode::F12),
+        pressed(KeyCode::F8),
+        pressed(KeyCode::F10),
+    );
 
-        // Render the game (removed problematic resolution scaling for cross-platform compatibility)
-        renderer.render(&game_state);
+    // Return to the most recent milestone save with F9
+    if pressed(KeyCode::F9) {
+        game_state.load_latest_milestone();
+    }
 
-        // Let macroquad handle frame rate limiting via VSync with next_frame()
-        // Remove manual frame limiting to allow 60+ FPS
+    // Handle performance mode toggle with P key
+    if pressed(KeyCode::P) {
+        let current_mode = renderer.performance_mode();
+        renderer.set_performance_mode(!current_mode);
+        if !current_mode {
+            game_state.add_debug_message("Performance mode enabled".to_string());
+        } else {
+            game_state.add_debug_message("Performance mode disabled".to_string());
+        }
+    }
 
-        // Present frame
-        next_frame().await;
+    // F3 shows or hides per-system timings
+    if pressed(KeyCode::F3) {
+        game_state.profiler.toggle();
+    }
+
+    // Q on the pause screen saves and leaves for the title screen
+    if app.state == AppState::Paused && pressed(KeyCode::Q) {
+        game_state.pending_saves.push(SaveReason::Timed);
+        game_state.process_saves();
+        return true;
+    }
+
+    // Update game state
+    for _ in 0..steps {
+        game_state.begin_step();
+        game_state.update(input_handler, FixedTimestep::STEP);
+        input_handler.consume_presses();
+
+        // Act on the choice made on the death screen
+        match game_state.take_game_over_choice() {
+            Some(GameOverChoice::Restart) => {
+                game_state.reset();
+                game_state.add_debug_message("You rise again.".to_string());
+            }
+            Some(GameOverChoice::Quit) => return true,
+            None => {}
+        }
+    }
+    game_state.render_blend = timestep.blend();
+    app.state = AppState::of_game(game_state);
+
+    // Play sounds heard from the camera
+    audio.play_cues(
+        game_state.take_sound_cues(),
+        Position::new(game_state.camera_x, game_state.camera_y),
+        &game_state.audio_settings,
+        get_time(),
+    );
+    audio.update_ambient(
+        AmbientRegion::around(&game_state.entities, game_state.player_id),
+        game_state.time.is_night(),
+        &game_state.audio_settings,
+        delta_time,
+    );
+
+    // Write any autosaves triggered this frame
+    game_state.process_saves();
+
+    // Send the game to anyone watching
+    if let Some(host) = spectator_host {
+        for addr in host.accept_pending() {
+            game_state.add_debug_message(format!("Spectator joined from {}", addr));
+        }
+        if host.tick(delta_time) {
+            match host.broadcast(game_state) {
+                Ok(0) => {}
+                Ok(dropped) => {
+                    game_state.add_debug_message(format!("{} spectator(s) disconnected", dropped))
+                }
+                Err(error) => {
+                    game_state.add_debug_message(format!("Spectator stream failed: {}", error))
+                }
+            }
+        }
+    }
+
+    // Render the game (removed problematic resolution scaling for cross-platform compatibility)
+    renderer.render(game_state);
+    for (section, elapsed) in renderer.take_phase_timings() {
+        game_state.profiler.record(section, elapsed);
+    }
+    game_state.profiler.end_frame();
+
+    // F12 saves a screenshot, F8 starts or stops the clip recorder and F10
+    // saves its last few seconds as a GIF
+    renderer.record_clip_frame(delta_time);
+    if screenshot {
+        match renderer.save_screenshot() {
+            Ok(path) => {
+                game_state.add_debug_message(format!("Screenshot saved to {}", path.display()))
+            }
+            Err(error) => game_state.add_debug_message(format!("Screenshot failed: {}", error)),
+        }
+    }
+    if toggle_clip {
+        if renderer.toggle_clip_recorder() {
+            game_state.add_debug_message("Clip recorder started".to_string());
+        } else {
+            game_state.add_debug_message("Clip recorder stopped".to_string());
+        }
+    }
+    if save_clip {
+        match renderer.save_clip() {
+            Ok(path) => game_state.add_debug_message(format!("Clip saved to {}", path.display())),
+            Err(error) => game_state.add_debug_message(format!("Clip failed: {}", error)),
+        }
     }
+    false
 }
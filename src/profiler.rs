@@ -0,0 +1,156 @@
+//! Profiler Module
+//!
+//! A lightweight timer registry behind the profiling overlay (F3). The game
+//! update loop reports how long each system took and the renderer how long
+//! each of its phases took; at the end of the frame the totals join a
+//! rolling window, from which the overlay shows the average and the worst
+//! frame for every section.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Frames kept in the rolling window, about two seconds at 60 FPS
+pub const PROFILE_WINDOW: usize = 120;
+
+/// A timed part of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfileSection {
+    Player,
+    Ai,
+    Blood,
+    Shelter,
+    Objectives,
+    /// Ground, entities, lighting and weather
+    RenderWorld,
+    /// Fog, markers and the HUD
+    RenderHud,
+    /// Menus, dialogue and other screens drawn over the HUD
+    RenderOverlay,
+}
+
+impl ProfileSection {
+    pub const ALL: [ProfileSection; 8] = [
+        ProfileSection::Player,
+        ProfileSection::Ai,
+        ProfileSection::Blood,
+        ProfileSection::Shelter,
+        ProfileSection::Objectives,
+        ProfileSection::RenderWorld,
+        ProfileSection::RenderHud,
+        ProfileSection::RenderOverlay,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProfileSection::Player => "Player",
+            ProfileSection::Ai => "AI",
+            ProfileSection::Blood => "Blood",
+            ProfileSection::Shelter => "Shelter",
+            ProfileSection::Objectives => "Objectives",
+            ProfileSection::RenderWorld => "Render world",
+            ProfileSection::RenderHud => "Render HUD",
+            ProfileSection::RenderOverlay => "Render overlay",
+        }
+    }
+
+    pub fn is_render_phase(self) -> bool {
+        matches!(
+            self,
+            ProfileSection::RenderWorld | ProfileSection::RenderHud | ProfileSection::RenderOverlay
+        )
+    }
+}
+
+/// Average and worst time of a section over the rolling window
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SectionStats {
+    pub average_ms: f32,
+    pub worst_ms: f32,
+}
+
+/// Collects per-section timings, frame by frame
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    /// Whether timings are being collected and the overlay shown
+    pub enabled: bool,
+    /// Time spent in each section so far this frame, summed over its steps
+    current: [Duration; ProfileSection::ALL.len()],
+    /// Milliseconds spent in each section over the last frames, oldest first
+    history: [VecDeque<f32>; ProfileSection::ALL.len()],
+}
+
+impl Profiler {
+    /// Show or hide the overlay, starting a fresh window when shown
+    pub fn toggle(&mut self) {
+        *self = Self {
+            enabled: !self.enabled,
+            ..Self::default()
+        };
+    }
+
+    /// Add time spent in a section this frame
+    pub fn record(&mut self, section: ProfileSection, elapsed: Duration) {
+        if self.enabled {
+            self.current[section as usize] += elapsed;
+        }
+    }
+
+    /// Close the frame, moving its totals into the rolling window
+    pub fn end_frame(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        for (total, history) in self.current.iter_mut().zip(self.history.iter_mut()) {
+            if history.len() == PROFILE_WINDOW {
+                history.pop_front();
+            }
+            history.push_back(total.as_secs_f32() * 1000.0);
+            *total = Duration::ZERO;
+        }
+    }
+
+    pub fn stats(&self, section: ProfileSection) -> SectionStats {
+        let history = &self.history[section as usize];
+        if history.is_empty() {
+            return SectionStats::default();
+        }
+        SectionStats {
+            average_ms: history.iter().sum::<f32>() / history.len() as f32,
+            worst_ms: history.iter().copied().fold(0.0, f32::max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiler_keeps_a_rolling_average_and_worst_frame() {
+        let mut profiler = Profiler::default();
+
+        // Nothing is collected while the overlay is hidden
+        profiler.record(ProfileSection::Ai, Duration::from_millis(5));
+        profiler.end_frame();
+        assert_eq!(profiler.stats(ProfileSection::Ai), SectionStats::default());
+
+        profiler.toggle();
+        // Two steps in one frame add up
+        profiler.record(ProfileSection::Ai, Duration::from_millis(1));
+        profiler.record(ProfileSection::Ai, Duration::from_millis(1));
+        profiler.end_frame();
+        profiler.record(ProfileSection::Ai, Duration::from_millis(6));
+        profiler.end_frame();
+
+        let stats = profiler.stats(ProfileSection::Ai);
+        assert!((stats.average_ms - 4.0).abs() < 0.01);
+        assert!((stats.worst_ms - 6.0).abs() < 0.01);
+        assert_eq!(profiler.stats(ProfileSection::Blood).worst_ms, 0.0);
+
+        // Old frames fall out of the window
+        for _ in 0..PROFILE_WINDOW {
+            profiler.end_frame();
+        }
+        assert_eq!(profiler.stats(ProfileSection::Ai).worst_ms, 0.0);
+    }
+}
@@ -0,0 +1,57 @@
+//! Achievement Rendering
+//!
+//! Draws the toast announcing a newly unlocked achievement: a panel that
+//! slides in from the right edge of the screen, names the achievement and
+//! what earned it, then slides back out.
+
+use super::Renderer;
+use crate::achievements::TOAST_DURATION;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+const ACHIEVEMENT_COLOR: Color = Color::new(0.95, 0.78, 0.35, 1.0);
+
+/// Seconds the toast takes to slide in or out
+const SLIDE_SECONDS: f32 = 0.4;
+
+impl Renderer {
+    /// The oldest achievement toast still waiting to be shown
+    pub(super) fn draw_achievement_toast(&self, game_state: &GameState) {
+        let Some((achievement, remaining)) = game_state.achievements.toasts.first() else {
+            return;
+        };
+        let scale = self.ui_scale;
+        let shown = TOAST_DURATION - remaining;
+        let slide = (shown / SLIDE_SECONDS)
+            .min(remaining / SLIDE_SECONDS)
+            .clamp(0.0, 1.0);
+
+        let title = achievement.display_name();
+        let description = achievement.description();
+        let title_size = 22.0 * scale;
+        let text_size = 16.0 * scale;
+        let width = measure_text(title, None, title_size as u16, 1.0)
+            .width
+            .max(measure_text(description, None, text_size as u16, 1.0).width)
+            + 32.0 * scale;
+        let height = 78.0 * scale;
+        let margin = 20.0 * scale;
+        let x = screen_width() - (width + margin) * slide;
+        let y = 90.0 * scale;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.08, 0.04, 0.04, 0.85));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 2.0, ACHIEVEMENT_COLOR);
+        let left = x + 16.0 * scale;
+        self.draw_text_with_font(
+            self.tr("achievements.unlocked"),
+            left,
+            y + 20.0 * scale,
+            13.0 * scale,
+            Color::new(0.8, 0.7, 0.5, 1.0),
+        );
+        self.draw_text_with_font(title, left, y + 44.0 * scale, title_size, ACHIEVEMENT_COLOR);
+        self.draw_text_with_font(description, left, y + 66.0 * scale, text_size, LIGHTGRAY);
+    }
+}
@@ -0,0 +1,36 @@
+//! Assault Rendering
+//!
+//! Marks the rally point while an allied squad assembles and the target
+//! while it is being stormed.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::AssaultPhase;
+use macroquad::prelude::*;
+
+const RALLY_COLOR: Color = Color::new(0.4, 0.9, 0.45, 1.0);
+const TARGET_COLOR: Color = Color::new(1.0, 0.35, 0.3, 1.0);
+
+impl Renderer {
+    pub(super) fn draw_assault_markers(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(assault) = &game_state.assaults.active else {
+            return;
+        };
+        let (target, color) = match assault.phase {
+            AssaultPhase::Assembling => (assault.rally_point, RALLY_COLOR),
+            AssaultPhase::Attacking => (assault.objective, TARGET_COLOR),
+        };
+        self.draw_objective_marker(
+            target,
+            color,
+            game_state.game_time,
+            camera_offset_x,
+            camera_offset_y,
+        );
+    }
+}
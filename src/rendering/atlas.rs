@@ -0,0 +1,195 @@
+//! Sprite Atlas Rendering
+//!
+//! Texture path for entity sprites. The atlas in `assets/sprites` holds
+//! each sprite as a stack of layers drawn as textured quads; most layers are
+//! white masks tinted with the theme's colors so palettes still apply, the
+//! rest carry their own colors. Entities the atlas has no sprite for, and
+//! every entity when the atlas cannot be loaded, fall back to the procedural
+//! pixel art.
+
+use super::Renderer;
+use crate::assets::AssetManager;
+use crate::components::EntityType;
+use macroquad::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use thiserror::Error;
+
+const ATLAS_IMAGE: &str = "sprites/atlas.png";
+const ATLAS_LAYOUT: &str = "sprites/atlas.json";
+/// Shown on the diagnostics screen when the atlas cannot be used
+const ATLAS_FALLBACK: &str = "pixel-art sprites";
+
+/// Tint name that takes the color of the entity's clan
+const CLAN_TINT: &str = "clan";
+
+/// Errors that can occur while loading the sprite atlas
+#[derive(Debug, Error)]
+pub enum AtlasError {
+    #[error("sprite atlas layout is not valid: {0}")]
+    Layout(#[from] serde_json::Error),
+    #[error("sprite atlas image is not valid: {0}")]
+    Image(String),
+    #[error("sprite layer of '{0}' lies outside the atlas image")]
+    Bounds(String),
+    #[error("sprite atlas layout is not UTF-8")]
+    Encoding,
+}
+
+/// One layer of a sprite: where it sits in the atlas and how it is tinted
+#[derive(Debug, Clone, Deserialize)]
+struct AtlasLayer {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    /// Palette color to tint the layer with, or `clan` for the clan color;
+    /// untinted layers are drawn in their own colors
+    #[serde(default)]
+    tint: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AtlasLayout {
+    sprites: HashMap<String, Vec<AtlasLayer>>,
+}
+
+/// The atlas texture and where each sprite lies in it
+pub(super) struct SpriteAtlas {
+    texture: Texture2D,
+    layout: AtlasLayout,
+}
+
+impl SpriteAtlas {
+    /// Decode an atlas image and check every layer of its layout lies inside it
+    fn decode(image: &[u8], layout: &str) -> Result<(Image, AtlasLayout), AtlasError> {
+        let layout: AtlasLayout = serde_json::from_str(layout)?;
+        let image = Image::from_file_with_format(image, Some(ImageFormat::Png))
+            .map_err(|error| AtlasError::Image(error.to_string()))?;
+        let (width, height) = (f32::from(image.width), f32::from(image.height));
+        for (name, layers) in &layout.sprites {
+            if layers
+                .iter()
+                .any(|layer| layer.x + layer.w > width || layer.y + layer.h > height)
+            {
+                return Err(AtlasError::Bounds(name.clone()));
+            }
+        }
+        Ok((image, layout))
+    }
+
+    /// Decode the atlas and upload it as a texture
+    fn upload(image: &[u8], layout: &[u8]) -> Result<Self, AtlasError> {
+        let layout = std::str::from_utf8(layout).map_err(|_| AtlasError::Encoding)?;
+        let (image, layout) = Self::decode(image, layout)?;
+        let texture = Texture2D::from_image(&image);
+        texture.set_filter(FilterMode::Nearest);
+        Ok(Self { texture, layout })
+    }
+
+    /// Atlas sprite drawn for an entity type
+    fn sprite_name(entity_type: &EntityType) -> Option<&'static str> {
+        match entity_type {
+            EntityType::Player => Some("vampire"),
+            EntityType::HostileInfected => Some("infected"),
+            EntityType::Animal => Some("animal"),
+            EntityType::ClanLeader(_) => Some("clan_leader"),
+            EntityType::ClanMember(_) => Some("clan_member"),
+            EntityType::Shelter | EntityType::Pickup => None,
+        }
+    }
+}
+
+impl Renderer {
+    /// Load the atlas from the assets folder. Loading needs a window, so it
+    /// cannot happen in `Renderer::new`; until it is loaded, and when it is
+    /// missing or broken, every sprite is drawn as pixel art.
+    pub fn load_atlas(&mut self, assets: &mut AssetManager) {
+        let image = assets.read(ATLAS_IMAGE, ATLAS_FALLBACK);
+        let layout = assets.read(ATLAS_LAYOUT, ATLAS_FALLBACK);
+        let (Some(image), Some(layout)) = (image, layout) else {
+            return;
+        };
+        match SpriteAtlas::upload(&image, &layout) {
+            Ok(atlas) => self.atlas = Some(atlas),
+            Err(AtlasError::Image(error)) => assets.mark_invalid(ATLAS_IMAGE, error),
+            Err(error) => assets.mark_invalid(ATLAS_LAYOUT, error),
+        }
+    }
+
+    /// Draw an entity from the atlas, centred on the screen position.
+    /// Returns false when there is no atlas sprite for it, so the caller can
+    /// draw the pixel-art version instead.
+    pub(super) fn draw_atlas_sprite(
+        &self,
+        entity_type: &EntityType,
+        x: f32,
+        y: f32,
+        size: f32,
+        flip: bool,
+        clan_color: Color,
+    ) -> bool {
+        let Some(atlas) = &self.atlas else {
+            return false;
+        };
+        let Some(layers) =
+            SpriteAtlas::sprite_name(entity_type).and_then(|name| atlas.layout.sprites.get(name))
+        else {
+            return false;
+        };
+
+        for layer in layers {
+            let color = match layer.tint.as_deref() {
+                None => WHITE,
+                Some(CLAN_TINT) => clan_color,
+                Some(name) => self.palette.sprite_color(name).unwrap_or(WHITE),
+            };
+            self.queue().sprite(
+                &atlas.texture,
+                Rect::new(layer.x, layer.y, layer.w, layer.h),
+                x - size / 2.0,
+                y - size / 2.0,
+                size,
+                flip,
+                color,
+            );
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHIPPED_IMAGE: &[u8] = include_bytes!("../../assets/sprites/atlas.png");
+    const SHIPPED_LAYOUT: &str = include_str!("../../assets/sprites/atlas.json");
+
+    #[test]
+    fn test_embedded_atlas_covers_every_drawn_entity() {
+        let (_, layout) = SpriteAtlas::decode(SHIPPED_IMAGE, SHIPPED_LAYOUT).unwrap();
+        let palette = crate::theme::Palette::default();
+        for entity_type in [
+            EntityType::Player,
+            EntityType::HostileInfected,
+            EntityType::Animal,
+            EntityType::ClanLeader("Bone-Eaters".into()),
+            EntityType::ClanMember("Bone-Eaters".into()),
+        ] {
+            let name = SpriteAtlas::sprite_name(&entity_type).unwrap();
+            let layers = &layout.sprites[name];
+            assert!(!layers.is_empty());
+            // Every tint names a palette color or the clan color
+            for tint in layers.iter().filter_map(|layer| layer.tint.as_deref()) {
+                assert!(tint == CLAN_TINT || palette.sprite_color(tint).is_some());
+            }
+        }
+
+        // A layer hanging off the image is rejected rather than drawn
+        let broken = r#"{"sprites": {"vampire": [{"x": 120, "y": 0, "w": 16, "h": 16}]}}"#;
+        assert!(matches!(
+            SpriteAtlas::decode(SHIPPED_IMAGE, broken),
+            Err(AtlasError::Bounds(_))
+        ));
+    }
+}
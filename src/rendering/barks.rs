@@ -0,0 +1,61 @@
+//! Bark Rendering
+//!
+//! Speech bubbles above creatures that just spoke up, with a small tail
+//! pointing down at the speaker. Threats and snarls get a red-edged bubble;
+//! bubbles fade out together with the line inside them.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_barks(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for bark in &game_state.barks.active {
+            let Some(speaker) = EntityFinder::by_id(&game_state.entities, bark.entity_id) else {
+                continue;
+            };
+            let alpha = bark.alpha();
+            let font_size = 14.0 * self.zoom_level.max(0.75);
+            let text_width = measure_text(bark.text, None, font_size as u16, 1.0).width;
+            let padding = 6.0;
+            let width = text_width + padding * 2.0;
+            let height = font_size + padding;
+            let position = game_state.render_position(speaker);
+            let anchor_x = position.x * self.zoom_level + camera_offset_x;
+            let anchor_y = (position.y - 22.0) * self.zoom_level + camera_offset_y;
+            let x = anchor_x - width / 2.0;
+            let y = anchor_y - height - 6.0;
+            if x + width < 0.0 || x > screen_width() || y + height < 0.0 || y > screen_height() {
+                continue;
+            }
+
+            let fill = Color::new(0.95, 0.93, 0.88, 0.9 * alpha);
+            let edge = if bark.kind.is_threat() {
+                Color::new(0.8, 0.1, 0.1, alpha)
+            } else {
+                Color::new(0.2, 0.2, 0.2, alpha)
+            };
+            self.queue().rectangle(x, y, width, height, fill);
+            self.queue().rectangle_lines(x, y, width, height, 1.5, edge);
+            self.queue().triangle(
+                vec2(anchor_x - 4.0, y + height),
+                vec2(anchor_x + 4.0, y + height),
+                vec2(anchor_x, anchor_y),
+                fill,
+            );
+            self.draw_text_with_font(
+                bark.text,
+                x + padding,
+                y + height - padding * 0.8,
+                font_size,
+                Color::new(0.1, 0.05, 0.05, alpha),
+            );
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Bestiary Rendering
+//!
+//! The bestiary page of the codex: every creature the player has seen, with
+//! its likeness and as much as the player has learned about it. What is not
+//! yet known is shown as a hint at how to learn it.
+
+use super::dream::wrap_text;
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::bestiary::KILLS_TO_LEARN_STATS;
+use macroquad::prelude::*;
+
+const KNOWN_TEXT: Color = Color::new(0.9, 0.85, 0.8, 1.0);
+const HEADING: Color = Color::new(0.8, 0.55, 0.45, 1.0);
+
+impl Renderer {
+    pub(super) fn draw_bestiary_page(&self, game_state: &GameState, x: f32, y: f32, width: f32) {
+        let scale = self.ui_scale;
+        let entries = game_state.bestiary.entries();
+        if entries.is_empty() {
+            self.draw_text_with_font(self.tr("bestiary.empty"), x, y, 16.0 * scale, GRAY);
+            return;
+        }
+
+        // Creatures seen, side by side
+        let selected = game_state
+            .selected_codex_entry
+            .min(entries.len().saturating_sub(1));
+        let mut name_x = x;
+        for (index, (lore, _)) in entries.iter().enumerate() {
+            let size = 18.0 * scale;
+            self.draw_text_with_font(
+                lore.name,
+                name_x,
+                y,
+                size,
+                if index == selected { GOLD } else { WHITE },
+            );
+            name_x += measure_text(lore.name, None, size as u16, 1.0).width + 20.0 * scale;
+        }
+
+        let (lore, knowledge) = entries[selected];
+        let sprite_size = 48.0 * scale;
+        let sprite_x = x + sprite_size / 2.0 + 4.0 * scale;
+        let sprite_y = y + 20.0 * scale + sprite_size / 2.0;
+        self.queue().rectangle_lines(
+            sprite_x - sprite_size * 0.7,
+            sprite_y - sprite_size * 0.7,
+            sprite_size * 1.4,
+            sprite_size * 1.4,
+            1.0,
+            Color::new(0.45, 0.25, 0.6, 1.0),
+        );
+        match lore.id {
+            "infected" => self.draw_infected_sprite(sprite_x, sprite_y, sprite_size, 0.0),
+            "animal" => self.draw_animal_sprite(sprite_x, sprite_y, sprite_size),
+            "clan_leader" => self.draw_clan_leader_sprite(sprite_x, sprite_y, sprite_size, PURPLE),
+            _ => self.draw_clan_member_sprite(sprite_x, sprite_y, sprite_size, DARKBLUE),
+        }
+
+        let text_x = x + sprite_size * 1.6;
+        let chars = ((width - sprite_size * 1.6) / (8.0 * scale)) as usize;
+        let mut text_y = y + 26.0 * scale;
+        let mut paragraph = |this: &Self, heading: &str, text: &str, color: Color| {
+            this.draw_text_with_font(heading, text_x, text_y, 15.0 * scale, HEADING);
+            text_y += 18.0 * scale;
+            for line in wrap_text(text, chars) {
+                this.draw_text_with_font(&line, text_x, text_y, 15.0 * scale, color);
+                text_y += 18.0 * scale;
+            }
+            text_y += 6.0 * scale;
+        };
+
+        paragraph(self, lore.name, lore.description, KNOWN_TEXT);
+
+        match knowledge.stats.filter(|_| knowledge.knows_stats()) {
+            Some(stats) => {
+                let line = self.strings.format(
+                    "bestiary.stats",
+                    &[
+                        ("health", &format!("{:.0}", stats.max_health)),
+                        ("attack", &format!("{:.0}", stats.attack_power)),
+                        ("defense", &format!("{:.0}", stats.defense)),
+                        ("kills", &knowledge.kills),
+                    ],
+                );
+                paragraph(self, self.tr("bestiary.strength"), &line, KNOWN_TEXT);
+                paragraph(
+                    self,
+                    self.tr("bestiary.weaknesses"),
+                    lore.weaknesses,
+                    KNOWN_TEXT,
+                );
+            }
+            None => {
+                let remaining = KILLS_TO_LEARN_STATS.saturating_sub(knowledge.kills);
+                let hint = self
+                    .strings
+                    .format("bestiary.kills_to_learn", &[("count", &remaining)]);
+                paragraph(self, self.tr("bestiary.strength"), &hint, GRAY);
+            }
+        }
+
+        if knowledge.notes_read {
+            paragraph(
+                self,
+                self.tr("bestiary.behaviour"),
+                lore.behavior,
+                KNOWN_TEXT,
+            );
+        } else {
+            paragraph(
+                self,
+                self.tr("bestiary.behaviour"),
+                self.tr("bestiary.notes_hint"),
+                GRAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Buff Rendering
+//!
+//! A row of small icons beside the health bar, one per active buff, each
+//! with a bar beneath it that empties as the buff wears off.
+
+use super::Renderer;
+use crate::components::*;
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Icons for `buffs`, left to right from (`x`, `y`)
+    pub(super) fn draw_buff_icons(&self, buffs: &Buffs, x: f32, y: f32) {
+        let scale = self.ui_scale;
+        let size = 20.0 * scale;
+        for (i, buff) in buffs.active.iter().enumerate() {
+            let icon_x = x + i as f32 * (size + 6.0 * scale);
+            let color = buff.kind.color();
+            self.queue()
+                .rectangle(icon_x, y, size, size, Color::new(0.0, 0.0, 0.0, 0.6));
+            self.queue()
+                .rectangle_lines(icon_x, y, size, size, 2.0, color);
+            self.draw_text_with_font(
+                buff.kind.icon(),
+                icon_x + 5.0 * scale,
+                y + 15.0 * scale,
+                18.0 * scale,
+                color,
+            );
+            self.queue().rectangle(
+                icon_x,
+                y + size + 2.0 * scale,
+                size * buff.fraction_left(),
+                3.0 * scale,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Build Rendering
+//!
+//! Draws the blueprint while the player is in build mode: the outline of
+//! what would be built, green where it can go and red where it cannot, and
+//! a banner naming the tier and its cost or the reason it is refused.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::ConstructionSystem;
+use macroquad::prelude::*;
+
+const VALID_COLOR: Color = Color::new(0.4, 0.9, 0.5, 1.0);
+const INVALID_COLOR: Color = Color::new(0.95, 0.35, 0.3, 1.0);
+
+impl Renderer {
+    pub(super) fn draw_blueprint(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(blueprint) = &game_state.blueprint else {
+            return;
+        };
+        let plan = ConstructionSystem::plan(&game_state.entities, blueprint);
+        let (shelter_type, color) = match &plan {
+            Ok(order) => (order.shelter_type.clone(), VALID_COLOR),
+            Err(_) => (ShelterType::Tent, INVALID_COLOR),
+        };
+
+        let zoom = self.zoom_level;
+        let x = blueprint.position.x * zoom + camera_offset_x;
+        let y = blueprint.position.y * zoom + camera_offset_y;
+        let (width, height) = shelter_type.visual_size();
+        let (width, height) = (width * zoom, height * zoom);
+        let pulse = 0.25 + (game_state.game_time * 4.0).sin().abs() * 0.15;
+        self.queue().rectangle(
+            x - width / 2.0,
+            y - height / 2.0,
+            width,
+            height,
+            Color::new(color.r, color.g, color.b, pulse),
+        );
+        self.queue()
+            .rectangle_lines(x - width / 2.0, y - height / 2.0, width, height, 2.0, color);
+
+        // Banner along the top, as with the crow scout
+        let scale = self.ui_scale;
+        let edge = 40.0 * scale;
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            edge,
+            Color::new(0.0, 0.0, 0.0, 0.45),
+        );
+        let status = match &plan {
+            Ok(order) => self.strings.format(
+                "build.plan",
+                &[
+                    ("shelter", &order.shelter_type.display_name()),
+                    ("blood", &format!("{:.0}", order.cost.blood)),
+                    ("seconds", &format!("{:.0}", order.cost.seconds)),
+                ],
+            ),
+            Err(error) => self
+                .strings
+                .format("build.blocked", &[("reason", &error.get_message())]),
+        };
+        let size = 20.0 * scale;
+        let text_width = measure_text(&status, None, size as u16, 1.0).width;
+        self.draw_text_with_font(
+            &status,
+            (screen_width() - text_width) / 2.0,
+            edge * 0.7,
+            size,
+            color,
+        );
+    }
+}
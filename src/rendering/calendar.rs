@@ -0,0 +1,54 @@
+//! Calendar Rendering
+//!
+//! Draws the banner announcing a special night as it begins and ends.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::{CalendarEvent, NightEvent};
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Banner across the top of the screen while a special night begins
+    /// or ends
+    pub(super) fn draw_calendar_banner(&self, game_state: &GameState) {
+        let Some((event, remaining)) = &game_state.calendar.banner else {
+            return;
+        };
+        let scale = self.ui_scale;
+        let alpha = remaining.min(1.0);
+        let title = self.tr(match event {
+            CalendarEvent::Began(NightEvent::BloodMoon) => "calendar.blood_moon",
+            CalendarEvent::Ended(NightEvent::BloodMoon) => "calendar.blood_moon_sets",
+        });
+        let message = event.get_message();
+
+        let title_size = 40.0 * scale;
+        let size = 20.0 * scale;
+        let title_width = measure_text(title, None, title_size as u16, 1.0).width;
+        let width = measure_text(&message, None, size as u16, 1.0)
+            .width
+            .max(title_width);
+        let y = 170.0 * scale;
+        self.queue().rectangle(
+            (screen_width() - width) / 2.0 - 20.0 * scale,
+            y - 44.0 * scale,
+            width + 40.0 * scale,
+            80.0 * scale,
+            Color::new(0.15, 0.0, 0.0, 0.8 * alpha),
+        );
+        self.draw_text_with_font(
+            title,
+            (screen_width() - title_width) / 2.0,
+            y,
+            title_size,
+            Color::new(0.9, 0.1, 0.1, alpha),
+        );
+        self.draw_text_with_font(
+            &message,
+            (screen_width() - measure_text(&message, None, size as u16, 1.0).width) / 2.0,
+            y + 26.0 * scale,
+            size,
+            Color::new(1.0, 0.85, 0.75, alpha),
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Capture Rendering
+//!
+//! Reads the finished frame back from the screen for screenshots and the
+//! clip recorder.
+
+use super::Renderer;
+use crate::capture::{CaptureError, ClipFrame, CLIP_MAX_WIDTH};
+use macroquad::prelude::*;
+use std::path::PathBuf;
+
+impl Renderer {
+    /// Hand the recorder a scaled-down copy of the frame when one is due.
+    /// Call after everything has been drawn.
+    pub fn record_clip_frame(&mut self, delta_time: f32) {
+        if !self.capture.recorder.is_due(delta_time) {
+            return;
+        }
+        let screen = get_screen_data();
+        let frame = ClipFrame::from_screen(
+            screen.width as usize,
+            screen.height as usize,
+            &screen.bytes,
+            CLIP_MAX_WIDTH,
+        );
+        self.capture.recorder.push(frame);
+    }
+
+    /// Save what is on screen as a PNG
+    pub fn save_screenshot(&self) -> Result<PathBuf, CaptureError> {
+        let screen = get_screen_data();
+        self.capture
+            .save_screenshot(screen.width as u32, screen.height as u32, &screen.bytes)
+    }
+
+    /// Start or stop the clip recorder, returning whether it now records
+    pub fn toggle_clip_recorder(&mut self) -> bool {
+        self.capture.recorder.toggle()
+    }
+
+    /// Save the last few seconds as an animated GIF
+    pub fn save_clip(&self) -> Result<PathBuf, CaptureError> {
+        self.capture.save_clip()
+    }
+}
@@ -0,0 +1,53 @@
+//! Console Rendering
+//!
+//! Draws the developer console as a dark strip across the top of the
+//! screen: its scrollback with the line being typed beneath.
+
+use super::Renderer;
+use crate::console::DevConsole;
+use macroquad::prelude::*;
+
+const BACKGROUND: Color = Color::new(0.0, 0.0, 0.0, 0.8);
+const ERROR_COLOR: Color = Color::new(1.0, 0.45, 0.4, 1.0);
+
+impl Renderer {
+    pub(super) fn draw_console(&self, console: &DevConsole) {
+        let line_height = self.ui(18.0);
+        let font_size = self.ui(16.0);
+        let lines: Vec<&str> = console.scrollback().collect();
+        let height = line_height * (lines.len() as f32 + 1.0) + self.ui(16.0);
+
+        self.queue()
+            .rectangle(0.0, 0.0, screen_width(), height, BACKGROUND);
+        self.queue()
+            .line(0.0, height, screen_width(), height, 1.0, DARKGRAY);
+
+        let x = self.ui(10.0);
+        let mut y = self.ui(8.0) + line_height * 0.8;
+        for line in lines {
+            let color = if line.starts_with("Error:") {
+                ERROR_COLOR
+            } else if line.starts_with('>') {
+                GRAY
+            } else {
+                WHITE
+            };
+            self.queue().builtin_text(line, x, y, font_size, color);
+            y += line_height;
+        }
+
+        // Blinking cursor after the line being typed
+        let cursor = if (get_time() * 2.0) as i64 % 2 == 0 {
+            "_"
+        } else {
+            ""
+        };
+        self.queue().builtin_text(
+            &format!("> {}{}", console.input, cursor),
+            x,
+            y,
+            font_size,
+            YELLOW,
+        );
+    }
+}
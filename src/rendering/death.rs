@@ -0,0 +1,157 @@
+//! Death Rendering
+//!
+//! Draws entities that have died: the death animation while it plays (ash
+//! crumbling, mist rising, a body collapsing) and afterwards the corpse,
+//! which fades away as its time in the world runs out.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::BODY_DECAY_HOURS;
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Draw dead entities under the living
+    pub(super) fn draw_fallen(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let margin = 40.0;
+        let body_lifetime = game_state.time.seconds_in(BODY_DECAY_HOURS);
+        for entity in &game_state.entities {
+            if !matches!(entity.ai_state, AIState::Dead) {
+                continue;
+            }
+            let Some(death) = entity.visual_state.death else {
+                continue;
+            };
+            let screen_x = entity.position.x * self.zoom_level + camera_offset_x;
+            let screen_y = entity.position.y * self.zoom_level + camera_offset_y;
+            if screen_x < -margin
+                || screen_x > screen_width() + margin
+                || screen_y < -margin
+                || screen_y > screen_height() + margin
+            {
+                continue;
+            }
+
+            let size = Self::body_size(&entity.entity_type) * self.zoom_level;
+            if death.style.leaves_body() {
+                let fade = game_state.decay.fade(entity.id, body_lifetime);
+                self.draw_corpse(entity, screen_x, screen_y, size, death.progress(), fade);
+            }
+            if !death.is_finished() {
+                self.draw_death_animation(entity, &death, screen_x, screen_y, size);
+            }
+        }
+    }
+
+    fn body_size(entity_type: &EntityType) -> f32 {
+        match entity_type {
+            EntityType::Player => 30.0,
+            EntityType::ClanLeader(_) => 28.0,
+            EntityType::ClanMember(_) => 24.0,
+            EntityType::HostileInfected => 20.0,
+            EntityType::Animal => 16.0,
+            EntityType::Shelter | EntityType::Pickup => 0.0,
+        }
+    }
+
+    /// The body at rest, fading in as the death animation ends and out as
+    /// it decays, with a blood pool that shrinks as the corpse dries
+    fn draw_corpse(
+        &self,
+        entity: &GameEntity,
+        x: f32,
+        y: f32,
+        size: f32,
+        progress: f32,
+        fade: f32,
+    ) {
+        let settle = ((progress - 0.6) / 0.4).clamp(0.0, 1.0) * fade;
+        if settle <= 0.0 {
+            return;
+        }
+
+        let freshness = entity.corpse.as_ref().map_or(0.0, Corpse::freshness);
+        if freshness > 0.0 {
+            self.queue().circle(
+                x,
+                y + 4.0,
+                10.0 + freshness * 6.0,
+                Color::new(0.4, 0.0, 0.0, (0.3 + freshness * 0.4) * settle),
+            );
+        }
+        self.queue().rectangle(
+            x - size * 0.4,
+            y - 3.0,
+            size * 0.8,
+            6.0,
+            Color::new(
+                entity.color.r * 0.4,
+                entity.color.g * 0.4,
+                entity.color.b * 0.4,
+                0.9 * settle,
+            ),
+        );
+    }
+
+    fn draw_death_animation(
+        &self,
+        entity: &GameEntity,
+        death: &DeathAnimation,
+        x: f32,
+        y: f32,
+        size: f32,
+    ) {
+        let progress = death.progress();
+        let fade = 1.0 - progress;
+        match death.style {
+            DeathStyle::Crumble => {
+                // The body sinks into a heap of ash while flakes fall away
+                let height = size * (1.0 - progress * 0.8);
+                self.queue().rectangle(
+                    x - size * 0.35,
+                    y + size * 0.3 - height,
+                    size * 0.7,
+                    height,
+                    Color::new(0.3, 0.3, 0.28, fade),
+                );
+                for flake in 0..8 {
+                    let offset = (entity.id as f32 * 1.7 + flake as f32 * 2.3).sin();
+                    let flake_x = x + offset * size * 0.5;
+                    let flake_y = y - size * 0.4 + progress * size * (0.6 + flake as f32 * 0.05);
+                    self.queue()
+                        .circle(flake_x, flake_y, 1.5, Color::new(0.5, 0.5, 0.48, fade));
+                }
+            }
+            DeathStyle::Mist => {
+                // Rising wisps that spread out and thin away
+                for wisp in 0..5 {
+                    let offset = wisp as f32 - 2.0;
+                    self.queue().circle(
+                        x + offset * size * 0.2 * (1.0 + progress),
+                        y - progress * size * (0.8 + wisp as f32 * 0.1),
+                        size * (0.3 + progress * 0.4),
+                        Color::new(0.75, 0.7, 0.85, 0.35 * fade),
+                    );
+                }
+            }
+            DeathStyle::Collapse => {
+                // The upright body tips over and flattens onto the ground
+                let width = size * (0.5 + progress * 0.6);
+                let height = size * (1.0 - progress * 0.65);
+                self.queue().ellipse(
+                    x,
+                    y + size * 0.2 * progress,
+                    width / 2.0,
+                    height / 2.0,
+                    0.0,
+                    Color::new(entity.color.r, entity.color.g, entity.color.b, fade),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Dialogue Rendering
+//!
+//! Draws the clan leader conversation panel: the leader's current line and the
+//! player's replies, with the selected reply highlighted.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_dialogue(&self, game_state: &GameState) {
+        let Some(dialogue) = &game_state.active_dialogue else {
+            return;
+        };
+        let node = dialogue.current();
+        let scale = self.ui_scale;
+        let line = 26.0 * scale;
+
+        let width = (screen_width() - 100.0).min(760.0 * scale);
+        let height = 120.0 * scale + line * node.options.len() as f32;
+        let x = (screen_width() - width) / 2.0;
+        let y = screen_height() - height - 30.0 * scale;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.08, 0.05, 0.1, 0.92));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 2.0, Color::new(0.6, 0.1, 0.1, 1.0));
+
+        let text_x = x + 20.0 * scale;
+        let mut text_y = y + 32.0 * scale;
+        self.draw_text_with_font(
+            &self.strings.format(
+                "dialogue.speaker",
+                &[
+                    ("leader", &dialogue.leader_name),
+                    ("clan", &dialogue.clan_name),
+                ],
+            ),
+            text_x,
+            text_y,
+            22.0 * scale,
+            GOLD,
+        );
+
+        text_y += line * 1.3;
+        self.draw_text_with_font(
+            &format!("\"{}\"", node.line),
+            text_x,
+            text_y,
+            18.0 * scale,
+            WHITE,
+        );
+
+        text_y += line * 0.5;
+        for (index, option) in node.options.iter().enumerate() {
+            text_y += line;
+            let selected = index == dialogue.selected_option;
+            if selected {
+                self.queue().rectangle(
+                    x + 10.0 * scale,
+                    text_y - 19.0 * scale,
+                    width - 20.0 * scale,
+                    line,
+                    Color::new(0.4, 0.1, 0.1, 0.6),
+                );
+            }
+            let prefix = if selected { "> " } else { "  " };
+            self.draw_text_with_font(
+                &format!("{}{}", prefix, option.text),
+                text_x,
+                text_y,
+                18.0 * scale,
+                if selected { YELLOW } else { LIGHTGRAY },
+            );
+        }
+
+        self.draw_text_with_font(
+            self.tr("dialogue.hint"),
+            text_x,
+            y + height - 12.0 * scale,
+            14.0 * scale,
+            GRAY,
+        );
+    }
+}
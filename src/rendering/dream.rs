@@ -0,0 +1,307 @@
+//! Dream Rendering
+//!
+//! Draws a vision in place of the world while the player dreams, the fade to
+//! black while they hibernate through the day, and the codex panel listing
+//! what past visions have revealed, with the bestiary as its second page.
+
+use super::Renderer;
+use crate::game_state::{CodexPage, GameState};
+use crate::systems::dream::{ActiveVision, FigureKind, DREAM_HALF_HEIGHT, DREAM_HALF_WIDTH};
+use macroquad::prelude::*;
+
+const DREAM_BACKGROUND: Color = Color::new(0.05, 0.02, 0.08, 1.0);
+const DREAM_FLOOR: Color = Color::new(0.16, 0.08, 0.22, 1.0);
+const DREAM_TEXT: Color = Color::new(0.85, 0.78, 1.0, 1.0);
+
+/// Split text into lines of at most `max_chars` characters
+pub(super) fn wrap_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + word.len() + 1 > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+impl Renderer {
+    /// Darken the world while the player hibernates, with the clock racing
+    /// by, and lift the darkness again as they wake
+    pub(super) fn draw_hibernation(&self, game_state: &GameState) {
+        let Some(hibernation) = &game_state.hibernation else {
+            return;
+        };
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::new(0.0, 0.0, 0.0, hibernation.fade * 0.85),
+        );
+        if !hibernation.is_asleep() {
+            return;
+        }
+
+        let scale = self.ui_scale;
+        let text = self.strings.format(
+            "dream.hibernating",
+            &[("time", &game_state.time.get_time_string())],
+        );
+        let size = 28.0 * scale;
+        let width = measure_text(&text, None, size as u16, 1.0).width;
+        self.draw_text_with_font(
+            &text,
+            (screen_width() - width) / 2.0,
+            screen_height() / 2.0,
+            size,
+            Color::new(DREAM_TEXT.r, DREAM_TEXT.g, DREAM_TEXT.b, hibernation.fade),
+        );
+    }
+
+    pub(super) fn draw_vision(&self, vision: &ActiveVision) {
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let center_y = screen_height() / 2.0;
+        clear_background(DREAM_BACKGROUND);
+
+        self.queue().rectangle(
+            center_x - DREAM_HALF_WIDTH * scale,
+            center_y - DREAM_HALF_HEIGHT * scale,
+            DREAM_HALF_WIDTH * 2.0 * scale,
+            DREAM_HALF_HEIGHT * 2.0 * scale,
+            DREAM_FLOOR,
+        );
+        let pulse = (get_time() as f32 * 1.5).sin() * 0.5 + 0.5;
+        self.queue().rectangle_lines(
+            center_x - DREAM_HALF_WIDTH * scale,
+            center_y - DREAM_HALF_HEIGHT * scale,
+            DREAM_HALF_WIDTH * 2.0 * scale,
+            DREAM_HALF_HEIGHT * 2.0 * scale,
+            2.0,
+            Color::new(0.5, 0.3, 0.7, 0.4 + pulse * 0.4),
+        );
+
+        let target = vision.current_step().map(|step| step.figure);
+        for (index, figure) in vision.figures.iter().enumerate() {
+            let x = center_x + figure.position.x * scale;
+            let y = center_y + figure.position.y * scale;
+            match figure.kind {
+                FigureKind::Person => {
+                    self.queue().circle(
+                        x,
+                        y - 14.0 * scale,
+                        7.0 * scale,
+                        Color::new(0.7, 0.65, 0.8, 0.9),
+                    );
+                    self.queue().rectangle(
+                        x - 7.0 * scale,
+                        y - 7.0 * scale,
+                        14.0 * scale,
+                        20.0 * scale,
+                        Color::new(0.45, 0.4, 0.6, 0.9),
+                    );
+                }
+                FigureKind::Light => {
+                    self.queue().circle(
+                        x,
+                        y,
+                        (14.0 + pulse * 4.0) * scale,
+                        Color::new(1.0, 0.95, 0.7, 0.25),
+                    );
+                    self.queue()
+                        .circle(x, y, 7.0 * scale, Color::new(1.0, 0.95, 0.8, 0.9));
+                }
+                FigureKind::Object => {
+                    self.queue().rectangle(
+                        x - 9.0 * scale,
+                        y - 5.0 * scale,
+                        18.0 * scale,
+                        10.0 * scale,
+                        Color::new(0.85, 0.75, 0.5, 0.9),
+                    );
+                }
+            }
+            if target == Some(index) {
+                self.queue().circle_lines(
+                    x,
+                    y,
+                    24.0 * scale,
+                    1.5,
+                    Color::new(1.0, 0.85, 0.3, 0.5 + pulse * 0.5),
+                );
+            }
+            let label_width = measure_text(figure.name, None, (14.0 * scale) as u16, 1.0).width;
+            self.draw_text_with_font(
+                figure.name,
+                x - label_width / 2.0,
+                y + 30.0 * scale,
+                14.0 * scale,
+                DREAM_TEXT,
+            );
+        }
+
+        // The dreamer, pale and translucent
+        let dreamer_x = center_x + vision.dreamer.x * scale;
+        let dreamer_y = center_y + vision.dreamer.y * scale;
+        self.queue().circle(
+            dreamer_x,
+            dreamer_y,
+            10.0 * scale,
+            Color::new(0.8, 0.1, 0.2, 0.7),
+        );
+        self.queue().circle_lines(
+            dreamer_x,
+            dreamer_y,
+            10.0 * scale,
+            1.5,
+            Color::new(1.0, 0.6, 0.7, 0.8),
+        );
+
+        let title = vision.script.title.to_uppercase();
+        let title_width = measure_text(&title, None, (28.0 * scale) as u16, 1.0).width;
+        self.draw_text_with_font(
+            &title,
+            center_x - title_width / 2.0,
+            center_y - (DREAM_HALF_HEIGHT + 40.0) * scale,
+            28.0 * scale,
+            DREAM_TEXT,
+        );
+        if let Some(step) = vision.current_step() {
+            self.draw_text_with_font(
+                step.prompt,
+                center_x - DREAM_HALF_WIDTH * scale,
+                center_y - (DREAM_HALF_HEIGHT + 12.0) * scale,
+                18.0 * scale,
+                GOLD,
+            );
+        }
+        if let Some(time_left) = vision.time_left() {
+            let timer = format!("{:.0}s", time_left.ceil());
+            let timer_width = measure_text(&timer, None, (18.0 * scale) as u16, 1.0).width;
+            self.draw_text_with_font(
+                &timer,
+                center_x + DREAM_HALF_WIDTH * scale - timer_width,
+                center_y - (DREAM_HALF_HEIGHT + 12.0) * scale,
+                18.0 * scale,
+                if time_left < 10.0 { RED } else { DREAM_TEXT },
+            );
+        }
+
+        if let Some((line, remaining)) = vision.line {
+            let alpha = remaining.min(1.0);
+            let mut text_y = center_y + (DREAM_HALF_HEIGHT + 30.0) * scale;
+            for text in wrap_text(line, 70) {
+                self.draw_text_with_font(
+                    &text,
+                    center_x - DREAM_HALF_WIDTH * scale,
+                    text_y,
+                    18.0 * scale,
+                    Color::new(DREAM_TEXT.r, DREAM_TEXT.g, DREAM_TEXT.b, alpha),
+                );
+                text_y += 22.0 * scale;
+            }
+        }
+
+        self.draw_text_with_font(
+            self.tr("dream.hint"),
+            center_x - DREAM_HALF_WIDTH * scale,
+            screen_height() - 20.0 * scale,
+            14.0 * scale,
+            GRAY,
+        );
+    }
+
+    pub(super) fn draw_codex(&self, game_state: &GameState) {
+        let scale = self.ui_scale;
+        let width = 560.0 * scale;
+        let height = 380.0 * scale;
+        let x = (screen_width() - width) / 2.0;
+        let y = (screen_height() - height) / 2.0;
+        let line = 26.0 * scale;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.06, 0.04, 0.09, 0.94));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 2.0, Color::new(0.45, 0.25, 0.6, 1.0));
+
+        let text_x = x + 20.0 * scale;
+        let mut text_y = y + 35.0 * scale;
+        let mut tab_x = text_x;
+        for (page, title) in [
+            (CodexPage::Lore, self.tr("codex.lore")),
+            (CodexPage::Bestiary, self.tr("codex.bestiary")),
+        ] {
+            let (size, color) = if page == game_state.codex_page {
+                (24.0 * scale, WHITE)
+            } else {
+                (18.0 * scale, GRAY)
+            };
+            self.draw_text_with_font(title, tab_x, text_y, size, color);
+            tab_x += measure_text(title, None, size as u16, 1.0).width + 24.0 * scale;
+        }
+        self.draw_text_with_font(
+            self.tr("codex.hint"),
+            text_x,
+            y + height - 14.0 * scale,
+            14.0 * scale,
+            GRAY,
+        );
+        let points = self.strings.format(
+            "skills.points",
+            &[(
+                "points",
+                &game_state
+                    .player_progression()
+                    .map_or(0, |progression| progression.skill_points),
+            )],
+        );
+        let points_width = measure_text(&points, None, (16.0 * scale) as u16, 1.0).width;
+        self.draw_text_with_font(
+            &points,
+            x + width - 20.0 * scale - points_width,
+            text_y,
+            16.0 * scale,
+            GOLD,
+        );
+
+        text_y += line;
+        if game_state.codex_page == CodexPage::Bestiary {
+            self.draw_bestiary_page(game_state, text_x, text_y, width - 40.0 * scale);
+            return;
+        }
+
+        let entries = game_state.lore_codex.entries();
+        if entries.is_empty() {
+            self.draw_text_with_font(self.tr("codex.empty"), text_x, text_y, 16.0 * scale, GRAY);
+            return;
+        }
+
+        let selected = game_state
+            .selected_codex_entry
+            .min(entries.len().saturating_sub(1));
+        for (index, entry) in entries.iter().enumerate() {
+            self.draw_text_with_font(
+                entry.title,
+                text_x,
+                text_y,
+                18.0 * scale,
+                if index == selected { GOLD } else { WHITE },
+            );
+            text_y += line;
+        }
+
+        text_y += line / 2.0;
+        for text in wrap_text(entries[selected].text, 60) {
+            self.draw_text_with_font(&text, text_x, text_y, 16.0 * scale, DREAM_TEXT);
+            text_y += 20.0 * scale;
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Hit Effects Rendering
+//!
+//! Damage numbers floating up from where blows landed, fading as they rise.
+//! Crits are drawn larger and gold, blows to the player in red.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_damage_numbers(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for number in &game_state.hit_effects.numbers {
+            let text = number.text();
+            let font_size = 18.0 * number.kind.scale() * self.zoom_level.max(0.75);
+            let width = measure_text(&text, None, font_size as u16, 1.0).width;
+            let x = number.position.x * self.zoom_level + camera_offset_x - width / 2.0;
+            let y = (number.position.y - 20.0 - number.rise()) * self.zoom_level + camera_offset_y;
+            let alpha = number.alpha();
+
+            self.draw_text_with_font(
+                &text,
+                x + 1.0,
+                y + 1.0,
+                font_size,
+                Color::new(0.0, 0.0, 0.0, 0.7 * alpha),
+            );
+            let mut color = number.kind.color();
+            color.a *= alpha;
+            self.draw_text_with_font(&text, x, y, font_size, color);
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! Entity Rendering
+//!
+//! Creatures in the world: each drawn from the sprite atlas or as pixel
+//! art, with the tints for its state, a health bar, and the rings and scent
+//! trails of the player's blood senses.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::{AbilitySystem, ScheduleSystem};
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_entities(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        // Pre-calculate screen bounds for better culling
+        let screen_w = screen_width();
+        let screen_h = screen_height();
+        let cull_margin = if self.performance_mode { 30.0 } else { 50.0 };
+
+        // Calculate camera movement for LOD decisions
+        let camera_speed = ((game_state.camera_x - self.last_camera_x).powi(2)
+            + (game_state.camera_y - self.last_camera_y).powi(2))
+        .sqrt();
+        let skip_details = self.performance_mode || camera_speed > 100.0;
+        let camera_center = Position::new(game_state.camera_x, game_state.camera_y);
+        let draw_radius = game_state.video_settings.active_entity_radius;
+
+        // Batch entities by type for potential future optimizations
+        let mut visible_entities = Vec::with_capacity(game_state.entities.len());
+
+        // Use high-performance iterator with pre-allocated capacity
+        visible_entities.reserve(game_state.entities.len() / 2);
+
+        // First pass: cull and collect visible entities using optimized iteration
+        for entity in game_state.entities.alive_entities() {
+            // Skip shelters and pickups (rendered separately)
+            if matches!(entity.entity_type, EntityType::Shelter | EntityType::Pickup) {
+                continue;
+            }
+
+            // Sleepers are out of sight inside their shelter, and animals
+            // gone to ground inside their dens
+            if ScheduleSystem::is_asleep(entity) || entity.is_hidden() {
+                continue;
+            }
+
+            // Low-spec mode only draws entities near the camera
+            if draw_radius
+                .is_some_and(|radius| entity.position.distance_to(&camera_center) > radius)
+            {
+                continue;
+            }
+
+            // Struck creatures are drawn knocked back from their attacker,
+            // and everyone between their last two simulated positions
+            let (recoil_x, recoil_y) = game_state.hit_effects.recoil_offset(entity.id);
+            let position = game_state.render_position(entity);
+            let screen_x = (position.x + recoil_x) * self.zoom_level + camera_offset_x;
+            let screen_y = (position.y + recoil_y) * self.zoom_level + camera_offset_y;
+
+            // Improved culling with tighter bounds
+            if screen_x > -cull_margin
+                && screen_x < screen_w + cull_margin
+                && screen_y > -cull_margin
+                && screen_y < screen_h + cull_margin
+            {
+                visible_entities.push((entity, screen_x, screen_y));
+            }
+        }
+
+        // Second pass: render visible entities using batched processing
+        self.render_entities_batched(&visible_entities, skip_details, game_state);
+    }
+
+    /// Render entities in batches for better performance
+    pub(super) fn render_entities_batched(
+        &self,
+        visible_entities: &[(&GameEntity, f32, f32)],
+        skip_details: bool,
+        game_state: &GameState,
+    ) {
+        // Group entities by type for batched rendering
+        let mut batches: std::collections::HashMap<std::mem::Discriminant<EntityType>, Vec<_>> =
+            std::collections::HashMap::new();
+
+        for &(entity, screen_x, screen_y) in visible_entities {
+            let discriminant = std::mem::discriminant(&entity.entity_type);
+            batches
+                .entry(discriminant)
+                .or_default()
+                .push((entity, screen_x, screen_y));
+        }
+
+        // Render each batch
+        for batch in batches.values() {
+            for &(entity, screen_x, screen_y) in batch {
+                let size = match entity.entity_type {
+                    EntityType::Player => {
+                        30.0 * entity
+                            .vampire_abilities
+                            .as_ref()
+                            .map_or(1.0, |abilities| abilities.ability_state.hitbox_scale())
+                    }
+                    EntityType::ClanLeader(_) => 28.0,
+                    EntityType::ClanMember(_) => 24.0,
+                    EntityType::HostileInfected => 20.0,
+                    EntityType::Animal => match entity.species {
+                        Some(Species::Rat) => 10.0,
+                        Some(Species::Wolf) => 20.0,
+                        Some(Species::Deer) | None => 16.0,
+                    },
+                    EntityType::Shelter | EntityType::Pickup => continue, // Already filtered out
+                };
+
+                let (screen_x, knocked_down) =
+                    Self::stagger_pose(entity, screen_x, game_state.game_time);
+
+                // Draw entity sprite, from the atlas when textured sprites
+                // are on and it has one
+                let facing_direction = entity
+                    .velocity
+                    .as_ref()
+                    .map(|v| v.x.atan2(v.y))
+                    .unwrap_or(0.0);
+                let clan_color = match &entity.entity_type {
+                    EntityType::ClanLeader(clan_name) | EntityType::ClanMember(clan_name) => {
+                        self.palette.clan_color(clan_name, entity.color)
+                    }
+                    _ => entity.color,
+                };
+                // The atlas only has the deer; rats and wolves are always
+                // drawn as pixel art
+                let textured = !knocked_down
+                    && game_state.video_settings.textured_sprites
+                    && !matches!(entity.species, Some(Species::Rat | Species::Wolf))
+                    && self.draw_atlas_sprite(
+                        &entity.entity_type,
+                        screen_x,
+                        screen_y,
+                        size,
+                        matches!(
+                            entity.entity_type,
+                            EntityType::Player | EntityType::HostileInfected
+                        ) && facing_direction.cos() <= 0.0,
+                        clan_color,
+                    );
+                match &entity.entity_type {
+                    _ if textured => {}
+                    _ if knocked_down => {
+                        self.draw_knocked_down(
+                            entity,
+                            screen_x,
+                            screen_y,
+                            size,
+                            clan_color,
+                            game_state.game_time,
+                        );
+                    }
+                    EntityType::Player => {
+                        self.draw_vampire_sprite(screen_x, screen_y, size, facing_direction);
+                    }
+                    EntityType::ClanLeader(_) => {
+                        self.draw_clan_leader_sprite(screen_x, screen_y, size, clan_color);
+                    }
+                    EntityType::HostileInfected => {
+                        self.draw_infected_sprite(screen_x, screen_y, size, facing_direction);
+                    }
+                    EntityType::Animal => match entity.species {
+                        Some(Species::Rat) => {
+                            self.draw_rat_sprite(screen_x, screen_y, size, entity.color);
+                        }
+                        Some(Species::Wolf) => {
+                            self.draw_wolf_sprite(screen_x, screen_y, size, entity.color);
+                        }
+                        Some(Species::Deer) | None => {
+                            self.draw_animal_sprite(screen_x, screen_y, size);
+                        }
+                    },
+                    EntityType::ClanMember(_) => {
+                        self.draw_clan_member_sprite(screen_x, screen_y, size, clan_color);
+                    }
+                    EntityType::Shelter | EntityType::Pickup => unreachable!(),
+                }
+
+                // Composite state tints over the sprite
+                if game_state.video_settings.post_processing && entity.visual_state.has_tint() {
+                    self.draw_tint_overlays(
+                        screen_x,
+                        screen_y,
+                        size,
+                        &entity.visual_state,
+                        game_state.game_time,
+                    );
+                }
+
+                // Draw health bar only if not skipping details and entity is close enough
+                if let Some(health) = &entity.health {
+                    if !skip_details {
+                        let distance_to_camera = ((entity.position.x - game_state.camera_x)
+                            .powi(2)
+                            + (entity.position.y - game_state.camera_y).powi(2))
+                        .sqrt();
+
+                        // Only draw health bars for entities within reasonable distance
+                        let health_bar_distance = if self.performance_mode { 150.0 } else { 300.0 };
+                        if distance_to_camera < health_bar_distance {
+                            self.draw_health_bar(screen_x, screen_y, size, health);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draw pulsing rings around creatures revealed by Blood Sense
+    pub(super) fn draw_blood_sense_pulse(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let pulse = (game_state.game_time * 6.0).sin() * 0.5 + 0.5;
+        for (_, position) in AbilitySystem::sensed_entities(
+            &game_state.entities,
+            &game_state.spatial_grid,
+            game_state.player_id,
+        ) {
+            let screen_x = position.x * self.zoom_level + camera_offset_x;
+            let screen_y = position.y * self.zoom_level + camera_offset_y;
+            self.queue().circle_lines(
+                screen_x,
+                screen_y,
+                18.0 + pulse * 6.0,
+                2.0,
+                Color::new(0.9, 0.0, 0.0, 0.5 + pulse * 0.4),
+            );
+        }
+    }
+
+    /// Draw Blood Sight's scent trails: drifting motes leading from the
+    /// player to each sensed creature, which pulses at the end of its trail
+    pub(super) fn draw_blood_sight(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let trails = AbilitySystem::scent_trails(
+            &game_state.entities,
+            &game_state.spatial_grid,
+            game_state.player_id,
+        );
+        let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) else {
+            return;
+        };
+        let to_screen = |position: &Position| {
+            vec2(
+                position.x * self.zoom_level + camera_offset_x,
+                position.y * self.zoom_level + camera_offset_y,
+            )
+        };
+        let start = to_screen(&player.position);
+        let motes = if self.performance_mode { 4 } else { 8 };
+        let pulse = (game_state.game_time * 4.0).sin() * 0.5 + 0.5;
+
+        for trail in trails {
+            let end = to_screen(&trail.position);
+            let alpha = 0.25 + trail.strength * 0.5;
+            for mote in 0..motes {
+                // Motes creep toward the prey, so the trail reads as a direction
+                let t = ((mote as f32 + game_state.game_time * 0.8) / motes as f32).fract();
+                let at = start.lerp(end, t);
+                self.queue().circle(
+                    at.x,
+                    at.y,
+                    (1.5 + t * 1.5) * self.zoom_level,
+                    Color::new(0.8, 0.05, 0.1, alpha * (0.4 + t * 0.6)),
+                );
+            }
+            self.queue().circle_lines(
+                end.x,
+                end.y,
+                (14.0 + pulse * 4.0) * self.zoom_level,
+                1.5 + trail.strength,
+                Color::new(0.85, 0.05, 0.1, alpha + pulse * 0.2),
+            );
+        }
+    }
+
+    /// Draw tint modifiers for an entity's visual state on top of its sprite
+    pub(super) fn draw_tint_overlays(
+        &self,
+        screen_x: f32,
+        screen_y: f32,
+        size: f32,
+        visual_state: &VisualState,
+        time: f32,
+    ) {
+        let radius = size * 0.6;
+
+        // Golden outline for allies
+        if visual_state.allied {
+            self.queue()
+                .circle_lines(screen_x, screen_y, radius + 2.0, 2.0, GOLD);
+        }
+
+        // Pale wash when starving for blood
+        if visual_state.starving {
+            self.queue()
+                .circle(screen_x, screen_y, radius, Color::new(0.9, 0.9, 1.0, 0.3));
+        }
+
+        // Pulsing blue shimmer while charmed
+        if visual_state.charmed > 0.0 {
+            let shimmer = ((time * 6.0).sin() + 1.0) * 0.5;
+            self.queue().circle(
+                screen_x,
+                screen_y,
+                radius,
+                Color::new(0.3, 0.5, 1.0, 0.2 + shimmer * 0.25),
+            );
+        }
+
+        // Red flash fading out after damage
+        if visual_state.damage_flash > 0.0 {
+            let alpha = visual_state.damage_flash / VisualState::DAMAGE_FLASH_DURATION;
+            self.queue().circle(
+                screen_x,
+                screen_y,
+                radius,
+                Color::new(1.0, 0.1, 0.1, 0.6 * alpha),
+            );
+        }
+    }
+
+    pub(super) fn draw_health_bar(
+        &self,
+        screen_x: f32,
+        screen_y: f32,
+        entity_size: f32,
+        health: &Health,
+    ) {
+        let bar_width = entity_size;
+        let bar_height = 6.0;
+        let bar_y = screen_y - entity_size / 2.0 - 12.0;
+
+        // Background bar
+        self.queue().rectangle(
+            screen_x - bar_width / 2.0,
+            bar_y,
+            bar_width,
+            bar_height,
+            Color::new(0.3, 0.0, 0.0, 0.8),
+        );
+
+        // Health bar
+        let health_percentage = health.current / health.max;
+        let health_width = bar_width * health_percentage;
+        let health_color = if health_percentage > 0.6 {
+            GREEN
+        } else if health_percentage > 0.3 {
+            YELLOW
+        } else {
+            RED
+        };
+
+        self.queue().rectangle(
+            screen_x - bar_width / 2.0,
+            bar_y,
+            health_width,
+            bar_height,
+            health_color,
+        );
+    }
+}
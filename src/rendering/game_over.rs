@@ -0,0 +1,116 @@
+//! Game Over Rendering
+//!
+//! The death screen: the world fades to a blood-red dark behind the vampire's
+//! epitaph, the final survival score, and the Restart and Quit options.
+
+use super::Renderer;
+use crate::achievements::Achievement;
+use crate::game_state::{GameOver, GameOverChoice, GameState};
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_game_over(&self, game_state: &GameState, game_over: &GameOver) {
+        let fade = game_over.fade();
+        let scale = self.ui_scale;
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::new(0.08, 0.0, 0.01, 0.85 * fade),
+        );
+
+        let center_x = screen_width() / 2.0;
+        let mut y = screen_height() / 2.0 - 150.0 * scale;
+        let centered = |text: &str, size: f32, y: f32, color: Color| {
+            let width = measure_text(text, None, size as u16, 1.0).width;
+            self.draw_text_with_font(text, center_x - width / 2.0, y, size, color);
+        };
+
+        let title_color = Color::new(0.85, 0.05, 0.1, fade);
+        centered(self.tr("game_over.title"), 52.0 * scale, y, title_color);
+        y += 40.0 * scale;
+        centered(
+            &self
+                .strings
+                .format("game_over.epitaph", &[("name", &game_state.player_name)]),
+            22.0 * scale,
+            y,
+            Color::new(0.8, 0.75, 0.75, fade),
+        );
+
+        let score = &game_over.score;
+        let stats = [
+            ("game_over.days", score.days_survived.to_string()),
+            ("game_over.feedings", score.total_feedings.to_string()),
+            ("game_over.kills", score.total_kills.to_string()),
+            (
+                "game_over.feedings_per_day",
+                format!("{:.1}", score.feeding_efficiency),
+            ),
+            (
+                "game_over.kills_per_feeding",
+                format!("{:.1}", score.combat_effectiveness),
+            ),
+        ]
+        .map(|(key, value)| self.strings.format(key, &[("value", &value)]));
+        y += 30.0 * scale;
+        for line in &stats {
+            y += 26.0 * scale;
+            centered(line, 20.0 * scale, y, Color::new(0.75, 0.75, 0.75, fade));
+        }
+        y += 40.0 * scale;
+        centered(
+            &self.strings.format(
+                "game_over.score",
+                &[("score", &format!("{:.0}", score.overall_score))],
+            ),
+            30.0 * scale,
+            y,
+            Color::new(1.0, 0.84, 0.0, fade),
+        );
+        let lifetime = &game_state.achievements.log;
+        y += 28.0 * scale;
+        centered(
+            &self.strings.format(
+                "game_over.lifetime",
+                &[
+                    ("best", &format!("{:.0}", lifetime.stats.best_score)),
+                    ("lives", &lifetime.stats.runs_ended),
+                    ("days", &lifetime.stats.days_survived),
+                    ("unlocked", &lifetime.unlocked.len()),
+                    ("total", &Achievement::ALL.len()),
+                ],
+            ),
+            16.0 * scale,
+            y,
+            Color::new(0.7, 0.65, 0.55, fade),
+        );
+
+        y += 40.0 * scale;
+        for choice in GameOverChoice::ALL {
+            let selected = choice == game_over.selected;
+            let label = if selected {
+                format!("> {} <", self.tr(choice.text_key()))
+            } else {
+                self.tr(choice.text_key()).to_string()
+            };
+            let color = if selected {
+                Color::new(1.0, 0.84, 0.0, fade)
+            } else {
+                Color::new(0.6, 0.6, 0.6, fade)
+            };
+            centered(&label, 24.0 * scale, y, color);
+            y += 32.0 * scale;
+        }
+
+        if game_over.elapsed >= GameOver::INPUT_DELAY {
+            centered(
+                self.tr("game_over.hint"),
+                16.0 * scale,
+                y + 10.0 * scale,
+                GRAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Ground Rendering
+//!
+//! Ground tiles around the camera, in full detail or as plain squares in
+//! performance mode, with water shimmering across rivers and lakes.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_ground_cached(
+        &mut self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        // Increment frame skip counter
+        self.frame_skip_counter += 1;
+
+        let mut tiles_drawn = 0;
+        let tile_cull_margin = if self.performance_mode { 30.0 } else { 80.0 };
+
+        // Calculate camera movement speed for LOD
+        let camera_speed = ((game_state.camera_x - self.last_camera_x).powi(2)
+            + (game_state.camera_y - self.last_camera_y).powi(2))
+        .sqrt();
+        let is_moving_fast = camera_speed > 150.0;
+
+        // Always draw ground, but vary detail level based on performance conditions
+        for tile in &game_state.ground_tiles {
+            let screen_x = tile.x * self.zoom_level + camera_offset_x;
+            let screen_y = tile.y * self.zoom_level + camera_offset_y;
+
+            // Only draw tiles that are visible on screen
+            if screen_x > -tile_cull_margin
+                && screen_x < screen_width() + tile_cull_margin
+                && screen_y > -tile_cull_margin
+                && screen_y < screen_height() + tile_cull_margin
+            {
+                // Determine detail level based on performance conditions
+                let distance_from_center = ((screen_x - screen_width() / 2.0).powi(2)
+                    + (screen_y - screen_height() / 2.0).powi(2))
+                .sqrt();
+
+                // Use simple rendering for performance optimization, but always render something
+                let use_simple_rendering = !game_state.video_settings.detailed_tiles
+                    || self.performance_mode
+                    || is_moving_fast
+                    || distance_from_center > 400.0;
+
+                if use_simple_rendering {
+                    self.draw_simple_ground_tile(
+                        screen_x,
+                        screen_y,
+                        64.0 * self.zoom_level,
+                        &tile.tile_type,
+                    );
+                } else {
+                    self.draw_ground_tile_optimized(
+                        screen_x,
+                        screen_y,
+                        64.0 * self.zoom_level,
+                        tile,
+                    );
+                }
+                if tile.tile_type == TileType::Water {
+                    self.draw_water_shimmer(
+                        screen_x,
+                        screen_y,
+                        64.0 * self.zoom_level,
+                        tile,
+                        game_state.game_time,
+                    );
+                }
+                tiles_drawn += 1;
+            }
+        }
+        self.last_tile_count = tiles_drawn;
+    }
+
+    pub(super) fn draw_ground_tile_optimized(&self, x: f32, y: f32, size: f32, tile: &GroundTile) {
+        let scale = size / 64.0;
+        let palette = &self.palette;
+
+        match tile.tile_type {
+            TileType::Grass => {
+                // Base grass color
+                self.queue()
+                    .rectangle(x, y, size, size, palette.grass.color());
+
+                // Optimized detail: draw fewer patches for performance
+                for (i, (px_offset, py_offset, width, height)) in
+                    tile.texture_data.grass_patches.iter().enumerate()
+                {
+                    if i % 3 == 0 {
+                        // Draw every 3rd patch for good balance
+                        let px = x + px_offset * scale;
+                        let py = y + py_offset * scale;
+                        self.queue().rectangle(
+                            px,
+                            py,
+                            width * scale,
+                            height * scale,
+                            palette.grass_detail.color(),
+                        );
+                    }
+                }
+            }
+            TileType::DeadGrass => {
+                // Dead grass base
+                self.queue()
+                    .rectangle(x, y, size, size, palette.dead_grass.color());
+
+                // Optimized detail for dead grass
+                for (i, (px_offset, py_offset, width, height)) in
+                    tile.texture_data.grass_patches.iter().enumerate()
+                {
+                    if i % 3 == 0 {
+                        let px = x + px_offset * scale;
+                        let py = y + py_offset * scale;
+                        self.queue().rectangle(
+                            px,
+                            py,
+                            width * scale,
+                            height * scale,
+                            palette.dead_grass_detail.color(),
+                        );
+                    }
+                }
+            }
+            TileType::Dirt => {
+                // Base dirt color
+                self.queue()
+                    .rectangle(x, y, size, size, palette.dirt.color());
+
+                // Optimized dirt spots
+                for (i, (px_offset, py_offset, radius)) in
+                    tile.texture_data.dirt_spots.iter().enumerate()
+                {
+                    if i % 2 == 0 {
+                        // Draw every other spot
+                        let px = x + px_offset * scale;
+                        let py = y + py_offset * scale;
+                        self.queue()
+                            .circle(px, py, radius * scale, palette.dirt_detail.color());
+                    }
+                }
+            }
+            TileType::Stone => {
+                // Simplified stone rendering
+                self.queue()
+                    .rectangle(x, y, size, size, palette.stone.color());
+
+                // Optimized stone blocks
+                for (i, (px_offset, py_offset, width, height)) in
+                    tile.texture_data.stone_blocks.iter().enumerate()
+                {
+                    if i % 2 == 0 {
+                        // Draw every other block
+                        let px = x + px_offset * scale;
+                        let py = y + py_offset * scale;
+                        self.queue().rectangle(
+                            px,
+                            py,
+                            width * scale,
+                            height * scale,
+                            palette.stone_detail.color(),
+                        );
+                    }
+                }
+            }
+            TileType::Water => {
+                self.queue()
+                    .rectangle(x, y, size, size, palette.water.color());
+            }
+        }
+    }
+
+    pub(super) fn draw_simple_ground_tile(&self, x: f32, y: f32, size: f32, tile_type: &TileType) {
+        // Simplified tile rendering for performance mode
+        let palette = &self.palette;
+        let color = match tile_type {
+            TileType::Grass => palette.grass.color(),
+            TileType::DeadGrass => palette.dead_grass.color(),
+            TileType::Dirt => palette.dirt.color(),
+            TileType::Stone => palette.stone.color(),
+            TileType::Water => palette.water.color(),
+        };
+        self.queue().rectangle(x, y, size, size, color);
+    }
+
+    /// Ripples drifting to and fro across a water tile, out of step with
+    /// its neighbours so rivers and lakes shimmer
+    pub(super) fn draw_water_shimmer(
+        &self,
+        x: f32,
+        y: f32,
+        size: f32,
+        tile: &GroundTile,
+        time: f32,
+    ) {
+        let scale = size / 64.0;
+        let ripple = self.palette.water_detail.color();
+        for row in 0..3 {
+            let phase = time * 1.5 + row as f32 * 2.1 + (tile.x + tile.y) * 0.013;
+            let offset = (phase.sin() * 0.5 + 0.5) * 36.0;
+            let ripple_y = y + (12.0 + row as f32 * 20.0) * scale;
+            self.queue().line(
+                x + (6.0 + offset) * scale,
+                ripple_y,
+                x + (20.0 + offset) * scale,
+                ripple_y,
+                2.0 * scale,
+                Color::new(
+                    ripple.r,
+                    ripple.g,
+                    ripple.b,
+                    0.35 + 0.25 * (phase * 1.7).cos(),
+                ),
+            );
+        }
+    }
+}
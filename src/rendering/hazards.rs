@@ -0,0 +1,97 @@
+//! Hazard Rendering
+//!
+//! Draws the terrain that troubles vampires (running water and garlic
+//! fields) and the lit windows of homes where human families still live.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_hazards(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let zoom = self.zoom_level;
+        for zone in &game_state.hazards {
+            let x = zone.x * zoom + camera_offset_x;
+            let y = zone.y * zoom + camera_offset_y;
+            let width = zone.width * zoom;
+            let height = zone.height * zoom;
+            if x > screen_width() || y > screen_height() || x + width < 0.0 || y + height < 0.0 {
+                continue;
+            }
+
+            match zone.kind {
+                HazardKind::RunningWater => {
+                    self.queue()
+                        .rectangle(x, y, width, height, Color::new(0.1, 0.25, 0.5, 0.85));
+                    // Ripples drifting downstream
+                    let flow = (game_state.game_time * 40.0) % 24.0;
+                    let mut ripple_y = y + flow - 24.0;
+                    while ripple_y < y + height {
+                        if ripple_y > y {
+                            self.queue().line(
+                                x + width * 0.2,
+                                ripple_y,
+                                x + width * 0.8,
+                                ripple_y + 3.0,
+                                1.5,
+                                Color::new(0.5, 0.7, 0.95, 0.5),
+                            );
+                        }
+                        ripple_y += 24.0;
+                    }
+                }
+                HazardKind::GarlicField => {
+                    self.queue()
+                        .rectangle(x, y, width, height, Color::new(0.3, 0.35, 0.2, 0.8));
+                    // Rows of white bulbs
+                    let spacing = 18.0 * zoom;
+                    let mut row_y = y + spacing / 2.0;
+                    while row_y < y + height {
+                        let mut bulb_x = x + spacing / 2.0;
+                        while bulb_x < x + width {
+                            self.queue().circle(
+                                bulb_x,
+                                row_y,
+                                2.5 * zoom,
+                                Color::new(0.95, 0.95, 0.85, 0.9),
+                            );
+                            bulb_x += spacing;
+                        }
+                        row_y += spacing;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Warm lit windows mark homes that are still inhabited
+    pub(super) fn draw_households(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for entity in &game_state.entities {
+            let Some(shelter) = entity.shelter.as_ref().filter(|shelter| shelter.inhabited) else {
+                continue;
+            };
+            let screen_x = entity.position.x * self.zoom_level + camera_offset_x;
+            let screen_y = entity.position.y * self.zoom_level + camera_offset_y;
+            let window = if shelter.invited {
+                Color::new(1.0, 0.85, 0.4, 0.95)
+            } else {
+                Color::new(1.0, 0.6, 0.2, 0.85)
+            };
+            for offset in [-14.0, 8.0] {
+                self.queue()
+                    .rectangle(screen_x + offset, screen_y - 10.0, 6.0, 8.0, window);
+            }
+        }
+    }
+}
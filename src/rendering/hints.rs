@@ -0,0 +1,60 @@
+//! Hint Rendering
+//!
+//! Draws the hints offered when the player is stuck on an objective: the
+//! toast that slides in at the top of the screen and the markers pointing
+//! at where the objective can be pursued.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+pub(super) const HINT_COLOR: Color = Color::new(0.55, 0.85, 1.0, 1.0);
+
+impl Renderer {
+    /// Markers over the places hints point to
+    pub(super) fn draw_hint_markers(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for target in game_state.hint_marker_positions() {
+            self.draw_objective_marker(
+                target,
+                HINT_COLOR,
+                game_state.game_time,
+                camera_offset_x,
+                camera_offset_y,
+            );
+        }
+    }
+
+    /// The latest hint toast, fading out as it expires
+    pub(super) fn draw_hint_toast(&self, game_state: &GameState) {
+        let Some((message, remaining)) = &game_state.objective_hints.toast else {
+            return;
+        };
+        let scale = self.ui_scale;
+        let alpha = remaining.min(1.0);
+        let size = 20.0 * scale;
+        let width = measure_text(message, None, size as u16, 1.0).width;
+        let x = (screen_width() - width) / 2.0;
+        let y = 160.0 * scale;
+        self.queue().rectangle(
+            x - 14.0 * scale,
+            y - 26.0 * scale,
+            width + 28.0 * scale,
+            38.0 * scale,
+            Color::new(0.02, 0.05, 0.1, 0.75 * alpha),
+        );
+        self.queue().rectangle_lines(
+            x - 14.0 * scale,
+            y - 26.0 * scale,
+            width + 28.0 * scale,
+            38.0 * scale,
+            1.5,
+            Color::new(HINT_COLOR.r, HINT_COLOR.g, HINT_COLOR.b, alpha),
+        );
+        self.draw_text_with_font(message, x, y, size, Color::new(0.9, 0.95, 1.0, alpha));
+    }
+}
@@ -0,0 +1,103 @@
+//! Hover Inspection Rendering
+//!
+//! Outlines the creature under the mouse cursor and shows a small panel
+//! beside the cursor with what it is, its health and how much blood it
+//! would yield, and whether it is close enough to strike or feed on.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::PlayerSystem;
+use macroquad::prelude::*;
+
+const OUTLINE_COLOR: Color = Color::new(0.95, 0.85, 0.3, 0.9);
+const PANEL_WIDTH: f32 = 190.0;
+const LINE_HEIGHT: f32 = 16.0;
+const PADDING: f32 = 8.0;
+
+impl Renderer {
+    pub(super) fn draw_hover_outline(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(entity) = game_state
+            .hovered_entity
+            .and_then(|id| EntityFinder::by_id(&game_state.entities, id))
+        else {
+            return;
+        };
+        let position = game_state.render_position(entity);
+        let x = position.x * self.zoom_level + camera_offset_x;
+        let y = position.y * self.zoom_level + camera_offset_y;
+        self.queue()
+            .circle_lines(x, y, 18.0 * self.zoom_level, 2.0, OUTLINE_COLOR);
+    }
+
+    pub(super) fn draw_hover_tooltip(&self, game_state: &GameState) {
+        let Some(inspection) = game_state
+            .hovered_entity
+            .and_then(|id| PlayerSystem::inspect(&game_state.entities, game_state.player_id, id))
+        else {
+            return;
+        };
+
+        let mut lines = Vec::new();
+        match inspection.health {
+            Some((current, max)) => lines.push((
+                self.strings.format(
+                    "inspect.health",
+                    &[
+                        ("current", &format!("{:.0}", current)),
+                        ("max", &format!("{:.0}", max)),
+                    ],
+                ),
+                LIGHTGRAY,
+            )),
+            None => lines.push((self.tr("inspect.dead").to_string(), GRAY)),
+        }
+        lines.push((
+            self.strings.format(
+                "inspect.blood",
+                &[("value", &format!("{:.0}", inspection.blood))],
+            ),
+            Color::new(0.9, 0.3, 0.3, 1.0),
+        ));
+        if inspection.can_attack {
+            lines.push((self.tr("inspect.attack").to_string(), WHITE));
+        }
+        if inspection.can_feed {
+            lines.push((self.tr("inspect.feed").to_string(), WHITE));
+        }
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let width = PANEL_WIDTH * self.ui_scale;
+        let height = ((lines.len() + 1) as f32 * LINE_HEIGHT + PADDING * 2.0) * self.ui_scale;
+        let x = (mouse_x + 16.0).min(screen_width() - width);
+        let y = (mouse_y + 16.0).min(screen_height() - height);
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.05, 0.02, 0.06, 0.9));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 1.0, DARKGRAY);
+
+        let mut line_y = y + (PADDING + 12.0) * self.ui_scale;
+        self.draw_text_with_font(
+            &inspection.name,
+            x + PADDING * self.ui_scale,
+            line_y,
+            15.0 * self.ui_scale,
+            GOLD,
+        );
+        for (line, color) in lines {
+            line_y += LINE_HEIGHT * self.ui_scale;
+            self.draw_text_with_font(
+                &line,
+                x + PADDING * self.ui_scale,
+                line_y,
+                13.0 * self.ui_scale,
+                color,
+            );
+        }
+    }
+}
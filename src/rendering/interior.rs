@@ -0,0 +1,164 @@
+//! Interior Rendering
+//!
+//! Draws the room inside a building or cave in place of the world: its
+//! floor and walls, the door, bed and stash, the vampire standing in it,
+//! and a prompt for whatever is within reach.
+
+use super::{RenderLayer, Renderer};
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::profiler::ProfileSection;
+use crate::systems::interior::{Fixture, InteriorScene, InteriorSystem, ROOM_SIZE};
+use macroquad::prelude::*;
+
+const ROOM_BACKGROUND: Color = Color::new(0.03, 0.02, 0.03, 1.0);
+const WALL_THICKNESS: f32 = 10.0;
+
+impl Renderer {
+    pub(super) fn draw_interior(&mut self, game_state: &GameState, scene: &InteriorScene) {
+        clear_background(ROOM_BACKGROUND);
+        self.queue().set_layer(RenderLayer::Terrain);
+
+        // The room is drawn as large as fits, centred on screen
+        let scale = (screen_width() * 0.8 / ROOM_SIZE.0).min(screen_height() * 0.7 / ROOM_SIZE.1);
+        let origin_x = (screen_width() - ROOM_SIZE.0 * scale) / 2.0;
+        let origin_y = (screen_height() - ROOM_SIZE.1 * scale) / 2.0;
+        let to_screen =
+            |position: Position| (origin_x + position.x * scale, origin_y + position.y * scale);
+
+        let (floor, wall) = match scene.shelter_type {
+            ShelterType::Cave => (
+                Color::new(0.18, 0.17, 0.16, 1.0),
+                Color::new(0.3, 0.28, 0.26, 1.0),
+            ),
+            _ => (
+                Color::new(0.28, 0.19, 0.12, 1.0),
+                Color::new(0.4, 0.33, 0.28, 1.0),
+            ),
+        };
+        let (width, height) = (ROOM_SIZE.0 * scale, ROOM_SIZE.1 * scale);
+        self.queue()
+            .rectangle(origin_x, origin_y, width, height, floor);
+        // Floorboards in a building, cracks in a cave
+        let seams = if scene.shelter_type == ShelterType::Cave {
+            5
+        } else {
+            12
+        };
+        for seam in 1..seams {
+            let y = origin_y + height * seam as f32 / seams as f32;
+            self.queue().line(
+                origin_x,
+                y,
+                origin_x + width,
+                y,
+                1.0,
+                Color::new(0.0, 0.0, 0.0, 0.25),
+            );
+        }
+        self.queue().rectangle_lines(
+            origin_x,
+            origin_y,
+            width,
+            height,
+            WALL_THICKNESS * scale,
+            wall,
+        );
+
+        // Door in the bottom wall
+        let (door_x, door_y) = to_screen(Fixture::Door.position());
+        self.queue().rectangle(
+            door_x - 24.0 * scale,
+            door_y - 4.0 * scale,
+            48.0 * scale,
+            24.0 * scale,
+            Color::new(0.35, 0.22, 0.1, 1.0),
+        );
+        self.queue().circle(
+            door_x + 14.0 * scale,
+            door_y + 8.0 * scale,
+            2.5 * scale,
+            GOLD,
+        );
+
+        // Bed with its pillow at the head
+        let (bed_x, bed_y) = to_screen(Fixture::Bed.position());
+        self.queue().rectangle(
+            bed_x - 30.0 * scale,
+            bed_y - 20.0 * scale,
+            60.0 * scale,
+            40.0 * scale,
+            Color::new(0.35, 0.05, 0.08, 1.0),
+        );
+        self.queue().rectangle(
+            bed_x - 26.0 * scale,
+            bed_y - 16.0 * scale,
+            14.0 * scale,
+            32.0 * scale,
+            Color::new(0.85, 0.82, 0.78, 1.0),
+        );
+
+        // Stash chest, with a lid band and lock
+        let (stash_x, stash_y) = to_screen(Fixture::Stash.position());
+        self.queue().rectangle(
+            stash_x - 22.0 * scale,
+            stash_y - 14.0 * scale,
+            44.0 * scale,
+            28.0 * scale,
+            Color::new(0.42, 0.28, 0.14, 1.0),
+        );
+        self.queue().rectangle(
+            stash_x - 22.0 * scale,
+            stash_y - 6.0 * scale,
+            44.0 * scale,
+            3.0 * scale,
+            Color::new(0.2, 0.2, 0.22, 1.0),
+        );
+        self.queue().circle(stash_x, stash_y, 2.5 * scale, GOLD);
+
+        let (player_x, player_y) = to_screen(scene.player);
+        let facing = if scene.facing_left {
+            std::f32::consts::PI
+        } else {
+            0.0
+        };
+        self.queue().set_layer(RenderLayer::Entities);
+        self.draw_vampire_sprite(player_x, player_y, 30.0 * scale, facing);
+
+        // What interact would do here
+        self.queue().set_layer(RenderLayer::Ui);
+        if let Some(fixture) = InteriorSystem::fixture_in_reach(scene) {
+            let prompt = self.tr(fixture.prompt_key());
+            if fixture == Fixture::Stash {
+                let stored: u32 = EntityFinder::by_id(&game_state.entities, scene.shelter_id)
+                    .and_then(|entity| entity.shelter.as_ref())
+                    .map_or(0, |shelter| shelter.stash.values().sum());
+                let font_size = self.ui(16.0);
+                self.queue().builtin_text(
+                    &self
+                        .strings
+                        .format("interior.stored", &[("count", &stored)]),
+                    player_x - self.ui(50.0),
+                    player_y - 24.0 * scale - self.ui(18.0),
+                    font_size,
+                    LIGHTGRAY,
+                );
+            }
+            let font_size = self.ui(20.0);
+            let dimensions = measure_text(prompt, None, font_size as u16, 1.0);
+            self.queue().builtin_text(
+                prompt,
+                player_x - dimensions.width / 2.0,
+                player_y - 24.0 * scale,
+                font_size,
+                WHITE,
+            );
+        }
+
+        self.flush_queue(RenderLayer::Entities);
+        self.end_phase(ProfileSection::RenderWorld);
+
+        // Sleeping here fades the room like the world
+        self.draw_hibernation(game_state);
+    }
+}
@@ -0,0 +1,237 @@
+//! Item Rendering
+//!
+//! Draws item pickups lying in the world and the inventory screen.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Small glinting icons for items on the ground
+    pub(super) fn draw_pickups(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let glint = (game_state.game_time * 3.0).sin() * 0.5 + 0.5;
+        for entity in &game_state.entities {
+            let Some(pickup) = entity.pickup else {
+                continue;
+            };
+            let screen_x = entity.position.x * self.zoom_level + camera_offset_x;
+            let screen_y = entity.position.y * self.zoom_level + camera_offset_y;
+            if screen_x < -20.0
+                || screen_x > screen_width() + 20.0
+                || screen_y < -20.0
+                || screen_y > screen_height() + 20.0
+            {
+                continue;
+            }
+
+            self.queue().circle(
+                screen_x,
+                screen_y,
+                10.0 + glint * 3.0,
+                Color::new(1.0, 1.0, 0.8, 0.1 + glint * 0.1),
+            );
+            self.draw_item_icon(pickup.item, screen_x, screen_y, self.zoom_level);
+        }
+    }
+
+    /// Bottled vials beside the blood bar: one icon per slot up to the
+    /// carry limit, with the next empty slot filling as overflow collects
+    pub(super) fn draw_blood_reserve(&self, x: f32, y: f32, vials: u32, bottling: f32) {
+        let scale = self.ui_scale;
+        let limit = ItemKind::BloodVial.carry_limit().unwrap_or(vials);
+        for slot in 0..limit {
+            let slot_x = x + slot as f32 * 12.0 * scale;
+            if slot < vials {
+                self.draw_item_icon(ItemKind::BloodVial, slot_x, y, scale);
+                continue;
+            }
+            self.queue().rectangle_lines(
+                slot_x - 3.0 * scale,
+                y - 7.0 * scale,
+                6.0 * scale,
+                12.0 * scale,
+                1.0,
+                Color::new(0.5, 0.3, 0.3, 0.6),
+            );
+            if slot == vials && bottling > 0.0 {
+                let fill = 12.0 * scale * bottling.min(1.0);
+                self.queue().rectangle(
+                    slot_x - 3.0 * scale,
+                    y + 5.0 * scale - fill,
+                    6.0 * scale,
+                    fill,
+                    Color::new(0.6, 0.0, 0.05, 0.7),
+                );
+            }
+        }
+    }
+
+    /// Icon for an item centred on (x, y)
+    fn draw_item_icon(&self, item: ItemKind, x: f32, y: f32, scale: f32) {
+        match item {
+            ItemKind::BloodVial => {
+                self.queue().rectangle(
+                    x - 3.0 * scale,
+                    y - 7.0 * scale,
+                    6.0 * scale,
+                    12.0 * scale,
+                    Color::new(0.6, 0.0, 0.05, 1.0),
+                );
+                self.queue().rectangle(
+                    x - 2.0 * scale,
+                    y - 9.0 * scale,
+                    4.0 * scale,
+                    3.0 * scale,
+                    BEIGE,
+                );
+            }
+            ItemKind::Bandage => {
+                self.queue().rectangle(
+                    x - 6.0 * scale,
+                    y - 4.0 * scale,
+                    12.0 * scale,
+                    8.0 * scale,
+                    Color::new(0.9, 0.88, 0.8, 1.0),
+                );
+                self.queue().line(
+                    x - 6.0 * scale,
+                    y,
+                    x + 6.0 * scale,
+                    y,
+                    1.0,
+                    Color::new(0.7, 0.65, 0.55, 1.0),
+                );
+            }
+            ItemKind::Key => {
+                self.queue()
+                    .circle_lines(x - 4.0 * scale, y, 3.0 * scale, 1.5, GOLD);
+                self.queue()
+                    .line(x - 1.0 * scale, y, x + 7.0 * scale, y, 1.5, GOLD);
+                self.queue().line(
+                    x + 5.0 * scale,
+                    y,
+                    x + 5.0 * scale,
+                    y + 3.0 * scale,
+                    1.5,
+                    GOLD,
+                );
+            }
+            ItemKind::Trinket => {
+                self.queue()
+                    .poly(x, y, 4, 5.0 * scale, 45.0, Color::new(0.5, 0.8, 0.9, 1.0));
+                self.queue()
+                    .poly_lines(x, y, 4, 5.0 * scale, 45.0, 1.0, WHITE);
+            }
+            ItemKind::HunterNotes => {
+                let paper = Color::new(0.85, 0.78, 0.6, 1.0);
+                self.queue().rectangle(
+                    x - 5.0 * scale,
+                    y - 6.0 * scale,
+                    10.0 * scale,
+                    12.0 * scale,
+                    paper,
+                );
+                for line in 0..3 {
+                    let line_y = y - 3.0 * scale + line as f32 * 3.0 * scale;
+                    self.queue().line(
+                        x - 3.0 * scale,
+                        line_y,
+                        x + 3.0 * scale,
+                        line_y,
+                        1.0,
+                        Color::new(0.35, 0.25, 0.2, 1.0),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Inventory screen: carried items, the highlighted item's effect and
+    /// how full the pack is
+    pub(super) fn draw_inventory(&self, game_state: &GameState) {
+        let scale = self.ui_scale;
+        let width = 420.0 * scale;
+        let height = 320.0 * scale;
+        let x = (screen_width() - width) / 2.0;
+        let y = (screen_height() - height) / 2.0;
+        let line = 28.0 * scale;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.08, 0.05, 0.08, 0.92));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 2.0, Color::new(0.6, 0.1, 0.1, 1.0));
+
+        let text_x = x + 20.0 * scale;
+        let mut text_y = y + 35.0 * scale;
+        self.draw_text_with_font(self.tr("items.title"), text_x, text_y, 24.0 * scale, WHITE);
+
+        let entries = game_state.player_inventory();
+        let capacity = EntityFinder::by_id(&game_state.entities, game_state.player_id)
+            .and_then(|player| player.inventory.as_ref())
+            .map(|inventory| (inventory.total(), inventory.capacity));
+        if let Some((total, capacity)) = capacity {
+            let label = format!("{}/{}", total, capacity);
+            let label_width = measure_text(&label, None, (16.0 * scale) as u16, 1.0).width;
+            self.draw_text_with_font(
+                &label,
+                x + width - 20.0 * scale - label_width,
+                text_y,
+                16.0 * scale,
+                LIGHTGRAY,
+            );
+        }
+
+        text_y += line;
+        if entries.is_empty() {
+            self.draw_text_with_font(self.tr("items.empty"), text_x, text_y, 18.0 * scale, GRAY);
+        }
+
+        let selected = game_state
+            .selected_item
+            .min(entries.len().saturating_sub(1));
+        for (index, (item, count)) in entries.iter().enumerate() {
+            let is_selected = index == selected;
+            if is_selected {
+                self.queue().rectangle(
+                    x + 10.0 * scale,
+                    text_y - 20.0 * scale,
+                    width - 20.0 * scale,
+                    line,
+                    Color::new(0.35, 0.05, 0.05, 0.8),
+                );
+            }
+            self.draw_item_icon(*item, text_x + 8.0 * scale, text_y - 6.0 * scale, scale);
+            self.draw_text_with_font(
+                &format!("{} x{}", item.display_name(), count),
+                text_x + 28.0 * scale,
+                text_y,
+                18.0 * scale,
+                if is_selected { GOLD } else { WHITE },
+            );
+            text_y += line;
+        }
+
+        if let Some((item, _)) = entries.get(selected) {
+            self.draw_text_with_font(
+                item.description(),
+                text_x,
+                y + height - 45.0 * scale,
+                16.0 * scale,
+                LIGHTGRAY,
+            );
+        }
+        self.draw_text_with_font(
+            self.tr("items.hint"),
+            text_x,
+            y + height - 15.0 * scale,
+            16.0 * scale,
+            GRAY,
+        );
+    }
+}
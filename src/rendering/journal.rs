@@ -0,0 +1,224 @@
+//! Journal Rendering
+//!
+//! Draws the quest journal: the current phase's objectives with how far the
+//! player has come on each, and the objectives already completed with the
+//! day they were done. Objectives the player is stuck on carry a hint
+//! beneath them. Quests taken on from clan leaders follow, open and done.
+//! Long lists scroll inside the panel.
+
+use super::dream::wrap_text;
+use super::hints::HINT_COLOR;
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::{ObjectivesSystem, QuestGoal};
+use macroquad::prelude::*;
+
+/// One line in the journal's scrolling list. Headings and empty lists
+/// hold translation keys.
+enum JournalRow {
+    Heading(&'static str),
+    Current(String, Option<f32>),
+    /// A line of guidance offered for the objective above
+    Hint(String),
+    Completed(String, Option<u32>),
+    Empty(&'static str),
+}
+
+impl Renderer {
+    pub(super) fn draw_journal(&self, game_state: &GameState) {
+        let scale = self.ui_scale;
+        let width = 600.0 * scale;
+        let height = (screen_height() - 80.0 * scale).min(460.0 * scale);
+        let x = (screen_width() - width) / 2.0;
+        let y = (screen_height() - height) / 2.0;
+        let line = 26.0 * scale;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.08, 0.05, 0.04, 0.94));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 2.0, Color::new(0.6, 0.4, 0.2, 1.0));
+
+        let text_x = x + 20.0 * scale;
+        let mut text_y = y + 35.0 * scale;
+        self.draw_text_with_font(
+            self.tr("journal.title"),
+            text_x,
+            text_y,
+            24.0 * scale,
+            WHITE,
+        );
+
+        let progress = game_state.get_objectives_progress();
+        text_y += line;
+        self.draw_text_with_font(
+            &self.strings.format(
+                "journal.phase",
+                &[
+                    ("phase", &format!("{:?}", progress.current_phase)),
+                    ("done", &progress.completed_count),
+                    ("left", &progress.remaining_count),
+                ],
+            ),
+            text_x,
+            text_y,
+            16.0 * scale,
+            LIGHTGRAY,
+        );
+        text_y += 12.0 * scale;
+        self.draw_journal_bar(
+            text_x,
+            text_y,
+            width - 40.0 * scale,
+            progress.completion_percentage / 100.0,
+        );
+        text_y += 20.0 * scale;
+
+        let stats = game_state.objective_stats();
+        let mut rows = vec![JournalRow::Heading("journal.current")];
+        if game_state.phase_objectives.is_empty() {
+            rows.push(JournalRow::Empty("journal.phase_done"));
+        }
+        for objective in &game_state.phase_objectives {
+            rows.push(JournalRow::Current(
+                objective.clone(),
+                ObjectivesSystem::objective_fraction(objective, &stats),
+            ));
+            if let Some(guidance) = game_state.objective_hints.guidance(objective) {
+                rows.extend(wrap_text(guidance, 70).into_iter().map(JournalRow::Hint));
+            }
+        }
+        rows.push(JournalRow::Heading("journal.quests"));
+        if game_state.quests.active.is_empty() && game_state.quests.completed.is_empty() {
+            rows.push(JournalRow::Empty("journal.no_quests"));
+        }
+        for quest in &game_state.quests.active {
+            let fraction = matches!(quest.goal, QuestGoal::Slay { .. }).then(|| quest.fraction());
+            rows.push(JournalRow::Current(quest.description(), fraction));
+        }
+        rows.extend(
+            game_state
+                .quests
+                .completed
+                .iter()
+                .rev()
+                .map(|(quest, day)| JournalRow::Completed(quest.clone(), Some(*day))),
+        );
+        rows.push(JournalRow::Heading("journal.completed"));
+        if game_state.completed_objectives.is_empty() {
+            rows.push(JournalRow::Empty("journal.none_completed"));
+        }
+        rows.extend(
+            game_state
+                .completed_objectives
+                .iter()
+                .rev()
+                .map(|objective| {
+                    JournalRow::Completed(
+                        objective.clone(),
+                        game_state.objective_completion_days.get(objective).copied(),
+                    )
+                }),
+        );
+
+        let footer_height = 30.0 * scale;
+        let visible = (((y + height - footer_height) - text_y) / line)
+            .floor()
+            .max(1.0) as usize;
+        let max_scroll = rows.len().saturating_sub(visible);
+        let scroll = game_state.journal_scroll.min(max_scroll);
+
+        for row in rows.iter().skip(scroll).take(visible) {
+            text_y += line;
+            match row {
+                JournalRow::Heading(title) => {
+                    self.draw_text_with_font(self.tr(title), text_x, text_y, 18.0 * scale, GOLD);
+                }
+                JournalRow::Current(objective, fraction) => {
+                    self.draw_text_with_font(
+                        &format!("- {}", objective),
+                        text_x + 10.0 * scale,
+                        text_y,
+                        16.0 * scale,
+                        WHITE,
+                    );
+                    if let Some(fraction) = fraction {
+                        self.draw_journal_bar(
+                            x + width - 170.0 * scale,
+                            text_y - 10.0 * scale,
+                            150.0 * scale,
+                            *fraction,
+                        );
+                    }
+                }
+                JournalRow::Hint(text) => {
+                    self.draw_text_with_font(
+                        text,
+                        text_x + 30.0 * scale,
+                        text_y,
+                        14.0 * scale,
+                        HINT_COLOR,
+                    );
+                }
+                JournalRow::Completed(objective, day) => {
+                    self.draw_text_with_font(
+                        &format!("+ {}", objective),
+                        text_x + 10.0 * scale,
+                        text_y,
+                        16.0 * scale,
+                        Color::new(0.6, 0.8, 0.6, 1.0),
+                    );
+                    if let Some(day) = day {
+                        self.draw_text_with_font(
+                            &self.strings.format("journal.day", &[("day", day)]),
+                            x + width - 90.0 * scale,
+                            text_y,
+                            16.0 * scale,
+                            GRAY,
+                        );
+                    }
+                }
+                JournalRow::Empty(text) => {
+                    self.draw_text_with_font(
+                        self.tr(text),
+                        text_x + 10.0 * scale,
+                        text_y,
+                        16.0 * scale,
+                        GRAY,
+                    );
+                }
+            }
+        }
+
+        let footer = if max_scroll > 0 {
+            self.strings.format(
+                "journal.scroll",
+                &[("page", &(scroll + 1)), ("pages", &(max_scroll + 1))],
+            )
+        } else {
+            self.tr("journal.close").to_string()
+        };
+        self.draw_text_with_font(
+            &footer,
+            text_x,
+            y + height - 12.0 * scale,
+            14.0 * scale,
+            GRAY,
+        );
+    }
+
+    /// A thin progress bar, filled by `fraction` (0.0 to 1.0)
+    fn draw_journal_bar(&self, x: f32, y: f32, width: f32, fraction: f32) {
+        let height = 8.0 * self.ui_scale;
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.2, 0.15, 0.1, 1.0));
+        self.queue().rectangle(
+            x,
+            y,
+            width * fraction.clamp(0.0, 1.0),
+            height,
+            Color::new(0.8, 0.55, 0.2, 1.0),
+        );
+        self.queue()
+            .rectangle_lines(x, y, width, height, 1.0, Color::new(0.6, 0.4, 0.2, 1.0));
+    }
+}
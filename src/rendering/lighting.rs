@@ -0,0 +1,90 @@
+//! Lighting Rendering
+//!
+//! The color of the sky and the day and night tint laid over the finished
+//! scene, with a soft glow around the player after dark.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::TimeSystem;
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// How close the hour is to sunrise or sunset, from 0.0 (over 1.5 hours
+    /// away) to 1.0 (exactly at 6:00 or 18:00)
+    pub(super) fn twilight_factor(time: &TimeSystem) -> f32 {
+        let hour = time.current_time();
+        let from_twilight = (hour - 6.0).abs().min((hour - 18.0).abs());
+        (1.0 - from_twilight / 1.5).max(0.0)
+    }
+
+    /// Sky colour behind the world: dark blue at night, pale blue by day,
+    /// orange around dawn and dusk
+    pub(super) fn sky_color(time: &TimeSystem) -> Color {
+        let night = Color::new(0.05, 0.05, 0.15, 1.0);
+        let day = Color::new(0.45, 0.6, 0.85, 1.0);
+        let twilight = Color::new(0.55, 0.3, 0.2, 1.0);
+
+        let base = Self::lerp_color(night, day, time.get_sunlight_intensity());
+        Self::lerp_color(base, twilight, Self::twilight_factor(time) * 0.7)
+    }
+
+    /// Scene-wide lighting tint: warm and faint at noon, deep blue at night,
+    /// orange around dawn and dusk
+    pub(super) fn scene_tint(time: &TimeSystem) -> Color {
+        let sunlight = time.get_sunlight_intensity();
+        let twilight = Self::twilight_factor(time);
+        let orange = Color::new(1.0, 0.5, 0.15, 0.22);
+
+        let base = if sunlight > 0.0 {
+            Color::new(1.0, 0.93, 0.7, 0.1 * sunlight)
+        } else {
+            Color::new(0.02, 0.04, 0.18, 0.45)
+        };
+        Self::lerp_color(base, orange, twilight * 0.8)
+    }
+
+    pub(super) fn lerp_color(from: Color, to: Color, amount: f32) -> Color {
+        let t = amount.clamp(0.0, 1.0);
+        Color::new(
+            from.r + (to.r - from.r) * t,
+            from.g + (to.g - from.g) * t,
+            from.b + (to.b - from.b) * t,
+            from.a + (to.a - from.a) * t,
+        )
+    }
+
+    /// Day/night lighting overlay over the world, with a soft glow around
+    /// the player after dark
+    pub(super) fn draw_lighting_pass(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let tint = Self::scene_tint(&game_state.time);
+        self.queue()
+            .rectangle(0.0, 0.0, screen_width(), screen_height(), tint);
+
+        if game_state.time.is_day() {
+            return;
+        }
+        let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) else {
+            return;
+        };
+
+        // Stacked translucent discs brighten the area nearest the player most
+        let screen_x = player.position.x * self.zoom_level + camera_offset_x;
+        let screen_y = player.position.y * self.zoom_level + camera_offset_y;
+        let rings = if self.performance_mode { 3 } else { 6 };
+        for ring in 0..rings {
+            let radius = (140.0 - ring as f32 * (100.0 / rings as f32)) * self.zoom_level;
+            self.queue().circle(
+                screen_x,
+                screen_y,
+                radius,
+                Color::new(0.75, 0.7, 1.0, 0.3 / rings as f32),
+            );
+        }
+    }
+}
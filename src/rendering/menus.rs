@@ -0,0 +1,815 @@
+//! Menu Rendering
+//!
+//! Screens opened over the game: pause with the world seed, video settings,
+//! the clan menu with its population trends, the legend, the quick start
+//! guide, and the fading controls hint of photo mode.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::population::POPULATION_HISTORY_DAYS;
+use crate::systems::{PhotoMode, WorldSystem};
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_pause_menu(&self, game_state: &GameState) {
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+
+        let center_x = screen_width() / 2.0;
+        let center_y = screen_height() / 2.0;
+
+        self.draw_text_with_font(
+            self.tr("pause.title"),
+            center_x - self.ui(50.0),
+            center_y - self.ui(50.0),
+            self.ui(36.0),
+            WHITE,
+        );
+        self.draw_text_with_font(
+            self.tr("pause.resume"),
+            center_x - self.ui(80.0),
+            center_y,
+            self.ui(20.0),
+            WHITE,
+        );
+        self.draw_text_with_font(
+            self.tr("pause.quit"),
+            center_x - self.ui(180.0),
+            center_y + self.ui(30.0),
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        // Shown so a run can be shared and played again
+        self.draw_text_with_font(
+            &self
+                .strings
+                .format("pause.seed", &[("seed", &game_state.world_seed)]),
+            center_x - self.ui(80.0),
+            center_y + self.ui(60.0),
+            self.ui(16.0),
+            GRAY,
+        );
+    }
+
+    pub(super) fn draw_photo_mode_hint(&self, photo_mode: &PhotoMode) {
+        let alpha = photo_mode.hint_alpha();
+        if alpha <= 0.0 {
+            return;
+        }
+        let scale = self.ui_scale;
+        let hint = self.tr("photo.hint");
+        let size = 16.0 * scale;
+        let width = measure_text(hint, None, size as u16, 1.0).width;
+        let x = (screen_width() - width) / 2.0;
+        let y = screen_height() - 30.0 * scale;
+        self.queue().rectangle(
+            x - 10.0 * scale,
+            y - 20.0 * scale,
+            width + 20.0 * scale,
+            30.0 * scale,
+            Color::new(0.0, 0.0, 0.0, 0.6 * alpha),
+        );
+        self.draw_text_with_font(hint, x, y, size, Color::new(1.0, 1.0, 1.0, alpha));
+    }
+
+    pub(super) fn draw_video_settings(&self, game_state: &GameState) {
+        let settings = &game_state.video_settings;
+        let width = 420.0 * self.ui_scale;
+        let height = 436.0 * self.ui_scale;
+        let x = (screen_width() - width) / 2.0;
+        let y = (screen_height() - height) / 2.0;
+        let line = 24.0 * self.ui_scale;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.1, 0.1, 0.2, 0.9));
+        self.queue().rectangle_lines(x, y, width, height, 2.0, GRAY);
+
+        let text_x = x + 20.0 * self.ui_scale;
+        let mut text_y = y + 35.0 * self.ui_scale;
+        self.draw_text_with_font(
+            self.tr("video.title"),
+            text_x,
+            text_y,
+            24.0 * self.ui_scale,
+            WHITE,
+        );
+
+        text_y += line * 1.5;
+        self.draw_text_with_font(
+            &self.strings.format(
+                "video.preset",
+                &[("preset", &settings.preset.display_name())],
+            ),
+            text_x,
+            text_y,
+            20.0 * self.ui_scale,
+            YELLOW,
+        );
+
+        let strings = &self.strings;
+        let details = [
+            (
+                "video.particles",
+                strings.on_off(settings.particles).to_string(),
+            ),
+            (
+                "video.detailed_tiles",
+                strings.on_off(settings.detailed_tiles).to_string(),
+            ),
+            (
+                "video.stars",
+                format!("{:.0}%", settings.star_density * 100.0),
+            ),
+            (
+                "video.glow",
+                strings.on_off(settings.post_processing).to_string(),
+            ),
+            ("settings.theme", settings.theme.display_name().to_string()),
+            (
+                "settings.camera_look_ahead",
+                strings.on_off(settings.camera_look_ahead).to_string(),
+            ),
+            (
+                "settings.screen_shake",
+                strings.on_off(settings.screen_shake).to_string(),
+            ),
+            (
+                "settings.sprites",
+                strings
+                    .get(if settings.textured_sprites {
+                        "settings.sprites_textured"
+                    } else {
+                        "settings.sprites_pixel_art"
+                    })
+                    .to_string(),
+            ),
+            (
+                "settings.frame_cap",
+                strings.format(
+                    "video.frame_cap_value",
+                    &[
+                        ("cap", &settings.frame_pacing.frame_cap.display_name()),
+                        ("vsync", &strings.on_off(settings.frame_pacing.vsync)),
+                    ],
+                ),
+            ),
+            ("settings.ui_scale", settings.ui_scale.display_name()),
+            (
+                "settings.language",
+                settings.language.display_name().to_string(),
+            ),
+            (
+                "video.active_radius",
+                match settings.active_entity_radius {
+                    Some(radius) => format!("{:.0}", radius),
+                    None => strings.get("video.whole_world").to_string(),
+                },
+            ),
+            (
+                "settings.master_volume",
+                format!("{:.0}%", game_state.audio_settings.master * 100.0),
+            ),
+        ];
+        for (label, value) in &details {
+            text_y += line;
+            self.draw_text_with_font(
+                &format!("{}: {}", self.tr(label), value),
+                text_x,
+                text_y,
+                16.0 * self.ui_scale,
+                LIGHTGRAY,
+            );
+        }
+
+        self.draw_text_with_font(
+            self.tr("video.hint"),
+            text_x,
+            y + height - 15.0 * self.ui_scale,
+            16.0 * self.ui_scale,
+            GRAY,
+        );
+    }
+
+    pub(super) fn draw_clan_menu(&self, game_state: &GameState) {
+        self.queue().rectangle(
+            self.ui(50.0),
+            self.ui(50.0),
+            self.right_edge(100.0),
+            self.bottom_edge(100.0),
+            Color::new(0.1, 0.1, 0.2, 0.9),
+        );
+
+        self.draw_text_with_font(
+            self.tr("clans.title"),
+            self.ui(70.0),
+            self.ui(80.0),
+            self.ui(24.0),
+            WHITE,
+        );
+
+        let mut y = self.ui(120.0);
+        for (index, clan_name) in game_state.sorted_clan_names().iter().enumerate() {
+            let Some(clan) = game_state.clans.get(clan_name) else {
+                continue;
+            };
+            let status_color = if clan.is_allied { GREEN } else { RED };
+
+            if index == game_state.selected_clan {
+                self.queue().rectangle(
+                    self.ui(60.0),
+                    y - self.ui(18.0),
+                    self.right_edge(120.0),
+                    self.ui(24.0),
+                    Color::new(0.3, 0.3, 0.5, 0.6),
+                );
+            }
+
+            self.draw_text_with_font(&clan.name, self.ui(70.0), y, self.ui(20.0), WHITE);
+            self.draw_text_with_font(
+                &self
+                    .strings
+                    .format("clans.leader", &[("name", &clan.leader_name)]),
+                self.ui(200.0),
+                y,
+                self.ui(16.0),
+                GRAY,
+            );
+            self.draw_text_with_font(
+                &self
+                    .strings
+                    .format("clans.members", &[("count", &clan.member_count)]),
+                self.ui(350.0),
+                y,
+                self.ui(16.0),
+                GRAY,
+            );
+            self.draw_text_with_font(
+                &self.strings.format(
+                    "clans.trust",
+                    &[("trust", &format!("{:.1}", clan.trust_towards_player))],
+                ),
+                self.ui(450.0),
+                y,
+                self.ui(16.0),
+                GRAY,
+            );
+
+            let status = self.tr(if clan.is_defeated {
+                "clans.subjugated"
+            } else if clan.is_allied {
+                "clans.allied"
+            } else if clan.is_hostile {
+                "clans.hostile"
+            } else {
+                "clans.neutral"
+            });
+            self.draw_text_with_font(status, self.ui(550.0), y, self.ui(16.0), status_color);
+
+            if clan.owes_tribute() {
+                let unrest_color = if clan.unrest > 0.6 {
+                    RED
+                } else if clan.unrest > 0.3 {
+                    ORANGE
+                } else {
+                    GRAY
+                };
+                self.draw_text_with_font(
+                    &self.strings.format(
+                        "clans.tribute",
+                        &[("level", &clan.tribute_level.display_name())],
+                    ),
+                    self.ui(660.0),
+                    y,
+                    self.ui(16.0),
+                    GRAY,
+                );
+                self.draw_text_with_font(
+                    &self.strings.format(
+                        "clans.unrest",
+                        &[("percent", &format!("{:.0}", clan.unrest * 100.0))],
+                    ),
+                    self.ui(820.0),
+                    y,
+                    self.ui(16.0),
+                    unrest_color,
+                );
+            }
+
+            y += self.ui(25.0);
+        }
+
+        let stockpile = &game_state.tribute_stockpile;
+        self.draw_text_with_font(
+            &self.strings.format(
+                "clans.stockpile",
+                &[
+                    ("vials", &stockpile.blood_vials),
+                    ("materials", &stockpile.materials),
+                    ("members", &stockpile.members),
+                ],
+            ),
+            self.ui(70.0),
+            y + self.ui(20.0),
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        self.draw_population_trends(game_state, self.ui(70.0), y + self.ui(60.0));
+
+        self.draw_text_with_font(
+            self.tr("clans.hint"),
+            self.ui(70.0),
+            self.bottom_edge(40.0),
+            self.ui(18.0),
+            LIGHTGRAY,
+        );
+    }
+
+    /// Bar chart of each clan's size at the start of recent days
+    pub(super) fn draw_population_trends(&self, game_state: &GameState, x: f32, y: f32) {
+        self.draw_text_with_font(
+            &self
+                .strings
+                .format("clans.population", &[("days", &POPULATION_HISTORY_DAYS)]),
+            x,
+            y,
+            self.ui(18.0),
+            WHITE,
+        );
+
+        let tallest = game_state
+            .clans
+            .values()
+            .flat_map(|clan| clan.population_history.iter().copied())
+            .max()
+            .unwrap_or(1)
+            .max(1) as f32;
+        let bar_width = self.ui(14.0);
+        let chart_height = self.ui(40.0);
+        let mut row_y = y + self.ui(20.0);
+        let clan_names = game_state.sorted_clan_names();
+        for clan_name in &clan_names {
+            let Some(clan) = game_state.clans.get(clan_name) else {
+                continue;
+            };
+            let color = self
+                .palette
+                .clan_color(&clan.name, WorldSystem::clan_member_color(&clan.name));
+            self.draw_text_with_font(&clan.name, x, row_y + chart_height, self.ui(16.0), GRAY);
+
+            let chart_x = x + self.ui(130.0);
+            for (day, &count) in clan.population_history.iter().enumerate() {
+                let height = count as f32 / tallest * chart_height;
+                self.queue().rectangle(
+                    chart_x + day as f32 * (bar_width + self.ui(4.0)),
+                    row_y + chart_height - height,
+                    bar_width,
+                    height,
+                    color,
+                );
+            }
+
+            let first = clan.population_history.first().copied();
+            let trend = match first.map(|first| clan.member_count as i64 - first as i64) {
+                Some(change) if change != 0 => self.strings.format(
+                    "clans.trend",
+                    &[
+                        ("count", &clan.member_count),
+                        ("change", &format!("{:+}", change)),
+                    ],
+                ),
+                _ => self
+                    .strings
+                    .format("clans.trend_steady", &[("count", &clan.member_count)]),
+            };
+            let trend_x = chart_x
+                + POPULATION_HISTORY_DAYS as f32 * (bar_width + self.ui(4.0))
+                + self.ui(10.0);
+            self.draw_text_with_font(
+                &trend,
+                trend_x,
+                row_y + chart_height,
+                self.ui(16.0),
+                LIGHTGRAY,
+            );
+
+            let enemies = game_state
+                .clan_relations
+                .enemies_of(&clan.name, &clan_names);
+            if !enemies.is_empty() {
+                self.draw_text_with_font(
+                    &self
+                        .strings
+                        .format("clans.at_war", &[("clans", &enemies.join(", "))]),
+                    trend_x + self.ui(160.0),
+                    row_y + chart_height,
+                    self.ui(16.0),
+                    Color::new(0.9, 0.35, 0.3, 1.0),
+                );
+            }
+            row_y += chart_height + self.ui(12.0);
+        }
+    }
+
+    pub(super) fn draw_legend(&self, _game_state: &GameState) {
+        // Semi-transparent background
+        self.queue().rectangle(
+            self.right_edge(320.0),
+            self.ui(50.0),
+            self.ui(270.0),
+            self.ui(400.0),
+            Color::new(0.0, 0.0, 0.0, 0.8),
+        );
+
+        // Legend title
+        self.draw_text_with_font(
+            self.tr("legend.title"),
+            self.right_edge(310.0),
+            self.ui(80.0),
+            self.ui(24.0),
+            WHITE,
+        );
+
+        let mut y = self.ui(110.0);
+        let legend_x = self.right_edge(310.0);
+        let color_size = self.ui(15.0);
+        let text_offset = self.ui(25.0);
+
+        // Player - vampire with pixel art
+        self.draw_vampire_sprite(
+            legend_x + color_size / 2.0,
+            y + color_size / 2.0,
+            color_size * 1.5, // Larger for better visibility
+            0.0,
+        );
+        self.draw_text_with_font(
+            self.tr("legend.player_vampire_red"),
+            legend_x + text_offset,
+            y,
+            self.ui(16.0),
+            WHITE,
+        );
+        y += self.ui(25.0);
+
+        // Clan Leaders with pixel art
+        self.draw_clan_leader_sprite(
+            legend_x + color_size / 2.0,
+            y + color_size / 2.0,
+            color_size * 1.5, // Larger for better visibility
+            BEIGE,
+        );
+        self.queue().builtin_text(
+            self.tr("legend.bone_eaters_leader"),
+            legend_x + text_offset,
+            y + self.ui(12.0),
+            self.ui(14.0),
+            WHITE,
+        );
+        y += self.ui(20.0);
+
+        self.draw_clan_leader_sprite(
+            legend_x + color_size / 2.0,
+            y + color_size / 2.0,
+            color_size * 1.5, // Larger for better visibility
+            PURPLE,
+        );
+        self.queue().builtin_text(
+            self.tr("legend.flame_haters_leader"),
+            legend_x + text_offset,
+            y + self.ui(12.0),
+            self.ui(14.0),
+            WHITE,
+        );
+        y += self.ui(20.0);
+
+        self.draw_clan_leader_sprite(
+            legend_x + color_size / 2.0,
+            y + color_size / 2.0,
+            color_size * 1.5, // Larger for better visibility
+            DARKBLUE,
+        );
+        self.queue().builtin_text(
+            self.tr("legend.night_bloods_leader"),
+            legend_x + text_offset,
+            y + self.ui(12.0),
+            self.ui(14.0),
+            WHITE,
+        );
+        y += self.ui(25.0);
+
+        // Enemies with pixel art
+        self.draw_infected_sprite(
+            legend_x + color_size / 2.0,
+            y + color_size / 2.0,
+            color_size * 1.5, // Larger for better visibility
+            0.0,
+        );
+        self.queue().builtin_text(
+            self.tr("legend.hostile_infected_red"),
+            legend_x + text_offset,
+            y + self.ui(12.0),
+            self.ui(14.0),
+            WHITE,
+        );
+        y += self.ui(20.0);
+
+        // Animals with pixel art
+        self.draw_animal_sprite(
+            legend_x + color_size / 2.0,
+            y + color_size / 2.0,
+            color_size * 1.5, // Larger for better visibility
+        );
+        self.queue().builtin_text(
+            self.tr("legend.animals_blood_sources"),
+            legend_x + text_offset,
+            y + self.ui(12.0),
+            self.ui(14.0),
+            WHITE,
+        );
+        y += self.ui(30.0);
+
+        // Game mechanics legend
+        self.queue().builtin_text(
+            self.tr("legend.game_tips"),
+            legend_x,
+            y,
+            self.ui(18.0),
+            YELLOW,
+        );
+        y += self.ui(25.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.red_entities_hostile"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.feed_animals_blood"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.interact_clan_leaders"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.avoid_sunlight_during"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.watch_blood_meter"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.health_bars_show"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.ground_terrain_shows"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(18.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.stars_twinkle_night"),
+            legend_x,
+            y,
+            self.ui(14.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(25.0);
+
+        self.queue().builtin_text(
+            self.tr("legend.press_l_close"),
+            legend_x,
+            y,
+            self.ui(16.0),
+            YELLOW,
+        );
+    }
+
+    pub(super) fn draw_quick_start_guide(&self) {
+        // Full screen overlay
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::new(0.0, 0.0, 0.0, 0.85),
+        );
+
+        let center_x = screen_width() / 2.0;
+        let mut y = self.ui(80.0);
+
+        // Title
+        self.draw_text_with_font(
+            self.tr("guide.title"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(32.0),
+            RED,
+        );
+        y += self.ui(60.0);
+
+        // Story intro
+        self.draw_text_with_font(
+            self.tr("guide.sole_survivor_viral"),
+            center_x - self.ui(250.0),
+            y,
+            self.ui(18.0),
+            WHITE,
+        );
+        y += self.ui(25.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.must_survive_adapt"),
+            center_x - self.ui(220.0),
+            y,
+            self.ui(18.0),
+            WHITE,
+        );
+        y += self.ui(25.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.game_features_pixel"),
+            center_x - self.ui(240.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(35.0);
+
+        // Essential controls
+        self.draw_text_with_font(
+            self.tr("guide.essential_controls"),
+            center_x - self.ui(100.0),
+            y,
+            self.ui(20.0),
+            YELLOW,
+        );
+        y += self.ui(30.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.wasd_move_around"),
+            center_x - self.ui(150.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.r_feed_animals"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.hold_r_drain"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.space_attack_hostile"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.e_talk_clan"),
+            center_x - self.ui(210.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(30.0);
+
+        // Survival tips
+        self.draw_text_with_font(
+            self.tr("guide.survival_tips"),
+            center_x - self.ui(70.0),
+            y,
+            self.ui(20.0),
+            YELLOW,
+        );
+        y += self.ui(30.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.keep_blood_meter"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.avoid_sunlight_during"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.feed_small_animals"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.build_trust_clan"),
+            center_x - self.ui(220.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.abilities_improve_each"),
+            center_x - self.ui(160.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(20.0);
+
+        self.draw_text_with_font(
+            self.tr("guide.walk_varied_ground"),
+            center_x - self.ui(170.0),
+            y,
+            self.ui(16.0),
+            LIGHTGRAY,
+        );
+        y += self.ui(40.0);
+
+        // Legend reference
+        self.draw_text_with_font(
+            self.tr("guide.press_l_detailed"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(16.0),
+            YELLOW,
+        );
+        y += self.ui(40.0);
+
+        // Close instructions
+        self.draw_text_with_font(
+            self.tr("guide.press_h_toggle"),
+            center_x - self.ui(200.0),
+            y,
+            self.ui(18.0),
+            WHITE,
+        );
+    }
+}
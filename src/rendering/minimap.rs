@@ -0,0 +1,247 @@
+//! Minimap Rendering
+//!
+//! Draws a corner overview of the whole world streamed in so far showing the
+//! player, territories, discovered shelters and the player's lair, read
+//! signposts, clan leaders, hostile infected sensed through blood sense, the
+//! crow scout with the creatures it has marked, skirmishes between clans and
+//! infected, raids between clans at war, and places hints point to. Ground
+//! the player has not explored is shaded.
+
+use super::hints::HINT_COLOR;
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::{ConstructionSystem, QueryShape, ShelterSystem, TerritorySystem};
+use macroquad::prelude::*;
+
+/// Base minimap size before UI scaling (keeps the starting area's 4:3
+/// aspect ratio)
+const MINIMAP_WIDTH: f32 = 200.0;
+const MINIMAP_HEIGHT: f32 = 150.0;
+
+impl Renderer {
+    /// Draw the minimap in the bottom-right corner of the screen
+    pub(super) fn draw_minimap(&self, game_state: &GameState) {
+        let width = MINIMAP_WIDTH * self.ui_scale;
+        let height = MINIMAP_HEIGHT * self.ui_scale;
+        let margin = 20.0 * self.ui_scale;
+        let origin_x = screen_width() - width - margin;
+        let origin_y = screen_height() - height - margin;
+
+        // Frame and background
+        self.queue().rectangle(
+            origin_x,
+            origin_y,
+            width,
+            height,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        self.queue()
+            .rectangle_lines(origin_x, origin_y, width, height, 2.0, GRAY);
+
+        // The map stretches as the world is streamed out
+        let bounds = game_state.world_bounds;
+        let (world_width, world_height) = (bounds.width(), bounds.max_y);
+        let to_minimap = |position: &Position| -> (f32, f32) {
+            (
+                origin_x + ((position.x - bounds.min_x) / world_width).clamp(0.0, 1.0) * width,
+                origin_y + (position.y / world_height).clamp(0.0, 1.0) * height,
+            )
+        };
+
+        // Ground line so the map reads as the same world as the main view
+        let (_, ground_y) = to_minimap(&Position::new(bounds.min_x, WorldBounds::GROUND_LEVEL));
+        self.queue().line(
+            origin_x,
+            ground_y,
+            origin_x + width,
+            ground_y,
+            1.0,
+            Color::new(0.3, 0.5, 0.3, 0.8),
+        );
+
+        let icon_size = 3.0 * self.ui_scale;
+
+        // Unexplored ground
+        let cell_width = FogOfWar::CELL_SIZE / world_width * width;
+        let cell_height = FogOfWar::CELL_SIZE / world_height * height;
+        for cell in game_state.fog_of_war.unexplored_cells() {
+            let (x, y) = to_minimap(&cell);
+            self.queue().rectangle(
+                x,
+                y,
+                cell_width,
+                cell_height,
+                Color::new(0.0, 0.0, 0.0, 0.6),
+            );
+        }
+
+        // Territories, filled in their holder's color and striped while
+        // contested
+        if game_state.show_territories {
+            for territory in &game_state.territories.territories {
+                let (x, y) = to_minimap(&territory.center);
+                let radius = territory.radius / world_width * width;
+                let control = TerritorySystem::control(territory, &game_state.entities);
+                let color = self.territory_color(&control);
+                if control.controller.is_some() {
+                    self.queue()
+                        .circle(x, y, radius, Color { a: 0.35, ..color });
+                }
+                if control.contested {
+                    self.draw_territory_stripes(
+                        x,
+                        y,
+                        radius,
+                        3.0 * self.ui_scale,
+                        1.0,
+                        Color { a: 0.5, ..color },
+                    );
+                }
+                self.queue()
+                    .circle_lines(x, y, radius, 1.0, Color { a: 0.8, ..color });
+            }
+        }
+
+        // Discovered shelters
+        let shelters = ShelterSystem::get_nearby_shelter_info(
+            &game_state.entities,
+            game_state.player_id,
+            world_width.hypot(world_height),
+        );
+        for shelter in shelters.iter().filter(|shelter| shelter.discovered) {
+            let (x, y) = to_minimap(&shelter.position);
+            self.queue().rectangle(
+                x - icon_size,
+                y - icon_size,
+                icon_size * 2.0,
+                icon_size * 2.0,
+                shelter.shelter_type.primary_color(),
+            );
+        }
+
+        // The player's lair, where they rise after dying
+        if let Some(lair) = ConstructionSystem::home_lair(&game_state.entities) {
+            let (x, y) = to_minimap(&lair.position);
+            self.queue()
+                .circle_lines(x, y, icon_size * 2.5, 1.5, Color::new(0.8, 0.1, 0.15, 1.0));
+        }
+
+        // Signposts the player has read
+        for signpost in game_state.signposts.iter().filter(|signpost| signpost.read) {
+            let (x, y) = to_minimap(&signpost.position);
+            self.queue().rectangle(
+                x - icon_size * 0.5,
+                y - icon_size * 0.5,
+                icon_size,
+                icon_size,
+                Color::new(0.8, 0.6, 0.3, 1.0),
+            );
+        }
+
+        let player = EntityFinder::by_id(&game_state.entities, game_state.player_id);
+        let sense_range = player
+            .and_then(|player| player.vampire_abilities.as_ref())
+            .map_or(0.0, |abilities| abilities.blood_sense_range());
+
+        for entity in game_state.entities.alive_entities() {
+            if let EntityType::ClanLeader(_) = entity.entity_type {
+                let (x, y) = to_minimap(&entity.position);
+                self.queue().circle(x, y, icon_size * 1.2, entity.color);
+                self.queue().circle_lines(x, y, icon_size * 1.2, 1.0, GOLD);
+            }
+        }
+
+        // Infected only show up within blood sense range
+        if let Some(player) = player {
+            let sensed = game_state.query_area(QueryShape::circle(player.position, sense_range));
+            for entity in sensed
+                .entities
+                .iter()
+                .map(|&index| &game_state.entities[index])
+            {
+                if matches!(entity.entity_type, EntityType::HostileInfected)
+                    && !matches!(entity.ai_state, AIState::Dead)
+                {
+                    let (x, y) = to_minimap(&entity.position);
+                    self.queue().circle(x, y, icon_size * 0.8, RED);
+                }
+            }
+        }
+
+        // Creatures the crow has marked, and the crow itself
+        for mark in &game_state.scout_marks {
+            if let Some(entity) = EntityFinder::by_id(&game_state.entities, mark.entity_id) {
+                let (x, y) = to_minimap(&entity.position);
+                self.queue()
+                    .circle_lines(x, y, icon_size, 1.0, Color::new(0.55, 0.8, 1.0, 0.9));
+            }
+        }
+        if let Some(scout) = &game_state.crow_scout {
+            let (x, y) = to_minimap(&scout.position);
+            self.queue().triangle(
+                vec2(x, y - icon_size),
+                vec2(x - icon_size * 1.5, y + icon_size),
+                vec2(x + icon_size * 1.5, y + icon_size),
+                Color::new(0.55, 0.8, 1.0, 1.0),
+            );
+        }
+
+        // Skirmishes away from the player: a pulsing ping while the fighting
+        // lasts, a fading ring for a while after
+        for skirmish in &game_state.skirmishes.skirmishes {
+            let (x, y) = to_minimap(&skirmish.position);
+            let pulse = (game_state.game_time * 4.0).sin() * 0.5 + 0.5;
+            let (radius, alpha) = if skirmish.ended {
+                (icon_size * 2.0, 0.4)
+            } else {
+                (icon_size * (1.5 + pulse * 1.5), 0.6 + pulse * 0.4)
+            };
+            self.queue()
+                .circle_lines(x, y, radius, 1.5, Color::new(1.0, 0.5, 0.1, alpha));
+        }
+
+        // Raids between clans at war
+        for clash in &game_state.clan_relations.clashes {
+            let (x, y) = to_minimap(&clash.position);
+            let pulse = (game_state.game_time * 5.0).sin() * 0.5 + 0.5;
+            self.queue().circle_lines(
+                x,
+                y,
+                icon_size * (1.5 + pulse * 1.5),
+                1.5,
+                Color::new(0.9, 0.1, 0.2, 0.6 + pulse * 0.4),
+            );
+        }
+
+        // Places hints point the player towards
+        for target in game_state.hint_marker_positions() {
+            let (x, y) = to_minimap(&target);
+            self.queue().circle(x, y, icon_size * 1.2, HINT_COLOR);
+            self.queue()
+                .circle_lines(x, y, icon_size * 2.2, 1.0, HINT_COLOR);
+        }
+
+        // Player last so it is always visible on top
+        if let Some(player) = player {
+            let (x, y) = to_minimap(&player.position);
+            let sense_radius = sense_range / world_width * width;
+            self.queue()
+                .circle_lines(x, y, sense_radius, 1.0, Color::new(0.8, 0.0, 0.0, 0.4));
+            self.queue().circle(x, y, icon_size * 1.3, WHITE);
+        }
+
+        let hint = self.tr(if game_state.skirmishes.latest().is_some() {
+            "minimap.hint_fight"
+        } else {
+            "minimap.hint"
+        });
+        self.draw_text_with_font(
+            hint,
+            origin_x + 4.0 * self.ui_scale,
+            origin_y + height - 4.0 * self.ui_scale,
+            12.0 * self.ui_scale,
+            LIGHTGRAY,
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Onboarding Rendering
+//!
+//! Draws the new game start selection, and during a guided first night the
+//! objective marker in the world, the current objective, and the banner used
+//! for step changes and dawn warnings.
+
+use super::{RenderLayer, Renderer};
+use crate::components::Position;
+use crate::game_state::GameState;
+use crate::settings::{NewGameField, NewGameSettings, WeaknessRules};
+use crate::systems::{OnboardingSystem, StartMode};
+use macroquad::prelude::*;
+
+const MARKER_COLOR: Color = Color::new(1.0, 0.85, 0.3, 1.0);
+
+impl Renderer {
+    /// New game screen: the vampire's name and world seed, guided or
+    /// classic start, the difficulty, and which vampire weaknesses apply.
+    /// `editing` holds the field open for typing and the text typed so far.
+    pub fn draw_start_menu(
+        &self,
+        settings: &NewGameSettings,
+        editing: Option<(NewGameField, &str)>,
+    ) {
+        let selected = settings.start_mode;
+        clear_background(Color::new(0.03, 0.02, 0.05, 1.0));
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let mut y = screen_height() / 2.0 - 120.0 * scale;
+
+        let title = format!("{}: {}", self.tr("title.name"), self.tr("title.subtitle"));
+        let title_size = 40.0 * scale;
+        let width = measure_text(&title, None, title_size as u16, 1.0).width;
+        self.draw_text_with_font(&title, center_x - width / 2.0, y, title_size, RED);
+        y += 40.0 * scale;
+
+        let seed = settings.seed.to_string();
+        for (field, key, value) in [
+            (
+                NewGameField::Name,
+                "new_game.name",
+                settings.player_name.as_str(),
+            ),
+            (NewGameField::Seed, "new_game.seed", seed.as_str()),
+        ] {
+            let (value, color) = match editing {
+                // Blinking caret while typing
+                Some((open, text)) if open == field && (get_time() * 2.0) as i64 % 2 == 0 => {
+                    (format!("{}_", text), GOLD)
+                }
+                Some((open, text)) if open == field => (text.to_string(), GOLD),
+                _ => (value.to_string(), LIGHTGRAY),
+            };
+            let line = self.strings.format(key, &[("value", &value)]);
+            let size = 20.0 * scale;
+            let width = measure_text(&line, None, size as u16, 1.0).width;
+            self.draw_text_with_font(&line, center_x - width / 2.0, y, size, color);
+            y += 26.0 * scale;
+        }
+        y += 4.0 * scale;
+
+        for mode in [StartMode::Guided, StartMode::Classic] {
+            let is_selected = mode == selected;
+            let box_width = 560.0 * scale;
+            let box_x = center_x - box_width / 2.0;
+            let box_height = 64.0 * scale;
+            let fill = if is_selected {
+                Color::new(0.35, 0.05, 0.05, 0.9)
+            } else {
+                Color::new(0.12, 0.08, 0.12, 0.9)
+            };
+            self.queue()
+                .rectangle(box_x, y, box_width, box_height, fill);
+            if is_selected {
+                self.queue()
+                    .rectangle_lines(box_x, y, box_width, box_height, 2.0, GOLD);
+            }
+            self.draw_text_with_font(
+                self.tr(mode.text_key()),
+                box_x + 16.0 * scale,
+                y + 26.0 * scale,
+                24.0 * scale,
+                if is_selected { GOLD } else { WHITE },
+            );
+            self.draw_text_with_font(
+                self.tr(mode.description_key()),
+                box_x + 16.0 * scale,
+                y + 50.0 * scale,
+                16.0 * scale,
+                LIGHTGRAY,
+            );
+            y += box_height + 16.0 * scale;
+        }
+
+        self.draw_text_with_font(
+            &self.strings.format(
+                "new_game.difficulty",
+                &[("difficulty", &settings.difficulty.display_name())],
+            ),
+            center_x - 280.0 * scale,
+            y + 6.0 * scale,
+            20.0 * scale,
+            WHITE,
+        );
+        y += 34.0 * scale;
+        self.draw_text_with_font(
+            self.tr("new_game.weaknesses"),
+            center_x - 280.0 * scale,
+            y,
+            20.0 * scale,
+            WHITE,
+        );
+        for index in 0..WeaknessRules::COUNT {
+            let Some((key, enabled)) = settings.weaknesses.describe(index) else {
+                continue;
+            };
+            y += 24.0 * scale;
+            let color = if enabled {
+                Color::new(0.9, 0.3, 0.3, 1.0)
+            } else {
+                GRAY
+            };
+            self.draw_text_with_font(
+                &self.strings.format(
+                    "new_game.weakness",
+                    &[
+                        ("number", &(index + 1)),
+                        ("weakness", &self.tr(key)),
+                        ("state", &self.strings.on_off(enabled)),
+                    ],
+                ),
+                center_x - 264.0 * scale,
+                y,
+                17.0 * scale,
+                color,
+            );
+        }
+
+        let hint = self.tr(match editing {
+            Some((NewGameField::Name, _)) => "new_game.hint_name",
+            Some((NewGameField::Seed, _)) => "new_game.hint_seed",
+            None => "new_game.hint",
+        });
+        let hint_size = 18.0 * scale;
+        let width = measure_text(hint, None, hint_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            hint,
+            center_x - width / 2.0,
+            y + 20.0 * scale,
+            hint_size,
+            GRAY,
+        );
+        self.flush_queue(RenderLayer::Overlay);
+    }
+
+    /// Marker over the guided first night's current objective
+    pub(super) fn draw_onboarding_marker(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(first_night) = &game_state.first_night else {
+            return;
+        };
+        let Some(target) = OnboardingSystem::marker_position(
+            first_night,
+            &game_state.entities,
+            game_state.player_id,
+        ) else {
+            return;
+        };
+
+        self.draw_objective_marker(
+            target,
+            MARKER_COLOR,
+            game_state.game_time,
+            camera_offset_x,
+            camera_offset_y,
+        );
+    }
+
+    /// Pulsing marker over a spot in the world, or an arrow at the screen
+    /// edge pointing toward it when it is off screen
+    pub(super) fn draw_objective_marker(
+        &self,
+        target: Position,
+        color: Color,
+        game_time: f32,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let screen_x = target.x * self.zoom_level + camera_offset_x;
+        let screen_y = target.y * self.zoom_level + camera_offset_y;
+        let pulse = (game_time * 4.0).sin() * 0.5 + 0.5;
+        let margin = 30.0;
+
+        let on_screen = screen_x > margin
+            && screen_x < screen_width() - margin
+            && screen_y > margin
+            && screen_y < screen_height() - margin;
+        if on_screen {
+            self.queue()
+                .circle_lines(screen_x, screen_y, 26.0 + pulse * 8.0, 2.0, color);
+            self.queue().triangle(
+                vec2(screen_x, screen_y - 34.0 - pulse * 6.0),
+                vec2(screen_x - 8.0, screen_y - 48.0 - pulse * 6.0),
+                vec2(screen_x + 8.0, screen_y - 48.0 - pulse * 6.0),
+                color,
+            );
+            return;
+        }
+
+        // Clamp to the screen edge and point outward
+        let center = vec2(screen_width() / 2.0, screen_height() / 2.0);
+        let direction = (vec2(screen_x, screen_y) - center).normalize_or_zero();
+        let edge = vec2(
+            screen_x.clamp(margin, screen_width() - margin),
+            screen_y.clamp(margin, screen_height() - margin),
+        );
+        let side = vec2(-direction.y, direction.x);
+        self.queue().triangle(
+            edge + direction * 14.0,
+            edge - direction * 6.0 + side * 9.0,
+            edge - direction * 6.0 - side * 9.0,
+            Color::new(color.r, color.g, color.b, 0.6 + pulse * 0.4),
+        );
+    }
+
+    /// Current objective panel and the banner message
+    pub(super) fn draw_onboarding_hud(&self, game_state: &GameState) {
+        let Some(first_night) = &game_state.first_night else {
+            return;
+        };
+        let scale = self.ui_scale;
+
+        let objective = self.strings.format(
+            "new_game.first_night",
+            &[("objective", &first_night.step.objective())],
+        );
+        let size = 18.0 * scale;
+        let width = measure_text(&objective, None, size as u16, 1.0).width;
+        let x = (screen_width() - width) / 2.0;
+        let y = screen_height() - 40.0 * scale;
+        self.queue().rectangle(
+            x - 10.0 * scale,
+            y - 22.0 * scale,
+            width + 20.0 * scale,
+            30.0 * scale,
+            Color::new(0.0, 0.0, 0.0, 0.6),
+        );
+        self.draw_text_with_font(&objective, x, y, size, MARKER_COLOR);
+
+        if let Some((message, remaining)) = &first_night.banner {
+            let alpha = remaining.min(1.0);
+            let size = 26.0 * scale;
+            let width = measure_text(message, None, size as u16, 1.0).width;
+            let x = (screen_width() - width) / 2.0;
+            let y = 110.0 * scale;
+            self.queue().rectangle(
+                x - 16.0 * scale,
+                y - 32.0 * scale,
+                width + 32.0 * scale,
+                46.0 * scale,
+                Color::new(0.1, 0.0, 0.0, 0.75 * alpha),
+            );
+            self.draw_text_with_font(message, x, y, size, Color::new(1.0, 0.9, 0.7, alpha));
+        }
+    }
+}
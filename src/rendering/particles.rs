@@ -0,0 +1,42 @@
+//! Particle Rendering
+//!
+//! Draws the particle pool: blood droplets, dust puffs, embers and ability
+//! sparkles, each fading out over its life.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::ParticleKind;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_particles(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for (i, particle) in game_state.particles.iter().enumerate() {
+            if self.performance_mode && i % 3 == 0 {
+                continue;
+            }
+            let x = particle.position.x * self.zoom_level + camera_offset_x;
+            let y = particle.position.y * self.zoom_level + camera_offset_y;
+            let fade = particle.life_fraction();
+            let mut color = particle.kind.color();
+            color.a *= fade;
+            let size = particle.size * self.zoom_level;
+
+            match particle.kind {
+                // Sparkles twinkle as a small cross
+                ParticleKind::Sparkle => {
+                    let arm = size * (1.0 + fade);
+                    self.queue().line(x - arm, y, x + arm, y, 1.0, color);
+                    self.queue().line(x, y - arm, x, y + arm, 1.0, color);
+                }
+                // Dust puffs spread as they fade
+                ParticleKind::Dust => self.queue().circle(x, y, size * (2.0 - fade), color),
+                ParticleKind::Blood | ParticleKind::Ember => self.queue().circle(x, y, size, color),
+            }
+        }
+    }
+}
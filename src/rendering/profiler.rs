@@ -0,0 +1,98 @@
+//! Profiler Rendering
+//!
+//! Draws the profiling overlay: one row per timed system or render phase,
+//! with its rolling average as a solid bar and its worst frame as a faint
+//! bar behind it.
+
+use super::Renderer;
+use crate::profiler::{ProfileSection, Profiler};
+use macroquad::prelude::*;
+
+/// A bar this long stands for one 60 FPS frame; slower sections run off
+/// the end and are drawn in red
+const FRAME_BUDGET_MS: f32 = 1000.0 / 60.0;
+
+const PANEL_WIDTH: f32 = 360.0;
+const ROW_HEIGHT: f32 = 20.0;
+const LABEL_WIDTH: f32 = 110.0;
+const BAR_WIDTH: f32 = 120.0;
+
+impl Renderer {
+    pub(super) fn draw_profiler(&self, profiler: &Profiler) {
+        let rows = ProfileSection::ALL.len() as f32 + 2.0;
+        let width = self.ui(PANEL_WIDTH);
+        let height = self.ui(ROW_HEIGHT * rows + 16.0);
+        let x = self.ui(20.0);
+        let y = (screen_height() - height) / 2.0;
+        let font_size = self.ui(15.0);
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.75));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 1.0, DARKGRAY);
+
+        let mut row_y = y + self.ui(8.0 + ROW_HEIGHT * 0.75);
+        let mut totals = (0.0, 0.0);
+        self.queue().builtin_text(
+            self.tr("profiler.title"),
+            x + self.ui(8.0),
+            row_y,
+            font_size,
+            YELLOW,
+        );
+        row_y += self.ui(ROW_HEIGHT);
+
+        for section in ProfileSection::ALL {
+            let stats = profiler.stats(section);
+            if section.is_render_phase() {
+                totals.1 += stats.average_ms;
+            } else {
+                totals.0 += stats.average_ms;
+            }
+
+            self.queue()
+                .builtin_text(section.label(), x + self.ui(8.0), row_y, font_size, WHITE);
+
+            let bar_x = x + self.ui(8.0 + LABEL_WIDTH);
+            let bar_y = row_y - self.ui(11.0);
+            let bar_height = self.ui(12.0);
+            let length = |ms: f32| self.ui(BAR_WIDTH) * (ms / FRAME_BUDGET_MS).min(1.0);
+            let color = if stats.worst_ms > FRAME_BUDGET_MS {
+                RED
+            } else {
+                GREEN
+            };
+            self.queue().rectangle(
+                bar_x,
+                bar_y,
+                length(stats.worst_ms),
+                bar_height,
+                Color::new(color.r, color.g, color.b, 0.3),
+            );
+            self.queue()
+                .rectangle(bar_x, bar_y, length(stats.average_ms), bar_height, color);
+            self.queue().builtin_text(
+                &format!("{:.2} / {:.2}", stats.average_ms, stats.worst_ms),
+                bar_x + self.ui(BAR_WIDTH + 8.0),
+                row_y,
+                font_size,
+                LIGHTGRAY,
+            );
+            row_y += self.ui(ROW_HEIGHT);
+        }
+
+        self.queue().builtin_text(
+            &self.strings.format(
+                "profiler.totals",
+                &[
+                    ("systems", &format!("{:.2}", totals.0)),
+                    ("render", &format!("{:.2}", totals.1)),
+                ],
+            ),
+            x + self.ui(8.0),
+            row_y,
+            font_size,
+            YELLOW,
+        );
+    }
+}
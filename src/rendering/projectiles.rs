@@ -0,0 +1,56 @@
+//! Projectile Rendering
+//!
+//! Draws blood lances in flight as a dark red shaft with a bright tip and a
+//! short fading trail behind.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+const LANCE_COLOR: Color = Color::new(0.55, 0.02, 0.05, 1.0);
+const LANCE_TIP_COLOR: Color = Color::new(0.95, 0.2, 0.2, 1.0);
+
+/// Lance length in world units
+const LANCE_LENGTH: f32 = 18.0;
+/// Trail length behind the lance, in world units
+const TRAIL_LENGTH: f32 = 30.0;
+
+impl Renderer {
+    pub(super) fn draw_projectiles(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for projectile in &game_state.projectiles {
+            let x = projectile.position.x * self.zoom_level + camera_offset_x;
+            let y = projectile.position.y * self.zoom_level + camera_offset_y;
+            let (dx, dy) = projectile.direction;
+            let length = LANCE_LENGTH * self.zoom_level;
+            let trail = TRAIL_LENGTH * self.zoom_level;
+
+            if !self.performance_mode {
+                let mut trail_color = LANCE_COLOR;
+                trail_color.a = 0.3;
+                self.queue().line(
+                    x - dx * (length + trail),
+                    y - dy * (length + trail),
+                    x - dx * length,
+                    y - dy * length,
+                    2.0 * self.zoom_level,
+                    trail_color,
+                );
+            }
+            self.queue().line(
+                x - dx * length,
+                y - dy * length,
+                x,
+                y,
+                3.0 * self.zoom_level,
+                LANCE_COLOR,
+            );
+            self.queue()
+                .circle(x, y, 2.0 * self.zoom_level, LANCE_TIP_COLOR);
+        }
+    }
+}
@@ -0,0 +1,583 @@
+//! Draw Command Queue
+//!
+//! Everything the renderer draws goes through here rather than straight to
+//! macroquad, and systems such as shelters submit to it too. Each command
+//! goes to a layer; the renderer flushes the queue layer by layer at the
+//! matching point of the frame, so anything submitted to `Terrain` lands
+//! beneath the creatures however late it was queued. Commands are plain
+//! data, so what a system draws can be checked in a test without a window.
+//! Only `clear_background` is left to run at once, before a frame's first
+//! command.
+
+use super::Renderer;
+use macroquad::prelude::*;
+use std::cell::RefMut;
+
+/// Layers of a frame, from the back to the front
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderLayer {
+    Background,
+    Terrain,
+    Entities,
+    Particles,
+    Ui,
+    Overlay,
+}
+
+impl RenderLayer {
+    pub const ALL: [RenderLayer; 6] = [
+        RenderLayer::Background,
+        RenderLayer::Terrain,
+        RenderLayer::Entities,
+        RenderLayer::Particles,
+        RenderLayer::Ui,
+        RenderLayer::Overlay,
+    ];
+}
+
+/// One shape or piece of text, in screen coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    Rectangle {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color: Color,
+    },
+    RectangleLines {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        thickness: f32,
+        color: Color,
+    },
+    Circle {
+        x: f32,
+        y: f32,
+        radius: f32,
+        color: Color,
+    },
+    CircleLines {
+        x: f32,
+        y: f32,
+        radius: f32,
+        thickness: f32,
+        color: Color,
+    },
+    Line {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        thickness: f32,
+        color: Color,
+    },
+    Triangle {
+        a: Vec2,
+        b: Vec2,
+        c: Vec2,
+        color: Color,
+    },
+    Ellipse {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        rotation: f32,
+        color: Color,
+    },
+    Poly {
+        x: f32,
+        y: f32,
+        sides: u8,
+        radius: f32,
+        rotation: f32,
+        color: Color,
+    },
+    PolyLines {
+        x: f32,
+        y: f32,
+        sides: u8,
+        radius: f32,
+        rotation: f32,
+        thickness: f32,
+        color: Color,
+    },
+    Arc {
+        x: f32,
+        y: f32,
+        sides: u8,
+        radius: f32,
+        rotation: f32,
+        thickness: f32,
+        arc: f32,
+        color: Color,
+    },
+    /// Part of a texture, scaled to a square of `size` with its top-left
+    /// corner at `x`, `y`
+    Sprite {
+        texture: Texture2D,
+        source: Rect,
+        x: f32,
+        y: f32,
+        size: f32,
+        flip_x: bool,
+        color: Color,
+    },
+    /// Text in the renderer's font
+    Text {
+        text: String,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: Color,
+    },
+    /// Text in macroquad's built-in font, for debug readouts
+    BuiltinText {
+        text: String,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: Color,
+    },
+}
+
+/// Commands waiting to be drawn, kept in submission order within each layer
+#[derive(Debug, Clone)]
+pub struct DrawQueue {
+    commands: Vec<(RenderLayer, DrawCommand)>,
+    /// Layer the shape helpers submit to
+    layer: RenderLayer,
+}
+
+impl Default for DrawQueue {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            layer: RenderLayer::Entities,
+        }
+    }
+}
+
+impl DrawQueue {
+    pub fn push(&mut self, layer: RenderLayer, command: DrawCommand) {
+        self.commands.push((layer, command));
+    }
+
+    /// Send the shape helpers' commands to another layer
+    pub fn set_layer(&mut self, layer: RenderLayer) {
+        self.layer = layer;
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Commands queued on a layer, in the order they were submitted
+    pub fn layer(&self, layer: RenderLayer) -> impl Iterator<Item = &DrawCommand> {
+        self.commands
+            .iter()
+            .filter(move |(queued, _)| *queued == layer)
+            .map(|(_, command)| command)
+    }
+
+    /// Remove and return every command on this layer and the ones behind
+    /// it, back to front
+    pub fn take_through(&mut self, layer: RenderLayer) -> Vec<DrawCommand> {
+        let (mut taken, kept): (Vec<_>, Vec<_>) = self
+            .commands
+            .drain(..)
+            .partition(|(queued, _)| *queued <= layer);
+        self.commands = kept;
+        // A stable sort keeps submission order within each layer
+        taken.sort_by_key(|(queued, _)| *queued);
+        taken.into_iter().map(|(_, command)| command).collect()
+    }
+
+    pub fn rectangle(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::Rectangle {
+                x,
+                y,
+                width,
+                height,
+                color,
+            },
+        );
+    }
+
+    pub fn rectangle_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        self.push(
+            self.layer,
+            DrawCommand::RectangleLines {
+                x,
+                y,
+                width,
+                height,
+                thickness,
+                color,
+            },
+        );
+    }
+
+    pub fn circle(&mut self, x: f32, y: f32, radius: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::Circle {
+                x,
+                y,
+                radius,
+                color,
+            },
+        );
+    }
+
+    pub fn circle_lines(&mut self, x: f32, y: f32, radius: f32, thickness: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::CircleLines {
+                x,
+                y,
+                radius,
+                thickness,
+                color,
+            },
+        );
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                thickness,
+                color,
+            },
+        );
+    }
+
+    pub fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.push(self.layer, DrawCommand::Triangle { a, b, c, color });
+    }
+
+    pub fn ellipse(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        rotation: f32,
+        color: Color,
+    ) {
+        self.push(
+            self.layer,
+            DrawCommand::Ellipse {
+                x,
+                y,
+                width,
+                height,
+                rotation,
+                color,
+            },
+        );
+    }
+
+    pub fn poly(&mut self, x: f32, y: f32, sides: u8, radius: f32, rotation: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::Poly {
+                x,
+                y,
+                sides,
+                radius,
+                rotation,
+                color,
+            },
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn poly_lines(
+        &mut self,
+        x: f32,
+        y: f32,
+        sides: u8,
+        radius: f32,
+        rotation: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        self.push(
+            self.layer,
+            DrawCommand::PolyLines {
+                x,
+                y,
+                sides,
+                radius,
+                rotation,
+                thickness,
+                color,
+            },
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn arc(
+        &mut self,
+        x: f32,
+        y: f32,
+        sides: u8,
+        radius: f32,
+        rotation: f32,
+        thickness: f32,
+        arc: f32,
+        color: Color,
+    ) {
+        self.push(
+            self.layer,
+            DrawCommand::Arc {
+                x,
+                y,
+                sides,
+                radius,
+                rotation,
+                thickness,
+                arc,
+                color,
+            },
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn sprite(
+        &mut self,
+        texture: &Texture2D,
+        source: Rect,
+        x: f32,
+        y: f32,
+        size: f32,
+        flip_x: bool,
+        color: Color,
+    ) {
+        self.push(
+            self.layer,
+            DrawCommand::Sprite {
+                texture: texture.clone(),
+                source,
+                x,
+                y,
+                size,
+                flip_x,
+                color,
+            },
+        );
+    }
+
+    pub fn text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::Text {
+                text: text.to_string(),
+                x,
+                y,
+                size,
+                color,
+            },
+        );
+    }
+
+    pub fn builtin_text(&mut self, text: &str, x: f32, y: f32, size: f32, color: Color) {
+        self.push(
+            self.layer,
+            DrawCommand::BuiltinText {
+                text: text.to_string(),
+                x,
+                y,
+                size,
+                color,
+            },
+        );
+    }
+}
+
+impl Renderer {
+    /// The queue this frame's drawing is submitted to
+    pub(super) fn queue(&self) -> RefMut<'_, DrawQueue> {
+        self.queue.borrow_mut()
+    }
+
+    /// Draw everything queued on `layer` and the layers behind it
+    pub(super) fn flush_queue(&self, layer: RenderLayer) {
+        let commands = self.queue().take_through(layer);
+        for command in &commands {
+            self.execute(command);
+        }
+    }
+
+    fn execute(&self, command: &DrawCommand) {
+        match command {
+            DrawCommand::Rectangle {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => draw_rectangle(*x, *y, *width, *height, *color),
+            DrawCommand::RectangleLines {
+                x,
+                y,
+                width,
+                height,
+                thickness,
+                color,
+            } => draw_rectangle_lines(*x, *y, *width, *height, *thickness, *color),
+            DrawCommand::Circle {
+                x,
+                y,
+                radius,
+                color,
+            } => draw_circle(*x, *y, *radius, *color),
+            DrawCommand::CircleLines {
+                x,
+                y,
+                radius,
+                thickness,
+                color,
+            } => draw_circle_lines(*x, *y, *radius, *thickness, *color),
+            DrawCommand::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                thickness,
+                color,
+            } => draw_line(*x1, *y1, *x2, *y2, *thickness, *color),
+            DrawCommand::Triangle { a, b, c, color } => draw_triangle(*a, *b, *c, *color),
+            DrawCommand::Ellipse {
+                x,
+                y,
+                width,
+                height,
+                rotation,
+                color,
+            } => draw_ellipse(*x, *y, *width, *height, *rotation, *color),
+            DrawCommand::Poly {
+                x,
+                y,
+                sides,
+                radius,
+                rotation,
+                color,
+            } => draw_poly(*x, *y, *sides, *radius, *rotation, *color),
+            DrawCommand::PolyLines {
+                x,
+                y,
+                sides,
+                radius,
+                rotation,
+                thickness,
+                color,
+            } => draw_poly_lines(*x, *y, *sides, *radius, *rotation, *thickness, *color),
+            DrawCommand::Arc {
+                x,
+                y,
+                sides,
+                radius,
+                rotation,
+                thickness,
+                arc,
+                color,
+            } => draw_arc(*x, *y, *sides, *radius, *rotation, *thickness, *arc, *color),
+            DrawCommand::Sprite {
+                texture,
+                source,
+                x,
+                y,
+                size,
+                flip_x,
+                color,
+            } => draw_texture_ex(
+                texture,
+                *x,
+                *y,
+                *color,
+                DrawTextureParams {
+                    dest_size: Some(vec2(*size, *size)),
+                    source: Some(*source),
+                    flip_x: *flip_x,
+                    ..Default::default()
+                },
+            ),
+            DrawCommand::Text {
+                text,
+                x,
+                y,
+                size,
+                color,
+            } => self.draw_font_text(text, *x, *y, *size, *color),
+            DrawCommand::BuiltinText {
+                text,
+                x,
+                y,
+                size,
+                color,
+            } => {
+                draw_text(text, *x, *y, *size, *color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_flushes_back_to_front_in_submission_order() {
+        let mut queue = DrawQueue::default();
+        queue.set_layer(RenderLayer::Ui);
+        queue.text("HUD", 0.0, 0.0, 12.0, WHITE);
+        queue.set_layer(RenderLayer::Terrain);
+        queue.rectangle(0.0, 0.0, 10.0, 10.0, RED);
+        queue.circle(5.0, 5.0, 2.0, BLUE);
+        queue.push(
+            RenderLayer::Background,
+            DrawCommand::Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: 1.0,
+                color: BLACK,
+            },
+        );
+        assert_eq!(queue.layer(RenderLayer::Terrain).count(), 2);
+
+        let taken = queue.take_through(RenderLayer::Entities);
+        assert_eq!(taken.len(), 3);
+        assert!(matches!(taken[0], DrawCommand::Circle { color, .. } if color == BLACK));
+        assert!(matches!(taken[1], DrawCommand::Rectangle { .. }));
+        assert!(matches!(taken[2], DrawCommand::Circle { color, .. } if color == BLUE));
+
+        // Later layers wait for their own flush
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.take_through(RenderLayer::Overlay).len(), 1);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+//! Scout Rendering
+//!
+//! Draws the fog of war over unexplored ground, the marks left by the crow
+//! scout on the creatures it has seen, and the crow itself with the time it
+//! has left while the player is piloting it.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+const FOG_COLOR: Color = Color::new(0.02, 0.02, 0.04, 0.7);
+const MARK_COLOR: Color = Color::new(0.55, 0.8, 1.0, 1.0);
+
+impl Renderer {
+    pub(super) fn draw_fog_of_war(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let size = FogOfWar::CELL_SIZE * self.zoom_level;
+        for cell in game_state.fog_of_war.unexplored_cells() {
+            let x = cell.x * self.zoom_level + camera_offset_x;
+            let y = cell.y * self.zoom_level + camera_offset_y;
+            if x + size < 0.0 || y + size < 0.0 || x > screen_width() || y > screen_height() {
+                continue;
+            }
+            // Slight overlap hides seams between neighbouring cells
+            self.queue()
+                .rectangle(x, y, size + 1.0, size + 1.0, FOG_COLOR);
+        }
+    }
+
+    /// Corner brackets around every creature the crow has marked, fading as
+    /// the mark runs out
+    pub(super) fn draw_scout_marks(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        for mark in &game_state.scout_marks {
+            let Some(entity) = EntityFinder::by_id(&game_state.entities, mark.entity_id) else {
+                continue;
+            };
+            let x = entity.position.x * self.zoom_level + camera_offset_x;
+            let y = entity.position.y * self.zoom_level + camera_offset_y;
+            let color = Color::new(
+                MARK_COLOR.r,
+                MARK_COLOR.g,
+                MARK_COLOR.b,
+                (mark.remaining / 5.0).min(0.9),
+            );
+            let half = 16.0 * self.zoom_level;
+            let arm = 6.0 * self.zoom_level;
+            for (sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+                let corner_x = x + sx * half;
+                let corner_y = y + sy * half;
+                self.queue().line(
+                    corner_x,
+                    corner_y,
+                    corner_x - sx * arm,
+                    corner_y,
+                    2.0,
+                    color,
+                );
+                self.queue().line(
+                    corner_x,
+                    corner_y,
+                    corner_x,
+                    corner_y - sy * arm,
+                    2.0,
+                    color,
+                );
+            }
+        }
+    }
+
+    pub(super) fn draw_crow_scout(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(scout) = &game_state.crow_scout else {
+            return;
+        };
+        let zoom = self.zoom_level;
+        let x = scout.position.x * zoom + camera_offset_x;
+        let y = scout.position.y * zoom + camera_offset_y;
+        let flap = (game_state.game_time * 14.0).sin() * 6.0 * zoom;
+        let facing = if scout.facing_left { -1.0 } else { 1.0 };
+
+        let body = Color::new(0.05, 0.05, 0.08, 1.0);
+        self.queue().triangle(
+            vec2(x - 4.0 * zoom, y),
+            vec2(x - 18.0 * zoom, y - flap),
+            vec2(x - 6.0 * zoom, y + 3.0 * zoom),
+            body,
+        );
+        self.queue().triangle(
+            vec2(x + 4.0 * zoom, y),
+            vec2(x + 18.0 * zoom, y - flap),
+            vec2(x + 6.0 * zoom, y + 3.0 * zoom),
+            body,
+        );
+        self.queue().circle(x, y, 5.0 * zoom, body);
+        self.queue()
+            .circle(x + facing * 5.0 * zoom, y - 2.0 * zoom, 3.0 * zoom, body);
+        self.queue().triangle(
+            vec2(x + facing * 7.0 * zoom, y - 3.0 * zoom),
+            vec2(x + facing * 11.0 * zoom, y - 1.5 * zoom),
+            vec2(x + facing * 7.0 * zoom, y - 0.5 * zoom),
+            Color::new(0.6, 0.5, 0.2, 1.0),
+        );
+
+        // Dim the edges of the view and show how long the crow has left
+        let scale = self.ui_scale;
+        let edge = 40.0 * scale;
+        let shade = Color::new(0.0, 0.0, 0.0, 0.45);
+        self.queue()
+            .rectangle(0.0, 0.0, screen_width(), edge, shade);
+        self.queue()
+            .rectangle(0.0, screen_height() - edge, screen_width(), edge, shade);
+        let status = self.strings.format(
+            "scout.status",
+            &[("seconds", &format!("{:.0}", scout.remaining.ceil()))],
+        );
+        let size = 20.0 * scale;
+        let width = measure_text(&status, None, size as u16, 1.0).width;
+        self.draw_text_with_font(
+            &status,
+            (screen_width() - width) / 2.0,
+            edge * 0.7,
+            size,
+            MARK_COLOR,
+        );
+    }
+}
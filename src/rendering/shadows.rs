@@ -0,0 +1,52 @@
+//! Shadow Rendering
+//!
+//! Darkens the ground where shelters shade it from the sun, so the player
+//! can see where it is safe to stand. Shadows deepen as the sun climbs.
+
+use super::Renderer;
+use crate::game_state::GameState;
+use crate::systems::ExposureSystem;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_shadows(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let intensity = game_state.time.get_sunlight_intensity();
+        if intensity <= 0.0 {
+            return;
+        }
+
+        let zoom = self.zoom_level;
+        for shadow in ExposureSystem::cast_shadows(&game_state.entities, &game_state.time) {
+            let points: Vec<Vec2> = shadow
+                .polygon
+                .iter()
+                .map(|point| {
+                    vec2(
+                        point.x * zoom + camera_offset_x,
+                        point.y * zoom + camera_offset_y,
+                    )
+                })
+                .collect();
+            let on_screen = points.iter().any(|point| {
+                point.x >= 0.0
+                    && point.y >= 0.0
+                    && point.x <= screen_width()
+                    && point.y <= screen_height()
+            });
+            if !on_screen {
+                continue;
+            }
+
+            let color = Color::new(0.0, 0.0, 0.05, 0.35 * intensity * shadow.density);
+            for index in 1..points.len().saturating_sub(1) {
+                self.queue()
+                    .triangle(points[0], points[index], points[index + 1], color);
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Signpost Rendering
+//!
+//! Draws the wooden roadside signposts and, when the player stands close
+//! enough, the destination painted on them.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::world::SIGNPOST_READ_RANGE;
+use macroquad::prelude::*;
+
+/// Signs become legible from a little further away than they can be read
+const LEGIBLE_RANGE: f32 = SIGNPOST_READ_RANGE * 3.0;
+
+const POST_COLOR: Color = Color::new(0.35, 0.22, 0.12, 1.0);
+const BOARD_COLOR: Color = Color::new(0.5, 0.34, 0.18, 1.0);
+
+impl Renderer {
+    pub(super) fn draw_signposts(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let player_pos = EntityFinder::by_id(&game_state.entities, game_state.player_id)
+            .map(|player| player.position);
+        let zoom = self.zoom_level;
+
+        for signpost in &game_state.signposts {
+            let screen_x = signpost.position.x * zoom + camera_offset_x;
+            let screen_y = signpost.position.y * zoom + camera_offset_y;
+            if screen_x < -40.0
+                || screen_x > screen_width() + 40.0
+                || screen_y < -60.0
+                || screen_y > screen_height() + 60.0
+            {
+                continue;
+            }
+
+            // Post with a board angled toward the destination
+            self.queue()
+                .rectangle(screen_x - 2.0, screen_y - 24.0, 4.0, 24.0, POST_COLOR);
+            let points_east = signpost.destination.x >= signpost.position.x;
+            let board_x = if points_east {
+                screen_x - 6.0
+            } else {
+                screen_x - 16.0
+            };
+            self.queue()
+                .rectangle(board_x, screen_y - 26.0, 22.0, 9.0, BOARD_COLOR);
+            let tip_x = if points_east { board_x + 22.0 } else { board_x };
+            let tip_dir = if points_east { 5.0 } else { -5.0 };
+            self.queue().triangle(
+                vec2(tip_x, screen_y - 26.0),
+                vec2(tip_x, screen_y - 17.0),
+                vec2(tip_x + tip_dir, screen_y - 21.5),
+                BOARD_COLOR,
+            );
+
+            let legible =
+                player_pos.is_some_and(|pos| pos.distance_to(&signpost.position) <= LEGIBLE_RANGE);
+            if legible {
+                let text = signpost.text();
+                let font_size = 16.0;
+                let width = measure_text(&text, None, font_size as u16, 1.0).width;
+                self.queue().rectangle(
+                    screen_x - width / 2.0 - 4.0,
+                    screen_y - 48.0,
+                    width + 8.0,
+                    18.0,
+                    Color::new(0.0, 0.0, 0.0, 0.6),
+                );
+                self.draw_text_with_font(
+                    &text,
+                    screen_x - width / 2.0,
+                    screen_y - 34.0,
+                    font_size,
+                    Color::new(0.95, 0.85, 0.6, 1.0),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Skill Tree Rendering
+//!
+//! Draws the skill tree: the player's level and experience, then the
+//! Combat, Blood and Shadow branches side by side. Learned skills are lit,
+//! those ready to learn are outlined, and the rest stay dim until their
+//! prerequisite is learned.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_skill_tree(&self, game_state: &GameState) {
+        let Some(progression) = game_state.player_progression() else {
+            return;
+        };
+        let scale = self.ui_scale;
+        let width = 660.0 * scale;
+        let height = 380.0 * scale;
+        let x = (screen_width() - width) / 2.0;
+        let y = (screen_height() - height) / 2.0;
+
+        self.queue()
+            .rectangle(x, y, width, height, Color::new(0.08, 0.03, 0.05, 0.94));
+        self.queue()
+            .rectangle_lines(x, y, width, height, 2.0, Color::new(0.6, 0.1, 0.15, 1.0));
+
+        let text_x = x + 20.0 * scale;
+        let mut text_y = y + 35.0 * scale;
+        self.draw_text_with_font(self.tr("skills.title"), text_x, text_y, 24.0 * scale, WHITE);
+        let points = self
+            .strings
+            .format("skills.points", &[("points", &progression.skill_points)]);
+        let points_width = measure_text(&points, None, (16.0 * scale) as u16, 1.0).width;
+        self.draw_text_with_font(
+            &points,
+            x + width - 20.0 * scale - points_width,
+            text_y,
+            16.0 * scale,
+            GOLD,
+        );
+
+        text_y += 26.0 * scale;
+        self.draw_text_with_font(
+            &self.strings.format(
+                "skills.level",
+                &[
+                    ("level", &progression.level),
+                    ("xp", &progression.xp),
+                    ("next", &progression.xp_to_next()),
+                ],
+            ),
+            text_x,
+            text_y,
+            16.0 * scale,
+            LIGHTGRAY,
+        );
+        text_y += 10.0 * scale;
+        let bar_width = width - 40.0 * scale;
+        self.queue()
+            .rectangle(text_x, text_y, bar_width, 6.0 * scale, DARKGRAY);
+        self.queue().rectangle(
+            text_x,
+            text_y,
+            bar_width * progression.xp as f32 / progression.xp_to_next() as f32,
+            6.0 * scale,
+            Color::new(0.7, 0.1, 0.15, 1.0),
+        );
+
+        let column_width = (width - 40.0 * scale) / SkillBranch::ALL.len() as f32;
+        let top = text_y + 40.0 * scale;
+        let (selected_branch, selected_row) = game_state.selected_skill;
+        for (column, branch) in SkillBranch::ALL.iter().enumerate() {
+            let column_x = text_x + column as f32 * column_width;
+            self.draw_text_with_font(
+                branch.display_name(),
+                column_x,
+                top,
+                20.0 * scale,
+                Color::new(0.9, 0.6, 0.6, 1.0),
+            );
+            for (row, skill) in branch.skills().iter().enumerate() {
+                let box_x = column_x;
+                let box_y = top + 16.0 * scale + row as f32 * 70.0 * scale;
+                let box_width = column_width - 16.0 * scale;
+                let box_height = 54.0 * scale;
+                if row > 0 {
+                    let centre = box_x + box_width / 2.0;
+                    self.queue()
+                        .line(centre, box_y - 16.0 * scale, centre, box_y, 2.0, GRAY);
+                }
+
+                let learned = progression.has(*skill);
+                let ready = progression.can_learn(*skill).is_ok();
+                let (fill, text) = if learned {
+                    (Color::new(0.45, 0.05, 0.1, 0.9), WHITE)
+                } else if ready {
+                    (Color::new(0.2, 0.08, 0.1, 0.9), WHITE)
+                } else {
+                    (Color::new(0.12, 0.1, 0.1, 0.9), GRAY)
+                };
+                self.queue()
+                    .rectangle(box_x, box_y, box_width, box_height, fill);
+                if (column, row) == (selected_branch, selected_row) {
+                    self.queue()
+                        .rectangle_lines(box_x, box_y, box_width, box_height, 3.0, GOLD);
+                } else if ready {
+                    self.queue()
+                        .rectangle_lines(box_x, box_y, box_width, box_height, 1.0, RED);
+                }
+                self.draw_text_with_font(
+                    skill.display_name(),
+                    box_x + 8.0 * scale,
+                    box_y + 22.0 * scale,
+                    16.0 * scale,
+                    text,
+                );
+                self.draw_text_with_font(
+                    &skill.description(),
+                    box_x + 8.0 * scale,
+                    box_y + 42.0 * scale,
+                    13.0 * scale,
+                    if learned { LIGHTGRAY } else { GRAY },
+                );
+            }
+        }
+
+        self.draw_text_with_font(
+            self.tr("skills.hint"),
+            text_x,
+            y + height - 14.0 * scale,
+            14.0 * scale,
+            GRAY,
+        );
+    }
+}
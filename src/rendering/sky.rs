@@ -0,0 +1,119 @@
+//! Sky Rendering
+//!
+//! The moon and the stars of the night sky.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_moon(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let screen_x = game_state.moon.x * self.zoom_level + camera_offset_x;
+        let screen_y = game_state.moon.y * self.zoom_level + camera_offset_y;
+
+        // Only draw moon if on screen
+        if screen_x > -50.0
+            && screen_x < screen_width() + 50.0
+            && screen_y > -50.0
+            && screen_y < screen_height() + 50.0
+        {
+            let moon_size = if game_state.time.is_day() { 22.0 } else { 38.0 }; // Larger for zoom
+            let moon_alpha = if game_state.time.is_day() {
+                0.2
+            } else {
+                game_state.moon.glow_intensity
+            };
+            // A blood moon burns red: glow, face and craters
+            let (glow, face, crater) = if game_state.calendar.is_blood_moon() {
+                (
+                    Color::new(0.8, 0.1, 0.05, 1.0),
+                    Color::new(0.85, 0.15, 0.1, 1.0),
+                    Color::new(0.6, 0.05, 0.05, 1.0),
+                )
+            } else {
+                (
+                    Color::new(0.9, 0.9, 0.7, 1.0),
+                    Color::new(0.95, 0.95, 0.85, 1.0),
+                    Color::new(0.8, 0.8, 0.7, 1.0),
+                )
+            };
+
+            // Moon glow
+            if !game_state.time.is_day() && game_state.video_settings.post_processing {
+                self.queue().circle(
+                    screen_x,
+                    screen_y,
+                    moon_size + 8.0,
+                    Color::new(glow.r, glow.g, glow.b, moon_alpha * 0.3),
+                );
+            }
+
+            // Main moon body
+            self.queue().circle(
+                screen_x,
+                screen_y,
+                moon_size,
+                Color::new(face.r, face.g, face.b, moon_alpha),
+            );
+
+            // Moon craters for detail
+            if !game_state.time.is_day() {
+                self.queue().circle(
+                    screen_x - 6.0,
+                    screen_y - 4.0,
+                    3.0,
+                    Color::new(crater.r, crater.g, crater.b, moon_alpha * 0.6),
+                );
+                self.queue().circle(
+                    screen_x + 4.0,
+                    screen_y + 2.0,
+                    2.0,
+                    Color::new(crater.r, crater.g, crater.b, moon_alpha * 0.6),
+                );
+                self.queue().circle(
+                    screen_x - 2.0,
+                    screen_y + 6.0,
+                    1.5,
+                    Color::new(crater.r, crater.g, crater.b, moon_alpha * 0.6),
+                );
+            }
+        }
+    }
+
+    pub(super) fn draw_stars(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        // The starfield covers the starting area and repeats across the
+        // rest of the sky, however far the world is streamed out
+        let tile_width = WorldBounds::default().width() * self.zoom_level;
+        for (i, star) in game_state.stars.iter().enumerate() {
+            if !game_state.video_settings.draws_star(i) {
+                continue;
+            }
+
+            let screen_y = star.y * self.zoom_level + camera_offset_y;
+            if screen_y <= -10.0 || screen_y >= screen_height() + 10.0 {
+                continue;
+            }
+            let alpha = star.brightness * if game_state.time.is_day() { 0.1 } else { 1.0 };
+
+            // Only draw stars on screen
+            let mut screen_x =
+                (star.x * self.zoom_level + camera_offset_x + 10.0).rem_euclid(tile_width) - 10.0;
+            while screen_x < screen_width() + 10.0 {
+                self.queue()
+                    .circle(screen_x, screen_y, 1.5, Color::new(1.0, 1.0, 0.9, alpha));
+                screen_x += tile_width;
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Spectator Rendering
+//!
+//! Status bar shown while watching a friend's run.
+
+use super::{RenderLayer, Renderer};
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Banner across the top of the screen describing the stream
+    pub fn draw_spectator_overlay(&self, status: &str, following: bool) {
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let height = 34.0 * scale;
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            height,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        self.draw_text_with_font(
+            &self
+                .strings
+                .format("spectator.status", &[("status", &status)]),
+            12.0 * scale,
+            23.0 * scale,
+            18.0 * scale,
+            Color::new(0.8, 0.85, 1.0, 1.0),
+        );
+
+        let hint = self.tr(if following {
+            "spectator.hint_following"
+        } else {
+            "spectator.hint_free"
+        });
+        let width = measure_text(hint, None, (16.0 * scale) as u16, 1.0).width;
+        self.draw_text_with_font(
+            hint,
+            screen_width() - width - 12.0 * scale,
+            23.0 * scale,
+            16.0 * scale,
+            GRAY,
+        );
+        self.flush_queue(RenderLayer::Overlay);
+    }
+}
@@ -0,0 +1,102 @@
+//! Spectral Vision Rendering
+//!
+//! While Spectral Vision is active the world pass is washed out to grey and
+//! every nearby creature is redrawn as a silhouette on top of it, so nothing
+//! drawn earlier (shelters, terrain, other entities) can hide them. Blood
+//! sources pulse red. This pass must run after the world and before the UI.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::AbilitySystem;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_spectral_pass(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) else {
+            return;
+        };
+        let Some(state) = player
+            .vampire_abilities
+            .as_ref()
+            .map(|abilities| &abilities.ability_state)
+        else {
+            return;
+        };
+        let spectral_remaining = state.spectral_remaining;
+        if spectral_remaining <= 0.0 {
+            return;
+        }
+
+        let targets = AbilitySystem::spectral_entities(
+            &game_state.entities,
+            &game_state.spatial_grid,
+            game_state.player_id,
+        );
+
+        // Fade the wash in and out over the first and last half second
+        let fade = (spectral_remaining / 0.5)
+            .min((state.duration(SpecialAbility::SpectralVision) - spectral_remaining) / 0.5)
+            .clamp(0.0, 1.0);
+
+        // Desaturate everything drawn so far
+        self.queue().rectangle(
+            0.0,
+            0.0,
+            screen_width(),
+            screen_height(),
+            Color::new(0.45, 0.45, 0.5, 0.55 * fade),
+        );
+
+        let pulse = (game_state.game_time * 5.0).sin() * 0.5 + 0.5;
+        for target in &targets {
+            let screen_x = target.position.x * self.zoom_level + camera_offset_x;
+            let screen_y = target.position.y * self.zoom_level + camera_offset_y;
+            let radius = match target.entity_type {
+                EntityType::ClanLeader(_) => 14.0,
+                EntityType::ClanMember(_) => 12.0,
+                EntityType::HostileInfected => 10.0,
+                _ => 8.0,
+            } * self.zoom_level;
+
+            self.queue().circle(
+                screen_x,
+                screen_y,
+                radius,
+                Color::new(0.7, 0.85, 1.0, 0.45 * fade),
+            );
+            self.queue().circle_lines(
+                screen_x,
+                screen_y,
+                radius,
+                1.5,
+                Color::new(0.85, 0.95, 1.0, 0.8 * fade),
+            );
+
+            if target.blood_source {
+                self.queue().circle(
+                    screen_x,
+                    screen_y,
+                    radius * 0.4 + pulse * 2.0,
+                    Color::new(0.9, 0.05, 0.05, (0.5 + pulse * 0.5) * fade),
+                );
+            }
+        }
+
+        // The player stays visible at the centre of the effect
+        let screen_x = player.position.x * self.zoom_level + camera_offset_x;
+        let screen_y = player.position.y * self.zoom_level + camera_offset_y;
+        self.queue().circle_lines(
+            screen_x,
+            screen_y,
+            15.0 * self.zoom_level,
+            2.0,
+            Color::new(1.0, 1.0, 1.0, 0.7 * fade),
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Sprite Rendering
+//!
+//! Pixel-art sprites for the player, clan leaders and members, the
+//! infected and animals, drawn when the sprite atlas has none.
+
+use super::Renderer;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_vampire_sprite(&self, x: f32, y: f32, size: f32, facing: f32) {
+        let pixel_size = size / 8.0;
+
+        // Main body (red)
+        self.queue().rectangle(
+            x - 2.0 * pixel_size,
+            y - 3.0 * pixel_size,
+            4.0 * pixel_size,
+            6.0 * pixel_size,
+            self.palette.vampire_body.color(),
+        );
+
+        // Head (pale)
+        self.queue().rectangle(
+            x - 1.5 * pixel_size,
+            y - 4.0 * pixel_size,
+            3.0 * pixel_size,
+            2.0 * pixel_size,
+            self.palette.vampire_skin.color(),
+        );
+
+        // Eyes (glowing red)
+        self.queue().rectangle(
+            x - 1.0 * pixel_size,
+            y - 3.5 * pixel_size,
+            pixel_size * 0.5,
+            pixel_size * 0.5,
+            self.palette.vampire_eyes.color(),
+        );
+        self.queue().rectangle(
+            x + 0.5 * pixel_size,
+            y - 3.5 * pixel_size,
+            pixel_size * 0.5,
+            pixel_size * 0.5,
+            self.palette.vampire_eyes.color(),
+        );
+
+        // Cape (dark red)
+        if facing.cos() > 0.0 {
+            // Facing right
+            self.queue().rectangle(
+                x - 3.0 * pixel_size,
+                y - 2.0 * pixel_size,
+                2.0 * pixel_size,
+                4.0 * pixel_size,
+                self.palette.vampire_cape.color(),
+            );
+        } else {
+            // Facing left
+            self.queue().rectangle(
+                x + 1.0 * pixel_size,
+                y - 2.0 * pixel_size,
+                2.0 * pixel_size,
+                4.0 * pixel_size,
+                self.palette.vampire_cape.color(),
+            );
+        }
+
+        // Fangs
+        self.queue().rectangle(
+            x - 0.5 * pixel_size,
+            y - 2.5 * pixel_size,
+            pixel_size * 0.3,
+            pixel_size * 0.5,
+            WHITE,
+        );
+        self.queue().rectangle(
+            x + 0.2 * pixel_size,
+            y - 2.5 * pixel_size,
+            pixel_size * 0.3,
+            pixel_size * 0.5,
+            WHITE,
+        );
+
+        // Border for visibility
+        self.queue().rectangle_lines(
+            x - 2.0 * pixel_size,
+            y - 4.0 * pixel_size,
+            4.0 * pixel_size,
+            7.0 * pixel_size,
+            1.0,
+            WHITE,
+        );
+    }
+
+    pub(super) fn draw_clan_leader_sprite(&self, x: f32, y: f32, size: f32, color: Color) {
+        let pixel_size = size / 10.0;
+
+        // Body
+        self.queue().rectangle(
+            x - 2.5 * pixel_size,
+            y - 2.0 * pixel_size,
+            5.0 * pixel_size,
+            4.0 * pixel_size,
+            color,
+        );
+
+        // Head
+        self.queue().rectangle(
+            x - 2.0 * pixel_size,
+            y - 4.0 * pixel_size,
+            4.0 * pixel_size,
+            2.0 * pixel_size,
+            self.palette.clan_skin.color(),
+        );
+
+        // Crown
+        self.queue().rectangle(
+            x - 2.5 * pixel_size,
+            y - 5.0 * pixel_size,
+            5.0 * pixel_size,
+            pixel_size,
+            GOLD,
+        );
+        self.queue().triangle(
+            Vec2::new(x, y - 5.5 * pixel_size),
+            Vec2::new(x - pixel_size, y - 4.5 * pixel_size),
+            Vec2::new(x + pixel_size, y - 4.5 * pixel_size),
+            GOLD,
+        );
+
+        // Eyes
+        self.queue().rectangle(
+            x - 1.5 * pixel_size,
+            y - 3.5 * pixel_size,
+            pixel_size * 0.5,
+            pixel_size * 0.5,
+            BLACK,
+        );
+        self.queue().rectangle(
+            x + pixel_size,
+            y - 3.5 * pixel_size,
+            pixel_size * 0.5,
+            pixel_size * 0.5,
+            BLACK,
+        );
+
+        // Weapon/Staff
+        self.queue().rectangle(
+            x + 3.0 * pixel_size,
+            y - 4.0 * pixel_size,
+            pixel_size * 0.5,
+            6.0 * pixel_size,
+            BROWN,
+        );
+        self.queue().circle(
+            x + 3.25 * pixel_size,
+            y - 4.5 * pixel_size,
+            pixel_size * 0.8,
+            color,
+        );
+    }
+
+    pub(super) fn draw_infected_sprite(&self, x: f32, y: f32, size: f32, facing: f32) {
+        let pixel_size = size / 8.0;
+
+        // Twisted body (dark red)
+        self.queue().rectangle(
+            x - 2.0 * pixel_size,
+            y - 2.0 * pixel_size,
+            4.0 * pixel_size,
+            4.0 * pixel_size,
+            self.palette.infected_body.color(),
+        );
+
+        // Deformed head
+        self.queue().rectangle(
+            x - 1.5 * pixel_size,
+            y - 3.5 * pixel_size,
+            3.0 * pixel_size,
+            1.5 * pixel_size,
+            self.palette.infected_skin.color(),
+        );
+
+        // Glowing hostile eyes
+        self.queue().rectangle(
+            x - pixel_size,
+            y - 3.0 * pixel_size,
+            pixel_size * 0.7,
+            pixel_size * 0.7,
+            self.palette.infected_eyes.color(),
+        );
+        self.queue().rectangle(
+            x + 0.3 * pixel_size,
+            y - 3.0 * pixel_size,
+            pixel_size * 0.7,
+            pixel_size * 0.7,
+            self.palette.infected_eyes.color(),
+        );
+
+        // Claws
+        if facing.cos() > 0.0 {
+            // Facing right
+            (0..3).for_each(|i| {
+                self.queue().rectangle(
+                    x + 2.0 * pixel_size + i as f32 * pixel_size * 0.3,
+                    y - pixel_size + i as f32 * pixel_size * 0.2,
+                    pixel_size * 0.2,
+                    pixel_size,
+                    GRAY,
+                );
+            });
+        } else {
+            // Facing left
+            (0..3).for_each(|i| {
+                self.queue().rectangle(
+                    x - 2.5 * pixel_size - i as f32 * pixel_size * 0.3,
+                    y - pixel_size + i as f32 * pixel_size * 0.2,
+                    pixel_size * 0.2,
+                    pixel_size,
+                    GRAY,
+                );
+            });
+        }
+
+        // Danger X mark
+        self.queue().line(
+            x - pixel_size,
+            y - pixel_size,
+            x + pixel_size,
+            y + pixel_size,
+            2.0,
+            RED,
+        );
+        self.queue().line(
+            x + pixel_size,
+            y - pixel_size,
+            x - pixel_size,
+            y + pixel_size,
+            2.0,
+            RED,
+        );
+    }
+
+    pub(super) fn draw_animal_sprite(&self, x: f32, y: f32, size: f32) {
+        let pixel_size = size / 6.0;
+        let fur = self.palette.animal_fur.color();
+
+        // Body (brown circle with texture)
+        self.queue().circle(x, y, size / 2.0, fur);
+        self.queue()
+            .circle(x, y, size / 2.5, self.palette.animal_hide.color());
+
+        // Ears
+        self.queue().triangle(
+            Vec2::new(x - pixel_size, y - pixel_size * 1.5),
+            Vec2::new(x - pixel_size * 1.5, y - pixel_size * 2.5),
+            Vec2::new(x - pixel_size * 0.5, y - pixel_size * 2.0),
+            fur,
+        );
+        self.queue().triangle(
+            Vec2::new(x + pixel_size, y - pixel_size * 1.5),
+            Vec2::new(x + pixel_size * 1.5, y - pixel_size * 2.5),
+            Vec2::new(x + pixel_size * 0.5, y - pixel_size * 2.0),
+            fur,
+        );
+
+        // Eyes
+        self.queue().circle(
+            x - pixel_size * 0.5,
+            y - pixel_size * 0.3,
+            pixel_size * 0.3,
+            BLACK,
+        );
+        self.queue().circle(
+            x + pixel_size * 0.5,
+            y - pixel_size * 0.3,
+            pixel_size * 0.3,
+            BLACK,
+        );
+
+        // Nose
+        self.queue()
+            .circle(x, y + pixel_size * 0.2, pixel_size * 0.2, BLACK);
+
+        // Tail
+        self.queue().circle(
+            x + pixel_size * 1.8,
+            y + pixel_size * 0.5,
+            pixel_size * 0.4,
+            fur,
+        );
+    }
+
+    pub(super) fn draw_rat_sprite(&self, x: f32, y: f32, size: f32, fur: Color) {
+        let pixel_size = size / 6.0;
+        let pink = Color::new(0.85, 0.55, 0.6, 1.0);
+
+        // Long thin tail trailing behind
+        self.queue().line(
+            x + pixel_size * 2.0,
+            y + pixel_size * 0.5,
+            x + pixel_size * 5.0,
+            y - pixel_size * 0.5,
+            pixel_size * 0.4,
+            pink,
+        );
+
+        // Low, stretched body
+        self.queue().ellipse(x, y, size / 2.0, size / 3.0, 0.0, fur);
+
+        // Round ears
+        self.queue().circle(
+            x - pixel_size * 1.2,
+            y - pixel_size * 1.6,
+            pixel_size * 0.6,
+            pink,
+        );
+        self.queue().circle(
+            x - pixel_size * 0.2,
+            y - pixel_size * 1.7,
+            pixel_size * 0.6,
+            pink,
+        );
+
+        // Beady eye and nose on the pointed snout
+        self.queue().circle(
+            x - pixel_size * 1.5,
+            y - pixel_size * 0.5,
+            pixel_size * 0.3,
+            BLACK,
+        );
+        self.queue()
+            .circle(x - pixel_size * 3.0, y, pixel_size * 0.3, pink);
+    }
+
+    pub(super) fn draw_wolf_sprite(&self, x: f32, y: f32, size: f32, fur: Color) {
+        let pixel_size = size / 6.0;
+        let belly = Color::new(
+            (fur.r + 0.25).min(1.0),
+            (fur.g + 0.25).min(1.0),
+            (fur.b + 0.25).min(1.0),
+            1.0,
+        );
+
+        // Bushy tail
+        self.queue().ellipse(
+            x + pixel_size * 2.4,
+            y + pixel_size * 0.4,
+            pixel_size * 1.4,
+            pixel_size * 0.6,
+            -20.0,
+            fur,
+        );
+
+        // Body with a pale underside
+        self.queue().circle(x, y, size / 2.0, fur);
+        self.queue()
+            .circle(x, y + pixel_size * 0.8, size / 3.5, belly);
+
+        // Tall pointed ears
+        self.queue().triangle(
+            Vec2::new(x - pixel_size * 1.6, y - pixel_size * 1.2),
+            Vec2::new(x - pixel_size * 1.2, y - pixel_size * 3.2),
+            Vec2::new(x - pixel_size * 0.4, y - pixel_size * 1.6),
+            fur,
+        );
+        self.queue().triangle(
+            Vec2::new(x + pixel_size * 1.6, y - pixel_size * 1.2),
+            Vec2::new(x + pixel_size * 1.2, y - pixel_size * 3.2),
+            Vec2::new(x + pixel_size * 0.4, y - pixel_size * 1.6),
+            fur,
+        );
+
+        // Yellow eyes
+        self.queue().circle(
+            x - pixel_size * 0.6,
+            y - pixel_size * 0.5,
+            pixel_size * 0.3,
+            YELLOW,
+        );
+        self.queue().circle(
+            x + pixel_size * 0.6,
+            y - pixel_size * 0.5,
+            pixel_size * 0.3,
+            YELLOW,
+        );
+
+        // Snout
+        self.queue()
+            .circle(x, y + pixel_size * 0.3, pixel_size * 0.7, belly);
+        self.queue()
+            .circle(x, y + pixel_size * 0.1, pixel_size * 0.25, BLACK);
+    }
+
+    pub(super) fn draw_clan_member_sprite(&self, x: f32, y: f32, size: f32, color: Color) {
+        let pixel_size = size / 8.0;
+
+        // Body
+        self.queue().rectangle(
+            x - 2.0 * pixel_size,
+            y - 2.0 * pixel_size,
+            4.0 * pixel_size,
+            4.0 * pixel_size,
+            color,
+        );
+
+        // Head
+        self.queue().rectangle(
+            x - 1.5 * pixel_size,
+            y - 3.5 * pixel_size,
+            3.0 * pixel_size,
+            1.5 * pixel_size,
+            self.palette.clan_skin.color(),
+        );
+
+        // Eyes
+        self.queue().rectangle(
+            x - pixel_size,
+            y - 3.0 * pixel_size,
+            pixel_size * 0.4,
+            pixel_size * 0.4,
+            BLACK,
+        );
+        self.queue().rectangle(
+            x + 0.6 * pixel_size,
+            y - 3.0 * pixel_size,
+            pixel_size * 0.4,
+            pixel_size * 0.4,
+            BLACK,
+        );
+
+        // Simple weapon
+        self.queue().rectangle(
+            x + 2.5 * pixel_size,
+            y - 3.0 * pixel_size,
+            pixel_size * 0.3,
+            4.0 * pixel_size,
+            GRAY,
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Stagger Rendering
+//!
+//! Hit reactions: a staggered creature shakes in place, easing off as it
+//! recovers, and a knocked down one lies on the ground with stars circling
+//! its head until it gets back up.
+
+use super::Renderer;
+use crate::components::*;
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Where to draw an entity reeling from a blow, and whether it is lying
+    /// knocked down instead of standing
+    pub(super) fn stagger_pose(entity: &GameEntity, x: f32, game_time: f32) -> (f32, bool) {
+        let AIState::Staggered(stagger) = &entity.ai_state else {
+            return (x, false);
+        };
+        if stagger.knocked_down {
+            return (x, true);
+        }
+        let shake = (game_time * 60.0).sin() * 3.0 * stagger.fraction_left();
+        (x + shake, false)
+    }
+
+    /// A body on the ground with stars circling above it
+    pub(super) fn draw_knocked_down(
+        &self,
+        entity: &GameEntity,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: Color,
+        game_time: f32,
+    ) {
+        self.queue()
+            .rectangle(x - size * 0.45, y, size * 0.9, size * 0.3, color);
+        self.queue()
+            .circle(x + size * 0.45, y + size * 0.15, size * 0.15, color);
+
+        let AIState::Staggered(stagger) = &entity.ai_state else {
+            return;
+        };
+        let alpha = stagger.fraction_left().min(0.5) * 2.0;
+        for star in 0..3 {
+            let angle = game_time * 4.0 + star as f32 * std::f32::consts::TAU / 3.0;
+            self.queue().circle(
+                x + size * 0.45 + angle.cos() * size * 0.3,
+                y - size * 0.2 + angle.sin() * size * 0.1,
+                size * 0.06,
+                Color::new(1.0, 0.9, 0.3, alpha),
+            );
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Territory Rendering
+//!
+//! Draws territory borders on the ground, filled in the color of whoever
+//! controls them and striped while contested, with the territory's name and
+//! its holder at the centre. A ring shows how long the player has held the
+//! one they are taking, and a status line is shown while the player stands
+//! in a territory that is not yet theirs.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::territory::{TerritoryControl, TerritoryController};
+use crate::systems::{TerritorySystem, WorldSystem};
+use macroquad::prelude::*;
+
+impl Renderer {
+    /// Color of whoever controls a territory: crimson for the player, the
+    /// clan's own color for a clan, pale when nobody holds it
+    pub(super) fn territory_color(&self, control: &TerritoryControl) -> Color {
+        match &control.controller {
+            Some(TerritoryController::Player) => Color::new(0.8, 0.1, 0.15, 1.0),
+            Some(TerritoryController::Clan(clan)) => self
+                .palette
+                .clan_color(clan, WorldSystem::clan_member_color(clan)),
+            None => Color::new(0.75, 0.75, 0.8, 1.0),
+        }
+    }
+
+    /// Diagonal stripes across a circle, marking a contested territory
+    pub(super) fn draw_territory_stripes(
+        &self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        spacing: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+        let mut offset = -radius + spacing / 2.0;
+        while offset < radius {
+            // Each stripe is the chord `offset` from the centre
+            let half_chord = (radius * radius - offset * offset).sqrt();
+            let (mid_x, mid_y) = (x + offset * diagonal, y + offset * diagonal);
+            self.queue().line(
+                mid_x - half_chord * diagonal,
+                mid_y + half_chord * diagonal,
+                mid_x + half_chord * diagonal,
+                mid_y - half_chord * diagonal,
+                thickness,
+                color,
+            );
+            offset += spacing;
+        }
+    }
+
+    pub(super) fn draw_territories(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let zoom = self.zoom_level;
+        for territory in &game_state.territories.territories {
+            let x = territory.center.x * zoom + camera_offset_x;
+            let y = territory.center.y * zoom + camera_offset_y;
+            let radius = territory.radius * zoom;
+            if x + radius < 0.0
+                || y + radius < 0.0
+                || x - radius > screen_width()
+                || y - radius > screen_height()
+            {
+                continue;
+            }
+
+            let control = TerritorySystem::control(territory, &game_state.entities);
+            let color = self.territory_color(&control);
+            let tint = |alpha: f32| Color::new(color.r, color.g, color.b, alpha);
+            if control.controller.is_some() {
+                self.queue().circle(x, y, radius, tint(0.12));
+            }
+            if control.contested {
+                self.draw_territory_stripes(x, y, radius, 24.0 * zoom, 6.0 * zoom, tint(0.1));
+            }
+            let border = if control.controller.is_some() {
+                0.8
+            } else {
+                0.5
+            };
+            self.queue().circle_lines(x, y, radius, 2.0, tint(border));
+
+            // Hold progress sweeps around the boundary
+            if !territory.captured && territory.hold_hours > 0.0 {
+                self.queue().arc(
+                    x,
+                    y,
+                    48,
+                    radius,
+                    -90.0,
+                    4.0,
+                    360.0 * territory.capture_progress(),
+                    Color::new(1.0, 0.85, 0.3, 0.9),
+                );
+            }
+
+            // Name at the centre, with who holds it beneath
+            let label_size = 16.0 * zoom;
+            let width = measure_text(&territory.name, None, label_size as u16, 1.0).width;
+            self.draw_text_with_font(&territory.name, x - width / 2.0, y, label_size, tint(0.8));
+
+            let holder = match &control.controller {
+                Some(TerritoryController::Player) => Some(self.tr("territory.yours")),
+                Some(TerritoryController::Clan(clan)) => Some(clan.as_str()),
+                None => None,
+            };
+            let holder = match (holder, control.contested) {
+                (Some(holder), true) => Some(
+                    self.strings
+                        .format("territory.contested", &[("holder", &holder)]),
+                ),
+                (Some(holder), false) => Some(holder.to_string()),
+                (None, _) => None,
+            };
+            if let Some(holder) = holder {
+                let size = 12.0 * zoom;
+                let width = measure_text(&holder, None, size as u16, 1.0).width;
+                self.draw_text_with_font(
+                    &holder,
+                    x - width / 2.0,
+                    y + 16.0 * zoom,
+                    size,
+                    tint(0.7),
+                );
+            }
+        }
+    }
+
+    /// What the player must still do to take the territory they stand in
+    pub(super) fn draw_territory_hud(&self, game_state: &GameState) {
+        let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) else {
+            return;
+        };
+        let territories = &game_state.territories;
+        let Some(territory) = territories
+            .territory_at(&player.position)
+            .map(|index| &territories.territories[index])
+        else {
+            return;
+        };
+        if territory.captured {
+            return;
+        }
+
+        let hostiles = TerritorySystem::hostiles_in(territory, &game_state.entities);
+        let status = if hostiles > 0 {
+            self.strings.format(
+                "territory.clear",
+                &[("name", &territory.name), ("count", &hostiles)],
+            )
+        } else {
+            self.strings.format(
+                "territory.holding",
+                &[
+                    ("name", &territory.name),
+                    (
+                        "percent",
+                        &format!("{:.0}", territory.capture_progress() * 100.0),
+                    ),
+                ],
+            )
+        };
+
+        let scale = self.ui_scale;
+        let size = 18.0 * scale;
+        let width = measure_text(&status, None, size as u16, 1.0).width;
+        let x = (screen_width() - width) / 2.0;
+        let y = 150.0 * scale;
+        self.queue().rectangle(
+            x - 10.0 * scale,
+            y - 20.0 * scale,
+            width + 20.0 * scale,
+            28.0 * scale,
+            Color::new(0.0, 0.0, 0.0, 0.6),
+        );
+        self.draw_text_with_font(&status, x, y, size, Color::new(1.0, 0.85, 0.3, 1.0));
+    }
+}
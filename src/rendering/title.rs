@@ -0,0 +1,363 @@
+//! Title Rendering
+//!
+//! Draws the title screen with its New Game / Continue / Settings / Quit
+//! menu, and the settings, controls and asset diagnostics screens reached
+//! from it.
+
+use super::{RenderLayer, Renderer};
+use crate::app::{ControlsMenu, SettingsMenu, SettingsRow, TitleMenu};
+use crate::assets::AssetManager;
+use crate::audio::AudioSettings;
+use crate::input::{key_name, ActionMap, InputAction};
+use crate::save::DamagedSave;
+use crate::settings::{Difficulty, VideoSettings};
+use macroquad::prelude::*;
+
+const BACKGROUND: Color = Color::new(0.03, 0.02, 0.05, 1.0);
+
+impl Renderer {
+    /// `missing_assets` counts assets that could not be loaded
+    pub fn draw_title_screen(&self, menu: &TitleMenu, missing_assets: usize) {
+        clear_background(BACKGROUND);
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let mut y = screen_height() / 2.0 - 140.0 * scale;
+
+        // A blood moon hangs behind the title
+        self.queue().circle(
+            center_x,
+            y - 20.0 * scale,
+            90.0 * scale,
+            Color::new(0.35, 0.02, 0.04, 0.6),
+        );
+        self.queue().circle(
+            center_x,
+            y - 20.0 * scale,
+            70.0 * scale,
+            Color::new(0.55, 0.05, 0.07, 0.5),
+        );
+
+        let title = self.tr("title.name");
+        let title_size = 56.0 * scale;
+        let width = measure_text(title, None, title_size as u16, 1.0).width;
+        self.draw_text_with_font(title, center_x - width / 2.0, y, title_size, RED);
+        y += 40.0 * scale;
+        let subtitle = self.tr("title.subtitle");
+        let subtitle_size = 26.0 * scale;
+        let width = measure_text(subtitle, None, subtitle_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            subtitle,
+            center_x - width / 2.0,
+            y,
+            subtitle_size,
+            LIGHTGRAY,
+        );
+
+        y += 70.0 * scale;
+        for &option in menu.options() {
+            let selected = option == menu.selected();
+            let label = if selected {
+                format!("> {} <", self.tr(option.text_key()))
+            } else {
+                self.tr(option.text_key()).to_string()
+            };
+            let size = 28.0 * scale;
+            let width = measure_text(&label, None, size as u16, 1.0).width;
+            self.draw_text_with_font(
+                &label,
+                center_x - width / 2.0,
+                y,
+                size,
+                if selected { GOLD } else { WHITE },
+            );
+            y += 40.0 * scale;
+        }
+
+        let hint = self.tr("title.hint");
+        let hint_size = 16.0 * scale;
+        let width = measure_text(hint, None, hint_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            hint,
+            center_x - width / 2.0,
+            y + 20.0 * scale,
+            hint_size,
+            GRAY,
+        );
+
+        if missing_assets > 0 {
+            let warning = self
+                .strings
+                .format("title.missing_assets", &[("count", &missing_assets)]);
+            let width = measure_text(&warning, None, hint_size as u16, 1.0).width;
+            self.draw_text_with_font(
+                &warning,
+                center_x - width / 2.0,
+                y + 46.0 * scale,
+                hint_size,
+                ORANGE,
+            );
+        }
+        self.flush_queue(RenderLayer::Overlay);
+    }
+
+    pub fn draw_settings_screen(
+        &self,
+        menu: &SettingsMenu,
+        video: &VideoSettings,
+        audio: &AudioSettings,
+        difficulty: Difficulty,
+    ) {
+        clear_background(BACKGROUND);
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let mut y = screen_height() / 2.0 - 180.0 * scale;
+
+        let title = self.tr("settings.title");
+        let title_size = 40.0 * scale;
+        let width = measure_text(title, None, title_size as u16, 1.0).width;
+        self.draw_text_with_font(title, center_x - width / 2.0, y, title_size, RED);
+
+        y += 60.0 * scale;
+        for row in SettingsRow::ALL {
+            let selected = row == menu.selected();
+            let (label, value) = row.describe(&self.strings, video, audio, difficulty);
+            let color = if selected { GOLD } else { WHITE };
+            let size = 22.0 * scale;
+            self.draw_text_with_font(&label, center_x - 200.0 * scale, y, size, color);
+            let value = if selected {
+                format!("< {} >", value)
+            } else {
+                value
+            };
+            self.draw_text_with_font(&value, center_x + 60.0 * scale, y, size, color);
+            y += 34.0 * scale;
+        }
+
+        let hint = self.tr("settings.hint");
+        let hint_size = 16.0 * scale;
+        let width = measure_text(hint, None, hint_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            hint,
+            center_x - width / 2.0,
+            y + 20.0 * scale,
+            hint_size,
+            GRAY,
+        );
+        self.flush_queue(RenderLayer::Overlay);
+    }
+
+    pub fn draw_controls_screen(&self, menu: &ControlsMenu, action_map: &ActionMap) {
+        clear_background(BACKGROUND);
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let mut y = 70.0 * scale;
+
+        let title = self.tr("controls.title");
+        let title_size = 40.0 * scale;
+        let width = measure_text(title, None, title_size as u16, 1.0).width;
+        self.draw_text_with_font(title, center_x - width / 2.0, y, title_size, RED);
+
+        // Keep the selected action in the middle of a window of rows
+        let rows = (((screen_height() - 190.0 * scale) / (26.0 * scale)) as usize).max(1);
+        let selected_index = InputAction::ALL
+            .iter()
+            .position(|action| *action == menu.selected())
+            .unwrap_or(0);
+        let first = selected_index
+            .saturating_sub(rows / 2)
+            .min(InputAction::ALL.len().saturating_sub(rows));
+
+        y += 40.0 * scale;
+        for action in InputAction::ALL.into_iter().skip(first).take(rows) {
+            let selected = action == menu.selected();
+            let keys = action_map
+                .keys_for(action)
+                .into_iter()
+                .filter_map(key_name)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let value = if selected && menu.is_capturing() {
+                self.tr("controls.press_key").to_string()
+            } else if keys.is_empty() {
+                self.tr("controls.unbound").to_string()
+            } else {
+                keys
+            };
+            let conflicted = !action_map.conflicts_with(action).is_empty();
+            let color = if selected {
+                GOLD
+            } else if conflicted {
+                ORANGE
+            } else {
+                WHITE
+            };
+            let size = 20.0 * scale;
+            self.draw_text_with_font(
+                action.display_name(),
+                center_x - 220.0 * scale,
+                y,
+                size,
+                color,
+            );
+            self.draw_text_with_font(&value, center_x + 60.0 * scale, y, size, color);
+            y += 26.0 * scale;
+        }
+
+        let conflicts = action_map.conflicts_with(menu.selected());
+        let hint = if !conflicts.is_empty() {
+            let actions = conflicts
+                .iter()
+                .map(|action| action.display_name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.strings
+                .format("controls.shares_key", &[("actions", &actions)])
+        } else if let Some(status) = &menu.status {
+            status.clone()
+        } else {
+            self.tr("controls.hint").to_string()
+        };
+        let hint_size = 16.0 * scale;
+        let width = measure_text(&hint, None, hint_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            &hint,
+            center_x - width / 2.0,
+            screen_height() - 30.0 * scale,
+            hint_size,
+            if conflicts.is_empty() { GRAY } else { ORANGE },
+        );
+        self.flush_queue(RenderLayer::Overlay);
+    }
+
+    /// Where assets were looked for and what became of each one
+    pub fn draw_diagnostics_screen(&self, assets: &AssetManager) {
+        clear_background(BACKGROUND);
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let left = center_x - 360.0 * scale;
+        let mut y = 70.0 * scale;
+
+        let title = self.tr("diagnostics.title");
+        let title_size = 40.0 * scale;
+        let width = measure_text(title, None, title_size as u16, 1.0).width;
+        self.draw_text_with_font(title, center_x - width / 2.0, y, title_size, RED);
+
+        let size = 18.0 * scale;
+        y += 44.0 * scale;
+        self.draw_text_with_font(self.tr("diagnostics.searched"), left, y, size, LIGHTGRAY);
+        for path in assets.search_paths() {
+            y += 24.0 * scale;
+            self.draw_text_with_font(&path.display().to_string(), left, y, size, GRAY);
+        }
+
+        y += 40.0 * scale;
+        let problems = assets.problems().count();
+        let summary = if problems == 0 {
+            self.tr("diagnostics.all_found").to_string()
+        } else {
+            self.strings
+                .format("diagnostics.missing", &[("count", &problems)])
+        };
+        self.draw_text_with_font(&summary, left, y, size, LIGHTGRAY);
+        // Problems first, so they are never scrolled off the bottom
+        let reports = assets
+            .problems()
+            .chain(assets.reports().iter().filter(|report| report.is_ok()));
+        for report in reports {
+            y += 24.0 * scale;
+            if y > screen_height() - 60.0 * scale {
+                break;
+            }
+            let color = if report.is_ok() { GREEN } else { ORANGE };
+            self.draw_text_with_font(&report.describe(), left, y, size, color);
+        }
+
+        let hint = self.tr("common.back_hint");
+        let hint_size = 16.0 * scale;
+        let width = measure_text(hint, None, hint_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            hint,
+            center_x - width / 2.0,
+            screen_height() - 30.0 * scale,
+            hint_size,
+            GRAY,
+        );
+        self.flush_queue(RenderLayer::Overlay);
+    }
+
+    /// Offer to put back the newest backup of a save that failed to load
+    pub fn draw_restore_save_screen(&self, damaged: &DamagedSave, remaining: usize) {
+        clear_background(BACKGROUND);
+        self.queue().set_layer(RenderLayer::Overlay);
+        let scale = self.ui_scale;
+        let center_x = screen_width() / 2.0;
+        let mut y = screen_height() / 2.0 - 120.0 * scale;
+
+        let title = self.tr("damaged.title");
+        let title_size = 40.0 * scale;
+        let width = measure_text(title, None, title_size as u16, 1.0).width;
+        self.draw_text_with_font(title, center_x - width / 2.0, y, title_size, RED);
+
+        let size = 20.0 * scale;
+        let mut lines = vec![
+            (
+                self.strings
+                    .format("damaged.could_not_load", &[("slot", &damaged.slot_name())]),
+                WHITE,
+            ),
+            (damaged.error.clone(), ORANGE),
+        ];
+        let hint = match &damaged.backup {
+            Some((path, backup)) => {
+                lines.push((String::new(), WHITE));
+                lines.push((
+                    self.strings.format(
+                        "damaged.backup",
+                        &[
+                            ("day", &backup.time.day_count()),
+                            ("feedings", &backup.feeding_count),
+                            ("kills", &backup.kills),
+                        ],
+                    ),
+                    WHITE,
+                ));
+                lines.push((path.display().to_string(), GRAY));
+                self.tr("damaged.restore_hint")
+            }
+            None => {
+                lines.push((String::new(), WHITE));
+                lines.push((self.tr("damaged.no_backup").to_string(), WHITE));
+                self.tr("damaged.continue_hint")
+            }
+        };
+        if remaining > 1 {
+            lines.push((
+                self.strings
+                    .format("damaged.more", &[("count", &(remaining - 1))]),
+                GRAY,
+            ));
+        }
+
+        y += 50.0 * scale;
+        for (line, color) in lines {
+            let width = measure_text(&line, None, size as u16, 1.0).width;
+            self.draw_text_with_font(&line, center_x - width / 2.0, y, size, color);
+            y += 30.0 * scale;
+        }
+
+        let hint_size = 16.0 * scale;
+        let width = measure_text(hint, None, hint_size as u16, 1.0).width;
+        self.draw_text_with_font(
+            hint,
+            center_x - width / 2.0,
+            y + 20.0 * scale,
+            hint_size,
+            GRAY,
+        );
+        self.flush_queue(RenderLayer::Overlay);
+    }
+}
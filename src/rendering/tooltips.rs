@@ -0,0 +1,62 @@
+//! Tooltip Rendering
+//!
+//! Panels beside the ability hotbar showing an ability's blood cost and what
+//! it will leave, its cooldown, its effects at the vampire's current stats
+//! and what is modifying them.
+
+use super::Renderer;
+use crate::systems::AbilityTooltip;
+use macroquad::prelude::*;
+
+/// Panel size on the 1280x720 layout, scaled with the rest of the HUD
+const PANEL_WIDTH: f32 = 300.0;
+const LINE_HEIGHT: f32 = 16.0;
+const PADDING: f32 = 8.0;
+
+impl Renderer {
+    /// Draw tooltip panels at `x`, each beside its hotbar row, pushed down
+    /// so they never overlap one another or run off the screen
+    pub(super) fn draw_ability_tooltips(&self, tooltips: &[(AbilityTooltip, f32)], x: f32) {
+        let (panel_width, line_height, padding) =
+            (self.ui(PANEL_WIDTH), self.ui(LINE_HEIGHT), self.ui(PADDING));
+        let mut next_free = 0.0_f32;
+        for (tooltip, row_y) in tooltips {
+            let lines = tooltip.lines();
+            let blocker = tooltip.blocker();
+            let rows = 1 + lines.len() + usize::from(blocker.is_some());
+            let height = rows as f32 * line_height + padding * 2.0;
+            let y = row_y
+                .max(next_free)
+                .min((screen_height() - height).max(0.0));
+            next_free = y + height + self.ui(4.0);
+
+            self.queue()
+                .rectangle(x, y, panel_width, height, Color::new(0.05, 0.02, 0.06, 0.9));
+            self.queue()
+                .rectangle_lines(x, y, panel_width, height, 1.0, DARKGRAY);
+
+            let mut line_y = y + padding + self.ui(12.0);
+            self.draw_text_with_font(
+                tooltip.ability.display_name(),
+                x + padding,
+                line_y,
+                self.ui(16.0),
+                GOLD,
+            );
+            for (index, line) in lines.iter().enumerate() {
+                line_y += line_height;
+                // The cost line turns red when the blood is not there
+                let color = if index == 0 && !tooltip.affordable() {
+                    Color::new(0.9, 0.4, 0.4, 1.0)
+                } else {
+                    LIGHTGRAY
+                };
+                self.draw_text_with_font(line, x + padding, line_y, self.ui(13.0), color);
+            }
+            if let Some(blocker) = blocker {
+                line_y += line_height;
+                self.draw_text_with_font(&blocker, x + padding, line_y, self.ui(13.0), ORANGE);
+            }
+        }
+    }
+}
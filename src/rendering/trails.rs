@@ -0,0 +1,47 @@
+//! Trail Rendering
+//!
+//! Draws the blood left on the ground by wounded creatures, each drop
+//! fading as it dries and as it nears the edge of the player's senses.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_blood_trails(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) else {
+            return;
+        };
+        let blood_sense = player
+            .vampire_abilities
+            .as_ref()
+            .map_or(0.0, |abilities| abilities.blood_sense);
+
+        let zoom = self.zoom_level;
+        for drop in &game_state.blood_trails.drops {
+            let x = drop.position.x * zoom + camera_offset_x;
+            let y = drop.position.y * zoom + camera_offset_y;
+            if x < -10.0 || y < -10.0 || x > screen_width() + 10.0 || y > screen_height() + 10.0 {
+                continue;
+            }
+            let alpha = drop.alpha(&player.position, blood_sense);
+            if alpha <= 0.0 {
+                continue;
+            }
+            // Fresh blood is bright; it darkens as it dries
+            let dryness = (drop.age / 10.0).min(1.0);
+            self.queue().circle(
+                x,
+                y,
+                drop.size * zoom,
+                Color::new(0.65 - 0.3 * dryness, 0.02, 0.04, alpha * 0.85),
+            );
+        }
+    }
+}
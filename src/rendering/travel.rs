@@ -0,0 +1,108 @@
+//! Travel Rendering
+//!
+//! Draws the chosen waypoint in the world, the route of an auto-walk in
+//! progress, and a line on the HUD naming the waypoint and its distance.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+const WAYPOINT_COLOR: Color = Color::new(0.4, 0.9, 1.0, 0.9);
+const ROUTE_COLOR: Color = Color::new(0.4, 0.9, 1.0, 0.35);
+
+impl Renderer {
+    pub(super) fn draw_waypoint(
+        &self,
+        game_state: &GameState,
+        camera_offset_x: f32,
+        camera_offset_y: f32,
+    ) {
+        let Some(waypoint) = &game_state.waypoint else {
+            return;
+        };
+        let to_screen = |position: &Position| {
+            (
+                position.x * self.zoom_level + camera_offset_x,
+                position.y * self.zoom_level + camera_offset_y,
+            )
+        };
+
+        // Dotted route from the player through the remaining points
+        if let (Some(auto_walk), Some(player)) = (
+            &game_state.auto_walk,
+            EntityFinder::by_id(&game_state.entities, game_state.player_id),
+        ) {
+            let mut from = to_screen(&player.position);
+            for point in auto_walk.remaining_route() {
+                let to = to_screen(point);
+                let length = ((to.0 - from.0).powi(2) + (to.1 - from.1).powi(2)).sqrt();
+                let dots = (length / 12.0) as usize;
+                for i in 0..dots {
+                    let t = i as f32 / dots as f32;
+                    self.queue().circle(
+                        from.0 + (to.0 - from.0) * t,
+                        from.1 + (to.1 - from.1) * t,
+                        2.0,
+                        ROUTE_COLOR,
+                    );
+                }
+                from = to;
+            }
+        }
+
+        let (x, y) = to_screen(&waypoint.position);
+        let bob = (game_state.game_time * 3.0).sin() * 3.0;
+        let size = 8.0;
+        let top = y - 30.0 + bob;
+        self.queue().triangle(
+            vec2(x, top + size * 2.0),
+            vec2(x - size, top),
+            vec2(x + size, top),
+            WAYPOINT_COLOR,
+        );
+        let label_width = measure_text(&waypoint.label, None, 14, 1.0).width;
+        self.draw_text_with_font(
+            &waypoint.label,
+            x - label_width / 2.0,
+            top - 6.0,
+            14.0,
+            WAYPOINT_COLOR,
+        );
+    }
+
+    /// Waypoint name, distance and whether auto-walk is on
+    pub(super) fn draw_waypoint_hud(&self, game_state: &GameState) {
+        let (Some(waypoint), Some(player)) = (
+            &game_state.waypoint,
+            EntityFinder::by_id(&game_state.entities, game_state.player_id),
+        ) else {
+            return;
+        };
+        let scale = self.ui_scale;
+        let status = self.tr(if game_state.auto_walk.is_some() {
+            "travel.auto_walking"
+        } else {
+            "travel.auto_walk_hint"
+        });
+        let text = self.strings.format(
+            "travel.waypoint",
+            &[
+                ("label", &waypoint.label),
+                (
+                    "distance",
+                    &format!("{:.0}", player.position.distance_to(&waypoint.position)),
+                ),
+                ("status", &status),
+            ],
+        );
+        let width = measure_text(&text, None, (16.0 * scale) as u16, 1.0).width;
+        self.draw_text_with_font(
+            &text,
+            (screen_width() - width) / 2.0,
+            30.0 * scale,
+            16.0 * scale,
+            WAYPOINT_COLOR,
+        );
+    }
+}
@@ -0,0 +1,516 @@
+//! UI Rendering
+//!
+//! The in-game HUD: the player's bars, abilities and status lines, the debug
+//! log, and the low-spec suggestion banner.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::systems::player::MAX_RETINUE;
+use crate::systems::shelter::WORK_BLOOD_PER_SECOND;
+use crate::systems::warfare::NEWS_PREFIX;
+use crate::systems::AbilitySystem;
+use macroquad::prelude::*;
+
+impl Renderer {
+    pub(super) fn draw_ui(&self, game_state: &GameState) {
+        // Time display with UI scaling
+        let mut time_text = self.strings.format(
+            "hud.time",
+            &[
+                ("time", &game_state.time.get_time_string()),
+                ("day", &game_state.time.day_count()),
+            ],
+        );
+        if game_state.time.weather() != Weather::Clear {
+            time_text.push_str(&format!(" - {}", game_state.time.weather().display_name()));
+        }
+        self.draw_text_with_font(
+            &time_text,
+            20.0 * self.ui_scale,
+            30.0 * self.ui_scale,
+            24.0 * self.ui_scale,
+            WHITE,
+        );
+
+        // Day/night indicator
+        let day_text = self.tr(if game_state.time.is_day() {
+            "hud.day_indicator"
+        } else {
+            "hud.night_indicator"
+        });
+        let day_color = if game_state.time.is_day() {
+            YELLOW
+        } else {
+            BLUE
+        };
+        self.draw_text_with_font(
+            day_text,
+            20.0 * self.ui_scale,
+            60.0 * self.ui_scale,
+            24.0 * self.ui_scale,
+            day_color,
+        );
+        self.draw_text_with_font(
+            &game_state.player_name,
+            110.0 * self.ui_scale,
+            60.0 * self.ui_scale,
+            20.0 * self.ui_scale,
+            LIGHTGRAY,
+        );
+
+        // Player stats using optimized entity finder
+        if let Some(player) = EntityFinder::by_id(&game_state.entities, game_state.player_id) {
+            let mut y_offset = 100.0 * self.ui_scale;
+
+            // Health bar
+            if let Some(health) = &player.health {
+                self.queue().rectangle(
+                    20.0 * self.ui_scale,
+                    y_offset,
+                    200.0 * self.ui_scale,
+                    20.0 * self.ui_scale,
+                    Color::new(0.3, 0.0, 0.0, 1.0),
+                );
+                let health_width = 200.0 * self.ui_scale * (health.current / health.max);
+                self.queue().rectangle(
+                    20.0 * self.ui_scale,
+                    y_offset,
+                    health_width,
+                    20.0 * self.ui_scale,
+                    RED,
+                );
+                self.draw_text_with_font(
+                    self.tr("hud.health"),
+                    20.0 * self.ui_scale,
+                    y_offset - 5.0 * self.ui_scale,
+                    16.0 * self.ui_scale,
+                    WHITE,
+                );
+                if let Some(buffs) = &player.buffs {
+                    self.draw_buff_icons(buffs, 230.0 * self.ui_scale, y_offset);
+                }
+                y_offset += 30.0 * self.ui_scale;
+            }
+
+            // Blood bar
+            if let Some(blood) = &player.blood_meter {
+                self.queue().rectangle(
+                    20.0 * self.ui_scale,
+                    y_offset,
+                    200.0 * self.ui_scale,
+                    20.0 * self.ui_scale,
+                    Color::new(0.0, 0.0, 0.3, 1.0),
+                );
+                let blood_width = 200.0 * self.ui_scale * (blood.current / blood.maximum);
+                self.queue().rectangle(
+                    20.0 * self.ui_scale,
+                    y_offset,
+                    blood_width,
+                    20.0 * self.ui_scale,
+                    BLUE,
+                );
+                self.draw_text_with_font(
+                    self.tr("hud.blood"),
+                    20.0 * self.ui_scale,
+                    y_offset - 5.0 * self.ui_scale,
+                    16.0 * self.ui_scale,
+                    WHITE,
+                );
+                let vials = player
+                    .inventory
+                    .as_ref()
+                    .map_or(0, |inventory| inventory.count(ItemKind::BloodVial));
+                self.draw_blood_reserve(
+                    230.0 * self.ui_scale,
+                    y_offset + 10.0 * self.ui_scale,
+                    vials,
+                    blood.bottling / ItemKind::VIAL_BLOOD,
+                );
+                y_offset += 30.0 * self.ui_scale;
+            }
+
+            // Special abilities; hovering one, or holding the info key,
+            // shows what it costs and does
+            if let Some(abilities) = &player.vampire_abilities {
+                let (mouse_x, mouse_y) = mouse_position();
+                let mut tooltips = Vec::new();
+                for (i, ability) in SpecialAbility::ALL.iter().enumerate() {
+                    let tooltip =
+                        AbilitySystem::tooltip(*ability, abilities, player.blood_meter.as_ref());
+                    let remaining = tooltip.cooldown_remaining;
+                    let unlearned = player
+                        .progression
+                        .as_ref()
+                        .is_some_and(|progression| !progression.knows(*ability));
+                    let (status, color) = if unlearned {
+                        (self.tr("hud.unlearned").to_string(), DARKGRAY)
+                    } else if *ability == SpecialAbility::BloodSight
+                        && abilities.ability_state.is_blood_sight()
+                    {
+                        (
+                            self.strings.on_off(true).to_string(),
+                            Color::new(0.9, 0.2, 0.2, 1.0),
+                        )
+                    } else if remaining > 0.0 {
+                        (format!("{:.0}s", remaining.ceil()), GRAY)
+                    } else if !tooltip.affordable() {
+                        (
+                            self.tr("hud.ready").to_string(),
+                            Color::new(0.9, 0.4, 0.4, 1.0),
+                        )
+                    } else {
+                        (self.tr("hud.ready").to_string(), WHITE)
+                    };
+                    self.draw_text_with_font(
+                        &self.strings.format(
+                            "hud.ability",
+                            &[
+                                ("key", &(i + 1)),
+                                ("name", &ability.display_name()),
+                                ("cost", &format!("{:.0}", ability.blood_cost())),
+                                ("status", &status),
+                            ],
+                        ),
+                        self.ui(20.0),
+                        y_offset,
+                        self.ui(14.0),
+                        color,
+                    );
+                    let hovered = (self.ui(20.0)..=self.ui(300.0)).contains(&mouse_x)
+                        && mouse_y > y_offset - self.ui(14.0)
+                        && mouse_y <= y_offset + self.ui(4.0);
+                    if game_state.show_ability_tooltips || hovered {
+                        tooltips.push((tooltip, y_offset - self.ui(14.0)));
+                    }
+                    y_offset += self.ui(18.0);
+                }
+                y_offset += self.ui(7.0);
+                self.draw_ability_tooltips(&tooltips, self.ui(310.0));
+            }
+
+            // Retinue
+            let followers: Vec<&FollowerOrder> = game_state
+                .entities
+                .iter()
+                .filter_map(|entity| match &entity.ai_state {
+                    AIState::Follower(order) => Some(order),
+                    _ => None,
+                })
+                .collect();
+            if let Some(order) = followers.first() {
+                self.draw_text_with_font(
+                    &self.strings.format(
+                        "hud.retinue",
+                        &[
+                            ("count", &followers.len()),
+                            ("max", &MAX_RETINUE),
+                            ("order", &order.display_name()),
+                        ],
+                    ),
+                    self.ui(20.0),
+                    y_offset,
+                    self.ui(16.0),
+                    GOLD,
+                );
+                y_offset += self.ui(25.0);
+            }
+
+            // Assault under way
+            if let Some(assault) = &game_state.assaults.active {
+                self.draw_text_with_font(
+                    &assault.status(),
+                    self.ui(20.0),
+                    y_offset,
+                    self.ui(16.0),
+                    ORANGE,
+                );
+                y_offset += self.ui(25.0);
+            }
+
+            // Phase info
+            self.draw_text_with_font(
+                &self.strings.format(
+                    "hud.phase",
+                    &[("phase", &format!("{:?}", game_state.phase))],
+                ),
+                self.ui(20.0),
+                y_offset,
+                self.ui(18.0),
+                WHITE,
+            );
+            y_offset += self.ui(25.0);
+
+            // Stats
+            self.draw_text_with_font(
+                &self.strings.format(
+                    "hud.stats",
+                    &[
+                        ("kills", &game_state.kills),
+                        ("feedings", &game_state.feeding_count),
+                    ],
+                ),
+                self.ui(20.0),
+                y_offset,
+                self.ui(18.0),
+                WHITE,
+            );
+            y_offset += self.ui(25.0);
+
+            // Shelter status
+            if game_state.is_player_in_shelter() {
+                let protection = game_state.get_player_shelter_protection();
+                let shelter = game_state.player_shelter();
+                let percent = (protection * 100.0) as u32;
+                let protection_text = match shelter {
+                    Some(shelter) => self.strings.format(
+                        "hud.in_shelter_state",
+                        &[("state", &shelter.state_text()), ("percent", &percent)],
+                    ),
+                    None => self
+                        .strings
+                        .format("hud.in_shelter", &[("percent", &percent)]),
+                };
+                self.draw_text_with_font(
+                    &protection_text,
+                    self.ui(20.0),
+                    y_offset,
+                    self.ui(18.0),
+                    GREEN,
+                );
+                y_offset += self.ui(25.0);
+                if let Some((work, progress)) = shelter.and_then(|shelter| {
+                    shelter
+                        .next_work()
+                        .map(|work| (work, shelter.work_progress))
+                }) {
+                    self.draw_text_with_font(
+                        &self.strings.format(
+                            "hud.shelter_work",
+                            &[
+                                ("work", &work.verb()),
+                                ("percent", &format!("{:.0}", progress * 100.0)),
+                                ("cost", &format!("{:.1}", WORK_BLOOD_PER_SECOND)),
+                            ],
+                        ),
+                        self.ui(20.0),
+                        y_offset,
+                        self.ui(14.0),
+                        LIGHTGRAY,
+                    );
+                    y_offset += self.ui(20.0);
+                }
+                if game_state.time.is_day() && game_state.hibernation.is_none() {
+                    self.draw_text_with_font(
+                        self.tr("hud.hibernate"),
+                        self.ui(20.0),
+                        y_offset,
+                        self.ui(14.0),
+                        LIGHTGRAY,
+                    );
+                    y_offset += self.ui(20.0);
+                }
+            } else if game_state.time.is_day() && game_state.time.get_sunlight_intensity() > 0.0 {
+                let exposure = game_state.get_player_sun_exposure();
+                if exposure < 1.0 {
+                    let shade_text = self
+                        .strings
+                        .format("hud.in_shade", &[("percent", &((exposure * 100.0) as u32))]);
+                    self.draw_text_with_font(
+                        &shade_text,
+                        self.ui(20.0),
+                        y_offset,
+                        self.ui(18.0),
+                        ORANGE,
+                    );
+                } else {
+                    let danger_text = self.tr("hud.exposed");
+                    self.draw_text_with_font(
+                        danger_text,
+                        self.ui(20.0),
+                        y_offset,
+                        self.ui(18.0),
+                        RED,
+                    );
+                }
+                y_offset += self.ui(25.0);
+            }
+
+            // How far the player's steps carry
+            if let Some(noise) = game_state.noise_profile() {
+                let color = match noise.label() {
+                    "Muffled" => GREEN,
+                    "Normal" => LIGHTGRAY,
+                    _ => ORANGE,
+                };
+                self.draw_text_with_font(
+                    &self.strings.format(
+                        "hud.noise",
+                        &[
+                            ("label", &noise.label()),
+                            ("multiplier", &format!("{:.2}", noise.multiplier)),
+                            ("detail", &noise.describe()),
+                        ],
+                    ),
+                    self.ui(20.0),
+                    y_offset,
+                    self.ui(16.0),
+                    color,
+                );
+                y_offset += self.ui(22.0);
+            }
+
+            // How well hidden the player is while sneaking
+            if let Some(stealth) = game_state
+                .stealth_profile()
+                .filter(|profile| profile.sneaking)
+            {
+                let color = if stealth.shadowed { GREEN } else { LIGHTGRAY };
+                self.draw_text_with_font(
+                    &self.strings.format(
+                        "hud.stealth",
+                        &[
+                            ("label", &stealth.label()),
+                            ("multiplier", &format!("{:.2}", stealth.multiplier)),
+                        ],
+                    ),
+                    self.ui(20.0),
+                    y_offset,
+                    self.ui(16.0),
+                    color,
+                );
+                y_offset += self.ui(22.0);
+            }
+
+            // Nearby shelters
+            let nearby_shelters = game_state.get_nearby_shelters();
+            if !nearby_shelters.is_empty() {
+                self.draw_text_with_font(
+                    self.tr("hud.nearby_shelters"),
+                    self.ui(20.0),
+                    y_offset,
+                    self.ui(16.0),
+                    LIGHTGRAY,
+                );
+                y_offset += self.ui(20.0);
+
+                for shelter in nearby_shelters.iter().take(3) {
+                    let shelter_text = if shelter.distance <= shelter.shelter_type.discovery_range()
+                    {
+                        self.strings.format(
+                            "hud.shelter_entry",
+                            &[("shelter", &shelter.get_description())],
+                        )
+                    } else {
+                        self.strings.format(
+                            "hud.shelter_distance",
+                            &[("distance", &format!("{:.0}", shelter.distance))],
+                        )
+                    };
+
+                    let text_color = if shelter.discovered { WHITE } else { GRAY };
+
+                    self.draw_text_with_font(
+                        &shelter_text,
+                        self.ui(25.0),
+                        y_offset,
+                        self.ui(14.0),
+                        text_color,
+                    );
+                    y_offset += self.ui(18.0);
+                }
+            }
+
+            // Objectives
+            self.draw_text_with_font(
+                self.tr("hud.objectives"),
+                self.ui(20.0),
+                y_offset,
+                self.ui(18.0),
+                YELLOW,
+            );
+            y_offset += self.ui(25.0);
+
+            for objective in &game_state.phase_objectives {
+                self.draw_text_with_font(
+                    &format!("• {}", objective),
+                    self.ui(30.0),
+                    y_offset,
+                    self.ui(14.0),
+                    WHITE,
+                );
+                y_offset += self.ui(18.0);
+            }
+        }
+
+        // Controls, anchored to the bottom edge
+        let controls_y = self.bottom_edge(100.0);
+        self.draw_text_with_font(
+            self.tr("hud.controls"),
+            self.ui(20.0),
+            controls_y,
+            self.ui(16.0),
+            GRAY,
+        );
+    }
+
+    pub(super) fn draw_low_spec_suggestion(&self) {
+        let text = self.tr("hud.low_spec");
+        let font_size = 18.0 * self.ui_scale;
+        let width = measure_text(text, None, font_size as u16, 1.0).width + 30.0 * self.ui_scale;
+        let x = (screen_width() - width) / 2.0;
+        let y = 20.0 * self.ui_scale;
+
+        self.queue().rectangle(
+            x,
+            y,
+            width,
+            32.0 * self.ui_scale,
+            Color::new(0.4, 0.1, 0.1, 0.85),
+        );
+        self.draw_text_with_font(
+            text,
+            x + 15.0 * self.ui_scale,
+            y + 22.0 * self.ui_scale,
+            font_size,
+            WHITE,
+        );
+    }
+
+    pub(super) fn draw_debug_messages(&self, game_state: &GameState) {
+        // Anchored to the top right corner, clear of the right margin
+        let debug_x = self.right_edge(420.0);
+        let mut debug_y = 50.0 * self.ui_scale;
+
+        // Draw background for debug messages
+        self.queue().rectangle(
+            debug_x - 10.0 * self.ui_scale,
+            debug_y - 30.0 * self.ui_scale,
+            410.0 * self.ui_scale,
+            (game_state.debug_messages.len() as f32 * 18.0 * self.ui_scale) + 40.0 * self.ui_scale,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+
+        // Draw title
+        self.draw_text_with_font(
+            self.tr("hud.debug_log"),
+            debug_x,
+            debug_y,
+            16.0 * self.ui_scale,
+            YELLOW,
+        );
+        debug_y += 25.0 * self.ui_scale;
+
+        // Draw messages
+        for message in &game_state.debug_messages {
+            // Clan news stands out from the rest of the log
+            let color = if message.starts_with(NEWS_PREFIX) {
+                Color::new(0.95, 0.8, 0.55, 1.0)
+            } else {
+                WHITE
+            };
+            self.draw_text_with_font(message, debug_x, debug_y, 12.0 * self.ui_scale, color);
+            debug_y += 18.0 * self.ui_scale;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Weather Rendering
+//!
+//! Draws rain as slanted streaks over the world, fog as a haze thickening
+//! toward the ground, and a grey wash under an overcast sky.
+
+use super::Renderer;
+use crate::components::*;
+use crate::game_state::GameState;
+use macroquad::prelude::*;
+
+const RAIN_COLOR: Color = Color::new(0.6, 0.65, 0.8, 0.35);
+const FOG_COLOR: Color = Color::new(0.75, 0.78, 0.8, 1.0);
+const OVERCAST_COLOR: Color = Color::new(0.35, 0.37, 0.4, 0.18);
+
+/// Horizontal bands the fog gradient is drawn in
+const FOG_BANDS: usize = 12;
+/// Fog opacity at the top and bottom of the screen
+const FOG_TOP_ALPHA: f32 = 0.1;
+const FOG_BOTTOM_ALPHA: f32 = 0.45;
+
+impl Renderer {
+    pub(super) fn draw_weather(&self, game_state: &GameState) {
+        match game_state.time.weather() {
+            Weather::Clear => {}
+            Weather::Overcast => self.draw_overcast(),
+            Weather::Rain => {
+                self.draw_overcast();
+                self.draw_rain();
+            }
+            Weather::Fog => self.draw_fog(),
+        }
+    }
+
+    fn draw_rain(&self) {
+        let count = if self.performance_mode { 60 } else { 150 };
+        let time = get_time() as f32;
+        let (width, height) = (screen_width(), screen_height());
+        for i in 0..count {
+            // Spread streaks with a fixed hash so they fall steadily in place
+            let seed = i as f32 * 12.9898;
+            let x = (seed.sin() * 43758.547).fract().abs() * width;
+            let speed = 500.0 + (seed.cos() * 1000.0).fract().abs() * 300.0;
+            let y = (((seed * 3.7).sin().abs() * height) + time * speed) % height;
+            self.queue().line(x, y, x - 4.0, y + 14.0, 1.0, RAIN_COLOR);
+        }
+    }
+
+    fn draw_overcast(&self) {
+        self.queue()
+            .rectangle(0.0, 0.0, screen_width(), screen_height(), OVERCAST_COLOR);
+    }
+
+    /// Haze in bands, thin overhead and thick near the ground, drifting
+    /// slowly so it does not look painted on
+    fn draw_fog(&self) {
+        let (width, height) = (screen_width(), screen_height());
+        let bands = if self.performance_mode {
+            FOG_BANDS / 2
+        } else {
+            FOG_BANDS
+        };
+        let band_height = height / bands as f32;
+        let drift = (get_time() as f32 * 0.3).sin() * 0.04;
+        for band in 0..bands {
+            let depth = (band as f32 + 0.5) / bands as f32;
+            let alpha = FOG_TOP_ALPHA + (FOG_BOTTOM_ALPHA - FOG_TOP_ALPHA) * depth + drift;
+            let mut color = FOG_COLOR;
+            color.a = alpha.clamp(0.0, 1.0);
+            self.queue().rectangle(
+                0.0,
+                band as f32 * band_height,
+                width,
+                band_height + 1.0,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,693 @@
+//! Save Module
+//!
+//! Snapshots of the game written to JSON files. A timed autosave is kept in a
+//! single slot, and key milestones (phase advances, new alliances, defeated
+//! clan leaders, built lairs) are written to a small set of rotating slots so
+//! the player can return to pivotal moments without saving by hand.
+//!
+//! Each file carries a checksum of the save it wraps, and every slot keeps a
+//! few older copies as backups, so a save damaged on disk is noticed on load
+//! and can be replaced by the newest backup that still checks out.
+
+use crate::components::*;
+use crate::game_state::GameState;
+use crate::leaderboard::{ScoreClaims, ScoreLedger};
+use crate::settings::{Difficulty, WeaknessRules};
+use crate::systems::{
+    AssaultTracker, Barks, Bestiary, Calendar, ClanRelations, ClanRoster, Decay, HintTracker,
+    LifecycleSystem, LoreCodex, QuestLog, SpawnDirector, TimeSystem, WorldSystem,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Save format version; bumped when old saves can no longer be read
+pub const SAVE_VERSION: u32 = 1;
+
+/// Seconds of play between timed autosaves
+const AUTOSAVE_INTERVAL: f32 = 180.0;
+
+/// Number of rotating milestone save slots
+const MILESTONE_SLOTS: usize = 5;
+
+/// Older copies kept of each slot
+const BACKUPS_PER_SLOT: usize = 3;
+
+/// FNV-1a, the same hash the leaderboard signs scores with
+fn checksum(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Errors that can occur while writing or reading saves
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("could not access save file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("save file is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("save was made by an incompatible version ({0})")]
+    Version(u32),
+    #[error("save file is damaged (checksum mismatch)")]
+    Checksum,
+}
+
+/// A save file as written: the save itself, kept byte for byte so the
+/// checksum can be recomputed, and the checksum of those bytes
+#[derive(Serialize, Deserialize)]
+struct SaveFile<'a> {
+    checksum: String,
+    #[serde(borrow)]
+    save: &'a RawValue,
+}
+
+/// A slot whose save could not be read, and the backup that could replace it
+#[derive(Debug, Clone)]
+pub struct DamagedSave {
+    pub path: PathBuf,
+    pub error: String,
+    /// Newest backup of the slot that still loads
+    pub backup: Option<(PathBuf, SaveData)>,
+}
+
+impl DamagedSave {
+    /// Name of the slot for messages, e.g. `autosave.json`
+    pub fn slot_name(&self) -> String {
+        self.path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+    }
+}
+
+/// A pivotal moment worth keeping a save for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Milestone {
+    PhaseAdvanced(GamePhase),
+    ClanAllied(String),
+    LeaderDefeated(String),
+    LairBuilt,
+}
+
+impl Milestone {
+    pub fn description(&self) -> String {
+        match self {
+            Milestone::PhaseAdvanced(phase) => format!("Entered {:?}", phase),
+            Milestone::ClanAllied(clan) => format!("Allied with the {}", clan),
+            Milestone::LeaderDefeated(clan) => format!("Defeated the leader of the {}", clan),
+            Milestone::LairBuilt => "Built a lair".to_string(),
+        }
+    }
+}
+
+/// Why a save was written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SaveReason {
+    Timed,
+    Milestone(Milestone),
+}
+
+/// Everything needed to restore a game in progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    /// Wall-clock milliseconds since the Unix epoch when the save was written
+    pub saved_at: u64,
+    pub reason: SaveReason,
+    pub entities: Vec<GameEntity>,
+    /// Entity ID slots handed out so far, live or free
+    pub next_entity_id: u32,
+    pub player_id: u32,
+    pub time: TimeSystem,
+    pub phase: GamePhase,
+    pub clans: HashMap<String, Clan>,
+    pub phase_objectives: Vec<String>,
+    pub completed_objectives: Vec<String>,
+    #[serde(default)]
+    pub objective_completion_days: HashMap<String, u32>,
+    pub game_time: f32,
+    pub kills: u32,
+    pub feeding_count: u32,
+    pub tribute_stockpile: TributeStockpile,
+    pub last_tribute_day: u32,
+    #[serde(default)]
+    pub weakness_rules: WeaknessRules,
+    #[serde(default = "default_player_name")]
+    pub player_name: String,
+    #[serde(default = "WorldSystem::place_territories")]
+    pub territories: TerritoryManager,
+    #[serde(default)]
+    pub fog_of_war: FogOfWar,
+    #[serde(default)]
+    pub weather: Weather,
+    #[serde(default)]
+    pub spawn_director: SpawnDirector,
+    #[serde(default)]
+    pub score_ledger: Option<ScoreLedger>,
+    #[serde(default)]
+    pub lore_codex: LoreCodex,
+    /// Skill points banked before they moved onto the player's progression
+    #[serde(default)]
+    pub skill_points: u32,
+    #[serde(default)]
+    pub clan_roster: ClanRoster,
+    #[serde(default)]
+    pub objective_hints: HintTracker,
+    #[serde(default)]
+    pub clan_relations: ClanRelations,
+    #[serde(default)]
+    pub bestiary: Bestiary,
+    #[serde(default)]
+    pub assaults: AssaultTracker,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    /// Saves from before seeds were laid out as seed 0 is now
+    #[serde(default)]
+    pub world_seed: u64,
+    #[serde(default)]
+    pub quests: QuestLog,
+    #[serde(default)]
+    pub world_bounds: WorldBounds,
+}
+
+fn default_player_name() -> String {
+    Player::default().name
+}
+
+impl SaveData {
+    /// Snapshot the persistent parts of a game
+    pub fn capture(game_state: &GameState, reason: SaveReason) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            reason,
+            entities: game_state.entities.clone(),
+            next_entity_id: game_state.entity_ids.next_slot(),
+            player_id: game_state.player_id,
+            time: game_state.time.clone(),
+            phase: game_state.phase.clone(),
+            clans: game_state.clans.clone(),
+            phase_objectives: game_state.phase_objectives.clone(),
+            completed_objectives: game_state.completed_objectives.clone(),
+            objective_completion_days: game_state.objective_completion_days.clone(),
+            game_time: game_state.game_time,
+            kills: game_state.kills,
+            feeding_count: game_state.feeding_count,
+            tribute_stockpile: game_state.tribute_stockpile.clone(),
+            last_tribute_day: game_state.last_tribute_day,
+            weakness_rules: game_state.weakness_rules,
+            player_name: game_state.player_name.clone(),
+            territories: game_state.territories.clone(),
+            fog_of_war: game_state.fog_of_war.clone(),
+            weather: game_state.time.weather(),
+            spawn_director: game_state.spawn_director.clone(),
+            score_ledger: Some(game_state.score_ledger.clone()),
+            lore_codex: game_state.lore_codex.clone(),
+            skill_points: 0,
+            clan_roster: game_state.clan_roster.clone(),
+            objective_hints: game_state.objective_hints.clone(),
+            clan_relations: game_state.clan_relations.clone(),
+            bestiary: game_state.bestiary.clone(),
+            assaults: game_state.assaults.clone(),
+            difficulty: game_state.difficulty,
+            world_seed: game_state.world_seed,
+            quests: game_state.quests.clone(),
+            world_bounds: game_state.world_bounds,
+        }
+    }
+
+    /// Put the saved game back into a game state.
+    /// The sky and UI state are left as they are, and the ground is only
+    /// regrown when the save comes from a different seed.
+    pub fn restore(self, game_state: &mut GameState) {
+        game_state.entity_ids = EntityAllocator::from_ids(
+            self.entities.iter().map(|entity| entity.id),
+            self.next_entity_id,
+        );
+        game_state.entities = self.entities;
+        game_state.player_id = self.player_id;
+        game_state.time = self.time;
+        game_state.phase = self.phase;
+        game_state.clans = self.clans;
+        game_state.phase_objectives = self.phase_objectives;
+        game_state.completed_objectives = self.completed_objectives;
+        game_state.objective_completion_days = self.objective_completion_days;
+        game_state.game_time = self.game_time;
+        game_state.kills = self.kills;
+        game_state.feeding_count = self.feeding_count;
+        game_state.tribute_stockpile = self.tribute_stockpile;
+        game_state.last_tribute_day = self.last_tribute_day;
+        game_state.weakness_rules = self.weakness_rules;
+        game_state.player_name = self.player_name;
+        game_state.territories = self.territories;
+        game_state.fog_of_war = self.fog_of_war;
+        game_state.time.set_weather(self.weather);
+        game_state.spawn_director = self.spawn_director;
+        // Saves from before score exports start a fresh chain from today
+        game_state.score_ledger = self.score_ledger.unwrap_or_else(|| {
+            ScoreLedger::new(ScoreClaims {
+                start_mode: game_state.start_mode,
+                difficulty: self.difficulty,
+                weaknesses: game_state.weakness_rules,
+                max_hostiles: game_state.spawn_director.max_hostiles,
+            })
+        });
+        game_state.lore_codex = self.lore_codex;
+        game_state.clan_roster = self.clan_roster;
+        game_state.objective_hints = self.objective_hints;
+        game_state.clan_relations = self.clan_relations;
+        game_state.bestiary = self.bestiary;
+        game_state.assaults = self.assaults;
+        game_state.difficulty = self.difficulty;
+        game_state.quests = self.quests;
+        game_state.world_bounds = self.world_bounds;
+        // The ground is not saved; grow it again from the save's seed
+        if game_state.world_seed != self.world_seed {
+            game_state.world_seed = self.world_seed;
+            WorldSystem::initialize_ground_terrain(&mut game_state.ground_tiles, self.world_seed);
+            game_state.terrain_chunks = TerrainChunks::from_tiles(&game_state.ground_tiles);
+        }
+
+        // Bodies in the save are already at rest
+        for entity in &mut game_state.entities {
+            // Saves from before collision have no colliders
+            if entity.collider.is_none() {
+                entity.collider = match &entity.shelter {
+                    Some(shelter) => Collider::for_shelter(&shelter.shelter_type),
+                    None => Collider::for_creature(&entity.entity_type),
+                };
+            }
+            // Nor do their infected hunt
+            if entity.hunger.is_none() && entity.entity_type == EntityType::HostileInfected {
+                entity.hunger = Some(Hunger::new(0.0, Hunger::INFECTED_RATE));
+            }
+            if matches!(entity.ai_state, AIState::Dead) {
+                let style = DeathStyle::for_entity(entity);
+                entity.visual_state.death = Some(DeathAnimation::finished(style));
+            }
+        }
+
+        // Saves from before the skill tree keep their points on a fresh one
+        if let Some(player) = game_state
+            .entities
+            .iter_mut()
+            .find(|entity| entity.id == game_state.player_id)
+        {
+            player
+                .progression
+                .get_or_insert_with(Progression::default)
+                .skill_points += self.skill_points;
+        }
+
+        game_state.damage_events.clear();
+        game_state.particles.clear();
+        game_state.barks = Barks::default();
+        game_state.decay = Decay::default();
+        game_state.calendar = Calendar::default();
+        LifecycleSystem::release_references(&mut game_state.entities, &game_state.entity_ids);
+        game_state.previous_positions.clear();
+        game_state.active_dialogue = None;
+        game_state.cutscene = None;
+        game_state.spatial_grid.rebuild(&game_state.entities);
+        game_state.milestone_tracker =
+            MilestoneTracker::new(&game_state.phase, &game_state.clans, &game_state.entities);
+    }
+}
+
+/// Writes and reads save files and decides when timed autosaves are due
+#[derive(Debug, Clone)]
+pub struct SaveManager {
+    directory: PathBuf,
+    pub autosave_interval: f32,
+    pub milestone_slots: usize,
+    pub backups_per_slot: usize,
+    time_since_autosave: f32,
+}
+
+impl SaveManager {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            autosave_interval: AUTOSAVE_INTERVAL,
+            milestone_slots: MILESTONE_SLOTS,
+            backups_per_slot: BACKUPS_PER_SLOT,
+            time_since_autosave: 0.0,
+        }
+    }
+
+    /// Advance the autosave timer; returns true when a timed autosave is due
+    pub fn tick(&mut self, delta_time: f32) -> bool {
+        self.time_since_autosave += delta_time;
+        if self.time_since_autosave >= self.autosave_interval {
+            self.time_since_autosave = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn autosave_path(&self) -> PathBuf {
+        self.directory.join("autosave.json")
+    }
+
+    /// Where the signed score of the last finished run is exported
+    pub fn leaderboard_path(&self) -> PathBuf {
+        self.directory.join("leaderboard.json")
+    }
+
+    /// Lifetime stats and achievements, shared by every run
+    pub fn achievements_path(&self) -> PathBuf {
+        self.directory.join("achievements.json")
+    }
+
+    pub fn milestone_path(&self, slot: usize) -> PathBuf {
+        self.directory.join(format!("milestone_{}.json", slot))
+    }
+
+    /// Older copy `generation` of a save file; 1 is the most recent
+    pub fn backup_path(path: &Path, generation: usize) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".bak{}", generation));
+        PathBuf::from(name)
+    }
+
+    /// Write a save to the slot its reason belongs in, returning the file written.
+    /// Milestone saves fill empty slots first, then replace the oldest.
+    pub fn write(&self, data: &SaveData) -> Result<PathBuf, SaveError> {
+        let path = match data.reason {
+            SaveReason::Timed => self.autosave_path(),
+            SaveReason::Milestone(_) => self.milestone_path(self.next_milestone_slot()),
+        };
+
+        fs::create_dir_all(&self.directory)?;
+        let save = RawValue::from_string(serde_json::to_string_pretty(data)?)?;
+        let file = SaveFile {
+            checksum: checksum(save.get().as_bytes()),
+            save: &save,
+        };
+        // Write to a temporary file first so a crash never leaves a half-written save
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec(&file)?)?;
+        self.rotate_backups(&path)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(path)
+    }
+
+    /// Shift a slot's backups down one generation and keep its current save
+    /// as the newest. A damaged save is not worth keeping, so it is left to
+    /// be overwritten and the good backups stay.
+    fn rotate_backups(&self, path: &Path) -> Result<(), SaveError> {
+        if self.backups_per_slot == 0 || Self::load(path).is_err() {
+            return Ok(());
+        }
+        for generation in (1..self.backups_per_slot).rev() {
+            let older = Self::backup_path(path, generation);
+            if older.is_file() {
+                fs::rename(&older, Self::backup_path(path, generation + 1))?;
+            }
+        }
+        fs::copy(path, Self::backup_path(path, 1))?;
+        Ok(())
+    }
+
+    /// Read a save file, checking it against its checksum
+    pub fn load(path: &Path) -> Result<SaveData, SaveError> {
+        let bytes = fs::read(path)?;
+        let data: SaveData = match serde_json::from_slice::<SaveFile>(&bytes) {
+            Ok(file) => {
+                if checksum(file.save.get().as_bytes()) != file.checksum {
+                    return Err(SaveError::Checksum);
+                }
+                serde_json::from_str(file.save.get())?
+            }
+            // Saves from before checksums are the bare save
+            Err(_) => serde_json::from_slice(&bytes)?,
+        };
+        if data.version != SAVE_VERSION {
+            return Err(SaveError::Version(data.version));
+        }
+        Ok(data)
+    }
+
+    /// The newest backup of a save file that still loads
+    pub fn newest_backup(&self, path: &Path) -> Option<(PathBuf, SaveData)> {
+        (1..=self.backups_per_slot)
+            .map(|generation| Self::backup_path(path, generation))
+            .find_map(|backup| Self::load(&backup).ok().map(|data| (backup, data)))
+    }
+
+    /// Slots holding a save that exists but cannot be read
+    pub fn damaged_saves(&self) -> Vec<DamagedSave> {
+        std::iter::once(self.autosave_path())
+            .chain((0..self.milestone_slots).map(|slot| self.milestone_path(slot)))
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                let error = Self::load(&path).err()?;
+                Some(DamagedSave {
+                    backup: self.newest_backup(&path),
+                    error: error.to_string(),
+                    path,
+                })
+            })
+            .collect()
+    }
+
+    /// Put a damaged slot's backup back in its place
+    pub fn restore_backup(&self, damaged: &DamagedSave) -> Result<SaveData, SaveError> {
+        let (backup, _) = damaged.backup.as_ref().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no backup to restore")
+        })?;
+        let temp_path = damaged.path.with_extension("json.tmp");
+        fs::copy(backup, &temp_path)?;
+        fs::rename(&temp_path, &damaged.path)?;
+        Self::load(&damaged.path)
+    }
+
+    /// All readable milestone saves, newest first
+    pub fn milestone_saves(&self) -> Vec<SaveData> {
+        let mut saves: Vec<SaveData> = (0..self.milestone_slots)
+            .filter_map(|slot| Self::load(&self.milestone_path(slot)).ok())
+            .collect();
+        saves.sort_by_key(|save| std::cmp::Reverse(save.saved_at));
+        saves
+    }
+
+    /// The most recent milestone save, if any
+    pub fn latest_milestone(&self) -> Option<SaveData> {
+        self.milestone_saves().into_iter().next()
+    }
+
+    /// The newest readable save of any kind, for continuing a game
+    pub fn latest_save(&self) -> Option<SaveData> {
+        let autosave = Self::load(&self.autosave_path()).ok();
+        self.milestone_saves()
+            .into_iter()
+            .chain(autosave)
+            .max_by_key(|save| save.saved_at)
+    }
+
+    fn next_milestone_slot(&self) -> usize {
+        let mut oldest: Option<(usize, u64)> = None;
+        for slot in 0..self.milestone_slots {
+            match Self::load(&self.milestone_path(slot)) {
+                Ok(save) => {
+                    if oldest.is_none_or(|(_, saved_at)| save.saved_at < saved_at) {
+                        oldest = Some((slot, save.saved_at));
+                    }
+                }
+                // Missing or unreadable slots are reused first
+                Err(_) => return slot,
+            }
+        }
+        oldest.map_or(0, |(slot, _)| slot)
+    }
+}
+
+impl Default for SaveManager {
+    fn default() -> Self {
+        Self::new("saves")
+    }
+}
+
+/// Remembers what has already been achieved so new milestones can be spotted
+#[derive(Debug, Clone)]
+pub struct MilestoneTracker {
+    phase: GamePhase,
+    allied_clans: HashSet<String>,
+    defeated_leaders: HashSet<String>,
+}
+
+impl MilestoneTracker {
+    pub fn new(phase: &GamePhase, clans: &HashMap<String, Clan>, entities: &[GameEntity]) -> Self {
+        Self {
+            phase: phase.clone(),
+            allied_clans: Self::allied(clans),
+            defeated_leaders: Self::defeated(entities),
+        }
+    }
+
+    /// Compare against the current game and return any milestones reached since the last call
+    pub fn observe(
+        &mut self,
+        phase: &GamePhase,
+        clans: &HashMap<String, Clan>,
+        entities: &[GameEntity],
+    ) -> Vec<Milestone> {
+        let mut milestones = Vec::new();
+
+        if *phase != self.phase {
+            self.phase = phase.clone();
+            milestones.push(Milestone::PhaseAdvanced(phase.clone()));
+        }
+
+        let allied = Self::allied(clans);
+        let mut newly_allied: Vec<_> = allied.difference(&self.allied_clans).cloned().collect();
+        newly_allied.sort();
+        milestones.extend(newly_allied.into_iter().map(Milestone::ClanAllied));
+        self.allied_clans = allied;
+
+        let defeated = Self::defeated(entities);
+        let mut newly_defeated: Vec<_> = defeated
+            .difference(&self.defeated_leaders)
+            .cloned()
+            .collect();
+        newly_defeated.sort();
+        milestones.extend(newly_defeated.into_iter().map(Milestone::LeaderDefeated));
+        self.defeated_leaders.extend(defeated);
+
+        milestones
+    }
+
+    fn allied(clans: &HashMap<String, Clan>) -> HashSet<String> {
+        clans
+            .values()
+            .filter(|clan| clan.is_allied)
+            .map(|clan| clan.name.clone())
+            .collect()
+    }
+
+    fn defeated(entities: &[GameEntity]) -> HashSet<String> {
+        entities
+            .iter()
+            .filter_map(|entity| match &entity.entity_type {
+                EntityType::ClanLeader(clan) if matches!(entity.ai_state, AIState::Dead) => {
+                    Some(clan.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_save_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vampire_rpg_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_tracker_reports_each_milestone_once() {
+        let game_state = GameState::new();
+        let mut tracker =
+            MilestoneTracker::new(&game_state.phase, &game_state.clans, &game_state.entities);
+        let mut clans = game_state.clans.clone();
+        clans.get_mut("Night-Bloods").unwrap().is_allied = true;
+
+        let milestones = tracker.observe(&GamePhase::ClanEncounters, &clans, &game_state.entities);
+        assert_eq!(
+            milestones,
+            vec![
+                Milestone::PhaseAdvanced(GamePhase::ClanEncounters),
+                Milestone::ClanAllied("Night-Bloods".to_string()),
+            ]
+        );
+        assert!(tracker
+            .observe(&GamePhase::ClanEncounters, &clans, &game_state.entities)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_milestone_slots_rotate_and_restore() {
+        let dir = temp_save_dir("rotation");
+        let mut manager = SaveManager::new(&dir);
+        manager.milestone_slots = 2;
+        let mut game_state = GameState::new();
+
+        for (i, clan) in ["Bone-Eaters", "Flame-Haters", "Night-Bloods"]
+            .iter()
+            .enumerate()
+        {
+            let mut data = SaveData::capture(
+                &game_state,
+                SaveReason::Milestone(Milestone::ClanAllied(clan.to_string())),
+            );
+            data.saved_at = i as u64;
+            data.kills = i as u32;
+            let path = manager.write(&data).unwrap();
+            // The third save replaces the oldest slot
+            assert_eq!(path, manager.milestone_path(i % 2));
+        }
+
+        let latest = manager.latest_milestone().unwrap();
+        assert_eq!(latest.kills, 2);
+        assert_eq!(manager.milestone_saves().len(), 2);
+
+        latest.restore(&mut game_state);
+        assert_eq!(game_state.kills, 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_damaged_save_detected_and_restored_from_backup() {
+        let dir = temp_save_dir("backups");
+        let mut manager = SaveManager::new(&dir);
+        manager.backups_per_slot = 2;
+        let mut data = SaveData::capture(&GameState::new(), SaveReason::Timed);
+        for kills in 1..=4 {
+            data.kills = kills;
+            manager.write(&data).unwrap();
+        }
+        let path = manager.autosave_path();
+        assert_eq!(SaveManager::load(&path).unwrap().kills, 4);
+        let backup = SaveManager::backup_path(&path, 1);
+        assert_eq!(SaveManager::load(&backup).unwrap().kills, 3);
+        assert_eq!(
+            SaveManager::load(&SaveManager::backup_path(&path, 2))
+                .unwrap()
+                .kills,
+            2
+        );
+        assert!(!SaveManager::backup_path(&path, 3).exists());
+        assert!(manager.damaged_saves().is_empty());
+
+        // Flip a digit inside the save so it still parses but no longer matches
+        let text = fs::read_to_string(&path).unwrap();
+        fs::write(&path, text.replacen("\"kills\": 4", "\"kills\": 9", 1)).unwrap();
+        assert!(matches!(SaveManager::load(&path), Err(SaveError::Checksum)));
+
+        let damaged = manager.damaged_saves();
+        assert_eq!(damaged.len(), 1);
+        assert_eq!(damaged[0].slot_name(), "autosave.json");
+        assert_eq!(damaged[0].backup.as_ref().unwrap().0, backup);
+        assert_eq!(manager.restore_backup(&damaged[0]).unwrap().kills, 3);
+        assert!(manager.damaged_saves().is_empty());
+
+        // Saves from before checksums still load
+        fs::write(&path, serde_json::to_vec(&data).unwrap()).unwrap();
+        assert_eq!(SaveManager::load(&path).unwrap().kills, 4);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
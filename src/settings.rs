@@ -0,0 +1,724 @@
+//! Settings Module
+//!
+//! Video settings and graphics presets. The low-spec preset strips the world
+//! down to flat tiles and a sparse sky so the game stays playable on older
+//! laptops, and the FPS monitor suggests it when frame rate stays low. Frame
+//! pacing (vsync and an optional frame cap) is kept on disk, since vsync has
+//! to be known before the window opens. Also holds the choices made on the
+//! new game screen, including the difficulty and the multipliers it applies
+//! and the world seed, and the fixed timestep that keeps the simulation independent of frame rate.
+
+use crate::components::Player;
+use crate::locale::Language;
+use crate::systems::{SpawnDirector, StartMode};
+use crate::theme::PaletteTheme;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Where frame pacing choices are kept
+pub const FRAME_PACING_PATH: &str = "frame_pacing.json";
+
+/// Errors that can occur while reading or writing frame pacing
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("could not access settings file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("settings file is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// Graphics quality presets selectable in the video settings menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphicsPreset {
+    #[default]
+    Standard,
+    LowSpec,
+}
+
+impl GraphicsPreset {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            GraphicsPreset::Standard => "Standard",
+            GraphicsPreset::LowSpec => "Low-Spec",
+        }
+    }
+
+    /// Cycle to the other preset
+    pub fn toggled(&self) -> Self {
+        match self {
+            GraphicsPreset::Standard => GraphicsPreset::LowSpec,
+            GraphicsPreset::LowSpec => GraphicsPreset::Standard,
+        }
+    }
+}
+
+/// Rendering options derived from a graphics preset
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoSettings {
+    pub preset: GraphicsPreset,
+    /// Spawn and draw blood particles
+    pub particles: bool,
+    /// Draw textured ground tiles instead of flat colors
+    pub detailed_tiles: bool,
+    /// Fraction of stars drawn (0.0 to 1.0)
+    pub star_density: f32,
+    /// Glows, halos, and status tint overlays
+    pub post_processing: bool,
+    /// Entities further than this from the camera are not drawn, and
+    /// creatures further than this from the player are not simulated
+    pub active_entity_radius: Option<f32>,
+    /// Color palette for sprites and terrain; kept when the preset changes
+    pub theme: PaletteTheme,
+    /// Camera leads the player in the direction they move; kept when the
+    /// preset changes
+    pub camera_look_ahead: bool,
+    /// Draw entities from the sprite atlas instead of procedural pixel art;
+    /// kept when the preset changes
+    pub textured_sprites: bool,
+    /// Shake the screen when the player is hit; kept when the preset changes
+    pub screen_shake: bool,
+    /// Vsync and frame cap; kept when the preset changes
+    pub frame_pacing: FramePacing,
+    /// Size of the HUD and menus; kept when the preset changes
+    pub ui_scale: UiScale,
+    /// Language of the HUD and menus; kept when the preset changes
+    pub language: Language,
+}
+
+impl VideoSettings {
+    /// Settings for a preset
+    pub fn from_preset(preset: GraphicsPreset) -> Self {
+        match preset {
+            GraphicsPreset::Standard => Self {
+                preset,
+                particles: true,
+                detailed_tiles: true,
+                star_density: 1.0,
+                post_processing: true,
+                active_entity_radius: None,
+                theme: PaletteTheme::default(),
+                camera_look_ahead: true,
+                textured_sprites: true,
+                screen_shake: true,
+                frame_pacing: FramePacing::default(),
+                ui_scale: UiScale::default(),
+                language: Language::default(),
+            },
+            GraphicsPreset::LowSpec => Self {
+                preset,
+                particles: false,
+                detailed_tiles: false,
+                star_density: 0.25,
+                post_processing: false,
+                active_entity_radius: Some(400.0),
+                theme: PaletteTheme::default(),
+                camera_look_ahead: true,
+                textured_sprites: true,
+                screen_shake: true,
+                frame_pacing: FramePacing::default(),
+                ui_scale: UiScale::default(),
+                language: Language::default(),
+            },
+        }
+    }
+
+    /// Switch to another preset, keeping the chosen theme, camera, sprite,
+    /// screen shake, frame pacing, UI scale and language options
+    pub fn with_preset(&self, preset: GraphicsPreset) -> Self {
+        Self {
+            theme: self.theme,
+            camera_look_ahead: self.camera_look_ahead,
+            textured_sprites: self.textured_sprites,
+            screen_shake: self.screen_shake,
+            frame_pacing: self.frame_pacing,
+            ui_scale: self.ui_scale,
+            language: self.language,
+            ..Self::from_preset(preset)
+        }
+    }
+
+    pub fn is_low_spec(&self) -> bool {
+        self.preset == GraphicsPreset::LowSpec
+    }
+
+    /// Whether the star at `index` should be drawn at the current density
+    pub fn draws_star(&self, index: usize) -> bool {
+        if self.star_density >= 1.0 {
+            return true;
+        }
+        if self.star_density <= 0.0 {
+            return false;
+        }
+        let step = (1.0 / self.star_density).round().max(1.0) as usize;
+        index.is_multiple_of(step)
+    }
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self::from_preset(GraphicsPreset::default())
+    }
+}
+
+/// Classic vampire weaknesses, each of which can be switched off for a new game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeaknessRules {
+    /// Crossing running water drains blood
+    pub running_water: bool,
+    /// Inhabited homes cannot be entered without an invitation
+    pub thresholds: bool,
+    /// Garlic fields cannot be walked into
+    pub garlic: bool,
+}
+
+impl WeaknessRules {
+    /// Number of toggleable rules, in the order used by `toggle` and `describe`
+    pub const COUNT: usize = 3;
+
+    /// Flip the rule at `index`
+    pub fn toggle(&mut self, index: usize) {
+        match index {
+            0 => self.running_water = !self.running_water,
+            1 => self.thresholds = !self.thresholds,
+            2 => self.garlic = !self.garlic,
+            _ => {}
+        }
+    }
+
+    /// Translation key of the rule at `index`'s label, and its current value
+    pub fn describe(&self, index: usize) -> Option<(&'static str, bool)> {
+        match index {
+            0 => Some(("new_game.running_water", self.running_water)),
+            1 => Some(("new_game.thresholds", self.thresholds)),
+            2 => Some(("new_game.garlic", self.garlic)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for WeaknessRules {
+    fn default() -> Self {
+        Self {
+            running_water: true,
+            thresholds: true,
+            garlic: true,
+        }
+    }
+}
+
+/// How hard a game is. Each level scales blood drain, sunlight damage and
+/// how many infected roam the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Nightmare,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Nightmare => "Nightmare",
+        }
+    }
+
+    /// The next level up, wrapping back to Easy after Nightmare
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|level| level == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The next level down, wrapping round to Nightmare before Easy
+    pub fn previous(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|level| level == self)
+            .unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Scale on how fast a vampire's blood drains
+    pub fn blood_drain_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.25,
+            Difficulty::Nightmare => 1.5,
+        }
+    }
+
+    /// Scale on the damage sunlight does
+    pub fn sunlight_damage_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+            Difficulty::Nightmare => 2.0,
+        }
+    }
+
+    /// Scale on how many infected are spawned
+    pub fn spawn_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+            Difficulty::Nightmare => 2.0,
+        }
+    }
+
+    /// A spawn count scaled for this difficulty, never below one
+    pub fn scale_spawn_count(&self, count: usize) -> usize {
+        ((count as f32 * self.spawn_multiplier()).round() as usize).max(1)
+    }
+}
+
+/// Longest name the player can give their vampire
+pub const MAX_PLAYER_NAME_LEN: usize = 24;
+
+/// Longest seed the player can type on the new game screen
+pub const MAX_SEED_LEN: usize = 20;
+
+/// A fresh world seed from the clock, kept to nine digits so it is easy to
+/// read out to a friend and type back in
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+        % 1_000_000_000
+}
+
+/// The seed for a line the player typed: a number is used as it is, and
+/// any other text is hashed, so "dracula" names a world as well as 1897
+pub fn seed_from_text(text: &str) -> u64 {
+    let text = text.trim();
+    text.parse().unwrap_or_else(|_| {
+        // FNV-1a, stable across runs and platforms unlike the std hasher
+        text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    })
+}
+
+/// The text field open on the new game screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NewGameField {
+    #[default]
+    Name,
+    Seed,
+}
+
+/// Everything chosen on the new game screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewGameSettings {
+    pub start_mode: StartMode,
+    pub weaknesses: WeaknessRules,
+    pub difficulty: Difficulty,
+    pub player_name: String,
+    /// Most hostile infected the night waves will fill the world with
+    pub max_hostiles: usize,
+    /// Seed the world is laid out from; sharing it shares the world
+    pub seed: u64,
+}
+
+impl Default for NewGameSettings {
+    fn default() -> Self {
+        Self {
+            start_mode: StartMode::default(),
+            weaknesses: WeaknessRules::default(),
+            difficulty: Difficulty::default(),
+            player_name: Player::default().name,
+            max_hostiles: SpawnDirector::DEFAULT_MAX_HOSTILES,
+            seed: random_seed(),
+        }
+    }
+}
+
+/// Highest frame rate the game runs at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FrameCap {
+    #[default]
+    Uncapped,
+    Fps30,
+    Fps60,
+    Fps120,
+    Fps144,
+}
+
+impl FrameCap {
+    pub const ALL: [FrameCap; 5] = [
+        FrameCap::Uncapped,
+        FrameCap::Fps30,
+        FrameCap::Fps60,
+        FrameCap::Fps120,
+        FrameCap::Fps144,
+    ];
+
+    pub fn fps(&self) -> Option<u32> {
+        match self {
+            FrameCap::Uncapped => None,
+            FrameCap::Fps30 => Some(30),
+            FrameCap::Fps60 => Some(60),
+            FrameCap::Fps120 => Some(120),
+            FrameCap::Fps144 => Some(144),
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        match self.fps() {
+            Some(fps) => format!("{} FPS", fps),
+            None => "Uncapped".to_string(),
+        }
+    }
+
+    /// The next cap in `ALL`, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|cap| cap == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Size of the HUD and menus. The layout is laid out for a 1280x720
+/// screen; `Auto` scales it to fit the screen, the others fix it at a
+/// multiple of that whatever the resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UiScale {
+    #[default]
+    Auto,
+    Percent75,
+    Percent100,
+    Percent125,
+    Percent150,
+    Percent200,
+}
+
+impl UiScale {
+    /// Screen the layout is laid out for
+    pub const BASE_WIDTH: f32 = 1280.0;
+    pub const BASE_HEIGHT: f32 = 720.0;
+    /// Bounds on the automatic scale, so tiny windows stay legible and huge
+    /// ones do not blow the HUD up past use
+    const AUTO_MIN: f32 = 0.5;
+    const AUTO_MAX: f32 = 3.0;
+
+    pub const ALL: [UiScale; 6] = [
+        UiScale::Auto,
+        UiScale::Percent75,
+        UiScale::Percent100,
+        UiScale::Percent125,
+        UiScale::Percent150,
+        UiScale::Percent200,
+    ];
+
+    /// The fixed multiple, or `None` when it follows the screen
+    pub fn factor(&self) -> Option<f32> {
+        match self {
+            UiScale::Auto => None,
+            UiScale::Percent75 => Some(0.75),
+            UiScale::Percent100 => Some(1.0),
+            UiScale::Percent125 => Some(1.25),
+            UiScale::Percent150 => Some(1.5),
+            UiScale::Percent200 => Some(2.0),
+        }
+    }
+
+    /// Scale for a screen of the given size. Automatic scaling fits the
+    /// base layout inside the screen without stretching it.
+    pub fn resolve(&self, screen_width: f32, screen_height: f32) -> f32 {
+        self.factor().unwrap_or_else(|| {
+            (screen_width / Self::BASE_WIDTH)
+                .min(screen_height / Self::BASE_HEIGHT)
+                .clamp(Self::AUTO_MIN, Self::AUTO_MAX)
+        })
+    }
+
+    pub fn display_name(&self) -> String {
+        match self.factor() {
+            Some(factor) => format!("{:.0}%", factor * 100.0),
+            None => "Auto".to_string(),
+        }
+    }
+
+    /// The next scale in `ALL`, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|scale| scale == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Vsync and frame cap, saved between sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FramePacing {
+    /// Wait for the display's refresh, where the driver allows it. Only read
+    /// when the window opens, so changes apply on the next launch.
+    pub vsync: bool,
+    pub frame_cap: FrameCap,
+}
+
+impl Default for FramePacing {
+    fn default() -> Self {
+        Self {
+            vsync: true,
+            frame_cap: FrameCap::Uncapped,
+        }
+    }
+}
+
+impl FramePacing {
+    pub fn load(path: &Path) -> Result<Self, SettingsError> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// The saved pacing, or the default when there is none or it is unreadable
+    pub fn load_or_default(path: &Path) -> Self {
+        Self::load(path).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SettingsError> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Holds frames to the frame cap. Sleeps for most of the wait and spins for
+/// the last moment, since sleeps can overshoot by a millisecond or more.
+/// Frame time still reaches the fixed timestep as measured, so the
+/// simulation keeps to real time under any cap.
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    frame_start: Instant,
+}
+
+impl FrameLimiter {
+    /// Time left to spin rather than sleep
+    const SPIN_MARGIN: Duration = Duration::from_micros(1500);
+
+    pub fn new() -> Self {
+        Self {
+            frame_start: Instant::now(),
+        }
+    }
+
+    /// How long the current frame still has to wait under `cap`
+    pub fn remaining(&self, cap: FrameCap, now: Instant) -> Duration {
+        let Some(fps) = cap.fps() else {
+            return Duration::ZERO;
+        };
+        let target = Duration::from_secs_f64(1.0 / f64::from(fps));
+        target.saturating_sub(now.duration_since(self.frame_start))
+    }
+
+    /// Wait out the rest of the frame, then start timing the next one
+    pub fn wait(&mut self, cap: FrameCap) {
+        let remaining = self.remaining(cap, Instant::now());
+        if !remaining.is_zero() {
+            let deadline = Instant::now() + remaining;
+            if remaining > Self::SPIN_MARGIN {
+                std::thread::sleep(remaining - Self::SPIN_MARGIN);
+            }
+            while Instant::now() < deadline {
+                std::hint::spin_loop();
+            }
+        }
+        self.frame_start = Instant::now();
+    }
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Watches frame rate and decides when to suggest the low-spec preset
+#[derive(Debug, Clone)]
+pub struct FpsMonitor {
+    /// FPS below which a sample counts as slow
+    pub threshold: f32,
+    /// Seconds of continuously slow samples before suggesting
+    pub sustain_seconds: f32,
+    slow_seconds: f32,
+    suggested: bool,
+}
+
+impl FpsMonitor {
+    pub fn new(threshold: f32, sustain_seconds: f32) -> Self {
+        Self {
+            threshold,
+            sustain_seconds,
+            slow_seconds: 0.0,
+            suggested: false,
+        }
+    }
+
+    /// Record an FPS sample covering `elapsed` seconds. Returns true once,
+    /// the first time frame rate has stayed below the threshold long enough.
+    pub fn record(&mut self, fps: f32, elapsed: f32) -> bool {
+        if fps >= self.threshold {
+            self.slow_seconds = 0.0;
+            return false;
+        }
+
+        self.slow_seconds += elapsed;
+        if !self.suggested && self.slow_seconds >= self.sustain_seconds {
+            self.suggested = true;
+            return true;
+        }
+        false
+    }
+
+    pub fn has_suggested(&self) -> bool {
+        self.suggested
+    }
+}
+
+impl Default for FpsMonitor {
+    fn default() -> Self {
+        Self::new(30.0, 5.0)
+    }
+}
+
+/// Runs the simulation in fixed steps whatever the frame rate. Frame time
+/// collects in an accumulator and is paid out a step at a time; what is
+/// left over says how far the renderer should blend from the previous
+/// simulated state toward the current one.
+#[derive(Debug, Clone, Default)]
+pub struct FixedTimestep {
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    /// Seconds simulated by one step (60 Hz)
+    pub const STEP: f32 = 1.0 / 60.0;
+    /// Most steps run in one frame; a longer stall is dropped rather than
+    /// caught up, so a slow frame cannot snowball into slower ones
+    pub const MAX_STEPS: u32 = 6;
+
+    /// Add a frame's time and return how many steps to simulate for it
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time.max(0.0);
+        let steps = ((self.accumulator / Self::STEP) as u32).min(Self::MAX_STEPS);
+        self.accumulator -= steps as f32 * Self::STEP;
+        if steps == Self::MAX_STEPS {
+            self.accumulator = self.accumulator.min(Self::STEP);
+        }
+        steps
+    }
+
+    /// How far the next step has come, from 0.0 to 1.0
+    pub fn blend(&self) -> f32 {
+        (self.accumulator / Self::STEP).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_spec_preset_strips_effects() {
+        let settings = VideoSettings::from_preset(GraphicsPreset::LowSpec);
+        assert!(!settings.particles);
+        assert!(!settings.detailed_tiles);
+        assert!(!settings.post_processing);
+        assert!(settings.active_entity_radius.is_some());
+
+        let drawn = (0..100).filter(|&i| settings.draws_star(i)).count();
+        assert_eq!(drawn, 25);
+    }
+
+    #[test]
+    fn test_frame_cap_wait_and_pacing_survive_presets() {
+        let limiter = FrameLimiter::new();
+        let start = limiter.frame_start;
+        assert_eq!(limiter.remaining(FrameCap::Uncapped, start), Duration::ZERO);
+        let remaining = limiter.remaining(FrameCap::Fps60, start + Duration::from_millis(10));
+        assert!(remaining > Duration::from_millis(6) && remaining < Duration::from_millis(7));
+        assert_eq!(
+            limiter.remaining(FrameCap::Fps60, start + Duration::from_millis(20)),
+            Duration::ZERO
+        );
+
+        let mut settings = VideoSettings::default();
+        settings.frame_pacing.frame_cap = FrameCap::Fps144;
+        settings.frame_pacing.vsync = false;
+        let low_spec = settings.with_preset(GraphicsPreset::LowSpec);
+        assert_eq!(low_spec.frame_pacing, settings.frame_pacing);
+        assert_eq!(FrameCap::Fps144.next(), FrameCap::Uncapped);
+    }
+
+    #[test]
+    fn test_ui_scale_fits_screen_unless_overridden() {
+        assert_eq!(UiScale::Auto.resolve(1280.0, 720.0), 1.0);
+        // 4K doubles the layout; an ultrawide is bound by its height
+        assert_eq!(UiScale::Auto.resolve(3840.0, 2160.0), 3.0);
+        assert_eq!(UiScale::Auto.resolve(2560.0, 1080.0), 1.5);
+        assert_eq!(UiScale::Auto.resolve(320.0, 240.0), 0.5);
+        assert_eq!(UiScale::Percent125.resolve(3840.0, 2160.0), 1.25);
+
+        let settings = VideoSettings {
+            ui_scale: UiScale::Percent150,
+            ..Default::default()
+        };
+        let low_spec = settings.with_preset(GraphicsPreset::LowSpec);
+        assert_eq!(low_spec.ui_scale, UiScale::Percent150);
+        assert_eq!(UiScale::Percent200.next(), UiScale::Auto);
+    }
+
+    #[test]
+    fn test_fps_monitor_requires_sustained_drop() {
+        let mut monitor = FpsMonitor::new(30.0, 3.0);
+
+        assert!(!monitor.record(20.0, 1.0));
+        assert!(!monitor.record(20.0, 1.0));
+        // A single good second resets the streak
+        assert!(!monitor.record(60.0, 1.0));
+        assert!(!monitor.record(20.0, 1.0));
+        assert!(!monitor.record(20.0, 1.0));
+        assert!(monitor.record(20.0, 1.0));
+
+        // Only suggests once
+        assert!(!monitor.record(20.0, 1.0));
+        assert!(monitor.has_suggested());
+    }
+
+    #[test]
+    fn test_fixed_timestep_pays_out_whole_steps_and_drops_stalls() {
+        let mut timestep = FixedTimestep::default();
+        let step = FixedTimestep::STEP;
+
+        // A fast display runs no step on some frames and blends between them
+        assert_eq!(timestep.advance(step * 0.4), 0);
+        assert!((timestep.blend() - 0.4).abs() < 1e-4);
+        assert_eq!(timestep.advance(step * 0.8), 1);
+        assert!((timestep.blend() - 0.2).abs() < 1e-4);
+
+        // A slow one runs several per frame
+        assert_eq!(timestep.advance(step * 3.0), 3);
+
+        // A long stall is capped instead of caught up
+        assert_eq!(timestep.advance(2.0), FixedTimestep::MAX_STEPS);
+        assert!(timestep.blend() <= 1.0);
+    }
+}
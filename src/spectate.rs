@@ -0,0 +1,321 @@
+//! Spectate Module
+//!
+//! Streams a run to friends watching as spectators. The host sends a
+//! snapshot of the game, in the save file format, to every connected
+//! spectator a few times a second as a line of JSON over TCP. Spectators
+//! render the latest snapshot with a free camera and cannot interact, so
+//! nothing needs to be deterministic on their side.
+//!
+//! The host never waits on a spectator: frames queue in a buffer per
+//! spectator and are written out as fast as each connection takes them. A
+//! spectator that falls too far behind is dropped.
+
+use crate::game_state::GameState;
+use crate::save::{SaveData, SaveReason, SAVE_VERSION};
+use serde::{Deserialize, Serialize};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use thiserror::Error;
+
+/// Port used when no address is given
+pub const DEFAULT_SPECTATE_PORT: u16 = 7878;
+
+/// Seconds between snapshots sent to spectators
+const SEND_INTERVAL: f32 = 0.1;
+
+/// Frames a spectator may fall behind by before it is dropped
+const MAX_BACKLOG_FRAMES: usize = 10;
+
+/// Errors that can occur while hosting or watching a stream
+#[derive(Debug, Error)]
+pub enum SpectateError {
+    #[error("network error: {0}")]
+    Io(#[from] io::Error),
+    #[error("snapshot is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("stream was made by an incompatible version ({0})")]
+    Version(u32),
+    #[error("the host ended the stream")]
+    Disconnected,
+}
+
+/// One snapshot of the host's game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorFrame {
+    /// Increases by one with every frame the host sends
+    pub sequence: u64,
+    pub snapshot: SaveData,
+}
+
+impl SpectatorFrame {
+    pub fn capture(game_state: &GameState, sequence: u64) -> Self {
+        Self {
+            sequence,
+            // Frames reuse the save format; the reason is never read
+            snapshot: SaveData::capture(game_state, SaveReason::Timed),
+        }
+    }
+
+    /// Show the snapshot in a spectator's game state, keeping the
+    /// spectator's own camera
+    pub fn apply(self, game_state: &mut GameState) {
+        let (camera_x, camera_y) = (game_state.camera_x, game_state.camera_y);
+        self.snapshot.restore(game_state);
+        game_state.camera_x = camera_x;
+        game_state.camera_y = camera_y;
+    }
+}
+
+/// A connected spectator and the bytes still to be sent to it
+struct Spectator {
+    stream: TcpStream,
+    outgoing: Vec<u8>,
+}
+
+impl Spectator {
+    /// Write as much of the outgoing buffer as the connection takes
+    /// without waiting. Returns false once the connection has failed.
+    fn flush(&mut self) -> bool {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return false,
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Accepts spectators and streams frames to them
+pub struct SpectatorHost {
+    listener: TcpListener,
+    spectators: Vec<Spectator>,
+    time_since_send: f32,
+    sequence: u64,
+}
+
+impl SpectatorHost {
+    /// Listen for spectators on the given address
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self, SpectateError> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            spectators: Vec::new(),
+            time_since_send: SEND_INTERVAL,
+            sequence: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, SpectateError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn spectator_count(&self) -> usize {
+        self.spectators.len()
+    }
+
+    /// Accept spectators waiting to connect, returning their addresses
+    pub fn accept_pending(&mut self) -> Vec<SocketAddr> {
+        let mut joined = Vec::new();
+        loop {
+            match self.listener.accept() {
+                Ok((stream, addr)) => {
+                    let configured = stream
+                        .set_nonblocking(true)
+                        .and_then(|_| stream.set_nodelay(true));
+                    if configured.is_ok() {
+                        self.spectators.push(Spectator {
+                            stream,
+                            outgoing: Vec::new(),
+                        });
+                        joined.push(addr);
+                    }
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => break,
+            }
+        }
+        joined
+    }
+
+    /// Advance the send timer; returns true when the next frame is due
+    pub fn tick(&mut self, delta_time: f32) -> bool {
+        self.time_since_send += delta_time;
+        if self.time_since_send >= SEND_INTERVAL {
+            self.time_since_send = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Queue the game for every spectator and send what their connections
+    /// will take, returning how many were dropped because their connection
+    /// failed or they fell more than `MAX_BACKLOG_FRAMES` behind
+    pub fn broadcast(&mut self, game_state: &GameState) -> Result<usize, SpectateError> {
+        if self.spectators.is_empty() {
+            return Ok(0);
+        }
+
+        let frame = SpectatorFrame::capture(game_state, self.sequence);
+        self.sequence += 1;
+        let mut line = serde_json::to_vec(&frame)?;
+        line.push(b'\n');
+
+        let before = self.spectators.len();
+        self.spectators.retain_mut(|spectator| {
+            if spectator.outgoing.len() + line.len() > line.len() * MAX_BACKLOG_FRAMES {
+                return false;
+            }
+            spectator.outgoing.extend_from_slice(&line);
+            spectator.flush()
+        });
+        Ok(before - self.spectators.len())
+    }
+}
+
+/// Connection to a host, collecting the frames it sends
+pub struct SpectatorClient {
+    stream: TcpStream,
+    pending: Vec<u8>,
+    last_sequence: Option<u64>,
+}
+
+impl SpectatorClient {
+    pub fn connect(addr: impl ToSocketAddrs) -> Result<Self, SpectateError> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nonblocking(true)?;
+        Ok(Self {
+            stream,
+            pending: Vec::new(),
+            last_sequence: None,
+        })
+    }
+
+    /// Sequence number of the last frame received
+    pub fn last_sequence(&self) -> Option<u64> {
+        self.last_sequence
+    }
+
+    /// Read everything the host has sent and return the newest complete
+    /// frame, if any arrived since the last poll
+    pub fn poll(&mut self) -> Result<Option<SpectatorFrame>, SpectateError> {
+        let mut buffer = [0u8; 16 * 1024];
+        loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => return Err(SpectateError::Disconnected),
+                Ok(read) => self.pending.extend_from_slice(&buffer[..read]),
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        // Only the newest complete line matters; older frames are skipped
+        let Some(end) = self.pending.iter().rposition(|&byte| byte == b'\n') else {
+            return Ok(None);
+        };
+        let complete: Vec<u8> = self.pending.drain(..=end).collect();
+        let newest = complete[..end]
+            .rsplit(|&byte| byte == b'\n')
+            .next()
+            .unwrap_or_default();
+
+        let frame: SpectatorFrame = serde_json::from_slice(newest)?;
+        if frame.snapshot.version != SAVE_VERSION {
+            return Err(SpectateError::Version(frame.snapshot.version));
+        }
+        self.last_sequence = Some(frame.sequence);
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_spectator_receives_latest_frame() {
+        let mut host = SpectatorHost::bind("127.0.0.1:0").unwrap();
+        let mut client = SpectatorClient::connect(host.local_addr().unwrap()).unwrap();
+
+        for _ in 0..100 {
+            if !host.accept_pending().is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(host.spectator_count(), 1);
+
+        let mut game_state = GameState::new();
+        game_state.kills = 3;
+        assert_eq!(host.broadcast(&game_state).unwrap(), 0);
+        game_state.kills = 7;
+        host.broadcast(&game_state).unwrap();
+
+        let mut frame = None;
+        for _ in 0..100 {
+            if let Some(received) = client.poll().unwrap() {
+                frame = Some(received);
+                if client.last_sequence() == Some(1) {
+                    break;
+                }
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        let frame = frame.expect("no frame received");
+        assert_eq!(frame.sequence, 1);
+
+        // The spectator sees the host's game but keeps its own camera
+        let mut spectator = GameState::new();
+        spectator.camera_x = -500.0;
+        frame.apply(&mut spectator);
+        assert_eq!(spectator.kills, 7);
+        assert_eq!(spectator.camera_x, -500.0);
+
+        drop(host);
+        let mut ended = false;
+        for _ in 0..100 {
+            if matches!(client.poll(), Err(SpectateError::Disconnected)) {
+                ended = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert!(ended);
+    }
+
+    #[test]
+    fn test_spectator_that_stops_reading_is_dropped() {
+        let mut host = SpectatorHost::bind("127.0.0.1:0").unwrap();
+        let _stalled = TcpStream::connect(host.local_addr().unwrap()).unwrap();
+        for _ in 0..100 {
+            if !host.accept_pending().is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(host.spectator_count(), 1);
+
+        // Frames pile up behind the full connection without the host
+        // waiting on it, until the spectator is let go
+        let game_state = GameState::new();
+        let mut dropped = 0;
+        for _ in 0..1000 {
+            dropped += host.broadcast(&game_state).unwrap();
+            if host.spectator_count() == 0 {
+                break;
+            }
+        }
+        assert_eq!(dropped, 1);
+        assert_eq!(host.spectator_count(), 0);
+    }
+}
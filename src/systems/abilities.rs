@@ -0,0 +1,836 @@
+//! Abilities System Module
+//!
+//! Activates the player's special vampire abilities. Each ability costs blood
+//! from the `BloodMeter` and goes on cooldown: Shadow Dash blinks the player a
+//! short distance, Blood Sense pulses to reveal nearby prey and enemies, Bat
+//! Form grants a burst of speed with a smaller hitbox, and Spectral Vision
+//! (unlocked by a developed blood sense) shows every creature nearby as a
+//! silhouette through shelters and terrain. Crow Scout sends out a crow for
+//! the player to pilot (see `ScoutSystem`). Blood Sight is toggled rather
+//! than timed: while it is on, scent trails lead to living prey within the
+//! blood sense range, and it drains a little blood every second. Blood
+//! Lance hurls a projectile toward the cursor (see `ProjectileSystem`).
+//! Every ability past Shadow Dash and Blood Sense must first be learned in
+//! the skill tree (see `Progression`).
+//!
+//! Hotbar tooltips are built here too, from the same numbers the abilities
+//! use, so a preview never disagrees with what activating actually does.
+
+use crate::components::*;
+use crate::systems::projectiles::{LANCE_DAMAGE, LANCE_RANGE};
+use crate::systems::query::{QueryShape, WorldQuery};
+use crate::systems::scout::CROW_SIGHT;
+use crate::systems::spatial::SpatialGrid;
+
+/// Base Shadow Dash distance before shadow movement bonuses
+const DASH_DISTANCE: f32 = 120.0;
+
+/// Extra dash distance per point of shadow movement
+const DASH_DISTANCE_PER_SHADOW: f32 = 60.0;
+
+/// How far Spectral Vision reaches
+const SPECTRAL_RANGE: f32 = 600.0;
+
+/// Abilities system responsible for special vampire abilities
+pub struct AbilitySystem;
+
+impl AbilitySystem {
+    /// Count down cooldowns and active effects for every vampire, and
+    /// drain blood for Blood Sight, which closes once the blood runs out
+    pub fn update(entities: &mut [GameEntity], delta_time: f32) {
+        for entity in entities.iter_mut() {
+            if let Some(abilities) = &mut entity.vampire_abilities {
+                let state = &mut abilities.ability_state;
+                state.update(delta_time);
+                if state.is_blood_sight() {
+                    let upkeep = SpecialAbility::BloodSight.upkeep() * delta_time;
+                    state.blood_sight = entity
+                        .blood_meter
+                        .as_mut()
+                        .is_some_and(|blood| blood.consume(upkeep));
+                }
+            }
+        }
+    }
+
+    /// Try to activate an ability for the player.
+    /// `direction` aims Shadow Dash (the movement input) and Blood Lance
+    /// (toward the cursor).
+    pub fn activate(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        bounds: &WorldBounds,
+        ability: SpecialAbility,
+        direction: (f32, f32),
+    ) -> AbilityResult {
+        let Some(player) = entities.iter_mut().find(|e| e.id == player_id) else {
+            return AbilityResult::Unavailable { ability };
+        };
+
+        if player
+            .shelter_occupancy
+            .as_ref()
+            .is_some_and(|occupancy| occupancy.is_in_shelter())
+        {
+            return AbilityResult::Unavailable { ability };
+        }
+
+        let (Some(abilities), Some(blood_meter)) =
+            (&mut player.vampire_abilities, &mut player.blood_meter)
+        else {
+            return AbilityResult::Unavailable { ability };
+        };
+
+        // Toggling Blood Sight off is free and skips the cooldown
+        if ability == SpecialAbility::BloodSight && abilities.ability_state.is_blood_sight() {
+            abilities.ability_state.blood_sight = false;
+            return AbilityResult::Ended { ability };
+        }
+
+        if !abilities.has_unlocked(ability) {
+            return AbilityResult::Locked {
+                ability,
+                required: ability.required_blood_sense(),
+            };
+        }
+
+        if let Some(skill) = Skill::unlocking(ability).filter(|_| {
+            player
+                .progression
+                .as_ref()
+                .is_some_and(|progression| !progression.knows(ability))
+        }) {
+            return AbilityResult::Unlearned { ability, skill };
+        }
+
+        if !abilities.ability_state.is_ready(ability) {
+            return AbilityResult::OnCooldown {
+                ability,
+                remaining: abilities.ability_state.cooldown_remaining(ability),
+            };
+        }
+
+        let dash_direction = Self::normalize(direction);
+        if matches!(
+            ability,
+            SpecialAbility::ShadowDash | SpecialAbility::BloodLance
+        ) && dash_direction.is_none()
+        {
+            return AbilityResult::NeedsDirection { ability };
+        }
+
+        if !blood_meter.consume(ability.blood_cost()) {
+            return AbilityResult::NotEnoughBlood {
+                ability,
+                needed: ability.blood_cost(),
+            };
+        }
+
+        abilities.ability_state.trigger(ability);
+
+        if let Some((dx, dy)) = dash_direction.filter(|_| ability == SpecialAbility::ShadowDash) {
+            let distance = (DASH_DISTANCE + abilities.shadow_movement * DASH_DISTANCE_PER_SHADOW)
+                * abilities.ability_state.power();
+            player.position = bounds.clamp(Position::new(
+                player.position.x + dx * distance,
+                player.position.y + dy * distance,
+            ));
+        }
+
+        AbilityResult::Activated { ability }
+    }
+
+    /// Entities revealed by an active Blood Sense pulse, with their positions
+    pub fn sensed_entities(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+    ) -> Vec<(u32, Position)> {
+        let Some(player) = EntityFinder::by_id(entities, player_id) else {
+            return Vec::new();
+        };
+        let Some(abilities) = player
+            .vampire_abilities
+            .as_ref()
+            .filter(|abilities| abilities.ability_state.is_sensing())
+        else {
+            return Vec::new();
+        };
+
+        // The pulse reaches twice as far as passive blood sense
+        let range = abilities.blood_sense_range() * 2.0;
+        let area = QueryShape::circle(player.position, range);
+        WorldQuery::query_area(entities, grid, &[], area)
+            .entities
+            .into_iter()
+            .map(|index| &entities[index])
+            .filter(|entity| {
+                matches!(
+                    entity.entity_type,
+                    EntityType::Animal | EntityType::HostileInfected
+                ) && !matches!(entity.ai_state, AIState::Dead)
+            })
+            .map(|entity| (entity.id, entity.position))
+            .collect()
+    }
+
+    /// Scent trails Blood Sight leads to: living prey within the blood
+    /// sense range, strongest for the nearest
+    pub fn scent_trails(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+    ) -> Vec<ScentTrail> {
+        let Some(player) = EntityFinder::by_id(entities, player_id) else {
+            return Vec::new();
+        };
+        let Some(abilities) = player
+            .vampire_abilities
+            .as_ref()
+            .filter(|abilities| abilities.ability_state.is_blood_sight())
+        else {
+            return Vec::new();
+        };
+
+        let range = abilities.blood_sense_range();
+        let area = QueryShape::circle(player.position, range);
+        WorldQuery::query_area(entities, grid, &[], area)
+            .entities
+            .into_iter()
+            .map(|index| &entities[index])
+            .filter(|entity| {
+                matches!(
+                    entity.entity_type,
+                    EntityType::Animal | EntityType::ClanMember(_) | EntityType::ClanLeader(_)
+                ) && !matches!(entity.ai_state, AIState::Dead)
+                    && entity.health.as_ref().is_none_or(|h| h.current > 0.0)
+            })
+            .map(|entity| ScentTrail {
+                id: entity.id,
+                position: entity.position,
+                strength: 1.0 - player.position.distance_to(&entity.position) / range,
+            })
+            .collect()
+    }
+
+    /// Creatures shown by active Spectral Vision, with their positions and
+    /// whether they are a source of blood (living prey or an undrained corpse)
+    pub fn spectral_entities(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+    ) -> Vec<SpectralTarget> {
+        let Some(player) = EntityFinder::by_id(entities, player_id) else {
+            return Vec::new();
+        };
+        if !player
+            .vampire_abilities
+            .as_ref()
+            .is_some_and(|abilities| abilities.ability_state.is_spectral())
+        {
+            return Vec::new();
+        }
+
+        let area = QueryShape::circle(player.position, SPECTRAL_RANGE);
+        WorldQuery::query_area(entities, grid, &[], area)
+            .entities
+            .into_iter()
+            .map(|index| &entities[index])
+            .filter(|entity| entity.id != player_id)
+            .filter_map(|entity| {
+                let alive = !matches!(entity.ai_state, AIState::Dead)
+                    && entity.health.as_ref().is_none_or(|h| h.current > 0.0);
+                let has_blood = entity.corpse.as_ref().is_some_and(|c| !c.is_dry());
+                if !alive && !has_blood {
+                    return None;
+                }
+                Some(SpectralTarget {
+                    id: entity.id,
+                    position: entity.position,
+                    entity_type: entity.entity_type.clone(),
+                    blood_source: has_blood
+                        || (alive && !matches!(entity.entity_type, EntityType::HostileInfected)),
+                })
+            })
+            .collect()
+    }
+
+    /// What an ability does at the vampire's current stats
+    pub fn effects(ability: SpecialAbility, abilities: &VampireAbilities) -> Vec<AbilityEffect> {
+        let power = abilities.ability_state.power();
+        match ability {
+            SpecialAbility::ShadowDash => vec![AbilityEffect::Blink {
+                distance: (DASH_DISTANCE + abilities.shadow_movement * DASH_DISTANCE_PER_SHADOW)
+                    * power,
+            }],
+            SpecialAbility::BloodSensePulse => vec![AbilityEffect::RevealLiving {
+                range: abilities.blood_sense_range() * 2.0,
+            }],
+            SpecialAbility::BatForm => vec![
+                AbilityEffect::Speed {
+                    multiplier: AbilityState::BAT_FORM_SPEED,
+                },
+                AbilityEffect::Hitbox {
+                    scale: AbilityState::BAT_FORM_HITBOX,
+                },
+            ],
+            SpecialAbility::SpectralVision => vec![AbilityEffect::SeeThroughWalls {
+                range: SPECTRAL_RANGE,
+            }],
+            SpecialAbility::CrowScout => vec![AbilityEffect::Scout { sight: CROW_SIGHT }],
+            SpecialAbility::BloodSight => vec![AbilityEffect::ScentTrails {
+                range: abilities.blood_sense_range(),
+            }],
+            SpecialAbility::BloodLance => vec![AbilityEffect::Lance {
+                damage: LANCE_DAMAGE * abilities.strength * power,
+                range: LANCE_RANGE,
+            }],
+        }
+    }
+
+    /// How the vampire's stats change an ability from its base values
+    pub fn modifiers(ability: SpecialAbility, abilities: &VampireAbilities) -> Vec<String> {
+        let mut modifiers = Vec::new();
+        match ability {
+            SpecialAbility::ShadowDash if abilities.shadow_movement > 0.0 => {
+                modifiers.push(format!(
+                    "Shadow movement {:.1}: +{:.0} distance",
+                    abilities.shadow_movement,
+                    abilities.shadow_movement * DASH_DISTANCE_PER_SHADOW
+                ));
+            }
+            SpecialAbility::BloodSensePulse if abilities.blood_sense > 0.0 => {
+                modifiers.push(format!(
+                    "Blood sense {:.1}: +{:.0} range",
+                    abilities.blood_sense,
+                    abilities.blood_sense * 200.0
+                ));
+            }
+            SpecialAbility::BloodSight if abilities.blood_sense > 0.0 => {
+                modifiers.push(format!(
+                    "Blood sense {:.1}: +{:.0} range",
+                    abilities.blood_sense,
+                    abilities.blood_sense * 100.0
+                ));
+            }
+            _ => {}
+        }
+        if abilities.ability_state.empowered {
+            modifiers.push(format!(
+                "Blood moon: {:.1}x power, recovers {:.1}x faster",
+                AbilityState::EMPOWERED_POWER,
+                AbilityState::EMPOWERED_POWER
+            ));
+        }
+        if !abilities.has_unlocked(ability) {
+            modifiers.push(format!(
+                "Locked until blood sense {:.1} (now {:.1})",
+                ability.required_blood_sense(),
+                abilities.blood_sense
+            ));
+        }
+        modifiers
+    }
+
+    /// Everything the hotbar tooltip shows for an ability
+    pub fn tooltip(
+        ability: SpecialAbility,
+        abilities: &VampireAbilities,
+        blood: Option<&BloodMeter>,
+    ) -> AbilityTooltip {
+        let current_blood = blood.map_or(0.0, |blood| blood.current);
+        AbilityTooltip {
+            ability,
+            blood_cost: ability.blood_cost(),
+            blood_after: current_blood - ability.blood_cost(),
+            cooldown: abilities.ability_state.cooldown(ability),
+            cooldown_remaining: abilities.ability_state.cooldown_remaining(ability),
+            duration: abilities.ability_state.duration(ability),
+            upkeep: ability.upkeep(),
+            effects: Self::effects(ability, abilities),
+            modifiers: Self::modifiers(ability, abilities),
+            unlocked: abilities.has_unlocked(ability),
+        }
+    }
+
+    fn normalize((x, y): (f32, f32)) -> Option<(f32, f32)> {
+        let length = (x * x + y * y).sqrt();
+        if length > 0.01 {
+            Some((x / length, y / length))
+        } else {
+            None
+        }
+    }
+}
+
+/// One thing an ability does, with its strength at the vampire's stats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AbilityEffect {
+    /// Reappear this far away in the held direction
+    Blink { distance: f32 },
+    /// Show prey and infected within this range
+    RevealLiving { range: f32 },
+    /// Move this many times faster
+    Speed { multiplier: f32 },
+    /// Hitbox scaled to this fraction of its size
+    Hitbox { scale: f32 },
+    /// See creatures through shelters and terrain within this range
+    SeeThroughWalls { range: f32 },
+    /// Pilot a crow that sees this far
+    Scout { sight: f32 },
+    /// Trails lead to living prey within this range
+    ScentTrails { range: f32 },
+    /// A projectile dealing this much damage to the first enemy it hits
+    Lance { damage: f32, range: f32 },
+}
+
+impl AbilityEffect {
+    pub fn describe(&self) -> String {
+        match self {
+            AbilityEffect::Blink { distance } => {
+                format!("Blink {:.0} units in the held direction", distance)
+            }
+            AbilityEffect::RevealLiving { range } => {
+                format!("Reveal prey and infected within {:.0}", range)
+            }
+            AbilityEffect::Speed { multiplier } => format!("Move {:.1}x faster", multiplier),
+            AbilityEffect::Hitbox { scale } => {
+                format!("Hitbox shrinks to {:.0}%", scale * 100.0)
+            }
+            AbilityEffect::SeeThroughWalls { range } => {
+                format!("See creatures through walls within {:.0}", range)
+            }
+            AbilityEffect::Scout { sight } => {
+                format!("Pilot a crow that sees {:.0} around it", sight)
+            }
+            AbilityEffect::ScentTrails { range } => {
+                format!("Follow scent trails to prey within {:.0}", range)
+            }
+            AbilityEffect::Lance { damage, range } => {
+                format!(
+                    "Hurl a lance for {:.0} damage up to {:.0} away",
+                    damage, range
+                )
+            }
+        }
+    }
+}
+
+/// Cost preview and effects of an ability, for the hotbar tooltip
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbilityTooltip {
+    pub ability: SpecialAbility,
+    pub blood_cost: f32,
+    /// Blood left after using it; negative when there is not enough
+    pub blood_after: f32,
+    pub cooldown: f32,
+    pub cooldown_remaining: f32,
+    /// Seconds the effect lasts, zero for instant abilities
+    pub duration: f32,
+    /// Blood per second while a toggled ability is on
+    pub upkeep: f32,
+    pub effects: Vec<AbilityEffect>,
+    pub modifiers: Vec<String>,
+    pub unlocked: bool,
+}
+
+impl AbilityTooltip {
+    pub fn affordable(&self) -> bool {
+        self.blood_after >= 0.0
+    }
+
+    /// Text lines of the tooltip body, below the ability's name
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Blood cost: {:.0} ({:.0} left after)",
+            self.blood_cost,
+            self.blood_after.max(0.0)
+        )];
+        let mut timing = format!("Cooldown: {:.0}s", self.cooldown);
+        if self.duration > 0.0 {
+            timing.push_str(&format!(", lasts {:.0}s", self.duration));
+        }
+        lines.push(timing);
+        if self.upkeep > 0.0 {
+            lines.push(format!(
+                "Drains {:.1} blood/s until toggled off",
+                self.upkeep
+            ));
+        }
+        lines.extend(self.effects.iter().map(AbilityEffect::describe));
+        lines.extend(self.modifiers.iter().cloned());
+        lines
+    }
+
+    /// Why the ability cannot be used right now, if it cannot
+    pub fn blocker(&self) -> Option<String> {
+        if !self.unlocked {
+            Some("Locked".to_string())
+        } else if self.cooldown_remaining > 0.0 {
+            Some(format!("Recovering: {:.1}s", self.cooldown_remaining))
+        } else if !self.affordable() {
+            Some(format!("Needs {:.0} more blood", -self.blood_after))
+        } else {
+            None
+        }
+    }
+}
+
+/// Where a Blood Sight trail leads
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScentTrail {
+    pub id: u32,
+    pub position: Position,
+    /// 1.0 beside the player, fading to 0.0 at the edge of the range
+    pub strength: f32,
+}
+
+/// A creature revealed by Spectral Vision
+#[derive(Debug, Clone)]
+pub struct SpectralTarget {
+    pub id: u32,
+    pub position: Position,
+    pub entity_type: EntityType,
+    pub blood_source: bool,
+}
+
+/// Outcome of trying to activate an ability
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbilityResult {
+    Activated {
+        ability: SpecialAbility,
+    },
+    /// A toggled ability was switched off
+    Ended {
+        ability: SpecialAbility,
+    },
+    OnCooldown {
+        ability: SpecialAbility,
+        remaining: f32,
+    },
+    NotEnoughBlood {
+        ability: SpecialAbility,
+        needed: f32,
+    },
+    NeedsDirection {
+        ability: SpecialAbility,
+    },
+    Unavailable {
+        ability: SpecialAbility,
+    },
+    Locked {
+        ability: SpecialAbility,
+        required: f32,
+    },
+    /// The skill that unlocks the ability has not been learned yet
+    Unlearned {
+        ability: SpecialAbility,
+        skill: Skill,
+    },
+}
+
+impl AbilityResult {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            AbilityResult::Activated { ability } => match ability {
+                SpecialAbility::ShadowDash => "You melt into shadow and reappear!".to_string(),
+                SpecialAbility::BloodSensePulse => {
+                    "Your senses flare - living blood glows around you.".to_string()
+                }
+                SpecialAbility::BatForm => "You burst into a swarm of bats!".to_string(),
+                SpecialAbility::SpectralVision => {
+                    "The world fades to grey - you see through the veil.".to_string()
+                }
+                SpecialAbility::CrowScout => {
+                    "A crow takes wing - you see through its eyes.".to_string()
+                }
+                SpecialAbility::BloodSight => {
+                    "The scent of living blood hangs in the air.".to_string()
+                }
+                SpecialAbility::BloodLance => {
+                    "Your blood hardens into a lance and flies!".to_string()
+                }
+            },
+            AbilityResult::Ended { ability } => format!("{} fades.", ability.display_name()),
+            AbilityResult::OnCooldown { ability, remaining } => format!(
+                "{} is recovering ({:.1}s)",
+                ability.display_name(),
+                remaining
+            ),
+            AbilityResult::NotEnoughBlood { ability, needed } => format!(
+                "Not enough blood for {} (needs {:.0})",
+                ability.display_name(),
+                needed
+            ),
+            AbilityResult::NeedsDirection {
+                ability: SpecialAbility::BloodLance,
+            } => "Point the cursor away from yourself to aim".to_string(),
+            AbilityResult::NeedsDirection { ability } => {
+                format!("Hold a direction to use {}", ability.display_name())
+            }
+            AbilityResult::Unavailable { ability } => {
+                format!("{} cannot be used right now", ability.display_name())
+            }
+            AbilityResult::Locked { ability, required } => format!(
+                "{} requires blood sense {:.1}",
+                ability.display_name(),
+                required
+            ),
+            AbilityResult::Unlearned { ability, skill } => format!(
+                "{} must be learned first ({} skill: {})",
+                ability.display_name(),
+                skill.branch().display_name(),
+                skill.display_name()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::prelude::*;
+
+    fn create_test_player(blood: f32) -> GameEntity {
+        GameEntity {
+            id: 1,
+            position: Position::new(800.0, 900.0),
+            velocity: Some(Velocity::zero()),
+            entity_type: EntityType::Player,
+            health: Some(Health::new(100.0)),
+            combat_stats: Some(CombatStats::new(25.0, 10.0)),
+            ai_state: AIState::Idle,
+            blood_meter: Some(BloodMeter {
+                current: blood,
+                maximum: 100.0,
+                drain_rate: 1.0,
+                feeding: FeedingState::default(),
+                bottling: 0.0,
+            }),
+            vampire_abilities: Some(VampireAbilities::default()),
+            shelter: None,
+            shelter_occupancy: None,
+            color: RED,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        }
+    }
+
+    #[test]
+    fn test_shadow_dash_moves_and_costs_blood() {
+        let mut entities = vec![create_test_player(50.0)];
+
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::ShadowDash,
+            (1.0, 0.0),
+        );
+
+        assert_eq!(
+            result,
+            AbilityResult::Activated {
+                ability: SpecialAbility::ShadowDash
+            }
+        );
+        assert_eq!(entities[0].position.x, 800.0 + DASH_DISTANCE);
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 42.0);
+
+        // Immediately reusing it hits the cooldown
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::ShadowDash,
+            (1.0, 0.0),
+        );
+        assert!(matches!(result, AbilityResult::OnCooldown { .. }));
+    }
+
+    #[test]
+    fn test_ability_requires_blood() {
+        let mut entities = vec![create_test_player(10.0)];
+
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::BatForm,
+            (0.0, 0.0),
+        );
+
+        assert!(matches!(result, AbilityResult::NotEnoughBlood { .. }));
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 10.0);
+    }
+
+    #[test]
+    fn test_bat_form_expires() {
+        let mut entities = vec![create_test_player(50.0)];
+        AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::BatForm,
+            (0.0, 0.0),
+        );
+
+        let state = &entities[0]
+            .vampire_abilities
+            .as_ref()
+            .unwrap()
+            .ability_state;
+        assert!(state.is_bat_form());
+        assert_eq!(state.speed_multiplier(), AbilityState::BAT_FORM_SPEED);
+
+        AbilitySystem::update(&mut entities, SpecialAbility::BatForm.duration() + 0.1);
+        let state = &entities[0]
+            .vampire_abilities
+            .as_ref()
+            .unwrap()
+            .ability_state;
+        assert!(!state.is_bat_form());
+        assert!(!state.is_ready(SpecialAbility::BatForm));
+    }
+
+    #[test]
+    fn test_tooltip_scales_with_stats_and_previews_cost() {
+        let mut player = create_test_player(6.0);
+        let abilities = player.vampire_abilities.as_mut().unwrap();
+        abilities.shadow_movement = 1.0;
+
+        let tooltip = AbilitySystem::tooltip(
+            SpecialAbility::ShadowDash,
+            abilities,
+            player.blood_meter.as_ref(),
+        );
+        assert_eq!(
+            tooltip.effects,
+            vec![AbilityEffect::Blink {
+                distance: DASH_DISTANCE + DASH_DISTANCE_PER_SHADOW
+            }]
+        );
+        assert_eq!(tooltip.modifiers.len(), 1);
+        assert!(!tooltip.affordable());
+        assert_eq!(tooltip.blocker().unwrap(), "Needs 2 more blood");
+        assert_eq!(tooltip.lines()[0], "Blood cost: 8 (0 left after)");
+
+        let spectral = AbilitySystem::tooltip(
+            SpecialAbility::SpectralVision,
+            player.vampire_abilities.as_ref().unwrap(),
+            player.blood_meter.as_ref(),
+        );
+        assert_eq!(spectral.blocker().unwrap(), "Locked");
+        assert!(spectral.lines()[1].contains("lasts 4s"));
+    }
+
+    #[test]
+    fn test_spectral_vision_requires_blood_sense() {
+        let mut entities = vec![create_test_player(50.0)];
+        let mut prey = create_test_player(0.0);
+        prey.id = 2;
+        prey.entity_type = EntityType::Animal;
+        prey.position = Position::new(1000.0, 900.0);
+        entities.push(prey);
+
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::SpectralVision,
+            (0.0, 0.0),
+        );
+        assert!(matches!(result, AbilityResult::Locked { .. }));
+        assert!(AbilitySystem::spectral_entities(
+            &entities,
+            &SpatialGrid::from_entities(&entities),
+            1
+        )
+        .is_empty());
+
+        entities[0].vampire_abilities.as_mut().unwrap().blood_sense = 1.0;
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::SpectralVision,
+            (0.0, 0.0),
+        );
+        assert!(matches!(result, AbilityResult::Activated { .. }));
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 25.0);
+
+        let revealed =
+            AbilitySystem::spectral_entities(&entities, &SpatialGrid::from_entities(&entities), 1);
+        assert_eq!(revealed.len(), 1);
+        assert!(revealed[0].blood_source);
+    }
+
+    #[test]
+    fn test_blood_sight_reach_scales_and_drains_until_toggled_or_dry() {
+        let mut entities = vec![create_test_player(4.0)];
+        for (id, x) in [(2, 900.0), (3, 1000.0)] {
+            let mut prey = create_test_player(0.0);
+            prey.id = id;
+            prey.entity_type = EntityType::Animal;
+            prey.position = Position::new(x, 900.0);
+            entities.push(prey);
+        }
+        let trails = |entities: &[GameEntity]| {
+            AbilitySystem::scent_trails(entities, &SpatialGrid::from_entities(entities), 1)
+        };
+
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::BloodSight,
+            (0.0, 0.0),
+        );
+        assert!(matches!(result, AbilityResult::Activated { .. }));
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 1.0);
+        let near = trails(&entities);
+        assert_eq!(near.len(), 1);
+        assert_eq!(near[0].id, 2);
+        assert!((near[0].strength - (1.0 - 100.0 / 150.0)).abs() < 0.001);
+
+        // A developed blood sense reaches the farther animal too
+        entities[0].vampire_abilities.as_mut().unwrap().blood_sense = 1.0;
+        assert_eq!(trails(&entities).len(), 2);
+
+        // Switching it off is free
+        let result = AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::BloodSight,
+            (0.0, 0.0),
+        );
+        assert!(matches!(result, AbilityResult::Ended { .. }));
+        assert!(trails(&entities).is_empty());
+
+        // Left on, it closes once the upkeep can no longer be paid
+        AbilitySystem::update(&mut entities, 1.0);
+        entities[0].blood_meter.as_mut().unwrap().current = 4.0;
+        AbilitySystem::activate(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            SpecialAbility::BloodSight,
+            (0.0, 0.0),
+        );
+        AbilitySystem::update(&mut entities, 1.0);
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 0.5);
+        AbilitySystem::update(&mut entities, 2.0);
+        assert!(trails(&entities).is_empty());
+    }
+}
@@ -4,38 +4,395 @@
 //! This system manages different AI states and behaviors for non-player entities.
 
 use crate::components::*;
+use crate::systems::pathfinding::{NavGrid, PathfindingSystem};
+use crate::systems::spatial::SpatialGrid;
+use crate::systems::stealth::{SightBlocker, StealthSystem};
+use crate::systems::WorldSystem;
+use crate::world_data::WorldData;
 use macroquad::prelude::*;
+use std::collections::HashSet;
+
+/// Furthest distance at which any AI reacts to the player
+const AI_AWARENESS_RANGE: f32 = 200.0;
+
+/// Distance at which hostile AI can strike a full-size target
+const ATTACK_RANGE: f32 = 30.0;
+
+/// Distance followers keep from the player
+const FOLLOW_DISTANCE: f32 = 50.0;
+
+/// Follower movement speed; they run faster to catch up when far behind
+const FOLLOWER_SPEED: f32 = 120.0;
+const FOLLOWER_CATCH_UP_DISTANCE: f32 = 200.0;
+
+/// How far a hungry infected looks for prey
+const HUNT_RANGE: f32 = 300.0;
+
+/// Speed of an infected stalking prey
+const HUNT_SPEED: f32 = 90.0;
+
+/// Furthest distance at which clan vampires notice the player feeding
+const WITNESS_RANGE: f32 = 250.0;
+
+/// Ambling speed of wandering creatures
+const WANDER_SPEED: f32 = 35.0;
+
+/// Range of seconds a wandering creature grazes before moving on
+const GRAZE_TIME: (f32, f32) = (2.0, 6.0);
 
 /// AI system responsible for NPC behavior and decision making
 pub struct AISystem;
 
 impl AISystem {
-    /// Update AI for all entities
-    pub fn update_all_ai(entities: &mut Vec<GameEntity>, player_id: u32, delta_time: f32) {
+    /// Update AI for all entities.
+    ///
+    /// `hearing_scale` stretches or shrinks every range at which AI notices
+    /// the player, following how much noise the player is making and how
+    /// well hidden they are. Creatures only notice a player they can see
+    /// past the `blockers`; those already fleeing keep running regardless.
+    /// Animals whose species hunts after dark turn on the player instead of
+    /// fleeing while `is_night`. Nothing walks into the rivers and lakes of
+    /// the world laid out by `world_seed`, and hostiles chasing the player
+    /// find their way around them and around solid shelters over `nav`.
+    /// Wanderers
+    /// further than `active_radius` from the player stand still, and nothing
+    /// leaves `bounds`.
+    ///
+    /// Returns the IDs of entities that are in range and want to attack the
+    /// player; the caller resolves those attacks through the combat system.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_all_ai(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        hearing_scale: f32,
+        blockers: &[SightBlocker],
+        is_night: bool,
+        bounds: &WorldBounds,
+        nav: &mut NavGrid,
+        world_seed: u64,
+        active_radius: Option<f32>,
+        delta_time: f32,
+    ) -> Vec<u32> {
         let player_pos = Self::get_player_position(entities, player_id);
+        // Bat form shrinks the player's hitbox, so enemies must get closer
+        let attack_range = ATTACK_RANGE
+            * EntityFinder::by_id(entities, player_id)
+                .and_then(|player| player.vampire_abilities.as_ref())
+                .map_or(1.0, |abilities| abilities.ability_state.hitbox_scale());
+
+        // Only entities near the player need a full behavior update
+        let near_player: HashSet<usize> = player_pos
+            .map(|pos| {
+                grid.query_radius(entities, pos, AI_AWARENESS_RANGE * hearing_scale)
+                    .into_iter()
+                    .collect()
+            })
+            .unwrap_or_default();
 
         // Pre-allocate with estimated capacity for better performance
-        let mut ai_updates = Vec::with_capacity(entities.len() / 4);
-
-        // Use high-performance iterator to filter living entities
-        let living_entities = entities.alive_entities().filter(|e| e.id != player_id);
-
-        // Process AI updates using new iterator
-        for entity in living_entities {
-            let update = match entity.ai_state {
-                AIState::Hostile => Self::update_hostile_ai(entity, &player_pos, delta_time),
-                AIState::Fleeing => Self::update_fleeing_ai(entity, &player_pos, delta_time),
-                AIState::Idle => Self::update_idle_ai(entity, &player_pos, delta_time),
-                AIState::Dead => None, // Filtered out by alive_entities()
+        let mut ai_updates = Vec::with_capacity(near_player.len());
+
+        for (index, entity) in entities.iter().enumerate() {
+            if entity.id == player_id || !Self::is_alive(entity) || entity.is_hidden() {
+                continue;
+            }
+
+            let update = if near_player.contains(&index) {
+                let seen_pos = player_pos.filter(|pos| {
+                    StealthSystem::has_line_of_sight(&entity.position, pos, blockers)
+                });
+                let animal = entity.entity_type == EntityType::Animal;
+                let hunting = animal && Self::hunts_now(entity, is_night);
+                match entity.ai_state {
+                    AIState::Hostile if seen_pos.is_none() => Some(AIUpdate::stop(entity.id)),
+                    // Day has come: night hunters give up the chase
+                    AIState::Hostile if animal && !hunting => Some(AIUpdate::stop(entity.id)),
+                    AIState::Fleeing if hunting => {
+                        Self::update_hostile_ai(entity, &seen_pos, nav, attack_range, hearing_scale)
+                    }
+                    AIState::Idle | AIState::Wander if hunting => Self::update_prowling_ai(
+                        entity,
+                        &seen_pos,
+                        nav,
+                        attack_range,
+                        hearing_scale,
+                    ),
+                    AIState::Hostile => {
+                        Self::update_hostile_ai(entity, &seen_pos, nav, attack_range, hearing_scale)
+                    }
+                    AIState::Fleeing => Self::update_fleeing_ai(entity, &player_pos, hearing_scale),
+                    AIState::Idle => Self::update_idle_ai(entity, &seen_pos, hearing_scale),
+                    AIState::Wander => Self::update_wandering_ai(entity, &seen_pos, hearing_scale),
+                    // Followers are driven by `update_followers`, squads by
+                    // `AssaultSystem`, and the staggered wait for
+                    // `CombatSystem::update_staggers`
+                    AIState::Dead
+                    | AIState::Follower(_)
+                    | AIState::Squad
+                    | AIState::Staggered(_) => None,
+                }
+            } else if matches!(entity.ai_state, AIState::Hostile | AIState::Fleeing) {
+                // Out of range of the player: hunters and prey settle down
+                Some(AIUpdate::stop(entity.id))
+            } else {
+                None
             };
 
             if let Some(ai_update) = update {
-                ai_updates.push(ai_update);
+                ai_updates.push((index, ai_update));
             }
         }
 
+        let attackers = ai_updates
+            .iter()
+            .filter(|(_, update)| update.should_attack)
+            .map(|(_, update)| update.entity_id)
+            .collect();
+
         // Apply AI updates with optimized collection
-        Self::apply_ai_updates(entities, ai_updates, delta_time);
+        Self::apply_ai_updates(
+            entities, ai_updates, is_night, bounds, world_seed, delta_time,
+        );
+        Self::update_wanderers(
+            entities,
+            player_pos,
+            active_radius,
+            bounds,
+            world_seed,
+            delta_time,
+        );
+
+        attackers
+    }
+
+    /// Move followers according to their orders, finding their way around
+    /// solid shelters and water over `nav`, without leaving `bounds`.
+    ///
+    /// Returns `(attacker, target)` pairs for followers close enough to
+    /// strike their target; the caller resolves them through the combat system.
+    pub fn update_followers(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        bounds: &WorldBounds,
+        nav: &mut NavGrid,
+        delta_time: f32,
+    ) -> Vec<(u32, u32)> {
+        let Some(player_pos) = Self::get_player_position(entities, player_id) else {
+            return Vec::new();
+        };
+        if !entities
+            .iter()
+            .any(|entity| matches!(entity.ai_state, AIState::Follower(_)))
+        {
+            return Vec::new();
+        }
+
+        let mut updates = Vec::new();
+        for (index, entity) in entities.iter().enumerate() {
+            let AIState::Follower(order) = entity.ai_state else {
+                continue;
+            };
+            if !Self::is_alive(entity) {
+                continue;
+            }
+
+            let target = match order {
+                FollowerOrder::Attack(target_id) => EntityFinder::by_id(entities, target_id)
+                    .filter(|target| Self::is_alive(target))
+                    .map(|target| (target.position, Some(target_id))),
+                _ => None,
+            };
+
+            let (destination, stop_distance, attack_target, new_order) = match (order, target) {
+                (FollowerOrder::Attack(_), Some((position, target_id))) => {
+                    (position, ATTACK_RANGE, target_id, order)
+                }
+                // The target is gone, so fall back in behind the player
+                (FollowerOrder::Attack(_), None) => {
+                    (player_pos, FOLLOW_DISTANCE, None, FollowerOrder::Follow)
+                }
+                (FollowerOrder::Hold(position), _) => (position, 5.0, None, order),
+                (FollowerOrder::Follow, _) => (player_pos, FOLLOW_DISTANCE, None, order),
+            };
+
+            let distance = Self::calculate_distance(&entity.position, &destination);
+            let (velocity, should_attack) = if distance <= stop_distance {
+                (Velocity { x: 0.0, y: 0.0 }, attack_target.is_some())
+            } else {
+                let direction =
+                    PathfindingSystem::steer(nav, entity.id, &entity.position, &destination);
+                let speed = if distance > FOLLOWER_CATCH_UP_DISTANCE {
+                    FOLLOWER_SPEED * 1.5
+                } else {
+                    FOLLOWER_SPEED
+                };
+                (
+                    Velocity {
+                        x: direction.0 * speed,
+                        y: direction.1 * speed,
+                    },
+                    false,
+                )
+            };
+
+            updates.push((
+                index,
+                velocity,
+                new_order,
+                attack_target.filter(|_| should_attack),
+            ));
+        }
+
+        let mut attacks = Vec::new();
+        for (index, velocity, order, attack_target) in updates {
+            let entity = &mut entities[index];
+            entity.position = bounds.clamp(Position::new(
+                entity.position.x + velocity.x * delta_time,
+                entity.position.y + velocity.y * delta_time,
+            ));
+            entity.velocity = Some(velocity);
+            entity.ai_state = AIState::Follower(order);
+            if let Some(target_id) = attack_target {
+                attacks.push((entity.id, target_id));
+            }
+        }
+
+        attacks
+    }
+
+    /// Grow every infected's hunger and move the hungry ones after prey
+    /// while the player is not around to draw their attention. Infected
+    /// further than `active_radius` from the player are left as they are.
+    ///
+    /// Returns `(hunter, prey)` pairs for infected within reach of their
+    /// prey; the caller resolves attacks on living prey through the combat
+    /// system and has the hunter devour dead prey.
+    pub fn update_hunters(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        hearing_scale: f32,
+        active_radius: Option<f32>,
+        delta_time: f32,
+    ) -> Vec<(u32, u32)> {
+        let player_pos = Self::get_player_position(entities, player_id);
+        let is_prey = |entity: &GameEntity| {
+            entity.entity_type == EntityType::Animal
+                && !entity.is_hidden()
+                && (Self::is_alive(entity)
+                    || entity
+                        .corpse
+                        .as_ref()
+                        .is_some_and(|corpse| !corpse.is_dry()))
+        };
+
+        let mut moves = Vec::new();
+        for (index, entity) in entities.iter().enumerate() {
+            let Some(hunger) = &entity.hunger else {
+                continue;
+            };
+            if !Self::is_alive(entity)
+                || matches!(entity.ai_state, AIState::Staggered(_))
+                || !Self::is_active(entity, player_pos, active_radius)
+            {
+                continue;
+            }
+            // The player takes precedence over any meal
+            let player_near = player_pos.is_some_and(|pos| {
+                Self::calculate_distance(&entity.position, &pos)
+                    < AI_AWARENESS_RANGE * hearing_scale
+            });
+            if player_near || !hunger.is_hungry() {
+                moves.push((index, None));
+                continue;
+            }
+
+            let current = hunger
+                .prey
+                .and_then(|prey_id| EntityFinder::by_id(entities, prey_id))
+                .filter(|prey| is_prey(prey));
+            let prey = current.or_else(|| {
+                grid.query_radius(entities, entity.position, HUNT_RANGE)
+                    .into_iter()
+                    .map(|other| &entities[other])
+                    .filter(|other| is_prey(other) && Self::is_alive(other))
+                    .min_by(|a, b| {
+                        Self::calculate_distance(&entity.position, &a.position)
+                            .total_cmp(&Self::calculate_distance(&entity.position, &b.position))
+                    })
+            });
+            moves.push((index, prey.map(|prey| (prey.id, prey.position))));
+        }
+
+        let mut hunts = Vec::new();
+        for (index, prey) in moves {
+            let entity = &mut entities[index];
+            let Some(hunger) = entity.hunger.as_mut() else {
+                continue;
+            };
+            hunger.update(delta_time);
+            hunger.prey = prey.map(|(prey_id, _)| prey_id);
+            let Some((prey_id, prey_pos)) = prey else {
+                continue;
+            };
+
+            let distance = Self::calculate_distance(&entity.position, &prey_pos);
+            if distance <= ATTACK_RANGE {
+                entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+                hunts.push((entity.id, prey_id));
+                continue;
+            }
+            let direction = Self::normalize_direction(
+                prey_pos.x - entity.position.x,
+                prey_pos.y - entity.position.y,
+            );
+            let step = (HUNT_SPEED * delta_time).min(distance);
+            entity.position.x += direction.0 * step;
+            entity.position.y += direction.1 * step;
+            entity.velocity = Some(Velocity {
+                x: direction.0 * HUNT_SPEED,
+                y: direction.1 * HUNT_SPEED,
+            });
+        }
+
+        hunts
+    }
+
+    /// Whether an entity is close enough to the player to be simulated
+    fn is_active(
+        entity: &GameEntity,
+        player_pos: Option<Position>,
+        active_radius: Option<f32>,
+    ) -> bool {
+        match (player_pos, active_radius) {
+            (Some(pos), Some(radius)) => Self::calculate_distance(&entity.position, &pos) <= radius,
+            _ => true,
+        }
+    }
+
+    /// Matches the filter used by `alive_entities()`
+    fn is_alive(entity: &GameEntity) -> bool {
+        !matches!(entity.ai_state, AIState::Dead)
+            && entity.health.as_ref().is_none_or(|h| h.current > 0.0)
+    }
+
+    /// Scale on how fast a creature moves, by its species
+    fn species_speed(entity: &GameEntity) -> f32 {
+        entity.species.map_or(1.0, |species| {
+            WorldData::current().archetypes.species(species).speed
+        })
+    }
+
+    /// Whether a creature's species hunts at this time of day
+    fn hunts_now(entity: &GameEntity, is_night: bool) -> bool {
+        is_night
+            && entity.species.is_some_and(|species| {
+                WorldData::current()
+                    .archetypes
+                    .species(species)
+                    .hunts_at_night
+            })
     }
 
     /// Get the player's current position using optimized entity finder
@@ -44,18 +401,20 @@ impl AISystem {
         EntityFinder::by_id(entities, player_id).map(|player| player.position)
     }
 
-    /// Update hostile AI behavior
+    /// Update hostile AI behavior, chasing the player along a route around
+    /// whatever stands in the way
     fn update_hostile_ai(
         entity: &GameEntity,
         player_pos: &Option<Position>,
-        delta_time: f32,
+        nav: &mut NavGrid,
+        attack_range: f32,
+        hearing_scale: f32,
     ) -> Option<AIUpdate> {
         if let Some(player_pos) = player_pos {
             let distance = Self::calculate_distance(&entity.position, player_pos);
 
             // Detection range for hostile entities
-            let detection_range = 200.0;
-            let attack_range = 30.0;
+            let detection_range = 200.0 * hearing_scale;
 
             if distance < detection_range {
                 if distance < attack_range {
@@ -71,12 +430,11 @@ impl AISystem {
                     })
                 } else {
                     // Move towards player
-                    let direction = Self::normalize_direction(
-                        player_pos.x - entity.position.x,
-                        player_pos.y - entity.position.y,
-                    );
+                    let direction =
+                        PathfindingSystem::steer(nav, entity.id, &entity.position, player_pos);
 
-                    let speed = 106.0; // Slightly slower than player
+                    // Slightly slower than player
+                    let speed = 106.0 * Self::species_speed(entity);
                     let velocity = Velocity {
                         x: direction.0 * speed,
                         y: direction.1 * speed,
@@ -110,11 +468,11 @@ impl AISystem {
     fn update_fleeing_ai(
         entity: &GameEntity,
         player_pos: &Option<Position>,
-        _delta_time: f32,
+        hearing_scale: f32,
     ) -> Option<AIUpdate> {
         if let Some(player_pos) = player_pos {
             let distance = Self::calculate_distance(&entity.position, player_pos);
-            let flee_range = 150.0;
+            let flee_range = 150.0 * hearing_scale;
 
             if distance < flee_range {
                 // Flee away from player
@@ -123,7 +481,8 @@ impl AISystem {
                     entity.position.y - player_pos.y,
                 );
 
-                let speed = 140.0; // Faster when fleeing
+                // Faster when fleeing
+                let speed = 140.0 * Self::species_speed(entity);
                 let velocity = Velocity {
                     x: direction.0 * speed,
                     y: direction.1 * speed,
@@ -156,34 +515,30 @@ impl AISystem {
     fn update_idle_ai(
         entity: &GameEntity,
         player_pos: &Option<Position>,
-        delta_time: f32,
+        hearing_scale: f32,
     ) -> Option<AIUpdate> {
         if let Some(player_pos) = player_pos {
             let distance = Self::calculate_distance(&entity.position, player_pos);
 
             // Check if entity should become hostile or flee based on entity type
             match entity.entity_type {
-                EntityType::HostileInfected => {
-                    if distance < 100.0 {
-                        // Become hostile when player is nearby
-                        return Some(AIUpdate {
-                            entity_id: entity.id,
-                            new_velocity: Velocity { x: 0.0, y: 0.0 },
-                            new_facing_direction: None,
-                            should_attack: false,
-                        });
-                    }
+                EntityType::HostileInfected if distance < 100.0 * hearing_scale => {
+                    // Become hostile when player is nearby
+                    return Some(AIUpdate {
+                        entity_id: entity.id,
+                        new_velocity: Velocity { x: 0.0, y: 0.0 },
+                        new_facing_direction: None,
+                        should_attack: false,
+                    });
                 }
-                EntityType::Animal => {
-                    if distance < 80.0 {
-                        // Animals flee when player approaches
-                        return Some(AIUpdate {
-                            entity_id: entity.id,
-                            new_velocity: Velocity { x: 0.0, y: 0.0 },
-                            new_facing_direction: None,
-                            should_attack: false,
-                        });
-                    }
+                EntityType::Animal if distance < 80.0 * hearing_scale => {
+                    // Animals flee when player approaches
+                    return Some(AIUpdate {
+                        entity_id: entity.id,
+                        new_velocity: Velocity { x: 0.0, y: 0.0 },
+                        new_facing_direction: None,
+                        should_attack: false,
+                    });
                 }
                 _ => {
                     // Other entities remain idle
@@ -194,15 +549,152 @@ impl AISystem {
         None
     }
 
-    /// Apply AI updates to entities
-    fn apply_ai_updates(entities: &mut Vec<GameEntity>, updates: Vec<AIUpdate>, delta_time: f32) {
-        for update in updates {
-            if let Some(entity) = entities.iter_mut().find(|e| e.id == update.entity_id) {
-                // Update velocity and position
+    /// A wandering creature bolts once the player comes close enough to be
+    /// heard; otherwise it goes about its business in `update_wanderers`
+    fn update_wandering_ai(
+        entity: &GameEntity,
+        player_pos: &Option<Position>,
+        hearing_scale: f32,
+    ) -> Option<AIUpdate> {
+        let player_pos = player_pos.as_ref()?;
+        let distance = Self::calculate_distance(&entity.position, player_pos);
+        if distance < 80.0 * hearing_scale {
+            Self::update_fleeing_ai(entity, &Some(*player_pos), hearing_scale)
+        } else {
+            None
+        }
+    }
+
+    /// A night hunter at rest goes for the player once it notices them,
+    /// at the distance other animals would bolt
+    fn update_prowling_ai(
+        entity: &GameEntity,
+        player_pos: &Option<Position>,
+        nav: &mut NavGrid,
+        attack_range: f32,
+        hearing_scale: f32,
+    ) -> Option<AIUpdate> {
+        let player_pos = player_pos.as_ref()?;
+        let distance = Self::calculate_distance(&entity.position, player_pos);
+        if distance < 80.0 * hearing_scale {
+            Self::update_hostile_ai(entity, &Some(*player_pos), nav, attack_range, hearing_scale)
+        } else {
+            None
+        }
+    }
+
+    /// Amble each wandering creature to a random spot in its home range, graze
+    /// there a while, then pick another
+    fn update_wanderers(
+        entities: &mut [GameEntity],
+        player_pos: Option<Position>,
+        active_radius: Option<f32>,
+        bounds: &WorldBounds,
+        world_seed: u64,
+        delta_time: f32,
+    ) {
+        for entity in entities.iter_mut() {
+            if !matches!(entity.ai_state, AIState::Wander)
+                || !Self::is_active(entity, player_pos, active_radius)
+            {
+                continue;
+            }
+            let speed = WANDER_SPEED * Self::species_speed(entity);
+            let Some(wander) = entity.wander.as_mut() else {
+                continue;
+            };
+            if wander.hiding {
+                entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+                continue;
+            }
+
+            if wander.is_grazing() {
+                wander.graze_remaining -= delta_time;
+                entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+                continue;
+            }
+
+            let target = *wander.target.get_or_insert_with(|| {
+                let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+                let radius = rand::gen_range(0.0, wander.range);
+                bounds.clamp(Position::new(
+                    wander.home.x + angle.cos() * radius,
+                    wander.home.y + angle.sin() * radius,
+                ))
+            });
+
+            let distance = Self::calculate_distance(&entity.position, &target);
+            let step = speed * delta_time;
+            if distance <= step {
+                entity.position = target;
+                entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+                wander.target = None;
+                wander.graze_remaining = rand::gen_range(GRAZE_TIME.0, GRAZE_TIME.1);
+                continue;
+            }
+
+            let direction = Self::normalize_direction(
+                target.x - entity.position.x,
+                target.y - entity.position.y,
+            );
+            let next = Position::new(
+                entity.position.x + direction.0 * step,
+                entity.position.y + direction.1 * step,
+            );
+            if Self::wades_in(&entity.position, &next, world_seed) {
+                // Water in the way: graze here and pick somewhere else
+                entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+                wander.target = None;
+                wander.graze_remaining = GRAZE_TIME.0;
+                continue;
+            }
+            entity.position = next;
+            entity.velocity = Some(Velocity {
+                x: direction.0 * speed,
+                y: direction.1 * speed,
+            });
+        }
+    }
+
+    /// Apply AI updates to entities, integrating the new velocities over the
+    /// frame's real `delta_time` so travel speed does not depend on framerate
+    fn apply_ai_updates(
+        entities: &mut [GameEntity],
+        updates: Vec<(usize, AIUpdate)>,
+        is_night: bool,
+        bounds: &WorldBounds,
+        world_seed: u64,
+        delta_time: f32,
+    ) {
+        for (index, update) in updates {
+            if let Some(entity) = entities.get_mut(index) {
+                // Update velocity and position, going around water rather
+                // than into it
                 entity.velocity = Some(update.new_velocity);
-                if let Some(velocity) = &entity.velocity {
-                    entity.position.x += velocity.x * delta_time;
-                    entity.position.y += velocity.y * delta_time;
+                if let Some(velocity) = entity.velocity.as_mut() {
+                    let from = entity.position;
+                    let step = (velocity.x * delta_time, velocity.y * delta_time);
+                    let along_x = Position::new(from.x + step.0, from.y);
+                    let along_y = Position::new(from.x, from.y + step.1);
+                    let next = [
+                        Position::new(from.x + step.0, from.y + step.1),
+                        along_x,
+                        along_y,
+                    ]
+                    .into_iter()
+                    .find(|next| !Self::wades_in(&from, next, world_seed));
+                    match next {
+                        Some(next) => {
+                            if next.x == from.x {
+                                velocity.x = 0.0;
+                            }
+                            if next.y == from.y {
+                                velocity.y = 0.0;
+                            }
+                            entity.position = next;
+                        }
+                        None => *velocity = Velocity { x: 0.0, y: 0.0 },
+                    }
                 }
 
                 // Update facing direction
@@ -210,8 +702,7 @@ impl AISystem {
                 // Facing direction now calculated from velocity when needed
 
                 // Keep entities within world bounds
-                entity.position.x = entity.position.x.clamp(0.0, 1600.0);
-                entity.position.y = entity.position.y.clamp(640.0, 1200.0);
+                entity.position = bounds.clamp(entity.position);
 
                 // Update AI state based on behavior
                 match entity.entity_type {
@@ -227,9 +718,17 @@ impl AISystem {
                         }
                     }
                     EntityType::Animal => {
+                        let hunting = Self::hunts_now(entity, is_night);
                         if let Some(velocity) = &entity.velocity {
-                            if velocity.x.abs() > 0.1 || velocity.y.abs() > 0.1 {
+                            let moving = velocity.x.abs() > 0.1 || velocity.y.abs() > 0.1;
+                            if hunting && (moving || update.should_attack) {
+                                entity.ai_state = AIState::Hostile;
+                            } else if moving {
                                 entity.ai_state = AIState::Fleeing;
+                            } else if let Some(wander) = entity.wander.as_mut() {
+                                // Safe again: go back to roaming its home range
+                                wander.target = None;
+                                entity.ai_state = AIState::Wander;
                             } else {
                                 entity.ai_state = AIState::Idle;
                             }
@@ -243,6 +742,12 @@ impl AISystem {
         }
     }
 
+    /// Whether stepping from `from` to `to` goes from dry land into water.
+    /// Creatures already in the water may always splash their way out.
+    fn wades_in(from: &Position, to: &Position, world_seed: u64) -> bool {
+        WorldSystem::is_water_at(to, world_seed) && !WorldSystem::is_water_at(from, world_seed)
+    }
+
     /// Calculate distance between two positions
     fn calculate_distance(pos1: &Position, pos2: &Position) -> f32 {
         ((pos1.x - pos2.x).powi(2) + (pos1.y - pos2.y).powi(2)).sqrt()
@@ -268,6 +773,38 @@ impl AISystem {
         (from.y - to.y).atan2(from.x - to.x)
     }
 
+    /// Conscious members of `clan_name` who can see `position` past the
+    /// `blockers`, within a witness range scaled by `sight_scale`. The
+    /// entities in `exclude` (the feeder and victim) never count.
+    pub fn witnesses(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        position: Position,
+        clan_name: &str,
+        exclude: &[u32],
+        sight_scale: f32,
+        blockers: &[SightBlocker],
+    ) -> Vec<u32> {
+        grid.query_radius(entities, position, WITNESS_RANGE * sight_scale)
+            .into_iter()
+            .map(|index| &entities[index])
+            .filter(|entity| {
+                !exclude.contains(&entity.id)
+                    && Self::is_alive(entity)
+                    && !matches!(entity.ai_state, AIState::Staggered(_))
+                    && matches!(
+                        &entity.entity_type,
+                        EntityType::ClanMember(clan) | EntityType::ClanLeader(clan)
+                            if clan == clan_name
+                    )
+                    && Self::calculate_distance(&entity.position, &position)
+                        <= WITNESS_RANGE * sight_scale
+                    && StealthSystem::has_line_of_sight(&entity.position, &position, blockers)
+            })
+            .map(|entity| entity.id)
+            .collect()
+    }
+
     /// Check if an entity should start combat with the player
     pub fn should_initiate_combat(
         entity: &GameEntity,
@@ -283,10 +820,26 @@ impl AISystem {
     /// Get AI behavior description for debugging
     pub fn get_ai_behavior_description(entity: &GameEntity) -> String {
         match entity.ai_state {
-            AIState::Idle => "Wandering peacefully".to_string(),
+            AIState::Idle => match &entity.schedule {
+                Some(schedule) => schedule.activity.display_name().to_string(),
+                None => "Wandering peacefully".to_string(),
+            },
+            AIState::Hostile if entity.hunger.as_ref().is_some_and(|h| h.prey.is_some()) => {
+                "Stalking an animal".to_string()
+            }
             AIState::Hostile => "Hunting for prey".to_string(),
             AIState::Fleeing => "Fleeing in terror".to_string(),
+            AIState::Wander if entity.wander.as_ref().is_some_and(Wander::is_grazing) => {
+                "Grazing".to_string()
+            }
+            AIState::Wander => "Wandering".to_string(),
             AIState::Dead => "Lifeless".to_string(),
+            AIState::Follower(order) => format!("{} for its master", order.display_name()),
+            AIState::Squad => "Marching to war".to_string(),
+            AIState::Staggered(Stagger {
+                knocked_down: true, ..
+            }) => "Knocked down".to_string(),
+            AIState::Staggered(_) => "Reeling from a blow".to_string(),
         }
     }
 
@@ -310,7 +863,7 @@ impl AISystem {
                 EntityType::Animal => {
                     // More fearful based on player reputation
                     if player_reputation > 0.5 {
-                        if matches!(entity.ai_state, AIState::Idle) {
+                        if matches!(entity.ai_state, AIState::Idle | AIState::Wander) {
                             entity.ai_state = AIState::Fleeing;
                         }
                     }
@@ -334,6 +887,18 @@ struct AIUpdate {
     should_attack: bool,
 }
 
+impl AIUpdate {
+    /// An update that brings the entity to a halt
+    fn stop(entity_id: u32) -> Self {
+        Self {
+            entity_id,
+            new_velocity: Velocity { x: 0.0, y: 0.0 },
+            new_facing_direction: None,
+            should_attack: false,
+        }
+    }
+}
+
 /// AI personality traits that affect behavior
 #[derive(Debug, Clone)]
 pub struct AIPersonality {
@@ -358,6 +923,11 @@ impl Default for AIPersonality {
 mod tests {
     use super::*;
 
+    /// Navigation over the starting area with no shelters standing
+    fn open_ground() -> NavGrid {
+        NavGrid::build(&[], &WorldBounds::default(), 0)
+    }
+
     fn create_test_entity(id: u32, entity_type: EntityType, ai_state: AIState) -> GameEntity {
         GameEntity {
             id,
@@ -375,6 +945,17 @@ mod tests {
             shelter: None,
             shelter_occupancy: None,
             color: WHITE,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         }
     }
 
@@ -413,4 +994,258 @@ mod tests {
         let description = AISystem::get_ai_behavior_description(&entity);
         assert_eq!(description, "Hunting for prey");
     }
+
+    #[test]
+    fn test_followers_follow_hold_and_attack() {
+        let mut player = create_test_entity(1, EntityType::Player, AIState::Idle);
+        player.position = Position::new(500.0, 900.0);
+        let mut follower = create_test_entity(
+            2,
+            EntityType::ClanMember("Night-Bloods".to_string()),
+            AIState::Follower(FollowerOrder::Follow),
+        );
+        follower.position = Position::new(700.0, 900.0);
+        let mut enemy = create_test_entity(3, EntityType::HostileInfected, AIState::Idle);
+        enemy.position = Position::new(720.0, 900.0);
+        let mut entities = vec![player, follower, enemy];
+
+        // Following closes the distance to the player
+        AISystem::update_followers(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            &mut open_ground(),
+            0.5,
+        );
+        assert!(entities[1].position.x < 700.0);
+
+        // Holding stays put
+        let hold = entities[1].position;
+        entities[1].ai_state = AIState::Follower(FollowerOrder::Hold(hold));
+        AISystem::update_followers(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            &mut open_ground(),
+            0.5,
+        );
+        assert_eq!(entities[1].position.x, hold.x);
+
+        // Attacking walks to the target and strikes once in range
+        entities[1].ai_state = AIState::Follower(FollowerOrder::Attack(3));
+        let mut attacks = Vec::new();
+        for _ in 0..20 {
+            attacks = AISystem::update_followers(
+                &mut entities,
+                1,
+                &WorldBounds::default(),
+                &mut open_ground(),
+                0.1,
+            );
+            if !attacks.is_empty() {
+                break;
+            }
+        }
+        assert_eq!(attacks, vec![(2, 3)]);
+
+        // Once the target is dead the follower falls back in
+        entities[2].ai_state = AIState::Dead;
+        AISystem::update_followers(
+            &mut entities,
+            1,
+            &WorldBounds::default(),
+            &mut open_ground(),
+            0.1,
+        );
+        assert!(matches!(
+            entities[1].ai_state,
+            AIState::Follower(FollowerOrder::Follow)
+        ));
+    }
+
+    #[test]
+    fn test_travel_distance_independent_of_frame_times() {
+        fn flee_for(frame_times: &[f32]) -> Position {
+            let mut player = create_test_entity(1, EntityType::Player, AIState::Idle);
+            player.position = Position::new(400.0, 700.0);
+            let mut animal = create_test_entity(2, EntityType::Animal, AIState::Fleeing);
+            animal.position = Position::new(450.0, 700.0);
+            let mut entities = vec![player, animal];
+
+            for &delta_time in frame_times {
+                let grid = SpatialGrid::from_entities(&entities);
+                AISystem::update_all_ai(
+                    &mut entities,
+                    &grid,
+                    1,
+                    1.0,
+                    &[],
+                    false,
+                    &WorldBounds::default(),
+                    &mut open_ground(),
+                    0,
+                    None,
+                    delta_time,
+                );
+            }
+            entities[1].position
+        }
+
+        // Half a second at a steady 60 FPS versus an uneven mix summing to the same
+        let steady = flee_for(&[1.0 / 60.0; 30]);
+        let uneven = flee_for(&[
+            1.0 / 30.0,
+            1.0 / 144.0,
+            0.05,
+            1.0 / 60.0,
+            0.1,
+            1.0 / 144.0,
+            0.2,
+            0.5 - (1.0 / 30.0 + 2.0 / 144.0 + 0.05 + 1.0 / 60.0 + 0.1 + 0.2),
+        ]);
+
+        // Fleeing speed is 140 units per second
+        assert!((steady.x - 520.0).abs() < 0.01);
+        assert!((uneven.x - steady.x).abs() < 0.01);
+        assert_eq!(uneven.y, steady.y);
+    }
+
+    #[test]
+    fn test_animals_wander_at_home_and_flee_when_approached() {
+        let mut player = create_test_entity(1, EntityType::Player, AIState::Idle);
+        player.position = Position::new(1000.0, 700.0);
+        let mut animal = create_test_entity(2, EntityType::Animal, AIState::Wander);
+        animal.position = Position::new(400.0, 900.0);
+        animal.wander = Some(Wander::new(animal.position, 100.0));
+        let mut entities = vec![player, animal];
+
+        // Left alone, the animal moves about but stays in its home range
+        let mut moved = false;
+        for _ in 0..600 {
+            let grid = SpatialGrid::from_entities(&entities);
+            AISystem::update_all_ai(
+                &mut entities,
+                &grid,
+                1,
+                1.0,
+                &[],
+                false,
+                &WorldBounds::default(),
+                &mut open_ground(),
+                0,
+                None,
+                0.1,
+            );
+            let position = entities[1].position;
+            moved |= position.distance_to(&Position::new(400.0, 900.0)) > 1.0;
+            assert!(position.distance_to(&Position::new(400.0, 900.0)) <= 100.5);
+        }
+        assert!(moved);
+        assert!(matches!(entities[1].ai_state, AIState::Wander));
+
+        // The player creeps close and it bolts
+        let near = Position::new(entities[1].position.x + 40.0, entities[1].position.y);
+        entities[0].position = near;
+        let grid = SpatialGrid::from_entities(&entities);
+        AISystem::update_all_ai(
+            &mut entities,
+            &grid,
+            1,
+            1.0,
+            &[],
+            false,
+            &WorldBounds::default(),
+            &mut open_ground(),
+            0,
+            None,
+            0.1,
+        );
+        assert!(matches!(entities[1].ai_state, AIState::Fleeing));
+    }
+
+    #[test]
+    fn test_wolves_flee_by_day_and_fight_back_at_night() {
+        let approach = |is_night: bool| {
+            let mut player = create_test_entity(1, EntityType::Player, AIState::Idle);
+            player.position = Position::new(440.0, 900.0);
+            let mut wolf = create_test_entity(2, EntityType::Animal, AIState::Wander);
+            wolf.position = Position::new(400.0, 900.0);
+            wolf.wander = Some(Wander::new(wolf.position, 100.0));
+            wolf.species = Some(Species::Wolf);
+            let mut entities = vec![player, wolf];
+            let grid = SpatialGrid::from_entities(&entities);
+            AISystem::update_all_ai(
+                &mut entities,
+                &grid,
+                1,
+                1.0,
+                &[],
+                is_night,
+                &WorldBounds::default(),
+                &mut open_ground(),
+                0,
+                None,
+                0.1,
+            );
+            entities.remove(1)
+        };
+
+        let by_day = approach(false);
+        assert!(matches!(by_day.ai_state, AIState::Fleeing));
+        assert!(by_day.position.x < 400.0);
+
+        let at_night = approach(true);
+        assert!(matches!(at_night.ai_state, AIState::Hostile));
+        assert!(at_night.position.x > 400.0);
+    }
+
+    #[test]
+    fn test_hungry_infected_hunt_animals_while_player_is_away() {
+        use crate::systems::{BloodSystem, CombatSystem};
+
+        let mut player = create_test_entity(1, EntityType::Player, AIState::Idle);
+        player.position = Position::new(1500.0, 700.0);
+        let mut infected = create_test_entity(2, EntityType::HostileInfected, AIState::Hostile);
+        infected.position = Position::new(400.0, 900.0);
+        infected.hunger = Some(Hunger::new(1.0, Hunger::INFECTED_RATE));
+        let mut animal = create_test_entity(3, EntityType::Animal, AIState::Idle);
+        animal.position = Position::new(600.0, 900.0);
+        let mut entities = vec![player, infected, animal];
+        let mut damage_events = Vec::new();
+
+        // The player is near, so the infected pays the animal no mind
+        entities[0].position = Position::new(450.0, 900.0);
+        let grid = SpatialGrid::from_entities(&entities);
+        assert!(AISystem::update_hunters(&mut entities, &grid, 1, 1.0, None, 0.1).is_empty());
+        assert_eq!(entities[1].hunger.as_ref().unwrap().prey, None);
+
+        // Beyond a low-spec active radius it is not simulated at all
+        entities[0].position = Position::new(1500.0, 700.0);
+        let grid = SpatialGrid::from_entities(&entities);
+        AISystem::update_hunters(&mut entities, &grid, 1, 1.0, Some(400.0), 0.1);
+        assert_eq!(entities[1].position, Position::new(400.0, 900.0));
+        assert_eq!(entities[1].hunger.as_ref().unwrap().prey, None);
+
+        // With the player gone it stalks, kills and devours the animal
+        for step in 0..300 {
+            let grid = SpatialGrid::from_entities(&entities);
+            let hunts = AISystem::update_hunters(&mut entities, &grid, 1, 1.0, None, 0.1);
+            for (hunter, prey) in hunts {
+                let blow = CombatSystem::resolve_attack(
+                    &mut entities,
+                    hunter,
+                    prey,
+                    step as f32 * 0.1,
+                    &mut damage_events,
+                );
+                if blow.is_some_and(|blow| blow.killed) {
+                    assert!(BloodSystem::devour(&mut entities, hunter, prey));
+                }
+            }
+        }
+        assert!(EntityFinder::by_id(&entities, 3).is_none());
+        let hunger = entities[1].hunger.as_ref().unwrap();
+        assert!(!hunger.is_hungry());
+        assert_eq!(hunger.prey, None);
+    }
 }
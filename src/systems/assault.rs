@@ -0,0 +1,733 @@
+//! Assault System Module
+//!
+//! Assaults the player calls in with an allied clan. The clan sends a squad
+//! of its members to a rally point outside the nearest hostile target, either
+//! the camp of a clan that is neither allied nor subjugated or a territory
+//! overrun by infected. Once the player joins them there the squad storms
+//! the target alongside the player. Clearing the defenders weakens the
+//! defeated clan and hands the territory over to the player; every squad
+//! member lost is a loss for the allied clan.
+
+use crate::components::*;
+use crate::systems::combat::{CombatSystem, DamageEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Most members a clan sends on an assault
+const SQUAD_SIZE: usize = 4;
+/// Fewest members a clan must have free to answer the call
+const MIN_SQUAD_SIZE: usize = 2;
+/// How far outside the target the squad assembles
+const RALLY_DISTANCE: f32 = 120.0;
+/// The squad and the player must gather this close to the rally point
+const RALLY_RADIUS: f32 = 60.0;
+/// Seconds the squad waits at the rally point before going home
+const ASSEMBLE_SECONDS: f32 = 120.0;
+/// Longest an assault lasts before the squad withdraws
+const ASSAULT_SECONDS: f32 = 90.0;
+/// Reach of a clan camp's defence around its leader
+const CAMP_RADIUS: f32 = 150.0;
+/// Squad movement speed
+const SQUAD_SPEED: f32 = 110.0;
+/// Distance at which squad members and defenders trade blows
+const ENGAGE_RANGE: f32 = 35.0;
+/// Strength lost by a clan whose camp falls
+const CAMP_STRENGTH_LOSS: f32 = 0.3;
+/// Fear of the player gained by a clan whose camp falls
+const CAMP_FEAR_GAIN: f32 = 0.2;
+/// Strength and trust gained by an ally after a victory
+const VICTORY_STRENGTH_GAIN: f32 = 0.1;
+const VICTORY_TRUST_GAIN: f32 = 0.1;
+/// Trust lost by an ally whose squad is wiped out or left waiting
+const DEFEAT_TRUST_LOSS: f32 = 0.1;
+
+/// What an assault is called against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AssaultTarget {
+    /// The camp of a clan that is neither allied nor subjugated
+    ClanCamp(String),
+    /// A territory not yet captured with infected roaming it
+    InfestedTerritory(String),
+}
+
+impl AssaultTarget {
+    pub fn display_name(&self) -> String {
+        match self {
+            AssaultTarget::ClanCamp(clan_name) => format!("the {} camp", clan_name),
+            AssaultTarget::InfestedTerritory(territory) => territory.clone(),
+        }
+    }
+}
+
+/// Where an assault has got to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssaultPhase {
+    /// The squad is gathering at the rally point and waiting for the player
+    Assembling,
+    /// The squad is storming the target
+    Attacking,
+}
+
+/// An assault under way
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assault {
+    pub ally: String,
+    pub target: AssaultTarget,
+    /// Centre of the target and the reach of its defence
+    pub objective: Position,
+    pub radius: f32,
+    pub rally_point: Position,
+    pub squad: Vec<u32>,
+    pub phase: AssaultPhase,
+    /// Seconds spent in the current phase
+    pub elapsed: f32,
+}
+
+impl Assault {
+    /// What the assault is waiting on, for the HUD
+    pub fn status(&self) -> String {
+        match self.phase {
+            AssaultPhase::Assembling => format!(
+                "The {} are assembling. Join them at the rally point ({:.0}s)",
+                self.ally,
+                (ASSEMBLE_SECONDS - self.elapsed).max(0.0)
+            ),
+            AssaultPhase::Attacking => format!(
+                "The {} are storming {} ({:.0}s)",
+                self.ally,
+                self.target.display_name(),
+                (ASSAULT_SECONDS - self.elapsed).max(0.0)
+            ),
+        }
+    }
+}
+
+/// The assault under way, if any, and when each clan was last called on
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssaultTracker {
+    pub active: Option<Assault>,
+    /// Day each clan last answered a call; a clan answers once a day
+    pub last_called: HashMap<String, u32>,
+}
+
+/// How an assault ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssaultOutcome {
+    /// Every defender fell
+    Victory,
+    /// Every squad member fell
+    Defeat,
+    /// Time ran out with both sides still standing
+    Withdrawn,
+    /// The player never reached the rally point
+    Disbanded,
+}
+
+/// Assault news for the message log
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssaultEvent {
+    /// A call the clan could not answer, and why
+    Refused(String),
+    Called {
+        ally: String,
+        target: String,
+        squad_size: usize,
+    },
+    Begun {
+        ally: String,
+        target: String,
+    },
+    Ended {
+        ally: String,
+        target: String,
+        outcome: AssaultOutcome,
+        casualties: u32,
+        /// Territory handed to the player by a victory
+        territory: Option<String>,
+    },
+}
+
+impl AssaultEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            AssaultEvent::Refused(reason) => reason.clone(),
+            AssaultEvent::Called {
+                ally,
+                target,
+                squad_size,
+            } => format!(
+                "The {} send {} fighters against {}. Meet them at the rally point.",
+                ally, squad_size, target
+            ),
+            AssaultEvent::Begun { ally, target } => {
+                format!("The {} charge {} at your side!", ally, target)
+            }
+            AssaultEvent::Ended {
+                ally,
+                target,
+                outcome,
+                casualties,
+                territory,
+            } => {
+                let result = match outcome {
+                    AssaultOutcome::Victory => format!("{} has fallen", target),
+                    AssaultOutcome::Defeat => format!("The {} were cut down at {}", ally, target),
+                    AssaultOutcome::Withdrawn => {
+                        format!("The {} pull back from {}", ally, target)
+                    }
+                    AssaultOutcome::Disbanded => {
+                        format!("Tired of waiting, the {} go home", ally)
+                    }
+                };
+                let mut message = format!("{}. The {} lost {}.", result, ally, casualties);
+                if let Some(territory) = territory {
+                    message.push_str(&format!(" {} is yours.", territory));
+                }
+                message
+            }
+        }
+    }
+}
+
+/// Assault system responsible for joint attacks with allied clans
+pub struct AssaultSystem;
+
+impl AssaultSystem {
+    /// Ask an allied clan to send a squad against the hostile target
+    /// nearest the player, rallying inside `bounds`
+    #[allow(clippy::too_many_arguments)]
+    pub fn call(
+        tracker: &mut AssaultTracker,
+        clans: &HashMap<String, Clan>,
+        entities: &mut [GameEntity],
+        territories: &TerritoryManager,
+        bounds: &WorldBounds,
+        player_id: u32,
+        ally: &str,
+        current_day: u32,
+    ) -> AssaultEvent {
+        let refuse = |reason: String| AssaultEvent::Refused(reason);
+        let Some(player_pos) = EntityFinder::by_id(entities, player_id).map(|p| p.position) else {
+            return refuse("There is no one to lead the assault.".to_string());
+        };
+        if !clans.get(ally).is_some_and(|clan| clan.is_allied) {
+            return refuse(format!("The {} are not your allies.", ally));
+        }
+        if let Some(assault) = &tracker.active {
+            return refuse(format!(
+                "The {} are already marching on {}.",
+                assault.ally,
+                assault.target.display_name()
+            ));
+        }
+        if tracker.last_called.get(ally) == Some(&current_day) {
+            return refuse(format!("The {} have fought enough for one day.", ally));
+        }
+        let Some(camp) = Self::camp_of(entities, ally) else {
+            return refuse(format!("The {} have no leader to send them.", ally));
+        };
+        let Some((target, objective, radius)) =
+            Self::nearest_target(clans, entities, territories, player_pos, ally)
+        else {
+            return refuse("There is nothing left worth assaulting.".to_string());
+        };
+
+        let mut free: Vec<(f32, usize)> = entities
+            .iter()
+            .enumerate()
+            .filter(|(_, entity)| {
+                matches!(&entity.entity_type, EntityType::ClanMember(name) if name == ally)
+                    && matches!(entity.ai_state, AIState::Idle | AIState::Wander)
+                    && entity.health.as_ref().is_some_and(Health::is_alive)
+            })
+            .map(|(index, entity)| (entity.position.distance_to(&camp), index))
+            .collect();
+        if free.len() < MIN_SQUAD_SIZE {
+            return refuse(format!("The {} cannot spare the fighters.", ally));
+        }
+        free.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let squad: Vec<u32> = free
+            .into_iter()
+            .take(SQUAD_SIZE)
+            .map(|(_, index)| {
+                entities[index].ai_state = AIState::Squad;
+                entities[index].id
+            })
+            .collect();
+
+        // Gather on the side of the target facing the player
+        let distance = objective.distance_to(&player_pos).max(1.0);
+        let reach = radius + RALLY_DISTANCE;
+        let rally_point = bounds.clamp(Position::new(
+            objective.x + (player_pos.x - objective.x) / distance * reach,
+            objective.y + (player_pos.y - objective.y) / distance * reach,
+        ));
+
+        let event = AssaultEvent::Called {
+            ally: ally.to_string(),
+            target: target.display_name(),
+            squad_size: squad.len(),
+        };
+        tracker.last_called.insert(ally.to_string(), current_day);
+        tracker.active = Some(Assault {
+            ally: ally.to_string(),
+            target,
+            objective,
+            radius,
+            rally_point,
+            squad,
+            phase: AssaultPhase::Assembling,
+            elapsed: 0.0,
+        });
+        event
+    }
+
+    /// March the squad to the rally point, storm the target once the player
+    /// arrives, and settle the outcome when one side is broken
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        tracker: &mut AssaultTracker,
+        clans: &mut HashMap<String, Clan>,
+        entities: &mut [GameEntity],
+        territories: &mut TerritoryManager,
+        bounds: &WorldBounds,
+        player_id: u32,
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+        delta_time: f32,
+    ) -> Vec<AssaultEvent> {
+        let Some(assault) = tracker.active.as_mut() else {
+            return Vec::new();
+        };
+        let Some(player_pos) = EntityFinder::by_id(entities, player_id).map(|p| p.position) else {
+            return Vec::new();
+        };
+        assault.elapsed += delta_time;
+
+        let outcome = match assault.phase {
+            AssaultPhase::Assembling => {
+                let rally_point = assault.rally_point;
+                let squad = assault.squad.clone();
+                for id in &squad {
+                    Self::march(
+                        entities,
+                        *id,
+                        bounds,
+                        rally_point,
+                        RALLY_RADIUS / 2.0,
+                        delta_time,
+                    );
+                }
+                let gathered = Self::living(entities, &squad)
+                    .all(|entity| entity.position.distance_to(&rally_point) <= RALLY_RADIUS);
+                if gathered && player_pos.distance_to(&rally_point) <= RALLY_RADIUS {
+                    assault.phase = AssaultPhase::Attacking;
+                    assault.elapsed = 0.0;
+                    // The defenders see the attack coming and turn on the player
+                    for index in Self::defenders(assault, entities) {
+                        if matches!(entities[index].ai_state, AIState::Idle | AIState::Wander) {
+                            entities[index].ai_state = AIState::Hostile;
+                        }
+                    }
+                    return vec![AssaultEvent::Begun {
+                        ally: assault.ally.clone(),
+                        target: assault.target.display_name(),
+                    }];
+                }
+                (assault.elapsed >= ASSEMBLE_SECONDS).then_some(AssaultOutcome::Disbanded)
+            }
+            AssaultPhase::Attacking => {
+                Self::fight(
+                    assault,
+                    entities,
+                    bounds,
+                    game_time,
+                    damage_events,
+                    delta_time,
+                );
+                if Self::defenders(assault, entities).is_empty() {
+                    Some(AssaultOutcome::Victory)
+                } else if Self::living(entities, &assault.squad).next().is_none() {
+                    Some(AssaultOutcome::Defeat)
+                } else {
+                    (assault.elapsed >= ASSAULT_SECONDS).then_some(AssaultOutcome::Withdrawn)
+                }
+            }
+        };
+
+        let Some(outcome) = outcome else {
+            return Vec::new();
+        };
+        let Some(assault) = tracker.active.take() else {
+            return Vec::new();
+        };
+        vec![Self::conclude(
+            assault,
+            outcome,
+            clans,
+            entities,
+            territories,
+        )]
+    }
+
+    /// Send the survivors home and shift clan strength and territory
+    /// according to the outcome
+    fn conclude(
+        assault: Assault,
+        outcome: AssaultOutcome,
+        clans: &mut HashMap<String, Clan>,
+        entities: &mut [GameEntity],
+        territories: &mut TerritoryManager,
+    ) -> AssaultEvent {
+        let survivors = Self::living(entities, &assault.squad).count();
+        let casualties = (assault.squad.len() - survivors) as u32;
+        for entity in entities.iter_mut() {
+            if assault.squad.contains(&entity.id) && matches!(entity.ai_state, AIState::Squad) {
+                entity.ai_state = AIState::Idle;
+                entity.velocity = Some(Velocity::zero());
+            }
+        }
+
+        let mut territory = None;
+        match outcome {
+            AssaultOutcome::Victory => {
+                if let AssaultTarget::ClanCamp(clan_name) = &assault.target {
+                    if let Some(clan) = clans.get_mut(clan_name) {
+                        clan.strength = (clan.strength - CAMP_STRENGTH_LOSS).max(0.1);
+                        clan.fear_of_player = (clan.fear_of_player + CAMP_FEAR_GAIN).min(1.0);
+                    }
+                }
+                if let Some(ally) = clans.get_mut(&assault.ally) {
+                    ally.strength += VICTORY_STRENGTH_GAIN;
+                    ally.trust_towards_player =
+                        (ally.trust_towards_player + VICTORY_TRUST_GAIN).min(1.0);
+                }
+                // The land around the target changes hands
+                let held = territories.territories.iter_mut().find(|held| {
+                    !held.captured
+                        && match &assault.target {
+                            AssaultTarget::InfestedTerritory(name) => held.name == *name,
+                            AssaultTarget::ClanCamp(_) => held.contains(&assault.objective),
+                        }
+                });
+                if let Some(held) = held {
+                    held.captured = true;
+                    held.hold_hours = Territory::HOURS_TO_CAPTURE;
+                    territory = Some(held.name.clone());
+                }
+            }
+            AssaultOutcome::Defeat | AssaultOutcome::Disbanded => {
+                if let Some(ally) = clans.get_mut(&assault.ally) {
+                    ally.trust_towards_player =
+                        (ally.trust_towards_player - DEFEAT_TRUST_LOSS).max(-1.0);
+                }
+            }
+            AssaultOutcome::Withdrawn => {}
+        }
+
+        AssaultEvent::Ended {
+            ally: assault.ally,
+            target: assault.target.display_name(),
+            outcome,
+            casualties,
+            territory,
+        }
+    }
+
+    /// The hostile target nearest the player, with its centre and reach
+    fn nearest_target(
+        clans: &HashMap<String, Clan>,
+        entities: &[GameEntity],
+        territories: &TerritoryManager,
+        player_pos: Position,
+        ally: &str,
+    ) -> Option<(AssaultTarget, Position, f32)> {
+        let camps = clans
+            .values()
+            .filter(|clan| clan.name != ally && !clan.is_allied && !clan.is_defeated)
+            .filter_map(|clan| {
+                let camp = Self::camp_of(entities, &clan.name)?;
+                Some((
+                    AssaultTarget::ClanCamp(clan.name.clone()),
+                    camp,
+                    CAMP_RADIUS,
+                ))
+            });
+        let infested = territories
+            .territories
+            .iter()
+            .filter(|territory| {
+                !territory.captured
+                    && entities.iter().any(|entity| {
+                        matches!(entity.entity_type, EntityType::HostileInfected)
+                            && entity.health.as_ref().is_some_and(Health::is_alive)
+                            && territory.contains(&entity.position)
+                    })
+            })
+            .map(|territory| {
+                (
+                    AssaultTarget::InfestedTerritory(territory.name.clone()),
+                    territory.center,
+                    territory.radius,
+                )
+            });
+
+        camps.chain(infested).min_by(|a, b| {
+            a.1.distance_to(&player_pos)
+                .total_cmp(&b.1.distance_to(&player_pos))
+        })
+    }
+
+    /// Where a clan's living leader holds camp
+    fn camp_of(entities: &[GameEntity], clan_name: &str) -> Option<Position> {
+        entities
+            .iter()
+            .find(|entity| {
+                matches!(&entity.entity_type, EntityType::ClanLeader(name) if name == clan_name)
+                    && entity.health.as_ref().is_some_and(Health::is_alive)
+            })
+            .map(|leader| leader.position)
+    }
+
+    /// Indices of the living defenders of the target. A camp is defended by
+    /// its members; its leader is left for the player to deal with.
+    fn defenders(assault: &Assault, entities: &[GameEntity]) -> Vec<usize> {
+        entities
+            .iter()
+            .enumerate()
+            .filter(|(_, entity)| {
+                let defends = match (&assault.target, &entity.entity_type) {
+                    (AssaultTarget::ClanCamp(clan_name), EntityType::ClanMember(name)) => {
+                        name == clan_name && !matches!(entity.ai_state, AIState::Follower(_))
+                    }
+                    (AssaultTarget::InfestedTerritory(_), EntityType::HostileInfected) => true,
+                    _ => false,
+                };
+                defends
+                    && entity.health.as_ref().is_some_and(Health::is_alive)
+                    && entity.position.distance_to(&assault.objective) <= assault.radius
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Each squad member closes on the nearest defender and strikes it, and
+    /// defenders strike back at squad members in reach
+    fn fight(
+        assault: &Assault,
+        entities: &mut [GameEntity],
+        bounds: &WorldBounds,
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+        delta_time: f32,
+    ) {
+        let defenders: Vec<(u32, Position)> = Self::defenders(assault, entities)
+            .into_iter()
+            .map(|index| (entities[index].id, entities[index].position))
+            .collect();
+        let nearest = |position: Position, others: &[(u32, Position)]| {
+            others.iter().copied().min_by(|a, b| {
+                a.1.distance_to(&position)
+                    .total_cmp(&b.1.distance_to(&position))
+            })
+        };
+
+        for id in &assault.squad {
+            let Some(position) = Self::living(entities, &[*id]).next().map(|e| e.position) else {
+                continue;
+            };
+            let Some((target_id, target_pos)) = nearest(position, &defenders) else {
+                continue;
+            };
+            if position.distance_to(&target_pos) <= ENGAGE_RANGE {
+                CombatSystem::resolve_attack(entities, *id, target_id, game_time, damage_events);
+            } else {
+                Self::march(entities, *id, bounds, target_pos, ENGAGE_RANGE, delta_time);
+            }
+        }
+
+        let squad: Vec<(u32, Position)> = Self::living(entities, &assault.squad)
+            .map(|entity| (entity.id, entity.position))
+            .collect();
+        for (id, position) in defenders {
+            if let Some((target_id, target_pos)) = nearest(position, &squad) {
+                if position.distance_to(&target_pos) <= ENGAGE_RANGE {
+                    CombatSystem::resolve_attack(entities, id, target_id, game_time, damage_events);
+                }
+            }
+        }
+    }
+
+    /// Move a squad member towards a destination, stopping within reach
+    fn march(
+        entities: &mut [GameEntity],
+        id: u32,
+        bounds: &WorldBounds,
+        destination: Position,
+        stop_distance: f32,
+        delta_time: f32,
+    ) {
+        let Some(entity) = entities
+            .iter_mut()
+            .find(|entity| entity.id == id && matches!(entity.ai_state, AIState::Squad))
+        else {
+            return;
+        };
+        let distance = entity.position.distance_to(&destination);
+        let velocity = if distance <= stop_distance {
+            Velocity::zero()
+        } else {
+            Velocity::new(
+                (destination.x - entity.position.x) / distance * SQUAD_SPEED,
+                (destination.y - entity.position.y) / distance * SQUAD_SPEED,
+            )
+        };
+        entity.position = bounds.clamp(Position::new(
+            entity.position.x + velocity.x * delta_time,
+            entity.position.y + velocity.y * delta_time,
+        ));
+        entity.velocity = Some(velocity);
+    }
+
+    /// Squad members still standing
+    fn living<'a>(
+        entities: &'a [GameEntity],
+        squad: &'a [u32],
+    ) -> impl Iterator<Item = &'a GameEntity> {
+        entities.iter().filter(|entity| {
+            squad.contains(&entity.id) && entity.health.as_ref().is_some_and(Health::is_alive)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+    use macroquad::prelude::WHITE;
+
+    #[test]
+    fn test_allied_squad_rallies_with_player_and_takes_camp() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        let mut clans = HashMap::new();
+        WorldSystem::initialize_clans(&mut clans);
+        WorldSystem::spawn_all_clan_leaders(&mut entities, &mut ids);
+        let camp =
+            |entities: &[GameEntity], clan: &str| AssaultSystem::camp_of(entities, clan).unwrap();
+        let ally_camp = camp(&entities, "Night-Bloods");
+        for _ in 0..SQUAD_SIZE {
+            WorldSystem::spawn_clan_member(
+                &mut entities,
+                &mut ids,
+                "Night-Bloods",
+                ally_camp.x,
+                ally_camp.y,
+                WHITE,
+            );
+        }
+        let mut territories = WorldSystem::place_territories();
+        for territory in &mut territories.territories {
+            territory.captured = true;
+        }
+
+        // Only allies answer, and only with a target to march on
+        let mut tracker = AssaultTracker::default();
+        let event = AssaultSystem::call(
+            &mut tracker,
+            &clans,
+            &mut entities,
+            &territories,
+            &WorldBounds::default(),
+            0,
+            "Night-Bloods",
+            1,
+        );
+        assert!(matches!(event, AssaultEvent::Refused(_)));
+        for clan in clans.values_mut() {
+            clan.is_allied = clan.name != "Bone-Eaters";
+        }
+        let target_camp = camp(&entities, "Bone-Eaters");
+        entities[0].position = Position::new(target_camp.x, target_camp.y + 500.0);
+        let event = AssaultSystem::call(
+            &mut tracker,
+            &clans,
+            &mut entities,
+            &territories,
+            &WorldBounds::default(),
+            0,
+            "Night-Bloods",
+            1,
+        );
+        assert!(matches!(
+            event,
+            AssaultEvent::Called {
+                squad_size: SQUAD_SIZE,
+                ..
+            }
+        ));
+        let assault = tracker.active.clone().unwrap();
+        assert_eq!(
+            assault.target,
+            AssaultTarget::ClanCamp("Bone-Eaters".to_string())
+        );
+
+        // The squad waits at the rally point until the player arrives
+        let mut damage_events = Vec::new();
+        let mut events = Vec::new();
+        for step in 0..200 {
+            events.extend(AssaultSystem::update(
+                &mut tracker,
+                &mut clans,
+                &mut entities,
+                &mut territories,
+                &WorldBounds::default(),
+                0,
+                step as f32 * 0.1,
+                &mut damage_events,
+                0.1,
+            ));
+        }
+        assert!(events.is_empty());
+        entities[0].position = assault.rally_point;
+
+        // One lone defender holds the camp
+        WorldSystem::spawn_clan_member(
+            &mut entities,
+            &mut ids,
+            "Bone-Eaters",
+            target_camp.x + 20.0,
+            target_camp.y,
+            WHITE,
+        );
+        let strength = clans["Bone-Eaters"].strength;
+        for step in 0..900 {
+            events.extend(AssaultSystem::update(
+                &mut tracker,
+                &mut clans,
+                &mut entities,
+                &mut territories,
+                &WorldBounds::default(),
+                0,
+                20.0 + step as f32 * 0.1,
+                &mut damage_events,
+                0.1,
+            ));
+        }
+        assert!(matches!(events[0], AssaultEvent::Begun { .. }));
+        assert!(matches!(
+            events[1],
+            AssaultEvent::Ended {
+                outcome: AssaultOutcome::Victory,
+                ..
+            }
+        ));
+        assert!(tracker.active.is_none());
+        assert!(clans["Bone-Eaters"].strength < strength);
+        assert!(!entities
+            .iter()
+            .any(|entity| matches!(entity.ai_state, AIState::Squad)));
+    }
+}
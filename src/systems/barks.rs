@@ -0,0 +1,303 @@
+//! Barks System Module
+//!
+//! Short lines creatures call out as their mood changes, shown in speech
+//! bubbles above their heads. Clan vampires greet the player as they come
+//! near, warmly or coldly depending on how far their clan trusts them;
+//! infected snarl when they turn on their prey; animals squeal as they bolt.
+//! Barks follow AI state changes rather than firing every frame, and each
+//! creature rests a while between lines so a crowd never turns into a din.
+
+use crate::components::*;
+use std::collections::{HashMap, HashSet};
+
+/// Seconds a bark stays above its speaker
+pub const BARK_LIFETIME: f32 = 2.5;
+/// Seconds over which a bark fades before it goes
+const BARK_FADE: f32 = 0.6;
+/// Seconds a creature keeps quiet after barking
+const BARK_COOLDOWN: f32 = 8.0;
+/// Creatures farther than this from the player go unheard
+const BARK_RANGE: f32 = 500.0;
+/// Clan vampires greet the player once they come this close
+const GREETING_RANGE: f32 = 120.0;
+/// Most bubbles shown at once; the oldest make way for new ones
+const MAX_BARKS: usize = 12;
+
+/// What prompted a creature to speak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarkKind {
+    /// A clan vampire greeting the player, by how much their clan trusts them
+    Greeting(Regard),
+    /// A clan vampire turning on the player
+    Threat,
+    /// A clan vampire taking up the player's orders
+    Obey,
+    /// An infected catching the scent of prey
+    Snarl,
+    /// An animal fleeing
+    Squeal,
+}
+
+/// How a clan regards the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regard {
+    Allied,
+    Friendly,
+    Wary,
+    Hostile,
+}
+
+impl Regard {
+    pub fn of(clan: &Clan) -> Self {
+        if clan.is_hostile {
+            Regard::Hostile
+        } else if clan.is_allied {
+            Regard::Allied
+        } else if clan.trust_towards_player >= 0.3 {
+            Regard::Friendly
+        } else {
+            Regard::Wary
+        }
+    }
+}
+
+impl BarkKind {
+    fn lines(&self) -> &'static [&'static str] {
+        match self {
+            BarkKind::Greeting(Regard::Allied) => {
+                &["My liege.", "The night is yours.", "We stand with you."]
+            }
+            BarkKind::Greeting(Regard::Friendly) => {
+                &["Good hunting.", "Well met, kindred.", "Evening."]
+            }
+            BarkKind::Greeting(Regard::Wary) => &["Keep walking.", "What do you want?", "Hm."],
+            BarkKind::Greeting(Regard::Hostile) | BarkKind::Threat => {
+                &["You'll pay for that!", "Blood for blood!", "Get them!"]
+            }
+            BarkKind::Obey => &["As you wish.", "Lead on.", "At your side."],
+            BarkKind::Snarl => &["Grrraaah!", "Hhhnnngh...", "RRRAAGH!"],
+            BarkKind::Squeal => &["Squeak!", "Eeek!", "*squeal*"],
+        }
+    }
+
+    /// Bubble tint, so a glance tells friend from foe
+    pub fn is_threat(&self) -> bool {
+        matches!(
+            self,
+            BarkKind::Greeting(Regard::Hostile) | BarkKind::Threat | BarkKind::Snarl
+        )
+    }
+}
+
+/// A line hanging above the creature that spoke it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bark {
+    pub entity_id: u32,
+    pub kind: BarkKind,
+    pub text: &'static str,
+    /// Seconds since it was spoken
+    pub age: f32,
+}
+
+impl Bark {
+    /// Opacity, fading out at the end of its life
+    pub fn alpha(&self) -> f32 {
+        ((BARK_LIFETIME - self.age) / BARK_FADE).clamp(0.0, 1.0)
+    }
+}
+
+/// The mood a bark answers to; changes between these are what set
+/// creatures talking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mood {
+    Calm,
+    /// A clan vampire with the player close by
+    Near,
+    Hostile,
+    Fleeing,
+    Following,
+    /// Dead, staggered or otherwise beyond speech
+    Silent,
+}
+
+/// Bubbles on screen and what each creature was last doing
+#[derive(Debug, Clone, Default)]
+pub struct Barks {
+    pub active: Vec<Bark>,
+    moods: HashMap<u32, Mood>,
+    /// Game time each creature last spoke
+    last_spoken: HashMap<u32, f32>,
+    /// Lines spoken so far, to vary the next
+    spoken: usize,
+}
+
+/// Barks system responsible for creature chatter
+pub struct BarkSystem;
+
+impl BarkSystem {
+    /// Age the bubbles on screen, then let creatures whose mood changed
+    /// near the player speak up
+    pub fn update(
+        barks: &mut Barks,
+        entities: &[GameEntity],
+        clans: &HashMap<String, Clan>,
+        player_id: u32,
+        game_time: f32,
+        delta_time: f32,
+    ) {
+        for bark in &mut barks.active {
+            bark.age += delta_time;
+        }
+        let present: HashSet<u32> = entities.iter().map(|entity| entity.id).collect();
+        barks
+            .active
+            .retain(|bark| bark.age < BARK_LIFETIME && present.contains(&bark.entity_id));
+        barks.moods.retain(|id, _| present.contains(id));
+
+        let Some(player_position) = EntityFinder::by_id(entities, player_id).map(|p| p.position)
+        else {
+            return;
+        };
+        for entity in entities.iter().filter(|entity| entity.id != player_id) {
+            let distance = entity.position.distance_to(&player_position);
+            let mood = Self::mood(entity, distance);
+            let previous = barks.moods.insert(entity.id, mood);
+            // Creatures first seen this frame settle in without a word
+            if previous.is_none_or(|previous| previous == mood) || distance > BARK_RANGE {
+                continue;
+            }
+            let Some(kind) = Self::bark_for(entity, mood, clans) else {
+                continue;
+            };
+            if barks
+                .last_spoken
+                .get(&entity.id)
+                .is_some_and(|last| game_time - last < BARK_COOLDOWN)
+            {
+                continue;
+            }
+            let lines = kind.lines();
+            let text = lines[(entity.id as usize + barks.spoken) % lines.len()];
+            barks.spoken += 1;
+            barks.last_spoken.insert(entity.id, game_time);
+            barks.active.retain(|bark| bark.entity_id != entity.id);
+            barks.active.push(Bark {
+                entity_id: entity.id,
+                kind,
+                text,
+                age: 0.0,
+            });
+        }
+        let excess = barks.active.len().saturating_sub(MAX_BARKS);
+        barks.active.drain(..excess);
+    }
+
+    fn mood(entity: &GameEntity, distance: f32) -> Mood {
+        let alive = entity.health.as_ref().is_some_and(|h| h.is_alive());
+        match &entity.ai_state {
+            _ if !alive => Mood::Silent,
+            AIState::Dead | AIState::Staggered(_) => Mood::Silent,
+            AIState::Hostile => Mood::Hostile,
+            AIState::Fleeing => Mood::Fleeing,
+            AIState::Follower(_) => Mood::Following,
+            AIState::Idle | AIState::Wander | AIState::Squad => {
+                let clan_vampire = matches!(
+                    entity.entity_type,
+                    EntityType::ClanMember(_) | EntityType::ClanLeader(_)
+                );
+                if clan_vampire && distance <= GREETING_RANGE {
+                    Mood::Near
+                } else {
+                    Mood::Calm
+                }
+            }
+        }
+    }
+
+    /// What a creature says on entering a mood, if anything
+    fn bark_for(
+        entity: &GameEntity,
+        mood: Mood,
+        clans: &HashMap<String, Clan>,
+    ) -> Option<BarkKind> {
+        match (&entity.entity_type, mood) {
+            (EntityType::ClanMember(clan) | EntityType::ClanLeader(clan), Mood::Near) => clans
+                .get(clan)
+                .map(|clan| BarkKind::Greeting(Regard::of(clan))),
+            (EntityType::ClanMember(_) | EntityType::ClanLeader(_), Mood::Hostile) => {
+                Some(BarkKind::Threat)
+            }
+            (EntityType::ClanMember(_) | EntityType::ClanLeader(_), Mood::Following) => {
+                Some(BarkKind::Obey)
+            }
+            (EntityType::HostileInfected, Mood::Hostile) => Some(BarkKind::Snarl),
+            (EntityType::Animal, Mood::Fleeing) => Some(BarkKind::Squeal),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+    use macroquad::prelude::RED;
+
+    #[test]
+    fn test_creatures_bark_on_mood_changes_and_then_rest() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(100.0, 700.0);
+        let mut clans = HashMap::new();
+        let mut clan = Clan::new("Night-Bloods", "Mara", 5);
+        clan.is_allied = true;
+        clans.insert(clan.name.clone(), clan);
+        let member = WorldSystem::spawn_clan_member(
+            &mut entities,
+            &mut ids,
+            "Night-Bloods",
+            400.0,
+            700.0,
+            RED,
+        );
+        let infected = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 200.0, 700.0);
+        entities[2].ai_state = AIState::Idle;
+        let mut barks = Barks::default();
+
+        // Everyone is first seen without a word
+        BarkSystem::update(&mut barks, &entities, &clans, player_id, 0.0, 0.1);
+        assert!(barks.active.is_empty());
+
+        // The infected snarls as it turns; the clansman greets the player
+        // who walks up to him as his ally
+        entities[2].ai_state = AIState::Hostile;
+        entities[1].position = Position::new(150.0, 700.0);
+        BarkSystem::update(&mut barks, &entities, &clans, player_id, 1.0, 0.1);
+        let kinds: Vec<(u32, BarkKind)> = barks
+            .active
+            .iter()
+            .map(|bark| (bark.entity_id, bark.kind))
+            .collect();
+        assert!(kinds.contains(&(infected, BarkKind::Snarl)));
+        assert!(kinds.contains(&(member, BarkKind::Greeting(Regard::Allied))));
+
+        // Stepping away and back soon after draws no second greeting
+        entities[1].position = Position::new(400.0, 700.0);
+        BarkSystem::update(&mut barks, &entities, &clans, player_id, 2.0, 0.1);
+        entities[1].position = Position::new(150.0, 700.0);
+        BarkSystem::update(&mut barks, &entities, &clans, player_id, 3.0, 0.1);
+        assert_eq!(
+            barks
+                .active
+                .iter()
+                .filter(|bark| bark.entity_id == member)
+                .count(),
+            1
+        );
+
+        // Bubbles fade and go
+        BarkSystem::update(&mut barks, &entities, &clans, player_id, 6.0, BARK_LIFETIME);
+        assert!(barks.active.is_empty());
+    }
+}
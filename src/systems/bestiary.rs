@@ -0,0 +1,288 @@
+//! Bestiary System Module
+//!
+//! What the player has learned about the creatures of the night. Seeing a
+//! creature up close records its name and likeness; killing a few of them
+//! teaches its strength and weaknesses; and hunter's notes found around the
+//! world describe the stranger things it does. The codex shows each entry
+//! only as far as it has been learned.
+
+use crate::components::*;
+use crate::systems::spatial::SpatialGrid;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How close a creature must come for the player to get a good look at it
+const SIGHTING_RANGE: f32 = 150.0;
+/// Kills before a creature's strength and weaknesses are known
+pub const KILLS_TO_LEARN_STATS: u32 = 3;
+
+/// Fixed lore for one kind of creature
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CreatureLore {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub weaknesses: &'static str,
+    pub behavior: &'static str,
+}
+
+/// Every creature the bestiary covers, in codex order
+pub const CREATURES: [CreatureLore; 4] = [
+    CreatureLore {
+        id: "infected",
+        name: "Infected",
+        description: "Once human, now hollowed out by the plague and driven by hunger.",
+        weaknesses: "Slow to recover from a heavy blow. Their blood is thin and poor.",
+        behavior: "Left hungry they leave the roads to hunt the animals in the fields, \
+                   and they eat every scrap of what they kill.",
+    },
+    CreatureLore {
+        id: "animal",
+        name: "Wild Animal",
+        description: "Deer, hares and foxes grazing the meadows at the edge of the woods.",
+        weaknesses: "Barely able to fight back. Easy prey for anything patient.",
+        behavior: "They keep to a home range and bolt at the first sound of footsteps; \
+                   walk softly and they will graze on undisturbed.",
+    },
+    CreatureLore {
+        id: "clan_member",
+        name: "Clan Vampire",
+        description: "Lesser vampires sworn to one of the clans, camped around their leader.",
+        weaknesses: "Easily staggered. Without their leader they lose heart.",
+        behavior: "When clans fall out they send raiding parties to the borders of the \
+                   territories between their camps.",
+    },
+    CreatureLore {
+        id: "clan_leader",
+        name: "Clan Elder",
+        description: "Ancient vampires who hold a clan together by will and fear.",
+        weaknesses: "Hard to stagger, but slow to strike. Their blood is the richest of all.",
+        behavior: "An elder remembers every word spoken to them; trust earned in \
+                   conversation can turn a rival into an ally.",
+    },
+];
+
+/// What the player has learned about one kind of creature
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CreatureKnowledge {
+    pub encountered: bool,
+    pub kills: u32,
+    /// Strength of the first one killed, recorded once enough have fallen
+    pub stats: Option<CreatureStats>,
+    pub notes_read: bool,
+}
+
+impl CreatureKnowledge {
+    pub fn knows_stats(&self) -> bool {
+        self.kills >= KILLS_TO_LEARN_STATS && self.stats.is_some()
+    }
+}
+
+/// A creature's strength as learned in combat
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CreatureStats {
+    pub max_health: f32,
+    pub attack_power: f32,
+    pub defense: f32,
+}
+
+/// The player's bestiary, by creature id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bestiary {
+    pub creatures: HashMap<String, CreatureKnowledge>,
+}
+
+impl Bestiary {
+    pub fn knowledge(&self, creature_id: &str) -> Option<&CreatureKnowledge> {
+        self.creatures.get(creature_id)
+    }
+
+    /// Creatures the player has seen, in codex order
+    pub fn entries(&self) -> Vec<(&'static CreatureLore, &CreatureKnowledge)> {
+        CREATURES
+            .iter()
+            .filter_map(|lore| {
+                self.creatures
+                    .get(lore.id)
+                    .filter(|knowledge| knowledge.encountered)
+                    .map(|knowledge| (lore, knowledge))
+            })
+            .collect()
+    }
+}
+
+/// Something newly learned for the bestiary
+#[derive(Debug, Clone, PartialEq)]
+pub enum BestiaryEvent {
+    Encountered(&'static str),
+    StatsLearned(&'static str),
+    BehaviorLearned(&'static str),
+    /// Hunter's notes that told the player nothing new
+    NothingNew,
+}
+
+impl BestiaryEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            BestiaryEvent::Encountered(name) => {
+                format!("Bestiary: {} added to the codex.", name)
+            }
+            BestiaryEvent::StatsLearned(name) => {
+                format!("Bestiary: you have learned the strength of the {}.", name)
+            }
+            BestiaryEvent::BehaviorLearned(name) => {
+                format!("Bestiary: the hunter's notes describe the {}.", name)
+            }
+            BestiaryEvent::NothingNew => "The hunter's notes tell you nothing new.".to_string(),
+        }
+    }
+}
+
+/// Bestiary system responsible for learning about creatures
+pub struct BestiarySystem;
+
+impl BestiarySystem {
+    /// Bestiary lore for a kind of entity, if it is a creature
+    pub fn lore_for(entity_type: &EntityType) -> Option<&'static CreatureLore> {
+        let id = match entity_type {
+            EntityType::HostileInfected => "infected",
+            EntityType::Animal => "animal",
+            EntityType::ClanMember(_) => "clan_member",
+            EntityType::ClanLeader(_) => "clan_leader",
+            EntityType::Player | EntityType::Shelter | EntityType::Pickup => return None,
+        };
+        CREATURES.iter().find(|lore| lore.id == id)
+    }
+
+    /// Record creatures close enough to the player to get a good look at
+    pub fn observe(
+        bestiary: &mut Bestiary,
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+    ) -> Vec<BestiaryEvent> {
+        let Some(player_pos) = EntityFinder::by_id(entities, player_id).map(|p| p.position) else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        for index in grid.query_radius(entities, player_pos, SIGHTING_RANGE) {
+            let Some(lore) = Self::lore_for(&entities[index].entity_type) else {
+                continue;
+            };
+            let knowledge = bestiary.creatures.entry(lore.id.to_string()).or_default();
+            if !knowledge.encountered {
+                knowledge.encountered = true;
+                events.push(BestiaryEvent::Encountered(lore.name));
+            }
+        }
+        events
+    }
+
+    /// Count a creature killed by the player, learning its strength once
+    /// enough of its kind have fallen
+    pub fn record_kill(bestiary: &mut Bestiary, victim: &GameEntity) -> Option<BestiaryEvent> {
+        let lore = Self::lore_for(&victim.entity_type)?;
+        let knowledge = bestiary.creatures.entry(lore.id.to_string()).or_default();
+        // Whatever was fought was certainly seen
+        knowledge.encountered = true;
+        knowledge.kills += 1;
+        if knowledge.stats.is_none() {
+            knowledge.stats = victim.health.as_ref().map(|health| {
+                let (attack_power, defense) = victim
+                    .combat_stats
+                    .as_ref()
+                    .map_or((0.0, 0.0), |stats| (stats.attack_power, stats.defense));
+                CreatureStats {
+                    max_health: health.max,
+                    attack_power,
+                    defense,
+                }
+            });
+        }
+        (knowledge.kills == KILLS_TO_LEARN_STATS).then_some(BestiaryEvent::StatsLearned(lore.name))
+    }
+
+    /// Read a set of hunter's notes, learning the behaviour of a creature
+    /// not yet studied, preferring ones the player has already seen
+    pub fn read_notes(bestiary: &mut Bestiary) -> BestiaryEvent {
+        let unread = |seen: bool| {
+            CREATURES.iter().find(|lore| {
+                let knowledge = bestiary.creatures.get(lore.id);
+                !knowledge.is_some_and(|k| k.notes_read)
+                    && knowledge.is_some_and(|k| k.encountered) == seen
+            })
+        };
+        let Some(lore) = unread(true).or_else(|| unread(false)) else {
+            return BestiaryEvent::NothingNew;
+        };
+        bestiary
+            .creatures
+            .entry(lore.id.to_string())
+            .or_default()
+            .notes_read = true;
+        BestiaryEvent::BehaviorLearned(lore.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+
+    #[test]
+    fn test_bestiary_unlocks_by_sighting_kills_and_notes() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        let player_pos = entities[0].position;
+        WorldSystem::spawn_hostile_infected(
+            &mut entities,
+            &mut ids,
+            player_pos.x + 50.0,
+            player_pos.y,
+        );
+        WorldSystem::spawn_animal(
+            &mut entities,
+            &mut ids,
+            Species::Deer,
+            player_pos.x + 600.0,
+            player_pos.y,
+        );
+        let mut bestiary = Bestiary::default();
+
+        // Only the nearby infected is seen
+        let grid = SpatialGrid::from_entities(&entities);
+        let events = BestiarySystem::observe(&mut bestiary, &entities, &grid, 0);
+        assert_eq!(events, vec![BestiaryEvent::Encountered("Infected")]);
+        assert!(BestiarySystem::observe(&mut bestiary, &entities, &grid, 0).is_empty());
+        assert_eq!(bestiary.entries().len(), 1);
+
+        // Its strength is learned on the third kill
+        let infected = entities[1].clone();
+        assert_eq!(BestiarySystem::record_kill(&mut bestiary, &infected), None);
+        assert_eq!(BestiarySystem::record_kill(&mut bestiary, &infected), None);
+        assert!(!bestiary.knowledge("infected").unwrap().knows_stats());
+        assert_eq!(
+            BestiarySystem::record_kill(&mut bestiary, &infected),
+            Some(BestiaryEvent::StatsLearned("Infected"))
+        );
+        let stats = bestiary.knowledge("infected").unwrap().stats.unwrap();
+        assert_eq!(stats.max_health, 50.0);
+
+        // Notes describe creatures already seen first, then the rest
+        assert_eq!(
+            BestiarySystem::read_notes(&mut bestiary),
+            BestiaryEvent::BehaviorLearned("Infected")
+        );
+        for _ in 1..CREATURES.len() {
+            BestiarySystem::read_notes(&mut bestiary);
+        }
+        assert_eq!(
+            BestiarySystem::read_notes(&mut bestiary),
+            BestiaryEvent::NothingNew
+        );
+        // Reading about a creature is not the same as seeing one
+        assert_eq!(bestiary.entries().len(), 1);
+    }
+}
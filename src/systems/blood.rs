@@ -4,27 +4,79 @@
 //! This system manages the core vampire survival mechanics around blood consumption.
 
 use crate::components::*;
+use crate::settings::Difficulty;
+use crate::systems::events::{EventBus, GameEvent};
+use crate::systems::exposure::{ExposureSystem, Shadow};
+use crate::systems::WorldSystem;
+use crate::world_data::WorldData;
 use macroquad::prelude::*;
 
+/// Corpse blood that fully sates a ravenous infected
+const INFECTED_MEAL_BLOOD: f32 = 12.0;
+
+/// Share of a living creature's health taken by a non-lethal drain
+pub const DRAIN_SHARE: f32 = 0.4;
+
+/// Seconds a drained creature lies unconscious
+pub const UNCONSCIOUS_SECONDS: f32 = 20.0;
+
+/// How many times faster vampires lose blood while in running water
+const RUNNING_WATER_DRAIN: f32 = 4.0;
+
+/// Blood taken by a non-lethal drain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drain {
+    pub quality: BloodQuality,
+    /// Blood drawn from the victim
+    pub raw: f32,
+    /// Blood the feeder actually absorbed once sated
+    pub absorbed: f32,
+    /// Blood that spilled over a full meter
+    pub overflow: f32,
+}
+
 /// Blood system responsible for blood mechanics and vampire survival
 pub struct BloodSystem;
 
 impl BloodSystem {
-    /// Update blood system for all entities
+    /// Update blood system for all entities, with drain and sunlight damage
+    /// scaled for the difficulty. Vampires burned by sunlight are reported
+    /// on the event bus.
     pub fn update_blood_system(
         entities: &mut Vec<GameEntity>,
         is_day: bool,
         sunlight_intensity: f32,
+        shadows: &[Shadow],
+        difficulty: Difficulty,
+        events: &mut EventBus,
         delta_time: f32,
     ) {
         for entity in entities.iter_mut() {
+            // Bodies lose blood the longer they lie
+            if let Some(corpse) = &mut entity.corpse {
+                corpse.decay(delta_time);
+            }
+
+            // Buffs from rich blood wear off
+            if let Some(buffs) = &mut entity.buffs {
+                buffs.tick(delta_time);
+            }
+
             if let Some(blood_meter) = &mut entity.blood_meter {
                 // Drain blood over time
-                Self::update_blood_drain(blood_meter, delta_time);
+                Self::update_blood_drain(
+                    blood_meter,
+                    difficulty.blood_drain_multiplier(),
+                    delta_time,
+                );
+                blood_meter.feeding.update(delta_time);
 
                 // Sunlight damage is now handled by the new shelter-aware function
                 // after the main entity loop to avoid borrowing issues
 
+                // Blood-starved vampires look pale
+                entity.visual_state.starving = blood_meter.is_starving();
+
                 // Apply starvation damage when blood is low
                 Self::apply_starvation_damage(entity, delta_time);
             }
@@ -32,13 +84,46 @@ impl BloodSystem {
 
         // Apply sunlight damage with shelter protection (separate pass to avoid borrowing issues)
         if is_day && sunlight_intensity > 0.0 {
-            Self::apply_sunlight_damage_with_shelter(entities, sunlight_intensity, delta_time);
+            let burned = Self::apply_sunlight_damage_with_shelter(
+                entities,
+                sunlight_intensity * difficulty.sunlight_damage_multiplier(),
+                shadows,
+                delta_time,
+            );
+            for entity in entities.iter().filter(|entity| burned.contains(&entity.id)) {
+                events.push(GameEvent::SunlightBurned {
+                    entity_id: entity.id,
+                    position: entity.position,
+                });
+            }
+        }
+    }
+
+    /// Running water weakens vampires: any standing in it lose blood
+    /// `RUNNING_WATER_DRAIN` times as fast as on dry land
+    pub fn apply_running_water(
+        entities: &mut [GameEntity],
+        seed: u64,
+        difficulty: Difficulty,
+        delta_time: f32,
+    ) {
+        for entity in entities.iter_mut() {
+            if !WorldSystem::is_water_at(&entity.position, seed) {
+                continue;
+            }
+            if let Some(blood_meter) = &mut entity.blood_meter {
+                Self::update_blood_drain(
+                    blood_meter,
+                    difficulty.blood_drain_multiplier() * (RUNNING_WATER_DRAIN - 1.0),
+                    delta_time,
+                );
+            }
         }
     }
 
     /// Update blood drain over time
-    fn update_blood_drain(blood_meter: &mut BloodMeter, delta_time: f32) {
-        blood_meter.current -= blood_meter.drain_rate * delta_time;
+    fn update_blood_drain(blood_meter: &mut BloodMeter, multiplier: f32, delta_time: f32) {
+        blood_meter.current -= blood_meter.drain_rate * multiplier * delta_time;
         blood_meter.current = blood_meter.current.max(0.0);
     }
 
@@ -50,20 +135,36 @@ impl BloodSystem {
         }
     }
 
-    /// Apply sunlight damage with shelter protection consideration
+    /// Apply sunlight damage with shelter protection consideration.
+    /// Occupants are protected by their shelter's roof; everyone else by
+    /// whatever shade they stand in.
+    /// Returns the IDs of entities that actually took damage.
     pub fn apply_sunlight_damage_with_shelter(
         entities: &mut Vec<GameEntity>,
         sunlight_intensity: f32,
+        shadows: &[Shadow],
         delta_time: f32,
-    ) {
+    ) -> Vec<u32> {
         // Collect entity IDs and base damage for entities with blood meters using iterator
         let damage_calculations: Vec<(u32, f32)> = entities
             .iter()
             .filter(|entity| entity.blood_meter.is_some() && entity.health.is_some())
-            .map(|entity| (entity.id, 3.0 * sunlight_intensity * delta_time))
+            .map(|entity| {
+                let sheltered = entity
+                    .shelter_occupancy
+                    .as_ref()
+                    .is_some_and(|occupancy| occupancy.is_in_shelter());
+                let exposure = if sheltered {
+                    1.0
+                } else {
+                    ExposureSystem::exposure(shadows, entity)
+                };
+                (entity.id, 3.0 * sunlight_intensity * exposure * delta_time)
+            })
             .collect();
 
         // Apply calculated damage
+        let mut burned = Vec::new();
         for (entity_id, base_damage) in damage_calculations {
             let protected_damage = crate::systems::ShelterSystem::calculate_shelter_protection(
                 entities,
@@ -76,7 +177,12 @@ impl BloodSystem {
                     health.current = (health.current - protected_damage).max(0.0);
                 }
             }
+            if protected_damage > 0.0 {
+                burned.push(entity_id);
+            }
         }
+
+        burned
     }
 
     /// Apply starvation damage when blood is critically low
@@ -91,73 +197,29 @@ impl BloodSystem {
         }
     }
 
-    /// Create blood particle effects at a position
-    pub fn create_blood_particles(
-        blood_particles: &mut Vec<BloodParticle>,
-        x: f32,
-        y: f32,
-        intensity: u32,
-        debug_messages: &mut Vec<String>,
-    ) {
-        debug_messages.push(format!(
-            "Creating {} blood particles at ({}, {})",
-            intensity, x, y
-        ));
-        (0..intensity).for_each(|_| {
-            blood_particles.push(BloodParticle::new(x, y));
-        });
-        debug_messages.push(format!(
-            "Blood particles vector now has {} particles",
-            blood_particles.len()
-        ));
-    }
-
-    /// Calculate blood gain from feeding on a specific entity type
+    /// Calculate blood gain from feeding on a specific entity type; richer
+    /// blood yields more of the prey's health
     pub fn calculate_blood_gain(target_entity: &GameEntity) -> f32 {
-        match target_entity.entity_type {
-            EntityType::Animal => {
-                // Animals provide moderate blood
-                if let Some(health) = &target_entity.health {
-                    health.current * 0.6
-                } else {
-                    0.0
-                }
-            }
-            EntityType::HostileInfected => {
-                // Infected provide less blood but still viable
-                if let Some(health) = &target_entity.health {
-                    health.current * 0.4
-                } else {
-                    0.0
-                }
-            }
-            EntityType::ClanMember(_) => {
-                // Clan members provide good blood
-                if let Some(health) = &target_entity.health {
-                    health.current * 0.8
-                } else {
-                    0.0
-                }
-            }
-            EntityType::ClanLeader(_) => {
-                // Clan leaders provide excellent blood
-                if let Some(health) = &target_entity.health {
-                    health.current * 1.0
-                } else {
-                    0.0
-                }
-            }
-            EntityType::Player => {
-                // Players can't feed on themselves
-                0.0
-            }
-            EntityType::Shelter => {
-                // Can't feed on shelters
-                0.0
+        match (
+            BloodQuality::of(&target_entity.entity_type),
+            &target_entity.health,
+        ) {
+            (Some(quality), Some(health)) => {
+                health.current * quality.yield_factor() * Self::species_yield(target_entity.species)
             }
+            _ => 0.0,
         }
     }
 
+    /// Scale on the blood an animal gives by its species: a rat is barely
+    /// a mouthful, a deer a feast. Creatures without a species give the
+    /// usual amount.
+    pub fn species_yield(species: Option<Species>) -> f32 {
+        species.map_or(1.0, |species| {
+            WorldData::current().archetypes.species(species).blood_yield
+        })
+    }
+
     /// Check if an entity is a valid feeding target
     pub fn is_valid_feeding_target(entity: &GameEntity) -> bool {
         // Must be alive
@@ -182,6 +244,7 @@ impl BloodSystem {
             EntityType::ClanLeader(_) => true,
             EntityType::Player => false, // Players can't feed on themselves
             EntityType::Shelter => false, // Can't feed on shelters
+            EntityType::Pickup => false,
         }
     }
 
@@ -215,6 +278,85 @@ impl BloodSystem {
         true
     }
 
+    /// A hungry infected devours the body of its prey, sating its hunger by
+    /// the blood left in it. Nothing is left of the body afterwards.
+    /// Returns whether the prey was eaten.
+    pub fn devour(entities: &mut Vec<GameEntity>, hunter_id: u32, prey_id: u32) -> bool {
+        let Some(blood) = entities
+            .iter()
+            .find(|entity| entity.id == prey_id)
+            .and_then(|prey| prey.corpse.as_ref())
+            .map(|corpse| corpse.blood)
+        else {
+            return false;
+        };
+        let Some(hunger) = entities
+            .iter_mut()
+            .find(|entity| entity.id == hunter_id)
+            .and_then(|hunter| hunter.hunger.as_mut())
+        else {
+            return false;
+        };
+
+        hunger.eat(blood / INFECTED_MEAL_BLOOD);
+        entities.retain(|entity| entity.id != prey_id);
+        true
+    }
+
+    /// Drink part of a living creature's blood without killing it. The
+    /// victim is left weakened and lies unconscious for a while, waking to
+    /// whatever it was doing before. Creatures already knocked down cannot
+    /// be drained again until they come round.
+    pub fn drain(
+        entities: &mut [GameEntity],
+        feeder_id: u32,
+        target_id: u32,
+        game_time: f32,
+    ) -> Option<Drain> {
+        let target = entities.iter_mut().find(|entity| entity.id == target_id)?;
+        let quality = BloodQuality::of(&target.entity_type)?;
+        if matches!(&target.ai_state, AIState::Staggered(stagger) if stagger.knocked_down) {
+            return None;
+        }
+        let health = target
+            .health
+            .as_mut()
+            .filter(|health| health.current > 0.0)?;
+        let taken = health.current * DRAIN_SHARE;
+        health.current -= taken;
+        let raw = taken * quality.yield_factor() * Self::species_yield(target.species);
+
+        let resume = match std::mem::replace(&mut target.ai_state, AIState::Idle) {
+            AIState::Staggered(stagger) => *stagger.resume,
+            state => state,
+        };
+        target.ai_state = AIState::Staggered(Stagger::new(UNCONSCIOUS_SECONDS, true, resume));
+
+        let feeder = entities.iter_mut().find(|entity| entity.id == feeder_id)?;
+        let absorbed = raw
+            * feeder
+                .progression
+                .as_ref()
+                .map_or(1.0, Progression::blood_multiplier);
+        let mut drain = Drain {
+            quality,
+            raw,
+            absorbed,
+            overflow: 0.0,
+        };
+        if let Some(blood_meter) = &mut feeder.blood_meter {
+            drain.absorbed *= blood_meter.feeding.gain_multiplier();
+            blood_meter.feeding.record_feed(game_time);
+            drain.overflow = blood_meter.add_blood(drain.absorbed);
+        }
+        if let Some(buffs) = &mut feeder.buffs {
+            for buff in quality.buffs() {
+                buffs.apply(buff);
+            }
+        }
+        Some(drain)
+    }
+
     /// Improve vampire abilities based on feeding experience
     fn improve_abilities_from_feeding(vampire: &mut GameEntity, blood_gained: f32) {
         if let Some(abilities) = &mut vampire.vampire_abilities {
@@ -283,11 +425,6 @@ impl BloodSystem {
         }
     }
 
-    /// Update blood particle effects
-    pub fn update_blood_particles(blood_particles: &mut Vec<BloodParticle>, delta_time: f32) {
-        blood_particles.retain_mut(|particle| particle.update(delta_time));
-    }
-
     /// Get blood efficiency based on vampire abilities
     pub fn get_blood_efficiency(abilities: &VampireAbilities) -> f32 {
         // Higher blood sense means more efficient feeding
@@ -414,16 +551,30 @@ mod tests {
                 current: 50.0,
                 maximum: 100.0,
                 drain_rate: 1.0,
+                feeding: FeedingState::default(),
+                bottling: 0.0,
             }),
             vampire_abilities: Some(VampireAbilities {
                 strength: 1.0,
                 speed: 1.0,
                 blood_sense: 0.0,
                 shadow_movement: 0.0,
+                ability_state: AbilityState::default(),
             }),
             shelter: None,
             shelter_occupancy: None,
             color: RED,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         }
     }
 
@@ -444,6 +595,17 @@ mod tests {
             shelter: None,
             shelter_occupancy: None,
             color: BROWN,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         }
     }
 
@@ -502,6 +664,54 @@ mod tests {
         assert_eq!(combat_rate, 2.0);
     }
 
+    #[test]
+    fn test_difficulty_scales_drain_and_sunlight() {
+        let run = |difficulty: Difficulty| {
+            let mut entities = vec![create_test_vampire()];
+            let mut events = EventBus::default();
+            BloodSystem::update_blood_system(
+                &mut entities,
+                true,
+                1.0,
+                &[],
+                difficulty,
+                &mut events,
+                1.0,
+            );
+            assert_eq!(events.queued().len(), 1);
+            let blood = entities[0].blood_meter.as_ref().unwrap().current;
+            let health = entities[0].health.as_ref().unwrap().current;
+            (blood, health)
+        };
+
+        let (normal_blood, normal_health) = run(Difficulty::Normal);
+        assert_eq!(normal_blood, 49.0);
+        assert_eq!(normal_health, 97.0);
+        let (easy_blood, easy_health) = run(Difficulty::Easy);
+        assert!(easy_blood > normal_blood && easy_health > normal_health);
+        let (nightmare_blood, nightmare_health) = run(Difficulty::Nightmare);
+        assert!(nightmare_blood < normal_blood && nightmare_health < normal_health);
+        assert_eq!(Difficulty::Hard.scale_spawn_count(8), 12);
+    }
+
+    #[test]
+    fn test_running_water_drains_vampires_faster() {
+        let water = (0..50)
+            .flat_map(|x| {
+                (0..18).map(move |y| Position::new(x as f32 * 32.0, 640.0 + y as f32 * 32.0))
+            })
+            .find(|position| WorldSystem::is_water_at(position, 0))
+            .expect("the world has water");
+        let mut wading = create_test_vampire();
+        wading.position = water;
+        let mut entities = vec![create_test_vampire(), wading];
+
+        BloodSystem::apply_running_water(&mut entities, 0, Difficulty::Normal, 1.0);
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 50.0);
+        // Three extra seconds' worth on top of the usual drain
+        assert_eq!(entities[1].blood_meter.as_ref().unwrap().current, 47.0);
+    }
+
     #[test]
     fn test_survival_score() {
         let score = BloodSystem::calculate_survival_score(10, 5, 8);
@@ -512,6 +722,53 @@ mod tests {
         assert_eq!(score.combat_effectiveness, 0.8); // 8/10
     }
 
+    #[test]
+    fn test_drain_leaves_victim_unconscious_and_witnesses_cost_trust() {
+        use crate::systems::{AISystem, SpatialGrid};
+
+        let clan = "Night-Bloods".to_string();
+        let mut victim = create_test_animal();
+        victim.entity_type = EntityType::ClanMember(clan.clone());
+        victim.health = Some(Health {
+            current: 50.0,
+            max: 50.0,
+        });
+        victim.ai_state = AIState::Wander;
+        let mut witness = victim.clone();
+        witness.id = 2;
+        witness.position = Position::new(100.0, 0.0);
+        let mut entities = vec![create_test_vampire(), victim, witness];
+
+        let drain = BloodSystem::drain(&mut entities, 0, 1, 10.0).unwrap();
+        assert_eq!(drain.quality, BloodQuality::Clan);
+        assert!((drain.raw - 50.0 * DRAIN_SHARE * 0.8).abs() < 0.001);
+        assert_eq!(entities[1].health.as_ref().unwrap().current, 30.0);
+        match &entities[1].ai_state {
+            AIState::Staggered(stagger) => {
+                assert!(stagger.knocked_down);
+                assert_eq!(stagger.remaining, UNCONSCIOUS_SECONDS);
+                assert!(matches!(*stagger.resume, AIState::Wander));
+            }
+            state => panic!("victim should be unconscious, not {:?}", state),
+        }
+        // An unconscious victim cannot be drained again
+        assert!(BloodSystem::drain(&mut entities, 0, 1, 40.0).is_none());
+
+        // The conscious clanmate sees it happen; the victim does not count
+        let grid = SpatialGrid::from_entities(&entities);
+        let position = entities[1].position;
+        let witnesses = AISystem::witnesses(&entities, &grid, position, &clan, &[0, 1], 1.0, &[]);
+        assert_eq!(witnesses, vec![2]);
+        let strangers = AISystem::witnesses(&entities, &grid, position, "Ash", &[0, 1], 1.0, &[]);
+        assert!(strangers.is_empty());
+
+        let mut night_bloods = Clan::new(&clan, "Mara", 5);
+        night_bloods.trust_towards_player = 0.5;
+        night_bloods.witness_crime(Crime::Feeding);
+        assert!(night_bloods.trust_towards_player < 0.5);
+        assert!(night_bloods.fear_of_player > 0.0);
+    }
+
     #[test]
     fn test_needs_urgent_feeding() {
         let mut vampire = create_test_vampire();
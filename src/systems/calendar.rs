@@ -0,0 +1,191 @@
+//! Calendar System Module
+//!
+//! Special nights that come round on a fixed cycle of days, reckoned from
+//! the `TimeSystem`. Every seventh night the moon rises red: twice as many
+//! infected pour out of the dark, animals go to ground in their dens, and
+//! the player's abilities run stronger and recover faster until dawn. Its
+//! rising and setting are announced with a banner.
+
+use crate::components::*;
+use crate::systems::TimeSystem;
+
+/// Nights between one blood moon and the next
+pub const BLOOD_MOON_INTERVAL: u32 = 7;
+/// How much faster infected spawn under a blood moon
+const BLOOD_MOON_SPAWN_RATE: f32 = 2.0;
+/// Seconds a calendar banner stays on screen
+const BANNER_DURATION: f32 = 4.0;
+
+/// A night out of the ordinary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NightEvent {
+    BloodMoon,
+}
+
+/// A special night beginning or ending
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarEvent {
+    Began(NightEvent),
+    Ended(NightEvent),
+}
+
+impl CalendarEvent {
+    /// Get a descriptive message for the debug log and banner
+    pub fn get_message(&self) -> String {
+        match self {
+            CalendarEvent::Began(NightEvent::BloodMoon) => {
+                "A blood moon rises. The infected swarm and your blood sings.".to_string()
+            }
+            CalendarEvent::Ended(NightEvent::BloodMoon) => {
+                "The blood moon sets. The night grows quiet.".to_string()
+            }
+        }
+    }
+}
+
+/// Tonight's special event, if any, and the banner announcing it
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    pub tonight: Option<NightEvent>,
+    /// Announcement on screen, with seconds left
+    pub banner: Option<(CalendarEvent, f32)>,
+}
+
+impl Calendar {
+    pub fn is_blood_moon(&self) -> bool {
+        self.tonight == Some(NightEvent::BloodMoon)
+    }
+
+    /// Scale on how quickly the infected come out of the dark tonight
+    pub fn spawn_rate(&self) -> f32 {
+        if self.is_blood_moon() {
+            BLOOD_MOON_SPAWN_RATE
+        } else {
+            1.0
+        }
+    }
+
+    /// Count down the banner, clearing it when it expires
+    pub fn tick_banner(&mut self, delta_time: f32) {
+        if let Some((_, remaining)) = self.banner.as_mut() {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.banner = None;
+            }
+        }
+    }
+}
+
+/// Calendar system responsible for special nights
+pub struct CalendarSystem;
+
+impl CalendarSystem {
+    /// The special event of the night under way, if any. A night spans
+    /// midnight, so it belongs to the day on which it fell.
+    pub fn night_event(time: &TimeSystem) -> Option<NightEvent> {
+        if time.is_day() {
+            return None;
+        }
+        let night = if time.current_time() >= 12.0 {
+            time.day_count()
+        } else {
+            time.day_count().saturating_sub(1)
+        };
+        ((night + 1) % BLOOD_MOON_INTERVAL == 0).then_some(NightEvent::BloodMoon)
+    }
+
+    /// Follow the clock into and out of special nights, hiding animals and
+    /// empowering the player for as long as a blood moon is up. Returns the
+    /// event if a special night began or ended.
+    pub fn update(
+        calendar: &mut Calendar,
+        entities: &mut [GameEntity],
+        time: &TimeSystem,
+        player_id: u32,
+    ) -> Option<CalendarEvent> {
+        let tonight = Self::night_event(time);
+        let event = match (calendar.tonight, tonight) {
+            (None, Some(started)) => Some(CalendarEvent::Began(started)),
+            (Some(ended), None) => Some(CalendarEvent::Ended(ended)),
+            _ => None,
+        };
+        calendar.tonight = tonight;
+        if let Some(event) = event {
+            calendar.banner = Some((event, BANNER_DURATION));
+        }
+
+        let blood_moon = calendar.is_blood_moon();
+        for entity in entities.iter_mut() {
+            if entity.id == player_id {
+                if let Some(abilities) = entity.vampire_abilities.as_mut() {
+                    abilities.ability_state.empowered = blood_moon;
+                }
+                continue;
+            }
+            let alive = entity.health.as_ref().is_some_and(|h| h.is_alive());
+            let Some(wander) = entity.wander.as_mut() else {
+                continue;
+            };
+            if wander.hiding == (blood_moon && alive) {
+                continue;
+            }
+            wander.hiding = blood_moon && alive;
+            wander.target = None;
+            if wander.hiding {
+                entity.position = wander.home;
+                entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+                entity.ai_state = AIState::Wander;
+            }
+        }
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+
+    #[test]
+    fn test_blood_moon_comes_every_seventh_night() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let deer = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Deer, 500.0, 800.0);
+        entities[1].position = Position::new(560.0, 820.0);
+        let mut calendar = Calendar::default();
+        let mut time = TimeSystem::new();
+
+        // An ordinary first night
+        assert_eq!(
+            CalendarSystem::update(&mut calendar, &mut entities, &time, player_id),
+            None
+        );
+        assert_eq!(calendar.spawn_rate(), 1.0);
+
+        // The seventh night brings the blood moon
+        time.advance_hours(24.0 * 6.0);
+        assert_eq!(
+            CalendarSystem::update(&mut calendar, &mut entities, &time, player_id),
+            Some(CalendarEvent::Began(NightEvent::BloodMoon))
+        );
+        assert!(calendar.banner.is_some());
+        assert_eq!(calendar.spawn_rate(), 2.0);
+        let animal = EntityFinder::by_id(&entities, deer).unwrap();
+        assert!(animal.is_hidden());
+        assert_eq!(animal.position, Position::new(500.0, 800.0));
+        let player = EntityFinder::by_id(&entities, player_id).unwrap();
+        let state = &player.vampire_abilities.as_ref().unwrap().ability_state;
+        assert!(state.cooldown(SpecialAbility::ShadowDash) < SpecialAbility::ShadowDash.cooldown());
+
+        // It lasts past midnight and sets at dawn
+        time.advance_hours(6.0);
+        assert!(CalendarSystem::update(&mut calendar, &mut entities, &time, player_id).is_none());
+        time.advance_hours(6.0);
+        assert_eq!(
+            CalendarSystem::update(&mut calendar, &mut entities, &time, player_id),
+            Some(CalendarEvent::Ended(NightEvent::BloodMoon))
+        );
+        assert!(!EntityFinder::by_id(&entities, deer).unwrap().is_hidden());
+    }
+}
@@ -0,0 +1,266 @@
+//! Camera System Module
+//!
+//! Moves the camera after whatever it is following and handles zoom. The
+//! camera eases towards its target instead of snapping to it, and can lead
+//! the player a little in the direction they are moving so more of what lies
+//! ahead is on screen. Zoom changes ease in the same way. In photo mode the
+//! camera is cut loose from the player and panned by hand over the frozen
+//! world.
+
+use crate::components::*;
+
+/// Zoom the game starts at, in screen pixels per world unit
+pub const DEFAULT_ZOOM: f32 = 1.5;
+pub const MIN_ZOOM: f32 = 0.75;
+pub const MAX_ZOOM: f32 = 2.5;
+
+/// Zoom change for one key press or mouse wheel notch
+const ZOOM_STEP: f32 = 0.125;
+
+/// How quickly the camera closes on its target; higher is snappier
+const FOLLOW_RATE: f32 = 6.0;
+const ZOOM_RATE: f32 = 10.0;
+
+/// Seconds of movement the camera leads the player by, and the furthest
+/// it will lead in world units
+const LOOK_AHEAD_SECONDS: f32 = 0.3;
+const MAX_LOOK_AHEAD: f32 = 90.0;
+
+/// Photo mode pan speed in screen pixels per second, so panning feels the
+/// same at every zoom
+const PHOTO_PAN_SPEED: f32 = 600.0;
+
+/// Seconds the photo mode controls stay on screen before fading away
+const PHOTO_HINT_TIME: f32 = 3.0;
+
+/// The camera held by the player rather than following anyone, for
+/// inspecting the world and taking screenshots
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhotoMode {
+    /// Cursor position while the left button drags the view
+    drag_from: Option<(f32, f32)>,
+    /// Seconds since photo mode was entered
+    pub age: f32,
+}
+
+impl PhotoMode {
+    /// Opacity of the controls hint, which fades so it stays out of shots
+    pub fn hint_alpha(&self) -> f32 {
+        (PHOTO_HINT_TIME - self.age).clamp(0.0, 1.0)
+    }
+}
+
+/// Zoom and follow settings for the camera
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraRig {
+    pub zoom: f32,
+    pub target_zoom: f32,
+    /// Lead the player in the direction they move
+    pub look_ahead: bool,
+    /// False until the camera has jumped to its first target; a fresh or
+    /// loaded game should not pan in from the origin
+    pub settled: bool,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            zoom: DEFAULT_ZOOM,
+            target_zoom: DEFAULT_ZOOM,
+            look_ahead: true,
+            settled: false,
+        }
+    }
+}
+
+/// Camera system responsible for following and zooming
+pub struct CameraSystem;
+
+impl CameraSystem {
+    /// Zoom in (positive steps) or out (negative), within the allowed range
+    pub fn zoom_by(rig: &mut CameraRig, steps: f32) {
+        rig.target_zoom = (rig.target_zoom + steps * ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    /// Where the camera wants to be: the target, led along its velocity
+    /// when look-ahead is on
+    pub fn focus(rig: &CameraRig, target: Position, velocity: Option<&Velocity>) -> Position {
+        let Some(velocity) = velocity.filter(|_| rig.look_ahead) else {
+            return target;
+        };
+        let (lead_x, lead_y) = (
+            velocity.x * LOOK_AHEAD_SECONDS,
+            velocity.y * LOOK_AHEAD_SECONDS,
+        );
+        let lead = (lead_x * lead_x + lead_y * lead_y).sqrt();
+        let scale = if lead > MAX_LOOK_AHEAD {
+            MAX_LOOK_AHEAD / lead
+        } else {
+            1.0
+        };
+        Position::new(target.x + lead_x * scale, target.y + lead_y * scale)
+    }
+
+    /// The world position under a point on a screen of `screen_size`,
+    /// for a camera centred on `camera` at the rig's zoom
+    pub fn screen_to_world(
+        rig: &CameraRig,
+        camera: Position,
+        screen: (f32, f32),
+        screen_size: (f32, f32),
+    ) -> Position {
+        Position::new(
+            camera.x + (screen.0 - screen_size.0 / 2.0) / rig.zoom,
+            camera.y + (screen.1 - screen_size.1 / 2.0) / rig.zoom,
+        )
+    }
+
+    /// Ease the camera towards its focus and its zoom towards the target zoom
+    pub fn follow(
+        rig: &mut CameraRig,
+        camera: &mut Position,
+        target: Position,
+        velocity: Option<&Velocity>,
+        delta_time: f32,
+    ) {
+        let focus = Self::focus(rig, target, velocity);
+        if !rig.settled {
+            *camera = focus;
+            rig.zoom = rig.target_zoom;
+            rig.settled = true;
+            return;
+        }
+
+        // Frame-rate independent exponential easing
+        let follow = 1.0 - (-FOLLOW_RATE * delta_time).exp();
+        camera.x += (focus.x - camera.x) * follow;
+        camera.y += (focus.y - camera.y) * follow;
+        Self::ease_zoom(rig, delta_time);
+    }
+
+    fn ease_zoom(rig: &mut CameraRig, delta_time: f32) {
+        let zoom = 1.0 - (-ZOOM_RATE * delta_time).exp();
+        rig.zoom += (rig.target_zoom - rig.zoom) * zoom;
+    }
+
+    /// Move the photo mode camera by the movement keys and by dragging the
+    /// world under the cursor while `dragging` holds the cursor position
+    pub fn pan(
+        photo_mode: &mut PhotoMode,
+        rig: &mut CameraRig,
+        camera: &mut Position,
+        movement: (f32, f32),
+        dragging: Option<(f32, f32)>,
+        delta_time: f32,
+    ) {
+        photo_mode.age += delta_time;
+        camera.x += movement.0 * PHOTO_PAN_SPEED / rig.zoom * delta_time;
+        camera.y += movement.1 * PHOTO_PAN_SPEED / rig.zoom * delta_time;
+        if let (Some(from), Some(to)) = (photo_mode.drag_from, dragging) {
+            camera.x -= (to.0 - from.0) / rig.zoom;
+            camera.y -= (to.1 - from.1) / rig.zoom;
+        }
+        photo_mode.drag_from = dragging;
+        Self::ease_zoom(rig, delta_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_eases_and_leads() {
+        let mut rig = CameraRig::default();
+        let mut camera = Position::new(0.0, 0.0);
+        CameraSystem::follow(
+            &mut rig,
+            &mut camera,
+            Position::new(100.0, 100.0),
+            None,
+            0.016,
+        );
+        assert_eq!(camera, Position::new(100.0, 100.0));
+
+        // Moving right, the camera closes in on a point ahead of the player
+        let velocity = Velocity::new(1000.0, 0.0);
+        CameraSystem::follow(
+            &mut rig,
+            &mut camera,
+            Position::new(100.0, 100.0),
+            Some(&velocity),
+            0.1,
+        );
+        assert!(camera.x > 100.0 && camera.x < 100.0 + MAX_LOOK_AHEAD);
+        for _ in 0..100 {
+            CameraSystem::follow(
+                &mut rig,
+                &mut camera,
+                Position::new(100.0, 100.0),
+                Some(&velocity),
+                0.1,
+            );
+        }
+        assert!((camera.x - (100.0 + MAX_LOOK_AHEAD)).abs() < 0.01);
+
+        for _ in 0..50 {
+            CameraSystem::zoom_by(&mut rig, -1.0);
+        }
+        assert_eq!(rig.target_zoom, MIN_ZOOM);
+    }
+
+    #[test]
+    fn test_photo_mode_pans_by_keys_and_drag() {
+        let mut rig = CameraRig::default();
+        let mut camera = Position::new(100.0, 100.0);
+        let mut photo_mode = PhotoMode::default();
+
+        // Keys pan at the same screen speed whatever the zoom
+        CameraSystem::pan(
+            &mut photo_mode,
+            &mut rig,
+            &mut camera,
+            (1.0, 0.0),
+            None,
+            0.5,
+        );
+        assert!((camera.x - (100.0 + PHOTO_PAN_SPEED * 0.5 / DEFAULT_ZOOM)).abs() < 0.01);
+
+        // Dragging left by 30 pixels moves the view right by 30 pixels' worth
+        let x = camera.x;
+        CameraSystem::pan(
+            &mut photo_mode,
+            &mut rig,
+            &mut camera,
+            (0.0, 0.0),
+            Some((300.0, 200.0)),
+            0.1,
+        );
+        assert_eq!(camera.x, x);
+        CameraSystem::pan(
+            &mut photo_mode,
+            &mut rig,
+            &mut camera,
+            (0.0, 0.0),
+            Some((270.0, 200.0)),
+            0.1,
+        );
+        assert!((camera.x - (x + 30.0 / DEFAULT_ZOOM)).abs() < 0.01);
+        assert!(photo_mode.hint_alpha() > 0.0);
+
+        // Zoom still eases while the world is frozen
+        CameraSystem::zoom_by(&mut rig, 2.0);
+        for _ in 0..60 {
+            CameraSystem::pan(
+                &mut photo_mode,
+                &mut rig,
+                &mut camera,
+                (0.0, 0.0),
+                None,
+                0.1,
+            );
+        }
+        assert!((rig.zoom - rig.target_zoom).abs() < 0.01);
+        assert_eq!(photo_mode.hint_alpha(), 0.0);
+    }
+}
@@ -0,0 +1,213 @@
+//! Collision System Module
+//!
+//! Runs after movement each frame. Creatures with a circle collider are
+//! pushed apart so they do not stand on top of each other, and pushed back
+//! out of the walls of solid shelters. Entities inside a shelter, the dead
+//! and pickups take no part.
+
+use crate::components::*;
+use crate::systems::spatial::SpatialGrid;
+
+/// Largest distance from an entity's position to the edge of any collider,
+/// bounding the proximity query for each creature
+const MAX_COLLIDER_REACH: f32 = 60.0;
+
+/// What an entity collides as this frame
+#[derive(Debug, Clone, Copy)]
+enum Shape {
+    Circle(f32),
+    Box { half_width: f32, half_height: f32 },
+    None,
+}
+
+/// Position and shape of one entity, copied out for the collision pass
+#[derive(Debug, Clone, Copy)]
+struct Body {
+    position: Position,
+    shape: Shape,
+}
+
+impl Body {
+    fn of(entity: &GameEntity) -> Self {
+        let shape = match entity.collider {
+            Some(Collider::Box {
+                half_width,
+                half_height,
+            }) => Shape::Box {
+                half_width,
+                half_height,
+            },
+            _ => CollisionSystem::body_radius(entity).map_or(Shape::None, Shape::Circle),
+        };
+        Self {
+            position: entity.position,
+            shape,
+        }
+    }
+}
+
+/// Collision system responsible for keeping bodies and walls apart
+pub struct CollisionSystem;
+
+impl CollisionSystem {
+    /// Separate overlapping creatures and push them out of solid shelters
+    pub fn resolve(entities: &mut [GameEntity], grid: &SpatialGrid) {
+        // Work on a compact copy of what collision needs: with a thousand
+        // creatures, striding over whole entities was most of the cost
+        let mut bodies: Vec<Body> = entities.iter().map(Body::of).collect();
+        let mut nearby = Vec::new();
+
+        for index in 0..bodies.len() {
+            let Shape::Circle(radius) = bodies[index].shape else {
+                continue;
+            };
+            let center = bodies[index].position;
+            let reach = radius + MAX_COLLIDER_REACH;
+            grid.candidates_into(center, reach, &mut nearby);
+
+            for &other in &nearby {
+                if other == index || other >= bodies.len() {
+                    continue;
+                }
+                if bodies[other].position.distance_squared_to(&center) > reach * reach {
+                    continue;
+                }
+                match bodies[other].shape {
+                    Shape::Box {
+                        half_width,
+                        half_height,
+                    } => {
+                        let (push_x, push_y) = Self::push_out_of_box(
+                            bodies[index].position,
+                            radius,
+                            bodies[other].position,
+                            half_width,
+                            half_height,
+                        );
+                        bodies[index].position.x += push_x;
+                        bodies[index].position.y += push_y;
+                    }
+                    // Each pair of bodies is handled once, from its first member
+                    Shape::Circle(other_radius) if other > index => {
+                        let (push_x, push_y) = Self::push_apart(
+                            bodies[index].position,
+                            bodies[other].position,
+                            radius + other_radius,
+                        );
+                        bodies[index].position.x += push_x / 2.0;
+                        bodies[index].position.y += push_y / 2.0;
+                        bodies[other].position.x -= push_x / 2.0;
+                        bodies[other].position.y -= push_y / 2.0;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for (entity, body) in entities.iter_mut().zip(&bodies) {
+            entity.position = body.position;
+        }
+    }
+
+    /// Radius of a creature that collides this frame, scaled down in bat form
+    fn body_radius(entity: &GameEntity) -> Option<f32> {
+        let Some(Collider::Circle { radius }) = entity.collider else {
+            return None;
+        };
+        let alive = entity.health.as_ref().is_none_or(Health::is_alive);
+        let sheltered = entity
+            .shelter_occupancy
+            .as_ref()
+            .is_some_and(|occupancy| occupancy.is_in_shelter());
+        if !alive || sheltered {
+            return None;
+        }
+        let scale = entity
+            .vampire_abilities
+            .as_ref()
+            .map_or(1.0, |abilities| abilities.ability_state.hitbox_scale());
+        Some(radius * scale)
+    }
+
+    /// How far to move `a` so two circles `min_distance` apart stop
+    /// overlapping; `b` moves the same amount the other way
+    fn push_apart(a: Position, b: Position, min_distance: f32) -> (f32, f32) {
+        let (dx, dy) = (a.x - b.x, a.y - b.y);
+        let distance = dx.hypot(dy);
+        if distance >= min_distance {
+            return (0.0, 0.0);
+        }
+        if distance <= f32::EPSILON {
+            // Exactly on top of each other: any direction will do
+            return (min_distance, 0.0);
+        }
+        let overlap = min_distance - distance;
+        (dx / distance * overlap, dy / distance * overlap)
+    }
+
+    /// How far to move a circle so it no longer overlaps a box
+    fn push_out_of_box(
+        center: Position,
+        radius: f32,
+        box_center: Position,
+        half_width: f32,
+        half_height: f32,
+    ) -> (f32, f32) {
+        let (dx, dy) = (center.x - box_center.x, center.y - box_center.y);
+        if dx.abs() < half_width && dy.abs() < half_height {
+            // Inside the walls: leave through the nearest side
+            let exit_x = half_width + radius - dx.abs();
+            let exit_y = half_height + radius - dy.abs();
+            return if exit_x < exit_y {
+                (exit_x.copysign(dx), 0.0)
+            } else {
+                (0.0, exit_y.copysign(dy))
+            };
+        }
+
+        let nearest = Position::new(
+            box_center.x + dx.clamp(-half_width, half_width),
+            box_center.y + dy.clamp(-half_height, half_height),
+        );
+        Self::push_apart(center, nearest, radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ShelterSystem, WorldSystem};
+
+    #[test]
+    fn test_bodies_separate_and_walls_block() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 0.0, 0.0);
+        entities[0].position = Position::new(5.0, 0.0);
+        ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            500.0,
+            700.0,
+            None,
+            None,
+        );
+        WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Deer, 535.0, 700.0);
+
+        let grid = SpatialGrid::from_entities(&entities);
+        CollisionSystem::resolve(&mut entities, &grid);
+
+        // Player and infected end up touching but not overlapping
+        let gap = entities[0].position.distance_to(&entities[1].position);
+        assert!((gap - 19.0).abs() < 0.01);
+        assert!(entities[0].position.x > entities[1].position.x);
+
+        // The animal inside the building is pushed out of its east wall, still
+        // close enough to enter the building
+        let animal = &entities[3];
+        assert!((animal.position.x - 547.0).abs() < 0.01);
+        assert!(animal.position.distance_to(&Position::new(500.0, 700.0)) <= 60.0);
+    }
+}
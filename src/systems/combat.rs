@@ -0,0 +1,444 @@
+//! Combat System Module
+//!
+//! Provides a single damage pipeline shared by player and AI attacks and
+//! projectiles.
+//! Attacks are resolved from the attacker's and defender's `CombatStats`
+//! (attack power, defense, critical hits, and dodging), and every resolved
+//! attack is recorded as a `DamageEvent` so that rendering can flash hit
+//! entities and objectives can track kills. Hits heavy enough for the
+//! target's poise stagger or knock it down, interrupting its attack.
+
+use crate::components::*;
+use macroquad::prelude::*;
+
+/// Minimum damage a landed hit can deal
+const MINIMUM_DAMAGE: f32 = 5.0;
+
+/// Attack power used by attackers without combat stats
+const DEFAULT_ATTACK_POWER: f32 = 20.0;
+
+/// How long damage events are kept around for hit feedback (seconds)
+/// Fraction of a slain creature's maximum health left as blood in its corpse
+const CORPSE_BLOOD_RATIO: f32 = 0.5;
+
+pub const DAMAGE_EVENT_LIFETIME: f32 = 0.25;
+
+/// Share of maximum health a hit must take, after poise, to stagger
+const STAGGER_THRESHOLD: f32 = 0.2;
+/// Share of maximum health a hit must take, after poise, to knock down
+const KNOCKDOWN_THRESHOLD: f32 = 0.45;
+/// Seconds a stagger and a knockdown last
+const STAGGER_DURATION: f32 = 0.4;
+const KNOCKDOWN_DURATION: f32 = 1.2;
+
+/// How a target reacted to a hit that landed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HitReaction {
+    #[default]
+    None,
+    Stagger,
+    Knockdown,
+}
+
+/// Combat system responsible for attack resolution and damage events
+pub struct CombatSystem;
+
+impl CombatSystem {
+    /// Resolve an attack from one entity against another.
+    ///
+    /// Returns the resulting event (which is also pushed onto `events`), or
+    /// `None` if the attack could not happen (cooldown, missing or dead entities).
+    pub fn resolve_attack(
+        entities: &mut [GameEntity],
+        attacker_id: u32,
+        target_id: u32,
+        game_time: f32,
+        events: &mut Vec<DamageEvent>,
+    ) -> Option<DamageEvent> {
+        let attacker_index = entities.iter().position(|e| e.id == attacker_id)?;
+        let target_index = entities.iter().position(|e| e.id == target_id)?;
+        if attacker_index == target_index {
+            return None;
+        }
+
+        // Gather attacker stats and check cooldown
+        let attacker = &entities[attacker_index];
+        if !attacker.health.as_ref().is_some_and(|h| h.is_alive()) {
+            return None;
+        }
+        let (attack_power, crit_chance, crit_multiplier) = match &attacker.combat_stats {
+            Some(stats) if !stats.can_attack(game_time) => return None,
+            Some(stats) => (stats.attack_power, stats.crit_chance, stats.crit_multiplier),
+            None => (DEFAULT_ATTACK_POWER, 0.0, 1.0),
+        };
+        let strength = attacker
+            .vampire_abilities
+            .as_ref()
+            .map_or(1.0, |abilities| abilities.strength)
+            * attacker
+                .buffs
+                .as_ref()
+                .map_or(1.0, |buffs| buffs.multiplier(BuffKind::Strength))
+            * attacker.progression.as_ref().map_or(1.0, |progression| {
+                progression.multiplier(BuffKind::Strength)
+            });
+
+        // Gather defender stats
+        let target = &entities[target_index];
+        if !target.health.as_ref().is_some_and(|h| h.is_alive()) {
+            return None;
+        }
+        let (defense, dodge_chance) = target
+            .combat_stats
+            .as_ref()
+            .map_or((0.0, 0.0), |stats| (stats.defense, stats.dodge_chance));
+
+        // Roll for dodge and critical hit
+        let dodged = rand::gen_range(0.0, 1.0) < dodge_chance;
+        let is_critical = !dodged && rand::gen_range(0.0, 1.0) < crit_chance;
+
+        let amount = if dodged {
+            0.0
+        } else {
+            Self::calculate_damage(
+                attack_power * strength,
+                defense,
+                is_critical,
+                crit_multiplier,
+            )
+        };
+
+        // Attacking starts the cooldown whether or not the hit landed
+        if let Some(stats) = &mut entities[attacker_index].combat_stats {
+            stats.last_attack_time = game_time;
+        }
+
+        Some(Self::land_hit(
+            entities,
+            attacker_id,
+            target_index,
+            amount,
+            is_critical,
+            dodged,
+            game_time,
+            events,
+        ))
+    }
+
+    /// Resolve a projectile striking a creature for `damage`. The target's
+    /// defense and dodge apply as for a blow, but a projectile never
+    /// lands a critical hit. Returns `None` if the target is already dead.
+    pub fn resolve_projectile_hit(
+        entities: &mut [GameEntity],
+        owner_id: u32,
+        target_id: u32,
+        damage: f32,
+        game_time: f32,
+        events: &mut Vec<DamageEvent>,
+    ) -> Option<DamageEvent> {
+        let target_index = entities.iter().position(|e| e.id == target_id)?;
+        let target = &entities[target_index];
+        if !target.health.as_ref().is_some_and(|h| h.is_alive()) {
+            return None;
+        }
+        let (defense, dodge_chance) = target
+            .combat_stats
+            .as_ref()
+            .map_or((0.0, 0.0), |stats| (stats.defense, stats.dodge_chance));
+        let dodged = rand::gen_range(0.0, 1.0) < dodge_chance;
+        let amount = if dodged {
+            0.0
+        } else {
+            Self::calculate_damage(damage, defense, false, 1.0)
+        };
+        Some(Self::land_hit(
+            entities,
+            owner_id,
+            target_index,
+            amount,
+            false,
+            dodged,
+            game_time,
+            events,
+        ))
+    }
+
+    /// Apply a resolved hit to the target, killing or staggering it, and
+    /// record the damage event
+    #[allow(clippy::too_many_arguments)]
+    fn land_hit(
+        entities: &mut [GameEntity],
+        attacker_id: u32,
+        target_index: usize,
+        amount: f32,
+        is_critical: bool,
+        dodged: bool,
+        game_time: f32,
+        events: &mut Vec<DamageEvent>,
+    ) -> DamageEvent {
+        let target = &mut entities[target_index];
+        let mut killed = false;
+        let mut reaction = HitReaction::None;
+        if !dodged {
+            target.visual_state.flash_damage();
+        }
+        if let Some(health) = &mut target.health {
+            health.take_damage(amount);
+            if !health.is_alive() {
+                target.ai_state = AIState::Dead;
+                // The body keeps some blood that can still be fed on
+                target.corpse = Some(Corpse::new(health.max * CORPSE_BLOOD_RATIO));
+                killed = true;
+            } else if !dodged {
+                reaction = Self::react_to_hit(target, amount, game_time);
+            }
+        }
+
+        let event = DamageEvent {
+            attacker_id,
+            target_id: target.id,
+            amount,
+            position: target.position,
+            is_critical,
+            dodged,
+            killed,
+            reaction,
+            time: game_time,
+        };
+        events.push(event.clone());
+        event
+    }
+
+    /// Stagger or knock down a creature hit hard enough for its poise. The
+    /// blow interrupts its attack, which has to wind up again from the start.
+    fn react_to_hit(target: &mut GameEntity, amount: f32, game_time: f32) -> HitReaction {
+        if target.entity_type == EntityType::Player {
+            return HitReaction::None;
+        }
+        let (Some(health), Some(stats)) = (&target.health, &mut target.combat_stats) else {
+            return HitReaction::None;
+        };
+        let impact = amount / health.max * (1.0 - stats.poise.clamp(0.0, 1.0));
+        let (reaction, duration) = if impact >= KNOCKDOWN_THRESHOLD {
+            (HitReaction::Knockdown, KNOCKDOWN_DURATION)
+        } else if impact >= STAGGER_THRESHOLD {
+            (HitReaction::Stagger, STAGGER_DURATION)
+        } else {
+            return HitReaction::None;
+        };
+        stats.last_attack_time = game_time;
+
+        // A fresh blow restarts the stagger without forgetting the old state
+        let resume = match &target.ai_state {
+            AIState::Staggered(stagger) => (*stagger.resume).clone(),
+            state => state.clone(),
+        };
+        target.ai_state = AIState::Staggered(Stagger::new(
+            duration,
+            reaction == HitReaction::Knockdown,
+            resume,
+        ));
+        if let Some(velocity) = &mut target.velocity {
+            *velocity = Velocity::zero();
+        }
+        reaction
+    }
+
+    /// Count staggers down and return recovered creatures to what they were
+    /// doing
+    pub fn update_staggers(entities: &mut [GameEntity], delta_time: f32) {
+        for entity in entities.iter_mut() {
+            let AIState::Staggered(stagger) = &mut entity.ai_state else {
+                continue;
+            };
+            stagger.remaining -= delta_time;
+            if stagger.remaining <= 0.0 {
+                entity.ai_state = (*stagger.resume).clone();
+            }
+        }
+    }
+
+    /// Calculate final damage from raw attack power and defense
+    pub fn calculate_damage(
+        attack_power: f32,
+        defense: f32,
+        is_critical: bool,
+        crit_multiplier: f32,
+    ) -> f32 {
+        let base_damage = (attack_power - defense).max(MINIMUM_DAMAGE);
+        if is_critical {
+            base_damage * crit_multiplier
+        } else {
+            base_damage
+        }
+    }
+
+    /// Find the nearest living entity within range that matches a filter
+    pub fn find_target_in_range<F>(
+        entities: &[GameEntity],
+        attacker_id: u32,
+        range: f32,
+        filter: F,
+    ) -> Option<u32>
+    where
+        F: Fn(&GameEntity) -> bool,
+    {
+        let attacker_pos = entities.iter().find(|e| e.id == attacker_id)?.position;
+
+        entities
+            .iter()
+            .filter(|entity| entity.id != attacker_id)
+            .filter(|entity| entity.health.as_ref().is_some_and(|h| h.is_alive()))
+            .filter(|entity| filter(entity))
+            .map(|entity| (entity.id, attacker_pos.distance_to(&entity.position)))
+            .filter(|(_, distance)| *distance <= range)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    /// Drop damage events older than their feedback lifetime
+    pub fn prune_events(events: &mut Vec<DamageEvent>, game_time: f32) {
+        events.retain(|event| game_time - event.time <= DAMAGE_EVENT_LIFETIME);
+    }
+
+    /// Count kills credited to an attacker in a batch of events
+    pub fn count_kills(events: &[DamageEvent], attacker_id: u32) -> u32 {
+        events
+            .iter()
+            .filter(|event| event.killed && event.attacker_id == attacker_id)
+            .count() as u32
+    }
+}
+
+/// A single resolved attack
+#[derive(Debug, Clone)]
+pub struct DamageEvent {
+    pub attacker_id: u32,
+    pub target_id: u32,
+    pub amount: f32,
+    pub position: Position,
+    pub is_critical: bool,
+    pub dodged: bool,
+    pub killed: bool,
+    pub reaction: HitReaction,
+    pub time: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_fighter(id: u32, x: f32, attack_power: f32, health: f32) -> GameEntity {
+        let mut stats = CombatStats::new(attack_power, 0.0);
+        stats.crit_chance = 0.0;
+        stats.dodge_chance = 0.0;
+
+        GameEntity {
+            id,
+            position: Position::new(x, 700.0),
+            velocity: Some(Velocity::zero()),
+            entity_type: EntityType::HostileInfected,
+            health: Some(Health::new(health)),
+            combat_stats: Some(stats),
+            ai_state: AIState::Hostile,
+            blood_meter: None,
+            vampire_abilities: None,
+            shelter: None,
+            shelter_occupancy: None,
+            color: WHITE,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        }
+    }
+
+    #[test]
+    fn test_calculate_damage() {
+        assert_eq!(CombatSystem::calculate_damage(25.0, 10.0, false, 1.5), 15.0);
+        assert_eq!(CombatSystem::calculate_damage(25.0, 10.0, true, 2.0), 30.0);
+        // Defense can never reduce a hit below the minimum
+        assert_eq!(CombatSystem::calculate_damage(5.0, 50.0, false, 1.5), 5.0);
+    }
+
+    #[test]
+    fn test_resolve_attack_kills_and_records_event() {
+        let mut entities = vec![
+            create_test_fighter(0, 100.0, 40.0, 100.0),
+            create_test_fighter(1, 120.0, 10.0, 30.0),
+        ];
+        let mut events = Vec::new();
+
+        let event = CombatSystem::resolve_attack(&mut entities, 0, 1, 5.0, &mut events).unwrap();
+        assert!(event.killed);
+        assert_eq!(event.amount, 40.0);
+        assert!(matches!(entities[1].ai_state, AIState::Dead));
+        assert_eq!(CombatSystem::count_kills(&events, 0), 1);
+
+        // Dead targets cannot be attacked again
+        assert!(CombatSystem::resolve_attack(&mut entities, 0, 1, 10.0, &mut events).is_none());
+    }
+
+    #[test]
+    fn test_heavy_hits_stagger_and_interrupt() {
+        let mut entities = vec![
+            create_test_fighter(0, 100.0, 25.0, 100.0),
+            create_test_fighter(1, 120.0, 10.0, 100.0),
+            create_test_fighter(2, 140.0, 50.0, 100.0),
+        ];
+        let mut events = Vec::new();
+
+        // A quarter of its health staggers the target and restarts its attack
+        let event = CombatSystem::resolve_attack(&mut entities, 0, 1, 5.0, &mut events).unwrap();
+        assert_eq!(event.reaction, HitReaction::Stagger);
+        assert!(matches!(entities[1].ai_state, AIState::Staggered(_)));
+        assert!(CombatSystem::resolve_attack(&mut entities, 1, 0, 5.5, &mut events).is_none());
+
+        // Half its health knocks it down, and it gets back up hunting
+        let event = CombatSystem::resolve_attack(&mut entities, 2, 1, 5.2, &mut events).unwrap();
+        assert_eq!(event.reaction, HitReaction::Knockdown);
+        CombatSystem::update_staggers(&mut entities, 1.0);
+        assert!(matches!(entities[1].ai_state, AIState::Staggered(_)));
+        CombatSystem::update_staggers(&mut entities, 0.3);
+        assert!(matches!(entities[1].ai_state, AIState::Hostile));
+
+        // A boss's poise turns the same knockdown into a stagger
+        entities[0].combat_stats.as_mut().unwrap().poise = 0.5;
+        let event = CombatSystem::resolve_attack(&mut entities, 2, 0, 7.0, &mut events).unwrap();
+        assert_eq!(event.reaction, HitReaction::Stagger);
+    }
+
+    #[test]
+    fn test_attack_cooldown() {
+        let mut entities = vec![
+            create_test_fighter(0, 100.0, 10.0, 100.0),
+            create_test_fighter(1, 120.0, 10.0, 100.0),
+        ];
+        let mut events = Vec::new();
+
+        assert!(CombatSystem::resolve_attack(&mut entities, 0, 1, 5.0, &mut events).is_some());
+        assert!(CombatSystem::resolve_attack(&mut entities, 0, 1, 5.5, &mut events).is_none());
+        assert!(CombatSystem::resolve_attack(&mut entities, 0, 1, 6.0, &mut events).is_some());
+    }
+
+    #[test]
+    fn test_prune_events() {
+        let mut entities = vec![
+            create_test_fighter(0, 100.0, 10.0, 100.0),
+            create_test_fighter(1, 120.0, 10.0, 100.0),
+        ];
+        let mut events = Vec::new();
+        CombatSystem::resolve_attack(&mut entities, 0, 1, 5.0, &mut events);
+
+        assert!(events.iter().any(|event| event.target_id == 1));
+        CombatSystem::prune_events(&mut events, 5.0 + DAMAGE_EVENT_LIFETIME * 2.0);
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,420 @@
+//! Construction System Module
+//!
+//! Build mode lets the player raise shelters of their own. A blueprint is
+//! steered around the vampire, who stands still meanwhile, and confirmed to
+//! pay the blood cost and lay the foundations. Building climbs through tiers:
+//! a tent on open ground, then a shed and finally a lair raised over the
+//! player's own structure. Each tier takes time to finish before it can be
+//! used. A finished lair is the player's home: after dying they rise there
+//! again, at the cost of some of the lair's condition.
+
+use crate::components::*;
+use crate::systems::shelter::ShelterSystem;
+
+/// Furthest the blueprint may be placed from the player
+pub const BUILD_REACH: f32 = 90.0;
+
+/// Blueprint speed in pixels per second
+const BLUEPRINT_SPEED: f32 = 160.0;
+
+/// Closest a new tent may stand to another shelter
+const MIN_SHELTER_SPACING: f32 = 70.0;
+
+/// Share of health and blood the player rises with in their lair
+const REBIRTH_HEALTH: f32 = 0.5;
+const REBIRTH_BLOOD: f32 = 0.25;
+
+/// Where the player means to build, while in build mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blueprint {
+    pub position: Position,
+}
+
+/// What confirming a blueprint would build
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildOrder {
+    pub shelter_type: ShelterType,
+    pub cost: BuildCost,
+    /// The player's own shelter being raised to the next tier, if any
+    pub upgrade_of: Option<u32>,
+}
+
+/// Why a blueprint cannot be built
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    NoGround,
+    TooClose,
+    FullyBuilt,
+    Occupied,
+    StillBuilding,
+    NotEnoughBlood { needed: f32 },
+    Unavailable,
+}
+
+impl BuildError {
+    pub fn get_message(&self) -> String {
+        match self {
+            BuildError::NoGround => "There is no ground to build on here.".to_string(),
+            BuildError::TooClose => "Too close to another shelter to build.".to_string(),
+            BuildError::FullyBuilt => "Your lair cannot be built any higher.".to_string(),
+            BuildError::Occupied => "Empty the shelter before rebuilding it.".to_string(),
+            BuildError::StillBuilding => "That is still being built.".to_string(),
+            BuildError::NotEnoughBlood { needed } => {
+                format!("You need {:.0} blood to build that.", needed)
+            }
+            BuildError::Unavailable => "You cannot build right now.".to_string(),
+        }
+    }
+}
+
+/// Building started or finished, or the player rising in their lair
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstructionEvent {
+    Started {
+        shelter_type: ShelterType,
+        blood: f32,
+    },
+    Completed {
+        shelter_type: ShelterType,
+    },
+    Reborn {
+        condition: ShelterCondition,
+    },
+}
+
+impl ConstructionEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            ConstructionEvent::Started {
+                shelter_type,
+                blood,
+            } => format!(
+                "You spend {:.0} blood and begin building a {}.",
+                blood,
+                shelter_type.display_name().to_lowercase()
+            ),
+            ConstructionEvent::Completed { shelter_type } => match shelter_type {
+                ShelterType::Lair => "Your lair is complete. Here you will rise again.".to_string(),
+                _ => format!(
+                    "Your {} is finished.",
+                    shelter_type.display_name().to_lowercase()
+                ),
+            },
+            ConstructionEvent::Reborn { condition } => format!(
+                "You rise again in your lair, weakened. The lair is now {}.",
+                condition.display_name().to_lowercase()
+            ),
+        }
+    }
+}
+
+/// Construction system responsible for build mode and the player's lair
+pub struct ConstructionSystem;
+
+impl ConstructionSystem {
+    /// Open build mode with the blueprint just in front of the player
+    pub fn begin(player_position: Position) -> Blueprint {
+        Blueprint {
+            position: Position::new(player_position.x, player_position.y + 40.0),
+        }
+    }
+
+    /// Move the blueprint along `direction` (the movement input), keeping it
+    /// within reach of the player
+    pub fn move_blueprint(
+        blueprint: &mut Blueprint,
+        player_position: Position,
+        direction: (f32, f32),
+        delta_time: f32,
+    ) {
+        let (dx, dy) = direction;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length <= 0.01 {
+            return;
+        }
+        let mut x = blueprint.position.x + dx / length * BLUEPRINT_SPEED * delta_time;
+        let mut y = blueprint.position.y + dy / length * BLUEPRINT_SPEED * delta_time;
+        let (off_x, off_y) = (x - player_position.x, y - player_position.y);
+        let distance = off_x.hypot(off_y);
+        if distance > BUILD_REACH {
+            x = player_position.x + off_x / distance * BUILD_REACH;
+            y = player_position.y + off_y / distance * BUILD_REACH;
+        }
+        blueprint.position = Position::new(x, y);
+    }
+
+    /// What would be built at the blueprint: the next tier of the player's
+    /// own shelter under it, or else a new tent on clear ground
+    pub fn plan(entities: &[GameEntity], blueprint: &Blueprint) -> Result<BuildOrder, BuildError> {
+        let site = blueprint.position;
+        let own = entities.iter().find(|entity| {
+            entity.shelter.as_ref().is_some_and(|shelter| {
+                shelter.built_by_player
+                    && entity.position.distance_to(&site) <= shelter.shelter_type.discovery_range()
+            })
+        });
+
+        if let Some(entity) = own {
+            let shelter = entity.shelter.as_ref().ok_or(BuildError::Unavailable)?;
+            if shelter.is_under_construction() {
+                return Err(BuildError::StillBuilding);
+            }
+            if shelter.occupant_count() > 0 {
+                return Err(BuildError::Occupied);
+            }
+            let shelter_type = shelter
+                .shelter_type
+                .next_tier()
+                .ok_or(BuildError::FullyBuilt)?;
+            let cost = shelter_type.build_cost().ok_or(BuildError::FullyBuilt)?;
+            return Ok(BuildOrder {
+                shelter_type,
+                cost,
+                upgrade_of: Some(entity.id),
+            });
+        }
+
+        if !ShelterSystem::has_ground_at_position(site.x, site.y) {
+            return Err(BuildError::NoGround);
+        }
+        let crowded = entities.iter().any(|entity| {
+            entity.shelter.is_some() && entity.position.distance_to(&site) < MIN_SHELTER_SPACING
+        });
+        if crowded {
+            return Err(BuildError::TooClose);
+        }
+        let cost = ShelterType::Tent
+            .build_cost()
+            .ok_or(BuildError::Unavailable)?;
+        Ok(BuildOrder {
+            shelter_type: ShelterType::Tent,
+            cost,
+            upgrade_of: None,
+        })
+    }
+
+    /// Pay for the blueprint and lay its foundations
+    pub fn build(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        player_id: u32,
+        blueprint: &Blueprint,
+    ) -> Result<ConstructionEvent, BuildError> {
+        let order = Self::plan(entities, blueprint)?;
+
+        let player = entities
+            .iter_mut()
+            .find(|entity| entity.id == player_id)
+            .ok_or(BuildError::Unavailable)?;
+        let blood = player.blood_meter.as_mut().ok_or(BuildError::Unavailable)?;
+        if !blood.consume(order.cost.blood) {
+            return Err(BuildError::NotEnoughBlood {
+                needed: order.cost.blood,
+            });
+        }
+
+        let shelter_id = match order.upgrade_of {
+            Some(id) => id,
+            None => ShelterSystem::spawn_shelter(
+                entities,
+                entity_ids,
+                ShelterType::Tent,
+                blueprint.position.x,
+                blueprint.position.y,
+                Some(ShelterCondition::Pristine),
+                None,
+            ),
+        };
+        if let Some(entity) = entities.iter_mut().find(|entity| entity.id == shelter_id) {
+            entity.collider = Collider::for_shelter(&order.shelter_type);
+            if let Some(shelter) = entity.shelter.as_mut() {
+                shelter.shelter_type = order.shelter_type.clone();
+                shelter.condition = ShelterCondition::Pristine;
+                shelter.built_by_player = true;
+                shelter.discovered = true;
+                shelter.work_progress = 0.0;
+                shelter.construction = Some(0.0);
+            }
+        }
+
+        Ok(ConstructionEvent::Started {
+            shelter_type: order.shelter_type,
+            blood: order.cost.blood,
+        })
+    }
+
+    /// Advance every shelter under construction, returning those finished
+    pub fn update(entities: &mut [GameEntity], delta_time: f32) -> Vec<ConstructionEvent> {
+        let mut events = Vec::new();
+        for shelter in entities
+            .iter_mut()
+            .filter_map(|entity| entity.shelter.as_mut())
+        {
+            let Some(progress) = shelter.construction else {
+                continue;
+            };
+            let seconds = shelter
+                .shelter_type
+                .build_cost()
+                .map_or(1.0, |cost| cost.seconds);
+            let progress = progress + delta_time / seconds;
+            if progress >= 1.0 {
+                shelter.construction = None;
+                events.push(ConstructionEvent::Completed {
+                    shelter_type: shelter.shelter_type.clone(),
+                });
+            } else {
+                shelter.construction = Some(progress);
+            }
+        }
+        events
+    }
+
+    /// The player's finished lair, if they have one that can take them in
+    pub fn home_lair(entities: &[GameEntity]) -> Option<&GameEntity> {
+        entities
+            .iter()
+            .find(|entity| entity.shelter.as_ref().is_some_and(Shelter::is_home))
+    }
+
+    /// Raise a dead player inside their lair with some health and blood,
+    /// wearing the lair down a step. Returns None if there is no lair.
+    pub fn rise_in_lair(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        current_time: f32,
+    ) -> Option<ConstructionEvent> {
+        let dead = entities
+            .iter()
+            .find(|entity| entity.id == player_id)?
+            .health
+            .as_ref()
+            .is_some_and(|health| !health.is_alive());
+        if !dead {
+            return None;
+        }
+        let lair = Self::home_lair(entities)?;
+        let (lair_id, lair_position) = (lair.id, lair.position);
+
+        // Out of wherever the player fell
+        for shelter in entities
+            .iter_mut()
+            .filter_map(|entity| entity.shelter.as_mut())
+        {
+            shelter.remove_occupant(player_id);
+        }
+
+        let player = entities.iter_mut().find(|entity| entity.id == player_id)?;
+        if let Some(health) = player.health.as_mut() {
+            health.current = health.max * REBIRTH_HEALTH;
+        }
+        if let Some(blood) = player.blood_meter.as_mut() {
+            blood.current = blood.current.max(blood.maximum * REBIRTH_BLOOD);
+        }
+        // Nothing of the death remains
+        player.ai_state = AIState::Idle;
+        player.corpse = None;
+        player.visual_state.death = None;
+        player.position = lair_position;
+        if let Some(velocity) = player.velocity.as_mut() {
+            *velocity = Velocity::zero();
+        }
+        player
+            .shelter_occupancy
+            .get_or_insert_with(ShelterOccupancy::new)
+            .enter_shelter(lair_id, current_time);
+
+        let shelter = entities
+            .iter_mut()
+            .find(|entity| entity.id == lair_id)
+            .and_then(|entity| entity.shelter.as_mut())?;
+        shelter.add_occupant(player_id);
+        shelter.condition = shelter
+            .condition
+            .worsened()
+            .unwrap_or(ShelterCondition::Ruined);
+        Some(ConstructionEvent::Reborn {
+            condition: shelter.condition.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+
+    #[test]
+    fn test_tent_grows_into_a_lair_that_revives_the_player() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(800.0, 1150.0);
+        entities[0].blood_meter.as_mut().unwrap().current = 100.0;
+        let player_position = entities[0].position;
+
+        // Out of reach the blueprint stops at the edge; off the ground is refused
+        let mut blueprint = ConstructionSystem::begin(player_position);
+        ConstructionSystem::move_blueprint(&mut blueprint, player_position, (0.0, 1.0), 10.0);
+        assert!((blueprint.position.distance_to(&player_position) - BUILD_REACH).abs() < 0.01);
+        assert_eq!(
+            ConstructionSystem::plan(&entities, &blueprint),
+            Err(BuildError::NoGround)
+        );
+
+        // A tent goes up on open ground and cannot be used until finished
+        let blueprint = Blueprint {
+            position: Position::new(800.0, 1100.0),
+        };
+        let event =
+            ConstructionSystem::build(&mut entities, &mut ids, player_id, &blueprint).unwrap();
+        assert!(matches!(event, ConstructionEvent::Started { blood, .. } if blood == 20.0));
+        let tent = entities.last().unwrap().shelter.as_ref().unwrap();
+        assert_eq!(tent.shelter_type, ShelterType::Tent);
+        assert!(!tent.can_accommodate());
+        assert_eq!(
+            ConstructionSystem::plan(&entities, &blueprint),
+            Err(BuildError::StillBuilding)
+        );
+        let events = ConstructionSystem::update(&mut entities, 10.0);
+        assert_eq!(
+            events,
+            vec![ConstructionEvent::Completed {
+                shelter_type: ShelterType::Tent
+            }]
+        );
+
+        // Building over it again raises a shed; the blood runs out at the lair
+        ConstructionSystem::build(&mut entities, &mut ids, player_id, &blueprint).unwrap();
+        ConstructionSystem::update(&mut entities, 20.0);
+        assert_eq!(
+            ConstructionSystem::build(&mut entities, &mut ids, player_id, &blueprint),
+            Err(BuildError::NotEnoughBlood { needed: 60.0 })
+        );
+        entities[0].blood_meter.as_mut().unwrap().current = 100.0;
+        ConstructionSystem::build(&mut entities, &mut ids, player_id, &blueprint).unwrap();
+        assert!(ConstructionSystem::home_lair(&entities).is_none());
+        ConstructionSystem::update(&mut entities, 40.0);
+        assert_eq!(
+            ConstructionSystem::plan(&entities, &blueprint),
+            Err(BuildError::FullyBuilt)
+        );
+        let lair_id = ConstructionSystem::home_lair(&entities).unwrap().id;
+
+        // Dying brings the player back inside the lair, which wears down
+        entities[0].health.as_mut().unwrap().current = 0.0;
+        let event = ConstructionSystem::rise_in_lair(&mut entities, player_id, 5.0);
+        assert_eq!(
+            event,
+            Some(ConstructionEvent::Reborn {
+                condition: ShelterCondition::Good
+            })
+        );
+        let player = &entities[0];
+        assert_eq!(player.health.as_ref().unwrap().current, 50.0);
+        assert_eq!(
+            player.shelter_occupancy.as_ref().unwrap().shelter_id,
+            Some(lair_id)
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Crime System Module
+//!
+//! Clans remember what they see done to their own. Attacking, feeding on or
+//! killing a clan member in sight of their clanmates costs the player that
+//! clan's trust and raises its fear; once loyalty sinks low enough the whole
+//! clan breaks with the player and its members turn hostile.
+
+use crate::components::*;
+use crate::systems::ai::AISystem;
+use crate::systems::events::{EventBus, GameEvent};
+use crate::systems::rebellion::RebellionSystem;
+use crate::systems::spatial::SpatialGrid;
+use crate::systems::stealth::SightBlocker;
+use std::collections::HashMap;
+
+/// Loyalty at or below which a wronged clan turns on the player
+const HOSTILE_LOYALTY: f32 = -0.5;
+
+/// Crime system responsible for witnesses and the clan grudges they carry
+pub struct CrimeSystem;
+
+impl CrimeSystem {
+    /// Report a crime by the player against `victim_id` at `position`. If any
+    /// of the victim's clanmates see it, the clan's standing with the player
+    /// drops and the clan may turn hostile; both are reported on the event
+    /// bus. Returns whether the crime was witnessed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn report(
+        clans: &mut HashMap<String, Clan>,
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        crime: Crime,
+        player_id: u32,
+        victim_id: u32,
+        position: Position,
+        sight_scale: f32,
+        blockers: &[SightBlocker],
+        events: &mut EventBus,
+    ) -> bool {
+        let Some(clan_name) =
+            EntityFinder::by_id(entities, victim_id).and_then(|victim| match &victim.entity_type {
+                EntityType::ClanMember(clan) | EntityType::ClanLeader(clan) => Some(clan.clone()),
+                _ => None,
+            })
+        else {
+            return false;
+        };
+        let Some(clan) = clans.get_mut(&clan_name).filter(|clan| !clan.is_hostile) else {
+            return false;
+        };
+        // Victims knocked senseless or killed see nothing; the unharmed
+        // victim of an attack is a witness like any other
+        let witnesses = AISystem::witnesses(
+            entities,
+            grid,
+            position,
+            &clan_name,
+            &[player_id],
+            sight_scale,
+            blockers,
+        );
+        if witnesses.is_empty() {
+            return false;
+        }
+
+        clan.witness_crime(crime);
+        events.push(GameEvent::CrimeWitnessed {
+            clan_name: clan_name.clone(),
+            crime,
+        });
+        if clan.loyalty_score() <= HOSTILE_LOYALTY {
+            clan.is_hostile = true;
+            RebellionSystem::break_allegiance(clan);
+            RebellionSystem::turn_members_hostile(entities, &clan_name);
+            events.push(GameEvent::ClanTurnedHostile { clan_name });
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+    use macroquad::prelude::*;
+
+    #[test]
+    fn test_witnessed_murders_turn_the_clan_hostile() {
+        let clan_name = "Night-Bloods";
+        let mut clans = HashMap::new();
+        clans.insert(clan_name.to_string(), Clan::new(clan_name, "Mara", 5));
+        let mut entities = Vec::new();
+        let mut entity_ids = EntityAllocator::default();
+        let mut ids = Vec::new();
+        for x in [100.0, 140.0, 180.0, 900.0] {
+            ids.push(WorldSystem::spawn_clan_member(
+                &mut entities,
+                &mut entity_ids,
+                clan_name,
+                x,
+                700.0,
+                RED,
+            ));
+        }
+        let mut events = EventBus::default();
+
+        // Killing the lone clanmate far away goes unseen
+        let far = ids[3];
+        entities.iter_mut().find(|e| e.id == far).unwrap().ai_state = AIState::Dead;
+        let grid = SpatialGrid::from_entities(&entities);
+        let far_pos = Position::new(900.0, 700.0);
+        assert!(!CrimeSystem::report(
+            &mut clans,
+            &mut entities,
+            &grid,
+            Crime::Murder,
+            99,
+            far,
+            far_pos,
+            1.0,
+            &[],
+            &mut events,
+        ));
+
+        // Murders in front of the clan drive it to open hostility
+        entities
+            .iter_mut()
+            .find(|e| e.id == ids[0])
+            .unwrap()
+            .ai_state = AIState::Dead;
+        for _ in 0..4 {
+            assert!(CrimeSystem::report(
+                &mut clans,
+                &mut entities,
+                &grid,
+                Crime::Murder,
+                99,
+                ids[0],
+                Position::new(100.0, 700.0),
+                1.0,
+                &[],
+                &mut events,
+            ));
+        }
+        let clan = &clans[clan_name];
+        assert!(clan.is_hostile);
+        assert!(clan.loyalty_score() <= HOSTILE_LOYALTY);
+        assert!(matches!(
+            events.queued().last(),
+            Some(GameEvent::ClanTurnedHostile { .. })
+        ));
+        assert!(entities
+            .iter()
+            .filter(|e| !matches!(e.ai_state, AIState::Dead))
+            .all(|e| matches!(e.ai_state, AIState::Hostile)));
+    }
+}
@@ -0,0 +1,456 @@
+//! Dialogue System Module
+//!
+//! Branching conversations with clan leaders. Each leader has their own tree
+//! of lines and replies; picking a reply moves the conversation along and can
+//! shift the clan's trust and fear of the player or change its allegiance.
+//! Leaders who do not count the player an enemy can also be asked for work
+//! (see `QuestSystem`).
+
+use crate::components::*;
+use std::collections::HashMap;
+
+/// Trust a clan must have in the player before agreeing to an alliance
+pub const ALLIANCE_TRUST: f32 = 0.5;
+
+/// Dialogue system responsible for clan leader conversations
+pub struct DialogueSystem;
+
+impl DialogueSystem {
+    /// Begin a conversation with the leader of a clan
+    pub fn start(clan: &Clan) -> Option<ActiveDialogue> {
+        let mut tree = Self::tree_for(&clan.leader_name)?;
+        // Allies are greeted differently from strangers
+        let current_node = if clan.is_allied || clan.is_defeated {
+            tree.ally_root
+        } else {
+            0
+        };
+        if !clan.is_hostile && !clan.is_defeated {
+            tree.nodes[current_node].options.push(
+                DialogueOption::new("Have you any work for me?", None)
+                    .with_effect(DialogueEffect::asking_for_work()),
+            );
+        }
+
+        Some(ActiveDialogue {
+            clan_name: clan.name.clone(),
+            leader_name: clan.leader_name.clone(),
+            tree,
+            current_node,
+            selected_option: 0,
+        })
+    }
+
+    /// Move the option cursor up or down, wrapping around
+    pub fn navigate(dialogue: &mut ActiveDialogue, down: bool) {
+        let count = dialogue.current().options.len();
+        if count == 0 {
+            return;
+        }
+        dialogue.selected_option = if down {
+            (dialogue.selected_option + 1) % count
+        } else {
+            (dialogue.selected_option + count - 1) % count
+        };
+    }
+
+    /// Choose the selected option, applying its effect to the clan.
+    /// Returns messages describing the outcome and whether the conversation goes on.
+    pub fn choose(
+        dialogue: &mut ActiveDialogue,
+        clans: &mut HashMap<String, Clan>,
+    ) -> DialogueStep {
+        let Some(option) = dialogue
+            .current()
+            .options
+            .get(dialogue.selected_option)
+            .cloned()
+        else {
+            return DialogueStep::Ended {
+                messages: Vec::new(),
+            };
+        };
+
+        let messages = clans
+            .get_mut(&dialogue.clan_name)
+            .map(|clan| Self::apply_effect(clan, &option.effect))
+            .unwrap_or_default();
+
+        match option.next {
+            Some(next) => {
+                dialogue.current_node = next;
+                dialogue.selected_option = 0;
+                DialogueStep::Continue { messages }
+            }
+            None => DialogueStep::Ended { messages },
+        }
+    }
+
+    /// Apply a reply's consequences to the clan
+    pub fn apply_effect(clan: &mut Clan, effect: &DialogueEffect) -> Vec<String> {
+        let mut messages = Vec::new();
+
+        clan.trust_towards_player = (clan.trust_towards_player + effect.trust).clamp(0.0, 1.0);
+        clan.fear_of_player = (clan.fear_of_player + effect.fear).clamp(0.0, 1.0);
+
+        if effect.trust > 0.0 {
+            messages.push(format!("The {} trust you more.", clan.name));
+        } else if effect.trust < 0.0 {
+            messages.push(format!("The {} trust you less.", clan.name));
+        }
+        if effect.fear > 0.0 {
+            messages.push(format!("The {} fear you more.", clan.name));
+        }
+
+        if effect.proposes_alliance && !clan.is_allied {
+            if clan.trust_towards_player >= ALLIANCE_TRUST {
+                clan.is_allied = true;
+                messages.push(format!("{} agrees to an alliance!", clan.leader_name));
+            } else {
+                messages.push(format!(
+                    "{} refuses - the {} do not trust you enough yet.",
+                    clan.leader_name, clan.name
+                ));
+            }
+        }
+
+        if effect.breaks_alliance && clan.is_allied {
+            clan.is_allied = false;
+            clan.tribute_level = TributeLevel::None;
+            messages.push(format!("The alliance with the {} is over.", clan.name));
+        }
+
+        messages
+    }
+
+    /// The dialogue tree for a clan leader
+    pub fn tree_for(leader_name: &str) -> Option<DialogueTree> {
+        match leader_name {
+            "Grimjaw" => Some(Self::grimjaw_tree()),
+            "Shadowmere" => Some(Self::shadowmere_tree()),
+            "Silentfang" => Some(Self::silentfang_tree()),
+            _ => None,
+        }
+    }
+
+    /// Grimjaw of the Bone-Eaters respects only strength
+    fn grimjaw_tree() -> DialogueTree {
+        DialogueTree {
+            ally_root: 3,
+            nodes: vec![
+                DialogueNode::new(
+                    "Another pale thing wandering into Bone-Eater ground. Speak, or be eaten.",
+                    vec![
+                        DialogueOption::new("I come to offer an alliance.", Some(1)),
+                        DialogueOption::new("Your clan will kneel to me.", Some(2))
+                            .with_effect(DialogueEffect::fear(0.15).and_trust(-0.1)),
+                        DialogueOption::new("I'll leave you to your bones.", None),
+                    ],
+                ),
+                DialogueNode::new(
+                    "Alliance? Words are cheap. Bring me proof of your strength.",
+                    vec![
+                        DialogueOption::new("I have fed well. Judge for yourself.", None)
+                            .with_effect(DialogueEffect::trust(0.1).and_fear(0.05)),
+                        DialogueOption::new("Then take my word and my hand.", None)
+                            .with_effect(DialogueEffect::trust(0.05).proposing_alliance()),
+                    ],
+                ),
+                DialogueNode::new(
+                    "Ha! Bold. Foolish, but bold. Prove it, and maybe we kneel.",
+                    vec![
+                        DialogueOption::new("I will. Watch your back until then.", None)
+                            .with_effect(DialogueEffect::fear(0.1)),
+                        DialogueOption::new("Perhaps I spoke too hastily.", None)
+                            .with_effect(DialogueEffect::trust(0.05).and_fear(-0.05)),
+                    ],
+                ),
+                DialogueNode::new(
+                    "The Bone-Eaters stand with you. What do you want?",
+                    vec![
+                        DialogueOption::new("Only to see my allies are well fed.", None)
+                            .with_effect(DialogueEffect::trust(0.05)),
+                        DialogueOption::new("Remember who leads here.", None)
+                            .with_effect(DialogueEffect::fear(0.1).and_trust(-0.05)),
+                        DialogueOption::new("I release you from our pact.", None)
+                            .with_effect(DialogueEffect::default().breaking_alliance()),
+                    ],
+                ),
+            ],
+        }
+    }
+
+    /// Shadowmere of the Flame-Haters is wary and values caution
+    fn shadowmere_tree() -> DialogueTree {
+        DialogueTree {
+            ally_root: 3,
+            nodes: vec![
+                DialogueNode::new(
+                    "You walk in the open like one who has never been burned. Why are you here?",
+                    vec![
+                        DialogueOption::new("To learn how the Flame-Haters survive.", Some(1))
+                            .with_effect(DialogueEffect::trust(0.05)),
+                        DialogueOption::new("To offer my protection.", Some(2)),
+                        DialogueOption::new("Nothing. Farewell.", None),
+                    ],
+                ),
+                DialogueNode::new(
+                    "We hide from fire and from day. Patience keeps us alive. Do you have patience?",
+                    vec![
+                        DialogueOption::new("I do. I will earn your trust slowly.", None)
+                            .with_effect(DialogueEffect::trust(0.15)),
+                        DialogueOption::new("Patience is for prey.", None)
+                            .with_effect(DialogueEffect::trust(-0.1).and_fear(0.1)),
+                    ],
+                ),
+                DialogueNode::new(
+                    "Protection costs. What would you ask of us?",
+                    vec![
+                        DialogueOption::new("Only your friendship. Join me.", None).with_effect(
+                            DialogueEffect::trust(0.05).proposing_alliance(),
+                        ),
+                        DialogueOption::new("Your obedience.", None)
+                            .with_effect(DialogueEffect::fear(0.15).and_trust(-0.1)),
+                    ],
+                ),
+                DialogueNode::new(
+                    "The shadows are quiet tonight, ally. Do you bring news?",
+                    vec![
+                        DialogueOption::new("Just checking on your people.", None)
+                            .with_effect(DialogueEffect::trust(0.05)),
+                        DialogueOption::new("Our pact ends tonight.", None)
+                            .with_effect(DialogueEffect::default().breaking_alliance()),
+                    ],
+                ),
+            ],
+        }
+    }
+
+    /// Silentfang of the Night-Bloods speaks little and prizes honesty
+    fn silentfang_tree() -> DialogueTree {
+        DialogueTree {
+            ally_root: 2,
+            nodes: vec![
+                DialogueNode::new(
+                    "...",
+                    vec![
+                        DialogueOption::new("I mean you no harm.", Some(1))
+                            .with_effect(DialogueEffect::trust(0.05)),
+                        DialogueOption::new("Answer me when I speak to you.", None)
+                            .with_effect(DialogueEffect::fear(0.1).and_trust(-0.1)),
+                        DialogueOption::new("(Leave quietly)", None),
+                    ],
+                ),
+                DialogueNode::new(
+                    "Harm is easy to promise. What do you truly want?",
+                    vec![
+                        DialogueOption::new("Allies. The night is too large to walk alone.", None)
+                            .with_effect(DialogueEffect::trust(0.1).proposing_alliance()),
+                        DialogueOption::new("Blood, like everyone else.", None)
+                            .with_effect(DialogueEffect::trust(0.1)),
+                    ],
+                ),
+                DialogueNode::new(
+                    "The Night-Bloods are with you.",
+                    vec![
+                        DialogueOption::new("And I with you.", None)
+                            .with_effect(DialogueEffect::trust(0.05)),
+                        DialogueOption::new("No longer.", None)
+                            .with_effect(DialogueEffect::default().breaking_alliance()),
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+/// A full conversation for one clan leader
+#[derive(Debug, Clone)]
+pub struct DialogueTree {
+    pub nodes: Vec<DialogueNode>,
+    /// Node the conversation opens on once the clan is allied or subjugated
+    pub ally_root: usize,
+}
+
+/// One line from the leader and the player's possible replies
+#[derive(Debug, Clone)]
+pub struct DialogueNode {
+    pub line: &'static str,
+    pub options: Vec<DialogueOption>,
+}
+
+impl DialogueNode {
+    fn new(line: &'static str, options: Vec<DialogueOption>) -> Self {
+        Self { line, options }
+    }
+}
+
+/// A reply the player can choose
+#[derive(Debug, Clone)]
+pub struct DialogueOption {
+    pub text: &'static str,
+    pub effect: DialogueEffect,
+    /// Node to continue to, or None to end the conversation
+    pub next: Option<usize>,
+}
+
+impl DialogueOption {
+    fn new(text: &'static str, next: Option<usize>) -> Self {
+        Self {
+            text,
+            effect: DialogueEffect::default(),
+            next,
+        }
+    }
+
+    fn with_effect(mut self, effect: DialogueEffect) -> Self {
+        self.effect = effect;
+        self
+    }
+}
+
+/// How a reply changes the clan's standing with the player
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DialogueEffect {
+    pub trust: f32,
+    pub fear: f32,
+    pub proposes_alliance: bool,
+    pub breaks_alliance: bool,
+    /// The player asks the leader for a quest
+    pub asks_for_work: bool,
+}
+
+impl DialogueEffect {
+    fn trust(amount: f32) -> Self {
+        Self {
+            trust: amount,
+            ..Self::default()
+        }
+    }
+
+    fn fear(amount: f32) -> Self {
+        Self {
+            fear: amount,
+            ..Self::default()
+        }
+    }
+
+    fn and_trust(mut self, amount: f32) -> Self {
+        self.trust = amount;
+        self
+    }
+
+    fn and_fear(mut self, amount: f32) -> Self {
+        self.fear = amount;
+        self
+    }
+
+    fn proposing_alliance(mut self) -> Self {
+        self.proposes_alliance = true;
+        self
+    }
+
+    fn breaking_alliance(mut self) -> Self {
+        self.breaks_alliance = true;
+        self
+    }
+
+    fn asking_for_work() -> Self {
+        Self {
+            asks_for_work: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// A conversation in progress
+#[derive(Debug, Clone)]
+pub struct ActiveDialogue {
+    pub clan_name: String,
+    pub leader_name: String,
+    pub tree: DialogueTree,
+    pub current_node: usize,
+    pub selected_option: usize,
+}
+
+impl ActiveDialogue {
+    /// The node currently being shown
+    pub fn current(&self) -> &DialogueNode {
+        &self.tree.nodes[self.current_node]
+    }
+}
+
+/// Result of choosing a reply
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogueStep {
+    Continue { messages: Vec<String> },
+    Ended { messages: Vec<String> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_clans() -> HashMap<String, Clan> {
+        let mut clans = HashMap::new();
+        clans.insert(
+            "Night-Bloods".to_string(),
+            Clan::new("Night-Bloods", "Silentfang", 10),
+        );
+        clans
+    }
+
+    #[test]
+    fn test_every_leader_has_valid_tree() {
+        for leader in ["Grimjaw", "Shadowmere", "Silentfang"] {
+            let tree = DialogueSystem::tree_for(leader).unwrap();
+            assert!(tree.ally_root < tree.nodes.len());
+            for node in &tree.nodes {
+                assert!(!node.options.is_empty());
+                for option in &node.options {
+                    assert!(option.next.is_none_or(|next| next < tree.nodes.len()));
+                }
+            }
+        }
+        assert!(DialogueSystem::tree_for("Nobody").is_none());
+    }
+
+    #[test]
+    fn test_branching_changes_trust_and_forms_alliance() {
+        let mut clans = create_clans();
+        clans.get_mut("Night-Bloods").unwrap().trust_towards_player = 0.4;
+        let mut dialogue = DialogueSystem::start(&clans["Night-Bloods"]).unwrap();
+
+        // "I mean you no harm." leads deeper into the tree
+        let step = DialogueSystem::choose(&mut dialogue, &mut clans);
+        assert!(matches!(step, DialogueStep::Continue { .. }));
+        assert_eq!(dialogue.current_node, 1);
+
+        // Ask for an alliance; trust now clears the threshold
+        let step = DialogueSystem::choose(&mut dialogue, &mut clans);
+        assert!(matches!(step, DialogueStep::Ended { .. }));
+        assert!(clans["Night-Bloods"].is_allied);
+
+        // Allies open on a different node
+        let dialogue = DialogueSystem::start(&clans["Night-Bloods"]).unwrap();
+        assert_eq!(dialogue.current_node, dialogue.tree.ally_root);
+    }
+
+    #[test]
+    fn test_navigation_wraps_and_threats_raise_fear() {
+        let mut clans = create_clans();
+        let mut dialogue = DialogueSystem::start(&clans["Night-Bloods"]).unwrap();
+
+        // Wrapping up lands on the last reply, asking for work
+        DialogueSystem::navigate(&mut dialogue, false);
+        assert_eq!(dialogue.selected_option, 3);
+        assert!(dialogue.current().options[3].effect.asks_for_work);
+        DialogueSystem::navigate(&mut dialogue, true);
+        DialogueSystem::navigate(&mut dialogue, true);
+        assert_eq!(dialogue.selected_option, 1);
+
+        DialogueSystem::choose(&mut dialogue, &mut clans);
+        assert!(clans["Night-Bloods"].fear_of_player > 0.0);
+        assert!(!clans["Night-Bloods"].is_allied);
+    }
+}
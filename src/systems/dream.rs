@@ -0,0 +1,479 @@
+//! Dream System Module
+//!
+//! Visions of the first vampire's past that come to the player while resting
+//! in a shelter through the day. Each vision is a small scripted scenario:
+//! the loader lays out a handful of temporary figures in a dream space, the
+//! player walks between them (no feeding, fighting or abilities) and speaks
+//! to them in order. Finishing a vision adds its entry to the lore codex and
+//! some visions also grant a skill point. Visions with a time limit fade if
+//! the player is too slow, and can come again on a later day.
+
+use crate::components::Position;
+use crate::input::{InputAction, InputHandler};
+use serde::{Deserialize, Serialize};
+
+/// Seconds of daytime rest before a vision can come
+pub const REST_BEFORE_DREAM: f32 = 20.0;
+
+/// Chance that a long enough rest brings a vision
+const DREAM_CHANCE: f32 = 0.5;
+
+/// Walking speed inside a vision
+const DREAM_WALK_SPEED: f32 = 140.0;
+
+/// How close the dreamer must be to speak to a figure
+const SPEAK_RANGE: f32 = 50.0;
+
+/// Seconds a revealed line stays on screen
+const LINE_DURATION: f32 = 5.0;
+
+/// Half the width and height of the dream space
+pub const DREAM_HALF_WIDTH: f32 = 300.0;
+pub const DREAM_HALF_HEIGHT: f32 = 180.0;
+
+/// How a figure in a vision is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FigureKind {
+    Person,
+    Light,
+    Object,
+}
+
+/// A figure placed in the dream space by a script
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FigureSpec {
+    pub name: &'static str,
+    pub kind: FigureKind,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// One beat of a vision: reach a figure and speak to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisionStep {
+    /// Index into the script's figures
+    pub figure: usize,
+    pub prompt: &'static str,
+    /// What the figure says or shows once reached
+    pub line: &'static str,
+}
+
+/// A lore entry unlocked by finishing a vision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodexEntry {
+    pub title: &'static str,
+    pub text: &'static str,
+}
+
+/// A scripted vision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisionScript {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub intro: &'static str,
+    pub figures: &'static [FigureSpec],
+    pub steps: &'static [VisionStep],
+    /// Seconds before the vision fades, if it is timed
+    pub time_limit: Option<f32>,
+    pub codex: CodexEntry,
+    pub grants_skill_point: bool,
+}
+
+/// Every vision, in the order they come to the player
+pub const VISIONS: [VisionScript; 3] = [
+    VisionScript {
+        id: "barrow",
+        title: "The Barrow",
+        intro: "Cold stone. The smell of earth. You remember waking here, long ago.",
+        figures: &[
+            FigureSpec {
+                name: "Grave Light",
+                kind: FigureKind::Light,
+                x: 200.0,
+                y: -100.0,
+            },
+            FigureSpec {
+                name: "Shepherd",
+                kind: FigureKind::Person,
+                x: -180.0,
+                y: 110.0,
+            },
+        ],
+        steps: &[
+            VisionStep {
+                figure: 0,
+                prompt: "Crawl toward the light at the barrow mouth",
+                line: "Moonlight. Your skin does not burn in it - only the sun will.",
+            },
+            VisionStep {
+                figure: 1,
+                prompt: "Go to the shepherd by the fire",
+                line: "\"You were dead three days, lord. We buried you with the kings.\"",
+            },
+        ],
+        time_limit: None,
+        codex: CodexEntry {
+            title: "The Barrow of the Kings",
+            text: "The first of us did not choose the night. He woke in a royal barrow \
+                   three days after his burial, hungry, and the shepherds who found him \
+                   were the first to feed him.",
+        },
+        grants_skill_point: false,
+    },
+    VisionScript {
+        id: "pact",
+        title: "The Pact of Ash",
+        intro: "Three elders wait around a cold hearth. They have come to bargain.",
+        figures: &[
+            FigureSpec {
+                name: "Elder of the Hills",
+                kind: FigureKind::Person,
+                x: -200.0,
+                y: -60.0,
+            },
+            FigureSpec {
+                name: "Elder of the Rivers",
+                kind: FigureKind::Person,
+                x: 0.0,
+                y: -130.0,
+            },
+            FigureSpec {
+                name: "Elder of the Marsh",
+                kind: FigureKind::Person,
+                x: 200.0,
+                y: -60.0,
+            },
+        ],
+        steps: &[
+            VisionStep {
+                figure: 0,
+                prompt: "Hear the Elder of the Hills",
+                line: "\"Take only what you need, and we will not hunt you.\"",
+            },
+            VisionStep {
+                figure: 1,
+                prompt: "Hear the Elder of the Rivers",
+                line: "\"Our waters are closed to you. Cross them and burn.\"",
+            },
+            VisionStep {
+                figure: 2,
+                prompt: "Hear the Elder of the Marsh",
+                line: "\"Enter no home unbidden. Swear it on ash.\"",
+            },
+        ],
+        time_limit: None,
+        codex: CodexEntry {
+            title: "The Pact of Ash",
+            text: "The old laws that bind us - running water, the threshold, the tithe \
+                   of blood - were not curses. They were the terms of a treaty struck \
+                   with three elders over a cold hearth.",
+        },
+        grants_skill_point: true,
+    },
+    VisionScript {
+        id: "library",
+        title: "The Burning Library",
+        intro: "Smoke and falling embers. The scrolls of your making must be saved.",
+        figures: &[
+            FigureSpec {
+                name: "Scroll of Names",
+                kind: FigureKind::Object,
+                x: -220.0,
+                y: 120.0,
+            },
+            FigureSpec {
+                name: "Scroll of Blood",
+                kind: FigureKind::Object,
+                x: 230.0,
+                y: 130.0,
+            },
+            FigureSpec {
+                name: "Hidden Door",
+                kind: FigureKind::Light,
+                x: 0.0,
+                y: -150.0,
+            },
+        ],
+        steps: &[
+            VisionStep {
+                figure: 0,
+                prompt: "Save the Scroll of Names",
+                line: "Every child of your blood, written in your own hand.",
+            },
+            VisionStep {
+                figure: 1,
+                prompt: "Save the Scroll of Blood",
+                line: "The secret of the thrall-bond. They must not burn it.",
+            },
+            VisionStep {
+                figure: 2,
+                prompt: "Escape through the hidden door",
+                line: "You escape into the night with the scrolls as the roof falls.",
+            },
+        ],
+        time_limit: Some(40.0),
+        codex: CodexEntry {
+            title: "The Burning Library",
+            text: "When the priests burned his library, the first vampire saved only \
+                   two scrolls: the names of his children and the secret of binding \
+                   mortals to his will. Both are said to survive.",
+        },
+        grants_skill_point: true,
+    },
+];
+
+/// A temporary figure loaded into an active vision
+#[derive(Debug, Clone, PartialEq)]
+pub struct DreamFigure {
+    pub name: &'static str,
+    pub kind: FigureKind,
+    pub position: Position,
+}
+
+/// A vision being played
+#[derive(Debug, Clone)]
+pub struct ActiveVision {
+    pub script: &'static VisionScript,
+    pub figures: Vec<DreamFigure>,
+    /// The dreamer's position, relative to the center of the dream space
+    pub dreamer: Position,
+    pub step: usize,
+    pub elapsed: f32,
+    /// The last line revealed and how long it stays on screen
+    pub line: Option<(&'static str, f32)>,
+}
+
+impl ActiveVision {
+    pub fn current_step(&self) -> Option<&'static VisionStep> {
+        self.script.steps.get(self.step)
+    }
+
+    /// Seconds left before a timed vision fades
+    pub fn time_left(&self) -> Option<f32> {
+        self.script
+            .time_limit
+            .map(|limit| (limit - self.elapsed).max(0.0))
+    }
+}
+
+/// How a vision ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum VisionOutcome {
+    Completed {
+        entry: CodexEntry,
+        skill_point: bool,
+    },
+    /// A timed vision ran out
+    Faded,
+    /// The player woke themselves
+    Woken,
+}
+
+impl VisionOutcome {
+    pub fn get_message(&self) -> String {
+        match self {
+            VisionOutcome::Completed {
+                entry,
+                skill_point: true,
+            } => format!(
+                "You wake remembering {}. (Codex entry added, +1 skill point)",
+                entry.title
+            ),
+            VisionOutcome::Completed { entry, .. } => {
+                format!("You wake remembering {}. (Codex entry added)", entry.title)
+            }
+            VisionOutcome::Faded => "The vision slips away before it is done.".to_string(),
+            VisionOutcome::Woken => "You tear yourself from the dream.".to_string(),
+        }
+    }
+}
+
+/// Lore the player has recovered through visions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoreCodex {
+    /// Ids of completed visions, in the order they were completed
+    pub unlocked: Vec<String>,
+}
+
+impl LoreCodex {
+    pub fn has(&self, vision_id: &str) -> bool {
+        self.unlocked.iter().any(|id| id == vision_id)
+    }
+
+    /// Unlocked entries in the order they were found
+    pub fn entries(&self) -> Vec<CodexEntry> {
+        self.unlocked
+            .iter()
+            .filter_map(|id| DreamSystem::vision(id))
+            .map(|script| script.codex)
+            .collect()
+    }
+}
+
+/// Dream system responsible for visions during rest
+pub struct DreamSystem;
+
+impl DreamSystem {
+    pub fn vision(id: &str) -> Option<&'static VisionScript> {
+        VISIONS.iter().find(|script| script.id == id)
+    }
+
+    /// The first vision the player has not yet completed
+    pub fn next_vision(codex: &LoreCodex) -> Option<&'static VisionScript> {
+        VISIONS.iter().find(|script| !codex.has(script.id))
+    }
+
+    /// Called through a daytime rest. Once the player has rested long enough
+    /// the day gets a single chance roll for a vision.
+    pub fn rest_brings_dream(
+        rest_time: f32,
+        last_roll_day: &mut Option<u32>,
+        day: u32,
+        roll: f32,
+    ) -> bool {
+        if rest_time < REST_BEFORE_DREAM || *last_roll_day == Some(day) {
+            return false;
+        }
+        *last_roll_day = Some(day);
+        roll < DREAM_CHANCE
+    }
+
+    /// Load a script into a playable vision with its temporary figures
+    pub fn load(script: &'static VisionScript) -> ActiveVision {
+        ActiveVision {
+            script,
+            figures: script
+                .figures
+                .iter()
+                .map(|spec| DreamFigure {
+                    name: spec.name,
+                    kind: spec.kind,
+                    position: Position::new(spec.x, spec.y),
+                })
+                .collect(),
+            dreamer: Position::new(0.0, 0.0),
+            step: 0,
+            elapsed: 0.0,
+            line: Some((script.intro, LINE_DURATION)),
+        }
+    }
+
+    /// Play one frame of a vision: walking, speaking with Interact, and
+    /// waking with Pause. Returns the outcome once the vision is over.
+    pub fn update(
+        vision: &mut ActiveVision,
+        input_handler: &InputHandler,
+        delta_time: f32,
+    ) -> Option<VisionOutcome> {
+        if input_handler.is_action_just_pressed(InputAction::Pause) {
+            return Some(VisionOutcome::Woken);
+        }
+
+        vision.elapsed += delta_time;
+        if vision.time_left() == Some(0.0) {
+            return Some(VisionOutcome::Faded);
+        }
+        if let Some((_, remaining)) = vision.line.as_mut() {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                vision.line = None;
+            }
+        }
+
+        let (dx, dy) = input_handler.movement_vector();
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.01 {
+            let step = DREAM_WALK_SPEED * delta_time / length;
+            vision.dreamer.x =
+                (vision.dreamer.x + dx * step).clamp(-DREAM_HALF_WIDTH, DREAM_HALF_WIDTH);
+            vision.dreamer.y =
+                (vision.dreamer.y + dy * step).clamp(-DREAM_HALF_HEIGHT, DREAM_HALF_HEIGHT);
+        }
+
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            return Self::speak(vision);
+        }
+        None
+    }
+
+    /// Speak to the figure the current step asks for, if the dreamer is
+    /// beside it
+    pub fn speak(vision: &mut ActiveVision) -> Option<VisionOutcome> {
+        let step = vision.current_step()?;
+        let figure = &vision.figures[step.figure];
+        if figure.position.distance_to(&vision.dreamer) > SPEAK_RANGE {
+            return None;
+        }
+
+        vision.line = Some((step.line, LINE_DURATION));
+        vision.step += 1;
+        if vision.step < vision.script.steps.len() {
+            return None;
+        }
+        Some(VisionOutcome::Completed {
+            entry: vision.script.codex,
+            skill_point: vision.script.grants_skill_point,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vision_plays_through_to_codex_entry() {
+        let mut codex = LoreCodex::default();
+        let script = DreamSystem::next_vision(&codex).unwrap();
+        assert_eq!(script.id, "barrow");
+        let mut vision = DreamSystem::load(script);
+        assert_eq!(vision.figures.len(), 2);
+
+        // Too far away to speak
+        assert_eq!(DreamSystem::speak(&mut vision), None);
+        assert_eq!(vision.step, 0);
+
+        vision.dreamer = vision.figures[0].position;
+        assert_eq!(DreamSystem::speak(&mut vision), None);
+        vision.dreamer = vision.figures[1].position;
+        let outcome = DreamSystem::speak(&mut vision);
+        assert!(matches!(
+            outcome,
+            Some(VisionOutcome::Completed {
+                skill_point: false,
+                ..
+            })
+        ));
+
+        codex.unlocked.push(script.id.to_string());
+        assert_eq!(codex.entries()[0].title, "The Barrow of the Kings");
+        assert_eq!(DreamSystem::next_vision(&codex).unwrap().id, "pact");
+
+        // Only a long enough rest brings a vision, with one roll a day
+        let mut last_roll_day = None;
+        assert!(!DreamSystem::rest_brings_dream(
+            5.0,
+            &mut last_roll_day,
+            1,
+            0.0
+        ));
+        assert!(!DreamSystem::rest_brings_dream(
+            REST_BEFORE_DREAM,
+            &mut last_roll_day,
+            1,
+            0.9
+        ));
+        assert!(!DreamSystem::rest_brings_dream(
+            REST_BEFORE_DREAM,
+            &mut last_roll_day,
+            1,
+            0.0
+        ));
+        assert!(DreamSystem::rest_brings_dream(
+            REST_BEFORE_DREAM,
+            &mut last_roll_day,
+            2,
+            0.0
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+//! Effects System Module
+//!
+//! Short-lived hit feedback built from the frame's damage events: a number
+//! that floats up from where each blow landed, a brief recoil that knocks the
+//! struck creature back from its attacker, and screen shake when the player
+//! is the one hit. The red flash on the struck sprite comes from its visual
+//! state; everything here only decides what the renderer draws.
+
+use crate::components::*;
+use crate::systems::DamageEvent;
+use macroquad::prelude::*;
+
+/// Seconds a damage number stays on screen
+const NUMBER_LIFETIME: f32 = 0.9;
+/// How fast a damage number floats upward, in world units per second
+const NUMBER_RISE: f32 = 45.0;
+/// Most damage numbers shown at once; the oldest make way for new ones
+const MAX_NUMBERS: usize = 48;
+
+/// Seconds a struck creature spends knocked back
+const RECOIL_DURATION: f32 = 0.15;
+/// How far a blow knocks its target back at its peak, in world units
+const RECOIL_DISTANCE: f32 = 6.0;
+
+/// Trauma added by a blow to the player, plus more per point of damage
+const SHAKE_BASE: f32 = 0.3;
+const SHAKE_PER_DAMAGE: f32 = 0.02;
+/// Trauma lost per second
+const SHAKE_DECAY: f32 = 2.0;
+/// Screen offset at full trauma, in pixels
+const MAX_SHAKE: f32 = 10.0;
+
+/// What a floating number reports, which decides its color and text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageNumberKind {
+    /// Damage dealt to anyone other than the player
+    Hit,
+    /// A critical blow
+    Critical,
+    /// A blow that missed
+    Dodge,
+    /// Damage taken by the player
+    Taken,
+}
+
+impl DamageNumberKind {
+    pub fn color(&self) -> Color {
+        match self {
+            DamageNumberKind::Hit => WHITE,
+            DamageNumberKind::Critical => Color::new(1.0, 0.85, 0.2, 1.0),
+            DamageNumberKind::Dodge => Color::new(0.7, 0.7, 0.75, 1.0),
+            DamageNumberKind::Taken => Color::new(1.0, 0.25, 0.25, 1.0),
+        }
+    }
+
+    /// Font size relative to an ordinary hit
+    pub fn scale(&self) -> f32 {
+        match self {
+            DamageNumberKind::Critical => 1.4,
+            DamageNumberKind::Dodge => 0.85,
+            DamageNumberKind::Hit | DamageNumberKind::Taken => 1.0,
+        }
+    }
+}
+
+/// A number floating up from where a blow landed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageNumber {
+    pub position: Position,
+    pub amount: f32,
+    pub kind: DamageNumberKind,
+    /// Seconds since it appeared
+    pub age: f32,
+}
+
+impl DamageNumber {
+    pub fn text(&self) -> String {
+        match self.kind {
+            DamageNumberKind::Dodge => "Miss".to_string(),
+            DamageNumberKind::Critical => format!("{:.0}!", self.amount),
+            DamageNumberKind::Hit | DamageNumberKind::Taken => format!("{:.0}", self.amount),
+        }
+    }
+
+    /// How far it has floated above where it appeared
+    pub fn rise(&self) -> f32 {
+        self.age * NUMBER_RISE
+    }
+
+    /// Opacity, fading out over the second half of its life
+    pub fn alpha(&self) -> f32 {
+        (2.0 * (1.0 - self.age / NUMBER_LIFETIME)).clamp(0.0, 1.0)
+    }
+}
+
+/// A creature knocked back from the blow that struck it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recoil {
+    pub entity_id: u32,
+    /// Unit direction away from the attacker
+    pub direction: (f32, f32),
+    /// Seconds left
+    pub remaining: f32,
+}
+
+/// Hit feedback waiting to be drawn
+#[derive(Debug, Clone, Default)]
+pub struct HitEffects {
+    pub numbers: Vec<DamageNumber>,
+    pub recoils: Vec<Recoil>,
+    /// Screen shake strength, from 0.0 to 1.0
+    pub trauma: f32,
+}
+
+impl HitEffects {
+    /// How far to draw an entity from where it stands while it recoils
+    pub fn recoil_offset(&self, entity_id: u32) -> (f32, f32) {
+        self.recoils
+            .iter()
+            .find(|recoil| recoil.entity_id == entity_id)
+            .map_or((0.0, 0.0), |recoil| {
+                // Snaps out at once and eases back in
+                let distance = RECOIL_DISTANCE * recoil.remaining / RECOIL_DURATION;
+                (recoil.direction.0 * distance, recoil.direction.1 * distance)
+            })
+    }
+
+    /// How far to move the camera this frame, in pixels
+    pub fn shake_offset(&self, time: f32) -> (f32, f32) {
+        if self.trauma <= 0.0 {
+            return (0.0, 0.0);
+        }
+        // Squaring makes small knocks subtle and heavy ones violent
+        let strength = self.trauma * self.trauma * MAX_SHAKE;
+        (
+            (time * 47.0).sin() * (time * 13.0).cos() * strength,
+            (time * 53.0).cos() * (time * 17.0).sin() * strength,
+        )
+    }
+}
+
+/// Effects system responsible for hit feedback
+pub struct EffectsSystem;
+
+impl EffectsSystem {
+    /// Add feedback for the blows in `events`. The screen only shakes for
+    /// blows to the player when `screen_shake` is on.
+    pub fn spawn(
+        effects: &mut HitEffects,
+        events: &[DamageEvent],
+        entities: &[GameEntity],
+        player_id: u32,
+        screen_shake: bool,
+    ) {
+        for event in events {
+            let on_player = event.target_id == player_id;
+            let kind = if event.dodged {
+                DamageNumberKind::Dodge
+            } else if event.is_critical {
+                DamageNumberKind::Critical
+            } else if on_player {
+                DamageNumberKind::Taken
+            } else {
+                DamageNumberKind::Hit
+            };
+            effects.numbers.push(DamageNumber {
+                position: event.position,
+                amount: event.amount,
+                kind,
+                age: 0.0,
+            });
+
+            if event.dodged {
+                continue;
+            }
+            if let Some(direction) = Self::knockback_direction(entities, event) {
+                effects
+                    .recoils
+                    .retain(|recoil| recoil.entity_id != event.target_id);
+                effects.recoils.push(Recoil {
+                    entity_id: event.target_id,
+                    direction,
+                    remaining: RECOIL_DURATION,
+                });
+            }
+            if on_player && screen_shake {
+                effects.trauma =
+                    (effects.trauma + SHAKE_BASE + event.amount * SHAKE_PER_DAMAGE).min(1.0);
+            }
+        }
+
+        let excess = effects.numbers.len().saturating_sub(MAX_NUMBERS);
+        effects.numbers.drain(..excess);
+    }
+
+    /// Unit direction from the attacker to the target, if both are known
+    fn knockback_direction(entities: &[GameEntity], event: &DamageEvent) -> Option<(f32, f32)> {
+        let attacker = entities.iter().find(|e| e.id == event.attacker_id)?;
+        let target = entities.iter().find(|e| e.id == event.target_id)?;
+        let dx = target.position.x - attacker.position.x;
+        let dy = target.position.y - attacker.position.y;
+        let length = (dx * dx + dy * dy).sqrt();
+        (length > f32::EPSILON).then(|| (dx / length, dy / length))
+    }
+
+    /// Age numbers and recoils, dropping those that are done, and let the
+    /// screen settle
+    pub fn update(effects: &mut HitEffects, delta_time: f32) {
+        for number in &mut effects.numbers {
+            number.age += delta_time;
+        }
+        effects
+            .numbers
+            .retain(|number| number.age < NUMBER_LIFETIME);
+
+        for recoil in &mut effects.recoils {
+            recoil.remaining -= delta_time;
+        }
+        effects.recoils.retain(|recoil| recoil.remaining > 0.0);
+
+        effects.trauma = (effects.trauma - SHAKE_DECAY * delta_time).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{HitReaction, WorldSystem};
+
+    #[test]
+    fn test_hits_float_numbers_knock_back_and_shake_only_for_the_player() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(100.0, 100.0);
+        let infected = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 150.0, 100.0);
+        let hit = |attacker_id, target_id, amount, dodged| DamageEvent {
+            attacker_id,
+            target_id,
+            amount,
+            position: Position::new(150.0, 100.0),
+            is_critical: false,
+            dodged,
+            killed: false,
+            reaction: HitReaction::None,
+            time: 0.0,
+        };
+
+        // The player's blow knocks the infected away to the right, and the
+        // screen stays still
+        let mut effects = HitEffects::default();
+        EffectsSystem::spawn(
+            &mut effects,
+            &[hit(player_id, infected, 12.0, false)],
+            &entities,
+            player_id,
+            true,
+        );
+        assert_eq!(effects.numbers[0].kind, DamageNumberKind::Hit);
+        assert_eq!(effects.numbers[0].text(), "12");
+        assert!(effects.recoil_offset(infected).0 > 0.0);
+        assert_eq!(effects.recoil_offset(player_id), (0.0, 0.0));
+        assert_eq!(effects.trauma, 0.0);
+
+        // The infected's reply shakes the screen unless shake is turned off;
+        // a miss only shows a number
+        let reply = [hit(infected, player_id, 10.0, false)];
+        let mut still = effects.clone();
+        EffectsSystem::spawn(&mut still, &reply, &entities, player_id, false);
+        assert_eq!(still.trauma, 0.0);
+        EffectsSystem::spawn(&mut effects, &reply, &entities, player_id, true);
+        assert_eq!(effects.numbers[1].kind, DamageNumberKind::Taken);
+        assert!(effects.recoil_offset(player_id).0 < 0.0);
+        assert!(effects.trauma > 0.0);
+        EffectsSystem::spawn(
+            &mut effects,
+            &[hit(infected, player_id, 10.0, true)],
+            &entities,
+            player_id,
+            true,
+        );
+        assert_eq!(effects.numbers[2].text(), "Miss");
+
+        // Everything rises, fades and settles within a second
+        EffectsSystem::update(&mut effects, 0.5);
+        assert!(effects.numbers[0].rise() > 0.0);
+        assert!(effects.numbers[0].alpha() < 1.0);
+        assert!(effects.recoils.is_empty());
+        EffectsSystem::update(&mut effects, 0.5);
+        assert!(effects.numbers.is_empty());
+        assert_eq!(effects.trauma, 0.0);
+        assert_eq!(effects.shake_offset(1.0), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,160 @@
+//! Events Module
+//!
+//! A typed queue for things that happen in the world which other systems
+//! care about. Feeding, fights, sunlight, shelters, alliances and objectives
+//! push events as they happen; the bus delivers them at the start of the next
+//! frame, when the objective counters and feedback effects read them.
+//! Producers never need to know who is listening.
+
+use crate::components::*;
+
+/// Something that happened this frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// A creature was killed in a fight
+    EntityDied {
+        entity_id: u32,
+        killer_id: u32,
+        position: Position,
+    },
+    /// A vampire fed, killing the victim unless it was a non-lethal drain
+    FedOnTarget {
+        feeder_id: u32,
+        target_id: u32,
+        position: Position,
+        lethal: bool,
+    },
+    /// Members of a clan saw the player commit a crime against one of
+    /// their own
+    CrimeWitnessed {
+        clan_name: String,
+        crime: Crime,
+    },
+    /// A clan's loyalty collapsed and its members turned on the player
+    ClanTurnedHostile {
+        clan_name: String,
+    },
+    /// Sunlight reached a vampire and hurt them
+    SunlightBurned {
+        entity_id: u32,
+        position: Position,
+    },
+    ShelterEntered {
+        entity_id: u32,
+        shelter_id: u32,
+    },
+    ShelterLeft {
+        entity_id: u32,
+        shelter_id: u32,
+    },
+    ClanAllied {
+        clan_name: String,
+    },
+    ObjectiveCompleted {
+        objective: String,
+    },
+    PhaseAdvanced {
+        phase: GamePhase,
+    },
+}
+
+impl GameEvent {
+    /// What the player is told, for events worth announcing
+    pub fn get_message(&self) -> Option<String> {
+        match self {
+            GameEvent::ClanAllied { clan_name } => {
+                Some(format!("The {} now stand with you.", clan_name))
+            }
+            GameEvent::CrimeWitnessed { clan_name, crime } => Some(format!(
+                "The {} saw you {} one of their own. Their trust in you falls.",
+                clan_name,
+                crime.description()
+            )),
+            GameEvent::ClanTurnedHostile { clan_name } => Some(format!(
+                "The {} have had enough of you. Their members turn hostile!",
+                clan_name
+            )),
+            GameEvent::ObjectiveCompleted { objective } => {
+                Some(format!("Objective complete: {}", objective))
+            }
+            GameEvent::PhaseAdvanced { phase } => Some(format!("A new phase begins: {:?}", phase)),
+            _ => None,
+        }
+    }
+}
+
+/// Events waiting for the next frame, and those delivered this frame
+#[derive(Debug, Clone, Default)]
+pub struct EventBus {
+    queued: Vec<GameEvent>,
+    delivered: Vec<GameEvent>,
+}
+
+impl EventBus {
+    /// Queue an event for delivery next frame
+    pub fn push(&mut self, event: GameEvent) {
+        self.queued.push(event);
+    }
+
+    /// Start a new frame: last frame's events are delivered and the ones
+    /// delivered before them dropped
+    pub fn advance(&mut self) {
+        self.delivered = std::mem::take(&mut self.queued);
+    }
+
+    /// Events delivered this frame, in the order they were pushed
+    pub fn events(&self) -> &[GameEvent] {
+        &self.delivered
+    }
+
+    /// Events queued so far for next frame
+    pub fn queued(&self) -> &[GameEvent] {
+        &self.queued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::ObjectivesSystem;
+
+    #[test]
+    fn test_events_arrive_next_frame_and_feed_the_objective_counters() {
+        let mut bus = EventBus::default();
+        bus.push(GameEvent::FedOnTarget {
+            feeder_id: 0,
+            target_id: 4,
+            position: Position::new(10.0, 10.0),
+            lethal: true,
+        });
+        bus.push(GameEvent::EntityDied {
+            entity_id: 5,
+            killer_id: 0,
+            position: Position::new(20.0, 10.0),
+        });
+        bus.push(GameEvent::EntityDied {
+            entity_id: 6,
+            killer_id: 5,
+            position: Position::new(30.0, 10.0),
+        });
+
+        // Nothing is delivered until the next frame starts
+        assert!(bus.events().is_empty());
+        bus.advance();
+        assert_eq!(bus.events().len(), 3);
+        assert!(bus.queued().is_empty());
+
+        // Only the player's own feedings and kills count
+        let (mut kills, mut feedings) = (0, 0);
+        ObjectivesSystem::record_events(bus.events(), 0, &mut kills, &mut feedings);
+        assert_eq!((kills, feedings), (1, 1));
+
+        // Delivered events last one frame
+        bus.advance();
+        assert!(bus.events().is_empty());
+        let allied = GameEvent::ClanAllied {
+            clan_name: "Night-Bloods".to_string(),
+        };
+        assert!(allied.get_message().unwrap().contains("Night-Bloods"));
+    }
+}
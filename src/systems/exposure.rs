@@ -0,0 +1,212 @@
+//! Exposure System Module
+//!
+//! Works out how much sunlight reaches each creature. Every shelter casts a
+//! shadow away from the sun: its footprint swept along a direction that
+//! follows the sun from east to west over the day, long at dawn and dusk
+//! and short at noon. A vampire standing in shade takes less sun damage in
+//! proportion to how much of their body the shade covers.
+
+use crate::components::*;
+use crate::systems::time::TimeSystem;
+
+/// Longest shadow a shelter casts, however low the sun
+const MAX_SHADOW_LENGTH: f32 = 200.0;
+/// Highest the sun climbs, as a fraction of straight overhead
+const MAX_SUN_ELEVATION: f32 = 0.9;
+/// Share of sunlight full shade keeps off; shade is never as safe as a roof
+const SHADE_PROTECTION: f32 = 0.8;
+/// Body radius for creatures without a collider
+const DEFAULT_BODY_RADIUS: f32 = 8.0;
+
+/// The shadow one shelter casts at this time of day
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shadow {
+    pub shelter_id: u32,
+    /// Convex outline of the shadow in world space
+    pub polygon: Vec<Position>,
+    /// Share of sunlight the shadow blocks
+    pub density: f32,
+}
+
+impl Shadow {
+    /// Whether a point lies inside the shadow
+    pub fn contains(&self, point: Position) -> bool {
+        let count = self.polygon.len();
+        count >= 3
+            && (0..count).all(|index| {
+                let a = self.polygon[index];
+                let b = self.polygon[(index + 1) % count];
+                Self::cross(a, b, point) >= 0.0
+            })
+    }
+
+    /// Positive when `point` lies to the left of the edge from `a` to `b`
+    fn cross(a: Position, b: Position, point: Position) -> f32 {
+        (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x)
+    }
+}
+
+/// Exposure system responsible for shadows and how much sun gets through
+pub struct ExposureSystem;
+
+impl ExposureSystem {
+    /// Direction shadows fall and how far per unit of shelter height, or
+    /// `None` while the sun is down
+    pub fn shadow_direction(time: &TimeSystem) -> Option<(f32, f32, f32)> {
+        let intensity = time.get_sunlight_intensity();
+        if intensity <= 0.0 {
+            return None;
+        }
+        // The sun rises in the east, passes south at noon and sets in the
+        // west, so shadows swing from west through north to east
+        let azimuth = (time.current_time() - 6.0) / 12.0 * std::f32::consts::PI;
+        let elevation = intensity * MAX_SUN_ELEVATION * std::f32::consts::FRAC_PI_2;
+        Some((-azimuth.cos(), -azimuth.sin(), 1.0 / elevation.tan()))
+    }
+
+    /// Shadows of every shelter standing in the sun
+    pub fn cast_shadows(entities: &[GameEntity], time: &TimeSystem) -> Vec<Shadow> {
+        let Some((direction_x, direction_y, length_per_height)) = Self::shadow_direction(time)
+        else {
+            return Vec::new();
+        };
+
+        entities
+            .iter()
+            .filter_map(|entity| {
+                let shelter_type = &entity.shelter.as_ref()?.shelter_type;
+                let height = shelter_type.shadow_height();
+                if height <= 0.0 {
+                    return None;
+                }
+                let length = (height * length_per_height).min(MAX_SHADOW_LENGTH);
+                let (width, depth) = shelter_type.visual_size();
+                let (half_width, half_depth) = (width / 2.0, depth / 2.0);
+
+                let mut corners = Vec::with_capacity(8);
+                for (corner_x, corner_y) in [
+                    (-half_width, -half_depth),
+                    (half_width, -half_depth),
+                    (half_width, half_depth),
+                    (-half_width, half_depth),
+                ] {
+                    let x = entity.position.x + corner_x;
+                    let y = entity.position.y + corner_y;
+                    corners.push(Position::new(x, y));
+                    corners.push(Position::new(
+                        x + direction_x * length,
+                        y + direction_y * length,
+                    ));
+                }
+
+                Some(Shadow {
+                    shelter_id: entity.id,
+                    polygon: Self::convex_hull(corners),
+                    density: shelter_type.shade_density(),
+                })
+            })
+            .collect()
+    }
+
+    /// Share of full sunlight reaching a creature, from 1.0 in the open
+    /// down to `1.0 - SHADE_PROTECTION` when deep shade covers them
+    pub fn exposure(shadows: &[Shadow], entity: &GameEntity) -> f32 {
+        let radius = match entity.collider {
+            Some(Collider::Circle { radius }) => radius,
+            _ => DEFAULT_BODY_RADIUS,
+        };
+        let center = entity.position;
+        let samples = [
+            center,
+            Position::new(center.x - radius, center.y),
+            Position::new(center.x + radius, center.y),
+            Position::new(center.x, center.y - radius),
+            Position::new(center.x, center.y + radius),
+        ];
+
+        let coverage = samples
+            .iter()
+            .map(|&point| {
+                shadows
+                    .iter()
+                    .filter(|shadow| shadow.contains(point))
+                    .map(|shadow| shadow.density)
+                    .fold(0.0, f32::max)
+            })
+            .sum::<f32>()
+            / samples.len() as f32;
+
+        1.0 - coverage * SHADE_PROTECTION
+    }
+
+    /// Convex hull of a set of points (monotone chain), wound so that
+    /// `Shadow::contains` sees the inside on the left of every edge
+    fn convex_hull(mut points: Vec<Position>) -> Vec<Position> {
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        let mut hull: Vec<Position> = Vec::with_capacity(points.len() + 1);
+        for pass in 0..2 {
+            let start = hull.len();
+            for &point in &points {
+                while hull.len() >= start + 2
+                    && Shadow::cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            // The last point of each half starts the other one
+            hull.pop();
+            if pass == 0 {
+                points.reverse();
+            }
+        }
+        hull
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ShelterSystem, WorldSystem};
+
+    #[test]
+    fn test_shadows_follow_the_sun_and_shade_reduces_exposure() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            500.0,
+            800.0,
+            None,
+            None,
+        );
+        let mut time = TimeSystem::new();
+
+        // In the morning the building's shadow falls to the northwest
+        time.set_time(8.0);
+        let shadows = ExposureSystem::cast_shadows(&entities, &time);
+        assert_eq!(shadows.len(), 1);
+        entities[0].position = Position::new(429.0, 759.0);
+        let shaded = ExposureSystem::exposure(&shadows, &entities[0]);
+        assert!((shaded - (1.0 - SHADE_PROTECTION)).abs() < 0.01);
+
+        // Standing against the sunny east wall leaves part of the body lit
+        entities[0].position = Position::new(540.0, 800.0);
+        let partial = ExposureSystem::exposure(&shadows, &entities[0]);
+        assert!(partial > 1.0 - SHADE_PROTECTION && partial < 1.0);
+
+        // By evening the shadow has swung round to the northeast
+        entities[0].position = Position::new(571.0, 759.0);
+        assert_eq!(ExposureSystem::exposure(&shadows, &entities[0]), 1.0);
+        time.set_time(16.0);
+        let shadows = ExposureSystem::cast_shadows(&entities, &time);
+        assert!(ExposureSystem::exposure(&shadows, &entities[0]) < 0.5);
+
+        // No shadows at night
+        time.set_time(23.0);
+        assert!(ExposureSystem::cast_shadows(&entities, &time).is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+//! Hibernation System Module
+//!
+//! Lets the player sleep through the day inside a shelter. Holding the feed
+//! key while sheltered by day sends the vampire into hibernation: the clock
+//! runs many times faster until sunset, with blood draining faster than usual
+//! for every hour skipped. Infected at the shelter, a blow to the sleeper,
+//! leaving the shelter or running low on blood wakes them early. The screen
+//! fades out while asleep and back in on waking.
+
+use crate::components::*;
+use crate::settings::Difficulty;
+use crate::systems::time::TimeSystem;
+
+/// Seconds the key must be held to fall asleep
+pub const HOLD_SECONDS: f32 = 1.0;
+
+/// How many times faster time passes while hibernating
+pub const SKIP_SPEED: f32 = 12.0;
+
+/// Blood drained per skipped second, relative to the usual drain
+const HIBERNATION_DRAIN: f32 = 1.25;
+
+/// Infected this close to the shelter rouse the sleeper
+const ALERT_RADIUS: f32 = 80.0;
+
+/// Seconds for the screen to fade fully out or back in
+const FADE_SECONDS: f32 = 0.6;
+
+/// A vampire asleep in their shelter, or just waking
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hibernation {
+    pub shelter_id: u32,
+    /// In-game hours skipped so far
+    pub hours_skipped: f32,
+    /// How dark the screen is (0.0 to 1.0)
+    pub fade: f32,
+    /// Asleep no longer; the screen is fading back in
+    pub waking: bool,
+}
+
+impl Hibernation {
+    /// Whether time is still being skipped
+    pub fn is_asleep(&self) -> bool {
+        !self.waking
+    }
+}
+
+/// Why hibernation began or ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum HibernationEvent {
+    FellAsleep,
+    WokeAtSunset { hours: f32 },
+    Disturbed { hours: f32 },
+    Starving { hours: f32 },
+    LeftShelter,
+}
+
+impl HibernationEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            HibernationEvent::FellAsleep => {
+                "You sink into the deathlike sleep of your kind...".to_string()
+            }
+            HibernationEvent::WokeAtSunset { hours } => {
+                format!("You wake at sunset after {:.0} hours of sleep.", hours)
+            }
+            HibernationEvent::Disturbed { hours } => format!(
+                "Something at the shelter wakes you after {:.0} hours!",
+                hours
+            ),
+            HibernationEvent::Starving { hours } => {
+                format!("Hunger drags you awake after {:.0} hours.", hours)
+            }
+            HibernationEvent::LeftShelter => "Your sleep is broken.".to_string(),
+        }
+    }
+}
+
+/// Hibernation system responsible for sleeping through the day
+pub struct HibernationSystem;
+
+impl HibernationSystem {
+    /// Count up while the key is held inside a shelter by day, falling
+    /// asleep once it has been held long enough
+    pub fn hold(
+        hold_time: &mut f32,
+        held: bool,
+        shelter_id: Option<u32>,
+        time: &TimeSystem,
+        delta_time: f32,
+    ) -> Option<Hibernation> {
+        let shelter_id = match shelter_id {
+            Some(id) if held && time.is_day() => id,
+            _ => {
+                *hold_time = 0.0;
+                return None;
+            }
+        };
+        *hold_time += delta_time;
+        if *hold_time < HOLD_SECONDS {
+            return None;
+        }
+        *hold_time = 0.0;
+        Some(Self::sleep(shelter_id))
+    }
+
+    /// Fall asleep in a shelter straight away
+    pub fn sleep(shelter_id: u32) -> Hibernation {
+        Hibernation {
+            shelter_id,
+            hours_skipped: 0.0,
+            fade: 0.0,
+            waking: false,
+        }
+    }
+
+    /// How fast the clock should run: `SKIP_SPEED` while asleep
+    pub fn time_scale(hibernation: Option<&Hibernation>) -> f32 {
+        if hibernation.is_some_and(Hibernation::is_asleep) {
+            SKIP_SPEED
+        } else {
+            1.0
+        }
+    }
+
+    /// Drain a sleeping player's blood for the time skipped this frame and
+    /// check whether they wake, or fade the screen back in for a waking one.
+    /// `attacked` is whether the player was hit this frame. Returns the
+    /// reason if they woke; the hibernation is done with once `fade` is back
+    /// to zero. The clock itself is sped up through `time_scale`.
+    pub fn update(
+        hibernation: &mut Hibernation,
+        time: &TimeSystem,
+        entities: &mut [GameEntity],
+        player_id: u32,
+        attacked: bool,
+        difficulty: Difficulty,
+        delta_time: f32,
+    ) -> Option<HibernationEvent> {
+        if hibernation.waking {
+            hibernation.fade = (hibernation.fade - delta_time / FADE_SECONDS).max(0.0);
+            return None;
+        }
+        hibernation.fade = (hibernation.fade + delta_time / FADE_SECONDS).min(1.0);
+
+        let event = Self::wake_reason(hibernation, time, entities, player_id, attacked);
+        if event.is_some() {
+            hibernation.waking = true;
+            return event;
+        }
+
+        // The blood system already drains for `delta_time`
+        let skipped = delta_time * (SKIP_SPEED - 1.0);
+        hibernation.hours_skipped += time.hours_in(skipped + delta_time);
+
+        if let Some(blood) = entities
+            .iter_mut()
+            .find(|entity| entity.id == player_id)
+            .and_then(|player| player.blood_meter.as_mut())
+        {
+            let drain = blood.drain_rate
+                * difficulty.blood_drain_multiplier()
+                * (skipped * HIBERNATION_DRAIN + delta_time * (HIBERNATION_DRAIN - 1.0));
+            blood.current = (blood.current - drain).max(0.0);
+        }
+        None
+    }
+
+    fn wake_reason(
+        hibernation: &Hibernation,
+        time: &TimeSystem,
+        entities: &[GameEntity],
+        player_id: u32,
+        attacked: bool,
+    ) -> Option<HibernationEvent> {
+        let hours = hibernation.hours_skipped;
+        let player = entities.iter().find(|entity| entity.id == player_id)?;
+        let sheltered = player
+            .shelter_occupancy
+            .as_ref()
+            .and_then(|occupancy| occupancy.shelter_id)
+            == Some(hibernation.shelter_id);
+        if !sheltered {
+            return Some(HibernationEvent::LeftShelter);
+        }
+        if time.is_night() {
+            return Some(HibernationEvent::WokeAtSunset { hours });
+        }
+        if player
+            .blood_meter
+            .as_ref()
+            .is_some_and(BloodMeter::is_starving)
+        {
+            return Some(HibernationEvent::Starving { hours });
+        }
+
+        let shelter_position = entities
+            .iter()
+            .find(|entity| entity.id == hibernation.shelter_id)?
+            .position;
+        let besieged = entities.iter().any(|entity| {
+            entity.entity_type == EntityType::HostileInfected
+                && entity.health.as_ref().is_some_and(Health::is_alive)
+                && !entity
+                    .shelter_occupancy
+                    .as_ref()
+                    .is_some_and(ShelterOccupancy::is_in_shelter)
+                && entity.position.distance_to(&shelter_position) <= ALERT_RADIUS
+        });
+        if attacked || besieged {
+            return Some(HibernationEvent::Disturbed { hours });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::shelter::ShelterSystem;
+    use crate::systems::world::WorldSystem;
+
+    #[test]
+    fn test_hibernation_skips_to_sunset_unless_disturbed() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let shelter_id = ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Cave,
+            400.0,
+            800.0,
+            None,
+            None,
+        );
+        entities[0].position = Position::new(400.0, 800.0);
+        entities[0]
+            .shelter_occupancy
+            .get_or_insert_with(ShelterOccupancy::new)
+            .enter_shelter(shelter_id, 0.0);
+        entities[0].blood_meter.as_mut().unwrap().current = 100.0;
+        let mut time = TimeSystem::with_settings(8.0, 120.0);
+
+        // A held key by day puts the vampire to sleep after a second
+        let mut hold = 0.0;
+        assert!(HibernationSystem::hold(&mut hold, true, Some(shelter_id), &time, 0.5).is_none());
+        let mut hibernation =
+            HibernationSystem::hold(&mut hold, true, Some(shelter_id), &time, 0.5).unwrap();
+
+        // Time races to sunset, costing more blood than staying awake
+        let mut woke = None;
+        let mut frames = 0;
+        while woke.is_none() && frames < 1000 {
+            time.update(0.1 * HibernationSystem::time_scale(Some(&hibernation)));
+            woke = HibernationSystem::update(
+                &mut hibernation,
+                &time,
+                &mut entities,
+                player_id,
+                false,
+                Difficulty::Normal,
+                0.1,
+            );
+            frames += 1;
+        }
+        assert!(matches!(woke, Some(HibernationEvent::WokeAtSunset { hours }) if hours > 9.0));
+        assert!(frames < 60);
+        let blood = entities[0].blood_meter.as_ref().unwrap().current;
+        assert!(blood < 100.0 - 50.0 * HIBERNATION_DRAIN * 0.9);
+        assert!(hibernation.waking && hibernation.fade > 0.0);
+
+        // Infected at the door wake the sleeper
+        time.set_time(8.0);
+        entities[0].blood_meter.as_mut().unwrap().current = 100.0;
+        let mut hibernation =
+            HibernationSystem::hold(&mut hold, true, Some(shelter_id), &time, 1.0).unwrap();
+        WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 430.0, 800.0);
+        let woke = HibernationSystem::update(
+            &mut hibernation,
+            &time,
+            &mut entities,
+            player_id,
+            false,
+            Difficulty::Normal,
+            0.1,
+        );
+        assert_eq!(woke, Some(HibernationEvent::Disturbed { hours: 0.0 }));
+    }
+}
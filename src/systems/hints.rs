@@ -0,0 +1,326 @@
+//! Hints System Module
+//!
+//! Notices when the player has gone several days without making headway on
+//! an objective and nudges them along, a little more firmly each time: first
+//! a passing toast, then a marker on the map, then guidance in the journal
+//! spelling out what to do. The words and the marker for each objective come
+//! from `assets/hints/objective_hints.json`.
+
+use crate::components::*;
+use crate::systems::objectives::{ObjectiveStats, ObjectivesSystem};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const OBJECTIVE_HINTS: &str = include_str!("../../assets/hints/objective_hints.json");
+
+/// Days without progress before each hint level is reached
+const TOAST_AFTER_DAYS: u32 = 2;
+const MARKER_AFTER_DAYS: u32 = 3;
+const GUIDANCE_AFTER_DAYS: u32 = 4;
+
+/// Seconds a hint toast stays on screen
+const TOAST_DURATION: f32 = 8.0;
+
+/// What the map marker for an objective points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintMarker {
+    /// The nearest shelter
+    Shelter,
+    /// The nearest living clan leader
+    ClanLeader,
+    /// The nearest living animal
+    Prey,
+    /// The nearest living infected
+    Infected,
+    /// The nearest territory not yet captured
+    Territory,
+}
+
+/// Hint metadata for one objective
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObjectiveHint {
+    pub toast: String,
+    pub marker: Option<HintMarker>,
+    pub guidance: String,
+}
+
+/// How firmly the player is being nudged towards an objective
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HintLevel {
+    Toast,
+    Marker,
+    Guidance,
+}
+
+impl HintLevel {
+    /// The level reached after this many days without progress
+    fn for_days_stuck(days: u32) -> Option<Self> {
+        if days >= GUIDANCE_AFTER_DAYS {
+            Some(HintLevel::Guidance)
+        } else if days >= MARKER_AFTER_DAYS {
+            Some(HintLevel::Marker)
+        } else if days >= TOAST_AFTER_DAYS {
+            Some(HintLevel::Toast)
+        } else {
+            None
+        }
+    }
+}
+
+/// How one objective is coming along and the hints shown for it so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HintState {
+    /// Progress when last measured
+    pub progress: f32,
+    /// Day progress last moved, or the objective was first seen
+    pub since_day: u32,
+    pub level: Option<HintLevel>,
+    /// Marker shown once the hint reaches `HintLevel::Marker`
+    pub marker: Option<HintMarker>,
+    /// Guidance shown once the hint reaches `HintLevel::Guidance`
+    pub guidance: Option<String>,
+}
+
+/// Hint progress for every current objective
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HintTracker {
+    pub states: HashMap<String, HintState>,
+    /// Hint toast shown across the top of the screen and its remaining time
+    #[serde(skip)]
+    pub toast: Option<(String, f32)>,
+}
+
+impl HintTracker {
+    /// Count down the toast, clearing it when it expires
+    pub fn tick_toast(&mut self, delta_time: f32) {
+        if let Some((_, remaining)) = self.toast.as_mut() {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.toast = None;
+            }
+        }
+    }
+
+    /// Markers currently shown, one per objective
+    pub fn active_markers(&self) -> Vec<HintMarker> {
+        let mut markers: Vec<HintMarker> = self
+            .states
+            .values()
+            .filter_map(|state| state.marker)
+            .collect();
+        markers.sort_by_key(|marker| *marker as u8);
+        markers.dedup();
+        markers
+    }
+
+    /// Guidance for an objective, once the hint has escalated that far
+    pub fn guidance(&self, objective: &str) -> Option<&str> {
+        self.states.get(objective)?.guidance.as_deref()
+    }
+}
+
+/// A hint escalating for an objective
+#[derive(Debug, Clone, PartialEq)]
+pub struct HintEvent {
+    pub objective: String,
+    pub level: HintLevel,
+    pub text: String,
+}
+
+impl HintEvent {
+    pub fn get_message(&self) -> String {
+        format!("Hint: {}", self.text)
+    }
+}
+
+/// Hints system responsible for nudging stuck players
+pub struct HintSystem;
+
+impl HintSystem {
+    /// Hint metadata for every objective that has some
+    pub fn load_hints() -> HashMap<String, ObjectiveHint> {
+        serde_json::from_str(OBJECTIVE_HINTS).unwrap_or_default()
+    }
+
+    /// Measure each current objective and escalate hints for the ones that
+    /// have not moved in days. Hints reset as soon as progress is made.
+    pub fn update(
+        tracker: &mut HintTracker,
+        phase_objectives: &[String],
+        stats: &ObjectiveStats,
+        current_day: u32,
+    ) -> Vec<HintEvent> {
+        tracker
+            .states
+            .retain(|objective, _| phase_objectives.contains(objective));
+
+        let mut hints: Option<HashMap<String, ObjectiveHint>> = None;
+        let mut events = Vec::new();
+        for objective in phase_objectives {
+            let progress = ObjectivesSystem::objective_fraction(objective, stats).unwrap_or(0.0);
+            let state = tracker
+                .states
+                .entry(objective.clone())
+                .or_insert_with(|| HintState {
+                    progress,
+                    since_day: current_day,
+                    level: None,
+                    marker: None,
+                    guidance: None,
+                });
+            if progress > state.progress {
+                *state = HintState {
+                    progress,
+                    since_day: current_day,
+                    level: None,
+                    marker: None,
+                    guidance: None,
+                };
+                continue;
+            }
+
+            let level = HintLevel::for_days_stuck(current_day.saturating_sub(state.since_day));
+            if level <= state.level {
+                continue;
+            }
+            let Some(level) = level else {
+                continue;
+            };
+            state.level = Some(level);
+
+            // Only read the hint file once something escalates
+            let hints = hints.get_or_insert_with(Self::load_hints);
+            let Some(hint) = hints.get(objective) else {
+                continue;
+            };
+            if level >= HintLevel::Marker {
+                state.marker = hint.marker;
+            }
+            let text = match level {
+                HintLevel::Toast => hint.toast.clone(),
+                HintLevel::Marker if hint.marker.is_none() => continue,
+                HintLevel::Marker => format!("{} It is marked on your map.", hint.toast),
+                HintLevel::Guidance => {
+                    state.guidance = Some(hint.guidance.clone());
+                    format!("Your journal (J) has guidance for '{}'.", objective)
+                }
+            };
+            tracker.toast = Some((text.clone(), TOAST_DURATION));
+            events.push(HintEvent {
+                objective: objective.clone(),
+                level,
+                text,
+            });
+        }
+
+        events
+    }
+
+    /// Where a hint marker points, measured from the player
+    pub fn marker_position(
+        marker: HintMarker,
+        entities: &[GameEntity],
+        player_id: u32,
+        territories: &TerritoryManager,
+    ) -> Option<Position> {
+        let player_pos = EntityFinder::by_id(entities, player_id)?.position;
+        let nearest = |positions: &mut dyn Iterator<Item = Position>| {
+            positions.min_by(|a, b| {
+                a.distance_to(&player_pos)
+                    .total_cmp(&b.distance_to(&player_pos))
+            })
+        };
+        let alive = |entity: &&GameEntity| {
+            !matches!(entity.ai_state, AIState::Dead)
+                && entity.health.as_ref().is_none_or(Health::is_alive)
+        };
+
+        match marker {
+            HintMarker::Shelter => nearest(
+                &mut entities
+                    .iter()
+                    .filter(|entity| entity.shelter.is_some())
+                    .map(|entity| entity.position),
+            ),
+            HintMarker::ClanLeader => nearest(
+                &mut entities
+                    .iter()
+                    .filter(|entity| matches!(entity.entity_type, EntityType::ClanLeader(_)))
+                    .filter(alive)
+                    .map(|entity| entity.position),
+            ),
+            HintMarker::Prey => nearest(
+                &mut entities
+                    .iter()
+                    .filter(|entity| matches!(entity.entity_type, EntityType::Animal))
+                    .filter(alive)
+                    .map(|entity| entity.position),
+            ),
+            HintMarker::Infected => nearest(
+                &mut entities
+                    .iter()
+                    .filter(|entity| matches!(entity.entity_type, EntityType::HostileInfected))
+                    .filter(alive)
+                    .map(|entity| entity.position),
+            ),
+            HintMarker::Territory => nearest(
+                &mut territories
+                    .territories
+                    .iter()
+                    .filter(|territory| !territory.captured)
+                    .map(|territory| territory.center),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints_escalate_while_stuck_and_reset_on_progress() {
+        // Every phase objective has a hint
+        let hints = HintSystem::load_hints();
+        for phase in [
+            GamePhase::SurvivalAndDiscovery,
+            GamePhase::ClanEncounters,
+            GamePhase::EmpireBuilding,
+            GamePhase::WorldReaction,
+        ] {
+            for objective in ObjectivesSystem::get_initial_objectives(&phase) {
+                assert!(hints.contains_key(&objective), "{}", objective);
+            }
+        }
+
+        let objectives = vec!["Feed on blood sources".to_string()];
+        let mut tracker = HintTracker::default();
+        let mut stats = ObjectiveStats::default();
+        let update = |tracker: &mut HintTracker, stats: &ObjectiveStats, day| {
+            HintSystem::update(tracker, &objectives, stats, day)
+        };
+
+        assert!(update(&mut tracker, &stats, 1).is_empty());
+        assert!(update(&mut tracker, &stats, 2).is_empty());
+
+        // Two days stuck brings a toast, then a marker, then guidance
+        let events = update(&mut tracker, &stats, 3);
+        assert_eq!(events[0].level, HintLevel::Toast);
+        assert!(tracker.toast.is_some());
+        assert!(update(&mut tracker, &stats, 3).is_empty());
+        assert_eq!(update(&mut tracker, &stats, 4)[0].level, HintLevel::Marker);
+        assert_eq!(tracker.active_markers(), vec![HintMarker::Prey]);
+        assert_eq!(
+            update(&mut tracker, &stats, 5)[0].level,
+            HintLevel::Guidance
+        );
+        assert!(tracker.guidance("Feed on blood sources").is_some());
+
+        // Feeding clears the hints and starts the clock again
+        stats.feeding_count = 1;
+        assert!(update(&mut tracker, &stats, 5).is_empty());
+        assert!(tracker.active_markers().is_empty());
+        assert!(tracker.guidance("Feed on blood sources").is_none());
+    }
+}
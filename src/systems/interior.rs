@@ -0,0 +1,295 @@
+//! Interior System Module
+//!
+//! Lets the player step inside a building or cave. Entering one swaps the
+//! world for a small room of its own, with the door the player came in by,
+//! a bed and a stash. Inside, the player walks the room instead of the
+//! world and is out of reach of sunlight and of everything outside, while
+//! the world goes on without them. Leaving by the door puts the player back
+//! on the doorstep.
+
+use crate::components::*;
+
+/// Width and height of a room, in room units
+pub const ROOM_SIZE: (f32, f32) = (480.0, 300.0);
+
+/// Walking speed inside, in room units per second
+const WALK_SPEED: f32 = 140.0;
+
+/// How close the player must stand to a fixture to use it
+const REACH: f32 = 45.0;
+
+/// Closest the player can come to a wall
+const WALL_MARGIN: f32 = 20.0;
+
+/// Something in the room the player can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fixture {
+    Door,
+    Bed,
+    Stash,
+}
+
+impl Fixture {
+    pub const ALL: [Fixture; 3] = [Fixture::Door, Fixture::Bed, Fixture::Stash];
+
+    /// Where the fixture stands in the room
+    pub fn position(self) -> Position {
+        match self {
+            Fixture::Door => Position::new(ROOM_SIZE.0 / 2.0, ROOM_SIZE.1 - WALL_MARGIN),
+            Fixture::Bed => Position::new(80.0, 70.0),
+            Fixture::Stash => Position::new(ROOM_SIZE.0 - 80.0, 70.0),
+        }
+    }
+
+    /// String table key for what using it does, shown when the player is
+    /// in reach
+    pub fn prompt_key(self) -> &'static str {
+        match self {
+            Fixture::Door => "interior.door",
+            Fixture::Bed => "interior.bed",
+            Fixture::Stash => "interior.stash",
+        }
+    }
+}
+
+/// The room the player is standing in
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteriorScene {
+    pub shelter_id: u32,
+    pub shelter_type: ShelterType,
+    /// Where the player stands, in room units
+    pub player: Position,
+    pub facing_left: bool,
+}
+
+/// What happened when the player used something inside
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteriorEvent {
+    Entered { name: String },
+    Stashed { count: u32 },
+    Retrieved { count: u32 },
+    StashEmpty,
+    CannotSleepAtNight,
+}
+
+impl InteriorEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            InteriorEvent::Entered { name } => {
+                format!(
+                    "You step inside the {}. The door shuts out the world.",
+                    name
+                )
+            }
+            InteriorEvent::Stashed { count } => format!("You stash {} item(s) here.", count),
+            InteriorEvent::Retrieved { count } => {
+                format!("You take {} item(s) from the stash.", count)
+            }
+            InteriorEvent::StashEmpty => "The stash is empty.".to_string(),
+            InteriorEvent::CannotSleepAtNight => {
+                "The night is young; you are not yet weary.".to_string()
+            }
+        }
+    }
+}
+
+/// Interior system responsible for the rooms inside shelters
+pub struct InteriorSystem;
+
+impl InteriorSystem {
+    /// Whether a shelter of this type has a room to step into
+    pub fn has_interior(shelter_type: &ShelterType) -> bool {
+        matches!(shelter_type, ShelterType::Building | ShelterType::Cave)
+    }
+
+    /// Step into the room of the shelter the player has just entered, if it
+    /// has one, just inside its door
+    pub fn enter(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        shelter_id: u32,
+    ) -> Option<(InteriorScene, InteriorEvent)> {
+        let shelter = EntityFinder::by_id(entities, shelter_id)?
+            .shelter
+            .as_ref()?;
+        if !Self::has_interior(&shelter.shelter_type) {
+            return None;
+        }
+        let scene = InteriorScene {
+            shelter_id,
+            shelter_type: shelter.shelter_type.clone(),
+            player: Position::new(
+                Fixture::Door.position().x,
+                Fixture::Door.position().y - REACH,
+            ),
+            facing_left: false,
+        };
+        let event = InteriorEvent::Entered {
+            name: shelter.display_name().to_lowercase(),
+        };
+
+        let occupancy = entities
+            .iter_mut()
+            .find(|entity| entity.id == player_id)?
+            .shelter_occupancy
+            .as_mut()?;
+        occupancy.indoors = true;
+        Some((scene, event))
+    }
+
+    /// Walk the player along `direction` (the movement input), kept off
+    /// the walls
+    pub fn walk(scene: &mut InteriorScene, direction: (f32, f32), delta_time: f32) {
+        let (dx, dy) = direction;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length < 0.01 {
+            return;
+        }
+        scene.player.x = (scene.player.x + dx / length * WALK_SPEED * delta_time)
+            .clamp(WALL_MARGIN, ROOM_SIZE.0 - WALL_MARGIN);
+        scene.player.y = (scene.player.y + dy / length * WALK_SPEED * delta_time)
+            .clamp(WALL_MARGIN, ROOM_SIZE.1 - WALL_MARGIN);
+        if dx.abs() > 0.01 {
+            scene.facing_left = dx < 0.0;
+        }
+    }
+
+    /// The nearest fixture within reach of the player
+    pub fn fixture_in_reach(scene: &InteriorScene) -> Option<Fixture> {
+        Fixture::ALL
+            .into_iter()
+            .map(|fixture| (fixture, fixture.position().distance_to(&scene.player)))
+            .filter(|&(_, distance)| distance <= REACH)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(fixture, _)| fixture)
+    }
+
+    /// Put everything the player carries into the shelter's stash or, with
+    /// empty pockets, take back as much of it as they can carry
+    pub fn use_stash(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        shelter_id: u32,
+    ) -> Option<InteriorEvent> {
+        let [player, shelter] = entities
+            .get_disjoint_mut([
+                entities.iter().position(|entity| entity.id == player_id)?,
+                entities.iter().position(|entity| entity.id == shelter_id)?,
+            ])
+            .ok()?;
+        let inventory = player.inventory.as_mut()?;
+        let stash = &mut shelter.shelter.as_mut()?.stash;
+
+        if inventory.total() > 0 {
+            let count = inventory.total();
+            for (item, quantity) in inventory.items.drain() {
+                *stash.entry(item).or_insert(0) += quantity;
+            }
+            return Some(InteriorEvent::Stashed { count });
+        }
+
+        let mut count = 0;
+        for item in ItemKind::ALL {
+            while stash.get(&item).is_some_and(|&left| left > 0) && inventory.add_item(item, 1) {
+                count += 1;
+                let left = stash.entry(item).or_insert(0);
+                *left -= 1;
+                if *left == 0 {
+                    stash.remove(&item);
+                }
+            }
+        }
+        Some(if count > 0 {
+            InteriorEvent::Retrieved { count }
+        } else {
+            InteriorEvent::StashEmpty
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ShelterSystem, SpatialGrid, WorldSystem};
+
+    #[test]
+    fn test_buildings_have_rooms_that_shut_out_the_sun_and_keep_a_stash() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let position = entities[0].position;
+        let building = ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            position.x + 20.0,
+            position.y,
+            None,
+            None,
+        );
+        let mut grid = SpatialGrid::default();
+        grid.rebuild(&entities);
+        ShelterSystem::handle_player_shelter_interaction(
+            &mut entities,
+            &grid,
+            player_id,
+            0.0,
+            false,
+        );
+
+        // Inside, the sun does no harm at all
+        let (mut scene, _) = InteriorSystem::enter(&mut entities, player_id, building).unwrap();
+        assert_eq!(
+            ShelterSystem::calculate_shelter_protection(&entities, player_id, 10.0),
+            0.0
+        );
+
+        // The player walks up to the stash and leaves everything there
+        assert_eq!(
+            InteriorSystem::fixture_in_reach(&scene),
+            Some(Fixture::Door)
+        );
+        let stash = Fixture::Stash.position();
+        for _ in 0..100 {
+            let direction = (stash.x - scene.player.x, stash.y - scene.player.y);
+            InteriorSystem::walk(&mut scene, direction, 0.05);
+        }
+        assert_eq!(
+            InteriorSystem::fixture_in_reach(&scene),
+            Some(Fixture::Stash)
+        );
+        let inventory = entities[0].inventory.as_mut().unwrap();
+        inventory.add_item(ItemKind::Bandage, 2);
+        assert_eq!(
+            InteriorSystem::use_stash(&mut entities, player_id, building),
+            Some(InteriorEvent::Stashed { count: 2 })
+        );
+        assert_eq!(entities[0].inventory.as_ref().unwrap().total(), 0);
+        assert_eq!(
+            InteriorSystem::use_stash(&mut entities, player_id, building),
+            Some(InteriorEvent::Retrieved { count: 2 })
+        );
+
+        // Leaving puts the player back out in the light
+        ShelterSystem::handle_player_shelter_interaction(
+            &mut entities,
+            &grid,
+            player_id,
+            0.0,
+            false,
+        );
+        assert!(!entities[0].shelter_occupancy.as_ref().unwrap().indoors);
+
+        // A tent has no room to step into
+        let tent = ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Tent,
+            0.0,
+            700.0,
+            None,
+            None,
+        );
+        assert!(InteriorSystem::enter(&mut entities, player_id, tent).is_none());
+    }
+}
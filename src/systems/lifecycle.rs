@@ -0,0 +1,261 @@
+//! Lifecycle System Module
+//!
+//! Clears the dead out of the world. A body lies where it fell for an
+//! in-game hour, to be fed on or stumbled over, fading before it goes; a
+//! vampire gone to mist leaves as soon as its death has played out. The
+//! slain sometimes drop what they carried beside the body. Despawned
+//! entities hand their IDs back to the allocator, and any shelter, hunter
+//! or follower still holding one lets go of it, since the slot will soon
+//! belong to someone else.
+
+use crate::components::*;
+use crate::systems::WorldSystem;
+use std::collections::{HashMap, HashSet};
+
+/// In-game hours a body lies in the world before it is gone
+pub const BODY_DECAY_HOURS: f32 = 1.0;
+/// Share of a body's time at the end over which it fades away
+const BODY_FADE: f32 = 0.25;
+/// How far from the body a dropped item lands
+const LOOT_OFFSET: f32 = 14.0;
+
+/// How long each dead entity has been dead
+#[derive(Debug, Clone, Default)]
+pub struct Decay {
+    dead_for: HashMap<u32, f32>,
+}
+
+impl Decay {
+    /// Opacity of a body that lasts `body_lifetime` seconds, fading out at
+    /// the end of its time
+    pub fn fade(&self, entity_id: u32, body_lifetime: f32) -> f32 {
+        let dead_for = self.dead_for.get(&entity_id).copied().unwrap_or(0.0);
+        ((body_lifetime - dead_for) / (body_lifetime * BODY_FADE)).clamp(0.0, 1.0)
+    }
+}
+
+/// An item left beside a slain creature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LootDrop {
+    pub pickup_id: u32,
+    pub item: ItemKind,
+    pub quantity: u32,
+}
+
+impl LootDrop {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        if self.quantity > 1 {
+            format!(
+                "{} x{} fall from the body.",
+                self.item.display_name(),
+                self.quantity
+            )
+        } else {
+            format!("A {} falls from the body.", self.item.display_name())
+        }
+    }
+}
+
+/// Lifecycle system responsible for despawning the dead
+pub struct LifecycleSystem;
+
+impl LifecycleSystem {
+    /// Age the dead and despawn those whose time is up, bodies after
+    /// `body_lifetime` seconds. Entities removed elsewhere, eaten or gone
+    /// home, have their IDs released here too. Returns the IDs released
+    /// this frame.
+    pub fn update(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        decay: &mut Decay,
+        player_id: u32,
+        body_lifetime: f32,
+        delta_time: f32,
+    ) -> Vec<u32> {
+        for entity in entities.iter() {
+            if entity.id != player_id && Self::is_dead(entity) {
+                *decay.dead_for.entry(entity.id).or_default() += delta_time;
+            }
+        }
+        entities.retain(|entity| {
+            decay
+                .dead_for
+                .get(&entity.id)
+                .is_none_or(|dead_for| *dead_for < Self::decay_time(entity, body_lifetime))
+        });
+
+        let present: HashSet<u32> = entities.iter().map(|entity| entity.id).collect();
+        decay.dead_for.retain(|id, _| present.contains(id));
+        let released = entity_ids.release_missing(&present);
+        if !released.is_empty() {
+            Self::release_references(entities, entity_ids);
+        }
+        released
+    }
+
+    fn is_dead(entity: &GameEntity) -> bool {
+        matches!(entity.ai_state, AIState::Dead)
+            || entity
+                .health
+                .as_ref()
+                .is_some_and(|health| !health.is_alive())
+    }
+
+    /// Seconds an entity stays in the world once dead
+    fn decay_time(entity: &GameEntity, body_lifetime: f32) -> f32 {
+        if entity.corpse.is_some() || DeathStyle::for_entity(entity).leaves_body() {
+            body_lifetime.max(DeathAnimation::DURATION)
+        } else {
+            DeathAnimation::DURATION
+        }
+    }
+
+    /// Leave whatever a slain creature carried on the ground beside it,
+    /// for a roll in `0.0..1.0`
+    pub fn drop_loot(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        victim_id: u32,
+        roll: f32,
+    ) -> Option<LootDrop> {
+        let victim = EntityFinder::by_id(entities, victim_id)?;
+        let (item, quantity) = ItemKind::loot(&victim.entity_type, roll)?;
+        let position = victim.position;
+        let pickup_id = WorldSystem::spawn_pickup(
+            entities,
+            entity_ids,
+            item,
+            quantity,
+            position.x + LOOT_OFFSET,
+            position.y,
+        );
+        Some(LootDrop {
+            pickup_id,
+            item,
+            quantity,
+        })
+    }
+
+    /// Drop every reference to an ID that is no longer live: shelter
+    /// occupants, homes, hunted prey and followers' attack targets
+    pub fn release_references(entities: &mut [GameEntity], entity_ids: &EntityAllocator) {
+        let live = |id: &u32| entity_ids.is_live(*id);
+        for entity in entities.iter_mut() {
+            if let Some(shelter) = &mut entity.shelter {
+                shelter.occupants.retain(live);
+                shelter.occupied = !shelter.occupants.is_empty();
+            }
+            if let Some(occupancy) = &mut entity.shelter_occupancy {
+                occupancy.shelter_id = occupancy.shelter_id.filter(live);
+            }
+            if let Some(schedule) = &mut entity.schedule {
+                schedule.home_shelter = schedule.home_shelter.filter(live);
+            }
+            if let Some(hunger) = &mut entity.hunger {
+                hunger.prey = hunger.prey.filter(live);
+            }
+            if let AIState::Follower(FollowerOrder::Attack(target)) = entity.ai_state {
+                if !live(&target) {
+                    entity.ai_state = AIState::Follower(FollowerOrder::Follow);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ShelterSystem, TimeSystem};
+
+    #[test]
+    fn test_dead_decay_and_their_ids_are_reused_safely() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let cave = ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Cave,
+            400.0,
+            800.0,
+            None,
+            None,
+        );
+        let infected = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 420.0, 800.0);
+        let deer = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Deer, 500.0, 800.0);
+        let hunter = entities.iter_mut().find(|e| e.id == infected).unwrap();
+        hunter.hunger.as_mut().unwrap().prey = Some(deer);
+        let shelter = entities.iter_mut().find(|e| e.id == cave).unwrap();
+        shelter.shelter.as_mut().unwrap().add_occupant(deer);
+
+        // The deer dies; its body lies a while before it goes
+        let body = entities.iter_mut().find(|e| e.id == deer).unwrap();
+        body.health.as_mut().unwrap().current = 0.0;
+        body.ai_state = AIState::Dead;
+        let lifetime = TimeSystem::new().seconds_in(BODY_DECAY_HOURS);
+        let mut decay = Decay::default();
+        let released = LifecycleSystem::update(
+            &mut entities,
+            &mut ids,
+            &mut decay,
+            player_id,
+            lifetime,
+            1.0,
+        );
+        assert!(released.is_empty());
+        assert_eq!(decay.fade(deer, lifetime), 1.0);
+        let released = LifecycleSystem::update(
+            &mut entities,
+            &mut ids,
+            &mut decay,
+            player_id,
+            lifetime,
+            lifetime,
+        );
+        assert_eq!(released, vec![deer]);
+        assert!(EntityFinder::by_id(&entities, deer).is_none());
+
+        // Nothing still points at it
+        let hunter = EntityFinder::by_id(&entities, infected).unwrap();
+        assert_eq!(hunter.hunger.as_ref().unwrap().prey, None);
+        let shelter = EntityFinder::by_id(&entities, cave).unwrap();
+        assert!(shelter.shelter.as_ref().unwrap().occupants.is_empty());
+
+        // Its slot goes to the next creature under a new generation
+        let fawn = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Deer, 520.0, 800.0);
+        assert_eq!(id_slot(fawn), id_slot(deer));
+        assert_ne!(fawn, deer);
+        assert!(ids.is_live(fawn) && !ids.is_live(deer));
+
+        // A restored game picks up the same slots and free list
+        let restored = EntityAllocator::from_ids(entities.iter().map(|e| e.id), ids.next_slot());
+        assert_eq!(restored.len(), ids.len());
+        assert!(restored.is_live(fawn));
+    }
+
+    #[test]
+    fn test_the_slain_sometimes_drop_loot() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let infected = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 300.0, 800.0);
+        let deer = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Deer, 500.0, 800.0);
+
+        // Animals carry nothing, and most infected nothing worth taking
+        assert_eq!(
+            LifecycleSystem::drop_loot(&mut entities, &mut ids, deer, 0.0),
+            None
+        );
+        assert_eq!(
+            LifecycleSystem::drop_loot(&mut entities, &mut ids, infected, 0.9),
+            None
+        );
+
+        let drop = LifecycleSystem::drop_loot(&mut entities, &mut ids, infected, 0.1).unwrap();
+        assert_eq!(drop.item, ItemKind::Bandage);
+        let pickup = EntityFinder::by_id(&entities, drop.pickup_id).unwrap();
+        assert_eq!(pickup.pickup, Some(Pickup::new(ItemKind::Bandage, 1)));
+        assert!(pickup.position.distance_to(&Position::new(300.0, 800.0)) <= LOOT_OFFSET);
+    }
+}
@@ -4,38 +4,170 @@
 //! Each system is responsible for a focused area of game logic and operates on
 //! game state data in a functional manner.
 
+pub mod abilities;
 pub mod ai;
+pub mod assault;
+pub mod barks;
+pub mod bestiary;
 pub mod blood;
+pub mod calendar;
+pub mod camera;
+pub mod collision;
+pub mod combat;
+pub mod construction;
+pub mod crime;
+pub mod dialogue;
+pub mod dream;
+pub mod effects;
+pub mod events;
+pub mod exposure;
+pub mod hibernation;
+pub mod hints;
+pub mod interior;
+pub mod lifecycle;
+pub mod noise;
 pub mod objectives;
+pub mod onboarding;
+pub mod particles;
+pub mod pathfinding;
+pub mod pause;
 pub mod player;
+pub mod population;
+pub mod progression;
+pub mod projectiles;
+pub mod query;
+pub mod quests;
+pub mod rebellion;
+pub mod schedule;
+pub mod scout;
 pub mod shelter;
+pub mod skirmish;
+pub mod spatial;
+pub mod status;
+pub mod stealth;
+pub mod territory;
 pub mod time;
+pub mod trails;
+pub mod travel;
+pub mod tribute;
+pub mod warfare;
+pub mod waves;
+pub mod weakness;
+pub mod weather;
 pub mod world;
 
 // Re-export systems for easier access
+pub use abilities::AbilitySystem;
 pub use ai::AISystem;
+pub use assault::AssaultSystem;
+pub use barks::BarkSystem;
+pub use bestiary::BestiarySystem;
 pub use blood::BloodSystem;
+pub use calendar::CalendarSystem;
+pub use camera::CameraSystem;
+pub use collision::CollisionSystem;
+pub use combat::CombatSystem;
+pub use construction::ConstructionSystem;
+pub use crime::CrimeSystem;
+pub use dialogue::DialogueSystem;
+pub use dream::DreamSystem;
+pub use effects::EffectsSystem;
+pub use exposure::ExposureSystem;
+pub use hibernation::HibernationSystem;
+pub use hints::HintSystem;
+pub use interior::InteriorSystem;
+pub use lifecycle::LifecycleSystem;
+pub use noise::NoiseSystem;
 pub use objectives::ObjectivesSystem;
+pub use onboarding::OnboardingSystem;
+pub use particles::ParticleSystem;
+pub use pathfinding::PathfindingSystem;
+pub use pause::PauseSystem;
 pub use player::PlayerSystem;
+pub use population::PopulationSystem;
+pub use progression::ProgressionSystem;
+pub use projectiles::ProjectileSystem;
+pub use query::WorldQuery;
+pub use quests::QuestSystem;
+pub use rebellion::RebellionSystem;
+pub use schedule::ScheduleSystem;
+pub use scout::ScoutSystem;
 pub use shelter::ShelterSystem;
+pub use skirmish::SkirmishSystem;
+pub use spatial::SpatialGrid;
+pub use status::StatusSystem;
+pub use stealth::StealthSystem;
+pub use territory::TerritorySystem;
 pub use time::TimeSystem;
+pub use trails::TrailSystem;
+pub use travel::TravelSystem;
+pub use tribute::TributeSystem;
+pub use warfare::WarfareSystem;
+pub use waves::WaveSystem;
+pub use weakness::WeaknessSystem;
+pub use weather::WeatherSystem;
 pub use world::WorldSystem;
 
 // Re-export common types used by systems
-pub use blood::{ActivityLevel, BloodStatus, SurvivalScore};
-pub use objectives::ObjectiveProgress;
-pub use player::{ExperienceType, PlayerAction, PlayerStatus};
-pub use shelter::ShelterInfo;
+pub use abilities::{AbilityEffect, AbilityResult, AbilityTooltip, ScentTrail, SpectralTarget};
+pub use assault::{
+    Assault, AssaultEvent, AssaultOutcome, AssaultPhase, AssaultTarget, AssaultTracker,
+};
+pub use barks::{Bark, BarkKind, Barks, Regard};
+pub use bestiary::{Bestiary, BestiaryEvent, CreatureKnowledge, CreatureLore, CreatureStats};
+pub use blood::{ActivityLevel, BloodStatus, Drain, SurvivalScore};
+pub use calendar::{Calendar, CalendarEvent, NightEvent, BLOOD_MOON_INTERVAL};
+pub use camera::{CameraRig, PhotoMode};
+pub use combat::{DamageEvent, HitReaction};
+pub use construction::{Blueprint, BuildError, BuildOrder, ConstructionEvent};
+pub use dialogue::{ActiveDialogue, DialogueEffect, DialogueStep, DialogueTree};
+pub use dream::{ActiveVision, CodexEntry, LoreCodex, VisionOutcome};
+pub use effects::{DamageNumber, DamageNumberKind, HitEffects, Recoil};
+pub use events::{EventBus, GameEvent};
+pub use exposure::Shadow;
+pub use hibernation::{Hibernation, HibernationEvent};
+pub use hints::{HintEvent, HintLevel, HintMarker, HintTracker, ObjectiveHint};
+pub use interior::{Fixture, InteriorEvent, InteriorScene};
+pub use lifecycle::{Decay, LootDrop, BODY_DECAY_HOURS};
+pub use noise::NoiseProfile;
+pub use objectives::{ObjectiveProgress, ObjectiveStats};
+pub use onboarding::{FirstNight, GuidedStep, OnboardingEvent, StartMode};
+pub use particles::{Particle, ParticleKind, ParticlePool};
+pub use pathfinding::{Cell, NavGrid, Route};
+pub use pause::{Cutscene, SystemPause};
+pub use player::{
+    EntityInspection, ExperienceType, FollowerCommand, ItemEvent, PlayerAction, PlayerStatus,
+    RecruitResult,
+};
+pub use population::{ClanRoster, PopulationEvent};
+pub use progression::SkillEvent;
+pub use query::{AreaQuery, QueryShape, RaycastHit, WorldRect, WorldTarget};
+pub use quests::{Quest, QuestEvent, QuestGoal, QuestLog, QuestReward};
+pub use rebellion::RebellionEvent;
+pub use scout::{CrowScout, ScoutEvent, ScoutMark};
+pub use shelter::{ShelterInfo, ShelterWorkEvent};
+pub use skirmish::{Skirmish, SkirmishEvent, SkirmishTracker};
+pub use stealth::{SightBlocker, StealthProfile};
+pub use territory::TerritoryEvent;
+pub use time::TimeTransition;
+pub use trails::{BloodDrop, BloodTrails};
+pub use travel::{AutoWalk, Waypoint};
+pub use tribute::TributeEvent;
+pub use warfare::{ClanClash, ClanRelations, WarfareEvent};
+pub use waves::{SpawnDirector, WaveEvent};
+pub use weakness::WeaknessEvent;
+pub use weather::WeatherChange;
 
 /// System update order for consistent game logic
 pub enum SystemUpdateOrder {
     Input = 0,
     Player = 1,
     AI = 2,
-    Shelter = 3,
-    Blood = 4,
-    Time = 5,
-    Objectives = 6,
+    Combat = 3,
+    Shelter = 4,
+    Blood = 5,
+    Time = 6,
+    Objectives = 7,
 }
 
 /// Trait for systems that need regular updates
@@ -0,0 +1,140 @@
+//! Noise System Module
+//!
+//! Works out how far the player's footsteps carry. Soft grass and rain mask
+//! noise, while bare stone and the silence of a still, clear night carry it
+//! further. The result scales how far hostile AI can hear the player, and is
+//! shown on the HUD so the player can choose how to approach.
+
+use crate::components::*;
+use crate::systems::WorldSystem;
+
+/// How much the ground underfoot carries noise
+fn surface_factor(tile_type: TileType) -> f32 {
+    match tile_type {
+        TileType::Grass => 0.8,
+        TileType::DeadGrass => 1.0,
+        TileType::Dirt => 1.0,
+        TileType::Stone => 1.25,
+        TileType::Water => 1.4,
+    }
+}
+
+/// How much the weather carries noise; rain drowns out footsteps and fog
+/// dulls them a little
+fn weather_factor(weather: Weather) -> f32 {
+    match weather {
+        Weather::Clear | Weather::Overcast => 1.0,
+        Weather::Rain => 0.65,
+        Weather::Fog => 0.9,
+    }
+}
+
+/// A clear night is quiet enough for every sound to carry
+const NIGHT_SILENCE_FACTOR: f32 = 1.15;
+
+/// What shapes the noise the player makes where they stand
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseProfile {
+    /// Ground underfoot, if the player is on the ground
+    pub surface: Option<TileType>,
+    pub weather: Weather,
+    /// Clear and still night with nothing to cover sound
+    pub silence: bool,
+    /// Scale applied to AI hearing ranges
+    pub multiplier: f32,
+}
+
+impl NoiseProfile {
+    pub fn label(&self) -> &'static str {
+        if self.multiplier < 0.75 {
+            "Muffled"
+        } else if self.multiplier <= 1.05 {
+            "Normal"
+        } else {
+            "Carrying"
+        }
+    }
+
+    /// Short list of what is muffling or carrying the player's noise
+    pub fn describe(&self) -> String {
+        let mut causes = Vec::new();
+        if self.weather != Weather::Clear {
+            causes.push(self.weather.display_name());
+        }
+        if self.silence {
+            causes.push("Silent night");
+        }
+        causes.push(match self.surface {
+            Some(TileType::Grass) => "Soft grass",
+            Some(TileType::DeadGrass) => "Dry grass",
+            Some(TileType::Dirt) => "Dirt",
+            Some(TileType::Stone) => "Stone",
+            Some(TileType::Water) => "Splashing",
+            None => "Open air",
+        });
+        causes.join(", ")
+    }
+}
+
+/// Noise system responsible for how far the player can be heard
+pub struct NoiseSystem;
+
+impl NoiseSystem {
+    /// The noise profile at a position for the current weather and time, on
+    /// the ground of the world laid out from `seed`
+    pub fn profile(
+        position: &Position,
+        seed: u64,
+        weather: Weather,
+        is_night: bool,
+    ) -> NoiseProfile {
+        let surface = WorldSystem::tile_type_at(position, seed);
+        let silence = is_night && weather == Weather::Clear;
+
+        let mut multiplier = surface.map_or(1.0, surface_factor) * weather_factor(weather);
+        if silence {
+            multiplier *= NIGHT_SILENCE_FACTOR;
+        }
+
+        NoiseProfile {
+            surface,
+            weather,
+            silence,
+            multiplier,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rain_muffles_and_silence_carries() {
+        // Find a grass tile and a stone tile on the ground
+        let find = |wanted: TileType| {
+            (0..200)
+                .map(|i| {
+                    Position::new(
+                        32.0 + 64.0 * (i % 25) as f32,
+                        672.0 + 64.0 * (i / 25) as f32,
+                    )
+                })
+                .find(|position| WorldSystem::tile_type_at(position, 0) == Some(wanted))
+                .unwrap()
+        };
+        let grass = find(TileType::Grass);
+        let stone = find(TileType::Stone);
+
+        let rainy_grass = NoiseSystem::profile(&grass, 0, Weather::Rain, true);
+        let silent_stone = NoiseSystem::profile(&stone, 0, Weather::Clear, true);
+        assert!(rainy_grass.multiplier < 0.6);
+        assert_eq!(rainy_grass.label(), "Muffled");
+        assert!(silent_stone.multiplier > 1.4);
+        assert_eq!(silent_stone.label(), "Carrying");
+        assert_eq!(silent_stone.describe(), "Silent night, Stone");
+
+        // By day a clear sky is no longer silent
+        assert!(!NoiseSystem::profile(&stone, 0, Weather::Clear, false).silence);
+    }
+}
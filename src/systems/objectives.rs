@@ -4,6 +4,7 @@
 //! This system manages the player's progression through different game phases.
 
 use crate::components::*;
+use crate::systems::events::GameEvent;
 use std::collections::HashMap;
 
 /// Objectives system responsible for tracking game progress and goals
@@ -54,6 +55,26 @@ impl ObjectivesSystem {
         );
     }
 
+    /// Count the player's kills and feedings among the frame's events
+    pub fn record_events(
+        events: &[GameEvent],
+        player_id: u32,
+        kills: &mut u32,
+        feeding_count: &mut u32,
+    ) {
+        for event in events {
+            match event {
+                GameEvent::EntityDied { killer_id, .. } if *killer_id == player_id => {
+                    *kills += 1;
+                }
+                GameEvent::FedOnTarget { feeder_id, .. } if *feeder_id == player_id => {
+                    *feeding_count += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Check survival-related objectives
     fn check_survival_objectives(
         time_system: &super::time::TimeSystem,
@@ -221,6 +242,13 @@ impl ObjectivesSystem {
             }
         }
 
+        if entities
+            .iter()
+            .any(|entity| entity.shelter.as_ref().is_some_and(Shelter::is_home))
+        {
+            Self::complete_objective("Build a lair", phase_objectives, completed_objectives);
+        }
+
         // Advanced shelter objectives
         if time_system.day_count() >= 7 {
             if let Some(player) = entities.iter().find(|e| e.id == player_id) {
@@ -394,6 +422,7 @@ impl ObjectivesSystem {
                 "Master daytime survival".to_string(),
                 "Achieve supernatural speed".to_string(),
                 "Map the entire realm".to_string(),
+                "Build a lair".to_string(),
             ],
             GamePhase::WorldReaction => vec![
                 "Conquer all vampire clans".to_string(),
@@ -442,6 +471,31 @@ impl ObjectivesSystem {
         }
     }
 
+    /// How far the player has come towards a counted objective (0.0 to 1.0).
+    /// Objectives that are simply done or not, like finding shelter, have
+    /// no measure.
+    pub fn objective_fraction(objective: &str, stats: &ObjectiveStats) -> Option<f32> {
+        let ratio = |value: usize, target: usize| (value as f32 / target.max(1) as f32).min(1.0);
+        let fraction = match objective {
+            "Survive your first week" => ratio(stats.day_count as usize, 7),
+            "Survive for a month" => ratio(stats.day_count as usize, 30),
+            "Survive for a year" => ratio(stats.day_count as usize, 365),
+            "Feed on blood sources" => ratio(stats.feeding_count as usize, 5),
+            "Master the art of feeding" => ratio(stats.feeding_count as usize, 25),
+            "Become an apex predator" => ratio(stats.feeding_count as usize, 100),
+            "Prove your combat prowess" => ratio(stats.kills as usize, 10),
+            "Become a feared warrior" => ratio(stats.kills as usize, 50),
+            "Earn the title of Apex Hunter" => ratio(stats.kills as usize, 200),
+            "Establish contact with clan leaders" => ratio(stats.allied_clans, 1),
+            "Form alliances with multiple clans" => ratio(stats.allied_clans, 2),
+            "Unite all vampire clans" => ratio(stats.allied_clans, stats.total_clans),
+            "Defeat a rival clan" => ratio(stats.defeated_clans, 1),
+            "Conquer all vampire clans" => ratio(stats.defeated_clans, stats.total_clans),
+            _ => return None,
+        };
+        Some(fraction)
+    }
+
     /// Get objectives progress summary
     pub fn get_progress_summary(
         completed_objectives: &[String],
@@ -476,6 +530,17 @@ pub struct ObjectiveProgress {
     pub recent_completions: Vec<String>,
 }
 
+/// The counts that counted objectives are measured against
+#[derive(Debug, Clone, Default)]
+pub struct ObjectiveStats {
+    pub day_count: u32,
+    pub kills: u32,
+    pub feeding_count: u32,
+    pub allied_clans: usize,
+    pub defeated_clans: usize,
+    pub total_clans: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -543,4 +608,21 @@ mod tests {
         assert_eq!(progress.remaining_count, 2);
         assert_eq!(progress.completion_percentage, 50.0);
     }
+
+    #[test]
+    fn test_objective_fraction() {
+        let stats = ObjectiveStats {
+            day_count: 3,
+            kills: 25,
+            total_clans: 3,
+            ..ObjectiveStats::default()
+        };
+        let fraction = |objective| ObjectivesSystem::objective_fraction(objective, &stats);
+
+        assert!((fraction("Survive your first week").unwrap() - 3.0 / 7.0).abs() < 1e-6);
+        assert_eq!(fraction("Prove your combat prowess"), Some(1.0));
+        assert_eq!(fraction("Become a feared warrior"), Some(0.5));
+        assert_eq!(fraction("Unite all vampire clans"), Some(0.0));
+        assert_eq!(fraction("Find shelter from sunlight"), None);
+    }
 }
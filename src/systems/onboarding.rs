@@ -0,0 +1,389 @@
+//! Onboarding System Module
+//!
+//! Scripts the guided first night offered at new game: a short chain of
+//! objectives with a world marker (find the cave, feed on a rabbit, meet
+//! Grimjaw), fewer infected around the starting area, and a series of
+//! warnings as dawn approaches. The classic start skips all of it.
+
+use crate::components::*;
+use crate::systems::shelter::ShelterSystem;
+use crate::systems::time::TimeSystem;
+use serde::{Deserialize, Serialize};
+
+/// Infected left alive in the world for a guided start
+const GUIDED_MAX_INFECTED: usize = 3;
+
+/// Where the guided start places its cave if the world has none
+const GUIDED_CAVE_POSITION: (f32, f32) = (650.0, 780.0);
+
+/// Hours before dawn at which the warnings fire, in order
+const DAWN_WARNINGS: [f32; 3] = [3.0, 1.0, 0.5];
+
+/// Seconds a banner message stays on screen
+const BANNER_DURATION: f32 = 6.0;
+
+/// How the player chose to begin a new game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StartMode {
+    /// Scripted first night with objectives and markers
+    Guided,
+    /// Straight into the open world
+    #[default]
+    Classic,
+}
+
+impl StartMode {
+    /// Key of the start's name in the translation tables
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            StartMode::Guided => "new_game.guided",
+            StartMode::Classic => "new_game.classic",
+        }
+    }
+
+    /// Key of the start's description in the translation tables
+    pub fn description_key(&self) -> &'static str {
+        match self {
+            StartMode::Guided => "new_game.guided_description",
+            StartMode::Classic => "new_game.classic_description",
+        }
+    }
+
+    /// The other mode, for toggling on the selection screen
+    pub fn toggled(&self) -> Self {
+        match self {
+            StartMode::Guided => StartMode::Classic,
+            StartMode::Classic => StartMode::Guided,
+        }
+    }
+}
+
+/// Steps of the guided first night, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuidedStep {
+    FindCave,
+    FeedOnRabbit,
+    MeetGrimjaw,
+    /// All objectives done; only the dawn warnings remain
+    SurviveUntilDawn,
+}
+
+impl GuidedStep {
+    pub fn objective(&self) -> &'static str {
+        match self {
+            GuidedStep::FindCave => "Find the cave to the east - it will shelter you from the sun",
+            GuidedStep::FeedOnRabbit => "Feed on a rabbit (R) to ease your hunger",
+            GuidedStep::MeetGrimjaw => "Meet Grimjaw, leader of the Bone-Eaters (E)",
+            GuidedStep::SurviveUntilDawn => "Return to the cave before the sun rises",
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            GuidedStep::FindCave => GuidedStep::FeedOnRabbit,
+            GuidedStep::FeedOnRabbit => GuidedStep::MeetGrimjaw,
+            GuidedStep::MeetGrimjaw | GuidedStep::SurviveUntilDawn => GuidedStep::SurviveUntilDawn,
+        }
+    }
+}
+
+/// Progress through the guided first night
+#[derive(Debug, Clone)]
+pub struct FirstNight {
+    pub step: GuidedStep,
+    /// The cave the first objective leads to
+    pub cave_id: u32,
+    /// Feeding count when the feeding step began
+    feeding_baseline: u32,
+    /// How many of `DAWN_WARNINGS` have been shown
+    warnings_given: usize,
+    /// Message shown across the top of the screen and its remaining time
+    pub banner: Option<(String, f32)>,
+}
+
+impl FirstNight {
+    fn show_banner(&mut self, message: String) {
+        self.banner = Some((message, BANNER_DURATION));
+    }
+
+    /// Count down the banner message, clearing it when it expires
+    pub fn tick_banner(&mut self, delta_time: f32) {
+        if let Some((_, remaining)) = self.banner.as_mut() {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.banner = None;
+            }
+        }
+    }
+}
+
+/// Something that happened during the guided first night
+#[derive(Debug, Clone, PartialEq)]
+pub enum OnboardingEvent {
+    StepCompleted(GuidedStep),
+    DawnWarning {
+        hours_left: f32,
+    },
+    /// Dawn broke; the guided start is over
+    NightSurvived,
+}
+
+impl OnboardingEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            OnboardingEvent::StepCompleted(GuidedStep::FindCave) => {
+                "You found the cave. Remember it - dawn will come.".to_string()
+            }
+            OnboardingEvent::StepCompleted(GuidedStep::FeedOnRabbit) => {
+                "Warm blood steadies you. Every night you must feed.".to_string()
+            }
+            OnboardingEvent::StepCompleted(GuidedStep::MeetGrimjaw) => {
+                "Grimjaw knows your face now. The clans will remember you.".to_string()
+            }
+            OnboardingEvent::StepCompleted(GuidedStep::SurviveUntilDawn) => String::new(),
+            OnboardingEvent::DawnWarning { hours_left } if *hours_left >= 1.0 => {
+                format!("Dawn in {} hours. Know where you will shelter.", hours_left)
+            }
+            OnboardingEvent::DawnWarning { .. } => {
+                "The sky is paling! Get into the cave (F) now!".to_string()
+            }
+            OnboardingEvent::NightSurvived => {
+                "You survived your first night. The world is yours to explore.".to_string()
+            }
+        }
+    }
+}
+
+/// Onboarding system responsible for the guided first night
+pub struct OnboardingSystem;
+
+impl OnboardingSystem {
+    /// Prepare the world for a guided start and return its progress tracker.
+    /// Thins out the infected, keeping only the ones furthest from the
+    /// player, and makes sure there is a cave to find.
+    pub fn begin_guided(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        player_id: u32,
+    ) -> FirstNight {
+        let player_pos = EntityFinder::by_id(entities, player_id)
+            .map_or(Position::new(400.0, 650.0), |player| player.position);
+
+        let mut infected: Vec<(u32, f32)> = entities
+            .iter()
+            .filter(|entity| matches!(entity.entity_type, EntityType::HostileInfected))
+            .map(|entity| (entity.id, entity.position.distance_to(&player_pos)))
+            .collect();
+        infected.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let culled: Vec<u32> = infected
+            .iter()
+            .skip(GUIDED_MAX_INFECTED)
+            .map(|(id, _)| *id)
+            .collect();
+        entities.retain(|entity| !culled.contains(&entity.id));
+
+        let cave_id = Self::nearest_cave(entities, player_pos).unwrap_or_else(|| {
+            ShelterSystem::spawn_shelter(
+                entities,
+                entity_ids,
+                ShelterType::Cave,
+                GUIDED_CAVE_POSITION.0,
+                GUIDED_CAVE_POSITION.1,
+                Some(ShelterCondition::Good),
+                Some("Fledgling's Cave".to_string()),
+            )
+        });
+
+        FirstNight {
+            step: GuidedStep::FindCave,
+            cave_id,
+            feeding_baseline: 0,
+            warnings_given: 0,
+            banner: Some((
+                GuidedStep::FindCave.objective().to_string(),
+                BANNER_DURATION,
+            )),
+        }
+    }
+
+    /// Advance the guided first night. `met_leader` is the leader the player
+    /// is currently talking to, if any. Once dawn breaks the events end with
+    /// `NightSurvived` and the caller should drop the tracker.
+    pub fn update(
+        first_night: &mut FirstNight,
+        entities: &[GameEntity],
+        player_id: u32,
+        time: &TimeSystem,
+        feeding_count: u32,
+        met_leader: Option<&str>,
+    ) -> Vec<OnboardingEvent> {
+        let mut events = Vec::new();
+        if time.is_day() {
+            events.push(OnboardingEvent::NightSurvived);
+            return events;
+        }
+
+        let player_pos = EntityFinder::by_id(entities, player_id).map(|player| player.position);
+        let done = match first_night.step {
+            GuidedStep::FindCave => EntityFinder::by_id(entities, first_night.cave_id)
+                .zip(player_pos)
+                .is_some_and(|(cave, player_pos)| {
+                    cave.position.distance_to(&player_pos) <= ShelterType::Cave.discovery_range()
+                }),
+            GuidedStep::FeedOnRabbit => feeding_count > first_night.feeding_baseline,
+            GuidedStep::MeetGrimjaw => met_leader == Some("Grimjaw"),
+            GuidedStep::SurviveUntilDawn => false,
+        };
+        if done {
+            events.push(OnboardingEvent::StepCompleted(first_night.step));
+            first_night.step = first_night.step.next();
+            first_night.feeding_baseline = feeding_count;
+            first_night.show_banner(first_night.step.objective().to_string());
+        }
+
+        if let Some(&hours_left) = DAWN_WARNINGS.get(first_night.warnings_given) {
+            if time.time_until_dawn() <= hours_left {
+                first_night.warnings_given += 1;
+                let warning = OnboardingEvent::DawnWarning { hours_left };
+                first_night.show_banner(warning.get_message());
+                events.push(warning);
+            }
+        }
+
+        events
+    }
+
+    /// Where the current objective's marker should be drawn
+    pub fn marker_position(
+        first_night: &FirstNight,
+        entities: &[GameEntity],
+        player_id: u32,
+    ) -> Option<Position> {
+        let player_pos = EntityFinder::by_id(entities, player_id)?.position;
+        let cave = || EntityFinder::by_id(entities, first_night.cave_id).map(|cave| cave.position);
+
+        match first_night.step {
+            GuidedStep::FindCave | GuidedStep::SurviveUntilDawn => cave(),
+            GuidedStep::FeedOnRabbit => entities
+                .iter()
+                .filter(|entity| {
+                    matches!(entity.entity_type, EntityType::Animal)
+                        && !matches!(entity.ai_state, AIState::Dead)
+                })
+                .map(|entity| entity.position)
+                .min_by(|a, b| {
+                    a.distance_to(&player_pos)
+                        .total_cmp(&b.distance_to(&player_pos))
+                }),
+            GuidedStep::MeetGrimjaw => entities
+                .iter()
+                .find(|entity| {
+                    matches!(&entity.entity_type, EntityType::ClanLeader(clan) if clan == "Bone-Eaters")
+                })
+                .map(|leader| leader.position),
+        }
+    }
+
+    fn nearest_cave(entities: &[GameEntity], from: Position) -> Option<u32> {
+        entities
+            .iter()
+            .filter(|entity| {
+                entity
+                    .shelter
+                    .as_ref()
+                    .is_some_and(|shelter| shelter.shelter_type == ShelterType::Cave)
+            })
+            .min_by(|a, b| {
+                a.position
+                    .distance_to(&from)
+                    .total_cmp(&b.position.distance_to(&from))
+            })
+            .map(|cave| cave.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+    use macroquad::rand::RandGenerator;
+
+    fn guided_world() -> (Vec<GameEntity>, u32, FirstNight) {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let rng = RandGenerator::new();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        WorldSystem::spawn_all_clan_leaders(&mut entities, &mut ids);
+        WorldSystem::spawn_hostile_infected_group(&mut entities, &mut ids, 8, &rng);
+        WorldSystem::spawn_animal_group(&mut entities, &mut ids, 4, 7, &rng);
+        let first_night = OnboardingSystem::begin_guided(&mut entities, &mut ids, player_id);
+        (entities, player_id, first_night)
+    }
+
+    #[test]
+    fn test_guided_start_restricts_infected_and_places_cave() {
+        let (entities, _, first_night) = guided_world();
+
+        let infected = entities
+            .iter()
+            .filter(|entity| matches!(entity.entity_type, EntityType::HostileInfected))
+            .count();
+        assert_eq!(infected, GUIDED_MAX_INFECTED);
+
+        let cave = EntityFinder::by_id(&entities, first_night.cave_id).unwrap();
+        assert_eq!(
+            cave.shelter.as_ref().unwrap().shelter_type,
+            ShelterType::Cave
+        );
+    }
+
+    #[test]
+    fn test_guided_steps_and_dawn_warnings() {
+        let (mut entities, player_id, mut first_night) = guided_world();
+        let mut time = TimeSystem::new();
+        let update = |first_night: &mut FirstNight,
+                      entities: &[GameEntity],
+                      time: &TimeSystem,
+                      feeding_count: u32,
+                      met_leader: Option<&str>| {
+            OnboardingSystem::update(
+                first_night,
+                entities,
+                player_id,
+                time,
+                feeding_count,
+                met_leader,
+            )
+        };
+
+        // Nothing done yet
+        assert!(update(&mut first_night, &entities, &time, 0, None).is_empty());
+        assert_eq!(first_night.step, GuidedStep::FindCave);
+
+        // Reach the cave, feed, then talk to Grimjaw
+        let cave_pos = EntityFinder::by_id(&entities, first_night.cave_id)
+            .unwrap()
+            .position;
+        entities[0].position = cave_pos;
+        update(&mut first_night, &entities, &time, 0, None);
+        assert_eq!(first_night.step, GuidedStep::FeedOnRabbit);
+        update(&mut first_night, &entities, &time, 1, None);
+        assert_eq!(first_night.step, GuidedStep::MeetGrimjaw);
+        update(&mut first_night, &entities, &time, 1, Some("Grimjaw"));
+        assert_eq!(first_night.step, GuidedStep::SurviveUntilDawn);
+
+        // Close to dawn the warnings fire one per update
+        time.set_time(5.6);
+        let warnings: usize = (0..4)
+            .map(|_| update(&mut first_night, &entities, &time, 1, None).len())
+            .sum();
+        assert_eq!(warnings, DAWN_WARNINGS.len());
+
+        // Sunrise ends the guided start
+        time.set_time(7.0);
+        assert_eq!(
+            update(&mut first_night, &entities, &time, 1, None),
+            vec![OnboardingEvent::NightSurvived]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Particle System Module
+//!
+//! Short-lived visual particles: blood from feeding and wounds, dust kicked
+//! up by movement, embers off a vampire burning in the sun, and sparkles when
+//! an ability is used. Particles live in a fixed-size pool whose slots are
+//! reused as particles die, so emitting never allocates once the pool is
+//! full-sized.
+
+use crate::components::*;
+use macroquad::prelude::*;
+
+/// Most particles alive at once; emitting into a full pool does nothing
+pub const PARTICLE_CAPACITY: usize = 512;
+
+/// Dust puffs per second while walking, and embers per second while burning
+const DUST_RATE: f32 = 12.0;
+const EMBER_RATE: f32 = 20.0;
+/// Slowest movement that kicks up dust
+const DUST_MIN_SPEED: f32 = 40.0;
+
+/// What a particle is, which decides how it moves and looks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleKind {
+    Blood,
+    Dust,
+    Ember,
+    Sparkle,
+}
+
+impl ParticleKind {
+    /// Seconds a particle of this kind lives
+    fn lifetime(&self) -> f32 {
+        match self {
+            ParticleKind::Blood => 1.5,
+            ParticleKind::Dust => 0.6,
+            ParticleKind::Ember => 1.0,
+            ParticleKind::Sparkle => 0.7,
+        }
+    }
+
+    /// Downward acceleration; embers rise instead of falling
+    fn gravity(&self) -> f32 {
+        match self {
+            ParticleKind::Blood => 98.0,
+            ParticleKind::Dust => 0.0,
+            ParticleKind::Ember => -20.0,
+            ParticleKind::Sparkle => 0.0,
+        }
+    }
+
+    /// Random starting velocity
+    fn launch(&self) -> (f32, f32) {
+        match self {
+            ParticleKind::Blood => (rand::gen_range(-60.0, 60.0), rand::gen_range(-100.0, -20.0)),
+            ParticleKind::Dust => (rand::gen_range(-20.0, 20.0), rand::gen_range(-20.0, -5.0)),
+            ParticleKind::Ember => (rand::gen_range(-15.0, 15.0), rand::gen_range(-60.0, -30.0)),
+            ParticleKind::Sparkle => (rand::gen_range(-40.0, 40.0), rand::gen_range(-40.0, 40.0)),
+        }
+    }
+
+    /// Random size in world units
+    fn size(&self) -> f32 {
+        match self {
+            ParticleKind::Blood => rand::gen_range(2.0, 4.0),
+            ParticleKind::Dust => rand::gen_range(2.0, 4.0),
+            ParticleKind::Ember => rand::gen_range(1.0, 2.0),
+            ParticleKind::Sparkle => rand::gen_range(1.0, 2.0),
+        }
+    }
+
+    /// Color at full life; particles fade out as they age
+    pub fn color(&self) -> Color {
+        match self {
+            ParticleKind::Blood => Color::new(0.8, 0.0, 0.0, 1.0),
+            ParticleKind::Dust => Color::new(0.6, 0.5, 0.35, 0.6),
+            ParticleKind::Ember => Color::new(1.0, 0.55, 0.1, 1.0),
+            ParticleKind::Sparkle => Color::new(0.75, 0.85, 1.0, 1.0),
+        }
+    }
+}
+
+/// One particle; a slot in the pool with no life left is free
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    pub kind: ParticleKind,
+    pub position: Position,
+    pub velocity: Velocity,
+    pub life: f32,
+    pub max_life: f32,
+    pub size: f32,
+}
+
+impl Particle {
+    pub fn is_alive(&self) -> bool {
+        self.life > 0.0
+    }
+
+    /// Fraction of life left, from 1.0 when emitted down to 0.0
+    pub fn life_fraction(&self) -> f32 {
+        (self.life / self.max_life).clamp(0.0, 1.0)
+    }
+}
+
+/// Fixed-capacity particle storage that reuses dead slots
+#[derive(Debug, Clone)]
+pub struct ParticlePool {
+    slots: Vec<Particle>,
+    /// Indices of dead slots ready for reuse
+    free: Vec<usize>,
+    capacity: usize,
+    /// Fractions of a particle owed to continuous emitters
+    dust_owed: f32,
+    ember_owed: f32,
+}
+
+impl ParticlePool {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free: Vec::with_capacity(capacity),
+            capacity,
+            dust_owed: 0.0,
+            ember_owed: 0.0,
+        }
+    }
+
+    /// Living particles
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.slots.iter().filter(|particle| particle.is_alive())
+    }
+
+    /// Number of living particles
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every particle, keeping the allocated slots
+    pub fn clear(&mut self) {
+        self.free.clear();
+        for (index, particle) in self.slots.iter_mut().enumerate() {
+            particle.life = 0.0;
+            self.free.push(index);
+        }
+    }
+
+    fn insert(&mut self, particle: Particle) {
+        if let Some(index) = self.free.pop() {
+            self.slots[index] = particle;
+        } else if self.slots.len() < self.capacity {
+            self.slots.push(particle);
+        }
+    }
+}
+
+impl Default for ParticlePool {
+    fn default() -> Self {
+        Self::with_capacity(PARTICLE_CAPACITY)
+    }
+}
+
+/// Particle system responsible for emitting and moving particles
+pub struct ParticleSystem;
+
+impl ParticleSystem {
+    /// Emit a burst of particles at a position
+    pub fn emit(pool: &mut ParticlePool, kind: ParticleKind, position: Position, count: u32) {
+        for _ in 0..count {
+            let (velocity_x, velocity_y) = kind.launch();
+            pool.insert(Particle {
+                kind,
+                position,
+                velocity: Velocity::new(velocity_x, velocity_y),
+                life: kind.lifetime(),
+                max_life: kind.lifetime(),
+                size: kind.size(),
+            });
+        }
+    }
+
+    /// Kick up dust behind a moving entity, at a steady rate while it moves
+    /// fast enough
+    pub fn emit_dust(pool: &mut ParticlePool, entity: &GameEntity, delta_time: f32) {
+        let moving = entity
+            .velocity
+            .as_ref()
+            .is_some_and(|velocity| velocity.x.hypot(velocity.y) >= DUST_MIN_SPEED);
+        if !moving {
+            pool.dust_owed = 0.0;
+            return;
+        }
+        pool.dust_owed += DUST_RATE * delta_time;
+        let count = pool.dust_owed.floor();
+        pool.dust_owed -= count;
+        // Dust rises from the feet rather than the middle of the sprite
+        let feet = Position::new(entity.position.x, entity.position.y + 10.0);
+        Self::emit(pool, ParticleKind::Dust, feet, count as u32);
+    }
+
+    /// Throw off embers from entities burning in the sun this frame
+    pub fn emit_embers(pool: &mut ParticlePool, burning: &[Position], delta_time: f32) {
+        if burning.is_empty() {
+            pool.ember_owed = 0.0;
+            return;
+        }
+        pool.ember_owed += EMBER_RATE * delta_time;
+        let count = pool.ember_owed.floor();
+        pool.ember_owed -= count;
+        for position in burning {
+            Self::emit(pool, ParticleKind::Ember, *position, count as u32);
+        }
+    }
+
+    /// Move and age every particle, freeing the slots of those that die
+    pub fn update(pool: &mut ParticlePool, delta_time: f32) {
+        let ParticlePool { slots, free, .. } = pool;
+        for (index, particle) in slots.iter_mut().enumerate() {
+            if !particle.is_alive() {
+                continue;
+            }
+            particle.position.x += particle.velocity.x * delta_time;
+            particle.position.y += particle.velocity.y * delta_time;
+            particle.velocity.y += particle.kind.gravity() * delta_time;
+            particle.life -= delta_time;
+            if !particle.is_alive() {
+                free.push(index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_reuses_slots_and_caps_particles() {
+        let mut pool = ParticlePool::with_capacity(8);
+        ParticleSystem::emit(&mut pool, ParticleKind::Blood, Position::new(0.0, 0.0), 5);
+        ParticleSystem::emit(&mut pool, ParticleKind::Sparkle, Position::new(0.0, 0.0), 5);
+        assert_eq!(pool.len(), 8);
+
+        // Blood falls, embers rise
+        ParticleSystem::emit(&mut pool, ParticleKind::Ember, Position::new(0.0, 0.0), 1);
+        ParticleSystem::update(&mut pool, 0.8);
+        assert!(pool
+            .iter()
+            .all(|particle| particle.kind == ParticleKind::Blood));
+        assert_eq!(pool.len(), 5);
+
+        // Dead slots are refilled without growing the pool
+        ParticleSystem::emit(&mut pool, ParticleKind::Ember, Position::new(0.0, 0.0), 3);
+        assert_eq!(pool.len(), 8);
+        assert_eq!(pool.slots.len(), 8);
+        ParticleSystem::update(&mut pool, 0.5);
+        assert!(pool
+            .iter()
+            .filter(|particle| particle.kind == ParticleKind::Ember)
+            .all(|ember| ember.position.y < 0.0));
+
+        pool.clear();
+        assert!(pool.is_empty());
+    }
+}
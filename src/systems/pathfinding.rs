@@ -0,0 +1,462 @@
+//! Pathfinding System Module
+//!
+//! Finds a way around obstacles for creatures chasing a target. The ground
+//! is divided into a grid of square cells, blocked wherever a solid shelter
+//! stands or water lies, and A* search over it gives the route. A creature
+//! with its goal in plain line walks straight at it; otherwise it heads for
+//! the furthest point along the route it can reach in a straight line.
+//!
+//! Each creature keeps its route from tick to tick and only searches again
+//! when its goal moves to another cell, it strays off the route, or the
+//! grid is rebuilt. A search gives up after expanding a fixed number of
+//! cells, so a goal that cannot be reached costs no more than a long walk.
+
+use crate::components::*;
+use crate::systems::WorldSystem;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Room kept around shelter walls, so a creature's body clears the corner
+const WALL_CLEARANCE: f32 = 10.0;
+
+/// Cost of a step straight across and diagonally between cells
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+/// Route cells looked ahead along for one that can be walked to directly
+const LOOKAHEAD: usize = 8;
+
+/// Cells a search may expand before giving the goal up as out of reach
+const MAX_EXPANDED: usize = 2048;
+
+/// Column and row of a navigation cell
+pub type Cell = (i32, i32);
+
+/// A solid shelter's centre, half width and half height
+type Wall = (Position, f32, f32);
+
+/// The way one creature is following towards its goal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    /// Cell the route was planned to
+    goal: Cell,
+    /// Cells still ahead, or `None` when the goal could not be reached
+    cells: Option<Vec<Cell>>,
+}
+
+impl Route {
+    pub fn goal(&self) -> Cell {
+        self.goal
+    }
+
+    pub fn is_reachable(&self) -> bool {
+        self.cells.is_some()
+    }
+
+    /// Drop the cells already walked through, up to and including the one
+    /// the creature stands in. Returns false once the creature is off the
+    /// route, so it has to be planned again.
+    fn advance(&mut self, at: Cell) -> bool {
+        let Some(cells) = self.cells.as_mut() else {
+            return true;
+        };
+        if let Some(index) = cells.iter().position(|&cell| cell == at) {
+            cells.drain(..=index);
+            return true;
+        }
+        cells
+            .first()
+            .is_some_and(|next| (next.0 - at.0).abs() <= 1 && (next.1 - at.1).abs() <= 1)
+    }
+}
+
+/// Which cells of the world can be walked through
+#[derive(Debug, Clone)]
+pub struct NavGrid {
+    /// World position of the top-left corner of the first cell
+    origin: Position,
+    columns: i32,
+    rows: i32,
+    blocked: Vec<bool>,
+    /// What the grid was built from, to tell when it is out of date
+    walls: Vec<Wall>,
+    bounds: WorldBounds,
+    world_seed: u64,
+    /// Routes being followed, by creature ID; they go with the grid when it
+    /// is rebuilt
+    routes: HashMap<u32, Route>,
+}
+
+impl NavGrid {
+    /// Width and height of a cell in world units
+    pub const CELL: f32 = 32.0;
+
+    /// Grid over the ground within `bounds` of the world laid out by
+    /// `world_seed`, with every solid shelter among `entities` blocked off
+    pub fn build(entities: &[GameEntity], bounds: &WorldBounds, world_seed: u64) -> Self {
+        let origin = Position::new(bounds.min_x, WorldBounds::GROUND_LEVEL);
+        let columns = (bounds.width() / Self::CELL).ceil() as i32;
+        let rows = (bounds.depth() / Self::CELL).ceil() as i32;
+
+        let mut grid = Self {
+            origin,
+            columns,
+            rows,
+            blocked: Vec::with_capacity((columns * rows) as usize),
+            walls: Self::walls(entities).collect(),
+            bounds: *bounds,
+            world_seed,
+            routes: HashMap::new(),
+        };
+        for row in 0..rows {
+            for column in 0..columns {
+                let centre = grid.centre((column, row));
+                let in_wall = grid
+                    .walls
+                    .iter()
+                    .any(|(position, half_width, half_height)| {
+                        (centre.x - position.x).abs() < half_width + WALL_CLEARANCE
+                            && (centre.y - position.y).abs() < half_height + WALL_CLEARANCE
+                    });
+                let water = WorldSystem::is_water_at(&centre, world_seed);
+                grid.blocked.push(in_wall || water);
+            }
+        }
+        grid
+    }
+
+    /// Whether the grid still matches the shelters among `entities`, the
+    /// world `bounds` and `world_seed`, so it need not be built again
+    pub fn is_current(
+        &self,
+        entities: &[GameEntity],
+        bounds: &WorldBounds,
+        world_seed: u64,
+    ) -> bool {
+        self.world_seed == world_seed
+            && self.bounds == *bounds
+            && Self::walls(entities).eq(self.walls.iter().copied())
+    }
+
+    fn walls(entities: &[GameEntity]) -> impl Iterator<Item = Wall> + '_ {
+        entities
+            .iter()
+            .filter(|entity| entity.shelter.is_some())
+            .filter_map(|entity| match entity.collider {
+                Some(Collider::Box {
+                    half_width,
+                    half_height,
+                }) => Some((entity.position, half_width, half_height)),
+                _ => None,
+            })
+    }
+
+    /// Route a creature is following, if it has planned one
+    pub fn route(&self, creature_id: u32) -> Option<&Route> {
+        self.routes.get(&creature_id)
+    }
+
+    /// Cell containing a position, clamped onto the grid
+    pub fn cell_of(&self, position: &Position) -> Cell {
+        (
+            (((position.x - self.origin.x) / Self::CELL).floor() as i32).clamp(0, self.columns - 1),
+            (((position.y - self.origin.y) / Self::CELL).floor() as i32).clamp(0, self.rows - 1),
+        )
+    }
+
+    /// World position at the middle of a cell
+    pub fn centre(&self, (column, row): Cell) -> Position {
+        Position::new(
+            self.origin.x + (column as f32 + 0.5) * Self::CELL,
+            self.origin.y + (row as f32 + 0.5) * Self::CELL,
+        )
+    }
+
+    /// Whether a cell cannot be walked through; cells off the grid count
+    /// as blocked
+    pub fn is_blocked(&self, (column, row): Cell) -> bool {
+        if column < 0 || row < 0 || column >= self.columns || row >= self.rows {
+            return true;
+        }
+        self.blocked[(row * self.columns + column) as usize]
+    }
+
+    /// Whether a straight walk from `from` to `to` crosses no blocked
+    /// cell, other than the ones the walk starts and ends in
+    pub fn is_clear(&self, from: &Position, to: &Position) -> bool {
+        let (start, end) = (self.cell_of(from), self.cell_of(to));
+        let steps = (from.distance_to(to) / (Self::CELL / 2.0)).ceil() as usize;
+        (1..steps).all(|step| {
+            let t = step as f32 / steps as f32;
+            let cell = self.cell_of(&Position::new(
+                from.x + (to.x - from.x) * t,
+                from.y + (to.y - from.y) * t,
+            ));
+            cell == start || cell == end || !self.is_blocked(cell)
+        })
+    }
+}
+
+/// Pathfinding system responsible for routes around obstacles
+pub struct PathfindingSystem;
+
+impl PathfindingSystem {
+    /// Cells to walk through from `start` to `goal`, in order and ending at
+    /// the goal's cell, or `None` when the goal cannot be reached within
+    /// `MAX_EXPANDED` cells of searching. The cells the walk starts and ends
+    /// in may be blocked, so creatures pressed against a wall or targets
+    /// inside one can still be routed.
+    pub fn find_path(grid: &NavGrid, start: &Position, goal: &Position) -> Option<Vec<Cell>> {
+        let (start, goal) = (grid.cell_of(start), grid.cell_of(goal));
+        let estimate = |(column, row): Cell| {
+            let (dx, dy) = (
+                (column - goal.0).unsigned_abs(),
+                (row - goal.1).unsigned_abs(),
+            );
+            STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+        };
+
+        let mut open = BinaryHeap::from([Reverse((estimate(start), 0, start))]);
+        let mut cost: HashMap<Cell, u32> = HashMap::from([(start, 0)]);
+        let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+        let mut expanded = 0;
+
+        while let Some(Reverse((_, spent, cell))) = open.pop() {
+            if cell == goal {
+                let mut path = vec![goal];
+                while let Some(&previous) = came_from.get(path.last()?) {
+                    if previous == start {
+                        break;
+                    }
+                    path.push(previous);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if spent > cost.get(&cell).copied().unwrap_or(u32::MAX) {
+                continue;
+            }
+            expanded += 1;
+            if expanded > MAX_EXPANDED {
+                return None;
+            }
+
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    if dx == 0 && dy == 0 {
+                        continue;
+                    }
+                    let next = (cell.0 + dx, cell.1 + dy);
+                    if next != goal && grid.is_blocked(next) {
+                        continue;
+                    }
+                    let diagonal = dx != 0 && dy != 0;
+                    // No squeezing diagonally between two blocked cells
+                    if diagonal
+                        && (grid.is_blocked((cell.0 + dx, cell.1))
+                            || grid.is_blocked((cell.0, cell.1 + dy)))
+                    {
+                        continue;
+                    }
+                    let step = if diagonal {
+                        DIAGONAL_COST
+                    } else {
+                        STRAIGHT_COST
+                    };
+                    let next_cost = spent + step;
+                    if next_cost < cost.get(&next).copied().unwrap_or(u32::MAX) {
+                        cost.insert(next, next_cost);
+                        came_from.insert(next, cell);
+                        open.push(Reverse((next_cost + estimate(next), next_cost, next)));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Unit direction for the creature `creature_id` to walk from `from`
+    /// to make for `goal`: straight at it when the way is clear or no route
+    /// exists, otherwise towards the furthest point on its route that can
+    /// be walked to directly
+    pub fn steer(
+        grid: &mut NavGrid,
+        creature_id: u32,
+        from: &Position,
+        goal: &Position,
+    ) -> (f32, f32) {
+        let waypoint = if grid.is_clear(from, goal) {
+            grid.routes.remove(&creature_id);
+            *goal
+        } else {
+            let (at, goal_cell) = (grid.cell_of(from), grid.cell_of(goal));
+            let planned = grid
+                .routes
+                .get_mut(&creature_id)
+                .is_some_and(|route| route.goal == goal_cell && route.advance(at));
+            if !planned {
+                let route = Route {
+                    goal: goal_cell,
+                    cells: Self::find_path(grid, from, goal),
+                };
+                grid.routes.insert(creature_id, route);
+            }
+            let grid = &*grid;
+            grid.routes[&creature_id]
+                .cells
+                .as_ref()
+                .and_then(|path| {
+                    path.iter()
+                        .take(LOOKAHEAD)
+                        .map(|&cell| {
+                            if cell == grid.cell_of(goal) {
+                                *goal
+                            } else {
+                                grid.centre(cell)
+                            }
+                        })
+                        .take_while(|point| grid.is_clear(from, point))
+                        .last()
+                        .or_else(|| path.first().map(|&cell| grid.centre(cell)))
+                })
+                .unwrap_or(*goal)
+        };
+
+        let (dx, dy) = (waypoint.x - from.x, waypoint.y - from.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.0 {
+            (dx / length, dy / length)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::ShelterSystem;
+
+    #[test]
+    fn test_paths_go_around_shelters_and_water() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            300.0,
+            900.0,
+            None,
+            None,
+        );
+        let mut grid = NavGrid::build(&entities, &WorldBounds::default(), 0);
+        let (from, goal) = (Position::new(220.0, 900.0), Position::new(380.0, 900.0));
+        assert!(grid.is_blocked(grid.cell_of(&Position::new(300.0, 900.0))));
+        assert!(!grid.is_clear(&from, &goal));
+
+        // The route never enters the building and ends at the goal
+        let path = PathfindingSystem::find_path(&grid, &from, &goal).unwrap();
+        assert!(path.iter().all(|&cell| !grid.is_blocked(cell)));
+        assert_eq!(path.last(), Some(&grid.cell_of(&goal)));
+
+        // So the chaser sets off around the wall rather than into it
+        let (dx, dy) = PathfindingSystem::steer(&mut grid, 1, &from, &goal);
+        assert!(dy.abs() > 0.5);
+        assert!(dx >= 0.0);
+
+        // Open ground is walked straight across
+        let open = Position::new(220.0, 1150.0);
+        assert_eq!(
+            PathfindingSystem::steer(&mut grid, 2, &open, &Position::new(380.0, 1150.0)),
+            (1.0, 0.0)
+        );
+
+        // Nothing is routed through the water
+        let water = (0..50)
+            .flat_map(|column| (0..18).map(move |row| (column, row)))
+            .filter(|&cell| WorldSystem::is_water_at(&grid.centre(cell), 0))
+            .collect::<Vec<_>>();
+        assert!(!water.is_empty());
+        assert!(water.iter().all(|&cell| grid.is_blocked(cell)));
+    }
+
+    #[test]
+    fn test_grid_goes_out_of_date_with_shelters_and_bounds() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let mut bounds = WorldBounds::default();
+        let grid = NavGrid::build(&entities, &bounds, 0);
+        assert!(grid.is_current(&entities, &bounds, 0));
+        assert!(!grid.is_current(&entities, &bounds, 1));
+
+        // A shelter going up needs the grid built again
+        ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            300.0,
+            900.0,
+            None,
+            None,
+        );
+        assert!(!grid.is_current(&entities, &bounds, 0));
+
+        // So does the world growing, and the new grid reaches the new ground
+        let grid = NavGrid::build(&entities, &bounds, 0);
+        assert!(grid.is_current(&entities, &bounds, 0));
+        bounds.include_chunk((5, 2));
+        assert!(!grid.is_current(&entities, &bounds, 0));
+        let grid = NavGrid::build(&entities, &bounds, 0);
+        let far = Position::new(2900.0, 1200.0);
+        assert_eq!(grid.centre(grid.cell_of(&far)).x.floor(), 2896.0);
+    }
+
+    #[test]
+    fn test_unreachable_goal_is_searched_once() {
+        // A player out in the middle of a lake, every cell around them water
+        let (seed, grid, lake) = (0..20)
+            .find_map(|seed| {
+                let grid = NavGrid::build(&[], &WorldBounds::default(), seed);
+                let lake = (1..grid.columns - 1)
+                    .flat_map(|column| (1..grid.rows - 1).map(move |row| (column, row)))
+                    .find(|&(column, row)| {
+                        (-1..=1)
+                            .all(|dx| (-1..=1).all(|dy| grid.is_blocked((column + dx, row + dy))))
+                    })?;
+                Some((seed, grid, lake))
+            })
+            .expect("some world has a lake");
+        let mut grid = grid;
+        let goal = grid.centre(lake);
+        let from = (0..grid.columns)
+            .flat_map(|column| (0..grid.rows).map(move |row| (column, row)))
+            .find(|&cell| !grid.is_blocked(cell) && !grid.is_clear(&grid.centre(cell), &goal))
+            .map(|cell| grid.centre(cell))
+            .unwrap();
+        assert_eq!(PathfindingSystem::find_path(&grid, &from, &goal), None);
+
+        // The creature walks straight at the player and remembers there is
+        // no way through, rather than searching every tick
+        let (dx, dy) = PathfindingSystem::steer(&mut grid, 7, &from, &goal);
+        let direction = (goal.x - from.x, goal.y - from.y);
+        let length = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+        assert!((dx - direction.0 / length).abs() < 1e-4);
+        assert!((dy - direction.1 / length).abs() < 1e-4);
+        let route = grid.route(7).unwrap();
+        assert_eq!(route.goal(), lake);
+        assert!(!route.is_reachable());
+
+        // A grid rebuilt forgets it, so the way is looked for again
+        let grid = NavGrid::build(&[], &WorldBounds::default(), seed);
+        assert!(grid.route(7).is_none());
+
+        // However big the world, a search for it stops at the cap
+        let mut bounds = WorldBounds::default();
+        bounds.include_chunk((40, 20));
+        let grid = NavGrid::build(&[], &bounds, seed);
+        assert!(grid.columns * grid.rows > MAX_EXPANDED as i32);
+        assert_eq!(PathfindingSystem::find_path(&grid, &from, &goal), None);
+    }
+}
@@ -0,0 +1,150 @@
+//! Pause System Module
+//!
+//! Decides which parts of the world run each frame. Menus still stop
+//! everything, but a conversation only needs to freeze the run itself, so
+//! blood particles, the sky and the camera keep moving behind the dialogue
+//! box. A cutscene picks its own set of suspended systems and can take the
+//! camera away from the player for a few seconds.
+
+use crate::components::*;
+
+/// Groups of systems that can be suspended independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemPause {
+    /// Time of day, blood, combat, status effects, clans and everything
+    /// else that changes the state of the run
+    pub simulation: bool,
+    /// Creatures deciding where to go and whom to attack
+    pub ai: bool,
+    /// The player moving and acting, or steering the crow scout
+    pub player_input: bool,
+    /// Particles, sky, animations and the camera
+    pub ambient: bool,
+}
+
+impl SystemPause {
+    /// Everything runs
+    pub const NONE: SystemPause = SystemPause {
+        simulation: false,
+        ai: false,
+        player_input: false,
+        ambient: false,
+    };
+
+    /// Everything stops, as behind a menu
+    pub const ALL: SystemPause = SystemPause {
+        simulation: true,
+        ai: true,
+        player_input: true,
+        ambient: true,
+    };
+
+    /// The run holds still while the world keeps moving around it
+    pub const DIALOGUE: SystemPause = SystemPause {
+        ambient: false,
+        ..SystemPause::ALL
+    };
+
+    /// Suspend everything either set of flags suspends
+    pub fn combine(self, other: SystemPause) -> SystemPause {
+        SystemPause {
+            simulation: self.simulation || other.simulation,
+            ai: self.ai || other.ai,
+            player_input: self.player_input || other.player_input,
+            ambient: self.ambient || other.ambient,
+        }
+    }
+
+    /// Whether nothing at all runs this frame
+    pub fn is_all(&self) -> bool {
+        *self == SystemPause::ALL
+    }
+}
+
+/// A short scripted scene that suspends some systems and may point the
+/// camera somewhere other than the player
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cutscene {
+    pub pause: SystemPause,
+    /// Where the camera looks while the scene plays
+    pub focus: Option<Position>,
+    /// Seconds left before control returns to the player
+    pub remaining: f32,
+}
+
+impl Cutscene {
+    pub fn new(pause: SystemPause, focus: Option<Position>, duration: f32) -> Self {
+        Self {
+            pause,
+            focus,
+            remaining: duration,
+        }
+    }
+}
+
+/// Pause system responsible for combining menus, dialogue and cutscenes
+pub struct PauseSystem;
+
+impl PauseSystem {
+    /// The systems suspended this frame
+    pub fn for_frame(
+        menu_open: bool,
+        in_dialogue: bool,
+        cutscene: Option<&Cutscene>,
+    ) -> SystemPause {
+        if menu_open {
+            return SystemPause::ALL;
+        }
+        let mut pause = SystemPause::NONE;
+        if in_dialogue {
+            pause = pause.combine(SystemPause::DIALOGUE);
+        }
+        if let Some(cutscene) = cutscene {
+            pause = pause.combine(cutscene.pause);
+        }
+        pause
+    }
+
+    /// Count a cutscene down, clearing it once it has finished
+    pub fn tick_cutscene(cutscene: &mut Option<Cutscene>, delta_time: f32) {
+        if let Some(scene) = cutscene.as_mut() {
+            scene.remaining -= delta_time;
+            if scene.remaining <= 0.0 {
+                *cutscene = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_flags_combine_and_cutscenes_end() {
+        assert!(PauseSystem::for_frame(true, false, None).is_all());
+        assert_eq!(
+            PauseSystem::for_frame(false, false, None),
+            SystemPause::NONE
+        );
+
+        // Dialogue freezes the run but leaves the ambient world running
+        let dialogue = PauseSystem::for_frame(false, true, None);
+        assert!(dialogue.simulation && dialogue.ai && dialogue.player_input);
+        assert!(!dialogue.ambient);
+
+        // A cutscene that only holds the player still lets AI act
+        let reveal = SystemPause {
+            player_input: true,
+            ..SystemPause::NONE
+        };
+        let mut cutscene = Some(Cutscene::new(reveal, Some(Position::new(10.0, 20.0)), 1.0));
+        let pause = PauseSystem::for_frame(false, false, cutscene.as_ref());
+        assert!(pause.player_input && !pause.ai && !pause.simulation);
+
+        PauseSystem::tick_cutscene(&mut cutscene, 0.6);
+        assert!(cutscene.is_some());
+        PauseSystem::tick_cutscene(&mut cutscene, 0.6);
+        assert!(cutscene.is_none());
+    }
+}
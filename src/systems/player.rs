@@ -4,8 +4,44 @@
 //! This system manages player input processing, movement updates, and action execution.
 
 use crate::components::*;
-use crate::InputHandler;
+use crate::input::{InputAction, InputHandler};
+use crate::settings::Difficulty;
+use crate::systems::bestiary::BestiarySystem;
+use crate::systems::blood::BloodSystem;
+use crate::systems::combat::{CombatSystem, DamageEvent};
+use crate::systems::events::GameEvent;
+use crate::systems::spatial::SpatialGrid;
 use macroquad::prelude::*;
+use std::collections::HashMap;
+
+/// Largest retinue the player can lead at once
+pub const MAX_RETINUE: usize = 4;
+
+/// Reach for recruiting clan members and picking an attack target for followers
+const RECRUIT_RANGE: f32 = 70.0;
+const FOLLOWER_TARGET_RANGE: f32 = 250.0;
+
+/// How close the player must walk to an item to pick it up
+const PICKUP_RANGE: f32 = 24.0;
+
+/// Reach of the player's blows and bite
+const ATTACK_RANGE: f32 = 60.0;
+const FEED_RANGE: f32 = 50.0;
+
+/// How near the cursor must be to a creature to point at it
+const PICK_RADIUS: f32 = 24.0;
+
+/// Reach for items used on something nearby (keys and gifts)
+const ITEM_USE_RANGE: f32 = 70.0;
+
+/// Health restored by a bandage
+const BANDAGE_HEALTH: f32 = 25.0;
+
+/// Trust a clan gains from a gifted trinket
+const GIFT_TRUST: f32 = 0.1;
+
+/// Share of their speed the player keeps while swimming
+const SWIMMING_SPEED: f32 = 0.35;
 
 /// Player system responsible for player-specific logic and actions
 pub struct PlayerSystem;
@@ -15,18 +51,17 @@ impl PlayerSystem {
     pub fn handle_input(
         entities: &mut Vec<GameEntity>,
         input_handler: &InputHandler,
+        grid: &SpatialGrid,
         player_id: u32,
-        game_time: f32,
+        _game_time: f32,
     ) {
         // Player actions
         // Feeding is now handled directly in GameState
 
-        if input_handler.is_key_just_pressed(KeyCode::E) {
-            Self::attempt_interaction(entities, player_id);
-        }
+        // Attacks are resolved by the combat system via GameState
 
-        if input_handler.is_key_just_pressed(KeyCode::Space) {
-            Self::attempt_attack(entities, player_id, game_time);
+        if input_handler.is_action_just_pressed(InputAction::Interact) {
+            Self::attempt_interaction(entities, grid, player_id);
         }
     }
 
@@ -35,45 +70,54 @@ impl PlayerSystem {
         entities: &mut Vec<GameEntity>,
         input_handler: &InputHandler,
         player_id: u32,
+        bounds: &WorldBounds,
         is_day: bool,
+        swimming: bool,
         delta_time: f32,
     ) {
-        if let Some(player) = entities.iter_mut().find(|e| e.id == player_id) {
-            let mut move_x = 0.0;
-            let mut move_y = 0.0;
-
-            // Get movement input
-            if input_handler.is_key_pressed(KeyCode::W) {
-                move_y = -1.0;
-            }
-            if input_handler.is_key_pressed(KeyCode::S) {
-                move_y = 1.0;
-            }
-            if input_handler.is_key_pressed(KeyCode::A) {
-                move_x = -1.0;
-            }
-            if input_handler.is_key_pressed(KeyCode::D) {
-                move_x = 1.0;
-            }
-
-            // Normalize diagonal movement
-            if move_x != 0.0 && move_y != 0.0 {
-                move_x *= 0.707; // 1/sqrt(2)
-                move_y *= 0.707;
-            }
+        // Get movement input (keyboard or analog stick)
+        let direction = input_handler.movement_vector();
+        Self::move_player(
+            entities, player_id, bounds, direction, is_day, swimming, delta_time,
+        );
+    }
 
+    /// Move the player along `direction` (a unit or zero vector) at their
+    /// current speed, which is far slower while `swimming`, without leaving
+    /// `bounds`
+    pub fn move_player(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        bounds: &WorldBounds,
+        (move_x, move_y): (f32, f32),
+        is_day: bool,
+        swimming: bool,
+        delta_time: f32,
+    ) {
+        if let Some(player) = entities.iter_mut().find(|e| e.id == player_id) {
             // Calculate speed with ability modifiers
             let base_speed = 260.0;
             let ability_speed_modifier = player
                 .vampire_abilities
                 .as_ref()
-                .map(|abilities| abilities.speed)
-                .unwrap_or(1.0);
+                .map(|abilities| abilities.speed * abilities.ability_state.speed_multiplier())
+                .unwrap_or(1.0)
+                * player
+                    .buffs
+                    .as_ref()
+                    .map_or(1.0, |buffs| buffs.multiplier(BuffKind::Speed))
+                * player
+                    .progression
+                    .as_ref()
+                    .map_or(1.0, |progression| progression.multiplier(BuffKind::Speed));
 
             // Apply sunlight penalty during day
             let sunlight_penalty = if is_day { 0.5 } else { 1.0 };
 
-            let final_speed = base_speed * ability_speed_modifier * sunlight_penalty;
+            let swimming_penalty = if swimming { SWIMMING_SPEED } else { 1.0 };
+
+            let final_speed =
+                base_speed * ability_speed_modifier * sunlight_penalty * swimming_penalty;
 
             // Update velocity
             if let Some(velocity) = &mut player.velocity {
@@ -91,18 +135,51 @@ impl PlayerSystem {
             // Facing direction calculation removed as field no longer exists
             // Direction is now calculated from velocity when needed for rendering
 
-            // Keep player within world bounds
-            player.position.x = player.position.x.clamp(0.0, 1600.0);
-            player.position.y = player.position.y.clamp(640.0, 1200.0); // Can't go above ground level
+            // Keep player within world bounds; they can't go above ground level
+            player.position = bounds.clamp(player.position);
         }
     }
 
-    /// Attempt to feed on a nearby entity
+    /// Attempt to feed on a nearby entity, returning the feeding for the
+    /// event bus when it succeeds
     pub fn attempt_feeding(
         entities: &mut Vec<GameEntity>,
+        grid: &SpatialGrid,
+        player_id: u32,
+        game_time: f32,
+        debug_messages: &mut Vec<String>,
+    ) -> Option<GameEvent> {
+        Self::feed(entities, grid, player_id, None, game_time, debug_messages)
+    }
+
+    /// Feed on one chosen creature or corpse, if it is within reach
+    pub fn attempt_feeding_on(
+        entities: &mut Vec<GameEntity>,
+        grid: &SpatialGrid,
+        player_id: u32,
+        target_id: u32,
+        game_time: f32,
+        debug_messages: &mut Vec<String>,
+    ) -> Option<GameEvent> {
+        Self::feed(
+            entities,
+            grid,
+            player_id,
+            Some(target_id),
+            game_time,
+            debug_messages,
+        )
+    }
+
+    /// Feed on the first target in reach, or only on `only_target` when given
+    fn feed(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
         player_id: u32,
+        only_target: Option<u32>,
+        game_time: f32,
         debug_messages: &mut Vec<String>,
-    ) -> Option<Position> {
+    ) -> Option<GameEvent> {
         debug_messages.push("Attempting to feed...".to_string());
         let player_index = entities.iter().position(|e| e.id == player_id);
         let player_pos = if let Some(idx) = player_index {
@@ -112,28 +189,40 @@ impl PlayerSystem {
             return None;
         };
 
-        let feed_range = 50.0;
+        // Feeding too soon after the last meal is not possible
+        let cooldown = entities[player_index?]
+            .blood_meter
+            .as_ref()
+            .map_or(0.0, |meter| meter.feeding.cooldown_remaining(game_time));
+        if cooldown > 0.0 {
+            debug_messages.push(format!("Still savoring your last meal ({:.1}s)", cooldown));
+            return None;
+        }
+
+        let feed_range = FEED_RANGE;
 
         // Find the first valid target index
         debug_messages.push(format!(
             "Searching for feeding targets within range {}...",
             feed_range
         ));
-        let target_index = entities.iter().enumerate().find_map(|(idx, entity)| {
-            if entity.id == player_id {
-                return None;
+        let candidates = grid.query_radius(entities, player_pos, feed_range);
+        let target_index = candidates.into_iter().find(|&idx| {
+            let entity = &entities[idx];
+            if entity.id == player_id
+                || entity.is_hidden()
+                || only_target.is_some_and(|id| id != entity.id)
+            {
+                return false;
             }
             let distance = Self::calculate_distance(&player_pos, &entity.position);
-            let has_health = entity.health.as_ref().map_or(false, |h| h.current > 0.0);
+            let has_health = entity.health.as_ref().is_some_and(|h| h.current > 0.0);
+            let has_blood = has_health || entity.corpse.as_ref().is_some_and(|c| !c.is_dry());
             debug_messages.push(format!(
-                "Checking entity {:?} at ({:.1}, {:.1}), distance: {:.1}, has_health: {}, in_range: {}",
-                entity.entity_type, entity.position.x, entity.position.y, distance, has_health, distance <= feed_range
+                "Checking entity {:?} at ({:.1}, {:.1}), distance: {:.1}, has_blood: {}, in_range: {}",
+                entity.entity_type, entity.position.x, entity.position.y, distance, has_blood, distance <= feed_range
             ));
-            if distance <= feed_range && has_health {
-                Some(idx)
-            } else {
-                None
-            }
+            distance <= feed_range && has_blood
         });
         debug_messages.push(format!("Target index found: {:?}", target_index));
 
@@ -157,39 +246,181 @@ impl PlayerSystem {
                 return None;
             };
 
-            if let Some(health) = &mut second.health {
-                debug_messages.push(format!(
-                    "Target found for feeding: {:?} at ({}, {}), health: {}",
-                    second.entity_type, second.position.x, second.position.y, health.current
-                ));
-                let target_pos = second.position;
-                let blood_amount = health.current * 0.6;
-                health.current = 0.0; // Feeding is lethal
-                second.ai_state = AIState::Dead;
-
-                // Apply benefits to player
-                if let Some(blood_meter) = &mut first.blood_meter {
-                    blood_meter.current =
-                        (blood_meter.current + blood_amount).min(blood_meter.maximum);
+            // Living prey is drained completely, richer blood yielding more;
+            // corpses give what blood is left
+            let target_pos = second.position;
+            let quality = BloodQuality::of(&second.entity_type);
+            let mut fresh = false;
+            let raw_blood = match (&mut second.health, &mut second.corpse) {
+                (Some(health), _) if health.current > 0.0 => {
+                    debug_messages.push(format!(
+                        "Target found for feeding: {:?} at ({}, {}), health: {}",
+                        second.entity_type, target_pos.x, target_pos.y, health.current
+                    ));
+                    fresh = true;
+                    let blood = health.current
+                        * quality.map_or(0.6, |quality| quality.yield_factor())
+                        * BloodSystem::species_yield(second.species);
+                    health.current = 0.0; // Feeding is lethal
+                    second.ai_state = AIState::Dead;
+                    blood
+                }
+                (_, Some(corpse)) => {
+                    debug_messages.push(format!(
+                        "Feeding on corpse of {:?} ({:.0}% fresh)",
+                        second.entity_type,
+                        corpse.freshness() * 100.0
+                    ));
+                    let blood = corpse.blood;
+                    corpse.blood = 0.0;
+                    blood
                 }
-                if let Some(player_health) = &mut first.health {
-                    player_health.current =
-                        (player_health.current + blood_amount * 0.2).min(player_health.max);
+                _ => {
+                    debug_messages.push("ERROR: Target has no blood to drink!".to_string());
+                    return None;
                 }
+            };
+            // A drained body leaves nothing behind to feed on
+            second.corpse = Some(Corpse::new(0.0));
+
+            // Rapid consecutive feedings yield less as the vampire grows sated
+            let mut blood_amount = raw_blood
+                * first
+                    .progression
+                    .as_ref()
+                    .map_or(1.0, Progression::blood_multiplier);
+            let mut overflow = 0.0;
+            if let Some(blood_meter) = &mut first.blood_meter {
+                blood_amount *= blood_meter.feeding.gain_multiplier();
+                blood_meter.feeding.record_feed(game_time);
+                overflow = blood_meter.add_blood(blood_amount);
+            }
+            match Self::bottle_overflow(first, overflow) {
+                0 => {}
+                1 => debug_messages.push("You bottle the excess blood in a vial.".to_string()),
+                vials => {
+                    debug_messages.push(format!("You bottle the excess blood in {} vials.", vials))
+                }
+            }
+            if blood_amount < raw_blood {
                 debug_messages.push(format!(
-                    "Feeding successful! Returning target position: ({}, {})",
-                    target_pos.x, target_pos.y
+                    "Sated: absorbed {:.0} of {:.0} blood",
+                    blood_amount, raw_blood
                 ));
-                return Some(target_pos);
-            } else {
-                debug_messages.push("ERROR: Target has no health component!".to_string());
             }
+
+            // Apply benefits to player
+            if let Some(player_health) = &mut first.health {
+                player_health.current =
+                    (player_health.current + blood_amount * 0.2).min(player_health.max);
+            }
+
+            // Fresh blood of quality leaves the vampire stronger or faster for a while
+            if let (true, Some(quality), Some(buffs)) = (fresh, quality, &mut first.buffs) {
+                for buff in quality.buffs() {
+                    buffs.apply(buff);
+                    debug_messages.push(format!(
+                        "{} blood surges through you: {} x{:.2} for {:.0}s",
+                        quality.display_name(),
+                        buff.kind.display_name(),
+                        buff.multiplier,
+                        buff.duration
+                    ));
+                }
+            }
+            debug_messages.push(format!(
+                "Feeding successful at ({}, {})",
+                target_pos.x, target_pos.y
+            ));
+            return Some(GameEvent::FedOnTarget {
+                feeder_id: player_id,
+                target_id: second.id,
+                position: target_pos,
+                lethal: true,
+            });
         } else {
             debug_messages.push("No valid target found for feeding".to_string());
         }
         None
     }
 
+    /// Drain part of the blood of the nearest living creature in reach,
+    /// leaving it weakened and unconscious rather than dead
+    pub fn attempt_drain(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        game_time: f32,
+        debug_messages: &mut Vec<String>,
+    ) -> Option<GameEvent> {
+        let player = EntityFinder::by_id(entities, player_id)?;
+        let player_pos = player.position;
+        let cooldown = player
+            .blood_meter
+            .as_ref()
+            .map_or(0.0, |meter| meter.feeding.cooldown_remaining(game_time));
+        if cooldown > 0.0 {
+            debug_messages.push(format!("Still savoring your last meal ({:.1}s)", cooldown));
+            return None;
+        }
+
+        let feed_range = FEED_RANGE;
+        let (target_id, target_pos) = grid
+            .query_radius(entities, player_pos, feed_range)
+            .into_iter()
+            .map(|index| &entities[index])
+            .filter(|entity| {
+                entity.id != player_id
+                    && BloodQuality::of(&entity.entity_type).is_some()
+                    && entity.health.as_ref().is_some_and(|h| h.current > 0.0)
+                    && !matches!(&entity.ai_state, AIState::Staggered(stagger) if stagger.knocked_down)
+            })
+            .map(|entity| {
+                (
+                    entity.id,
+                    entity.position,
+                    Self::calculate_distance(&player_pos, &entity.position),
+                )
+            })
+            .filter(|(_, _, distance)| *distance <= feed_range)
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(id, position, _)| (id, position))?;
+
+        let drain = BloodSystem::drain(entities, player_id, target_id, game_time)?;
+        let player = entities.iter_mut().find(|entity| entity.id == player_id)?;
+        match Self::bottle_overflow(player, drain.overflow) {
+            0 => {}
+            1 => debug_messages.push("You bottle the excess blood in a vial.".to_string()),
+            vials => {
+                debug_messages.push(format!("You bottle the excess blood in {} vials.", vials))
+            }
+        }
+        if let Some(player_health) = &mut player.health {
+            player_health.current =
+                (player_health.current + drain.absorbed * 0.2).min(player_health.max);
+        }
+        debug_messages.push(format!(
+            "You drink deep but stop short: {:.0} {} blood, and your victim slumps unconscious",
+            drain.absorbed,
+            drain.quality.display_name()
+        ));
+        for buff in drain.quality.buffs() {
+            debug_messages.push(format!(
+                "{} blood surges through you: {} x{:.2} for {:.0}s",
+                drain.quality.display_name(),
+                buff.kind.display_name(),
+                buff.multiplier,
+                buff.duration
+            ));
+        }
+        Some(GameEvent::FedOnTarget {
+            feeder_id: player_id,
+            target_id,
+            position: target_pos,
+            lethal: false,
+        })
+    }
+
     /// Execute feeding on a target entity
     fn feed_on_target(entities: &mut Vec<GameEntity>, player_id: u32, target_id: u32) -> bool {
         let blood_gained = {
@@ -232,165 +463,420 @@ impl PlayerSystem {
         false
     }
 
-    /// Attempt to attack a nearby hostile entity
+    /// Attempt to attack a nearby hostile entity through the combat system
     pub fn attempt_attack(
-        entities: &mut Vec<GameEntity>,
+        entities: &mut [GameEntity],
         player_id: u32,
         game_time: f32,
-    ) -> Option<Position> {
-        let player_index = entities.iter().position(|e| e.id == player_id);
-        let player_pos = if let Some(idx) = player_index {
-            entities[idx].position
+        damage_events: &mut Vec<DamageEvent>,
+    ) -> Option<DamageEvent> {
+        let target_id =
+            CombatSystem::find_target_in_range(entities, player_id, ATTACK_RANGE, |entity| {
+                Self::can_attack(entity)
+            })?;
+
+        CombatSystem::resolve_attack(entities, player_id, target_id, game_time, damage_events)
+    }
+
+    /// Attack one chosen creature, if it is within reach
+    pub fn attempt_attack_on(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        target_id: u32,
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+    ) -> Option<DamageEvent> {
+        let player_pos = EntityFinder::by_id(entities, player_id)?.position;
+        let target = EntityFinder::by_id(entities, target_id)?;
+        if !Self::can_attack(target) || player_pos.distance_to(&target.position) > ATTACK_RANGE {
+            return None;
+        }
+        CombatSystem::resolve_attack(entities, player_id, target_id, game_time, damage_events)
+    }
+
+    /// Creatures the player's blows can land on
+    fn can_attack(entity: &GameEntity) -> bool {
+        matches!(
+            entity.entity_type,
+            EntityType::HostileInfected | EntityType::Animal
+        )
+    }
+
+    /// The creature or body under the cursor at `world_pos`, nearest first
+    pub fn pick_entity(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        world_pos: Position,
+    ) -> Option<u32> {
+        grid.query_radius(entities, world_pos, PICK_RADIUS)
+            .into_iter()
+            .map(|index| &entities[index])
+            .filter(|entity| {
+                entity.id != player_id
+                    && BloodQuality::of(&entity.entity_type).is_some()
+                    && (entity.health.as_ref().is_some_and(|h| h.current > 0.0)
+                        || entity.corpse.is_some())
+            })
+            .min_by(|a, b| {
+                a.position
+                    .distance_to(&world_pos)
+                    .total_cmp(&b.position.distance_to(&world_pos))
+            })
+            .map(|entity| entity.id)
+    }
+
+    /// What the player learns by pointing at a creature
+    pub fn inspect(
+        entities: &[GameEntity],
+        player_id: u32,
+        target_id: u32,
+    ) -> Option<EntityInspection> {
+        let player_pos = EntityFinder::by_id(entities, player_id)?.position;
+        let target = EntityFinder::by_id(entities, target_id)?;
+        let kind = BestiarySystem::lore_for(&target.entity_type)?.name;
+        let name = match &target.entity_type {
+            EntityType::ClanMember(clan) | EntityType::ClanLeader(clan) => {
+                format!("{} ({})", kind, clan)
+            }
+            _ => kind.to_string(),
+        };
+        let alive = target.health.as_ref().is_some_and(|h| h.current > 0.0);
+        let blood = if alive {
+            BloodSystem::calculate_blood_gain(target)
+        } else {
+            target.corpse.as_ref().map_or(0.0, |corpse| corpse.blood)
+        };
+        let distance = player_pos.distance_to(&target.position);
+        Some(EntityInspection {
+            name,
+            health: target
+                .health
+                .as_ref()
+                .filter(|_| alive)
+                .map(|health| (health.current, health.max)),
+            blood,
+            can_attack: alive && Self::can_attack(target) && distance <= ATTACK_RANGE,
+            can_feed: blood > 0.0 && distance <= FEED_RANGE,
+        })
+    }
+
+    /// Attempt to interact with nearby entities (clan leaders, NPCs)
+    pub fn attempt_interaction(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+    ) -> Option<String> {
+        let player_pos = if let Some(player) = entities.iter().find(|e| e.id == player_id) {
+            player.position
         } else {
             return None;
         };
 
-        let attack_range = 60.0;
-        // Find the first valid target index
-        let target_index = entities.iter().position(|entity| {
-            entity.id != player_id
-                && matches!(
-                    entity.entity_type,
-                    EntityType::HostileInfected | EntityType::Animal
-                )
-                && Self::calculate_distance(&player_pos, &entity.position) <= attack_range
-                && entity.health.as_ref().map_or(false, |h| h.current > 0.0)
-        });
+        let interact_range = 70.0;
 
-        if let (Some(player_idx), Some(target_idx)) = (player_index, target_index) {
-            // Safe split for double mutable borrow
-            let (first, second) = if player_idx < target_idx {
-                let (first, second) = entities.split_at_mut(target_idx);
-                (&mut first[player_idx], &mut second[0])
-            } else if player_idx > target_idx {
-                let (first, second) = entities.split_at_mut(player_idx);
-                (&mut second[0], &mut first[target_idx])
-            } else {
-                // Should never happen: player cannot be their own target
-                return None;
+        // Find nearby clan leaders
+        grid.query_radius(entities, player_pos, interact_range)
+            .into_iter()
+            .find_map(|idx| match &entities[idx].entity_type {
+                EntityType::ClanLeader(clan_name) => Some(clan_name.clone()),
+                _ => None,
+            })
+    }
+
+    /// Try to recruit a clan member into the player's retinue.
+    ///
+    /// A nearby member of an allied clan joins directly. Next to an allied
+    /// clan's leader, the result asks the caller to call a member out of the
+    /// clan, since most members are not present in the world.
+    pub fn attempt_recruitment(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        clans: &mut HashMap<String, Clan>,
+        player_id: u32,
+    ) -> RecruitResult {
+        let Some(player_pos) = EntityFinder::by_id(entities, player_id).map(|p| p.position) else {
+            return RecruitResult::NoCandidate;
+        };
+
+        if Self::follower_count(entities) >= MAX_RETINUE {
+            return RecruitResult::RetinueFull;
+        }
+
+        let nearby = grid.query_radius(entities, player_pos, RECRUIT_RANGE);
+
+        // Prefer members already standing nearby
+        let mut refused_clan = None;
+        for &index in &nearby {
+            let entity = &entities[index];
+            let EntityType::ClanMember(clan_name) = &entity.entity_type else {
+                continue;
+            };
+            if !matches!(entity.ai_state, AIState::Idle) {
+                continue;
+            }
+            let Some(clan) = clans.get_mut(clan_name) else {
+                continue;
             };
+            if !clan.is_allied {
+                refused_clan.get_or_insert_with(|| clan_name.clone());
+                continue;
+            }
 
-            // Extract attack power and check cooldown
-            let attack_power = if let Some(combat_stats) = &first.combat_stats {
-                if combat_stats.can_attack(game_time) {
-                    combat_stats.attack_power
-                } else {
-                    return None; // Still on cooldown
-                }
-            } else {
-                20.0 // Default attack power
+            let clan_name = clan_name.clone();
+            clan.member_count = clan.member_count.saturating_sub(1);
+            let entity = &mut entities[index];
+            entity.ai_state = AIState::Follower(FollowerOrder::Follow);
+            return RecruitResult::Recruited {
+                entity_id: entity.id,
+                clan_name,
             };
+        }
 
-            if let Some(health) = &mut second.health {
-                println!(
-                    "Target found for attack: {:?} at ({}, {}), health: {}",
-                    second.entity_type, second.position.x, second.position.y, health.current
-                );
-                let target_pos = second.position;
-                // Apply damage to target
-                let defense = second.combat_stats.as_ref().map_or(0.0, |cs| cs.defense);
-                let final_damage = (attack_power - defense).max(5.0); // Minimum damage
+        // Otherwise ask a leader to spare one of their people
+        for &index in &nearby {
+            let EntityType::ClanLeader(clan_name) = &entities[index].entity_type else {
+                continue;
+            };
+            let Some(clan) = clans.get_mut(clan_name) else {
+                continue;
+            };
+            if !clan.is_allied {
+                refused_clan.get_or_insert_with(|| clan_name.clone());
+                continue;
+            }
+            // The leader is counted among the members and always stays
+            if clan.member_count <= 1 {
+                return RecruitResult::NoneToSpare {
+                    clan_name: clan_name.clone(),
+                };
+            }
 
-                health.current -= final_damage;
-                health.current = health.current.max(0.0);
+            clan.member_count -= 1;
+            return RecruitResult::Summoned {
+                clan_name: clan_name.clone(),
+            };
+        }
 
-                if health.current <= 0.0 {
-                    second.ai_state = AIState::Dead;
-                }
+        match refused_clan {
+            Some(clan_name) => RecruitResult::NotAllied { clan_name },
+            None => RecruitResult::NoCandidate,
+        }
+    }
 
-                // Update player attack cooldown
-                if let Some(combat_stats) = &mut first.combat_stats {
-                    combat_stats.last_attack_time = game_time;
-                }
+    /// Give every follower an order. Returns how many followers received it,
+    /// or `None` when attacking was ordered but no enemy is near the player.
+    pub fn command_followers(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        command: FollowerCommand,
+    ) -> Option<usize> {
+        let target = match command {
+            FollowerCommand::AttackTarget => Some(CombatSystem::find_target_in_range(
+                entities,
+                player_id,
+                FOLLOWER_TARGET_RANGE,
+                |entity| {
+                    matches!(entity.entity_type, EntityType::HostileInfected)
+                        || matches!(entity.ai_state, AIState::Hostile)
+                },
+            )?),
+            _ => None,
+        };
 
-                return Some(target_pos);
+        let mut commanded = 0;
+        for entity in entities.iter_mut() {
+            if !matches!(entity.ai_state, AIState::Follower(_)) {
+                continue;
             }
+            let order = match (command, target) {
+                (FollowerCommand::Follow, _) => FollowerOrder::Follow,
+                (FollowerCommand::Hold, _) => FollowerOrder::Hold(entity.position),
+                (FollowerCommand::AttackTarget, Some(target_id)) => {
+                    FollowerOrder::Attack(target_id)
+                }
+                (FollowerCommand::AttackTarget, None) => continue,
+            };
+            entity.ai_state = AIState::Follower(order);
+            commanded += 1;
         }
-        None
+
+        Some(commanded)
     }
 
-    /// Execute an attack on a target entity
-    fn attack_entity(
+    /// Number of living followers in the player's retinue
+    pub fn follower_count(entities: &[GameEntity]) -> usize {
+        entities
+            .iter()
+            .filter(|entity| matches!(entity.ai_state, AIState::Follower(_)))
+            .count()
+    }
+
+    /// Pick up any items the player is standing on. Collected pickups are
+    /// removed from `entities`, so the caller must rebuild the spatial grid
+    /// when anything is picked up. A full inventory is only reported when
+    /// the player steps up to the item from `previous`.
+    pub fn collect_pickups(
         entities: &mut Vec<GameEntity>,
         player_id: u32,
-        target_id: u32,
-        game_time: f32,
-    ) -> bool {
-        // Get player attack power and check cooldown
-        let attack_power = if let Some(player) = entities.iter().find(|e| e.id == player_id) {
-            if let Some(combat_stats) = &player.combat_stats {
-                if combat_stats.can_attack(game_time) {
-                    combat_stats.attack_power
-                } else {
-                    return false; // Still on cooldown
-                }
-            } else {
-                20.0 // Default attack power
-            }
-        } else {
-            return false;
+        previous: Position,
+    ) -> Vec<ItemEvent> {
+        let Some(player_index) = entities.iter().position(|e| e.id == player_id) else {
+            return Vec::new();
+        };
+        let player_pos = entities[player_index].position;
+        let in_reach: Vec<(u32, Pickup, bool)> = entities
+            .iter()
+            .filter(|entity| entity.position.distance_to(&player_pos) <= PICKUP_RANGE)
+            .filter_map(|entity| {
+                let newly_reached = entity.position.distance_to(&previous) > PICKUP_RANGE;
+                entity
+                    .pickup
+                    .map(|pickup| (entity.id, pickup, newly_reached))
+            })
+            .collect();
+        let Some(inventory) = entities[player_index].inventory.as_mut() else {
+            return Vec::new();
         };
 
-        // Apply damage to target
-        let mut target_killed = false;
-        if let Some(target) = entities.iter_mut().find(|e| e.id == target_id) {
-            if let Some(health) = &mut target.health {
-                let defense = target.combat_stats.as_ref().map_or(0.0, |cs| cs.defense);
-                let final_damage = (attack_power - defense).max(5.0); // Minimum damage
-
-                health.current -= final_damage;
-                health.current = health.current.max(0.0);
-
-                if health.current <= 0.0 {
-                    target.ai_state = AIState::Dead;
-                    target_killed = true;
-                }
+        let mut events = Vec::new();
+        let mut collected = Vec::new();
+        for (id, pickup, newly_reached) in in_reach {
+            if inventory.add_item(pickup.item, pickup.quantity) {
+                collected.push(id);
+                events.push(ItemEvent::PickedUp {
+                    item: pickup.item,
+                    quantity: pickup.quantity,
+                });
+            } else if newly_reached {
+                events.push(ItemEvent::InventoryFull(pickup.item));
             }
         }
 
-        // Update player attack cooldown
-        if let Some(player) = entities.iter_mut().find(|e| e.id == player_id) {
-            if let Some(combat_stats) = &mut player.combat_stats {
-                combat_stats.last_attack_time = game_time;
+        entities.retain(|entity| !collected.contains(&entity.id));
+        events
+    }
+
+    /// Bottle blood that overflowed a full meter. Overflow collects in the
+    /// meter until it fills a vial; once the vial carry limit is reached the
+    /// rest is wasted. Returns the number of vials filled.
+    pub fn bottle_overflow(player: &mut GameEntity, overflow: f32) -> u32 {
+        let (Some(blood_meter), Some(inventory)) =
+            (player.blood_meter.as_mut(), player.inventory.as_mut())
+        else {
+            return 0;
+        };
+
+        blood_meter.bottling += overflow;
+        let mut filled = 0;
+        while blood_meter.bottling >= ItemKind::VIAL_BLOOD {
+            if !inventory.add_item(ItemKind::BloodVial, 1) {
+                // No room for another vial; keep one vial's worth waiting
+                blood_meter.bottling = ItemKind::VIAL_BLOOD;
+                break;
             }
+            blood_meter.bottling -= ItemKind::VIAL_BLOOD;
+            filled += 1;
         }
-
-        target_killed
+        filled
     }
 
-    /// Attempt to interact with nearby entities (clan leaders, NPCs)
-    pub fn attempt_interaction(entities: &mut Vec<GameEntity>, player_id: u32) -> Option<String> {
-        let player_pos = if let Some(player) = entities.iter().find(|e| e.id == player_id) {
-            player.position
-        } else {
-            return None;
+    /// Use an item from the player's inventory. The item is only consumed
+    /// when it has an effect.
+    pub fn use_item(
+        entities: &mut [GameEntity],
+        clans: &mut HashMap<String, Clan>,
+        player_id: u32,
+        item: ItemKind,
+    ) -> ItemEvent {
+        let Some(player_index) = entities.iter().position(|e| e.id == player_id) else {
+            return ItemEvent::NotNeeded(item);
         };
+        let has_item = entities[player_index]
+            .inventory
+            .as_ref()
+            .is_some_and(|inventory| inventory.has_item(item, 1));
+        if !has_item {
+            return ItemEvent::NoneLeft(item);
+        }
+        let player_pos = entities[player_index].position;
 
-        let interact_range = 70.0;
-
-        // Find nearby clan leaders
-        for entity in entities.iter() {
-            if let EntityType::ClanLeader(clan_name) = &entity.entity_type {
-                let distance = Self::calculate_distance(&player_pos, &entity.position);
-                if distance <= interact_range {
-                    return Some(clan_name.clone());
+        let event = match item {
+            ItemKind::BloodVial => {
+                let player = &mut entities[player_index];
+                match player.blood_meter.as_mut() {
+                    Some(blood) if blood.current < blood.maximum => {
+                        let before = blood.current;
+                        blood.current = (blood.current + ItemKind::VIAL_BLOOD).min(blood.maximum);
+                        ItemEvent::DrankVial {
+                            blood: blood.current - before,
+                        }
+                    }
+                    _ => ItemEvent::NotNeeded(item),
                 }
             }
-        }
+            ItemKind::Bandage => {
+                let player = &mut entities[player_index];
+                match player.health.as_mut() {
+                    Some(health) if health.current < health.max => {
+                        let before = health.current;
+                        health.heal(BANDAGE_HEALTH);
+                        ItemEvent::Bandaged {
+                            health: health.current - before,
+                        }
+                    }
+                    _ => ItemEvent::NotNeeded(item),
+                }
+            }
+            ItemKind::Key => entities
+                .iter_mut()
+                .filter(|entity| entity.position.distance_to(&player_pos) <= ITEM_USE_RANGE)
+                .filter_map(|entity| entity.shelter.as_mut())
+                .find(|shelter| shelter.locked)
+                .map_or(ItemEvent::NotNeeded(item), |shelter| {
+                    shelter.locked = false;
+                    shelter.discover();
+                    ItemEvent::Unlocked(shelter.display_name().to_string())
+                }),
+            ItemKind::Trinket => entities
+                .iter()
+                .filter(|entity| entity.position.distance_to(&player_pos) <= ITEM_USE_RANGE)
+                .find_map(|entity| match &entity.entity_type {
+                    EntityType::ClanLeader(clan_name) => Some(clan_name.clone()),
+                    _ => None,
+                })
+                .and_then(|clan_name| clans.get_mut(&clan_name))
+                .map_or(ItemEvent::NotNeeded(item), |clan| {
+                    clan.trust_towards_player = (clan.trust_towards_player + GIFT_TRUST).min(1.0);
+                    ItemEvent::GiftAccepted {
+                        leader_name: clan.leader_name.clone(),
+                    }
+                }),
+            // The caller records what the notes teach in the bestiary
+            ItemKind::HunterNotes => ItemEvent::NotesRead,
+        };
 
-        None
+        if event.consumes_item() {
+            if let Some(inventory) = entities[player_index].inventory.as_mut() {
+                inventory.remove_item(item, 1);
+            }
+        }
+        event
     }
 
-    /// Apply sunlight damage to the player during daytime
+    /// Apply sunlight damage to the player during daytime, scaled for the
+    /// difficulty
     pub fn apply_sunlight_damage(
         entities: &mut Vec<GameEntity>,
         player_id: u32,
         sunlight_intensity: f32,
+        difficulty: Difficulty,
         delta_time: f32,
     ) -> f32 {
         if let Some(player) = entities.iter_mut().find(|e| e.id == player_id) {
             if let Some(health) = &mut player.health {
-                let damage = 3.0 * sunlight_intensity * delta_time;
+                let damage =
+                    3.0 * sunlight_intensity * difficulty.sunlight_damage_multiplier() * delta_time;
                 health.current = (health.current - damage).max(0.0);
                 return damage;
             }
@@ -485,6 +971,20 @@ impl PlayerSystem {
     }
 }
 
+/// What the player sees of a creature they point at
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityInspection {
+    pub name: String,
+    /// Current and maximum health, while it lives
+    pub health: Option<(f32, f32)>,
+    /// Blood a feeding would yield
+    pub blood: f32,
+    /// Within reach of a blow
+    pub can_attack: bool,
+    /// Within reach of the bite, with blood to drink
+    pub can_feed: bool,
+}
+
 /// Player status information
 #[derive(Debug, Clone)]
 pub struct PlayerStatus {
@@ -505,6 +1005,141 @@ pub enum PlayerAction {
     SpecialAbility,
 }
 
+/// Commands the player can give the retinue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowerCommand {
+    Follow,
+    Hold,
+    AttackTarget,
+}
+
+impl FollowerCommand {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FollowerCommand::Follow => "Follow me",
+            FollowerCommand::Hold => "Hold position",
+            FollowerCommand::AttackTarget => "Attack my target",
+        }
+    }
+}
+
+/// Outcome of a recruitment attempt
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecruitResult {
+    /// A member standing nearby joined the retinue
+    Recruited {
+        entity_id: u32,
+        clan_name: String,
+    },
+    /// A leader agreed to send a member; the caller spawns them
+    Summoned {
+        clan_name: String,
+    },
+    NotAllied {
+        clan_name: String,
+    },
+    NoneToSpare {
+        clan_name: String,
+    },
+    RetinueFull,
+    NoCandidate,
+}
+
+impl RecruitResult {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            RecruitResult::Recruited { clan_name, .. } | RecruitResult::Summoned { clan_name } => {
+                format!("A warrior of the {} joins your retinue.", clan_name)
+            }
+            RecruitResult::NotAllied { clan_name } => {
+                format!("The {} will not serve you until you are allied.", clan_name)
+            }
+            RecruitResult::NoneToSpare { clan_name } => {
+                format!("The {} have no one left to spare.", clan_name)
+            }
+            RecruitResult::RetinueFull => {
+                format!("Your retinue is full ({} followers).", MAX_RETINUE)
+            }
+            RecruitResult::NoCandidate => "There is no one here to recruit.".to_string(),
+        }
+    }
+}
+
+/// Outcome of picking up or using an item
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemEvent {
+    PickedUp {
+        item: ItemKind,
+        quantity: u32,
+    },
+    InventoryFull(ItemKind),
+    DrankVial {
+        blood: f32,
+    },
+    Bandaged {
+        health: f32,
+    },
+    /// A key opened the named shelter
+    Unlocked(String),
+    /// A clan leader accepted a trinket
+    GiftAccepted {
+        leader_name: String,
+    },
+    NotesRead,
+    /// The item would have no effect right now and was kept
+    NotNeeded(ItemKind),
+    NoneLeft(ItemKind),
+}
+
+impl ItemEvent {
+    /// Whether the item was used up
+    pub fn consumes_item(&self) -> bool {
+        matches!(
+            self,
+            ItemEvent::DrankVial { .. }
+                | ItemEvent::Bandaged { .. }
+                | ItemEvent::Unlocked(_)
+                | ItemEvent::GiftAccepted { .. }
+                | ItemEvent::NotesRead
+        )
+    }
+
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            ItemEvent::PickedUp { item, quantity: 1 } => {
+                format!("Picked up a {}.", item.display_name())
+            }
+            ItemEvent::PickedUp { item, quantity } => {
+                format!("Picked up {} x {}.", quantity, item.display_name())
+            }
+            ItemEvent::InventoryFull(item) => {
+                format!("No room to carry the {}.", item.display_name())
+            }
+            ItemEvent::DrankVial { blood } => {
+                format!("You drink the vial (+{:.0} blood).", blood)
+            }
+            ItemEvent::Bandaged { health } => {
+                format!("You bind your wounds (+{:.0} health).", health)
+            }
+            ItemEvent::Unlocked(name) => format!("The key turns - the {} is open.", name),
+            ItemEvent::GiftAccepted { leader_name } => {
+                format!("{} accepts your gift.", leader_name)
+            }
+            ItemEvent::NotesRead => "You read through the hunter's notes.".to_string(),
+            ItemEvent::NotNeeded(item) => match item {
+                ItemKind::BloodVial => "You are not thirsty.".to_string(),
+                ItemKind::Bandage => "You have no wounds to bind.".to_string(),
+                ItemKind::Key => "There is no locked door nearby.".to_string(),
+                ItemKind::Trinket => "There is no clan leader here to accept a gift.".to_string(),
+                ItemKind::HunterNotes => "The notes are too faded to read.".to_string(),
+            },
+            ItemEvent::NoneLeft(item) => format!("You have no {} left.", item.display_name()),
+        }
+    }
+}
+
 /// Types of experience for leveling up abilities
 #[derive(Debug, Clone, Copy)]
 pub enum ExperienceType {
@@ -517,6 +1152,7 @@ pub enum ExperienceType {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::systems::world::WorldSystem;
 
     fn create_test_player() -> GameEntity {
         GameEntity {
@@ -534,16 +1170,30 @@ mod tests {
                 current: 50.0,
                 maximum: 100.0,
                 drain_rate: 1.0,
+                feeding: FeedingState::default(),
+                bottling: 0.0,
             }),
             vampire_abilities: Some(VampireAbilities {
                 strength: 1.0,
                 speed: 1.0,
                 blood_sense: 0.0,
                 shadow_movement: 0.0,
+                ability_state: AbilityState::default(),
             }),
             shelter: None,
             shelter_occupancy: None,
             color: RED,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         }
     }
 
@@ -586,4 +1236,246 @@ mod tests {
         let distance = PlayerSystem::calculate_distance(&pos1, &pos2);
         assert_eq!(distance, 5.0); // 3-4-5 triangle
     }
+
+    fn create_test_prey(id: u32, x: f32) -> GameEntity {
+        GameEntity {
+            id,
+            position: Position { x, y: 100.0 },
+            velocity: None,
+            entity_type: EntityType::Animal,
+            health: Some(Health::new(50.0)),
+            combat_stats: None,
+            ai_state: AIState::Idle,
+            blood_meter: None,
+            vampire_abilities: None,
+            shelter: None,
+            shelter_occupancy: None,
+            color: BROWN,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        }
+    }
+
+    fn player_blood(entities: &[GameEntity]) -> f32 {
+        entities[0].blood_meter.as_ref().unwrap().current
+    }
+
+    #[test]
+    fn test_feeding_cooldown_and_satiation() {
+        let mut entities = vec![
+            create_test_player(),
+            create_test_prey(1, 110.0),
+            create_test_prey(2, 120.0),
+        ];
+        entities[0].blood_meter.as_mut().unwrap().current = 0.0;
+        let mut messages = Vec::new();
+
+        let grid = SpatialGrid::from_entities(&entities);
+        assert!(
+            PlayerSystem::attempt_feeding(&mut entities, &grid, 0, 10.0, &mut messages).is_some()
+        );
+        let first_gain = player_blood(&entities);
+        assert!((first_gain - 30.0).abs() < 0.01);
+
+        // Chain-feeding straight away is blocked by the cooldown
+        assert!(
+            PlayerSystem::attempt_feeding(&mut entities, &grid, 0, 10.5, &mut messages).is_none()
+        );
+
+        // After the cooldown the second meal is reduced by satiation
+        assert!(
+            PlayerSystem::attempt_feeding(&mut entities, &grid, 0, 13.0, &mut messages).is_some()
+        );
+        let second_gain = player_blood(&entities) - first_gain;
+        assert!(second_gain < first_gain);
+    }
+
+    #[test]
+    fn test_mouse_picks_and_targets_the_creature_under_the_cursor() {
+        let mut entities = vec![
+            create_test_player(),
+            create_test_prey(1, 130.0),
+            create_test_prey(2, 300.0),
+        ];
+        let grid = SpatialGrid::from_entities(&entities);
+
+        assert_eq!(
+            PlayerSystem::pick_entity(&entities, &grid, 0, Position::new(305.0, 95.0)),
+            Some(2)
+        );
+        assert_eq!(
+            PlayerSystem::pick_entity(&entities, &grid, 0, Position::new(200.0, 100.0)),
+            None
+        );
+        // The player never picks themselves
+        assert_eq!(
+            PlayerSystem::pick_entity(&entities, &grid, 0, Position::new(100.0, 100.0)),
+            None
+        );
+
+        let near = PlayerSystem::inspect(&entities, 0, 1).unwrap();
+        assert_eq!(near.health, Some((50.0, 50.0)));
+        assert!((near.blood - 30.0).abs() < 0.01);
+        assert!(near.can_attack && near.can_feed);
+        let far = PlayerSystem::inspect(&entities, 0, 2).unwrap();
+        assert!(!far.can_attack && !far.can_feed);
+
+        // Clicking the distant creature neither strikes nor bites it
+        let mut events = Vec::new();
+        assert!(PlayerSystem::attempt_attack_on(&mut entities, 0, 2, 0.0, &mut events).is_none());
+        let mut messages = Vec::new();
+        assert!(
+            PlayerSystem::attempt_feeding_on(&mut entities, &grid, 0, 2, 0.0, &mut messages)
+                .is_none()
+        );
+        assert!(
+            PlayerSystem::attempt_feeding_on(&mut entities, &grid, 0, 1, 0.0, &mut messages)
+                .is_some()
+        );
+        assert!(entities[2].health.as_ref().unwrap().is_alive());
+    }
+
+    #[test]
+    fn test_corpse_blood_decays() {
+        let mut entities = vec![create_test_player(), create_test_prey(1, 110.0)];
+        entities[0].blood_meter.as_mut().unwrap().current = 0.0;
+        entities[1].health.as_mut().unwrap().current = 0.0;
+        entities[1].ai_state = AIState::Dead;
+        entities[1].corpse = Some(Corpse::new(20.0));
+
+        entities[1]
+            .corpse
+            .as_mut()
+            .unwrap()
+            .decay(Corpse::BLOOD_HALF_LIFE);
+
+        let grid = SpatialGrid::from_entities(&entities);
+        let mut messages = Vec::new();
+        assert!(
+            PlayerSystem::attempt_feeding(&mut entities, &grid, 0, 0.0, &mut messages).is_some()
+        );
+        assert!((player_blood(&entities) - 10.0).abs() < 0.01);
+        assert!(entities[1].corpse.as_ref().unwrap().is_dry());
+
+        // A drained corpse cannot be fed on again
+        assert!(
+            PlayerSystem::attempt_feeding(&mut entities, &grid, 0, 5.0, &mut messages).is_none()
+        );
+    }
+
+    #[test]
+    fn test_recruitment_requires_alliance() {
+        let mut member = create_test_player();
+        member.id = 1;
+        member.entity_type = EntityType::ClanMember("Night-Bloods".to_string());
+        member.position = Position { x: 130.0, y: 100.0 };
+        member.ai_state = AIState::Idle;
+        let mut entities = vec![create_test_player(), member];
+        let grid = SpatialGrid::from_entities(&entities);
+
+        let mut clans = HashMap::new();
+        clans.insert(
+            "Night-Bloods".to_string(),
+            Clan::new("Night-Bloods", "Silentfang", 10),
+        );
+
+        let result = PlayerSystem::attempt_recruitment(&mut entities, &grid, &mut clans, 0);
+        assert!(matches!(result, RecruitResult::NotAllied { .. }));
+
+        clans.get_mut("Night-Bloods").unwrap().is_allied = true;
+        let result = PlayerSystem::attempt_recruitment(&mut entities, &grid, &mut clans, 0);
+        assert!(matches!(
+            result,
+            RecruitResult::Recruited { entity_id: 1, .. }
+        ));
+        assert_eq!(PlayerSystem::follower_count(&entities), 1);
+        assert_eq!(clans["Night-Bloods"].member_count, 9);
+
+        // With no enemy around, an attack order has no target
+        assert_eq!(
+            PlayerSystem::command_followers(&mut entities, 0, FollowerCommand::AttackTarget),
+            None
+        );
+        assert_eq!(
+            PlayerSystem::command_followers(&mut entities, 0, FollowerCommand::Hold),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_pickups_and_item_use() {
+        let mut entities = vec![create_test_player()];
+        entities[0].inventory = Some(Inventory::new(3));
+        let mut ids = EntityAllocator::from_ids([entities[0].id], 1);
+        WorldSystem::spawn_pickup(
+            &mut entities,
+            &mut ids,
+            ItemKind::BloodVial,
+            2,
+            110.0,
+            100.0,
+        );
+        WorldSystem::spawn_pickup(&mut entities, &mut ids, ItemKind::Key, 2, 95.0, 100.0);
+
+        // The vials fit; the keys would overflow and stay on the ground
+        let events = PlayerSystem::collect_pickups(&mut entities, 0, Position::new(0.0, 100.0));
+        assert_eq!(
+            events,
+            vec![
+                ItemEvent::PickedUp {
+                    item: ItemKind::BloodVial,
+                    quantity: 2
+                },
+                ItemEvent::InventoryFull(ItemKind::Key),
+            ]
+        );
+        assert_eq!(entities.len(), 2);
+        // Standing still does not repeat the warning
+        let events = PlayerSystem::collect_pickups(&mut entities, 0, Position::new(100.0, 100.0));
+        assert!(events.is_empty());
+
+        let mut clans = HashMap::new();
+        let event = PlayerSystem::use_item(&mut entities, &mut clans, 0, ItemKind::BloodVial);
+        assert_eq!(event, ItemEvent::DrankVial { blood: 30.0 });
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 80.0);
+
+        // A full blood meter keeps the last vial
+        entities[0].blood_meter.as_mut().unwrap().current = 100.0;
+        let event = PlayerSystem::use_item(&mut entities, &mut clans, 0, ItemKind::BloodVial);
+        assert_eq!(event, ItemEvent::NotNeeded(ItemKind::BloodVial));
+        let inventory = entities[0].inventory.as_ref().unwrap();
+        assert_eq!(inventory.count(ItemKind::BloodVial), 1);
+
+        let event = PlayerSystem::use_item(&mut entities, &mut clans, 0, ItemKind::Bandage);
+        assert_eq!(event, ItemEvent::NoneLeft(ItemKind::Bandage));
+    }
+
+    #[test]
+    fn test_overflow_bottled_up_to_carry_limit() {
+        let mut player = create_test_player();
+        player.inventory = Some(Inventory::new(20));
+
+        // Overflow short of a vial waits in the meter
+        assert_eq!(PlayerSystem::bottle_overflow(&mut player, 20.0), 0);
+        assert_eq!(PlayerSystem::bottle_overflow(&mut player, 75.0), 3);
+        assert_eq!(player.blood_meter.as_ref().unwrap().bottling, 5.0);
+
+        // Past the vial limit the excess is lost
+        assert_eq!(PlayerSystem::bottle_overflow(&mut player, 200.0), 2);
+        let inventory = player.inventory.as_ref().unwrap();
+        assert_eq!(inventory.count(ItemKind::BloodVial), 5);
+        assert_eq!(
+            player.blood_meter.as_ref().unwrap().bottling,
+            ItemKind::VIAL_BLOOD
+        );
+    }
 }
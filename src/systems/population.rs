@@ -0,0 +1,307 @@
+//! Population System Module
+//!
+//! Keeps each clan's members present in the world. Every member on a clan's
+//! rolls apart from its leader is a clan member entity camped around the
+//! leader: members who fall in battle leave the rolls, members handed over
+//! as tribute or sent off as followers leave the camp, and clans at peace
+//! take in a new recruit each day. A daily census records each clan's size
+//! for the clan menu.
+
+use crate::components::*;
+use crate::systems::world::{WorldSystem, CAMP_RADIUS};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Days of population history kept for the clan menu
+pub const POPULATION_HISTORY_DAYS: usize = 7;
+
+/// Clans stop recruiting once they reach this size
+const MAX_CLAN_MEMBERS: u32 = 20;
+/// Clans more restless than this are in no state to take in recruits
+const RECRUIT_UNREST_LIMIT: f32 = 0.3;
+
+/// Which clan member entities stand for each clan's rolls
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClanRoster {
+    /// Clan of every member entity on the rolls, by entity ID
+    pub members: HashMap<u32, String>,
+    /// Day of the last census
+    pub last_census_day: u32,
+}
+
+impl ClanRoster {
+    /// Member entities on a clan's rolls
+    pub fn count(&self, clan_name: &str) -> usize {
+        self.members
+            .values()
+            .filter(|clan| *clan == clan_name)
+            .count()
+    }
+}
+
+/// Changes to clan numbers worth telling the player about
+#[derive(Debug, Clone, PartialEq)]
+pub enum PopulationEvent {
+    Casualty { clan_name: String, remaining: u32 },
+    Recruited { clan_name: String, total: u32 },
+}
+
+impl PopulationEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            PopulationEvent::Casualty {
+                clan_name,
+                remaining,
+            } => format!(
+                "The {} have lost one of their own. {} remain.",
+                clan_name, remaining
+            ),
+            PopulationEvent::Recruited { clan_name, total } => {
+                format!(
+                    "The {} took in a recruit and now number {}.",
+                    clan_name, total
+                )
+            }
+        }
+    }
+}
+
+/// Population system responsible for matching clan entities to clan rolls
+pub struct PopulationSystem;
+
+impl PopulationSystem {
+    /// Count losses, take the daily census, then spawn or send away members
+    /// until each clan's camp matches its rolls
+    pub fn update(
+        roster: &mut ClanRoster,
+        clans: &mut HashMap<String, Clan>,
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        current_day: u32,
+    ) -> Vec<PopulationEvent> {
+        let mut events = Self::count_losses(roster, clans, entities);
+
+        // Members spawned by other systems, like ambushers, join the rolls
+        for entity in entities.iter() {
+            if let EntityType::ClanMember(clan_name) = &entity.entity_type {
+                if Self::in_camp(entity) && !roster.members.contains_key(&entity.id) {
+                    roster.members.insert(entity.id, clan_name.clone());
+                }
+            }
+        }
+
+        if current_day > roster.last_census_day {
+            roster.last_census_day = current_day;
+            events.extend(Self::take_census(clans));
+        }
+
+        let mut clan_names: Vec<String> = clans.keys().cloned().collect();
+        clan_names.sort();
+        for clan_name in clan_names {
+            // The leader is counted among the members
+            let target = clans[&clan_name].member_count.saturating_sub(1) as usize;
+            let present = roster.count(&clan_name);
+            if present < target {
+                Self::spawn_members(roster, entities, entity_ids, &clan_name, target - present);
+            } else if present > target {
+                Self::send_away(roster, entities, &clan_name, present - target);
+            }
+        }
+
+        events
+    }
+
+    /// Take members who died or vanished off the rolls, and followers who
+    /// left when they were recruited
+    fn count_losses(
+        roster: &mut ClanRoster,
+        clans: &mut HashMap<String, Clan>,
+        entities: &[GameEntity],
+    ) -> Vec<PopulationEvent> {
+        let mut fallen = Vec::new();
+        // Looked up once per member, so index the entities rather than scan
+        let by_id: HashMap<u32, &GameEntity> =
+            entities.iter().map(|entity| (entity.id, entity)).collect();
+        roster.members.retain(|id, clan_name| {
+            match by_id.get(id) {
+                Some(entity) if Self::in_camp(entity) => true,
+                // Recruitment already took them off the clan's count
+                Some(entity) if matches!(entity.ai_state, AIState::Follower(_)) => false,
+                _ => {
+                    fallen.push(clan_name.clone());
+                    false
+                }
+            }
+        });
+
+        fallen.sort();
+        fallen
+            .into_iter()
+            .filter_map(|clan_name| {
+                let clan = clans.get_mut(&clan_name)?;
+                clan.member_count = clan.member_count.saturating_sub(1);
+                Some(PopulationEvent::Casualty {
+                    clan_name,
+                    remaining: clan.member_count,
+                })
+            })
+            .collect()
+    }
+
+    /// Let clans at peace grow and record every clan's size for the day
+    fn take_census(clans: &mut HashMap<String, Clan>) -> Vec<PopulationEvent> {
+        let mut events = Vec::new();
+        let mut clan_names: Vec<String> = clans.keys().cloned().collect();
+        clan_names.sort();
+        for clan_name in clan_names {
+            let Some(clan) = clans.get_mut(&clan_name) else {
+                continue;
+            };
+            let has_history = !clan.population_history.is_empty();
+            if has_history
+                && clan.unrest <= RECRUIT_UNREST_LIMIT
+                && clan.member_count < MAX_CLAN_MEMBERS
+            {
+                clan.member_count += 1;
+                events.push(PopulationEvent::Recruited {
+                    clan_name: clan_name.clone(),
+                    total: clan.member_count,
+                });
+            }
+
+            clan.population_history.push(clan.member_count);
+            let excess = clan
+                .population_history
+                .len()
+                .saturating_sub(POPULATION_HISTORY_DAYS);
+            clan.population_history.drain(..excess);
+        }
+        events
+    }
+
+    /// Spawn members around the clan's leader; a clan without a living
+    /// leader has no camp to return to
+    fn spawn_members(
+        roster: &mut ClanRoster,
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        clan_name: &str,
+        count: usize,
+    ) {
+        let Some(camp) = entities
+            .iter()
+            .find(|entity| {
+                matches!(&entity.entity_type, EntityType::ClanLeader(name) if name == clan_name)
+                    && entity.health.as_ref().is_some_and(Health::is_alive)
+            })
+            .map(|leader| leader.position)
+        else {
+            return;
+        };
+
+        let color = WorldSystem::clan_member_color(clan_name);
+        for _ in 0..count {
+            let angle = macroquad::rand::gen_range(0.0, std::f32::consts::TAU);
+            let distance = macroquad::rand::gen_range(30.0, CAMP_RADIUS);
+            let id = WorldSystem::spawn_clan_member(
+                entities,
+                entity_ids,
+                clan_name,
+                camp.x + angle.cos() * distance,
+                (camp.y + angle.sin() * distance).max(650.0),
+                color,
+            );
+            roster.members.insert(id, clan_name.to_string());
+        }
+    }
+
+    /// Remove members the clan no longer has, calm ones before hostile or
+    /// marching ones and the newest first
+    fn send_away(
+        roster: &mut ClanRoster,
+        entities: &mut Vec<GameEntity>,
+        clan_name: &str,
+        count: usize,
+    ) {
+        let mut candidates: Vec<(bool, u32)> = entities
+            .iter()
+            .filter(|entity| {
+                roster
+                    .members
+                    .get(&entity.id)
+                    .is_some_and(|c| c == clan_name)
+            })
+            .map(|entity| {
+                let busy = matches!(entity.ai_state, AIState::Hostile | AIState::Squad);
+                (busy, entity.id)
+            })
+            .collect();
+        candidates.sort_by_key(|&(busy, id)| (busy, std::cmp::Reverse(id)));
+
+        let leaving: Vec<u32> = candidates
+            .into_iter()
+            .take(count)
+            .map(|(_, id)| id)
+            .collect();
+        for id in &leaving {
+            roster.members.remove(id);
+        }
+        entities.retain(|entity| !leaving.contains(&entity.id));
+    }
+
+    /// Whether a clan member is alive and with their clan rather than
+    /// following the player
+    fn in_camp(entity: &GameEntity) -> bool {
+        matches!(entity.entity_type, EntityType::ClanMember(_))
+            && !matches!(entity.ai_state, AIState::Follower(_))
+            && entity.health.as_ref().is_some_and(Health::is_alive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_members_follow_clan_rolls() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let mut clans = HashMap::new();
+        WorldSystem::initialize_clans(&mut clans);
+        WorldSystem::spawn_all_clan_leaders(&mut entities, &mut ids);
+        let mut roster = ClanRoster::default();
+
+        PopulationSystem::update(&mut roster, &mut clans, &mut entities, &mut ids, 1);
+        let members_of = |entities: &[GameEntity], clan: &str| {
+            entities
+                .iter()
+                .filter(|e| matches!(&e.entity_type, EntityType::ClanMember(name) if name == clan))
+                .count()
+        };
+        assert_eq!(members_of(&entities, "Night-Bloods"), 9);
+        assert_eq!(clans["Night-Bloods"].population_history, vec![10]);
+
+        // A member falls in battle, and tribute takes two more
+        let fallen = entities
+            .iter_mut()
+            .find(|e| matches!(&e.entity_type, EntityType::ClanMember(name) if name == "Night-Bloods"))
+            .unwrap();
+        fallen.health.as_mut().unwrap().current = 0.0;
+        fallen.ai_state = AIState::Dead;
+        clans.get_mut("Night-Bloods").unwrap().member_count -= 2;
+        let events = PopulationSystem::update(&mut roster, &mut clans, &mut entities, &mut ids, 1);
+        assert_eq!(
+            events,
+            vec![PopulationEvent::Casualty {
+                clan_name: "Night-Bloods".into(),
+                remaining: 7,
+            }]
+        );
+        assert_eq!(roster.count("Night-Bloods"), 6);
+
+        // The next day a calm clan takes in a recruit, who joins the camp
+        PopulationSystem::update(&mut roster, &mut clans, &mut entities, &mut ids, 2);
+        assert_eq!(clans["Night-Bloods"].population_history, vec![10, 8]);
+        assert_eq!(roster.count("Night-Bloods"), 7);
+    }
+}
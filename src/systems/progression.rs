@@ -0,0 +1,186 @@
+//! Progression System Module
+//!
+//! Awards the player experience for kills, feedings and completed
+//! objectives as they come off the event bus, and spends skill points in
+//! the skill tree (see `Progression`).
+
+use crate::components::*;
+use crate::systems::events::GameEvent;
+
+/// Progression system responsible for experience and learning skills
+pub struct ProgressionSystem;
+
+impl ProgressionSystem {
+    /// Experience the player earned from this frame's events
+    pub fn experience_from(events: &[GameEvent], player_id: u32) -> u32 {
+        events
+            .iter()
+            .map(|event| match event {
+                GameEvent::EntityDied { killer_id, .. } if *killer_id == player_id => KILL_XP,
+                GameEvent::FedOnTarget { feeder_id, .. } if *feeder_id == player_id => FEED_XP,
+                GameEvent::ObjectiveCompleted { .. } => OBJECTIVE_XP,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Award the player experience for this frame's events. Returns the
+    /// new level if they climbed one.
+    pub fn record_events(
+        events: &[GameEvent],
+        entities: &mut [GameEntity],
+        player_id: u32,
+    ) -> Option<u32> {
+        let xp = Self::experience_from(events, player_id);
+        if xp == 0 {
+            return None;
+        }
+        let progression = entities
+            .iter_mut()
+            .find(|entity| entity.id == player_id)?
+            .progression
+            .as_mut()?;
+        (progression.gain_xp(xp) > 0).then_some(progression.level)
+    }
+
+    /// Spend one of the player's skill points on a skill
+    pub fn learn(entities: &mut [GameEntity], player_id: u32, skill: Skill) -> SkillEvent {
+        let Some(progression) = entities
+            .iter_mut()
+            .find(|entity| entity.id == player_id)
+            .and_then(|player| player.progression.as_mut())
+        else {
+            return SkillEvent::Refused(skill, LearnError::NoSkillPoints);
+        };
+        match progression.learn(skill) {
+            Ok(()) => SkillEvent::Learned(skill),
+            Err(error) => SkillEvent::Refused(skill, error),
+        }
+    }
+}
+
+/// What came of trying to learn a skill
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkillEvent {
+    Learned(Skill),
+    Refused(Skill, LearnError),
+}
+
+impl SkillEvent {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            SkillEvent::Learned(skill) => match skill.unlocks() {
+                Some(ability) => format!(
+                    "You learn {}. {} is yours to use.",
+                    skill.display_name(),
+                    ability.display_name()
+                ),
+                None => format!("You learn {}.", skill.display_name()),
+            },
+            SkillEvent::Refused(skill, LearnError::AlreadyKnown) => {
+                format!("You already know {}.", skill.display_name())
+            }
+            SkillEvent::Refused(skill, LearnError::NoSkillPoints) => {
+                format!("You need a skill point to learn {}.", skill.display_name())
+            }
+            SkillEvent::Refused(skill, LearnError::MissingPrerequisite(required)) => format!(
+                "Learn {} before {}.",
+                required.display_name(),
+                skill.display_name()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{AbilityResult, AbilitySystem, WorldSystem};
+
+    #[test]
+    fn test_experience_levels_up_and_skills_unlock_abilities() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].blood_meter.as_mut().unwrap().current = 100.0;
+
+        // Abilities beyond the first two wait on the skill tree
+        assert!(matches!(
+            AbilitySystem::activate(
+                &mut entities,
+                player_id,
+                &WorldBounds::default(),
+                SpecialAbility::BatForm,
+                (0.0, 0.0)
+            ),
+            AbilityResult::Unlearned {
+                skill: Skill::WingsOfNight,
+                ..
+            }
+        ));
+
+        // Four kills and a feeding reach level 2, one point short of the
+        // skill that needs a root skill learned first
+        let died = GameEvent::EntityDied {
+            entity_id: 9,
+            killer_id: player_id,
+            position: Position::new(0.0, 0.0),
+        };
+        let fed = GameEvent::FedOnTarget {
+            feeder_id: player_id,
+            target_id: 9,
+            position: Position::new(0.0, 0.0),
+            lethal: true,
+        };
+        let events = vec![died.clone(), died.clone(), died.clone(), died, fed];
+        assert_eq!(
+            ProgressionSystem::record_events(&events, &mut entities, player_id),
+            Some(2)
+        );
+        assert_eq!(
+            ProgressionSystem::learn(&mut entities, player_id, Skill::WingsOfNight),
+            SkillEvent::Refused(
+                Skill::WingsOfNight,
+                LearnError::MissingPrerequisite(Skill::Fleetness)
+            )
+        );
+        assert_eq!(
+            ProgressionSystem::learn(&mut entities, player_id, Skill::Fleetness),
+            SkillEvent::Learned(Skill::Fleetness)
+        );
+        assert_eq!(
+            ProgressionSystem::learn(&mut entities, player_id, Skill::WingsOfNight),
+            SkillEvent::Refused(Skill::WingsOfNight, LearnError::NoSkillPoints)
+        );
+        let progression = entities[0].progression.as_ref().unwrap();
+        assert_eq!(progression.xp, 15);
+        assert!((progression.multiplier(BuffKind::Speed) - 1.1).abs() < 1e-6);
+
+        // Two objectives are worth another level, and its point buys Bat Form
+        let objective = GameEvent::ObjectiveCompleted {
+            objective: "Survive".to_string(),
+        };
+        assert_eq!(
+            ProgressionSystem::record_events(
+                &[objective.clone(), objective],
+                &mut entities,
+                player_id
+            ),
+            Some(3)
+        );
+        ProgressionSystem::learn(&mut entities, player_id, Skill::WingsOfNight);
+        assert_eq!(
+            AbilitySystem::activate(
+                &mut entities,
+                player_id,
+                &WorldBounds::default(),
+                SpecialAbility::BatForm,
+                (0.0, 0.0)
+            ),
+            AbilityResult::Activated {
+                ability: SpecialAbility::BatForm
+            }
+        );
+    }
+}
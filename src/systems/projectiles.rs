@@ -0,0 +1,210 @@
+//! Projectiles System Module
+//!
+//! Flies missiles across the world. A projectile travels in a straight line
+//! until it strikes the first enemy in its path, hits a wall, or runs out of
+//! range; hits go through the combat system like any blow. The player's
+//! Blood Lance is the only projectile so far.
+
+use crate::components::*;
+use crate::systems::combat::{CombatSystem, DamageEvent};
+use crate::systems::spatial::SpatialGrid;
+use crate::systems::stealth::SightBlocker;
+
+/// Blood Lance damage before the thrower's strength
+pub const LANCE_DAMAGE: f32 = 30.0;
+
+/// How far a Blood Lance flies before it falls apart
+pub const LANCE_RANGE: f32 = 400.0;
+
+/// Blood Lance flight speed in world units per second
+const LANCE_SPEED: f32 = 520.0;
+
+/// How close a projectile must pass to a creature to strike it
+const HIT_RADIUS: f32 = 16.0;
+
+/// Projectile system responsible for missiles in flight
+pub struct ProjectileSystem;
+
+impl ProjectileSystem {
+    /// Throw a Blood Lance from the owner toward `direction`, with damage
+    /// scaled by the owner's strength and any strength buff. Returns
+    /// whether it was thrown.
+    pub fn fire_lance(
+        projectiles: &mut Vec<Projectile>,
+        entities: &[GameEntity],
+        owner_id: u32,
+        direction: (f32, f32),
+    ) -> bool {
+        let Some(owner) = EntityFinder::by_id(entities, owner_id) else {
+            return false;
+        };
+        let length = (direction.0 * direction.0 + direction.1 * direction.1).sqrt();
+        if length <= f32::EPSILON {
+            return false;
+        }
+        let strength = owner.vampire_abilities.as_ref().map_or(1.0, |abilities| {
+            abilities.strength * abilities.ability_state.power()
+        }) * owner
+            .buffs
+            .as_ref()
+            .map_or(1.0, |buffs| buffs.multiplier(BuffKind::Strength))
+            * owner.progression.as_ref().map_or(1.0, |progression| {
+                progression.multiplier(BuffKind::Strength)
+            });
+        projectiles.push(Projectile {
+            owner_id,
+            position: owner.position,
+            direction: (direction.0 / length, direction.1 / length),
+            speed: LANCE_SPEED,
+            damage: LANCE_DAMAGE * strength,
+            range_left: LANCE_RANGE,
+        });
+        true
+    }
+
+    /// Move every projectile along its path. One that passes close enough
+    /// to an enemy strikes the nearest along the way and is spent, as is one
+    /// that meets a wall or flies its full range.
+    pub fn update(
+        projectiles: &mut Vec<Projectile>,
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        blockers: &[SightBlocker],
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+        delta_time: f32,
+    ) {
+        projectiles.retain_mut(|projectile| {
+            let step = (projectile.speed * delta_time).min(projectile.range_left);
+            let start = projectile.position;
+            let end = Position::new(
+                start.x + projectile.direction.0 * step,
+                start.y + projectile.direction.1 * step,
+            );
+
+            let target = grid
+                .query_radius(entities, start, step + HIT_RADIUS)
+                .into_iter()
+                .map(|index| &entities[index])
+                .filter(|entity| entity.id != projectile.owner_id && Self::is_enemy(entity))
+                .filter_map(|entity| {
+                    let along =
+                        Self::distance_along(&start, projectile.direction, &entity.position);
+                    let passed_by = (0.0..=step).contains(&along)
+                        && Self::distance_to_segment(&start, &end, &entity.position) <= HIT_RADIUS;
+                    passed_by.then_some((entity.id, along))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((target_id, _)) = target {
+                CombatSystem::resolve_projectile_hit(
+                    entities,
+                    projectile.owner_id,
+                    target_id,
+                    projectile.damage,
+                    game_time,
+                    damage_events,
+                );
+                return false;
+            }
+
+            if blockers.iter().any(|blocker| blocker.blocks(&start, &end)) {
+                return false;
+            }
+            projectile.position = end;
+            projectile.range_left -= step;
+            projectile.range_left > 0.0
+        });
+    }
+
+    /// Whether a projectile strikes this creature: infected, animals, and
+    /// clan vampires that have turned on the player
+    pub fn is_enemy(entity: &GameEntity) -> bool {
+        let alive = entity.health.as_ref().is_some_and(|h| h.is_alive())
+            && !matches!(entity.ai_state, AIState::Dead);
+        alive
+            && match entity.entity_type {
+                EntityType::HostileInfected | EntityType::Animal => true,
+                EntityType::ClanMember(_) | EntityType::ClanLeader(_) => {
+                    matches!(entity.ai_state, AIState::Hostile)
+                }
+                EntityType::Player | EntityType::Shelter | EntityType::Pickup => false,
+            }
+    }
+
+    /// How far along the path a point lies, measured from `start`
+    fn distance_along(start: &Position, direction: (f32, f32), point: &Position) -> f32 {
+        (point.x - start.x) * direction.0 + (point.y - start.y) * direction.1
+    }
+
+    fn distance_to_segment(start: &Position, end: &Position, point: &Position) -> f32 {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let length_squared = dx * dx + dy * dy;
+        let t = if length_squared > 0.0 {
+            (((point.x - start.x) * dx + (point.y - start.y) * dy) / length_squared).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (x, y) = (start.x + dx * t, start.y + dy * t);
+        ((point.x - x).powi(2) + (point.y - y).powi(2)).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+
+    #[test]
+    fn test_lance_flies_past_neutrals_and_strikes_the_first_enemy() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let (x, y) = (entities[0].position.x, entities[0].position.y);
+        let bystander = WorldSystem::spawn_clan_member(
+            &mut entities,
+            &mut ids,
+            "Night-Bloods",
+            x + 60.0,
+            y,
+            macroquad::prelude::RED,
+        );
+        let near = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, x + 160.0, y);
+        let far = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, x + 200.0, y);
+        let grid = SpatialGrid::from_entities(&entities);
+
+        let mut projectiles = Vec::new();
+        assert!(!ProjectileSystem::fire_lance(
+            &mut projectiles,
+            &entities,
+            player_id,
+            (0.0, 0.0)
+        ));
+        assert!(ProjectileSystem::fire_lance(
+            &mut projectiles,
+            &entities,
+            player_id,
+            (2.0, 0.0)
+        ));
+        assert_eq!(projectiles[0].direction, (1.0, 0.0));
+
+        let mut damage_events = Vec::new();
+        for _ in 0..60 {
+            ProjectileSystem::update(
+                &mut projectiles,
+                &mut entities,
+                &grid,
+                &[],
+                0.0,
+                &mut damage_events,
+                1.0 / 60.0,
+            );
+        }
+        assert!(projectiles.is_empty());
+        assert_eq!(damage_events.len(), 1);
+        assert_eq!(damage_events[0].attacker_id, player_id);
+        assert_eq!(damage_events[0].target_id, near);
+        assert!(damage_events
+            .iter()
+            .all(|event| event.target_id != bystander && event.target_id != far));
+    }
+}
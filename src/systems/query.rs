@@ -0,0 +1,422 @@
+//! World Query Module
+//!
+//! One place to ask "what is here?" of the world. `raycast` walks a line
+//! segment and reports every entity, shelter and ground tile it crosses,
+//! nearest first; `query_area` reports everything inside a circle or
+//! rectangle. Entity lookups go through the `SpatialGrid`, so callers no
+//! longer need their own distance loops over every entity.
+
+use crate::components::*;
+use crate::systems::spatial::SpatialGrid;
+
+/// Largest entity radius, used to widen grid lookups so edge overlaps are found
+const MAX_ENTITY_RADIUS: f32 = 15.0;
+
+/// World query helpers shared by gameplay, AI and rendering
+pub struct WorldQuery;
+
+impl WorldQuery {
+    /// Collision radius of an entity; bat form shrinks the player
+    pub fn entity_radius(entity: &GameEntity) -> f32 {
+        let radius = match entity.entity_type {
+            EntityType::Player => 15.0,
+            EntityType::ClanLeader(_) => 14.0,
+            EntityType::ClanMember(_) => 12.0,
+            EntityType::HostileInfected => 10.0,
+            EntityType::Animal => 8.0,
+            EntityType::Shelter | EntityType::Pickup => 0.0,
+        };
+        radius
+            * entity
+                .vampire_abilities
+                .as_ref()
+                .map_or(1.0, |abilities| abilities.ability_state.hitbox_scale())
+    }
+
+    /// Everything crossed by the segment from `from` to `to`, nearest first.
+    /// Dead entities are skipped.
+    pub fn raycast(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        tiles: &[GroundTile],
+        from: Position,
+        to: Position,
+    ) -> Vec<RaycastHit> {
+        let length = from.distance_to(&to);
+        let midpoint = Position::new((from.x + to.x) / 2.0, (from.y + to.y) / 2.0);
+        let mut hits = Vec::new();
+
+        // Candidates lie within the circle around the segment
+        let reach = length / 2.0 + Self::max_shelter_reach();
+        for index in grid.query_radius(entities, midpoint, reach) {
+            let entity = &entities[index];
+            if matches!(entity.ai_state, AIState::Dead) {
+                continue;
+            }
+
+            let entry = match &entity.shelter {
+                Some(shelter) => {
+                    let (width, height) = shelter.shelter_type.visual_size();
+                    Self::segment_rect_entry(
+                        from,
+                        to,
+                        WorldRect::centered(entity.position, width, height),
+                    )
+                }
+                None => Self::segment_circle_entry(
+                    from,
+                    to,
+                    entity.position,
+                    Self::entity_radius(entity),
+                ),
+            };
+
+            if let Some(t) = entry {
+                let target = if entity.shelter.is_some() {
+                    WorldTarget::Shelter {
+                        index,
+                        id: entity.id,
+                    }
+                } else {
+                    WorldTarget::Entity {
+                        index,
+                        id: entity.id,
+                    }
+                };
+                hits.push(RaycastHit::new(target, from, to, t));
+            }
+        }
+
+        for (index, tile) in tiles.iter().enumerate() {
+            let rect = WorldRect::new(tile.x, tile.y, GroundTile::SIZE, GroundTile::SIZE);
+            if let Some(t) = Self::segment_rect_entry(from, to, rect) {
+                hits.push(RaycastHit::new(WorldTarget::Tile { index }, from, to, t));
+            }
+        }
+
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Everything inside a shape. Entities and shelters are matched by their
+    /// centre point; tiles by overlap.
+    pub fn query_area(
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        tiles: &[GroundTile],
+        shape: QueryShape,
+    ) -> AreaQuery {
+        let mut result = AreaQuery::default();
+
+        let (center, radius) = shape.bounding_circle();
+        for index in grid.query_radius(entities, center, radius) {
+            let entity = &entities[index];
+            if !shape.contains(entity.position) {
+                continue;
+            }
+            if entity.shelter.is_some() {
+                result.shelters.push(index);
+            } else {
+                result.entities.push(index);
+            }
+        }
+
+        let bounds = shape.bounds();
+        result.tiles = tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| {
+                bounds.overlaps(&WorldRect::new(
+                    tile.x,
+                    tile.y,
+                    GroundTile::SIZE,
+                    GroundTile::SIZE,
+                ))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        result
+    }
+
+    /// Half-diagonal of the largest shelter, so rays catch shelters whose
+    /// centre is outside the segment's circle but whose walls are inside it
+    fn max_shelter_reach() -> f32 {
+        let (width, height) = ShelterType::BridgeUnderpass.visual_size();
+        ((width * width + height * height).sqrt() / 2.0).max(MAX_ENTITY_RADIUS)
+    }
+
+    /// Fraction along the segment where it first touches a circle
+    fn segment_circle_entry(
+        from: Position,
+        to: Position,
+        center: Position,
+        radius: f32,
+    ) -> Option<f32> {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let (fx, fy) = (from.x - center.x, from.y - center.y);
+
+        let c = fx * fx + fy * fy - radius * radius;
+        if c <= 0.0 {
+            // Starts inside the circle
+            return Some(0.0);
+        }
+
+        let a = dx * dx + dy * dy;
+        if a == 0.0 {
+            return None;
+        }
+        let b = 2.0 * (fx * dx + fy * dy);
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        let t = (-b - discriminant.sqrt()) / (2.0 * a);
+        (0.0..=1.0).contains(&t).then_some(t)
+    }
+
+    /// Fraction along the segment where it first enters a rectangle (slab test)
+    fn segment_rect_entry(from: Position, to: Position, rect: WorldRect) -> Option<f32> {
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let mut t_min: f32 = 0.0;
+        let mut t_max: f32 = 1.0;
+
+        for (start, delta, low, high) in [
+            (from.x, dx, rect.x, rect.x + rect.w),
+            (from.y, dy, rect.y, rect.y + rect.h),
+        ] {
+            if delta.abs() < f32::EPSILON {
+                if start < low || start > high {
+                    return None;
+                }
+            } else {
+                let t1 = (low - start) / delta;
+                let t2 = (high - start) / delta;
+                t_min = t_min.max(t1.min(t2));
+                t_max = t_max.min(t1.max(t2));
+                if t_min > t_max {
+                    return None;
+                }
+            }
+        }
+
+        Some(t_min)
+    }
+}
+
+/// Axis-aligned rectangle in world units (top-left corner and size)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldRect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl WorldRect {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    pub fn centered(center: Position, w: f32, h: f32) -> Self {
+        Self::new(center.x - w / 2.0, center.y - h / 2.0, w, h)
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x >= self.x
+            && position.x <= self.x + self.w
+            && position.y >= self.y
+            && position.y <= self.y + self.h
+    }
+
+    pub fn overlaps(&self, other: &WorldRect) -> bool {
+        self.x < other.x + other.w
+            && other.x < self.x + self.w
+            && self.y < other.y + other.h
+            && other.y < self.y + self.h
+    }
+}
+
+/// Area to search in `query_area`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryShape {
+    Circle { center: Position, radius: f32 },
+    Rect(WorldRect),
+}
+
+impl QueryShape {
+    pub fn circle(center: Position, radius: f32) -> Self {
+        QueryShape::Circle { center, radius }
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        match self {
+            QueryShape::Circle { center, radius } => position.distance_to(center) <= *radius,
+            QueryShape::Rect(rect) => rect.contains(position),
+        }
+    }
+
+    fn bounding_circle(&self) -> (Position, f32) {
+        match self {
+            QueryShape::Circle { center, radius } => (*center, *radius),
+            QueryShape::Rect(rect) => (
+                Position::new(rect.x + rect.w / 2.0, rect.y + rect.h / 2.0),
+                (rect.w * rect.w + rect.h * rect.h).sqrt() / 2.0,
+            ),
+        }
+    }
+
+    fn bounds(&self) -> WorldRect {
+        match self {
+            QueryShape::Circle { center, radius } => {
+                WorldRect::centered(*center, radius * 2.0, radius * 2.0)
+            }
+            QueryShape::Rect(rect) => *rect,
+        }
+    }
+}
+
+/// Something in the world a query can find
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldTarget {
+    /// A creature; `index` is its position in the entity list
+    Entity {
+        index: usize,
+        id: u32,
+    },
+    Shelter {
+        index: usize,
+        id: u32,
+    },
+    /// A ground tile; `index` is its position in the tile list
+    Tile {
+        index: usize,
+    },
+}
+
+/// One thing crossed by a ray
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastHit {
+    pub target: WorldTarget,
+    /// Where the ray first touches the target
+    pub point: Position,
+    /// Distance from the ray's start to `point`
+    pub distance: f32,
+}
+
+impl RaycastHit {
+    fn new(target: WorldTarget, from: Position, to: Position, t: f32) -> Self {
+        let point = Position::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+        Self {
+            target,
+            point,
+            distance: from.distance_to(&point),
+        }
+    }
+}
+
+/// Everything found inside a query area, as indices into the entity and tile lists
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AreaQuery {
+    pub entities: Vec<usize>,
+    pub shelters: Vec<usize>,
+    pub tiles: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::prelude::*;
+
+    fn create_entity(id: u32, x: f32, y: f32, entity_type: EntityType) -> GameEntity {
+        GameEntity {
+            id,
+            position: Position::new(x, y),
+            velocity: None,
+            entity_type,
+            health: Some(Health::new(50.0)),
+            combat_stats: None,
+            ai_state: AIState::Idle,
+            blood_meter: None,
+            vampire_abilities: None,
+            shelter: None,
+            shelter_occupancy: None,
+            color: WHITE,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        }
+    }
+
+    #[test]
+    fn test_raycast_orders_hits_by_distance() {
+        let mut shelter = create_entity(3, 500.0, 700.0, EntityType::Shelter);
+        shelter.shelter = Some(Shelter::new(ShelterType::Building));
+        let entities = vec![
+            create_entity(1, 300.0, 700.0, EntityType::Animal),
+            create_entity(2, 200.0, 705.0, EntityType::HostileInfected),
+            shelter,
+            create_entity(4, 300.0, 800.0, EntityType::Animal),
+        ];
+        let grid = SpatialGrid::from_entities(&entities);
+
+        let hits = WorldQuery::raycast(
+            &entities,
+            &grid,
+            &[],
+            Position::new(100.0, 700.0),
+            Position::new(700.0, 700.0),
+        );
+        let ids: Vec<WorldTarget> = hits.iter().map(|hit| hit.target).collect();
+
+        assert_eq!(
+            ids,
+            vec![
+                WorldTarget::Entity { index: 1, id: 2 },
+                WorldTarget::Entity { index: 0, id: 1 },
+                WorldTarget::Shelter { index: 2, id: 3 },
+            ]
+        );
+        // The building's west wall is 40 units left of its centre
+        assert!((hits[2].point.x - 460.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_query_area_circle_and_rect() {
+        let entities = vec![
+            create_entity(1, 100.0, 700.0, EntityType::Animal),
+            create_entity(2, 180.0, 700.0, EntityType::Animal),
+        ];
+        let grid = SpatialGrid::from_entities(&entities);
+        let tiles = vec![
+            GroundTile::new(64.0, 640.0, TileType::Grass),
+            GroundTile::new(640.0, 640.0, TileType::Stone),
+        ];
+
+        let found = WorldQuery::query_area(
+            &entities,
+            &grid,
+            &tiles,
+            QueryShape::circle(Position::new(100.0, 700.0), 50.0),
+        );
+        assert_eq!(found.entities, vec![0]);
+        assert_eq!(found.tiles, vec![0]);
+
+        let found = WorldQuery::query_area(
+            &entities,
+            &grid,
+            &tiles,
+            QueryShape::Rect(WorldRect::new(150.0, 650.0, 100.0, 100.0)),
+        );
+        assert_eq!(found.entities, vec![1]);
+    }
+}
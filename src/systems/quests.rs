@@ -0,0 +1,502 @@
+//! Quests System Module
+//!
+//! Tasks clan leaders hand out to a player they do not count as an enemy.
+//! Asking a leader for work takes on that clan's next quest: clearing
+//! infected from around a clan's ground, or carrying a gift to another
+//! clan's leader. Kills are counted as they come off the event bus and a
+//! delivery is made by speaking to its recipient with the item in hand.
+//! Finished quests pay out in trust, items or a territory the clan cedes
+//! to the player, and every quest taken or done is kept in the journal.
+
+use crate::components::*;
+use crate::systems::events::GameEvent;
+use crate::world_data::WorldData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How far from a clan's camp kills count towards clearing its ground
+const QUEST_AREA_RADIUS: f32 = 350.0;
+
+/// What must be done to finish a quest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QuestGoal {
+    /// Kill infected near a named place
+    Slay {
+        count: u32,
+        place: String,
+        center: Position,
+    },
+    /// Bring an item to another clan's leader
+    Deliver { item: ItemKind, to_clan: String },
+}
+
+/// What a finished quest pays
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QuestReward {
+    /// The giving clan's trust in the player rises
+    Trust(f32),
+    Item(ItemKind, u32),
+    /// The giving clan cedes a territory it holds sway over
+    Territory,
+}
+
+impl QuestReward {
+    pub fn describe(&self) -> String {
+        match self {
+            QuestReward::Trust(amount) => format!("+{:.0}% trust", amount * 100.0),
+            QuestReward::Item(item, 1) => item.display_name().to_string(),
+            QuestReward::Item(item, quantity) => {
+                format!("{} x{}", item.display_name(), quantity)
+            }
+            QuestReward::Territory => "a territory".to_string(),
+        }
+    }
+}
+
+/// A task taken on from a clan leader
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quest {
+    /// Clan whose leader gave the quest
+    pub clan: String,
+    pub goal: QuestGoal,
+    pub reward: QuestReward,
+    /// Kills made towards a slaying quest
+    pub progress: u32,
+}
+
+impl Quest {
+    /// One-line summary for the journal
+    pub fn description(&self) -> String {
+        match &self.goal {
+            QuestGoal::Slay { count, place, .. } => {
+                format!("Kill {} infected near {} ({})", count, place, self.clan)
+            }
+            QuestGoal::Deliver { item, to_clan } => {
+                let leader = WorldData::current()
+                    .clan(to_clan)
+                    .map_or(to_clan.as_str(), |clan| clan.leader.as_str());
+                format!(
+                    "Deliver a {} to {} ({})",
+                    item.display_name(),
+                    leader,
+                    self.clan
+                )
+            }
+        }
+    }
+
+    /// How far along the quest is (0.0 to 1.0)
+    pub fn fraction(&self) -> f32 {
+        match &self.goal {
+            QuestGoal::Slay { count, .. } => self.progress as f32 / (*count).max(1) as f32,
+            QuestGoal::Deliver { .. } => 0.0,
+        }
+    }
+}
+
+/// Quests under way and those already done
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestLog {
+    pub active: Vec<Quest>,
+    /// Finished quests with the day each was done, oldest first
+    pub completed: Vec<(String, u32)>,
+    /// Quests finished for each clan, which decides what it asks next
+    pub done_for: HashMap<String, u32>,
+}
+
+impl QuestLog {
+    pub fn active_for(&self, clan_name: &str) -> Option<&Quest> {
+        self.active.iter().find(|quest| quest.clan == clan_name)
+    }
+}
+
+/// Something that happened with a quest
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuestEvent {
+    Offered {
+        leader: String,
+        description: String,
+    },
+    /// The leader's quest is still waiting to be done
+    Pending {
+        leader: String,
+        description: String,
+    },
+    /// The leader has no work for an enemy or a clan brought to heel
+    Refused {
+        leader: String,
+    },
+    Progress {
+        description: String,
+        done: u32,
+        of: u32,
+    },
+    Completed {
+        description: String,
+        reward: String,
+    },
+}
+
+impl QuestEvent {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            QuestEvent::Offered {
+                leader,
+                description,
+            } => format!("{} asks a task of you: {}.", leader, description),
+            QuestEvent::Pending {
+                leader,
+                description,
+            } => format!("{} is still waiting on you: {}.", leader, description),
+            QuestEvent::Refused { leader } => {
+                format!("{} has no work for the likes of you.", leader)
+            }
+            QuestEvent::Progress {
+                description,
+                done,
+                of,
+            } => format!("{}: {}/{}", description, done, of),
+            QuestEvent::Completed {
+                description,
+                reward,
+            } => format!("Quest done: {}. Reward: {}.", description, reward),
+        }
+    }
+}
+
+/// Quests system responsible for clan leaders' tasks
+pub struct QuestSystem;
+
+impl QuestSystem {
+    /// Ask a clan's leader for work. A leader whose quest is still open
+    /// reminds the player of it instead.
+    pub fn request(
+        log: &mut QuestLog,
+        clans: &HashMap<String, Clan>,
+        clan_name: &str,
+    ) -> QuestEvent {
+        let Some(clan) = clans.get(clan_name) else {
+            return QuestEvent::Refused {
+                leader: clan_name.to_string(),
+            };
+        };
+        let leader = clan.leader_name.clone();
+        if clan.is_hostile || clan.is_defeated {
+            return QuestEvent::Refused { leader };
+        }
+        if let Some(quest) = log.active_for(clan_name) {
+            return QuestEvent::Pending {
+                leader,
+                description: quest.description(),
+            };
+        }
+        let done = log.done_for.get(clan_name).copied().unwrap_or(0);
+        let quest = Self::next_quest(clan_name, clans, done);
+        let description = quest.description();
+        log.active.push(quest);
+        QuestEvent::Offered {
+            leader,
+            description,
+        }
+    }
+
+    /// The quest a clan gives after `done` of its quests have been finished:
+    /// clearing its own ground, a gift for a neighbour, then a bigger
+    /// clearing for a territory, round again
+    fn next_quest(clan_name: &str, clans: &HashMap<String, Clan>, done: u32) -> Quest {
+        let world = WorldData::current();
+        let clan = world.clan(clan_name);
+        let place = clan
+            .and_then(|clan| clan.territory.clone())
+            .unwrap_or_else(|| clan_name.to_string());
+        let center = clan.map_or(Position::new(0.0, 0.0), |clan| {
+            Position::new(clan.camp.0, clan.camp.1)
+        });
+        let mut others: Vec<&String> = clans
+            .keys()
+            .filter(|name| name.as_str() != clan_name)
+            .collect();
+        others.sort();
+        let neighbour = others.get(done as usize % others.len().max(1)).cloned();
+
+        let (goal, reward) = match (done % 3, neighbour) {
+            (1, Some(to_clan)) => (
+                QuestGoal::Deliver {
+                    item: ItemKind::BloodVial,
+                    to_clan: to_clan.clone(),
+                },
+                QuestReward::Item(ItemKind::Trinket, 2),
+            ),
+            (2, _) => (
+                QuestGoal::Slay {
+                    count: 8,
+                    place,
+                    center,
+                },
+                QuestReward::Territory,
+            ),
+            _ => (
+                QuestGoal::Slay {
+                    count: 5,
+                    place,
+                    center,
+                },
+                QuestReward::Trust(0.2),
+            ),
+        };
+        Quest {
+            clan: clan_name.to_string(),
+            goal,
+            reward,
+            progress: 0,
+        }
+    }
+
+    /// Count the player's kills of infected towards slaying quests
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_events(
+        log: &mut QuestLog,
+        events: &[GameEvent],
+        entities: &mut [GameEntity],
+        clans: &mut HashMap<String, Clan>,
+        territories: &mut TerritoryManager,
+        player_id: u32,
+        day: u32,
+    ) -> Vec<QuestEvent> {
+        let mut quest_events = Vec::new();
+        for event in events {
+            let GameEvent::EntityDied {
+                entity_id,
+                killer_id,
+                position,
+            } = event
+            else {
+                continue;
+            };
+            let infected = EntityFinder::by_id(entities, *entity_id)
+                .is_some_and(|victim| victim.entity_type == EntityType::HostileInfected);
+            if *killer_id != player_id || !infected {
+                continue;
+            }
+            for quest in &mut log.active {
+                let QuestGoal::Slay { count, center, .. } = &quest.goal else {
+                    continue;
+                };
+                if quest.progress < *count && center.distance_to(position) <= QUEST_AREA_RADIUS {
+                    quest.progress += 1;
+                    quest_events.push(QuestEvent::Progress {
+                        description: quest.description(),
+                        done: quest.progress,
+                        of: *count,
+                    });
+                }
+            }
+        }
+
+        let finished: Vec<usize> = (0..log.active.len())
+            .filter(|&index| {
+                let quest = &log.active[index];
+                matches!(quest.goal, QuestGoal::Slay { count, .. } if quest.progress >= count)
+            })
+            .collect();
+        for index in finished.into_iter().rev() {
+            let quest = log.active.remove(index);
+            quest_events.push(Self::complete(
+                log,
+                quest,
+                entities,
+                clans,
+                territories,
+                player_id,
+                day,
+            ));
+        }
+        quest_events
+    }
+
+    /// Hand over anything the player carries for a clan's leader. Returns
+    /// the deliveries completed.
+    pub fn deliver(
+        log: &mut QuestLog,
+        entities: &mut [GameEntity],
+        clans: &mut HashMap<String, Clan>,
+        territories: &mut TerritoryManager,
+        player_id: u32,
+        recipient: &str,
+        day: u32,
+    ) -> Vec<QuestEvent> {
+        let mut quest_events = Vec::new();
+        let mut index = 0;
+        while index < log.active.len() {
+            let QuestGoal::Deliver { item, to_clan } = &log.active[index].goal else {
+                index += 1;
+                continue;
+            };
+            let handed_over = to_clan == recipient
+                && entities
+                    .iter_mut()
+                    .find(|entity| entity.id == player_id)
+                    .and_then(|player| player.inventory.as_mut())
+                    .is_some_and(|inventory| inventory.remove_item(*item, 1));
+            if !handed_over {
+                index += 1;
+                continue;
+            }
+            let quest = log.active.remove(index);
+            quest_events.push(Self::complete(
+                log,
+                quest,
+                entities,
+                clans,
+                territories,
+                player_id,
+                day,
+            ));
+        }
+        quest_events
+    }
+
+    /// Pay out a finished quest and note it in the journal
+    fn complete(
+        log: &mut QuestLog,
+        quest: Quest,
+        entities: &mut [GameEntity],
+        clans: &mut HashMap<String, Clan>,
+        territories: &mut TerritoryManager,
+        player_id: u32,
+        day: u32,
+    ) -> QuestEvent {
+        let description = quest.description();
+        let reward = match &quest.reward {
+            QuestReward::Trust(amount) => {
+                if let Some(clan) = clans.get_mut(&quest.clan) {
+                    clan.trust_towards_player = (clan.trust_towards_player + amount).min(1.0);
+                }
+                quest.reward.describe()
+            }
+            QuestReward::Item(item, quantity) => {
+                let given = entities
+                    .iter_mut()
+                    .find(|entity| entity.id == player_id)
+                    .and_then(|player| player.inventory.as_mut())
+                    .is_some_and(|inventory| inventory.add_item(*item, *quantity));
+                if given {
+                    quest.reward.describe()
+                } else {
+                    format!("{} (no room to carry it)", quest.reward.describe())
+                }
+            }
+            QuestReward::Territory => {
+                match territories
+                    .territories
+                    .iter_mut()
+                    .find(|territory| !territory.captured)
+                {
+                    Some(territory) => {
+                        territory.captured = true;
+                        territory.hold_hours = Territory::HOURS_TO_CAPTURE;
+                        format!("the {} is yours", territory.name)
+                    }
+                    None => "their thanks, having no land left to give".to_string(),
+                }
+            }
+        };
+        *log.done_for.entry(quest.clan.clone()).or_default() += 1;
+        log.completed.push((description.clone(), day));
+        QuestEvent::Completed {
+            description,
+            reward,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+
+    #[test]
+    fn test_leaders_give_quests_that_pay_on_completion() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let mut clans = HashMap::new();
+        WorldSystem::initialize_clans(&mut clans);
+        let mut territories = WorldSystem::place_territories();
+        let mut log = QuestLog::default();
+
+        // Grimjaw wants his ground cleared; asking again only reminds
+        assert!(matches!(
+            QuestSystem::request(&mut log, &clans, "Bone-Eaters"),
+            QuestEvent::Offered { .. }
+        ));
+        assert!(matches!(
+            QuestSystem::request(&mut log, &clans, "Bone-Eaters"),
+            QuestEvent::Pending { .. }
+        ));
+
+        // Kills far away do not count; five near the Bone Pit do
+        let infected = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 220.0, 680.0);
+        let kill = |x: f32| GameEvent::EntityDied {
+            entity_id: infected,
+            killer_id: player_id,
+            position: Position::new(x, 680.0),
+        };
+        let far = [kill(1500.0)];
+        let events = QuestSystem::record_events(
+            &mut log,
+            &far,
+            &mut entities,
+            &mut clans,
+            &mut territories,
+            player_id,
+            1,
+        );
+        assert!(events.is_empty());
+        let near = vec![kill(220.0); 5];
+        let events = QuestSystem::record_events(
+            &mut log,
+            &near,
+            &mut entities,
+            &mut clans,
+            &mut territories,
+            player_id,
+            1,
+        );
+        assert!(matches!(events.last(), Some(QuestEvent::Completed { .. })));
+        assert!((clans["Bone-Eaters"].trust_towards_player - 0.2).abs() < 1e-6);
+        assert_eq!(log.completed.len(), 1);
+
+        // Next comes a delivery, made by speaking to the recipient with it
+        QuestSystem::request(&mut log, &clans, "Bone-Eaters");
+        let QuestGoal::Deliver { item, to_clan } = log.active[0].goal.clone() else {
+            panic!("expected a delivery");
+        };
+        entities[0].inventory.as_mut().unwrap().add_item(item, 1);
+        let events = QuestSystem::deliver(
+            &mut log,
+            &mut entities,
+            &mut clans,
+            &mut territories,
+            player_id,
+            &to_clan,
+            2,
+        );
+        assert_eq!(events.len(), 1);
+        let inventory = entities[0].inventory.as_ref().unwrap();
+        assert_eq!(inventory.count(ItemKind::Trinket), 2);
+        assert!(log.active.is_empty());
+
+        // The third quest is paid in land
+        QuestSystem::request(&mut log, &clans, "Bone-Eaters");
+        assert_eq!(log.active[0].reward, QuestReward::Territory);
+
+        // Enemies get no work
+        clans.get_mut("Flame-Haters").unwrap().is_hostile = true;
+        assert!(matches!(
+            QuestSystem::request(&mut log, &clans, "Flame-Haters"),
+            QuestEvent::Refused { .. }
+        ));
+    }
+}
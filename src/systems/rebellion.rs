@@ -0,0 +1,344 @@
+//! Rebellion System Module
+//!
+//! Allied and subjugated clans that trust the player too little, or fear
+//! them too much, secretly plot betrayal. A hidden defection timer runs while
+//! the clan stays disloyal; halfway through an emissary warns the player, and
+//! clan members near the player let slip hints. Restoring the clan's loyalty
+//! before the timer runs out calls the plot off.
+
+use crate::components::*;
+use macroquad::prelude::*;
+use std::collections::HashMap;
+
+/// Seconds between a plot starting and the betrayal
+const PLOT_DURATION: f32 = 90.0;
+
+/// Unrest at which a clan starts plotting regardless of trust
+const PLOT_UNREST_THRESHOLD: f32 = 0.5;
+
+/// How close the player must be to overhear a bark hint
+const BARK_RANGE: f32 = 150.0;
+
+/// Minimum seconds between bark hints from the same clan
+const BARK_INTERVAL: f32 = 20.0;
+
+/// Number of clan warriors waiting at an ambushed meeting
+const AMBUSH_SIZE: usize = 3;
+
+/// Rebellion system responsible for clan plots and betrayal events
+pub struct RebellionSystem;
+
+impl RebellionSystem {
+    /// Advance every clan's plot and report anything the player should notice
+    pub fn update(
+        clans: &mut HashMap<String, Clan>,
+        entities: &mut [GameEntity],
+        player_id: u32,
+        game_time: f32,
+        delta_time: f32,
+    ) -> Vec<RebellionEvent> {
+        let mut events = Vec::new();
+        let player_pos = EntityFinder::by_id(entities, player_id).map(|p| p.position);
+
+        let mut clan_names: Vec<String> = clans.keys().cloned().collect();
+        clan_names.sort();
+
+        for clan_name in clan_names {
+            let Some(clan) = clans.get_mut(&clan_name) else {
+                continue;
+            };
+
+            if clan.plot.is_none() {
+                if Self::is_disloyal(clan) {
+                    clan.plot = Some(RebellionPlot::new(
+                        Self::choose_betrayal(clan),
+                        PLOT_DURATION,
+                    ));
+                }
+                continue;
+            }
+
+            if !Self::is_disloyal(clan) {
+                clan.plot = None;
+                events.push(RebellionEvent::Preempted { clan_name });
+                continue;
+            }
+
+            let near_clan =
+                player_pos.is_some_and(|pos| Self::is_player_near_clan(entities, &clan_name, &pos));
+            let Some(plot) = clan.plot.as_mut() else {
+                continue;
+            };
+
+            if plot.ambush_ready {
+                // The ambush waits for the player's next visit
+                continue;
+            }
+
+            plot.remaining -= delta_time;
+
+            if !plot.emissary_warned && plot.progress() >= 0.5 {
+                plot.emissary_warned = true;
+                events.push(RebellionEvent::EmissaryWarning {
+                    clan_name: clan_name.clone(),
+                    kind: plot.kind,
+                });
+            }
+
+            if near_clan && game_time - plot.last_bark_time >= BARK_INTERVAL {
+                plot.last_bark_time = game_time;
+                events.push(RebellionEvent::BarkHint {
+                    clan_name: clan_name.clone(),
+                    kind: plot.kind,
+                });
+            }
+
+            if plot.remaining <= 0.0 {
+                match plot.kind {
+                    BetrayalKind::Ambush => {
+                        plot.ambush_ready = true;
+                    }
+                    BetrayalKind::Defection => {
+                        Self::break_allegiance(clan);
+                        events.push(RebellionEvent::Defected { clan_name });
+                    }
+                    BetrayalKind::TerritoryRevolt => {
+                        Self::break_allegiance(clan);
+                        Self::turn_members_hostile(entities, &clan_name);
+                        events.push(RebellionEvent::TerritoryRevolt { clan_name });
+                    }
+                }
+            }
+        }
+
+        events
+    }
+
+    /// A clan is disloyal when it pays tribute but holds the player in
+    /// contempt (low trust, high fear) or is close to open unrest
+    pub fn is_disloyal(clan: &Clan) -> bool {
+        clan.owes_tribute()
+            && ((clan.trust_towards_player < 0.3 && clan.fear_of_player > 0.5)
+                || clan.unrest >= PLOT_UNREST_THRESHOLD)
+    }
+
+    /// Pick how a disloyal clan will betray the player
+    fn choose_betrayal(clan: &Clan) -> BetrayalKind {
+        if clan.is_defeated {
+            BetrayalKind::TerritoryRevolt
+        } else if clan.fear_of_player > 0.5 {
+            BetrayalKind::Ambush
+        } else {
+            BetrayalKind::Defection
+        }
+    }
+
+    /// Spring a prepared ambush when the player meets the clan leader.
+    /// Returns the IDs of the spawned attackers.
+    pub fn spring_ambush(
+        clan: &mut Clan,
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        player_pos: Position,
+        color: Color,
+    ) -> Vec<u32> {
+        if !clan.plot.as_ref().is_some_and(|plot| plot.ambush_ready) {
+            return Vec::new();
+        }
+
+        Self::break_allegiance(clan);
+
+        (0..AMBUSH_SIZE)
+            .map(|i| {
+                let angle = i as f32 / AMBUSH_SIZE as f32 * std::f32::consts::TAU;
+                let x = player_pos.x + angle.cos() * 80.0;
+                let y = (player_pos.y + angle.sin() * 80.0).max(650.0);
+                let id = crate::systems::WorldSystem::spawn_clan_member(
+                    entities, entity_ids, &clan.name, x, y, color,
+                );
+                if let Some(entity) = entities.iter_mut().find(|e| e.id == id) {
+                    entity.ai_state = AIState::Hostile;
+                }
+                id
+            })
+            .collect()
+    }
+
+    /// End the clan's allegiance to the player
+    pub(crate) fn break_allegiance(clan: &mut Clan) {
+        clan.is_allied = false;
+        clan.is_defeated = false;
+        clan.tribute_level = TributeLevel::None;
+        clan.trust_towards_player = 0.0;
+        clan.unrest = 0.0;
+        clan.plot = None;
+    }
+
+    /// Turn every living member of a clan against the player
+    pub(crate) fn turn_members_hostile(entities: &mut [GameEntity], clan_name: &str) {
+        for entity in entities.iter_mut() {
+            let in_clan = match &entity.entity_type {
+                EntityType::ClanMember(name) | EntityType::ClanLeader(name) => name == clan_name,
+                _ => false,
+            };
+            // Sworn followers stay loyal to the player
+            if in_clan && !matches!(entity.ai_state, AIState::Dead | AIState::Follower(_)) {
+                entity.ai_state = AIState::Hostile;
+            }
+        }
+    }
+
+    /// Check whether any of the clan's people are within earshot of the player
+    fn is_player_near_clan(
+        entities: &[GameEntity],
+        clan_name: &str,
+        player_pos: &Position,
+    ) -> bool {
+        entities.iter().any(|entity| {
+            let in_clan = match &entity.entity_type {
+                EntityType::ClanMember(name) | EntityType::ClanLeader(name) => name == clan_name,
+                _ => false,
+            };
+            in_clan && entity.position.distance_to(player_pos) <= BARK_RANGE
+        })
+    }
+}
+
+/// Something the player can notice about a clan's plotting
+#[derive(Debug, Clone, PartialEq)]
+pub enum RebellionEvent {
+    EmissaryWarning {
+        clan_name: String,
+        kind: BetrayalKind,
+    },
+    BarkHint {
+        clan_name: String,
+        kind: BetrayalKind,
+    },
+    Preempted {
+        clan_name: String,
+    },
+    Defected {
+        clan_name: String,
+    },
+    TerritoryRevolt {
+        clan_name: String,
+    },
+}
+
+impl RebellionEvent {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            RebellionEvent::EmissaryWarning { clan_name, kind } => match kind {
+                BetrayalKind::Defection => format!(
+                    "An emissary whispers that the {} are courting other masters...",
+                    clan_name
+                ),
+                BetrayalKind::Ambush => format!(
+                    "An emissary warns: do not visit the {} leader unguarded.",
+                    clan_name
+                ),
+                BetrayalKind::TerritoryRevolt => format!(
+                    "An emissary reports the {} are sharpening blades in their territory.",
+                    clan_name
+                ),
+            },
+            RebellionEvent::BarkHint { clan_name, kind } => match kind {
+                BetrayalKind::Defection => {
+                    format!("A {} member mutters: \"Not for much longer...\"", clan_name)
+                }
+                BetrayalKind::Ambush => format!(
+                    "A {} member grins: \"Do come see our leader soon.\"",
+                    clan_name
+                ),
+                BetrayalKind::TerritoryRevolt => {
+                    format!("A {} member spits: \"This land is ours.\"", clan_name)
+                }
+            },
+            RebellionEvent::Preempted { clan_name } => {
+                format!("The unrest among the {} has quieted.", clan_name)
+            }
+            RebellionEvent::Defected { clan_name } => {
+                format!("The {} have abandoned your rule!", clan_name)
+            }
+            RebellionEvent::TerritoryRevolt { clan_name } => {
+                format!("The {} territory has risen in revolt!", clan_name)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_fearful_clan(subjugated: bool) -> Clan {
+        let mut clan = Clan::new("Bone-Eaters", "Grimjaw", 15);
+        clan.is_allied = !subjugated;
+        clan.is_defeated = subjugated;
+        clan.trust_towards_player = 0.1;
+        clan.fear_of_player = 0.8;
+        clan
+    }
+
+    #[test]
+    fn test_disloyal_clan_starts_plot() {
+        let mut clans = HashMap::new();
+        clans.insert("Bone-Eaters".to_string(), create_fearful_clan(false));
+        let mut entities = Vec::new();
+
+        RebellionSystem::update(&mut clans, &mut entities, 0, 0.0, 0.1);
+
+        let plot = clans["Bone-Eaters"].plot.as_ref().unwrap();
+        assert_eq!(plot.kind, BetrayalKind::Ambush);
+    }
+
+    #[test]
+    fn test_emissary_warning_then_defection() {
+        let mut clans = HashMap::new();
+        let mut clan = create_fearful_clan(false);
+        clan.fear_of_player = 0.0;
+        clan.unrest = 0.6;
+        clans.insert("Bone-Eaters".to_string(), clan);
+        let mut entities = Vec::new();
+
+        let mut all_events = Vec::new();
+        for step in 0..=100 {
+            all_events.extend(RebellionSystem::update(
+                &mut clans,
+                &mut entities,
+                0,
+                step as f32,
+                1.0,
+            ));
+        }
+
+        let warning = all_events
+            .iter()
+            .position(|e| matches!(e, RebellionEvent::EmissaryWarning { .. }))
+            .unwrap();
+        let defection = all_events
+            .iter()
+            .position(|e| matches!(e, RebellionEvent::Defected { .. }))
+            .unwrap();
+        assert!(warning < defection);
+        assert!(!clans["Bone-Eaters"].is_allied);
+    }
+
+    #[test]
+    fn test_restored_loyalty_preempts_plot() {
+        let mut clans = HashMap::new();
+        clans.insert("Bone-Eaters".to_string(), create_fearful_clan(true));
+        let mut entities = Vec::new();
+
+        RebellionSystem::update(&mut clans, &mut entities, 0, 0.0, 1.0);
+        assert!(clans["Bone-Eaters"].plot.is_some());
+
+        clans.get_mut("Bone-Eaters").unwrap().trust_towards_player = 0.9;
+        let events = RebellionSystem::update(&mut clans, &mut entities, 0, 1.0, 1.0);
+
+        assert!(matches!(events[0], RebellionEvent::Preempted { .. }));
+        assert!(clans["Bone-Eaters"].plot.is_none());
+    }
+}
@@ -0,0 +1,282 @@
+//! Schedule System Module
+//!
+//! Drives the daily routine of NPCs with a `Schedule`. Clan members head for
+//! the nearest free shelter at dawn and sleep there through the day, then
+//! come out at dusk to patrol in a circle around their clan leader. Only
+//! idle NPCs keep to their schedule; followers, hostiles and the fleeing are
+//! left to the AI system.
+
+use crate::components::*;
+use crate::systems::ShelterSystem;
+use std::collections::HashMap;
+
+/// Walking speed while patrolling
+const PATROL_SPEED: f32 = 50.0;
+
+/// Walking speed while heading home at dawn
+const RETURN_SPEED: f32 = 90.0;
+
+/// How far a clan member will look for a shelter to sleep in
+const SHELTER_SEARCH_RANGE: f32 = 800.0;
+
+/// Schedule system responsible for NPC daily routines
+pub struct ScheduleSystem;
+
+impl ScheduleSystem {
+    /// Move every scheduled NPC according to the time of day, keeping them
+    /// inside `bounds`
+    pub fn update(
+        entities: &mut [GameEntity],
+        bounds: &WorldBounds,
+        is_day: bool,
+        current_time: f32,
+        delta_time: f32,
+    ) {
+        // Patrols circle the living leader of their clan
+        let leaders: HashMap<String, Position> = entities
+            .iter()
+            .filter(|entity| !matches!(entity.ai_state, AIState::Dead))
+            .filter_map(|entity| match &entity.entity_type {
+                EntityType::ClanLeader(clan) => Some((clan.clone(), entity.position)),
+                _ => None,
+            })
+            .collect();
+
+        for index in 0..entities.len() {
+            let Some(schedule) = entities[index].schedule.as_ref() else {
+                continue;
+            };
+            let entity_id = entities[index].id;
+
+            if !matches!(entities[index].ai_state, AIState::Idle) {
+                // Woken by something more pressing than the clock
+                if schedule.activity == ScheduleActivity::Sleep {
+                    ShelterSystem::release_npc(entities, entity_id);
+                }
+                if let Some(schedule) = entities[index].schedule.as_mut() {
+                    schedule.activity = ScheduleActivity::Patrol;
+                    schedule.home_shelter = None;
+                }
+                continue;
+            }
+
+            if is_day {
+                Self::return_to_shelter(entities, index, bounds, current_time, delta_time);
+            } else {
+                if schedule.activity == ScheduleActivity::Sleep {
+                    ShelterSystem::release_npc(entities, entity_id);
+                }
+                let clan_leader = match &entities[index].entity_type {
+                    EntityType::ClanMember(clan) => leaders.get(clan).copied(),
+                    _ => None,
+                };
+                Self::patrol(&mut entities[index], bounds, clan_leader, delta_time);
+            }
+        }
+    }
+
+    /// Walk to a free shelter and go to sleep once there
+    fn return_to_shelter(
+        entities: &mut [GameEntity],
+        index: usize,
+        bounds: &WorldBounds,
+        current_time: f32,
+        delta_time: f32,
+    ) {
+        let entity = &entities[index];
+        let in_shelter = entity
+            .shelter_occupancy
+            .as_ref()
+            .is_some_and(|occupancy| occupancy.is_in_shelter());
+        if in_shelter {
+            Self::stop(&mut entities[index], ScheduleActivity::Sleep);
+            return;
+        }
+
+        // Keep heading for the chosen shelter while it still has room
+        let home = entity
+            .schedule
+            .as_ref()
+            .and_then(|schedule| schedule.home_shelter)
+            .and_then(|id| EntityFinder::by_id(entities, id))
+            .filter(|home| home.shelter.as_ref().is_some_and(|s| s.can_accommodate()))
+            .map(|home| home.id)
+            .or_else(|| {
+                ShelterSystem::find_nearest_available_shelter(
+                    entities,
+                    entity.position,
+                    SHELTER_SEARCH_RANGE,
+                )
+            });
+        let Some((home_id, home_position, entry_range)) = home
+            .and_then(|id| EntityFinder::by_id(entities, id))
+            .and_then(|home| {
+                let shelter = home.shelter.as_ref()?;
+                Some((
+                    home.id,
+                    home.position,
+                    shelter.shelter_type.discovery_range(),
+                ))
+            })
+        else {
+            // Nowhere to go: wait out the day where they stand
+            Self::stop(&mut entities[index], ScheduleActivity::ReturnToShelter);
+            return;
+        };
+
+        let entity_id = entities[index].id;
+        if entities[index].position.distance_to(&home_position) <= entry_range
+            && ShelterSystem::admit_npc(entities, entity_id, home_id, current_time)
+        {
+            let entity = &mut entities[index];
+            entity.position = home_position;
+            Self::stop(entity, ScheduleActivity::Sleep);
+            return;
+        }
+
+        let entity = &mut entities[index];
+        Self::walk_towards(entity, home_position, bounds, RETURN_SPEED, delta_time);
+        if let Some(schedule) = entity.schedule.as_mut() {
+            schedule.activity = ScheduleActivity::ReturnToShelter;
+            schedule.home_shelter = Some(home_id);
+        }
+    }
+
+    /// Walk the patrol circle around the clan leader, or around the NPC's
+    /// post if the leader is gone
+    fn patrol(
+        entity: &mut GameEntity,
+        bounds: &WorldBounds,
+        clan_leader: Option<Position>,
+        delta_time: f32,
+    ) {
+        let Some(schedule) = entity.schedule.as_mut() else {
+            return;
+        };
+        schedule.activity = ScheduleActivity::Patrol;
+        schedule.home_shelter = None;
+        schedule.patrol_angle = (schedule.patrol_angle
+            + PATROL_SPEED / schedule.patrol_radius * delta_time)
+            % std::f32::consts::TAU;
+
+        let center = clan_leader.unwrap_or(schedule.post);
+        let target = Position::new(
+            center.x + schedule.patrol_angle.cos() * schedule.patrol_radius,
+            center.y + schedule.patrol_angle.sin() * schedule.patrol_radius,
+        );
+        // Hurry back to the circle if far from it, e.g. just out of a shelter
+        let speed = if entity.position.distance_to(&target) > 40.0 {
+            RETURN_SPEED
+        } else {
+            PATROL_SPEED
+        };
+        Self::walk_towards(entity, target, bounds, speed, delta_time);
+    }
+
+    fn walk_towards(
+        entity: &mut GameEntity,
+        target: Position,
+        bounds: &WorldBounds,
+        speed: f32,
+        delta_time: f32,
+    ) {
+        let dx = target.x - entity.position.x;
+        let dy = target.y - entity.position.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+        let step = (speed * delta_time).min(distance);
+        let velocity = if distance > 0.01 {
+            Velocity {
+                x: dx / distance * speed,
+                y: dy / distance * speed,
+            }
+        } else {
+            Velocity { x: 0.0, y: 0.0 }
+        };
+        if distance > 0.01 {
+            entity.position = bounds.clamp(Position::new(
+                entity.position.x + dx / distance * step,
+                entity.position.y + dy / distance * step,
+            ));
+        }
+        entity.velocity = Some(velocity);
+    }
+
+    fn stop(entity: &mut GameEntity, activity: ScheduleActivity) {
+        entity.velocity = Some(Velocity { x: 0.0, y: 0.0 });
+        if let Some(schedule) = entity.schedule.as_mut() {
+            schedule.activity = activity;
+        }
+    }
+
+    /// Whether an entity is asleep in a shelter and out of sight
+    pub fn is_asleep(entity: &GameEntity) -> bool {
+        entity
+            .schedule
+            .as_ref()
+            .is_some_and(|schedule| schedule.activity == ScheduleActivity::Sleep)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+    use macroquad::prelude::BLUE;
+
+    #[test]
+    fn test_clan_member_sleeps_by_day_and_patrols_by_night() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_clan_leader(
+            &mut entities,
+            &mut ids,
+            "Silentfang",
+            "Night-Bloods",
+            800.0,
+            700.0,
+            BLUE,
+        );
+        let member_id = WorldSystem::spawn_clan_member(
+            &mut entities,
+            &mut ids,
+            "Night-Bloods",
+            820.0,
+            700.0,
+            BLUE,
+        );
+        let shelter_id = ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Cave,
+            1000.0,
+            700.0,
+            None,
+            None,
+        );
+
+        // Dawn: walk to the cave and fall asleep inside it
+        for _ in 0..60 {
+            ScheduleSystem::update(&mut entities, &WorldBounds::default(), true, 0.0, 0.1);
+        }
+        let member = EntityFinder::by_id(&entities, member_id).unwrap();
+        assert!(ScheduleSystem::is_asleep(member));
+        assert_eq!(
+            member.shelter_occupancy.as_ref().unwrap().shelter_id,
+            Some(shelter_id)
+        );
+
+        // Dusk: leave the cave and circle back to the leader
+        for _ in 0..100 {
+            ScheduleSystem::update(&mut entities, &WorldBounds::default(), false, 0.0, 0.1);
+        }
+        let member = EntityFinder::by_id(&entities, member_id).unwrap();
+        assert_eq!(
+            member.schedule.as_ref().unwrap().activity,
+            ScheduleActivity::Patrol
+        );
+        assert!(!member.shelter_occupancy.as_ref().unwrap().is_in_shelter());
+        assert!(member.position.distance_to(&Position::new(800.0, 700.0)) < 130.0);
+        let shelter = EntityFinder::by_id(&entities, shelter_id).unwrap();
+        assert!(shelter.shelter.as_ref().unwrap().occupants.is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+//! Scout System Module
+//!
+//! Flies the crow sent out by the Crow Scout ability. While the crow is out
+//! the player steers it instead of the vampire, who stands still and exposed.
+//! The crow cannot interact with anything, but it lifts the fog of war as it
+//! flies and marks the creatures it sees so they can be tracked after it
+//! returns. It comes back on its own when its time runs out or when the
+//! player dismisses it.
+
+use crate::components::*;
+use crate::systems::query::{QueryShape, WorldQuery};
+use crate::systems::spatial::SpatialGrid;
+
+/// Crow flying speed in pixels per second
+const CROW_SPEED: f32 = 420.0;
+
+/// How far the crow sees, both for lifting fog and marking creatures
+pub const CROW_SIGHT: f32 = 220.0;
+
+/// Seconds a creature stays marked after the crow last saw it
+const MARK_DURATION: f32 = 30.0;
+
+/// The crow being piloted by the player
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrowScout {
+    pub position: Position,
+    /// Seconds until the crow returns on its own
+    pub remaining: f32,
+    pub facing_left: bool,
+}
+
+/// A creature spotted by the crow
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoutMark {
+    pub entity_id: u32,
+    pub remaining: f32,
+}
+
+/// Scout system responsible for the crow scout and its marks
+pub struct ScoutSystem;
+
+impl ScoutSystem {
+    /// Release a crow from the player's position
+    pub fn launch(from: Position) -> CrowScout {
+        CrowScout {
+            position: from,
+            remaining: SpecialAbility::CrowScout.duration(),
+            facing_left: false,
+        }
+    }
+
+    /// Fly the crow along `direction` (the movement input). Returns
+    /// `Some(Returned)` once the crow's time is up. The crow may fly over
+    /// the sky as well as the ground, but not past the edges of `bounds`.
+    pub fn fly(
+        scout: &mut CrowScout,
+        bounds: &WorldBounds,
+        direction: (f32, f32),
+        delta_time: f32,
+    ) -> Option<ScoutEvent> {
+        scout.remaining -= delta_time;
+        if scout.remaining <= 0.0 {
+            return Some(ScoutEvent::Returned);
+        }
+
+        let (dx, dy) = direction;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > 0.01 {
+            scout.position.x = (scout.position.x + dx / length * CROW_SPEED * delta_time)
+                .clamp(bounds.min_x, bounds.max_x);
+            scout.position.y =
+                (scout.position.y + dy / length * CROW_SPEED * delta_time).clamp(0.0, bounds.max_y);
+            if dx.abs() > 0.01 {
+                scout.facing_left = dx < 0.0;
+            }
+        }
+        None
+    }
+
+    /// Lift the fog under the crow and mark every living creature it can see.
+    /// Creatures already marked have their mark refreshed.
+    pub fn observe(
+        scout: &CrowScout,
+        entities: &[GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        fog: &mut FogOfWar,
+        marks: &mut Vec<ScoutMark>,
+    ) {
+        fog.reveal(scout.position, CROW_SIGHT);
+
+        let seen = WorldQuery::query_area(
+            entities,
+            grid,
+            &[],
+            QueryShape::circle(scout.position, CROW_SIGHT),
+        );
+        for entity in seen.entities.into_iter().map(|index| &entities[index]) {
+            let markable = matches!(
+                entity.entity_type,
+                EntityType::HostileInfected
+                    | EntityType::Animal
+                    | EntityType::ClanLeader(_)
+                    | EntityType::ClanMember(_)
+            );
+            if entity.id == player_id || !markable || matches!(entity.ai_state, AIState::Dead) {
+                continue;
+            }
+            match marks.iter_mut().find(|mark| mark.entity_id == entity.id) {
+                Some(mark) => mark.remaining = MARK_DURATION,
+                None => marks.push(ScoutMark {
+                    entity_id: entity.id,
+                    remaining: MARK_DURATION,
+                }),
+            }
+        }
+    }
+
+    /// Fade marks over time and drop those on creatures that have died or
+    /// vanished
+    pub fn update_marks(marks: &mut Vec<ScoutMark>, entities: &[GameEntity], delta_time: f32) {
+        marks.retain_mut(|mark| {
+            mark.remaining -= delta_time;
+            mark.remaining > 0.0
+                && EntityFinder::by_id(entities, mark.entity_id)
+                    .is_some_and(|entity| !matches!(entity.ai_state, AIState::Dead))
+        });
+    }
+}
+
+/// The crow scout coming back to the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoutEvent {
+    /// The crow's time ran out
+    Returned,
+    /// The player called the crow back
+    Dismissed,
+}
+
+impl ScoutEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            ScoutEvent::Returned => "The crow tires and flies back to you.".to_string(),
+            ScoutEvent::Dismissed => "You call the crow back to your shoulder.".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+
+    #[test]
+    fn test_crow_scouts_and_returns() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(200.0, 700.0);
+        let hostile_id =
+            WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 1000.0, 900.0);
+        let grid = SpatialGrid::from_entities(&entities);
+        let mut fog = FogOfWar::new();
+        let mut marks = Vec::new();
+
+        let mut scout = ScoutSystem::launch(entities[0].position);
+        assert!(!fog.is_explored(&Position::new(1000.0, 900.0)));
+
+        // Fly two seconds to the south-east, within sight of the hostile
+        for _ in 0..20 {
+            assert_eq!(
+                ScoutSystem::fly(&mut scout, &WorldBounds::default(), (1.0, 0.3), 0.1),
+                None
+            );
+        }
+        ScoutSystem::observe(&scout, &entities, &grid, 0, &mut fog, &mut marks);
+        assert!(fog.is_explored(&Position::new(1000.0, 900.0)));
+        assert_eq!(marks.len(), 1);
+        assert_eq!(marks[0].entity_id, hostile_id);
+
+        // Marks fade, and the crow comes home when its time is up
+        ScoutSystem::update_marks(&mut marks, &entities, MARK_DURATION + 1.0);
+        assert!(marks.is_empty());
+        let remaining = scout.remaining;
+        assert_eq!(
+            ScoutSystem::fly(&mut scout, &WorldBounds::default(), (0.0, 0.0), remaining),
+            Some(ScoutEvent::Returned)
+        );
+    }
+}
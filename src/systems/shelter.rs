@@ -2,11 +2,21 @@
 //!
 //! Manages shelter mechanics, sunlight protection, and shelter interactions
 //! in the Vampire RPG. This system handles shelter discovery, occupancy,
-//! and protection calculations against deadly sunlight.
+//! and protection calculations against deadly sunlight. A player holding
+//! interact inside a shelter mends it a step at a time, then reinforces it,
+//! spending blood on the labour.
 
 use crate::components::*;
+use crate::rendering::{DrawQueue, RenderLayer};
+use crate::systems::spatial::SpatialGrid;
 use macroquad::prelude::*;
 
+/// Largest `ShelterType::discovery_range`, bounding proximity queries
+const MAX_DISCOVERY_RANGE: f32 = 60.0;
+
+/// Blood spent per second of work on a shelter
+pub const WORK_BLOOD_PER_SECOND: f32 = 0.5;
+
 /// Shelter system responsible for managing all shelter-related mechanics
 pub struct ShelterSystem;
 
@@ -34,8 +44,10 @@ impl ShelterSystem {
     /// Handle player attempting to enter/exit shelter
     pub fn handle_player_shelter_interaction(
         entities: &mut Vec<GameEntity>,
+        grid: &SpatialGrid,
         player_id: u32,
         current_time: f32,
+        respect_thresholds: bool,
     ) -> Option<String> {
         let player_pos = entities.iter().find(|e| e.id == player_id)?.position;
 
@@ -63,7 +75,8 @@ impl ShelterSystem {
         let mut nearest_shelter: Option<(u32, f32, String)> = None;
         let mut nearby_shelters_found = 0;
 
-        for entity in entities.iter() {
+        for idx in grid.query_radius(entities, player_pos, MAX_DISCOVERY_RANGE) {
+            let entity = &entities[idx];
             if let Some(shelter) = &entity.shelter {
                 let distance = ((player_pos.x - entity.position.x).powi(2)
                     + (player_pos.y - entity.position.y).powi(2))
@@ -84,6 +97,20 @@ impl ShelterSystem {
             // Try to enter the shelter
             if let Some(shelter_entity) = entities.iter_mut().find(|e| e.id == shelter_id) {
                 if let Some(shelter) = &mut shelter_entity.shelter {
+                    if respect_thresholds && shelter.bars_vampires() {
+                        shelter.discover();
+                        return Some(format!(
+                            "The threshold of the {} holds - you have not been invited in",
+                            shelter.display_name()
+                        ));
+                    }
+                    if shelter.locked {
+                        shelter.discover();
+                        return Some(format!(
+                            "The door of the {} is locked - you need a key",
+                            shelter.display_name()
+                        ));
+                    }
                     if shelter.can_accommodate() {
                         shelter.discover();
 
@@ -126,6 +153,48 @@ impl ShelterSystem {
         None
     }
 
+    /// Put a frame of work into the shelter the player is inside, paid for
+    /// in blood. Returns an event when a repair or reinforcement finishes.
+    pub fn work_on_shelter(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        delta_time: f32,
+    ) -> Option<ShelterWorkEvent> {
+        let player = entities.iter_mut().find(|e| e.id == player_id)?;
+        let shelter_id = player.shelter_occupancy.as_ref()?.shelter_id?;
+        let shelter = entities
+            .iter()
+            .find(|e| e.id == shelter_id)
+            .and_then(|e| e.shelter.as_ref())?;
+        shelter.next_work()?;
+
+        let player = entities.iter_mut().find(|e| e.id == player_id)?;
+        if !player
+            .blood_meter
+            .as_mut()
+            .is_some_and(|blood| blood.consume(WORK_BLOOD_PER_SECOND * delta_time))
+        {
+            return None;
+        }
+
+        let shelter = entities
+            .iter_mut()
+            .find(|e| e.id == shelter_id)
+            .and_then(|e| e.shelter.as_mut())?;
+        let work = shelter.apply_work(delta_time)?;
+        let name = shelter.display_name().to_string();
+        Some(match work {
+            ShelterWork::Repair(condition) => ShelterWorkEvent::Repaired {
+                shelter: name,
+                condition,
+            },
+            ShelterWork::Reinforce(level) => ShelterWorkEvent::Reinforced {
+                shelter: name,
+                level,
+            },
+        })
+    }
+
     /// Get shelter information for nearby shelters (for UI display)
     pub fn get_nearby_shelter_info(
         entities: &[GameEntity],
@@ -156,6 +225,7 @@ impl ShelterSystem {
                 position: entity.position,
                 shelter_type: shelter.shelter_type.clone(),
                 condition: shelter.condition.clone(),
+                upgrade_level: shelter.upgrade_level,
                 protection_level: shelter.effective_protection(),
                 occupancy: format!(
                     "{}/{}",
@@ -209,6 +279,10 @@ impl ShelterSystem {
         };
 
         if let Some(occupancy) = &entity.shelter_occupancy {
+            // No daylight reaches the room inside
+            if occupancy.indoors {
+                return 0.0;
+            }
             if let Some(shelter_id) = occupancy.shelter_id {
                 if let Some(shelter_entity) = entities.iter().find(|e| e.id == shelter_id) {
                     if let Some(shelter) = &shelter_entity.shelter {
@@ -235,7 +309,7 @@ impl ShelterSystem {
     /// Spawn a shelter at the specified location with ground validation
     pub fn spawn_shelter_safe(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         shelter_type: ShelterType,
         x: f32,
         y: f32,
@@ -253,7 +327,7 @@ impl ShelterSystem {
 
         Some(Self::spawn_shelter(
             entities,
-            next_entity_id,
+            entity_ids,
             shelter_type,
             x,
             y,
@@ -265,7 +339,7 @@ impl ShelterSystem {
     /// Spawn a shelter at the specified location
     pub fn spawn_shelter(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         shelter_type: ShelterType,
         x: f32,
         y: f32,
@@ -280,9 +354,9 @@ impl ShelterSystem {
             );
         }
 
-        let id = *next_entity_id;
-        *next_entity_id += 1;
+        let id = entity_ids.allocate();
 
+        let collider = Collider::for_shelter(&shelter_type);
         let mut shelter = match condition {
             Some(cond) => Shelter::with_condition(shelter_type, cond),
             None => Shelter::new(shelter_type),
@@ -305,6 +379,17 @@ impl ShelterSystem {
             shelter: Some(shelter),
             shelter_occupancy: None,
             color: WHITE, // Will be overridden by shelter rendering
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         };
 
         entities.push(entity);
@@ -325,10 +410,14 @@ impl ShelterSystem {
                     + (entity_pos.y - entity.position.y).powi(2))
                 .sqrt();
 
-                if distance <= max_distance && shelter.can_accommodate() {
-                    if nearest.is_none() || distance < nearest.as_ref().unwrap().1 {
-                        nearest = Some((entity.id, distance));
-                    }
+                // NPCs cannot open locked doors or cross an uninvited threshold
+                if distance <= max_distance
+                    && shelter.can_accommodate()
+                    && !shelter.locked
+                    && !shelter.bars_vampires()
+                    && nearest.is_none_or(|(_, best)| distance < best)
+                {
+                    nearest = Some((entity.id, distance));
                 }
             }
         }
@@ -389,23 +478,59 @@ impl ShelterSystem {
             if let Some(shelter_id) =
                 Self::find_nearest_available_shelter(entities, entity_pos, 200.0)
             {
-                // Try to assign this entity to the shelter
-                if let Some(shelter_entity) = entities.iter_mut().find(|e| e.id == shelter_id) {
-                    if let Some(shelter) = &mut shelter_entity.shelter {
-                        if shelter.add_occupant(entity_id) {
-                            // Update entity's occupancy
-                            if let Some(entity) = entities.iter_mut().find(|e| e.id == entity_id) {
-                                if let Some(occupancy) = &mut entity.shelter_occupancy {
-                                    occupancy.enter_shelter(shelter_id, current_time);
-                                }
-                            }
-                        }
-                    }
-                }
+                Self::admit_npc(entities, entity_id, shelter_id, current_time);
             }
         }
     }
 
+    /// Take an NPC into a shelter, updating both the shelter's occupants and
+    /// the NPC's occupancy. Returns false if the shelter has no room.
+    pub fn admit_npc(
+        entities: &mut [GameEntity],
+        entity_id: u32,
+        shelter_id: u32,
+        current_time: f32,
+    ) -> bool {
+        let admitted = entities
+            .iter_mut()
+            .find(|e| e.id == shelter_id)
+            .and_then(|shelter_entity| shelter_entity.shelter.as_mut())
+            .is_some_and(|shelter| shelter.add_occupant(entity_id));
+        if admitted {
+            if let Some(entity) = entities.iter_mut().find(|e| e.id == entity_id) {
+                entity
+                    .shelter_occupancy
+                    .get_or_insert_with(ShelterOccupancy::new)
+                    .enter_shelter(shelter_id, current_time);
+            }
+        }
+        admitted
+    }
+
+    /// Send an NPC back out of whatever shelter it is in
+    pub fn release_npc(entities: &mut [GameEntity], entity_id: u32) {
+        let Some(shelter_id) = EntityFinder::by_id(entities, entity_id)
+            .and_then(|entity| entity.shelter_occupancy.as_ref())
+            .and_then(|occupancy| occupancy.shelter_id)
+        else {
+            return;
+        };
+        if let Some(shelter) = entities
+            .iter_mut()
+            .find(|e| e.id == shelter_id)
+            .and_then(|shelter_entity| shelter_entity.shelter.as_mut())
+        {
+            shelter.remove_occupant(entity_id);
+        }
+        if let Some(occupancy) = entities
+            .iter_mut()
+            .find(|e| e.id == entity_id)
+            .and_then(|entity| entity.shelter_occupancy.as_mut())
+        {
+            occupancy.leave_shelter();
+        }
+    }
+
     /// Apply protection effects to entities in shelters
     fn apply_shelter_protection(entities: &mut [GameEntity], _sunlight_intensity: f32) {
         for entity in entities {
@@ -441,17 +566,20 @@ impl ShelterSystem {
         }
     }
 
-    /// Render all shelters with pixel art style
+    /// Queue every shelter's pixel art on the terrain layer
     pub fn render_shelters(
+        queue: &mut DrawQueue,
         entities: &[GameEntity],
         camera_offset_x: f32,
         camera_offset_y: f32,
         zoom_level: f32,
         show_debug_info: bool,
     ) {
+        queue.set_layer(RenderLayer::Terrain);
         for entity in entities {
             if let Some(shelter) = &entity.shelter {
                 Self::render_shelter(
+                    queue,
                     entity,
                     shelter,
                     camera_offset_x,
@@ -465,6 +593,7 @@ impl ShelterSystem {
 
     /// Render a single shelter with pixel art style
     fn render_shelter(
+        queue: &mut DrawQueue,
         entity: &GameEntity,
         shelter: &Shelter,
         camera_offset_x: f32,
@@ -480,51 +609,124 @@ impl ShelterSystem {
 
         // Draw main shelter structure based on type
         match shelter.shelter_type {
-            ShelterType::Cave => {
-                Self::draw_cave(screen_x, screen_y, scaled_width, scaled_height, shelter)
-            }
-            ShelterType::Building => {
-                Self::draw_building(screen_x, screen_y, scaled_width, scaled_height, shelter)
-            }
-            ShelterType::TreeCover => {
-                Self::draw_tree_cover(screen_x, screen_y, scaled_width, scaled_height, shelter)
-            }
-            ShelterType::Underground => {
-                Self::draw_underground(screen_x, screen_y, scaled_width, scaled_height, shelter)
-            }
-            ShelterType::Ruins => {
-                Self::draw_ruins(screen_x, screen_y, scaled_width, scaled_height, shelter)
-            }
-            ShelterType::Shed => {
-                Self::draw_shed(screen_x, screen_y, scaled_width, scaled_height, shelter)
-            }
+            ShelterType::Cave => Self::draw_cave(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+            ShelterType::Building => Self::draw_building(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+            ShelterType::TreeCover => Self::draw_tree_cover(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+            ShelterType::Underground => Self::draw_underground(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+            ShelterType::Ruins => Self::draw_ruins(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+            ShelterType::Shed => Self::draw_shed(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
             ShelterType::BridgeUnderpass => Self::draw_bridge_underpass(
+                queue,
                 screen_x,
                 screen_y,
                 scaled_width,
                 scaled_height,
                 shelter,
             ),
+            ShelterType::Tent => Self::draw_tent(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+            ShelterType::Lair => Self::draw_lair(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            ),
+        }
+
+        // Scaffolding and a progress bar over anything still being built
+        if let Some(progress) = shelter.construction {
+            Self::draw_construction(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                progress,
+            );
         }
 
         // Draw status indicators
         if shelter.discovered {
-            Self::draw_status_indicators(screen_x, screen_y, scaled_width, scaled_height, shelter);
+            Self::draw_status_indicators(
+                queue,
+                screen_x,
+                screen_y,
+                scaled_width,
+                scaled_height,
+                shelter,
+            );
         }
 
         // Draw debug information if enabled
         if show_debug_info && shelter.discovered {
-            Self::draw_debug_info(screen_x, screen_y, scaled_height, shelter);
+            Self::draw_debug_info(queue, screen_x, screen_y, scaled_height, shelter);
         }
     }
 
     /// Draw a cave shelter
-    fn draw_cave(screen_x: f32, screen_y: f32, width: f32, height: f32, shelter: &Shelter) {
+    fn draw_cave(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let primary = shelter.shelter_type.primary_color();
         let secondary = shelter.shelter_type.secondary_color();
 
         // Cave opening (dark interior)
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height / 2.0,
             width,
@@ -533,24 +735,31 @@ impl ShelterSystem {
         );
 
         // Cave entrance arch
-        draw_circle(screen_x, screen_y - height / 4.0, width / 3.0, primary);
-        draw_circle(screen_x, screen_y - height / 4.0, width / 4.0, secondary);
+        queue.circle(screen_x, screen_y - height / 4.0, width / 3.0, primary);
+        queue.circle(screen_x, screen_y - height / 4.0, width / 4.0, secondary);
 
         // Rocky details
         (0..5).for_each(|i| {
             let offset_x = (i as f32 - 2.0) * width / 8.0;
             let offset_y = rand::gen_range(-height / 3.0, height / 3.0);
-            draw_circle(screen_x + offset_x, screen_y + offset_y, 3.0, primary);
+            queue.circle(screen_x + offset_x, screen_y + offset_y, 3.0, primary);
         });
     }
 
     /// Draw a building shelter
-    fn draw_building(screen_x: f32, screen_y: f32, width: f32, height: f32, shelter: &Shelter) {
+    fn draw_building(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let primary = shelter.shelter_type.primary_color();
         let secondary = shelter.shelter_type.secondary_color();
 
         // Main building structure
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height / 2.0,
             width,
@@ -559,7 +768,7 @@ impl ShelterSystem {
         );
 
         // Roof
-        draw_triangle(
+        queue.triangle(
             Vec2::new(screen_x - width / 2.0, screen_y - height / 2.0),
             Vec2::new(screen_x + width / 2.0, screen_y - height / 2.0),
             Vec2::new(screen_x, screen_y - height),
@@ -567,7 +776,7 @@ impl ShelterSystem {
         );
 
         // Door
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 8.0,
             screen_y + height / 4.0,
             width / 4.0,
@@ -576,14 +785,14 @@ impl ShelterSystem {
         );
 
         // Windows
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 3.0,
             screen_y - height / 6.0,
             width / 6.0,
             width / 6.0,
             DARKBLUE,
         );
-        draw_rectangle(
+        queue.rectangle(
             screen_x + width / 6.0,
             screen_y - height / 6.0,
             width / 6.0,
@@ -593,21 +802,28 @@ impl ShelterSystem {
     }
 
     /// Draw tree cover shelter
-    fn draw_tree_cover(screen_x: f32, screen_y: f32, width: f32, height: f32, shelter: &Shelter) {
+    fn draw_tree_cover(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let primary = shelter.shelter_type.primary_color();
         let secondary = shelter.shelter_type.secondary_color();
 
         // Tree trunks
         (0..3).for_each(|i| {
             let x_offset = (i as f32 - 1.0) * width / 3.0;
-            draw_rectangle(screen_x + x_offset - 4.0, screen_y, 8.0, height, secondary);
+            queue.rectangle(screen_x + x_offset - 4.0, screen_y, 8.0, height, secondary);
         });
 
         // Tree canopy (overlapping circles for density)
         (0..5).for_each(|i| {
             let x_offset = (i as f32 - 2.0) * width / 6.0;
             let y_offset = rand::gen_range(-height / 4.0, 0.0);
-            draw_circle(
+            queue.circle(
                 screen_x + x_offset,
                 screen_y - height / 3.0 + y_offset,
                 width / 4.0,
@@ -616,16 +832,23 @@ impl ShelterSystem {
         });
 
         // Denser inner foliage
-        draw_circle(screen_x, screen_y - height / 3.0, width / 3.0, secondary);
+        queue.circle(screen_x, screen_y - height / 3.0, width / 3.0, secondary);
     }
 
     /// Draw underground shelter
-    fn draw_underground(screen_x: f32, screen_y: f32, width: f32, height: f32, shelter: &Shelter) {
+    fn draw_underground(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let primary = shelter.shelter_type.primary_color();
         let secondary = shelter.shelter_type.secondary_color();
 
         // Hatch/entrance
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height / 2.0,
             width,
@@ -636,7 +859,7 @@ impl ShelterSystem {
         // Metal grating pattern
         (0..4).for_each(|i| {
             let line_y = screen_y - height / 2.0 + (i as f32 + 1.0) * height / 5.0;
-            draw_line(
+            queue.line(
                 screen_x - width / 2.0,
                 line_y,
                 screen_x + width / 2.0,
@@ -647,7 +870,7 @@ impl ShelterSystem {
         });
         (0..3).for_each(|i| {
             let line_x = screen_x - width / 2.0 + (i as f32 + 1.0) * width / 4.0;
-            draw_line(
+            queue.line(
                 line_x,
                 screen_y - height / 2.0,
                 line_x,
@@ -658,23 +881,30 @@ impl ShelterSystem {
         });
 
         // Ladder indication
-        draw_rectangle(screen_x - 3.0, screen_y, 6.0, height + 10.0, DARKGRAY);
+        queue.rectangle(screen_x - 3.0, screen_y, 6.0, height + 10.0, DARKGRAY);
     }
 
     /// Draw ruins shelter
-    fn draw_ruins(screen_x: f32, screen_y: f32, width: f32, height: f32, shelter: &Shelter) {
+    fn draw_ruins(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let primary = shelter.shelter_type.primary_color();
         let secondary = shelter.shelter_type.secondary_color();
 
         // Broken walls
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height / 2.0,
             width / 3.0,
             height,
             primary,
         );
-        draw_rectangle(
+        queue.rectangle(
             screen_x + width / 6.0,
             screen_y - height / 3.0,
             width / 3.0,
@@ -686,12 +916,12 @@ impl ShelterSystem {
         (0..8).for_each(|_| {
             let debris_x = screen_x + rand::gen_range(-width / 2.0, width / 2.0);
             let debris_y = screen_y + rand::gen_range(-height / 2.0, height / 2.0);
-            draw_circle(debris_x, debris_y, rand::gen_range(2.0, 6.0), secondary);
+            queue.circle(debris_x, debris_y, rand::gen_range(2.0, 6.0), secondary);
         });
 
         // Archway (partially collapsed)
-        draw_circle(screen_x, screen_y - height / 4.0, width / 4.0, primary);
-        draw_rectangle(
+        queue.circle(screen_x, screen_y - height / 4.0, width / 4.0, primary);
+        queue.rectangle(
             screen_x - width / 4.0,
             screen_y - height / 4.0,
             width / 2.0,
@@ -701,12 +931,19 @@ impl ShelterSystem {
     }
 
     /// Draw shed shelter
-    fn draw_shed(screen_x: f32, screen_y: f32, width: f32, height: f32, shelter: &Shelter) {
+    fn draw_shed(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let primary = shelter.shelter_type.primary_color();
         let secondary = shelter.shelter_type.secondary_color();
 
         // Main shed structure
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height / 2.0,
             width,
@@ -715,7 +952,7 @@ impl ShelterSystem {
         );
 
         // Slanted roof
-        draw_triangle(
+        queue.triangle(
             Vec2::new(screen_x - width / 2.0, screen_y - height / 2.0),
             Vec2::new(screen_x + width / 2.0, screen_y - height / 2.0),
             Vec2::new(screen_x + width / 2.0, screen_y - height),
@@ -723,7 +960,7 @@ impl ShelterSystem {
         );
 
         // Door
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 6.0,
             screen_y + height / 6.0,
             width / 3.0,
@@ -732,7 +969,7 @@ impl ShelterSystem {
         );
 
         // Small window
-        draw_rectangle(
+        queue.rectangle(
             screen_x + width / 4.0,
             screen_y - height / 6.0,
             width / 8.0,
@@ -741,8 +978,124 @@ impl ShelterSystem {
         );
     }
 
+    /// Draw a canvas tent
+    fn draw_tent(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
+        let primary = shelter.shelter_type.primary_color();
+        let secondary = shelter.shelter_type.secondary_color();
+
+        // Canvas
+        queue.triangle(
+            Vec2::new(screen_x - width / 2.0, screen_y + height / 2.0),
+            Vec2::new(screen_x + width / 2.0, screen_y + height / 2.0),
+            Vec2::new(screen_x, screen_y - height / 2.0),
+            primary,
+        );
+
+        // Flap
+        queue.triangle(
+            Vec2::new(screen_x - width / 6.0, screen_y + height / 2.0),
+            Vec2::new(screen_x + width / 6.0, screen_y + height / 2.0),
+            Vec2::new(screen_x, screen_y),
+            DARKBROWN,
+        );
+
+        // Pole
+        queue.line(
+            screen_x,
+            screen_y - height / 2.0,
+            screen_x,
+            screen_y - height / 2.0 - 4.0,
+            2.0,
+            secondary,
+        );
+    }
+
+    /// Draw the player's lair
+    fn draw_lair(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
+        let primary = shelter.shelter_type.primary_color();
+        let secondary = shelter.shelter_type.secondary_color();
+
+        // Stone walls
+        queue.rectangle(
+            screen_x - width / 2.0,
+            screen_y - height / 2.0,
+            width,
+            height,
+            primary,
+        );
+
+        // Battlements
+        let merlon = width / 7.0;
+        for i in 0..4 {
+            queue.rectangle(
+                screen_x - width / 2.0 + i as f32 * merlon * 2.0,
+                screen_y - height / 2.0 - merlon,
+                merlon,
+                merlon,
+                primary,
+            );
+        }
+
+        // Arched door
+        queue.rectangle(
+            screen_x - width / 8.0,
+            screen_y,
+            width / 4.0,
+            height / 2.0,
+            BLACK,
+        );
+        queue.circle(screen_x, screen_y, width / 8.0, BLACK);
+
+        // Blood-red banners either side of the door
+        for side in [-1.0, 1.0] {
+            queue.rectangle(
+                screen_x + side * width / 3.0 - width / 20.0,
+                screen_y - height / 3.0,
+                width / 10.0,
+                height / 2.5,
+                secondary,
+            );
+        }
+    }
+
+    /// Draw scaffolding over a shelter under construction, with its progress
+    fn draw_construction(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        width: f32,
+        height: f32,
+        progress: f32,
+    ) {
+        let left = screen_x - width / 2.0;
+        let top = screen_y - height / 2.0;
+        let scaffold = Color::new(0.75, 0.6, 0.35, 0.9);
+        queue.rectangle_lines(left, top, width, height, 2.0, scaffold);
+        queue.line(left, top, left + width, top + height, 1.5, scaffold);
+        queue.line(left + width, top, left, top + height, 1.5, scaffold);
+
+        let bar_y = top - 8.0;
+        queue.rectangle(left, bar_y, width, 4.0, DARKGRAY);
+        queue.rectangle(left, bar_y, width * progress.clamp(0.0, 1.0), 4.0, ORANGE);
+    }
+
     /// Draw bridge underpass shelter
     fn draw_bridge_underpass(
+        queue: &mut DrawQueue,
         screen_x: f32,
         screen_y: f32,
         width: f32,
@@ -753,7 +1106,7 @@ impl ShelterSystem {
         let secondary = shelter.shelter_type.secondary_color();
 
         // Bridge structure above
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height,
             width,
@@ -762,14 +1115,14 @@ impl ShelterSystem {
         );
 
         // Support pillars
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 3.0,
             screen_y - height,
             8.0,
             height * 1.5,
             primary,
         );
-        draw_rectangle(
+        queue.rectangle(
             screen_x + width / 3.0,
             screen_y - height,
             8.0,
@@ -778,7 +1131,7 @@ impl ShelterSystem {
         );
 
         // Underpass area (shadowed)
-        draw_rectangle(
+        queue.rectangle(
             screen_x - width / 2.0,
             screen_y - height / 2.0,
             width,
@@ -789,6 +1142,7 @@ impl ShelterSystem {
 
     /// Draw status indicators for shelters
     fn draw_status_indicators(
+        queue: &mut DrawQueue,
         screen_x: f32,
         screen_y: f32,
         width: f32,
@@ -811,8 +1165,8 @@ impl ShelterSystem {
         let bar_x = screen_x - bar_width / 2.0;
         let bar_y = screen_y + height / 2.0 + 5.0;
 
-        draw_rectangle(bar_x, bar_y, bar_width, bar_height, DARKGRAY);
-        draw_rectangle(
+        queue.rectangle(bar_x, bar_y, bar_width, bar_height, DARKGRAY);
+        queue.rectangle(
             bar_x,
             bar_y,
             bar_width * protection,
@@ -822,7 +1176,7 @@ impl ShelterSystem {
 
         // Occupancy indicator
         if shelter.occupied {
-            draw_circle(
+            queue.circle(
                 screen_x + width / 2.0 - 8.0,
                 screen_y - height / 2.0 + 8.0,
                 4.0,
@@ -832,7 +1186,7 @@ impl ShelterSystem {
 
         // Condition indicator
         let condition_color = shelter.condition.status_color();
-        draw_circle(
+        queue.circle(
             screen_x - width / 2.0 + 8.0,
             screen_y - height / 2.0 + 8.0,
             3.0,
@@ -841,18 +1195,24 @@ impl ShelterSystem {
     }
 
     /// Draw debug information for shelters
-    fn draw_debug_info(screen_x: f32, screen_y: f32, height: f32, shelter: &Shelter) {
+    fn draw_debug_info(
+        queue: &mut DrawQueue,
+        screen_x: f32,
+        screen_y: f32,
+        height: f32,
+        shelter: &Shelter,
+    ) {
         let debug_y = screen_y + height / 2.0 + 20.0;
 
         let protection_text = format!("{}%", (shelter.effective_protection() * 100.0) as u32);
-        draw_text(&protection_text, screen_x - 15.0, debug_y, 16.0, WHITE);
+        queue.text(&protection_text, screen_x - 15.0, debug_y, 16.0, WHITE);
 
         let occupancy_text = format!(
             "{}/{}",
             shelter.occupant_count(),
             shelter.shelter_type.max_capacity()
         );
-        draw_text(
+        queue.text(
             &occupancy_text,
             screen_x - 10.0,
             debug_y + 15.0,
@@ -869,6 +1229,7 @@ pub struct ShelterInfo {
     pub position: Position,
     pub shelter_type: ShelterType,
     pub condition: ShelterCondition,
+    pub upgrade_level: u32,
     pub protection_level: f32,
     pub occupancy: String,
     pub distance: f32,
@@ -885,25 +1246,96 @@ impl ShelterInfo {
             .unwrap_or(self.shelter_type.display_name());
         let protection_pct = (self.protection_level * 100.0) as u32;
 
+        let reinforced = if self.upgrade_level > 0 {
+            format!(", reinforced x{}", self.upgrade_level)
+        } else {
+            String::new()
+        };
+
         format!(
-            "{} - {}% protection, {} occupants, {:.0}m away",
-            name, protection_pct, self.occupancy, self.distance
+            "{} ({}{}) - {}% protection, {} occupants, {:.0}m away",
+            name,
+            self.condition.display_name(),
+            reinforced,
+            protection_pct,
+            self.occupancy,
+            self.distance
         )
     }
 }
 
+/// A finished piece of work on a shelter
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShelterWorkEvent {
+    Repaired {
+        shelter: String,
+        condition: ShelterCondition,
+    },
+    Reinforced {
+        shelter: String,
+        level: u32,
+    },
+}
+
+impl ShelterWorkEvent {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            ShelterWorkEvent::Repaired { shelter, condition } => format!(
+                "You patch up the {} - it is now in {} condition",
+                shelter,
+                condition.display_name().to_lowercase()
+            ),
+            ShelterWorkEvent::Reinforced { shelter, level }
+                if *level >= Shelter::MAX_UPGRADE_LEVEL =>
+            {
+                format!("The {} is as strong as you can make it", shelter)
+            }
+            ShelterWorkEvent::Reinforced { shelter, level } => format!(
+                "You reinforce the {} against the sun (level {})",
+                shelter, level
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rendering::DrawCommand;
+
+    #[test]
+    fn test_shelters_queue_draws_on_terrain_layer() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            100.0,
+            200.0,
+            None,
+            None,
+        );
+
+        let mut queue = DrawQueue::default();
+        ShelterSystem::render_shelters(&mut queue, &entities, 0.0, 0.0, 1.0, false);
+        assert!(!queue.is_empty());
+        assert_eq!(queue.layer(RenderLayer::Terrain).count(), queue.len());
+        // The building's roof
+        assert!(queue
+            .layer(RenderLayer::Terrain)
+            .any(|command| matches!(command, DrawCommand::Triangle { .. })));
+    }
 
     #[test]
     fn test_shelter_spawning() {
         let mut entities = Vec::new();
-        let mut next_id = 0;
+        let mut ids = EntityAllocator::default();
 
         let shelter_id = ShelterSystem::spawn_shelter(
             &mut entities,
-            &mut next_id,
+            &mut ids,
             ShelterType::Cave,
             100.0,
             200.0,
@@ -923,12 +1355,12 @@ mod tests {
     #[test]
     fn test_shelter_protection_calculation() {
         let mut entities = Vec::new();
-        let mut next_id = 0;
+        let mut ids = EntityAllocator::default();
 
         // Create a shelter and an entity
         let shelter_id = ShelterSystem::spawn_shelter(
             &mut entities,
-            &mut next_id,
+            &mut ids,
             ShelterType::Cave,
             0.0,
             0.0,
@@ -936,8 +1368,7 @@ mod tests {
             None,
         );
 
-        let entity_id = next_id;
-        next_id += 1;
+        let entity_id = ids.allocate();
 
         let mut entity = GameEntity {
             id: entity_id,
@@ -955,6 +1386,17 @@ mod tests {
             shelter: None,
             shelter_occupancy: Some(ShelterOccupancy::new()),
             color: RED,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         };
 
         entities.push(entity);
@@ -981,12 +1423,12 @@ mod tests {
     #[test]
     fn test_find_nearest_shelter() {
         let mut entities = Vec::new();
-        let mut next_id = 0;
+        let mut ids = EntityAllocator::default();
 
         // Create two shelters at different distances
         ShelterSystem::spawn_shelter(
             &mut entities,
-            &mut next_id,
+            &mut ids,
             ShelterType::Cave,
             50.0,
             0.0,
@@ -995,7 +1437,7 @@ mod tests {
         );
         ShelterSystem::spawn_shelter(
             &mut entities,
-            &mut next_id,
+            &mut ids,
             ShelterType::Shed,
             200.0,
             0.0,
@@ -1020,11 +1462,10 @@ mod tests {
     #[test]
     fn test_shelter_info_generation() {
         let mut entities = Vec::new();
-        let mut next_id = 0;
+        let mut ids = EntityAllocator::default();
 
         // Create player
-        let player_id = next_id;
-        next_id += 1;
+        let player_id = ids.allocate();
         let player = GameEntity {
             id: player_id,
             position: Position { x: 0.0, y: 0.0 },
@@ -1041,13 +1482,24 @@ mod tests {
             shelter: None,
             shelter_occupancy: None,
             color: RED,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         };
         entities.push(player);
 
         // Create discovered shelter
         let shelter_id = ShelterSystem::spawn_shelter(
             &mut entities,
-            &mut next_id,
+            &mut ids,
             ShelterType::Building,
             30.0,
             40.0,
@@ -1068,4 +1520,72 @@ mod tests {
         assert_eq!(shelter_info[0].name, Some("Town Hall".to_string()));
         assert!(shelter_info[0].discovered);
     }
+
+    #[test]
+    fn test_work_repairs_then_reinforces_for_blood() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = crate::systems::WorldSystem::spawn_player(&mut entities, &mut ids);
+        let shelter_id = ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Cave,
+            100.0,
+            700.0,
+            None,
+            None,
+        );
+        let shelter = |entities: &[GameEntity]| {
+            entities
+                .iter()
+                .find(|e| e.id == shelter_id)
+                .and_then(|e| e.shelter.clone())
+                .unwrap()
+        };
+        entities
+            .iter_mut()
+            .find(|e| e.id == shelter_id)
+            .and_then(|e| e.shelter.as_mut())
+            .unwrap()
+            .condition = ShelterCondition::Good;
+
+        // Outside, holding interact does nothing
+        assert!(ShelterSystem::work_on_shelter(&mut entities, player_id, 1.0).is_none());
+        let player = entities.iter_mut().find(|e| e.id == player_id).unwrap();
+        let blood_before = player.blood_meter.as_ref().unwrap().current;
+        player
+            .shelter_occupancy
+            .get_or_insert_with(ShelterOccupancy::new)
+            .enter_shelter(shelter_id, 0.0);
+
+        let halfway = Shelter::REPAIR_SECONDS / 2.0;
+        assert!(ShelterSystem::work_on_shelter(&mut entities, player_id, halfway).is_none());
+        assert_eq!(shelter(&entities).work_progress, 0.5);
+        let event = ShelterSystem::work_on_shelter(&mut entities, player_id, halfway).unwrap();
+        assert_eq!(
+            event,
+            ShelterWorkEvent::Repaired {
+                shelter: "Cave".to_string(),
+                condition: ShelterCondition::Pristine
+            }
+        );
+        let pristine = shelter(&entities).effective_protection();
+
+        let event =
+            ShelterSystem::work_on_shelter(&mut entities, player_id, Shelter::REINFORCE_SECONDS)
+                .unwrap();
+        assert!(matches!(
+            event,
+            ShelterWorkEvent::Reinforced { level: 1, .. }
+        ));
+        assert!(shelter(&entities).effective_protection() > pristine);
+        assert!(shelter(&entities)
+            .get_status_text()
+            .contains("Pristine, Reinforced I"));
+
+        let player = entities.iter().find(|e| e.id == player_id).unwrap();
+        let spent = blood_before - player.blood_meter.as_ref().unwrap().current;
+        let worked = Shelter::REPAIR_SECONDS + Shelter::REINFORCE_SECONDS;
+        assert!((spent - worked * WORK_BLOOD_PER_SECOND).abs() < 0.01);
+    }
 }
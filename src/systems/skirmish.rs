@@ -0,0 +1,272 @@
+//! Skirmish System Module
+//!
+//! Fights that break out between clan members and hostile infected away from
+//! the player. Both sides trade blows through the combat system, and each
+//! fight is tracked so it can be pinged on the minimap and reported in the
+//! event log with where it is happening.
+
+use crate::components::*;
+use crate::systems::combat::{CombatSystem, DamageEvent};
+use crate::systems::spatial::SpatialGrid;
+
+/// Distance at which a clan member and an infected come to blows
+const ENGAGE_RANGE: f32 = 45.0;
+/// Fights closer than this to the player are the player's own business
+const DISTANT_RANGE: f32 = 350.0;
+/// Fighting this close to a known skirmish is part of it
+const SKIRMISH_RADIUS: f32 = 150.0;
+/// Seconds without anyone in reach of each other before a skirmish is over
+const QUIET_SECONDS: f32 = 6.0;
+/// Seconds a finished skirmish stays pinged on the minimap
+const PING_SECONDS: f32 = 10.0;
+
+/// A fight the player is not part of
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skirmish {
+    pub clan_name: String,
+    /// Where the fighting is, following the combatants as they move
+    pub position: Position,
+    /// Seconds since anyone was last in reach of an enemy
+    pub quiet_time: f32,
+    pub casualties: u32,
+    pub ended: bool,
+}
+
+impl Skirmish {
+    /// Whether the skirmish is still worth a minimap ping
+    pub fn is_pinged(&self) -> bool {
+        !self.ended || self.quiet_time < QUIET_SECONDS + PING_SECONDS
+    }
+}
+
+/// Skirmishes being fought or recently over
+#[derive(Debug, Clone, Default)]
+pub struct SkirmishTracker {
+    pub skirmishes: Vec<Skirmish>,
+}
+
+impl SkirmishTracker {
+    /// The most recently started skirmish still on the minimap
+    pub fn latest(&self) -> Option<&Skirmish> {
+        self.skirmishes
+            .iter()
+            .rev()
+            .find(|skirmish| skirmish.is_pinged())
+    }
+}
+
+/// Skirmish news for the event log
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkirmishEvent {
+    /// `bearing` is where the fight is from the player, e.g. "400m north"
+    Started { clan_name: String, bearing: String },
+    Ended {
+        clan_name: String,
+        bearing: String,
+        casualties: u32,
+    },
+}
+
+impl SkirmishEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            SkirmishEvent::Started { clan_name, bearing } => {
+                format!(
+                    "The {} are fighting infected {} of you.",
+                    clan_name, bearing
+                )
+            }
+            SkirmishEvent::Ended {
+                clan_name,
+                bearing,
+                casualties,
+            } => format!(
+                "The fighting between the {} and the infected {} of you is over. {} fell.",
+                clan_name, bearing, casualties
+            ),
+        }
+    }
+}
+
+/// Skirmish system responsible for fights between clans and infected
+pub struct SkirmishSystem;
+
+impl SkirmishSystem {
+    /// Let clan members and infected in reach of each other fight, and track
+    /// the skirmishes this starts and ends
+    pub fn update(
+        tracker: &mut SkirmishTracker,
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+        delta_time: f32,
+    ) -> Vec<SkirmishEvent> {
+        let Some(player_pos) = entities
+            .iter()
+            .find(|entity| entity.id == player_id)
+            .map(|player| player.position)
+        else {
+            return Vec::new();
+        };
+
+        // Pairs of clan member and infected close enough to fight
+        let mut fights = Vec::new();
+        let mut nearby = Vec::new();
+        for member in entities.iter() {
+            let EntityType::ClanMember(clan_name) = &member.entity_type else {
+                continue;
+            };
+            if !Self::can_fight(member)
+                || matches!(member.ai_state, AIState::Follower(_) | AIState::Squad)
+                || member.position.distance_to(&player_pos) < DISTANT_RANGE
+            {
+                continue;
+            }
+            grid.query_radius_into(entities, member.position, ENGAGE_RANGE, &mut nearby);
+            let enemy = nearby.iter().map(|&index| &entities[index]).find(|other| {
+                other.entity_type == EntityType::HostileInfected && Self::can_fight(other)
+            });
+            if let Some(enemy) = enemy {
+                fights.push((member.id, enemy.id, clan_name.clone(), member.position));
+            }
+        }
+
+        for skirmish in &mut tracker.skirmishes {
+            skirmish.quiet_time += delta_time;
+        }
+
+        let mut events = Vec::new();
+        for (member_id, enemy_id, clan_name, position) in fights {
+            let mut casualties = 0;
+            for (attacker, target) in [(member_id, enemy_id), (enemy_id, member_id)] {
+                let blow = CombatSystem::resolve_attack(
+                    entities,
+                    attacker,
+                    target,
+                    game_time,
+                    damage_events,
+                );
+                casualties += u32::from(blow.is_some_and(|blow| blow.killed));
+            }
+
+            let existing = tracker.skirmishes.iter_mut().find(|skirmish| {
+                !skirmish.ended
+                    && skirmish.clan_name == clan_name
+                    && skirmish.position.distance_to(&position) <= SKIRMISH_RADIUS
+            });
+            match existing {
+                Some(skirmish) => {
+                    skirmish.position = position;
+                    skirmish.quiet_time = 0.0;
+                    skirmish.casualties += casualties;
+                }
+                None => {
+                    events.push(SkirmishEvent::Started {
+                        clan_name: clan_name.clone(),
+                        bearing: Self::bearing(player_pos, position),
+                    });
+                    tracker.skirmishes.push(Skirmish {
+                        clan_name,
+                        position,
+                        quiet_time: 0.0,
+                        casualties,
+                        ended: false,
+                    });
+                }
+            }
+        }
+
+        for skirmish in &mut tracker.skirmishes {
+            if !skirmish.ended && skirmish.quiet_time >= QUIET_SECONDS {
+                skirmish.ended = true;
+                events.push(SkirmishEvent::Ended {
+                    clan_name: skirmish.clan_name.clone(),
+                    bearing: Self::bearing(player_pos, skirmish.position),
+                    casualties: skirmish.casualties,
+                });
+            }
+        }
+        tracker.skirmishes.retain(Skirmish::is_pinged);
+
+        events
+    }
+
+    /// Distance and direction of a place from the player, e.g. "400m north"
+    pub fn bearing(from: Position, to: Position) -> String {
+        let distance = (from.distance_to(&to) / 10.0).round() * 10.0;
+        format!("{}m {}", distance, from.compass_direction_to(&to))
+    }
+
+    fn can_fight(entity: &GameEntity) -> bool {
+        entity
+            .health
+            .as_ref()
+            .is_some_and(|health| health.is_alive())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+    use macroquad::prelude::WHITE;
+
+    #[test]
+    fn test_distant_fights_start_and_end_skirmishes() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(100.0, 660.0);
+        WorldSystem::spawn_clan_member(
+            &mut entities,
+            &mut ids,
+            "Night-Bloods",
+            900.0,
+            660.0,
+            WHITE,
+        );
+        WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 920.0, 660.0);
+        let mut tracker = SkirmishTracker::default();
+        let mut damage_events = Vec::new();
+        let grid = SpatialGrid::from_entities(&entities);
+
+        let events = SkirmishSystem::update(
+            &mut tracker,
+            &mut entities,
+            &grid,
+            0,
+            10.0,
+            &mut damage_events,
+            0.1,
+        );
+        assert_eq!(
+            events,
+            vec![SkirmishEvent::Started {
+                clan_name: "Night-Bloods".into(),
+                bearing: "800m east".into(),
+            }]
+        );
+        assert!(!damage_events.is_empty());
+        assert_eq!(tracker.latest().unwrap().position.x, 900.0);
+
+        // Fighting until one side falls, after which the skirmish goes quiet
+        let mut ended = Vec::new();
+        for step in 0..600 {
+            ended.extend(SkirmishSystem::update(
+                &mut tracker,
+                &mut entities,
+                &grid,
+                0,
+                11.0 + step as f32,
+                &mut damage_events,
+                0.1,
+            ));
+        }
+        assert!(matches!(
+            ended.as_slice(),
+            [SkirmishEvent::Ended { casualties: 1, .. }]
+        ));
+    }
+}
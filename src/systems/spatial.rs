@@ -0,0 +1,216 @@
+//! Spatial System Module
+//!
+//! A uniform grid over the world that buckets entities by position so that
+//! radius queries only look at nearby cells instead of scanning every entity.
+//! The grid stores indices into the entity Vec and is rebuilt once per frame.
+
+use crate::components::*;
+use std::collections::HashMap;
+
+/// Default cell size in world units; roughly the largest common query radius
+const DEFAULT_CELL_SIZE: f32 = 100.0;
+
+/// Uniform grid of entity indices used for proximity queries
+#[derive(Debug, Clone)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    entity_count: usize,
+}
+
+impl SpatialGrid {
+    /// Create an empty grid with the given cell size
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+            entity_count: 0,
+        }
+    }
+
+    /// Build a grid for a set of entities
+    pub fn from_entities(entities: &[GameEntity]) -> Self {
+        let mut grid = Self::default();
+        grid.rebuild(entities);
+        grid
+    }
+
+    /// Re-bucket every entity by its current position.
+    /// Cell allocations are kept between frames to avoid churn.
+    pub fn rebuild(&mut self, entities: &[GameEntity]) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+
+        for (index, entity) in entities.iter().enumerate() {
+            let cell = self.cell_of(&entity.position);
+            self.cells.entry(cell).or_default().push(index);
+        }
+
+        self.entity_count = entities.len();
+    }
+
+    /// Indices of entities within `radius` of `center`, in ascending order.
+    ///
+    /// Distances are checked against current positions, so entities that
+    /// moved since the last rebuild are still filtered correctly as long as
+    /// they have not left their cell.
+    pub fn query_radius(
+        &self,
+        entities: &[GameEntity],
+        center: Position,
+        radius: f32,
+    ) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.query_radius_into(entities, center, radius, &mut found);
+        found
+    }
+
+    /// Same as [`query_radius`](Self::query_radius), but fills `found`
+    /// instead of allocating, for callers that query once per entity
+    pub fn query_radius_into(
+        &self,
+        entities: &[GameEntity],
+        center: Position,
+        radius: f32,
+        found: &mut Vec<usize>,
+    ) {
+        let radius_squared = radius * radius;
+        self.candidates_into(center, radius, found);
+        found.retain(|&index| {
+            entities.get(index).is_some_and(|entity| {
+                let dx = entity.position.x - center.x;
+                let dy = entity.position.y - center.y;
+                dx * dx + dy * dy <= radius_squared
+            })
+        });
+
+        // Keep results in entity order so "first match" logic stays stable
+        found.sort_unstable();
+    }
+
+    /// Indices of every entity in the cells that a circle of `radius` around
+    /// `center` touches, unordered and without a distance check, for callers
+    /// that keep their own copy of positions
+    pub fn candidates_into(&self, center: Position, radius: f32, found: &mut Vec<usize>) {
+        found.clear();
+        let (min_x, min_y) = self.cell_of(&Position::new(center.x - radius, center.y - radius));
+        let (max_x, max_y) = self.cell_of(&Position::new(center.x + radius, center.y + radius));
+
+        for cell_x in min_x..=max_x {
+            for cell_y in min_y..=max_y {
+                if let Some(cell) = self.cells.get(&(cell_x, cell_y)) {
+                    found.extend_from_slice(cell);
+                }
+            }
+        }
+    }
+
+    /// Number of entities indexed by the last rebuild
+    pub fn len(&self) -> usize {
+        self.entity_count
+    }
+
+    /// Whether the last rebuild indexed no entities
+    pub fn is_empty(&self) -> bool {
+        self.entity_count == 0
+    }
+
+    fn cell_of(&self, position: &Position) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::prelude::*;
+
+    fn create_entity(id: u32, x: f32, y: f32) -> GameEntity {
+        GameEntity {
+            id,
+            position: Position::new(x, y),
+            velocity: None,
+            entity_type: EntityType::Animal,
+            health: Some(Health::new(50.0)),
+            combat_stats: None,
+            ai_state: AIState::Idle,
+            blood_meter: None,
+            vampire_abilities: None,
+            shelter: None,
+            shelter_occupancy: None,
+            color: BROWN,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        }
+    }
+
+    #[test]
+    fn test_query_matches_linear_scan() {
+        let entities: Vec<GameEntity> = (0..200)
+            .map(|i| create_entity(i, (i * 37 % 1600) as f32, (640 + i * 53 % 560) as f32))
+            .collect();
+        let grid = SpatialGrid::from_entities(&entities);
+        let center = Position::new(800.0, 900.0);
+
+        let expected: Vec<usize> = entities
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.position.distance_to(&center) <= 250.0)
+            .map(|(i, _)| i)
+            .collect();
+
+        assert_eq!(grid.query_radius(&entities, center, 250.0), expected);
+    }
+
+    #[test]
+    fn test_query_handles_cell_boundaries_and_negatives() {
+        let entities = vec![
+            create_entity(1, 99.0, 0.0),
+            create_entity(2, 101.0, 0.0),
+            create_entity(3, -5.0, 0.0),
+        ];
+        let grid = SpatialGrid::from_entities(&entities);
+
+        let found = grid.query_radius(&entities, Position::new(100.0, 0.0), 10.0);
+        assert_eq!(found, vec![0, 1]);
+
+        let found = grid.query_radius(&entities, Position::new(0.0, 0.0), 6.0);
+        assert_eq!(found, vec![2]);
+    }
+
+    #[test]
+    fn test_rebuild_tracks_movement() {
+        let mut entities = vec![create_entity(1, 50.0, 50.0)];
+        let mut grid = SpatialGrid::from_entities(&entities);
+
+        entities[0].position = Position::new(950.0, 950.0);
+        grid.rebuild(&entities);
+
+        assert!(grid
+            .query_radius(&entities, Position::new(50.0, 50.0), 20.0)
+            .is_empty());
+        assert_eq!(
+            grid.query_radius(&entities, Position::new(950.0, 950.0), 20.0),
+            vec![0]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Status System Module
+//!
+//! Keeps each entity's `VisualState` in step with the game: counts down timed
+//! effects such as damage flashes and charms, starts the death animation
+//! when an entity dies, and marks members of clans allied with the player
+//! so the renderer can outline them. Clan members won over into the
+//! retinue shimmer with the charm for a moment.
+
+use crate::components::*;
+use std::collections::HashMap;
+
+/// Seconds a newly recruited follower shimmers with the charm
+pub const RECRUIT_CHARM_SECONDS: f32 = 3.0;
+
+/// Status system responsible for per-entity visual status effects
+pub struct StatusSystem;
+
+impl StatusSystem {
+    /// Update visual status for all entities
+    pub fn update_visual_states(
+        entities: &mut [GameEntity],
+        clans: &HashMap<String, Clan>,
+        delta_time: f32,
+    ) {
+        for entity in entities.iter_mut() {
+            entity.visual_state.update(delta_time);
+
+            // However it died, the body stops and begins to fall
+            if matches!(entity.ai_state, AIState::Dead) && entity.visual_state.death.is_none() {
+                entity.visual_state.death =
+                    Some(DeathAnimation::new(DeathStyle::for_entity(entity)));
+                if let Some(velocity) = &mut entity.velocity {
+                    *velocity = Velocity::zero();
+                }
+            }
+
+            entity.visual_state.allied = match &entity.entity_type {
+                EntityType::ClanLeader(clan_name) | EntityType::ClanMember(clan_name) => {
+                    clans.get(clan_name).is_some_and(|clan| {
+                        clan.is_allied && !matches!(entity.ai_state, AIState::Hostile)
+                    })
+                }
+                _ => false,
+            };
+        }
+    }
+
+    /// Charm an entity for a duration, making it shimmer blue
+    pub fn apply_charm(entities: &mut [GameEntity], entity_id: u32, duration: f32) -> bool {
+        if let Some(entity) = entities.iter_mut().find(|e| e.id == entity_id) {
+            entity.visual_state.charm(duration);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::prelude::*;
+
+    fn create_clan_member(clan_name: &str) -> GameEntity {
+        GameEntity {
+            id: 1,
+            position: Position::new(100.0, 700.0),
+            velocity: Some(Velocity::zero()),
+            entity_type: EntityType::ClanMember(clan_name.to_string()),
+            health: Some(Health::new(80.0)),
+            combat_stats: None,
+            ai_state: AIState::Idle,
+            blood_meter: None,
+            vampire_abilities: None,
+            shelter: None,
+            shelter_occupancy: None,
+            color: BLUE,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        }
+    }
+
+    #[test]
+    fn test_allied_members_are_marked() {
+        let mut clans = HashMap::new();
+        let mut clan = Clan::new("Night-Bloods", "Silentfang", 10);
+        clan.is_allied = true;
+        clans.insert(clan.name.clone(), clan);
+
+        let mut entities = vec![create_clan_member("Night-Bloods")];
+        StatusSystem::update_visual_states(&mut entities, &clans, 0.016);
+        assert!(entities[0].visual_state.allied);
+
+        clans.get_mut("Night-Bloods").unwrap().is_allied = false;
+        StatusSystem::update_visual_states(&mut entities, &clans, 0.016);
+        assert!(!entities[0].visual_state.allied);
+    }
+
+    #[test]
+    fn test_timed_effects_expire() {
+        let clans = HashMap::new();
+        let mut entities = vec![create_clan_member("Night-Bloods")];
+
+        entities[0].visual_state.flash_damage();
+        assert!(StatusSystem::apply_charm(&mut entities, 1, 1.0));
+        assert!(entities[0].visual_state.has_tint());
+
+        StatusSystem::update_visual_states(&mut entities, &clans, 2.0);
+        assert!(!entities[0].visual_state.has_tint());
+    }
+
+    #[test]
+    fn test_death_animation_plays_once() {
+        let clans = HashMap::new();
+        let mut entities = vec![create_clan_member("Night-Bloods")];
+        entities[0].velocity = Some(Velocity::new(40.0, 0.0));
+        entities[0].ai_state = AIState::Dead;
+
+        StatusSystem::update_visual_states(&mut entities, &clans, 0.016);
+        let death = entities[0].visual_state.death.unwrap();
+        assert_eq!(death.style, DeathStyle::Collapse);
+        assert!(!death.is_finished());
+        assert_eq!(entities[0].velocity.unwrap().x, 0.0);
+
+        StatusSystem::update_visual_states(&mut entities, &clans, DeathAnimation::DURATION);
+        assert!(entities[0].visual_state.death.unwrap().is_finished());
+    }
+}
@@ -0,0 +1,209 @@
+//! Stealth System Module
+//!
+//! Decides how well hidden the player is. Holding the sneak key slows the
+//! vampire to a creep and shrinks the range at which hostile AI notices them;
+//! in shadow or at night that reduction grows with the shadow_movement
+//! ability. Hostiles must also see the player to acquire them: solid
+//! shelters block their line of sight.
+
+use crate::components::*;
+
+/// Share of detection range sneaking takes away
+pub const SNEAK_REDUCTION: f32 = 0.5;
+
+/// Movement speed while sneaking, relative to walking
+pub const SNEAK_SPEED: f32 = 0.5;
+
+/// How much each point of shadow_movement adds to the reduction in shadow
+const SHADOW_SCALING: f32 = 0.5;
+
+/// Detection range never shrinks below this share, however well hidden
+const MIN_DETECTION: f32 = 0.15;
+
+/// Seconds of sneaking past unaware hostiles that teach shadow movement
+pub const PRACTICE_SECONDS: f32 = 10.0;
+
+/// How hidden the player is where they stand
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StealthProfile {
+    pub sneaking: bool,
+    /// In shade or under the night sky
+    pub shadowed: bool,
+    /// Scale applied to AI detection ranges
+    pub multiplier: f32,
+}
+
+impl StealthProfile {
+    pub fn label(&self) -> &'static str {
+        match (self.sneaking, self.shadowed) {
+            (true, true) => "Hidden",
+            (true, false) => "Sneaking",
+            (false, _) => "Visible",
+        }
+    }
+}
+
+/// The footprint of something hostiles cannot see through
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SightBlocker {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl SightBlocker {
+    /// Whether the segment from `from` to `to` passes through this footprint
+    pub fn blocks(&self, from: &Position, to: &Position) -> bool {
+        let (mut enter, mut exit) = (0.0f32, 1.0f32);
+        for (start, delta, min, max) in [
+            (from.x, to.x - from.x, self.min.x, self.max.x),
+            (from.y, to.y - from.y, self.min.y, self.max.y),
+        ] {
+            if delta.abs() < f32::EPSILON {
+                if start < min || start > max {
+                    return false;
+                }
+                continue;
+            }
+            let (a, b) = ((min - start) / delta, (max - start) / delta);
+            enter = enter.max(a.min(b));
+            exit = exit.min(a.max(b));
+            if enter > exit {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Stealth system responsible for hiding the player from hostile eyes
+pub struct StealthSystem;
+
+impl StealthSystem {
+    /// How hidden the player is. `exposure` is how much sunlight reaches
+    /// them (1.0 in the open), as worked out by the exposure system.
+    pub fn profile(player: &GameEntity, is_night: bool, exposure: f32) -> StealthProfile {
+        let abilities = player.vampire_abilities.as_ref();
+        let sneaking = abilities.is_some_and(|abilities| abilities.ability_state.is_sneaking());
+        let shadow_movement = abilities.map_or(0.0, |abilities| abilities.shadow_movement);
+        let shadowed = is_night || exposure < 1.0;
+
+        let mut reduction = if sneaking { SNEAK_REDUCTION } else { 0.0 };
+        if shadowed {
+            reduction *= 1.0 + shadow_movement * SHADOW_SCALING;
+        }
+
+        StealthProfile {
+            sneaking,
+            shadowed,
+            multiplier: (1.0 - reduction).max(MIN_DETECTION),
+        }
+    }
+
+    /// The footprints of every solid shelter, which hostiles cannot see past
+    pub fn sight_blockers(entities: &[GameEntity]) -> Vec<SightBlocker> {
+        entities
+            .iter()
+            .filter(|entity| entity.entity_type == EntityType::Shelter)
+            .filter_map(|entity| match entity.collider {
+                Some(Collider::Box {
+                    half_width,
+                    half_height,
+                }) => Some(SightBlocker {
+                    min: Position::new(
+                        entity.position.x - half_width,
+                        entity.position.y - half_height,
+                    ),
+                    max: Position::new(
+                        entity.position.x + half_width,
+                        entity.position.y + half_height,
+                    ),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether nothing in `blockers` stands between the two positions
+    pub fn has_line_of_sight(from: &Position, to: &Position, blockers: &[SightBlocker]) -> bool {
+        !blockers.iter().any(|blocker| blocker.blocks(from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{AISystem, NavGrid, ShelterSystem, SpatialGrid, WorldSystem};
+
+    #[test]
+    fn test_sneaking_in_shadow_and_walls_hide_the_player() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(400.0, 900.0);
+
+        // Sneaking halves detection in daylight and does more in the dark as
+        // shadow movement grows
+        let open = StealthSystem::profile(&entities[0], false, 1.0);
+        assert_eq!(open.multiplier, 1.0);
+        let abilities = entities[0].vampire_abilities.as_mut().unwrap();
+        abilities.ability_state.sneaking = true;
+        assert_eq!(
+            StealthSystem::profile(&entities[0], false, 1.0).multiplier,
+            0.5
+        );
+        let abilities = entities[0].vampire_abilities.as_mut().unwrap();
+        abilities.shadow_movement = 1.0;
+        let hidden = StealthSystem::profile(&entities[0], true, 1.0);
+        assert_eq!(hidden.label(), "Hidden");
+        assert!((hidden.multiplier - 0.25).abs() < 1e-6);
+
+        // An infected 150 away notices a sneaking player only in the open...
+        let infected = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 550.0, 900.0);
+        let chases = |entities: &mut Vec<GameEntity>, scale: f32| {
+            let blockers = StealthSystem::sight_blockers(entities);
+            let mut grid = SpatialGrid::default();
+            grid.rebuild(entities);
+            let mut nav = NavGrid::build(entities, &WorldBounds::default(), 0);
+            AISystem::update_all_ai(
+                entities,
+                &grid,
+                player_id,
+                scale,
+                &blockers,
+                false,
+                &WorldBounds::default(),
+                &mut nav,
+                0,
+                None,
+                0.1,
+            );
+            let hunter = entities.iter().find(|e| e.id == infected).unwrap();
+            hunter.velocity.as_ref().is_some_and(|v| v.x < 0.0)
+        };
+        assert!(chases(&mut entities, open.multiplier));
+        assert!(!chases(&mut entities, hidden.multiplier));
+
+        // ...and not at all with a building between them
+        ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            475.0,
+            900.0,
+            None,
+            None,
+        );
+        let blockers = StealthSystem::sight_blockers(&entities);
+        assert!(!StealthSystem::has_line_of_sight(
+            &Position::new(550.0, 900.0),
+            &Position::new(400.0, 900.0),
+            &blockers
+        ));
+        assert!(StealthSystem::has_line_of_sight(
+            &Position::new(550.0, 1100.0),
+            &Position::new(400.0, 1100.0),
+            &blockers
+        ));
+        assert!(!chases(&mut entities, open.multiplier));
+    }
+}
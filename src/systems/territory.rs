@@ -0,0 +1,306 @@
+//! Territory System Module
+//!
+//! Lets the player take territories for their own. A territory is captured by
+//! standing in it with no hostiles left inside and holding it for
+//! `Territory::HOURS_TO_CAPTURE` in-game hours; leaving or letting hostiles
+//! back in loses the hold. Captured territories pay blood once a day.
+//! Territories the player has not taken belong to whichever clan has the
+//! most members inside, and are contested while rivals share them.
+
+use crate::components::*;
+use crate::systems::PlayerSystem;
+use std::collections::HashMap;
+
+/// Who holds a territory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerritoryController {
+    Player,
+    Clan(String),
+}
+
+/// Who holds a territory and whether anyone disputes it
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TerritoryControl {
+    pub controller: Option<TerritoryController>,
+    /// More than one side is present: the player, clans or the infected
+    pub contested: bool,
+}
+
+/// Territory system responsible for capture and daily income
+pub struct TerritorySystem;
+
+impl TerritorySystem {
+    /// Advance the player's hold on the territory they stand in by `hours`
+    /// of in-game time, and drop any hold they have walked away from
+    pub fn update(
+        manager: &mut TerritoryManager,
+        entities: &[GameEntity],
+        player_id: u32,
+        hours: f32,
+    ) -> Vec<TerritoryEvent> {
+        let mut events = Vec::new();
+        let Some(player) = EntityFinder::by_id(entities, player_id) else {
+            return events;
+        };
+        let player_pos = player.position;
+
+        for territory in manager.territories.iter_mut() {
+            if territory.captured {
+                continue;
+            }
+
+            if !territory.contains(&player_pos) {
+                if territory.hold_hours > 0.0 {
+                    territory.hold_hours = 0.0;
+                    events.push(TerritoryEvent::Abandoned(territory.name.clone()));
+                }
+                continue;
+            }
+
+            if Self::hostiles_in(territory, entities) > 0 {
+                if territory.hold_hours > 0.0 {
+                    territory.hold_hours = 0.0;
+                    events.push(TerritoryEvent::Contested(territory.name.clone()));
+                }
+                continue;
+            }
+
+            if territory.hold_hours == 0.0 {
+                events.push(TerritoryEvent::HoldStarted(territory.name.clone()));
+            }
+            territory.hold_hours += hours;
+            if territory.hold_hours >= Territory::HOURS_TO_CAPTURE {
+                territory.captured = true;
+                territory.hold_hours = Territory::HOURS_TO_CAPTURE;
+                events.push(TerritoryEvent::Captured {
+                    name: territory.name.clone(),
+                    blood_income: territory.blood_income,
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Number of living hostiles inside a territory
+    pub fn hostiles_in(territory: &Territory, entities: &[GameEntity]) -> usize {
+        entities
+            .iter()
+            .filter(|entity| {
+                matches!(entity.ai_state, AIState::Hostile) && territory.contains(&entity.position)
+            })
+            .count()
+    }
+
+    /// Who controls a territory: the player once captured, otherwise the
+    /// clan with the most living members inside. It is contested while more
+    /// than one side is present, counting a player still taking it and any
+    /// hostile infected.
+    pub fn control(territory: &Territory, entities: &[GameEntity]) -> TerritoryControl {
+        let mut clans: HashMap<&str, usize> = HashMap::new();
+        for entity in entities {
+            let (EntityType::ClanLeader(clan) | EntityType::ClanMember(clan)) = &entity.entity_type
+            else {
+                continue;
+            };
+            let alive = entity
+                .health
+                .as_ref()
+                .is_some_and(|health| health.current > 0.0);
+            if alive && territory.contains(&entity.position) {
+                *clans.entry(clan.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        let player_present = territory.captured || territory.hold_hours > 0.0;
+        let infected_present = Self::hostiles_in(territory, entities) > 0;
+        let sides = clans.len() + usize::from(player_present) + usize::from(infected_present);
+
+        let controller = if territory.captured {
+            Some(TerritoryController::Player)
+        } else {
+            clans
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(clan, _)| TerritoryController::Clan(clan.to_string()))
+        };
+        TerritoryControl {
+            controller,
+            contested: sides > 1,
+        }
+    }
+
+    /// Pay the day's blood from captured territories into the player's
+    /// blood meter, bottling whatever does not fit. Should be called once
+    /// per elapsed in-game day.
+    pub fn collect_income(
+        manager: &mut TerritoryManager,
+        entities: &mut [GameEntity],
+        player_id: u32,
+        current_day: u32,
+    ) -> Option<TerritoryEvent> {
+        if current_day <= manager.last_income_day {
+            return None;
+        }
+        manager.last_income_day = current_day;
+
+        let blood = manager.daily_income();
+        if blood <= 0.0 {
+            return None;
+        }
+        let player = entities.iter_mut().find(|entity| entity.id == player_id)?;
+        let overflow = player.blood_meter.as_mut()?.add_blood(blood);
+        let vials = PlayerSystem::bottle_overflow(player, overflow);
+
+        Some(TerritoryEvent::Income {
+            blood,
+            territories: manager.captured_count(),
+            vials,
+        })
+    }
+}
+
+/// Progress in taking and holding territory
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerritoryEvent {
+    /// The player began holding a cleared territory
+    HoldStarted(String),
+    /// The player left a territory before capturing it
+    Abandoned(String),
+    /// Hostiles moved into a territory being held
+    Contested(String),
+    Captured {
+        name: String,
+        blood_income: f32,
+    },
+    /// A day's blood from captured territories
+    Income {
+        blood: f32,
+        territories: usize,
+        vials: u32,
+    },
+}
+
+impl TerritoryEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            TerritoryEvent::HoldStarted(name) => format!(
+                "{} is clear. Hold it for {} hours to claim it.",
+                name,
+                Territory::HOURS_TO_CAPTURE
+            ),
+            TerritoryEvent::Abandoned(name) => format!("You abandoned your hold on {}.", name),
+            TerritoryEvent::Contested(name) => {
+                format!("Hostiles have entered {} - the hold is broken!", name)
+            }
+            TerritoryEvent::Captured { name, blood_income } => format!(
+                "{} is yours! It will yield {:.0} blood each day.",
+                name, blood_income
+            ),
+            TerritoryEvent::Income {
+                blood,
+                territories,
+                vials: 0,
+            } => format!("Your {} territories yield {:.0} blood.", territories, blood),
+            TerritoryEvent::Income {
+                blood,
+                territories,
+                vials,
+            } => format!(
+                "Your {} territories yield {:.0} blood ({} bottled into vials).",
+                territories, blood, vials
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+    use macroquad::prelude::{BLUE, RED};
+
+    #[test]
+    fn test_capture_and_income() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(300.0, 760.0);
+        let hostile_id = WorldSystem::spawn_hostile_infected(&mut entities, &mut ids, 320.0, 760.0);
+        let mut manager =
+            TerritoryManager::new(vec![Territory::new("Old Mill", 300.0, 760.0, 100.0, 20.0)]);
+
+        // Nothing is held while a hostile remains inside
+        let events = TerritorySystem::update(&mut manager, &entities, 0, 1.0);
+        assert!(events.is_empty());
+        assert_eq!(manager.territories[0].hold_hours, 0.0);
+
+        entities.retain(|entity| entity.id != hostile_id);
+        let events = TerritorySystem::update(&mut manager, &entities, 0, 1.0);
+        assert_eq!(
+            events,
+            vec![TerritoryEvent::HoldStarted("Old Mill".to_string())]
+        );
+
+        // Walking away loses the hold
+        entities[0].position = Position::new(600.0, 760.0);
+        TerritorySystem::update(&mut manager, &entities, 0, 1.0);
+        assert_eq!(manager.territories[0].hold_hours, 0.0);
+
+        entities[0].position = Position::new(300.0, 760.0);
+        for _ in 0..4 {
+            TerritorySystem::update(&mut manager, &entities, 0, 1.0);
+        }
+        assert!(manager.territories[0].captured);
+
+        // One payment per day
+        entities[0].blood_meter.as_mut().unwrap().current = 50.0;
+        let income = TerritorySystem::collect_income(&mut manager, &mut entities, 0, 1);
+        assert!(matches!(
+            income,
+            Some(TerritoryEvent::Income { blood, .. }) if blood == 20.0
+        ));
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 70.0);
+        assert!(TerritorySystem::collect_income(&mut manager, &mut entities, 0, 1).is_none());
+    }
+
+    #[test]
+    fn test_control_goes_to_the_largest_clan_and_rivals_contest_it() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(900.0, 760.0);
+        let mut territory = Territory::new("Old Mill", 300.0, 760.0, 100.0, 20.0);
+        assert_eq!(
+            TerritorySystem::control(&territory, &entities),
+            TerritoryControl::default()
+        );
+
+        for x in [280.0, 320.0] {
+            WorldSystem::spawn_clan_member(&mut entities, &mut ids, "Bloodfangs", x, 600.0, RED);
+            entities.last_mut().unwrap().position = Position::new(x, 760.0);
+        }
+        let control = TerritorySystem::control(&territory, &entities);
+        assert_eq!(
+            control.controller,
+            Some(TerritoryController::Clan("Bloodfangs".to_string()))
+        );
+        assert!(!control.contested);
+
+        // A rival clan's lone member disputes it without taking it
+        WorldSystem::spawn_clan_member(&mut entities, &mut ids, "Shadowclaws", 300.0, 600.0, BLUE);
+        entities.last_mut().unwrap().position = Position::new(300.0, 780.0);
+        let control = TerritorySystem::control(&territory, &entities);
+        assert_eq!(
+            control.controller,
+            Some(TerritoryController::Clan("Bloodfangs".to_string()))
+        );
+        assert!(control.contested);
+
+        // Once captured, it is the player's, though the clans still dispute it
+        territory.captured = true;
+        let control = TerritorySystem::control(&territory, &entities);
+        assert_eq!(control.controller, Some(TerritoryController::Player));
+        assert!(control.contested);
+    }
+}
@@ -2,9 +2,19 @@
 //!
 //! Manages the day/night cycle and time progression in the Vampire RPG.
 //! This system handles time advancement, sunlight calculations, and day counting.
+//! The current weather is kept here too, since clouds, rain and fog filter
+//! the sunlight.
 
+use crate::components::Weather;
 use serde::{Deserialize, Serialize};
 
+/// The moment day turns to night or back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeTransition {
+    Dawn,
+    Dusk,
+}
+
 /// Time system responsible for day/night cycle management
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeSystem {
@@ -16,6 +26,9 @@ pub struct TimeSystem {
     day_count: u32,
     /// Whether it's currently daytime
     is_day: bool,
+    /// The weather, which dims the sun
+    #[serde(default)]
+    weather: Weather,
 }
 
 impl TimeSystem {
@@ -26,6 +39,7 @@ impl TimeSystem {
             day_length: 120.0,  // 2 minutes per full day
             day_count: 0,
             is_day: false,
+            weather: Weather::Clear,
         }
     }
 
@@ -37,11 +51,14 @@ impl TimeSystem {
             day_length,
             day_count: 0,
             is_day,
+            weather: Weather::Clear,
         }
     }
 
-    /// Update the time system
-    pub fn update(&mut self, delta_time: f32) {
+    /// Update the time system, returning the transition if dawn or dusk just passed
+    pub fn update(&mut self, delta_time: f32) -> Option<TimeTransition> {
+        let was_day = self.is_day;
+
         // Advance time based on day length
         self.current_time += (delta_time / self.day_length) * 24.0;
 
@@ -53,6 +70,12 @@ impl TimeSystem {
 
         // Update day/night status (6 AM to 6 PM is day)
         self.is_day = self.current_time >= 6.0 && self.current_time < 18.0;
+
+        match (was_day, self.is_day) {
+            (false, true) => Some(TimeTransition::Dawn),
+            (true, false) => Some(TimeTransition::Dusk),
+            _ => None,
+        }
     }
 
     /// Get formatted time string (HH:MM)
@@ -92,8 +115,24 @@ impl TimeSystem {
         self.current_time
     }
 
+    /// Seconds of real time in one full day
+    pub fn day_length(&self) -> f32 {
+        self.day_length
+    }
+
+    /// In-game hours that pass in `seconds` of real time
+    pub fn hours_in(&self, seconds: f32) -> f32 {
+        seconds / self.day_length * 24.0
+    }
+
+    /// Seconds of real time that `hours` in-game hours take
+    pub fn seconds_in(&self, hours: f32) -> f32 {
+        hours / 24.0 * self.day_length
+    }
+
     /// Calculate sunlight intensity (0.0 to 1.0)
-    /// Returns 0.0 at night, peaks at 1.0 at noon
+    /// Returns 0.0 at night, peaks at noon: 1.0 under a clear sky, less
+    /// through cloud, rain or fog
     pub fn get_sunlight_intensity(&self) -> f32 {
         if !self.is_day {
             return 0.0;
@@ -107,10 +146,18 @@ impl TimeSystem {
             0.0
         } else {
             // Linear interpolation from 0 at sunrise/sunset to 1 at noon
-            1.0 - (noon_distance / max_distance)
+            (1.0 - (noon_distance / max_distance)) * self.weather.sunlight_factor()
         }
     }
 
+    pub fn weather(&self) -> Weather {
+        self.weather
+    }
+
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.weather = weather;
+    }
+
     /// Get time until next dawn (in hours)
     pub fn time_until_dawn(&self) -> f32 {
         if self.current_time < 6.0 {
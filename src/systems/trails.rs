@@ -0,0 +1,213 @@
+//! Trail System Module
+//!
+//! Wounded creatures bleed. A blow splashes a few drops of blood where it
+//! landed, and anything below half health keeps dripping as it moves, faster
+//! the worse it is hurt, leaving a trail of droplets on the ground. The
+//! drops dry out after a while; how long they stay visible, and how far off
+//! they can be made out, grows with the player's blood sense.
+
+use crate::components::*;
+use crate::systems::DamageEvent;
+use macroquad::rand;
+use std::collections::HashMap;
+
+/// Creatures below this share of their health drip blood
+const WOUNDED_SHARE: f32 = 0.5;
+/// Seconds between drops at the wounded threshold and near death
+const SLOW_DRIP: f32 = 0.8;
+const FAST_DRIP: f32 = 0.25;
+/// Drops splashed by each blow that draws blood, and how far they scatter
+const SPLASH_DROPS: usize = 3;
+const SPLASH_SCATTER: f32 = 10.0;
+/// How far a drip lands from the creature's centre
+const DRIP_SCATTER: f32 = 4.0;
+/// Drop radius range, in world units
+const DROP_SIZE: (f32, f32) = (1.5, 3.0);
+
+/// Seconds a drop stays visible with no blood sense, plus more per point
+const BASE_VISIBLE_SECONDS: f32 = 8.0;
+const VISIBLE_SECONDS_PER_SENSE: f32 = 6.0;
+/// Distance a drop can be seen from with no blood sense, plus more per point
+const BASE_VISIBLE_RANGE: f32 = 250.0;
+const VISIBLE_RANGE_PER_SENSE: f32 = 120.0;
+/// Seconds after which a drop is gone whatever the blood sense
+const MAX_DROP_AGE: f32 = 45.0;
+/// Most drops on the ground at once; the oldest dry up first
+const MAX_DROPS: usize = 600;
+
+/// A droplet of blood on the ground
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloodDrop {
+    pub position: Position,
+    /// Radius in world units
+    pub size: f32,
+    /// Seconds since it fell
+    pub age: f32,
+}
+
+impl BloodDrop {
+    /// Opacity to a player at `viewer` with `blood_sense`: fading with age
+    /// and towards the edge of what the player can make out
+    pub fn alpha(&self, viewer: &Position, blood_sense: f32) -> f32 {
+        let freshness = 1.0 - self.age / BloodTrails::visible_seconds(blood_sense);
+        let range = BloodTrails::visible_range(blood_sense);
+        let nearness = (range - self.position.distance_to(viewer)) / (range * 0.2);
+        (freshness.min(nearness)).clamp(0.0, 1.0)
+    }
+}
+
+/// Blood on the ground and the drip timers of the creatures shedding it
+#[derive(Debug, Clone, Default)]
+pub struct BloodTrails {
+    pub drops: Vec<BloodDrop>,
+    /// Seconds until each wounded creature's next drop
+    drip_timers: HashMap<u32, f32>,
+}
+
+impl BloodTrails {
+    /// Seconds a drop stays visible to a player with `blood_sense`
+    pub fn visible_seconds(blood_sense: f32) -> f32 {
+        (BASE_VISIBLE_SECONDS + blood_sense * VISIBLE_SECONDS_PER_SENSE).min(MAX_DROP_AGE)
+    }
+
+    /// Distance a drop can be seen from by a player with `blood_sense`
+    pub fn visible_range(blood_sense: f32) -> f32 {
+        BASE_VISIBLE_RANGE + blood_sense * VISIBLE_RANGE_PER_SENSE
+    }
+
+    fn shed(&mut self, position: Position, scatter: f32) {
+        self.drops.push(BloodDrop {
+            position: Position::new(
+                position.x + rand::gen_range(-scatter, scatter),
+                position.y + rand::gen_range(-scatter, scatter),
+            ),
+            size: rand::gen_range(DROP_SIZE.0, DROP_SIZE.1),
+            age: 0.0,
+        });
+    }
+}
+
+/// Trail system responsible for blood left by the wounded
+pub struct TrailSystem;
+
+impl TrailSystem {
+    /// Splash blood where each blow in `events` drew it. The player's own
+    /// wounds leave nothing to follow.
+    pub fn splash(trails: &mut BloodTrails, events: &[DamageEvent], player_id: u32) {
+        for event in events {
+            if event.dodged || event.target_id == player_id {
+                continue;
+            }
+            for _ in 0..SPLASH_DROPS {
+                trails.shed(event.position, SPLASH_SCATTER);
+            }
+        }
+    }
+
+    /// Drip blood from every badly wounded creature, and age and dry up
+    /// the drops already on the ground
+    pub fn update(
+        trails: &mut BloodTrails,
+        entities: &[GameEntity],
+        player_id: u32,
+        delta_time: f32,
+    ) {
+        let mut bleeding = HashMap::new();
+        for entity in entities {
+            let Some(health) = entity.health.as_ref() else {
+                continue;
+            };
+            let share = health.current / health.max;
+            if entity.id == player_id || health.current <= 0.0 || share >= WOUNDED_SHARE {
+                continue;
+            }
+
+            let interval = FAST_DRIP + (SLOW_DRIP - FAST_DRIP) * share / WOUNDED_SHARE;
+            let mut timer = trails
+                .drip_timers
+                .get(&entity.id)
+                .copied()
+                .unwrap_or(interval)
+                - delta_time;
+            if timer <= 0.0 {
+                trails.shed(entity.position, DRIP_SCATTER);
+                timer += interval;
+            }
+            bleeding.insert(entity.id, timer);
+        }
+        trails.drip_timers = bleeding;
+
+        for drop in &mut trails.drops {
+            drop.age += delta_time;
+        }
+        trails.drops.retain(|drop| drop.age < MAX_DROP_AGE);
+        let excess = trails.drops.len().saturating_sub(MAX_DROPS);
+        trails.drops.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+
+    #[test]
+    fn test_wounded_creatures_leave_trails_seen_longer_with_blood_sense() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        let deer = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Deer, 400.0, 800.0);
+        let mut trails = BloodTrails::default();
+
+        // Healthy creatures and the player leave nothing behind
+        TrailSystem::update(&mut trails, &entities, player_id, 2.0);
+        assert!(trails.drops.is_empty());
+
+        // A deer below half health drips as it goes
+        let health = entities[1].health.as_mut().unwrap();
+        health.current = health.max * 0.2;
+        for _ in 0..20 {
+            TrailSystem::update(&mut trails, &entities, player_id, 0.1);
+        }
+        assert!(trails.drops.len() >= 3);
+        assert!(trails
+            .drops
+            .iter()
+            .all(|drop| drop.position.distance_to(&entities[1].position) < 10.0));
+
+        // A blow splashes blood, unless it missed
+        let hit = DamageEvent {
+            attacker_id: player_id,
+            target_id: deer,
+            amount: 5.0,
+            position: Position::new(400.0, 800.0),
+            is_critical: false,
+            dodged: false,
+            killed: false,
+            reaction: crate::systems::HitReaction::None,
+            time: 0.0,
+        };
+        let before = trails.drops.len();
+        TrailSystem::splash(&mut trails, std::slice::from_ref(&hit), player_id);
+        assert_eq!(trails.drops.len(), before + SPLASH_DROPS);
+        TrailSystem::splash(
+            &mut trails,
+            &[DamageEvent {
+                dodged: true,
+                ..hit
+            }],
+            player_id,
+        );
+        assert_eq!(trails.drops.len(), before + SPLASH_DROPS);
+
+        // A keen blood sense still sees an old drop from further away
+        let drop = BloodDrop {
+            position: Position::new(0.0, 0.0),
+            size: 2.0,
+            age: 10.0,
+        };
+        let viewer = Position::new(300.0, 0.0);
+        assert_eq!(drop.alpha(&viewer, 0.0), 0.0);
+        assert!(drop.alpha(&viewer, 3.0) > 0.5);
+    }
+}
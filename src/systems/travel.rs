@@ -0,0 +1,133 @@
+//! Travel System Module
+//!
+//! Waypoints and auto-walk. The player picks a waypoint among known places
+//! (the current objective, territories and discovered shelters) and can set
+//! off towards it hands-free. Long trips follow the road along the top of
+//! the ground. Any movement input or being hurt stops the walk.
+
+use crate::components::*;
+use crate::systems::world::ROAD_LEVEL;
+
+/// How close counts as having reached a point on the route
+const ARRIVAL_DISTANCE: f32 = 12.0;
+
+/// Trips shorter than this across the map go straight rather than by road
+const ROAD_MIN_DISTANCE: f32 = 300.0;
+
+/// Take the road only if it is no more than this much longer than going
+/// straight
+const ROAD_MAX_DETOUR: f32 = 1.5;
+
+/// A place the player can travel to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub label: String,
+    pub position: Position,
+}
+
+impl Waypoint {
+    pub fn new(label: impl Into<String>, position: Position) -> Self {
+        Self {
+            label: label.into(),
+            position,
+        }
+    }
+}
+
+/// An auto-walk in progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoWalk {
+    pub destination: Waypoint,
+    /// Points to walk through in order, ending at the destination
+    pub route: Vec<Position>,
+    /// Index of the route point being walked to
+    pub next: usize,
+}
+
+impl AutoWalk {
+    /// The route points not yet reached
+    pub fn remaining_route(&self) -> &[Position] {
+        &self.route[self.next.min(self.route.len())..]
+    }
+}
+
+/// Travel system responsible for waypoints and auto-walk
+pub struct TravelSystem;
+
+impl TravelSystem {
+    /// Route from `from` to `to`, by road when the trip is long and the road
+    /// is not too far out of the way
+    pub fn road_route(from: Position, to: Position) -> Vec<Position> {
+        let direct = from.distance_to(&to);
+        let on_ramp = Position::new(from.x, ROAD_LEVEL);
+        let off_ramp = Position::new(to.x, ROAD_LEVEL);
+        let by_road =
+            from.distance_to(&on_ramp) + on_ramp.distance_to(&off_ramp) + off_ramp.distance_to(&to);
+
+        if (to.x - from.x).abs() >= ROAD_MIN_DISTANCE && by_road <= direct * ROAD_MAX_DETOUR {
+            vec![on_ramp, off_ramp, to]
+        } else {
+            vec![to]
+        }
+    }
+
+    /// Set off from `from` towards a waypoint
+    pub fn start(from: Position, destination: Waypoint) -> AutoWalk {
+        AutoWalk {
+            route: Self::road_route(from, destination.position),
+            destination,
+            next: 0,
+        }
+    }
+
+    /// Direction to walk from `position`, or None once the destination is
+    /// reached
+    pub fn steer(auto_walk: &mut AutoWalk, position: Position) -> Option<(f32, f32)> {
+        while let Some(point) = auto_walk.route.get(auto_walk.next) {
+            let dx = point.x - position.x;
+            let dy = point.y - position.y;
+            let distance = (dx * dx + dy * dy).sqrt();
+            if distance > ARRIVAL_DISTANCE {
+                return Some((dx / distance, dy / distance));
+            }
+            auto_walk.next += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_trips_follow_the_road() {
+        let from = Position::new(200.0, 720.0);
+        let to = Position::new(1300.0, 760.0);
+        let mut walk = TravelSystem::start(from, Waypoint::new("Chapel Ruins", to));
+        assert_eq!(
+            walk.route,
+            vec![
+                Position::new(200.0, ROAD_LEVEL),
+                Position::new(1300.0, ROAD_LEVEL),
+                to
+            ]
+        );
+
+        // Heads up to the road first, then along it
+        assert_eq!(TravelSystem::steer(&mut walk, from), Some((0.0, -1.0)));
+        let on_road = Position::new(205.0, ROAD_LEVEL);
+        assert_eq!(TravelSystem::steer(&mut walk, on_road), Some((1.0, 0.0)));
+        assert_eq!(walk.remaining_route().len(), 2);
+        let off_ramp = Position::new(1300.0, ROAD_LEVEL);
+        assert_eq!(TravelSystem::steer(&mut walk, off_ramp), Some((0.0, 1.0)));
+        assert_eq!(TravelSystem::steer(&mut walk, to), None);
+
+        // Short or deep trips go straight
+        let deep = Position::new(700.0, 1150.0);
+        assert_eq!(
+            TravelSystem::road_route(Position::new(200.0, 1100.0), deep),
+            vec![deep]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Tribute System Module
+//!
+//! Handles the empire-phase economy: allied and subjugated clans pay a daily
+//! tribute of blood vials, materials, and members. Demanding more than a clan's
+//! trust or fear will bear breeds unrest, and sustained unrest ends in rebellion.
+
+use crate::components::*;
+use std::collections::HashMap;
+
+/// Unrest level at which a clan breaks away from the player
+const REBELLION_THRESHOLD: f32 = 1.0;
+
+/// Unrest gained per day for each tribute level above what the clan tolerates
+const UNREST_PER_EXCESS_LEVEL: f32 = 0.25;
+
+/// Unrest shed per day while the clan is taxed within its tolerance
+const UNREST_DECAY: f32 = 0.1;
+
+/// Tribute system responsible for clan taxation and rebellion
+pub struct TributeSystem;
+
+impl TributeSystem {
+    /// Collect tribute from every clan that owes it and update unrest.
+    /// Should be called once per elapsed in-game day.
+    pub fn collect_daily_tribute(
+        clans: &mut HashMap<String, Clan>,
+        stockpile: &mut TributeStockpile,
+    ) -> Vec<TributeEvent> {
+        let mut events = Vec::new();
+
+        let mut clan_names: Vec<String> = clans.keys().cloned().collect();
+        clan_names.sort();
+
+        for clan_name in clan_names {
+            let Some(clan) = clans.get_mut(&clan_name) else {
+                continue;
+            };
+
+            if !clan.owes_tribute() || clan.tribute_level == TributeLevel::None {
+                clan.unrest = (clan.unrest - UNREST_DECAY).max(0.0);
+                continue;
+            }
+
+            let payment = Self::calculate_payment(clan);
+            stockpile.blood_vials += payment.blood_vials;
+            stockpile.materials += payment.materials;
+            stockpile.members += payment.members;
+            clan.member_count = clan.member_count.saturating_sub(payment.members);
+
+            Self::update_unrest(clan);
+
+            if clan.unrest >= REBELLION_THRESHOLD {
+                Self::trigger_rebellion(clan);
+                events.push(TributeEvent::Rebellion {
+                    clan_name: clan_name.clone(),
+                });
+            } else {
+                events.push(TributeEvent::Paid {
+                    clan_name: clan_name.clone(),
+                    payment,
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Calculate what a clan pays at its current tribute level
+    pub fn calculate_payment(clan: &Clan) -> TributeStockpile {
+        let rate = clan.tribute_level.rate();
+        let output = clan.member_count as f32 * clan.strength;
+
+        TributeStockpile {
+            blood_vials: (output * rate).round() as u32,
+            materials: (output * rate * 2.0).round() as u32,
+            // Only the harshest demands take members away from the clan
+            members: if clan.tribute_level >= TributeLevel::Heavy && clan.member_count > 1 {
+                1
+            } else {
+                0
+            },
+        }
+    }
+
+    /// Raise or lower unrest depending on how far demands exceed tolerance
+    fn update_unrest(clan: &mut Clan) {
+        let demanded = clan.tribute_level as i32;
+        let tolerated = clan.tolerated_tribute() as i32;
+        let excess = demanded - tolerated;
+
+        if excess > 0 {
+            clan.unrest += excess as f32 * UNREST_PER_EXCESS_LEVEL;
+            // Resentment erodes whatever goodwill the clan had left
+            clan.trust_towards_player = (clan.trust_towards_player - 0.05).max(0.0);
+        } else {
+            clan.unrest = (clan.unrest - UNREST_DECAY).max(0.0);
+        }
+    }
+
+    /// A rebelling clan throws off the player's rule
+    fn trigger_rebellion(clan: &mut Clan) {
+        clan.is_allied = false;
+        clan.is_defeated = false;
+        clan.tribute_level = TributeLevel::None;
+        clan.unrest = 0.0;
+        clan.plot = None;
+        clan.trust_towards_player = 0.0;
+        clan.fear_of_player = (clan.fear_of_player - 0.3).max(0.0);
+    }
+
+    /// Change the tribute demanded from a clan. Returns false if the clan
+    /// does not owe tribute.
+    pub fn adjust_tribute(clan: &mut Clan, raise: bool) -> bool {
+        if !clan.owes_tribute() {
+            return false;
+        }
+
+        clan.tribute_level = if raise {
+            clan.tribute_level.raise()
+        } else {
+            clan.tribute_level.lower()
+        };
+        true
+    }
+}
+
+/// Outcome of a day's tribute collection for one clan
+#[derive(Debug, Clone)]
+pub enum TributeEvent {
+    Paid {
+        clan_name: String,
+        payment: TributeStockpile,
+    },
+    Rebellion {
+        clan_name: String,
+    },
+}
+
+impl TributeEvent {
+    /// Get a descriptive message for the debug log
+    pub fn get_message(&self) -> String {
+        match self {
+            TributeEvent::Paid { clan_name, payment } => format!(
+                "{} paid tribute: {} blood vials, {} materials, {} members",
+                clan_name, payment.blood_vials, payment.materials, payment.members
+            ),
+            TributeEvent::Rebellion { clan_name } => {
+                format!("{} have rebelled against your taxation!", clan_name)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_allied_clan(trust: f32) -> Clan {
+        let mut clan = Clan::new("Night-Bloods", "Silentfang", 10);
+        clan.is_allied = true;
+        clan.trust_towards_player = trust;
+        clan
+    }
+
+    #[test]
+    fn test_tribute_requires_allegiance() {
+        let mut clan = Clan::new("Bone-Eaters", "Grimjaw", 15);
+        assert!(!TributeSystem::adjust_tribute(&mut clan, true));
+        assert_eq!(clan.tribute_level, TributeLevel::None);
+
+        clan.is_defeated = true;
+        assert!(TributeSystem::adjust_tribute(&mut clan, true));
+        assert_eq!(clan.tribute_level, TributeLevel::Light);
+    }
+
+    #[test]
+    fn test_tolerated_tribute_collects_without_unrest() {
+        let mut clans = HashMap::new();
+        let mut clan = create_allied_clan(0.8);
+        clan.tribute_level = TributeLevel::Moderate;
+        clans.insert(clan.name.clone(), clan);
+
+        let mut stockpile = TributeStockpile::default();
+        let events = TributeSystem::collect_daily_tribute(&mut clans, &mut stockpile);
+
+        assert!(matches!(events[0], TributeEvent::Paid { .. }));
+        assert_eq!(stockpile.blood_vials, 2);
+        assert_eq!(stockpile.materials, 4);
+        assert_eq!(clans["Night-Bloods"].unrest, 0.0);
+    }
+
+    #[test]
+    fn test_excessive_taxation_breeds_rebellion() {
+        let mut clans = HashMap::new();
+        let mut clan = create_allied_clan(0.3);
+        clan.tribute_level = TributeLevel::Extortionate;
+        clans.insert(clan.name.clone(), clan);
+
+        let mut stockpile = TributeStockpile::default();
+        let mut rebelled = false;
+        for _ in 0..5 {
+            let events = TributeSystem::collect_daily_tribute(&mut clans, &mut stockpile);
+            if events
+                .iter()
+                .any(|e| matches!(e, TributeEvent::Rebellion { .. }))
+            {
+                rebelled = true;
+                break;
+            }
+        }
+
+        assert!(rebelled);
+        let clan = &clans["Night-Bloods"];
+        assert!(!clan.is_allied);
+        assert_eq!(clan.tribute_level, TributeLevel::None);
+    }
+}
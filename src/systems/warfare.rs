@@ -0,0 +1,583 @@
+//! Warfare System Module
+//!
+//! The clans' dealings with each other, carried on in the background. Each
+//! day every pair of clans drifts closer or further apart, pushed apart by
+//! unrest and drawn together by a shared alliance with the player. Clans that
+//! fall out go to war and send raiding parties to clash on the border of the
+//! territory between their camps. Declarations, truces and the outcome of
+//! every clash reach the player as news in the message log.
+
+use crate::components::*;
+use crate::systems::combat::{CombatSystem, DamageEvent};
+use crate::systems::skirmish::SkirmishSystem;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Prefix marking clan news in the message log
+pub const NEWS_PREFIX: &str = "News: ";
+
+/// Standing at or below which two clans go to war
+const WAR_THRESHOLD: f32 = -0.5;
+/// Standing two clans at war must climb back above to agree a truce
+const TRUCE_THRESHOLD: f32 = -0.3;
+/// Largest random shift in a standing each day
+const DAILY_DRIFT: f32 = 0.08;
+/// Share of a standing that fades back towards neutral each day
+const DAILY_EASING: f32 = 0.05;
+/// Daily push apart from the more restless clan of a pair, at full unrest
+const UNREST_PULL: f32 = 0.1;
+/// Daily pull together for two clans both allied with the player
+const SHARED_ALLY_PULL: f32 = 0.06;
+/// Standing lost by both sides after every clash
+const CLASH_GRIEVANCE: f32 = 0.1;
+
+/// Seconds between raids for each war
+const RAID_INTERVAL: f32 = 90.0;
+/// Members each clan sends on a raid
+const RAID_PARTY_SIZE: usize = 3;
+/// Raids are not staged this close to the player
+const DISTANT_RANGE: f32 = 350.0;
+/// Distance at which raiders come to blows
+const ENGAGE_RANGE: f32 = 45.0;
+/// Longest a clash lasts before both sides withdraw
+const CLASH_SECONDS: f32 = 30.0;
+/// Clan strength won by the victor of a clash and lost by the vanquished
+const CLASH_STRENGTH_SWING: f32 = 0.05;
+
+/// A raid between two clans at war
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClanClash {
+    pub attacker: String,
+    pub defender: String,
+    pub position: Position,
+    /// Raiders sent by both sides
+    pub fighters: Vec<u32>,
+    pub elapsed: f32,
+    pub casualties: u32,
+}
+
+/// How every pair of clans regards each other, and the clashes under way
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClanRelations {
+    /// Standing between each pair of clans from -1.0 (war) to 1.0 (pact),
+    /// keyed by `ClanRelations::pair_key`
+    pub standings: HashMap<String, f32>,
+    /// Pairs currently at war, by the same key
+    pub wars: Vec<String>,
+    /// Day of the last round of diplomacy
+    pub last_diplomacy_day: u32,
+    #[serde(skip)]
+    pub raid_timer: f32,
+    #[serde(skip)]
+    pub clashes: Vec<ClanClash>,
+}
+
+impl ClanRelations {
+    /// Key for a pair of clans, the same whichever way round they are named
+    pub fn pair_key(a: &str, b: &str) -> String {
+        if a <= b {
+            format!("{} & {}", a, b)
+        } else {
+            format!("{} & {}", b, a)
+        }
+    }
+
+    pub fn standing(&self, a: &str, b: &str) -> f32 {
+        self.standings
+            .get(&Self::pair_key(a, b))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn set_standing(&mut self, a: &str, b: &str, standing: f32) {
+        self.standings
+            .insert(Self::pair_key(a, b), standing.clamp(-1.0, 1.0));
+    }
+
+    pub fn at_war(&self, a: &str, b: &str) -> bool {
+        self.wars.contains(&Self::pair_key(a, b))
+    }
+
+    /// Clans at war with the given clan
+    pub fn enemies_of<'a>(&self, clan_name: &str, clans: &'a [String]) -> Vec<&'a str> {
+        clans
+            .iter()
+            .filter(|other| *other != clan_name && self.at_war(clan_name, other))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Clan news for the message log
+#[derive(Debug, Clone, PartialEq)]
+pub enum WarfareEvent {
+    WarDeclared {
+        aggressor: String,
+        target: String,
+    },
+    Truce {
+        clans: (String, String),
+    },
+    ClashStarted {
+        attacker: String,
+        defender: String,
+        bearing: String,
+    },
+    ClashEnded {
+        /// `None` when both sides withdrew with equal numbers
+        victor: Option<String>,
+        clans: (String, String),
+        casualties: u32,
+    },
+}
+
+impl WarfareEvent {
+    pub fn get_message(&self) -> String {
+        let news = match self {
+            WarfareEvent::WarDeclared { aggressor, target } => {
+                format!("The {} have declared war on the {}.", aggressor, target)
+            }
+            WarfareEvent::Truce { clans: (a, b) } => {
+                format!("The {} and the {} have agreed a truce.", a, b)
+            }
+            WarfareEvent::ClashStarted {
+                attacker,
+                defender,
+                bearing,
+            } => format!(
+                "The {} are raiding the {} {} of you.",
+                attacker, defender, bearing
+            ),
+            WarfareEvent::ClashEnded {
+                victor: Some(victor),
+                clans: (a, b),
+                casualties,
+            } => {
+                let loser = if victor == a { b } else { a };
+                format!(
+                    "The {} drove off the {}. {} fell.",
+                    victor, loser, casualties
+                )
+            }
+            WarfareEvent::ClashEnded {
+                victor: None,
+                clans: (a, b),
+                casualties,
+            } => format!(
+                "The {} and the {} withdrew from the field. {} fell.",
+                a, b, casualties
+            ),
+        };
+        format!("{}{}", NEWS_PREFIX, news)
+    }
+}
+
+/// Warfare system responsible for diplomacy and raids between clans
+pub struct WarfareSystem;
+
+impl WarfareSystem {
+    /// Shift every pair's standing once a day, declaring wars and truces as
+    /// standings cross the thresholds
+    pub fn update_diplomacy(
+        relations: &mut ClanRelations,
+        clans: &HashMap<String, Clan>,
+        current_day: u32,
+    ) -> Vec<WarfareEvent> {
+        if current_day <= relations.last_diplomacy_day {
+            return Vec::new();
+        }
+        relations.last_diplomacy_day = current_day;
+
+        let mut clan_names: Vec<&String> = clans
+            .iter()
+            .filter(|(_, clan)| !clan.is_defeated)
+            .map(|(name, _)| name)
+            .collect();
+        clan_names.sort();
+
+        let mut events = Vec::new();
+        for (index, a) in clan_names.iter().enumerate() {
+            for b in &clan_names[index + 1..] {
+                let (clan_a, clan_b) = (&clans[*a], &clans[*b]);
+                let mut standing = relations.standing(a, b);
+                standing -= standing * DAILY_EASING;
+                standing += macroquad::rand::gen_range(-DAILY_DRIFT, DAILY_DRIFT);
+                standing -= clan_a.unrest.max(clan_b.unrest) * UNREST_PULL;
+                if clan_a.is_allied && clan_b.is_allied {
+                    standing += SHARED_ALLY_PULL;
+                }
+                relations.set_standing(a, b, standing);
+                let standing = relations.standing(a, b);
+
+                let key = ClanRelations::pair_key(a, b);
+                let at_war = relations.wars.contains(&key);
+                if !at_war && standing <= WAR_THRESHOLD {
+                    relations.wars.push(key);
+                    // The more restless clan strikes first
+                    let (aggressor, target) = if clan_b.unrest > clan_a.unrest {
+                        (b, a)
+                    } else {
+                        (a, b)
+                    };
+                    events.push(WarfareEvent::WarDeclared {
+                        aggressor: aggressor.to_string(),
+                        target: target.to_string(),
+                    });
+                } else if at_war && standing > TRUCE_THRESHOLD {
+                    relations.wars.retain(|war| *war != key);
+                    events.push(WarfareEvent::Truce {
+                        clans: (a.to_string(), b.to_string()),
+                    });
+                }
+            }
+        }
+
+        // Subjugated clans fight no wars
+        relations.wars.retain(|war| {
+            clan_names
+                .iter()
+                .filter(|name| war.split(" & ").any(|side| side == name.as_str()))
+                .count()
+                == 2
+        });
+
+        events
+    }
+
+    /// Stage raids between clans at war and fight the clashes under way
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_clashes(
+        relations: &mut ClanRelations,
+        clans: &mut HashMap<String, Clan>,
+        entities: &mut [GameEntity],
+        territories: &TerritoryManager,
+        player_id: u32,
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+        delta_time: f32,
+    ) -> Vec<WarfareEvent> {
+        let Some(player_pos) = EntityFinder::by_id(entities, player_id).map(|p| p.position) else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+
+        relations.raid_timer += delta_time;
+        if relations.raid_timer >= RAID_INTERVAL {
+            relations.raid_timer = 0.0;
+            for war in relations.wars.clone() {
+                let Some((a, b)) = war.split_once(" & ") else {
+                    continue;
+                };
+                let busy = relations
+                    .clashes
+                    .iter()
+                    .any(|clash| ClanRelations::pair_key(&clash.attacker, &clash.defender) == war);
+                if busy {
+                    continue;
+                }
+                // Whoever is stronger takes the fight to the other
+                let strength = |name: &str| clans.get(name).map_or(0.0, |clan| clan.strength);
+                let (attacker, defender) = if strength(b) > strength(a) {
+                    (b, a)
+                } else {
+                    (a, b)
+                };
+                if let Some(clash) =
+                    Self::stage_raid(entities, territories, player_pos, attacker, defender)
+                {
+                    events.push(WarfareEvent::ClashStarted {
+                        attacker: clash.attacker.clone(),
+                        defender: clash.defender.clone(),
+                        bearing: SkirmishSystem::bearing(player_pos, clash.position),
+                    });
+                    relations.clashes.push(clash);
+                }
+            }
+        }
+
+        for clash in &mut relations.clashes {
+            clash.elapsed += delta_time;
+            clash.casualties += Self::fight(clash, entities, game_time, damage_events);
+        }
+
+        let (finished, ongoing): (Vec<ClanClash>, Vec<ClanClash>) =
+            relations.clashes.drain(..).partition(|clash| {
+                clash.elapsed >= CLASH_SECONDS
+                    || Self::survivors(clash, entities, &clash.attacker) == 0
+                    || Self::survivors(clash, entities, &clash.defender) == 0
+            });
+        relations.clashes = ongoing;
+
+        for clash in finished {
+            let attackers = Self::survivors(&clash, entities, &clash.attacker);
+            let defenders = Self::survivors(&clash, entities, &clash.defender);
+            let victor = match attackers.cmp(&defenders) {
+                std::cmp::Ordering::Greater => Some(clash.attacker.clone()),
+                std::cmp::Ordering::Less => Some(clash.defender.clone()),
+                std::cmp::Ordering::Equal => None,
+            };
+            if let Some(victor) = &victor {
+                let loser = if *victor == clash.attacker {
+                    &clash.defender
+                } else {
+                    &clash.attacker
+                };
+                if let Some(clan) = clans.get_mut(victor) {
+                    clan.strength += CLASH_STRENGTH_SWING;
+                }
+                if let Some(clan) = clans.get_mut(loser) {
+                    clan.strength = (clan.strength - CLASH_STRENGTH_SWING).max(0.1);
+                }
+            }
+            let standing = relations.standing(&clash.attacker, &clash.defender);
+            relations.set_standing(&clash.attacker, &clash.defender, standing - CLASH_GRIEVANCE);
+            events.push(WarfareEvent::ClashEnded {
+                victor,
+                clans: (clash.attacker, clash.defender),
+                casualties: clash.casualties,
+            });
+        }
+
+        events
+    }
+
+    /// Send each side's nearest camp members to the border of the territory
+    /// lying between the two camps. No raid is staged near the player or
+    /// for a clan without a living leader.
+    fn stage_raid(
+        entities: &mut [GameEntity],
+        territories: &TerritoryManager,
+        player_pos: Position,
+        attacker: &str,
+        defender: &str,
+    ) -> Option<ClanClash> {
+        let camp = |clan_name: &str| {
+            entities
+                .iter()
+                .find(|entity| {
+                    matches!(&entity.entity_type, EntityType::ClanLeader(name) if name == clan_name)
+                        && entity.health.as_ref().is_some_and(Health::is_alive)
+                })
+                .map(|leader| leader.position)
+        };
+        let (attacker_camp, defender_camp) = (camp(attacker)?, camp(defender)?);
+        let midpoint = Position::new(
+            (attacker_camp.x + defender_camp.x) / 2.0,
+            (attacker_camp.y + defender_camp.y) / 2.0,
+        );
+        let position = territories
+            .territories
+            .iter()
+            .min_by(|a, b| {
+                a.center
+                    .distance_to(&midpoint)
+                    .total_cmp(&b.center.distance_to(&midpoint))
+            })
+            .map_or(midpoint, |territory| {
+                let distance = territory.center.distance_to(&midpoint).max(1.0);
+                Position::new(
+                    territory.center.x
+                        + (midpoint.x - territory.center.x) / distance * territory.radius,
+                    territory.center.y
+                        + (midpoint.y - territory.center.y) / distance * territory.radius,
+                )
+            });
+        if position.distance_to(&player_pos) < DISTANT_RANGE {
+            return None;
+        }
+
+        let mut fighters = Vec::new();
+        for (clan_name, side) in [(attacker, -1.0), (defender, 1.0)] {
+            let mut party: Vec<(f32, usize)> = entities
+                .iter()
+                .enumerate()
+                .filter(|(_, entity)| {
+                    matches!(&entity.entity_type, EntityType::ClanMember(name) if name == clan_name)
+                        && !matches!(entity.ai_state, AIState::Follower(_) | AIState::Squad)
+                        && entity.health.as_ref().is_some_and(Health::is_alive)
+                })
+                .map(|(index, entity)| (entity.position.distance_to(&position), index))
+                .collect();
+            party.sort_by(|a, b| a.0.total_cmp(&b.0));
+            if party.is_empty() {
+                return None;
+            }
+            for (slot, (_, index)) in party.into_iter().take(RAID_PARTY_SIZE).enumerate() {
+                let raider = &mut entities[index];
+                raider.position = Position::new(
+                    position.x + side * 15.0,
+                    position.y + (slot as f32 - 1.0) * 20.0,
+                );
+                if let Some(velocity) = raider.velocity.as_mut() {
+                    *velocity = Velocity::new(0.0, 0.0);
+                }
+                fighters.push(raider.id);
+            }
+        }
+
+        Some(ClanClash {
+            attacker: attacker.to_string(),
+            defender: defender.to_string(),
+            position,
+            fighters,
+            elapsed: 0.0,
+            casualties: 0,
+        })
+    }
+
+    /// Each raider strikes the nearest enemy raider in reach; returns how
+    /// many fell
+    fn fight(
+        clash: &ClanClash,
+        entities: &mut [GameEntity],
+        game_time: f32,
+        damage_events: &mut Vec<DamageEvent>,
+    ) -> u32 {
+        let raiders: Vec<(u32, String, Position)> = clash
+            .fighters
+            .iter()
+            .filter_map(|id| EntityFinder::by_id(entities, *id))
+            .filter(|entity| entity.health.as_ref().is_some_and(Health::is_alive))
+            .filter_map(|entity| match &entity.entity_type {
+                EntityType::ClanMember(clan) => Some((entity.id, clan.clone(), entity.position)),
+                _ => None,
+            })
+            .collect();
+
+        let mut casualties = 0;
+        for (id, clan, position) in &raiders {
+            let target = raiders
+                .iter()
+                .filter(|(_, other_clan, other_pos)| {
+                    other_clan != clan && other_pos.distance_to(position) <= ENGAGE_RANGE
+                })
+                .min_by(|a, b| {
+                    a.2.distance_to(position)
+                        .total_cmp(&b.2.distance_to(position))
+                });
+            if let Some((target_id, _, _)) = target {
+                let blow = CombatSystem::resolve_attack(
+                    entities,
+                    *id,
+                    *target_id,
+                    game_time,
+                    damage_events,
+                );
+                casualties += u32::from(blow.is_some_and(|blow| blow.killed));
+            }
+        }
+        casualties
+    }
+
+    /// Raiders of one side still standing
+    fn survivors(clash: &ClanClash, entities: &[GameEntity], clan_name: &str) -> usize {
+        clash
+            .fighters
+            .iter()
+            .filter_map(|id| EntityFinder::by_id(entities, *id))
+            .filter(|entity| {
+                matches!(&entity.entity_type, EntityType::ClanMember(name) if name == clan_name)
+                    && entity.health.as_ref().is_some_and(Health::is_alive)
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+    use macroquad::prelude::WHITE;
+
+    #[test]
+    fn test_restless_clans_go_to_war_and_raid_each_other() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(1500.0, 100.0);
+        let mut clans = HashMap::new();
+        WorldSystem::initialize_clans(&mut clans);
+        WorldSystem::spawn_all_clan_leaders(&mut entities, &mut ids);
+        let leader_pos = |entities: &[GameEntity], clan: &str| {
+            entities
+                .iter()
+                .find(|e| matches!(&e.entity_type, EntityType::ClanLeader(name) if name == clan))
+                .unwrap()
+                .position
+        };
+        for clan in ["Night-Bloods", "Bone-Eaters"] {
+            let camp = leader_pos(&entities, clan);
+            for _ in 0..RAID_PARTY_SIZE {
+                WorldSystem::spawn_clan_member(
+                    &mut entities,
+                    &mut ids,
+                    clan,
+                    camp.x,
+                    camp.y,
+                    WHITE,
+                );
+            }
+        }
+
+        // Unrest pushes a pair already at odds into war
+        let mut relations = ClanRelations::default();
+        relations.set_standing("Night-Bloods", "Bone-Eaters", -0.6);
+        clans.get_mut("Night-Bloods").unwrap().unrest = 1.0;
+        let events = WarfareSystem::update_diplomacy(&mut relations, &clans, 1);
+        assert!(events.contains(&WarfareEvent::WarDeclared {
+            aggressor: "Night-Bloods".into(),
+            target: "Bone-Eaters".into(),
+        }));
+        assert!(relations.at_war("Bone-Eaters", "Night-Bloods"));
+        assert!(WarfareSystem::update_diplomacy(&mut relations, &clans, 1).is_empty());
+        relations
+            .wars
+            .retain(|war| war == "Bone-Eaters & Night-Bloods");
+
+        // The next raid sends both parties to clash until one side breaks
+        let territories = WorldSystem::place_territories();
+        let mut damage_events = Vec::new();
+        let mut update = |relations: &mut ClanRelations,
+                          clans: &mut HashMap<String, Clan>,
+                          entities: &mut Vec<GameEntity>,
+                          game_time: f32,
+                          delta_time: f32| {
+            WarfareSystem::update_clashes(
+                relations,
+                clans,
+                entities,
+                &territories,
+                0,
+                game_time,
+                &mut damage_events,
+                delta_time,
+            )
+        };
+        relations.raid_timer = RAID_INTERVAL;
+        let events = update(&mut relations, &mut clans, &mut entities, 0.0, 0.0);
+        assert!(matches!(
+            events.as_slice(),
+            [WarfareEvent::ClashStarted { .. }]
+        ));
+        assert_eq!(relations.clashes[0].fighters.len(), RAID_PARTY_SIZE * 2);
+
+        let standing = relations.standing("Night-Bloods", "Bone-Eaters");
+        let mut ended = Vec::new();
+        for step in 0..400 {
+            ended.extend(update(
+                &mut relations,
+                &mut clans,
+                &mut entities,
+                step as f32 * 0.1,
+                0.1,
+            ));
+        }
+        assert!(matches!(
+            ended.as_slice(),
+            [WarfareEvent::ClashEnded { casualties, .. }] if *casualties > 0
+        ));
+        assert!(relations.clashes.is_empty());
+        assert!(relations.standing("Night-Bloods", "Bone-Eaters") < standing);
+        assert!(ended[0].get_message().starts_with(NEWS_PREFIX));
+    }
+}
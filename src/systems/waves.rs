@@ -0,0 +1,268 @@
+//! Wave System Module
+//!
+//! The spawn director. Once night falls it sends waves of hostile infected
+//! out of the dark, more and larger the longer the player survives. New
+//! infected appear off-screen so the player never sees them pop into being,
+//! and no wave pushes the number of living infected past the director's cap.
+//! Under a blood moon (see `CalendarSystem`) the waves come twice as fast.
+
+use crate::components::*;
+use crate::systems::camera::{DEFAULT_ZOOM, MIN_ZOOM};
+use crate::systems::query::WorldRect;
+use crate::systems::WorldSystem;
+use macroquad::prelude::rand;
+use serde::{Deserialize, Serialize};
+
+/// Seconds into the night before the first wave, and between waves
+const WAVE_INTERVAL: f32 = 45.0;
+
+/// Infected in the first wave of the first night
+const BASE_WAVE_SIZE: usize = 3;
+
+/// Extra infected per wave for every day survived
+const WAVE_GROWTH_PER_DAY: usize = 2;
+
+/// Most waves in a single night
+const MAX_WAVES_PER_NIGHT: u32 = 4;
+
+/// Half the size of the area around the camera that is kept clear of new
+/// spawns at the default zoom, in world units (a little more than is
+/// visible on screen). Zooming out widens it.
+const VIEW_HALF_WIDTH: f32 = 460.0;
+const VIEW_HALF_HEIGHT: f32 = 270.0;
+
+/// Attempts at finding an off-screen spot for each infected
+const SPAWN_ATTEMPTS: usize = 20;
+
+/// Night-time wave spawning state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnDirector {
+    /// Most living infected the director will allow at once
+    pub max_hostiles: usize,
+    /// Seconds until the next wave tonight
+    pub next_wave_in: f32,
+    pub waves_tonight: u32,
+    /// Days survived before the current night began
+    pub night_day: u32,
+}
+
+impl SpawnDirector {
+    pub const DEFAULT_MAX_HOSTILES: usize = 30;
+
+    pub fn new(max_hostiles: usize) -> Self {
+        Self {
+            max_hostiles,
+            next_wave_in: WAVE_INTERVAL,
+            waves_tonight: 0,
+            night_day: 0,
+        }
+    }
+
+    /// Waves sent on a night after `day_count` days
+    pub fn waves_for_night(day_count: u32) -> u32 {
+        (1 + day_count / 2).min(MAX_WAVES_PER_NIGHT)
+    }
+
+    /// Infected in each wave after `day_count` days
+    pub fn wave_size(day_count: u32) -> usize {
+        BASE_WAVE_SIZE + WAVE_GROWTH_PER_DAY * day_count as usize
+    }
+}
+
+impl Default for SpawnDirector {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_HOSTILES)
+    }
+}
+
+/// Wave system responsible for night-time respawns
+pub struct WaveSystem;
+
+impl WaveSystem {
+    /// Count down to the next wave and spawn it when due. Waves only come at
+    /// night; the count starts over each night. A `spawn_rate` above one
+    /// sends waves that much more often, and that many more of them.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        director: &mut SpawnDirector,
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        is_night: bool,
+        day_count: u32,
+        spawn_rate: f32,
+        view: WorldRect,
+        delta_time: f32,
+    ) -> Option<WaveEvent> {
+        // A night spans midnight, so it is counted from the day before it
+        if !is_night {
+            director.waves_tonight = 0;
+            director.next_wave_in = WAVE_INTERVAL;
+            director.night_day = day_count;
+            return None;
+        }
+        let waves = SpawnDirector::waves_for_night(director.night_day) as f32 * spawn_rate;
+        if director.waves_tonight as f32 >= waves.round() {
+            return None;
+        }
+
+        director.next_wave_in -= delta_time * spawn_rate;
+        if director.next_wave_in > 0.0 {
+            return None;
+        }
+        director.next_wave_in = WAVE_INTERVAL;
+        director.waves_tonight += 1;
+
+        let living = entities
+            .iter()
+            .filter(|entity| {
+                matches!(entity.entity_type, EntityType::HostileInfected)
+                    && !matches!(entity.ai_state, AIState::Dead)
+            })
+            .count();
+        let room = director.max_hostiles.saturating_sub(living);
+        let wanted = SpawnDirector::wave_size(director.night_day).min(room);
+
+        let mut spawned = 0;
+        for _ in 0..wanted {
+            if let Some(position) = Self::off_screen_position(view) {
+                WorldSystem::spawn_hostile_infected(entities, entity_ids, position.x, position.y);
+                spawned += 1;
+            }
+        }
+
+        Some(WaveEvent {
+            wave: director.waves_tonight,
+            spawned,
+            capped: wanted < SpawnDirector::wave_size(director.night_day),
+        })
+    }
+
+    /// The area around the camera kept clear of new spawns at `zoom`
+    pub fn keep_clear(camera: Position, zoom: f32) -> WorldRect {
+        let view_scale = DEFAULT_ZOOM / zoom.max(MIN_ZOOM);
+        WorldRect::centered(
+            camera,
+            VIEW_HALF_WIDTH * 2.0 * view_scale,
+            VIEW_HALF_HEIGHT * 2.0 * view_scale,
+        )
+    }
+
+    /// A random spot on the ground outside the camera's view
+    pub fn off_screen_position(view: WorldRect) -> Option<Position> {
+        let (min_x, max_x, min_y, max_y) =
+            WorldSystem::get_spawn_bounds(&EntityType::HostileInfected);
+        (0..SPAWN_ATTEMPTS)
+            .map(|_| Position::new(rand::gen_range(min_x, max_x), rand::gen_range(min_y, max_y)))
+            .find(|position| !view.contains(*position))
+    }
+}
+
+/// A night wave arriving
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveEvent {
+    /// Which wave of the night this is, counting from 1
+    pub wave: u32,
+    pub spawned: usize,
+    /// The wave was cut short by the director's cap
+    pub capped: bool,
+}
+
+impl WaveEvent {
+    pub fn get_message(&self) -> String {
+        if self.spawned == 0 {
+            "The night is restless, but no more infected come.".to_string()
+        } else {
+            format!(
+                "Wave {}: {} infected stir in the darkness.",
+                self.wave, self.spawned
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waves_come_at_night_off_screen_and_respect_cap() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let camera = Position::new(400.0, 800.0);
+        let view = WaveSystem::keep_clear(camera, DEFAULT_ZOOM);
+        let mut director = SpawnDirector::new(4);
+
+        // Nothing by day
+        assert_eq!(
+            WaveSystem::update(
+                &mut director,
+                &mut entities,
+                &mut ids,
+                false,
+                0,
+                1.0,
+                view,
+                100.0
+            ),
+            None
+        );
+
+        let event = WaveSystem::update(
+            &mut director,
+            &mut entities,
+            &mut ids,
+            true,
+            0,
+            1.0,
+            view,
+            WAVE_INTERVAL,
+        )
+        .unwrap();
+        assert_eq!(event.spawned, SpawnDirector::wave_size(0));
+        for entity in &entities {
+            assert!(!view.contains(entity.position));
+        }
+
+        // The first night only has one wave
+        assert_eq!(
+            WaveSystem::update(
+                &mut director,
+                &mut entities,
+                &mut ids,
+                true,
+                0,
+                1.0,
+                view,
+                WAVE_INTERVAL
+            ),
+            None
+        );
+
+        // Later nights bring more waves, but never past the cap
+        WaveSystem::update(
+            &mut director,
+            &mut entities,
+            &mut ids,
+            false,
+            4,
+            1.0,
+            view,
+            1.0,
+        );
+        let event = WaveSystem::update(
+            &mut director,
+            &mut entities,
+            &mut ids,
+            true,
+            4,
+            1.0,
+            view,
+            WAVE_INTERVAL,
+        )
+        .unwrap();
+        assert_eq!(event.spawned, 1);
+        assert!(event.capped);
+        assert_eq!(entities.len(), 4);
+        assert_eq!(SpawnDirector::waves_for_night(4), 3);
+    }
+}
@@ -0,0 +1,320 @@
+//! Weakness System Module
+//!
+//! Enforces the classic vampire weaknesses chosen for the game: running water
+//! drains blood while the player wades through it, garlic fields turn the
+//! player back at their edge, and inhabited homes stay barred until the
+//! family invites the player in. Each rule can be switched off in
+//! `WeaknessRules`.
+
+use crate::components::*;
+use crate::settings::WeaknessRules;
+use crate::systems::spatial::SpatialGrid;
+
+/// Blood lost per second while standing in running water
+const WATER_DRAIN_PER_SECOND: f32 = 15.0;
+
+/// A family only invites in a visitor who looks well fed, and so human
+const INVITE_BLOOD_RATIO: f32 = 0.6;
+
+/// How close the player must be to knock on a door
+const KNOCK_RANGE: f32 = 70.0;
+
+/// Weakness system responsible for vampire world rules
+pub struct WeaknessSystem;
+
+impl WeaknessSystem {
+    /// Apply terrain weaknesses after the player has moved from `previous`.
+    /// Garlic pushes the player back out; running water drains blood unless
+    /// the player is flying over it in bat form.
+    pub fn update(
+        entities: &mut [GameEntity],
+        player_id: u32,
+        hazards: &[HazardZone],
+        rules: &WeaknessRules,
+        previous: Position,
+        delta_time: f32,
+    ) -> Option<WeaknessEvent> {
+        let player = entities.iter_mut().find(|entity| entity.id == player_id)?;
+        let mut event = None;
+
+        if rules.garlic {
+            let in_garlic = |position: &Position| {
+                hazards
+                    .iter()
+                    .any(|zone| zone.kind == HazardKind::GarlicField && zone.contains(position))
+            };
+            if in_garlic(&player.position) {
+                if !in_garlic(&previous) {
+                    event = Some(WeaknessEvent::GarlicRepelled);
+                }
+                player.position = Self::outside_garlic(hazards, player.position, previous);
+            }
+        }
+
+        if rules.running_water {
+            let flying = player
+                .vampire_abilities
+                .as_ref()
+                .is_some_and(|abilities| abilities.ability_state.is_bat_form());
+            let in_water = |position: &Position| {
+                hazards
+                    .iter()
+                    .any(|zone| zone.kind == HazardKind::RunningWater && zone.contains(position))
+            };
+            if !flying && in_water(&player.position) {
+                if let Some(blood) = player.blood_meter.as_mut() {
+                    blood.current = (blood.current - WATER_DRAIN_PER_SECOND * delta_time).max(0.0);
+                }
+                if !in_water(&previous) {
+                    event = Some(WeaknessEvent::EnteredRunningWater);
+                }
+            }
+        }
+
+        event
+    }
+
+    /// Knock on the nearest inhabited home. At night a well-fed visitor is
+    /// invited in; anyone else is turned away.
+    pub fn knock(
+        entities: &mut [GameEntity],
+        grid: &SpatialGrid,
+        player_id: u32,
+        is_night: bool,
+    ) -> Option<WeaknessEvent> {
+        let player = EntityFinder::by_id(entities, player_id)?;
+        let player_pos = player.position;
+        let looks_human = player
+            .blood_meter
+            .as_ref()
+            .is_some_and(|blood| blood.current / blood.maximum >= INVITE_BLOOD_RATIO);
+
+        let index = grid
+            .query_radius(entities, player_pos, KNOCK_RANGE)
+            .into_iter()
+            .filter(|&index| {
+                entities[index]
+                    .shelter
+                    .as_ref()
+                    .is_some_and(|shelter| shelter.bars_vampires())
+            })
+            .min_by(|&a, &b| {
+                entities[a]
+                    .position
+                    .distance_to(&player_pos)
+                    .total_cmp(&entities[b].position.distance_to(&player_pos))
+            })?;
+
+        let shelter = entities[index].shelter.as_mut()?;
+        let home = shelter.display_name().to_string();
+        if is_night && looks_human {
+            shelter.invited = true;
+            Some(WeaknessEvent::Invited(home))
+        } else {
+            Some(WeaknessEvent::Refused(home))
+        }
+    }
+
+    /// Where to put a player who ended up inside a garlic field: back where
+    /// they came from, or if that is garlic too, just past the nearest edge
+    fn outside_garlic(hazards: &[HazardZone], position: Position, previous: Position) -> Position {
+        let garlic = hazards
+            .iter()
+            .filter(|zone| zone.kind == HazardKind::GarlicField);
+        let Some(zone) = garlic.clone().find(|zone| zone.contains(&position)) else {
+            return position;
+        };
+        if !garlic.clone().any(|zone| zone.contains(&previous)) {
+            return previous;
+        }
+
+        let exits = [
+            Position::new(zone.x - 1.0, position.y),
+            Position::new(zone.x + zone.width + 1.0, position.y),
+            Position::new(position.x, zone.y - 1.0),
+            Position::new(position.x, zone.y + zone.height + 1.0),
+        ];
+        exits
+            .into_iter()
+            .min_by(|a, b| {
+                a.distance_to(&position)
+                    .total_cmp(&b.distance_to(&position))
+            })
+            .unwrap_or(position)
+    }
+}
+
+/// Something a weakness did to the player
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeaknessEvent {
+    EnteredRunningWater,
+    GarlicRepelled,
+    /// The family of the named home invited the player in
+    Invited(String),
+    Refused(String),
+}
+
+impl WeaknessEvent {
+    pub fn get_message(&self) -> String {
+        match self {
+            WeaknessEvent::EnteredRunningWater => {
+                "Running water! It burns through your blood - get across quickly.".to_string()
+            }
+            WeaknessEvent::GarlicRepelled => "The stench of garlic drives you back.".to_string(),
+            WeaknessEvent::Invited(home) => {
+                format!("The family of the {} invites you in.", home)
+            }
+            WeaknessEvent::Refused(home) => {
+                format!("Nobody at the {} will open the door to you.", home)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::world::WorldSystem;
+
+    fn player_at(x: f32, y: f32) -> Vec<GameEntity> {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        WorldSystem::spawn_player(&mut entities, &mut ids);
+        entities[0].position = Position::new(x, y);
+        entities
+    }
+
+    #[test]
+    fn test_running_water_drains_blood_unless_flying() {
+        let hazards = vec![HazardZone::new(
+            HazardKind::RunningWater,
+            500.0,
+            640.0,
+            40.0,
+            560.0,
+        )];
+        let mut entities = player_at(520.0, 700.0);
+        let previous = Position::new(490.0, 700.0);
+
+        let event = WeaknessSystem::update(
+            &mut entities,
+            0,
+            &hazards,
+            &WeaknessRules::default(),
+            previous,
+            1.0,
+        );
+        assert_eq!(event, Some(WeaknessEvent::EnteredRunningWater));
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 35.0);
+
+        // Switched off, the water is harmless
+        let rules = WeaknessRules {
+            running_water: false,
+            ..WeaknessRules::default()
+        };
+        WeaknessSystem::update(&mut entities, 0, &hazards, &rules, previous, 1.0);
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 35.0);
+
+        // Bat form flies over
+        entities[0]
+            .vampire_abilities
+            .as_mut()
+            .unwrap()
+            .ability_state
+            .trigger(SpecialAbility::BatForm);
+        WeaknessSystem::update(
+            &mut entities,
+            0,
+            &hazards,
+            &WeaknessRules::default(),
+            previous,
+            1.0,
+        );
+        assert_eq!(entities[0].blood_meter.as_ref().unwrap().current, 35.0);
+    }
+
+    #[test]
+    fn test_garlic_field_turns_player_back() {
+        let hazards = vec![HazardZone::new(
+            HazardKind::GarlicField,
+            100.0,
+            900.0,
+            100.0,
+            100.0,
+        )];
+        let mut entities = player_at(105.0, 950.0);
+        let previous = Position::new(95.0, 950.0);
+
+        let event = WeaknessSystem::update(
+            &mut entities,
+            0,
+            &hazards,
+            &WeaknessRules::default(),
+            previous,
+            0.1,
+        );
+        assert_eq!(event, Some(WeaknessEvent::GarlicRepelled));
+        assert_eq!(entities[0].position, previous);
+
+        // Already stuck inside: pushed out through the nearest edge
+        entities[0].position = Position::new(190.0, 950.0);
+        WeaknessSystem::update(
+            &mut entities,
+            0,
+            &hazards,
+            &WeaknessRules::default(),
+            Position::new(185.0, 950.0),
+            0.1,
+        );
+        assert_eq!(entities[0].position, Position::new(201.0, 950.0));
+    }
+
+    #[test]
+    fn test_threshold_needs_invitation() {
+        let mut entities = player_at(300.0, 700.0);
+        let mut ids = EntityAllocator::from_ids([entities[0].id], 1);
+        let home_id = crate::systems::ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Building,
+            330.0,
+            700.0,
+            None,
+            Some("Miller House".to_string()),
+        );
+        entities[1].shelter.as_mut().unwrap().inhabited = true;
+        let grid = SpatialGrid::from_entities(&entities);
+
+        // Barred until invited
+        let message = crate::systems::ShelterSystem::handle_player_shelter_interaction(
+            &mut entities,
+            &grid,
+            0,
+            0.0,
+            true,
+        );
+        assert!(message.unwrap().contains("not been invited"));
+
+        // Hungry vampires are turned away; well-fed ones are let in at night
+        entities[0].blood_meter.as_mut().unwrap().current = 30.0;
+        assert_eq!(
+            WeaknessSystem::knock(&mut entities, &grid, 0, true),
+            Some(WeaknessEvent::Refused("Miller House".to_string()))
+        );
+        entities[0].blood_meter.as_mut().unwrap().current = 80.0;
+        assert_eq!(
+            WeaknessSystem::knock(&mut entities, &grid, 0, true),
+            Some(WeaknessEvent::Invited("Miller House".to_string()))
+        );
+
+        crate::systems::ShelterSystem::handle_player_shelter_interaction(
+            &mut entities,
+            &grid,
+            0,
+            0.0,
+            true,
+        );
+        let occupancy = entities[0].shelter_occupancy.as_ref().unwrap();
+        assert_eq!(occupancy.shelter_id, Some(home_id));
+    }
+}
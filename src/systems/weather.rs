@@ -0,0 +1,74 @@
+//! Weather System Module
+//!
+//! Turns the weather at every dawn and dusk. Rain, fog and an overcast sky
+//! all weaken the sun, so a grey day is safer to be abroad in; rain muffles
+//! footsteps and fog hides the vampire from hostile eyes.
+
+use crate::components::*;
+use crate::systems::time::TimeSystem;
+
+/// The weather turning from one state to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeatherChange {
+    pub from: Weather,
+    pub to: Weather,
+}
+
+impl WeatherChange {
+    pub fn get_message(&self) -> String {
+        match (self.from, self.to) {
+            (_, Weather::Rain) => "Rain begins to fall, muffling your steps.".to_string(),
+            (_, Weather::Fog) => "Fog rolls in, hiding you from prying eyes.".to_string(),
+            (_, Weather::Overcast) => "Clouds gather and dull the sky.".to_string(),
+            (Weather::Rain, Weather::Clear) => "The rain stops.".to_string(),
+            (Weather::Fog, Weather::Clear) => "The fog lifts.".to_string(),
+            _ => "The sky clears.".to_string(),
+        }
+    }
+}
+
+/// Weather system responsible for the changing sky
+pub struct WeatherSystem;
+
+impl WeatherSystem {
+    /// Roll the weather for the coming day or night with `roll` in 0.0..1.0,
+    /// returning the change if it turned
+    pub fn turn(time: &mut TimeSystem, roll: f32) -> Option<WeatherChange> {
+        let from = time.weather();
+        let to = Weather::roll(roll);
+        if to == from {
+            return None;
+        }
+        time.set_weather(to);
+        Some(WeatherChange { from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_turns_and_dims_the_noon_sun() {
+        let mut time = TimeSystem::with_settings(12.0, 120.0);
+        assert_eq!(time.get_sunlight_intensity(), 1.0);
+
+        let change = WeatherSystem::turn(&mut time, 0.3).unwrap();
+        assert_eq!(change.to, Weather::Fog);
+        assert!(change.get_message().contains("Fog"));
+        assert_eq!(time.get_sunlight_intensity(), 0.75);
+        assert!(WeatherSystem::turn(&mut time, 0.3).is_none());
+
+        WeatherSystem::turn(&mut time, 0.1);
+        assert_eq!(time.weather(), Weather::Rain);
+        assert_eq!(time.get_sunlight_intensity(), 0.5);
+        WeatherSystem::turn(&mut time, 0.5);
+        assert_eq!(time.weather(), Weather::Overcast);
+        assert_eq!(
+            WeatherSystem::turn(&mut time, 0.9).unwrap().get_message(),
+            "The sky clears."
+        );
+        assert_eq!(Weather::Fog.sight_factor(), 0.75);
+        assert_eq!(Weather::Rain.sight_factor(), 1.0);
+    }
+}
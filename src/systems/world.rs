@@ -4,83 +4,186 @@
 //! This system is responsible for creating the initial game world state.
 
 use crate::components::*;
+use crate::settings::Difficulty;
+use crate::world_data::{Archetype, CombatTemplate, WorldData};
 use macroquad::prelude::*;
+use macroquad::rand::RandGenerator;
 use std::collections::HashMap;
 
+/// Top of the ground; everything above is sky
+const GROUND_LEVEL: f32 = WorldBounds::GROUND_LEVEL;
+
+/// Tiles around the player's starting point that are always dry land:
+/// first and last column, first and last row
+const START_CLEARING: (i32, i32, i32, i32) = (3, 8, 10, 12);
+
+/// One river winds down the map in every band this many tiles wide
+const RIVER_SPACING: i32 = 48;
+
+/// Furthest a river winds either side of its course, in tiles
+const RIVER_MEANDER: f32 = 1.5;
+
+/// Width of the square cells, in tiles, that may each hold a lake
+const LAKE_CELL: i32 = 8;
+
+/// Percentage of lake cells that do hold one
+const LAKE_CHANCE: u64 = 30;
+
+/// Chunks within this many chunks of the camera are loaded
+const CHUNK_LOAD_RADIUS: i32 = 1;
+
+/// Chunks further than this from the camera are unloaded; the gap to the
+/// load radius stops chunks flickering in and out at a boundary
+const CHUNK_UNLOAD_RADIUS: i32 = 2;
+
+/// Items the player can carry at once
+const PLAYER_INVENTORY_CAPACITY: u32 = 20;
+
+/// How far from their leader clan members make camp
+pub const CAMP_RADIUS: f32 = 120.0;
+
+/// Range of a clan member's patrol circle around its post
+const PATROL_RADIUS: (f32, f32) = (60.0, 110.0);
+
+/// Items left lying around the world: position, item and quantity
+const ITEM_CACHES: [(f32, f32, ItemKind, u32); 10] = [
+    (520.0, 700.0, ItemKind::Bandage, 2),
+    (760.0, 880.0, ItemKind::BloodVial, 1),
+    (1180.0, 1080.0, ItemKind::BloodVial, 2),
+    (340.0, 1120.0, ItemKind::Key, 1),
+    (940.0, 760.0, ItemKind::Trinket, 1),
+    (1400.0, 720.0, ItemKind::Trinket, 1),
+    (620.0, 1140.0, ItemKind::Bandage, 1),
+    (300.0, 900.0, ItemKind::HunterNotes, 1),
+    (1050.0, 1000.0, ItemKind::HunterNotes, 1),
+    (1500.0, 1150.0, ItemKind::HunterNotes, 2),
+];
+
+/// A cellar whose door only opens with a key: position and name
+const LOCKED_CELLAR: (f32, f32, &str) = (880.0, 1120.0, "Old Cellar");
+
+/// Road positions (x) where signposts stand
+const SIGNPOST_SITES: [f32; 4] = [80.0, 400.0, 1000.0, 1400.0];
+
+/// Height of the road running along the top of the ground
+pub const ROAD_LEVEL: f32 = 680.0;
+
+/// Signposts never point to a territory closer than this
+const SIGNPOST_MIN_DISTANCE: f32 = 150.0;
+
+/// How close the player must stand to read a signpost
+pub const SIGNPOST_READ_RANGE: f32 = 40.0;
+
+/// Shelters this close to a signpost's destination are revealed when it is read
+const SIGNPOST_REVEAL_RADIUS: f32 = 350.0;
+
 /// World system responsible for entity spawning and world management
 pub struct WorldSystem;
 
 impl WorldSystem {
-    /// Initialize the game world with all starting entities and environment
+    /// Initialize the game world with all starting entities and environment,
+    /// as laid out by the world data tables. The difficulty decides how many
+    /// infected roam it, and the seed where everything random falls: the
+    /// same seed always lays out the same world.
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_world(
         entities: &mut Vec<GameEntity>,
         clans: &mut HashMap<String, Clan>,
         stars: &mut Vec<Star>,
         moon: &mut Moon,
         ground_tiles: &mut Vec<GroundTile>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
+        difficulty: Difficulty,
+        seed: u64,
     ) -> u32 {
+        let rng = RandGenerator::new();
+        rng.srand(seed);
+
         // Clear existing entities
         entities.clear();
 
         // Create the player entity
-        let player_id = Self::spawn_player(entities, next_entity_id);
+        let player_id = Self::spawn_player(entities, entity_ids);
 
         // Initialize clans
         Self::initialize_clans(clans);
 
         // Spawn clan leaders
-        Self::spawn_all_clan_leaders(entities, next_entity_id);
+        Self::spawn_all_clan_leaders(entities, entity_ids);
 
         // Spawn hostile infected creatures
-        Self::spawn_hostile_infected_group(entities, next_entity_id, 8);
+        let archetypes = &WorldData::current().archetypes;
+        Self::spawn_hostile_infected_group(
+            entities,
+            entity_ids,
+            difficulty.scale_spawn_count(archetypes.infected.count),
+            &rng,
+        );
 
-        // Spawn animals (blood sources)
-        Self::spawn_animal_group(entities, next_entity_id, 12);
+        // Spawn animals (blood sources), each suited to the ground it is on
+        Self::spawn_animal_group(entities, entity_ids, archetypes.animal.count, seed, &rng);
 
         // Spawn shelters throughout the world
-        Self::spawn_world_shelters(entities, next_entity_id);
+        Self::spawn_world_shelters(entities, entity_ids, &rng);
+        Self::settle_households(entities, entity_ids);
+
+        // Scatter item pickups and the locked cellar their key opens
+        Self::spawn_item_caches(entities, entity_ids);
+
+        // Camp each clan's members around its leader
+        Self::spawn_clan_camps(entities, entity_ids, clans, &rng);
 
         // Initialize environment
-        Self::initialize_starfield(stars);
+        Self::initialize_starfield(stars, &rng);
         Self::initialize_moon(moon);
-        Self::initialize_ground_terrain(ground_tiles);
+        Self::initialize_ground_terrain(ground_tiles, seed);
 
         player_id
     }
 
     /// Create the player entity
-    pub fn spawn_player(entities: &mut Vec<GameEntity>, next_entity_id: &mut u32) -> u32 {
-        let player_id = *next_entity_id;
+    pub fn spawn_player(entities: &mut Vec<GameEntity>, entity_ids: &mut EntityAllocator) -> u32 {
+        let player_id = entity_ids.allocate();
+        let archetype = &WorldData::current().archetypes.player;
         let player = GameEntity {
             id: player_id,
             position: Position { x: 400.0, y: 650.0 },
             velocity: Some(Velocity { x: 0.0, y: 0.0 }),
             entity_type: EntityType::Player,
-            health: Some(Health {
-                current: 100.0,
-                max: 100.0,
-            }),
-            combat_stats: Some(CombatStats::new(25.0, 10.0)),
+            health: Some(Self::health_of(archetype)),
+            combat_stats: Self::combat_stats_of(archetype),
             ai_state: AIState::Idle,
             blood_meter: Some(BloodMeter {
                 current: 50.0,
                 maximum: 100.0,
                 drain_rate: 1.0,
+                feeding: FeedingState::default(),
+                bottling: 0.0,
             }),
             vampire_abilities: Some(VampireAbilities {
                 strength: 1.0,
                 speed: 1.0,
                 blood_sense: 0.0,
                 shadow_movement: 0.0,
+                ability_state: AbilityState::default(),
             }),
             shelter: None,
             shelter_occupancy: Some(ShelterOccupancy::new()),
             color: RED,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: Some(Inventory::new(PLAYER_INVENTORY_CAPACITY)),
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: Collider::for_creature(&EntityType::Player),
+            hunger: None,
+            buffs: Some(Buffs::default()),
+            progression: Some(Progression::default()),
+            species: None,
         };
 
         entities.push(player);
-        *next_entity_id += 1;
         player_id
     }
 
@@ -88,55 +191,37 @@ impl WorldSystem {
     pub fn initialize_clans(clans: &mut HashMap<String, Clan>) {
         clans.clear();
 
-        clans.insert(
-            "Bone-Eaters".to_string(),
-            Clan::new("Bone-Eaters", "Grimjaw", 15),
-        );
-        clans.insert(
-            "Flame-Haters".to_string(),
-            Clan::new("Flame-Haters", "Shadowmere", 12),
-        );
-        clans.insert(
-            "Night-Bloods".to_string(),
-            Clan::new("Night-Bloods", "Silentfang", 10),
-        );
+        for clan in &WorldData::current().clans {
+            clans.insert(
+                clan.name.clone(),
+                Clan::new(&clan.name, &clan.leader, clan.members),
+            );
+        }
     }
 
-    /// Spawn all clan leaders at their designated positions
-    pub fn spawn_all_clan_leaders(entities: &mut Vec<GameEntity>, next_entity_id: &mut u32) {
-        Self::spawn_clan_leader(
-            entities,
-            next_entity_id,
-            "Grimjaw",
-            "Bone-Eaters",
-            200.0,
-            650.0,
-            BEIGE,
-        );
-        Self::spawn_clan_leader(
-            entities,
-            next_entity_id,
-            "Shadowmere",
-            "Flame-Haters",
-            600.0,
-            650.0,
-            PURPLE,
-        );
-        Self::spawn_clan_leader(
-            entities,
-            next_entity_id,
-            "Silentfang",
-            "Night-Bloods",
-            800.0,
-            650.0,
-            DARKBLUE,
-        );
+    /// Spawn all clan leaders at their clans' camps
+    pub fn spawn_all_clan_leaders(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+    ) {
+        for clan in &WorldData::current().clans {
+            let (x, y) = clan.camp;
+            Self::spawn_clan_leader(
+                entities,
+                entity_ids,
+                &clan.leader,
+                &clan.name,
+                x,
+                y,
+                clan.leader_color(),
+            );
+        }
     }
 
     /// Spawn a single clan leader
     pub fn spawn_clan_leader(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         _name: &str,
         clan_name: &str,
         x: f32,
@@ -157,163 +242,237 @@ impl WorldSystem {
                 10,
             ) {
                 return Self::spawn_clan_leader_at_position(
-                    entities,
-                    next_entity_id,
-                    _name,
-                    clan_name,
-                    safe_x,
-                    safe_y,
-                    color,
+                    entities, entity_ids, _name, clan_name, safe_x, safe_y, color,
                 );
             } else {
                 // Fallback to minimum ground level
                 let safe_y = 650.0; // Ground level + padding
                 eprintln!("Using fallback ground position: ({}, {})", x, safe_y);
                 return Self::spawn_clan_leader_at_position(
-                    entities,
-                    next_entity_id,
-                    _name,
-                    clan_name,
-                    x,
-                    safe_y,
-                    color,
+                    entities, entity_ids, _name, clan_name, x, safe_y, color,
                 );
             }
         }
 
-        Self::spawn_clan_leader_at_position(entities, next_entity_id, _name, clan_name, x, y, color)
+        Self::spawn_clan_leader_at_position(entities, entity_ids, _name, clan_name, x, y, color)
     }
 
     /// Internal function to spawn clan leader at verified position
     fn spawn_clan_leader_at_position(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         _name: &str,
         clan_name: &str,
         x: f32,
         y: f32,
         color: Color,
     ) -> u32 {
-        let entity_id = *next_entity_id;
+        let entity_id = entity_ids.allocate();
+        let entity_type = EntityType::ClanLeader(clan_name.to_string());
+        let archetype = &WorldData::current().archetypes.clan_leader;
         let entity = GameEntity {
             id: entity_id,
             position: Position { x, y },
             velocity: Some(Velocity { x: 0.0, y: 0.0 }),
-            entity_type: EntityType::ClanLeader(clan_name.to_string()),
-            health: Some(Health {
-                current: 120.0,
-                max: 120.0,
-            }),
-            combat_stats: Some(CombatStats::new(30.0, 15.0)),
+            collider: Collider::for_creature(&entity_type),
+            entity_type,
+            health: Some(Self::health_of(archetype)),
+            // Leaders shrug off blows that stagger their people
+            combat_stats: Self::combat_stats_of(archetype),
             ai_state: AIState::Idle,
             blood_meter: None,
             vampire_abilities: None,
             shelter: None,
             shelter_occupancy: None,
             color,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         };
 
         entities.push(entity);
-        *next_entity_id += 1;
         entity_id
     }
 
     /// Spawn a group of hostile infected creatures
     pub fn spawn_hostile_infected_group(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         count: usize,
+        rng: &RandGenerator,
     ) {
         (0..count).for_each(|_| {
             let (min_x, max_x, min_y, max_y) = Self::get_spawn_bounds(&EntityType::HostileInfected);
-            let x = rand::gen_range(min_x, max_x);
-            let y = rand::gen_range(min_y, max_y);
-            Self::spawn_hostile_infected(entities, next_entity_id, x, y);
+            let x = rng.gen_range(min_x, max_x);
+            let y = rng.gen_range(min_y, max_y);
+            let hunger = rng.gen_range(0.0, Hunger::HUNT_THRESHOLD);
+            Self::spawn_hostile_infected_with_hunger(entities, entity_ids, x, y, hunger);
         });
     }
 
     /// Spawn a single hostile infected creature
     pub fn spawn_hostile_infected(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         x: f32,
         y: f32,
     ) -> u32 {
-        let entity_id = *next_entity_id;
+        let hunger = rand::gen_range(0.0, Hunger::HUNT_THRESHOLD);
+        Self::spawn_hostile_infected_with_hunger(entities, entity_ids, x, y, hunger)
+    }
+
+    /// Internal function to spawn a hostile infected already this hungry
+    fn spawn_hostile_infected_with_hunger(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        x: f32,
+        y: f32,
+        hunger: f32,
+    ) -> u32 {
+        let entity_id = entity_ids.allocate();
+        let archetype = &WorldData::current().archetypes.infected;
         let entity = GameEntity {
             id: entity_id,
             position: Position { x, y },
             velocity: Some(Velocity { x: 0.0, y: 0.0 }),
             entity_type: EntityType::HostileInfected,
-            health: Some(Health {
-                current: 50.0,
-                max: 50.0,
-            }),
-            combat_stats: Some(CombatStats::new(20.0, 8.0)),
+            health: Some(Self::health_of(archetype)),
+            combat_stats: Self::combat_stats_of(archetype),
             ai_state: AIState::Hostile,
             blood_meter: None,
             vampire_abilities: None,
             shelter: None,
             shelter_occupancy: None,
             color: DARKGREEN,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: Collider::for_creature(&EntityType::HostileInfected),
+            hunger: Some(Hunger::new(hunger, Hunger::INFECTED_RATE)),
+            buffs: None,
+            progression: None,
+            species: None,
         };
 
         entities.push(entity);
-        *next_entity_id += 1;
         entity_id
     }
 
-    /// Spawn a group of animals
+    /// Spawn a group of animals, the species of each picked to suit the
+    /// ground where it lands
     pub fn spawn_animal_group(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         count: usize,
+        seed: u64,
+        rng: &RandGenerator,
     ) {
         (0..count).for_each(|_| {
             let (min_x, max_x, min_y, max_y) = Self::get_spawn_bounds(&EntityType::Animal);
-            let x = rand::gen_range(min_x, max_x);
-            let y = rand::gen_range(min_y, max_y);
-            Self::spawn_animal(entities, next_entity_id, x, y);
+            // Animals keep out of the water, so look for dry ground
+            let mut position =
+                Position::new(rng.gen_range(min_x, max_x), rng.gen_range(min_y, max_y));
+            for _ in 0..8 {
+                if !Self::is_water_at(&position, seed) {
+                    break;
+                }
+                position = Position::new(rng.gen_range(min_x, max_x), rng.gen_range(min_y, max_y));
+            }
+            let species = Self::species_for_ground(
+                Self::tile_type_at(&position, seed),
+                rng.gen_range(0.0, 1.0),
+            );
+            Self::spawn_animal(entities, entity_ids, species, position.x, position.y);
         });
     }
 
-    /// Spawn a single animal
+    /// The species found on a kind of ground, weighted by each species'
+    /// liking for it; `roll` is uniform in `0.0..1.0`. Above ground, any
+    /// species is as likely as another.
+    pub fn species_for_ground(tile: Option<TileType>, roll: f32) -> Species {
+        let archetypes = &WorldData::current().archetypes;
+        let weights: Vec<(Species, f32)> = Species::ALL
+            .iter()
+            .map(|&species| {
+                let weight = tile.map_or(1.0, |tile| {
+                    archetypes.species(species).terrain.weight(tile).max(0.0)
+                });
+                (species, weight)
+            })
+            .collect();
+        let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return Species::Deer;
+        }
+        let mut remaining = roll * total;
+        let mut picked = Species::Deer;
+        for &(species, weight) in weights.iter().filter(|(_, weight)| *weight > 0.0) {
+            picked = species;
+            if remaining < weight {
+                break;
+            }
+            remaining -= weight;
+        }
+        picked
+    }
+
+    /// Spawn a single animal of the given species
     pub fn spawn_animal(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
+        species: Species,
         x: f32,
         y: f32,
     ) -> u32 {
-        let entity_id = *next_entity_id;
+        let entity_id = entity_ids.allocate();
+        let archetype = WorldData::current().archetypes.species(species);
         let entity = GameEntity {
             id: entity_id,
             position: Position { x, y },
             velocity: Some(Velocity { x: 0.0, y: 0.0 }),
             entity_type: EntityType::Animal,
-            health: Some(Health {
-                current: 25.0,
-                max: 25.0,
-            }),
-            combat_stats: None,
-            ai_state: AIState::Idle,
+            health: Some(Health::new(archetype.health)),
+            combat_stats: archetype.combat.as_ref().map(CombatTemplate::stats),
+            ai_state: AIState::Wander,
             blood_meter: None,
             vampire_abilities: None,
             shelter: None,
             shelter_occupancy: None,
-            color: BROWN,
+            color: archetype.color(),
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: Some(Wander::new(Position { x, y }, archetype.wander_range)),
+            collider: Collider::for_creature(&EntityType::Animal),
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: Some(species),
         };
 
         entities.push(entity);
-        *next_entity_id += 1;
         entity_id
     }
 
     /// Initialize the starfield background
-    pub fn initialize_starfield(stars: &mut Vec<Star>) {
+    pub fn initialize_starfield(stars: &mut Vec<Star>, rng: &RandGenerator) {
+        let area = WorldBounds::default();
         stars.clear();
         (0..200).for_each(|_| {
-            let x = rand::gen_range(0.0, 1600.0);
-            let y = rand::gen_range(0.0, 1200.0);
+            let x = rng.gen_range(area.min_x, area.max_x);
+            let y = rng.gen_range(0.0, area.max_y);
             stars.push(Star::new(x, y));
         });
     }
@@ -323,31 +482,104 @@ impl WorldSystem {
         *moon = Moon::new();
     }
 
-    /// Initialize ground terrain tiles
-    pub fn initialize_ground_terrain(ground_tiles: &mut Vec<GroundTile>) {
+    /// Generate ground tiles for the starting area of the world. Everything
+    /// beyond it is streamed in chunk by chunk by `stream_terrain`.
+    pub fn initialize_ground_terrain(ground_tiles: &mut Vec<GroundTile>, seed: u64) {
         ground_tiles.clear();
 
-        let tile_size = 64.0;
-        let world_width = 1600.0;
-        let world_height = 1200.0;
-        let ground_level = 640.0; // Ground starts at y = 640 (aligned with tile positions)
+        let area = WorldBounds::default();
+        let (min_x, min_y) = TerrainChunks::coord_of(area.min_x, GROUND_LEVEL);
+        let (max_x, max_y) = TerrainChunks::coord_of(area.max_x - 1.0, area.max_y - 1.0);
+        for chunk_x in min_x..=max_x {
+            for chunk_y in min_y..=max_y {
+                ground_tiles.extend(Self::generate_chunk((chunk_x, chunk_y), seed));
+            }
+        }
+    }
 
-        for x in (0..((world_width / tile_size) as i32)).map(|i| i as f32 * tile_size) {
-            // Ensure tiles start exactly at ground level
-            let start_tile_y = ((ground_level / tile_size).ceil() as i32) * tile_size as i32;
-            for y in (start_tile_y..((world_height / tile_size) as i32 * tile_size as i32))
-                .step_by(tile_size as usize)
-                .map(|i| i as f32)
-            {
-                let tile_type = Self::determine_tile_type();
-                ground_tiles.push(GroundTile::new(x, y, tile_type));
+    /// Load terrain chunks around `center` and drop chunks that have fallen
+    /// far behind, keeping `ground_tiles` bounded however far the world
+    /// extends. `bounds` grows to take in every chunk around `center`, so
+    /// there is always ground to walk onto. Returns the number of chunks
+    /// loaded and unloaded.
+    pub fn stream_terrain(
+        ground_tiles: &mut Vec<GroundTile>,
+        chunks: &mut TerrainChunks,
+        bounds: &mut WorldBounds,
+        center: Position,
+        seed: u64,
+    ) -> (usize, usize) {
+        let (center_x, center_y) = TerrainChunks::coord_of(center.x, center.y);
+
+        let dropped = chunks.unload_where(|(x, y)| {
+            (x - center_x).abs() <= CHUNK_UNLOAD_RADIUS
+                && (y - center_y).abs() <= CHUNK_UNLOAD_RADIUS
+        });
+        if !dropped.is_empty() {
+            ground_tiles.retain(|tile| !dropped.contains(&TerrainChunks::coord_of(tile.x, tile.y)));
+        }
+
+        let mut loaded = 0;
+        for chunk_x in center_x - CHUNK_LOAD_RADIUS..=center_x + CHUNK_LOAD_RADIUS {
+            for chunk_y in center_y - CHUNK_LOAD_RADIUS..=center_y + CHUNK_LOAD_RADIUS {
+                let coord = (chunk_x, chunk_y);
+                bounds.include_chunk(coord);
+                if !chunks.is_loaded(coord) {
+                    ground_tiles.extend(Self::generate_chunk(coord, seed));
+                    chunks.mark_loaded(coord);
+                    loaded += 1;
+                }
             }
         }
+
+        (loaded, dropped.len())
+    }
+
+    /// Generate the ground tiles of one chunk. Chunks above ground level are
+    /// empty sky. Tile types depend only on position and the world seed, so
+    /// a chunk looks the same every time it is streamed back in.
+    pub fn generate_chunk(coord: ChunkCoord, seed: u64) -> Vec<GroundTile> {
+        let tiles_per_side = (TerrainChunks::SIZE / GroundTile::SIZE) as i32;
+        let first_x = coord.0 * tiles_per_side;
+        let first_y = coord.1 * tiles_per_side;
+        let ground_row = (GROUND_LEVEL / GroundTile::SIZE).ceil() as i32;
+
+        (first_x..first_x + tiles_per_side)
+            .flat_map(|tile_x| {
+                (first_y.max(ground_row)..first_y + tiles_per_side).map(move |tile_y| {
+                    GroundTile::new(
+                        tile_x as f32 * GroundTile::SIZE,
+                        tile_y as f32 * GroundTile::SIZE,
+                        Self::determine_tile_type(tile_x, tile_y, seed),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Type of the ground tile under a position, or None above ground
+    pub fn tile_type_at(position: &Position, seed: u64) -> Option<TileType> {
+        let tile_y = (position.y / GroundTile::SIZE).floor() as i32;
+        if tile_y < (GROUND_LEVEL / GroundTile::SIZE).ceil() as i32 {
+            return None;
+        }
+        let tile_x = (position.x / GroundTile::SIZE).floor() as i32;
+        Some(Self::determine_tile_type(tile_x, tile_y, seed))
+    }
+
+    /// Whether a position is in a river or lake
+    pub fn is_water_at(position: &Position, seed: u64) -> bool {
+        Self::tile_type_at(position, seed) == Some(TileType::Water)
     }
 
-    /// Determine the type of tile to place based on random generation
-    fn determine_tile_type() -> TileType {
-        match rand::gen_range(0, 100) {
+    /// Pick a tile type from a hash of the tile's grid position and the
+    /// world seed
+    fn determine_tile_type(tile_x: i32, tile_y: i32, seed: u64) -> TileType {
+        if Self::is_water_tile(tile_x, tile_y, seed) {
+            return TileType::Water;
+        }
+
+        match Self::tile_hash(tile_x, tile_y, seed) % 100 {
             0..=60 => TileType::Grass,
             61..=80 => TileType::DeadGrass,
             81..=95 => TileType::Dirt,
@@ -355,6 +587,47 @@ impl WorldSystem {
         }
     }
 
+    /// Whether a tile lies in a river or lake. A river winds down each band
+    /// of `RIVER_SPACING` columns and some `LAKE_CELL` squares pool into a
+    /// lake; the road bridges the rivers and the player's starting
+    /// clearing is always dry.
+    fn is_water_tile(tile_x: i32, tile_y: i32, seed: u64) -> bool {
+        let road_row = (ROAD_LEVEL / GroundTile::SIZE).floor() as i32;
+        let (min_x, max_x, min_y, max_y) = START_CLEARING;
+        if tile_y == road_row
+            || ((min_x..=max_x).contains(&tile_x) && (min_y..=max_y).contains(&tile_y))
+        {
+            return false;
+        }
+
+        let region = tile_x.div_euclid(RIVER_SPACING);
+        let river = Self::tile_hash(region, -1, seed);
+        let course = (region * RIVER_SPACING) as f32 + 12.0 + (river % 9) as f32;
+        let phase = ((river >> 8) % 628) as f32 / 100.0;
+        let centre = course + RIVER_MEANDER * (tile_y as f32 * 0.7 + phase).sin();
+        if (tile_x as f32 + 0.5 - centre).abs() < 0.75 {
+            return true;
+        }
+
+        let (cell_x, cell_y) = (tile_x.div_euclid(LAKE_CELL), tile_y.div_euclid(LAKE_CELL));
+        let lake = Self::tile_hash(cell_x, cell_y, !seed);
+        if lake % 100 >= LAKE_CHANCE {
+            return false;
+        }
+        let centre_x = (cell_x * LAKE_CELL) as f32 + 2.0 + ((lake >> 8) % 4) as f32;
+        let centre_y = (cell_y * LAKE_CELL) as f32 + 2.0 + ((lake >> 16) % 4) as f32;
+        let radius = 1.0 + ((lake >> 24) % 10) as f32 / 10.0;
+        (tile_x as f32 + 0.5 - centre_x).hypot(tile_y as f32 + 0.5 - centre_y) <= radius
+    }
+
+    /// Hash of a grid position and the world seed
+    fn tile_hash(tile_x: i32, tile_y: i32, seed: u64) -> u64 {
+        let mut hash = ((tile_x as u32 as u64) << 32 | tile_y as u32 as u64) ^ seed;
+        hash = (hash ^ (hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+        hash = (hash ^ (hash >> 33)).wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        hash ^ (hash >> 33)
+    }
+
     /// Check if a position has ground (is within the ground area)
     pub fn has_ground_at_position(x: f32, y: f32) -> bool {
         let world_width = 1600.0;
@@ -366,15 +639,15 @@ impl WorldSystem {
     }
 
     /// Generate a random position within the ground area
-    pub fn generate_random_ground_position() -> (f32, f32) {
+    pub fn generate_random_ground_position(rng: &RandGenerator) -> (f32, f32) {
         let world_width = 1600.0;
         let world_height = 1200.0;
         let ground_level = 640.0;
 
         // Generate random position within ground area with some padding from edges
         let padding = 64.0;
-        let x = rand::gen_range(padding, world_width - padding);
-        let y = rand::gen_range(ground_level + padding, world_height - padding);
+        let x = rng.gen_range(padding, world_width - padding);
+        let y = rng.gen_range(ground_level + padding, world_height - padding);
 
         (x, y)
     }
@@ -393,12 +666,17 @@ impl WorldSystem {
     /// Spawn a clan member at a specific location
     pub fn spawn_clan_member(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         clan_name: &str,
         x: f32,
         y: f32,
         color: Color,
     ) -> u32 {
+        let patrol = (
+            rand::gen_range(0.0, std::f32::consts::TAU),
+            rand::gen_range(PATROL_RADIUS.0, PATROL_RADIUS.1),
+        );
+
         // Validate ground position
         if !Self::has_ground_at_position(x, y) {
             eprintln!(
@@ -413,64 +691,201 @@ impl WorldSystem {
                 10,
             ) {
                 return Self::spawn_clan_member_at_position(
-                    entities,
-                    next_entity_id,
-                    clan_name,
-                    safe_x,
-                    safe_y,
-                    color,
+                    entities, entity_ids, clan_name, safe_x, safe_y, color, patrol,
                 );
             } else {
                 // Fallback to minimum ground level
                 let safe_y = 650.0; // Ground level + padding
                 eprintln!("Using fallback ground position: ({}, {})", x, safe_y);
                 return Self::spawn_clan_member_at_position(
+                    entities, entity_ids, clan_name, x, safe_y, color, patrol,
+                );
+            }
+        }
+
+        Self::spawn_clan_member_at_position(entities, entity_ids, clan_name, x, y, color, patrol)
+    }
+
+    /// Camp each clan's starting members around its leader, placed and
+    /// given their patrols from the world's seeded generator
+    pub fn spawn_clan_camps(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        clans: &HashMap<String, Clan>,
+        rng: &RandGenerator,
+    ) {
+        let mut clan_names: Vec<&String> = clans.keys().collect();
+        clan_names.sort();
+        for clan_name in clan_names {
+            let Some(camp) = entities
+                .iter()
+                .find(|entity| {
+                    matches!(&entity.entity_type, EntityType::ClanLeader(name) if name == clan_name)
+                })
+                .map(|leader| leader.position)
+            else {
+                continue;
+            };
+
+            let color = Self::clan_member_color(clan_name);
+            // The leader is counted among the members
+            for _ in 1..clans[clan_name].member_count {
+                let angle = rng.gen_range(0.0, std::f32::consts::TAU);
+                let distance = rng.gen_range(30.0, CAMP_RADIUS);
+                let patrol = (
+                    rng.gen_range(0.0, std::f32::consts::TAU),
+                    rng.gen_range(PATROL_RADIUS.0, PATROL_RADIUS.1),
+                );
+                Self::spawn_clan_member_at_position(
                     entities,
-                    next_entity_id,
+                    entity_ids,
                     clan_name,
-                    x,
-                    safe_y,
+                    camp.x + angle.cos() * distance,
+                    (camp.y + angle.sin() * distance).max(650.0),
                     color,
+                    patrol,
                 );
             }
         }
-
-        Self::spawn_clan_member_at_position(entities, next_entity_id, clan_name, x, y, color)
     }
 
-    /// Internal function to spawn clan member at verified position
+    /// Internal function to spawn clan member at verified position, with
+    /// the angle and radius of its place on its patrol circle
     fn spawn_clan_member_at_position(
         entities: &mut Vec<GameEntity>,
-        next_entity_id: &mut u32,
+        entity_ids: &mut EntityAllocator,
         clan_name: &str,
         x: f32,
         y: f32,
         color: Color,
+        patrol: (f32, f32),
     ) -> u32 {
-        let entity_id = *next_entity_id;
+        let entity_id = entity_ids.allocate();
+        let entity_type = EntityType::ClanMember(clan_name.to_string());
+        let archetype = &WorldData::current().archetypes.clan_member;
         let entity = GameEntity {
             id: entity_id,
             position: Position { x, y },
             velocity: Some(Velocity { x: 0.0, y: 0.0 }),
-            entity_type: EntityType::ClanMember(clan_name.to_string()),
-            health: Some(Health {
-                current: 80.0,
-                max: 80.0,
-            }),
-            combat_stats: Some(CombatStats::new(15.0, 5.0)),
+            collider: Collider::for_creature(&entity_type),
+            entity_type,
+            health: Some(Self::health_of(archetype)),
+            combat_stats: Self::combat_stats_of(archetype),
             ai_state: AIState::Idle,
             blood_meter: None,
             vampire_abilities: None,
             shelter: None,
             shelter_occupancy: None,
             color,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: Some(Schedule::new(Position { x, y }, patrol.0, patrol.1)),
+            wander: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         };
 
         entities.push(entity);
-        *next_entity_id += 1;
         entity_id
     }
 
+    /// Full health for a creature of this archetype
+    fn health_of(archetype: &Archetype) -> Health {
+        Health {
+            current: archetype.health,
+            max: archetype.health,
+        }
+    }
+
+    fn combat_stats_of(archetype: &Archetype) -> Option<CombatStats> {
+        archetype.combat.as_ref().map(|combat| combat.stats())
+    }
+
+    /// Get the color used for members of a clan
+    pub fn clan_member_color(clan_name: &str) -> Color {
+        WorldData::current()
+            .clan(clan_name)
+            .map_or(WHITE, |clan| clan.member_color())
+    }
+
+    /// Name of the territory a clan holds around its leader
+    pub fn clan_territory_name(clan_name: &str) -> Option<&'static str> {
+        WorldData::current()
+            .clan(clan_name)
+            .and_then(|clan| clan.territory.as_deref())
+    }
+
+    /// Place signposts along the road, each pointing to the nearest clan
+    /// territory that is not already in plain sight of it
+    pub fn place_signposts(entities: &[GameEntity]) -> Vec<Signpost> {
+        let territories: Vec<(&'static str, Position)> = entities
+            .iter()
+            .filter_map(|entity| match &entity.entity_type {
+                EntityType::ClanLeader(clan_name) => {
+                    Self::clan_territory_name(clan_name).map(|name| (name, entity.position))
+                }
+                _ => None,
+            })
+            .collect();
+
+        SIGNPOST_SITES
+            .iter()
+            .filter_map(|&x| {
+                let position = Position::new(x, ROAD_LEVEL);
+                territories
+                    .iter()
+                    .filter(|(_, home)| position.distance_to(home) >= SIGNPOST_MIN_DISTANCE)
+                    .min_by(|(_, a), (_, b)| {
+                        position.distance_to(a).total_cmp(&position.distance_to(b))
+                    })
+                    .map(|(name, home)| Signpost::new(position, name, *home))
+            })
+            .collect()
+    }
+
+    /// Read the signpost next to the player. Shelters around the territory it
+    /// points to are marked as discovered so they appear on the map.
+    pub fn read_signpost(
+        signposts: &mut [Signpost],
+        entities: &mut [GameEntity],
+        player_id: u32,
+    ) -> Option<String> {
+        let player_pos = EntityFinder::by_id(entities, player_id)?.position;
+        let signpost = signposts
+            .iter_mut()
+            .find(|signpost| signpost.position.distance_to(&player_pos) <= SIGNPOST_READ_RANGE)?;
+        signpost.read = true;
+
+        let mut revealed = 0;
+        for entity in entities.iter_mut() {
+            if let Some(shelter) = entity.shelter.as_mut() {
+                if !shelter.discovered
+                    && entity.position.distance_to(&signpost.destination) <= SIGNPOST_REVEAL_RADIUS
+                {
+                    shelter.discover();
+                    revealed += 1;
+                }
+            }
+        }
+
+        Some(match revealed {
+            0 => format!("The sign reads: {}", signpost.text()),
+            1 => format!(
+                "The sign reads: {} (1 shelter marked on your map)",
+                signpost.text()
+            ),
+            count => format!(
+                "The sign reads: {} ({} shelters marked on your map)",
+                signpost.text(),
+                count
+            ),
+        })
+    }
+
     /// Get spawn bounds for different entity types
     pub fn get_spawn_bounds(entity_type: &EntityType) -> (f32, f32, f32, f32) {
         match entity_type {
@@ -480,6 +895,7 @@ impl WorldSystem {
             EntityType::HostileInfected => (50.0, 1350.0, 640.0, 850.0),
             EntityType::Animal => (50.0, 1200.0, 650.0, 1150.0),
             EntityType::Shelter => (0.0, 1600.0, 0.0, 800.0),
+            EntityType::Pickup => (50.0, 1400.0, 650.0, 1150.0),
         }
     }
 
@@ -501,169 +917,29 @@ impl WorldSystem {
     }
 
     /// Spawn shelters throughout the world for vampire protection
-    fn spawn_world_shelters(entities: &mut Vec<GameEntity>, next_entity_id: &mut u32) {
-        use crate::components::{ShelterCondition, ShelterType};
+    fn spawn_world_shelters(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        rng: &RandGenerator,
+    ) {
         use crate::systems::ShelterSystem;
 
-        // Spawn a variety of shelters across the map
-        let shelter_locations = [
-            // Caves - high protection, scattered around edges
-            (
-                200.0,
-                150.0,
-                ShelterType::Cave,
-                Some(ShelterCondition::Good),
-                Some("Ancient Cave"),
-            ),
-            (
-                800.0,
-                100.0,
-                ShelterType::Cave,
-                Some(ShelterCondition::Pristine),
-                Some("Deep Cavern"),
-            ),
-            (
-                1200.0,
-                400.0,
-                ShelterType::Cave,
-                Some(ShelterCondition::Damaged),
-                None,
-            ),
-            // Buildings - medium protection, more central
-            (
-                500.0,
-                300.0,
-                ShelterType::Building,
-                Some(ShelterCondition::Good),
-                Some("Abandoned House"),
-            ),
-            (
-                700.0,
-                500.0,
-                ShelterType::Building,
-                Some(ShelterCondition::Damaged),
-                Some("Old Warehouse"),
-            ),
-            (
-                900.0,
-                250.0,
-                ShelterType::Building,
-                Some(ShelterCondition::Poor),
-                None,
-            ),
-            // Underground bunkers - maximum protection, rare
-            (
-                350.0,
-                650.0,
-                ShelterType::Underground,
-                Some(ShelterCondition::Pristine),
-                Some("Emergency Bunker"),
-            ),
-            (
-                1000.0,
-                700.0,
-                ShelterType::Underground,
-                Some(ShelterCondition::Good),
-                None,
-            ),
-            // Ruins - atmospheric, medium protection
-            (
-                150.0,
-                400.0,
-                ShelterType::Ruins,
-                Some(ShelterCondition::Damaged),
-                Some("Temple Ruins"),
-            ),
-            (
-                650.0,
-                200.0,
-                ShelterType::Ruins,
-                Some(ShelterCondition::Poor),
-                Some("Castle Remains"),
-            ),
-            (
-                1100.0,
-                650.0,
-                ShelterType::Ruins,
-                Some(ShelterCondition::Good),
-                None,
-            ),
-            // Sheds - common, lower protection
-            (
-                450.0,
-                450.0,
-                ShelterType::Shed,
-                Some(ShelterCondition::Good),
-                None,
-            ),
-            (
-                750.0,
-                350.0,
-                ShelterType::Shed,
-                Some(ShelterCondition::Damaged),
-                None,
-            ),
-            (
-                550.0,
-                650.0,
-                ShelterType::Shed,
-                Some(ShelterCondition::Good),
-                None,
-            ),
-            // Tree cover - temporary protection, natural
-            (
-                300.0,
-                500.0,
-                ShelterType::TreeCover,
-                Some(ShelterCondition::Good),
-                Some("Dense Grove"),
-            ),
-            (
-                850.0,
-                450.0,
-                ShelterType::TreeCover,
-                Some(ShelterCondition::Good),
-                None,
-            ),
-            (
-                1150.0,
-                200.0,
-                ShelterType::TreeCover,
-                Some(ShelterCondition::Damaged),
-                None,
-            ),
-            // Bridge underpasses - urban shelter
-            (
-                600.0,
-                400.0,
-                ShelterType::BridgeUnderpass,
-                Some(ShelterCondition::Good),
-                Some("Highway Underpass"),
-            ),
-            (
-                950.0,
-                550.0,
-                ShelterType::BridgeUnderpass,
-                Some(ShelterCondition::Damaged),
-                None,
-            ),
-        ];
-
-        // Spawn shelters with better distribution
+        // Spawn the placement table's shelters with better distribution
         let mut spawned_shelters = Vec::new();
 
-        for (desired_x, desired_y, shelter_type, condition, name) in shelter_locations.iter() {
-            let (spawn_x, spawn_y) = if Self::has_ground_at_position(*desired_x, *desired_y) {
+        for site in &WorldData::current().shelters.wild {
+            let (desired_x, desired_y) = site.position;
+            let (spawn_x, spawn_y) = if Self::has_ground_at_position(desired_x, desired_y) {
                 // If already on valid ground, use original position
-                (*desired_x, *desired_y)
-            } else if Self::is_relocatable_to_ground(*desired_x, *desired_y) {
+                (desired_x, desired_y)
+            } else if Self::is_relocatable_to_ground(desired_x, desired_y) {
                 // If close to ground area, relocate to a random ground position
                 // but avoid clustering by checking against already spawned shelters
                 let mut attempts = 0;
                 let max_attempts = 10;
 
                 loop {
-                    let (candidate_x, candidate_y) = Self::generate_random_ground_position();
+                    let (candidate_x, candidate_y) = Self::generate_random_ground_position(rng);
 
                     // Check if this position is too close to existing shelters
                     let min_distance = 120.0; // Minimum distance between shelters
@@ -683,7 +959,7 @@ impl WorldSystem {
                 // If too far from ground area, skip this shelter
                 println!(
                     "Info: Skipping shelter '{}' at ({}, {}) - too far from ground area",
-                    name.as_ref().unwrap_or(&"Unnamed"),
+                    site.name.as_deref().unwrap_or("Unnamed"),
                     desired_x,
                     desired_y
                 );
@@ -696,16 +972,127 @@ impl WorldSystem {
             // Spawn the shelter
             ShelterSystem::spawn_shelter(
                 entities,
-                next_entity_id,
-                shelter_type.clone(),
+                entity_ids,
+                site.shelter_type.clone(),
                 spawn_x,
                 spawn_y,
-                condition.clone(),
-                name.map(|s| s.to_string()),
+                site.condition.clone(),
+                site.name.clone(),
             );
         }
     }
 
+    /// Spawn the homes still lived in by human families. Vampires cannot
+    /// enter them without an invitation.
+    pub fn settle_households(entities: &mut Vec<GameEntity>, entity_ids: &mut EntityAllocator) {
+        use crate::systems::ShelterSystem;
+
+        for site in &WorldData::current().shelters.households {
+            let (x, y) = site.position;
+            let id = ShelterSystem::spawn_shelter(
+                entities,
+                entity_ids,
+                site.shelter_type.clone(),
+                x,
+                y,
+                site.condition.clone(),
+                site.name.clone(),
+            );
+            if let Some(shelter) = entities
+                .iter_mut()
+                .find(|entity| entity.id == id)
+                .and_then(|entity| entity.shelter.as_mut())
+            {
+                shelter.inhabited = true;
+            }
+        }
+    }
+
+    /// Place the world's item pickups and the locked cellar
+    pub fn spawn_item_caches(entities: &mut Vec<GameEntity>, entity_ids: &mut EntityAllocator) {
+        use crate::systems::ShelterSystem;
+
+        for (x, y, item, quantity) in ITEM_CACHES {
+            Self::spawn_pickup(entities, entity_ids, item, quantity, x, y);
+        }
+
+        let (x, y, name) = LOCKED_CELLAR;
+        let id = ShelterSystem::spawn_shelter(
+            entities,
+            entity_ids,
+            ShelterType::Underground,
+            x,
+            y,
+            Some(ShelterCondition::Pristine),
+            Some(name.to_string()),
+        );
+        if let Some(shelter) = entities
+            .iter_mut()
+            .find(|entity| entity.id == id)
+            .and_then(|entity| entity.shelter.as_mut())
+        {
+            shelter.locked = true;
+        }
+    }
+
+    /// Spawn an item lying on the ground for the player to pick up
+    pub fn spawn_pickup(
+        entities: &mut Vec<GameEntity>,
+        entity_ids: &mut EntityAllocator,
+        item: ItemKind,
+        quantity: u32,
+        x: f32,
+        y: f32,
+    ) -> u32 {
+        let id = entity_ids.allocate();
+
+        entities.push(GameEntity {
+            id,
+            position: Position::new(x, y),
+            velocity: None,
+            entity_type: EntityType::Pickup,
+            health: None,
+            combat_stats: None,
+            ai_state: AIState::Idle,
+            blood_meter: None,
+            vampire_abilities: None,
+            shelter: None,
+            shelter_occupancy: None,
+            color: WHITE, // Pickups are drawn by item kind
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: Some(Pickup::new(item, quantity)),
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
+        });
+        id
+    }
+
+    /// Running water and garlic fields laid over the world
+    pub fn place_hazards() -> Vec<HazardZone> {
+        vec![
+            // A stream running the full depth of the ground, east of the clans
+            HazardZone::new(HazardKind::RunningWater, 1040.0, GROUND_LEVEL, 40.0, 560.0),
+            // A farmer's garlic patch in the south-west
+            HazardZone::new(HazardKind::GarlicField, 80.0, 950.0, 180.0, 120.0),
+        ]
+    }
+
+    /// Territories the player can capture, laid over the hunting grounds
+    pub fn place_territories() -> TerritoryManager {
+        TerritoryManager::new(vec![
+            Territory::new("Old Mill", 280.0, 780.0, 120.0, 15.0),
+            Territory::new("Crossroads", 720.0, 760.0, 110.0, 20.0),
+            Territory::new("Chapel Ruins", 1230.0, 780.0, 110.0, 25.0),
+        ])
+    }
+
     /// Find a safe spawn position for an entity type
     pub fn find_safe_spawn_position(
         entities: &[GameEntity],
@@ -735,13 +1122,13 @@ mod tests {
     #[test]
     fn test_player_spawn() {
         let mut entities = Vec::new();
-        let mut next_id = 0;
+        let mut ids = EntityAllocator::default();
 
-        let player_id = WorldSystem::spawn_player(&mut entities, &mut next_id);
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
 
         assert_eq!(entities.len(), 1);
         assert_eq!(player_id, 0);
-        assert_eq!(next_id, 1);
+        assert_eq!(ids.len(), 1);
         assert!(matches!(entities[0].entity_type, EntityType::Player));
     }
 
@@ -781,6 +1168,17 @@ mod tests {
             shelter: None,
             shelter_occupancy: None,
             color: WHITE,
+            visual_state: VisualState::default(),
+            corpse: None,
+            inventory: None,
+            pickup: None,
+            schedule: None,
+            wander: None,
+            collider: None,
+            hunger: None,
+            buffs: None,
+            progression: None,
+            species: None,
         }];
 
         // Position too close should be invalid
@@ -793,4 +1191,197 @@ mod tests {
             &entities, 200.0, 200.0, 50.0
         ));
     }
+
+    #[test]
+    fn test_signposts_point_to_territories() {
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let player_id = WorldSystem::spawn_player(&mut entities, &mut ids);
+        WorldSystem::spawn_all_clan_leaders(&mut entities, &mut ids);
+        crate::systems::ShelterSystem::spawn_shelter(
+            &mut entities,
+            &mut ids,
+            ShelterType::Cave,
+            250.0,
+            700.0,
+            None,
+            None,
+        );
+
+        let mut signposts = WorldSystem::place_signposts(&entities);
+        assert_eq!(signposts.len(), SIGNPOST_SITES.len());
+        assert!(signposts.iter().all(|signpost| {
+            signpost.position.distance_to(&signpost.destination) >= SIGNPOST_MIN_DISTANCE
+        }));
+
+        // The sign by the player's spawn points back west to the Bone Pit
+        let near_spawn = &signposts[1];
+        assert_eq!(near_spawn.text(), "Bone Pit - 200m west");
+
+        // Too far away to read
+        entities[0].position = Position::new(400.0, 900.0);
+        assert!(WorldSystem::read_signpost(&mut signposts, &mut entities, player_id).is_none());
+
+        entities[0].position = Position::new(400.0, 690.0);
+        let message = WorldSystem::read_signpost(&mut signposts, &mut entities, player_id);
+        assert!(message.unwrap().contains("1 shelter marked"));
+        assert!(signposts[1].read);
+        assert!(
+            entities
+                .last()
+                .unwrap()
+                .shelter
+                .as_ref()
+                .unwrap()
+                .discovered
+        );
+    }
+
+    #[test]
+    fn test_terrain_streams_around_camera() {
+        let mut ground_tiles = Vec::new();
+        WorldSystem::initialize_ground_terrain(&mut ground_tiles, 0);
+        let mut chunks = TerrainChunks::from_tiles(&ground_tiles);
+        let mut bounds = WorldBounds::default();
+        let original: Vec<(f32, f32, String)> = ground_tiles
+            .iter()
+            .map(|tile| (tile.x, tile.y, format!("{:?}", tile.tile_type)))
+            .collect();
+
+        // Travel far east: new chunks appear and the start area is dropped
+        let far_east = Position::new(20_000.0, 800.0);
+        let (loaded, unloaded) =
+            WorldSystem::stream_terrain(&mut ground_tiles, &mut chunks, &mut bounds, far_east, 0);
+        assert_eq!(loaded, 9);
+        assert!(unloaded > 0);
+        assert!(chunks.len() <= 25);
+        assert!(ground_tiles.iter().all(|tile| tile.x >= 19_000.0));
+        assert!(ground_tiles.iter().all(|tile| tile.y >= GROUND_LEVEL));
+        assert!(bounds.max_x > far_east.x + TerrainChunks::SIZE / 2.0);
+
+        // Standing still loads nothing more
+        assert_eq!(
+            WorldSystem::stream_terrain(&mut ground_tiles, &mut chunks, &mut bounds, far_east, 0),
+            (0, 0)
+        );
+
+        // Coming back regenerates identical terrain
+        let home = Position::new(800.0, 800.0);
+        WorldSystem::stream_terrain(&mut ground_tiles, &mut chunks, &mut bounds, home, 0);
+        // The world stays as wide as it has been explored
+        assert!(bounds.max_x > far_east.x);
+        for tile in &ground_tiles {
+            if let Some((_, _, tile_type)) = original
+                .iter()
+                .find(|(x, y, _)| *x == tile.x && *y == tile.y)
+            {
+                assert_eq!(*tile_type, format!("{:?}", tile.tile_type));
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_lays_out_the_same_world() {
+        let layout = |seed: u64| {
+            let mut entities = Vec::new();
+            let mut ground_tiles = Vec::new();
+            WorldSystem::initialize_world(
+                &mut entities,
+                &mut HashMap::new(),
+                &mut Vec::new(),
+                &mut Moon::new(),
+                &mut ground_tiles,
+                &mut EntityAllocator::default(),
+                Difficulty::Normal,
+                seed,
+            );
+            // Infected hunger and clan patrols are rolled too
+            let positions: Vec<_> = entities
+                .iter()
+                .map(|entity| {
+                    (
+                        entity.id,
+                        entity.position,
+                        entity.hunger.as_ref().map(|hunger| hunger.current),
+                        entity
+                            .schedule
+                            .as_ref()
+                            .map(|schedule| (schedule.patrol_angle, schedule.patrol_radius)),
+                    )
+                })
+                .collect();
+            let tiles: Vec<String> = ground_tiles
+                .iter()
+                .map(|tile| format!("{:?}", tile.tile_type))
+                .collect();
+            (positions, tiles)
+        };
+
+        // Nothing is left to the unseeded global generator
+        let first = layout(1897);
+        rand::srand(42);
+        assert_eq!(first, layout(1897));
+        let (positions, tiles) = layout(1897);
+        assert!(positions.iter().any(|(_, _, hunger, _)| hunger.is_some()));
+        assert!(positions.iter().any(|(_, _, _, patrol)| patrol.is_some()));
+        let (other_positions, other_tiles) = layout(1922);
+        assert_ne!(positions, other_positions);
+        assert_ne!(tiles, other_tiles);
+    }
+
+    #[test]
+    fn test_species_follow_the_ground_they_roam() {
+        // Deer keep off bare stone; every species turns up somewhere
+        for step in 0..100 {
+            let roll = step as f32 / 100.0;
+            assert_ne!(
+                WorldSystem::species_for_ground(Some(TileType::Stone), roll),
+                Species::Deer
+            );
+        }
+        let mut seen = Vec::new();
+        for tile in [TileType::Grass, TileType::DeadGrass, TileType::Stone] {
+            for step in 0..100 {
+                let species = WorldSystem::species_for_ground(Some(tile), step as f32 / 100.0);
+                if !seen.contains(&species) {
+                    seen.push(species);
+                }
+            }
+        }
+        assert_eq!(seen.len(), Species::ALL.len());
+
+        // Each species brings its own stats
+        let mut entities = Vec::new();
+        let mut ids = EntityAllocator::default();
+        let rat = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Rat, 500.0, 800.0);
+        let wolf = WorldSystem::spawn_animal(&mut entities, &mut ids, Species::Wolf, 540.0, 800.0);
+        let rat = EntityFinder::by_id(&entities, rat).unwrap();
+        let wolf = EntityFinder::by_id(&entities, wolf).unwrap();
+        assert_eq!(rat.species, Some(Species::Rat));
+        assert!(rat.combat_stats.is_none());
+        assert!(wolf.combat_stats.is_some());
+        assert!(wolf.health.as_ref().unwrap().max > rat.health.as_ref().unwrap().max);
+    }
+
+    #[test]
+    fn test_rivers_and_lakes_leave_the_road_and_start_dry() {
+        let water_tiles = |seed: u64| -> Vec<(i32, i32)> {
+            (0..25)
+                .flat_map(|x| (10..19).map(move |y| (x, y)))
+                .filter(|&(x, y)| WorldSystem::determine_tile_type(x, y, seed) == TileType::Water)
+                .collect()
+        };
+
+        for seed in [0, 1897, 1922] {
+            let water = water_tiles(seed);
+            assert!(!water.is_empty());
+            assert_eq!(water, water_tiles(seed));
+            // The road bridges the river and the player starts on dry land
+            assert!(water.iter().all(|&(_, y)| y != 10));
+            let (min_x, max_x, min_y, max_y) = START_CLEARING;
+            assert!(!water
+                .iter()
+                .any(|&(x, y)| (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y)));
+        }
+    }
 }
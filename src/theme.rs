@@ -0,0 +1,187 @@
+//! Theme Module
+//!
+//! Color palettes for the procedural sprites and ground tiles. Each theme is
+//! a JSON file in `assets/themes/`; the built-in ones are compiled into the
+//! game, and debug builds watch the file of the selected theme so colors can
+//! be tuned while the game runs.
+
+use macroquad::prelude::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors that can occur while reading a theme file
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    #[error("could not read theme file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("theme file is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+}
+
+/// The built-in themes selectable in settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PaletteTheme {
+    #[default]
+    Classic,
+    Gothic,
+    HighContrast,
+}
+
+impl PaletteTheme {
+    pub const ALL: [PaletteTheme; 3] = [
+        PaletteTheme::Classic,
+        PaletteTheme::Gothic,
+        PaletteTheme::HighContrast,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PaletteTheme::Classic => "Classic",
+            PaletteTheme::Gothic => "Gothic",
+            PaletteTheme::HighContrast => "High Contrast",
+        }
+    }
+
+    /// The next theme in `ALL`, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|theme| theme == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Theme file on disk, watched for changes in debug builds
+    pub fn path(&self) -> PathBuf {
+        let file = match self {
+            PaletteTheme::Classic => "classic.json",
+            PaletteTheme::Gothic => "gothic.json",
+            PaletteTheme::HighContrast => "high_contrast.json",
+        };
+        PathBuf::from("assets/themes").join(file)
+    }
+
+    fn embedded(&self) -> &'static str {
+        match self {
+            PaletteTheme::Classic => include_str!("../assets/themes/classic.json"),
+            PaletteTheme::Gothic => include_str!("../assets/themes/gothic.json"),
+            PaletteTheme::HighContrast => include_str!("../assets/themes/high_contrast.json"),
+        }
+    }
+
+    /// The palette as compiled into the game
+    pub fn palette(&self) -> Palette {
+        serde_json::from_str(self.embedded()).expect("built-in theme is valid")
+    }
+}
+
+/// An RGB color as written in theme files
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rgb(pub [f32; 3]);
+
+impl Rgb {
+    pub fn color(&self) -> Color {
+        let [r, g, b] = self.0;
+        Color::new(r, g, b, 1.0)
+    }
+}
+
+/// Every color a theme sets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    pub name: String,
+    pub vampire_body: Rgb,
+    pub vampire_skin: Rgb,
+    pub vampire_eyes: Rgb,
+    pub vampire_cape: Rgb,
+    pub infected_body: Rgb,
+    pub infected_skin: Rgb,
+    pub infected_eyes: Rgb,
+    pub animal_fur: Rgb,
+    pub animal_hide: Rgb,
+    /// Faces of clan leaders and members
+    pub clan_skin: Rgb,
+    /// Clothing color of each clan, by clan name
+    pub clans: HashMap<String, Rgb>,
+    pub grass: Rgb,
+    pub grass_detail: Rgb,
+    pub dead_grass: Rgb,
+    pub dead_grass_detail: Rgb,
+    pub dirt: Rgb,
+    pub dirt_detail: Rgb,
+    pub stone: Rgb,
+    pub stone_detail: Rgb,
+    pub water: Rgb,
+    /// Ripples shimmering across water
+    pub water_detail: Rgb,
+}
+
+impl Palette {
+    /// Read a palette from a theme file
+    pub fn load(path: &std::path::Path) -> Result<Self, ThemeError> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// The clan's color in this theme, or `fallback` for clans it does not list
+    pub fn clan_color(&self, clan_name: &str, fallback: Color) -> Color {
+        self.clans
+            .get(clan_name)
+            .map_or(fallback, |color| color.color())
+    }
+
+    /// A sprite color by its field name, as sprite atlas layers refer to them
+    pub fn sprite_color(&self, name: &str) -> Option<Color> {
+        let color = match name {
+            "vampire_body" => self.vampire_body,
+            "vampire_skin" => self.vampire_skin,
+            "vampire_eyes" => self.vampire_eyes,
+            "vampire_cape" => self.vampire_cape,
+            "infected_body" => self.infected_body,
+            "infected_skin" => self.infected_skin,
+            "infected_eyes" => self.infected_eyes,
+            "animal_fur" => self.animal_fur,
+            "animal_hide" => self.animal_hide,
+            "clan_skin" => self.clan_skin,
+            _ => return None,
+        };
+        Some(color.color())
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        PaletteTheme::default().palette()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::WorldSystem;
+
+    #[test]
+    fn test_built_in_themes_parse_and_cover_every_clan() {
+        for theme in PaletteTheme::ALL {
+            let palette = theme.palette();
+            assert_eq!(palette.name, theme.display_name());
+            for clan in ["Bone-Eaters", "Flame-Haters", "Night-Bloods"] {
+                assert!(
+                    palette.clans.contains_key(clan),
+                    "{} lacks {}",
+                    palette.name,
+                    clan
+                );
+            }
+        }
+
+        // The classic theme keeps the original clan colors
+        let classic = PaletteTheme::Classic.palette();
+        assert_eq!(
+            classic.clan_color("Night-Bloods", Color::new(0.0, 0.0, 0.0, 1.0)),
+            WorldSystem::clan_member_color("Night-Bloods")
+        );
+        assert_eq!(PaletteTheme::HighContrast.next(), PaletteTheme::Classic);
+    }
+}
@@ -0,0 +1,339 @@
+//! World Data Module
+//!
+//! The moddable tables the world is built from: creature archetypes, the
+//! clans and their camps, and where shelters stand. Each table is a JSON file
+//! in `assets/world/`; the shipped files are compiled into the game, and a
+//! copy found through the asset search paths at startup replaces them, so the
+//! world can be tweaked without recompiling.
+
+use crate::assets::AssetManager;
+use crate::components::{CombatStats, ShelterCondition, ShelterType, Species, TileType};
+use crate::theme::Rgb;
+use macroquad::prelude::Color;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+const ARCHETYPES_FILE: &str = "world/archetypes.json";
+const CLANS_FILE: &str = "world/clans.json";
+const SHELTERS_FILE: &str = "world/shelters.json";
+
+const EMBEDDED_ARCHETYPES: &str = include_str!("../assets/world/archetypes.json");
+const EMBEDDED_CLANS: &str = include_str!("../assets/world/clans.json");
+const EMBEDDED_SHELTERS: &str = include_str!("../assets/world/shelters.json");
+
+/// The tables in use, set once at startup
+static WORLD_DATA: OnceLock<WorldData> = OnceLock::new();
+
+/// Errors that can occur while reading a world data file
+#[derive(Debug, Error)]
+pub enum WorldDataError {
+    #[error("world data file is not valid: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("world data file lists no clans")]
+    NoClans,
+}
+
+/// Attack, defense and poise of a creature that can fight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CombatTemplate {
+    pub attack: f32,
+    pub defense: f32,
+    /// Resistance to staggering; the usual amount when left out
+    #[serde(default)]
+    pub poise: Option<f32>,
+}
+
+impl CombatTemplate {
+    pub fn stats(&self) -> CombatStats {
+        let stats = CombatStats::new(self.attack, self.defense);
+        CombatStats {
+            poise: self.poise.unwrap_or(stats.poise),
+            ..stats
+        }
+    }
+}
+
+/// Starting stats for one kind of creature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Archetype {
+    pub health: f32,
+    /// Creatures without combat stats cannot fight back
+    #[serde(default)]
+    pub combat: Option<CombatTemplate>,
+    /// How many roam the world at the start of a Normal game
+    #[serde(default)]
+    pub count: usize,
+    /// How far from where they spawned they wander
+    #[serde(default)]
+    pub wander_range: f32,
+}
+
+/// How likely a species is to be the one found on each kind of ground,
+/// relative to the other species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainWeights {
+    pub grass: f32,
+    pub dead_grass: f32,
+    pub dirt: f32,
+    pub stone: f32,
+}
+
+impl TerrainWeights {
+    pub fn weight(&self, tile: TileType) -> f32 {
+        match tile {
+            TileType::Grass => self.grass,
+            TileType::DeadGrass => self.dead_grass,
+            TileType::Dirt => self.dirt,
+            TileType::Stone => self.stone,
+            // Nothing makes its home in the water
+            TileType::Water => 0.0,
+        }
+    }
+}
+
+/// Stats and habits of one animal species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesArchetype {
+    pub health: f32,
+    /// Species without combat stats cannot fight back
+    #[serde(default)]
+    pub combat: Option<CombatTemplate>,
+    pub wander_range: f32,
+    /// Scale on the blood drunk from it
+    pub blood_yield: f32,
+    /// Scale on how fast it wanders, flees and chases
+    pub speed: f32,
+    /// Turns on the player after dark instead of fleeing
+    #[serde(default)]
+    pub hunts_at_night: bool,
+    pub terrain: TerrainWeights,
+    pub color: Rgb,
+}
+
+impl SpeciesArchetype {
+    pub fn color(&self) -> Color {
+        self.color.color()
+    }
+}
+
+/// Every animal species
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesTable {
+    pub rat: SpeciesArchetype,
+    pub deer: SpeciesArchetype,
+    pub wolf: SpeciesArchetype,
+}
+
+/// Every creature archetype, from `archetypes.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Archetypes {
+    pub player: Archetype,
+    pub clan_leader: Archetype,
+    pub clan_member: Archetype,
+    pub infected: Archetype,
+    /// How many animals roam the world; each species sets its own stats
+    pub animal: Archetype,
+    pub species: SpeciesTable,
+}
+
+impl Archetypes {
+    pub fn species(&self, species: Species) -> &SpeciesArchetype {
+        match species {
+            Species::Rat => &self.species.rat,
+            Species::Deer => &self.species.deer,
+            Species::Wolf => &self.species.wolf,
+        }
+    }
+}
+
+/// A clan, its leader and where they camp, from `clans.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClanDef {
+    pub name: String,
+    pub leader: String,
+    /// Members the clan starts with
+    pub members: u32,
+    /// Where the leader stands
+    pub camp: (f32, f32),
+    /// Territory the clan holds around its camp, named on signposts
+    #[serde(default)]
+    pub territory: Option<String>,
+    pub leader_color: Rgb,
+    pub member_color: Rgb,
+}
+
+impl ClanDef {
+    pub fn leader_color(&self) -> Color {
+        self.leader_color.color()
+    }
+
+    pub fn member_color(&self) -> Color {
+        self.member_color.color()
+    }
+}
+
+/// Where a shelter stands and what state it is in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShelterSite {
+    pub position: (f32, f32),
+    pub shelter_type: ShelterType,
+    #[serde(default)]
+    pub condition: Option<ShelterCondition>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// The shelter placement tables, from `shelters.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShelterTable {
+    /// Empty shelters scattered around the map; those off the ground are
+    /// moved onto it
+    pub wild: Vec<ShelterSite>,
+    /// Homes still lived in by human families
+    pub households: Vec<ShelterSite>,
+}
+
+/// All the tables the world is built from
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldData {
+    pub archetypes: Archetypes,
+    pub clans: Vec<ClanDef>,
+    pub shelters: ShelterTable,
+}
+
+impl WorldData {
+    /// The tables as compiled into the game
+    pub fn embedded() -> Self {
+        Self {
+            archetypes: serde_json::from_str(EMBEDDED_ARCHETYPES)
+                .expect("built-in archetypes are valid"),
+            clans: serde_json::from_str(EMBEDDED_CLANS).expect("built-in clans are valid"),
+            shelters: serde_json::from_str(EMBEDDED_SHELTERS).expect("built-in shelters are valid"),
+        }
+    }
+
+    /// Read each table from the assets folder, keeping the built-in one for
+    /// any file that is missing or invalid. Problems are recorded in the
+    /// asset report.
+    pub fn load(assets: &mut AssetManager) -> Self {
+        let mut data = Self::embedded();
+        if let Some(archetypes) = Self::read(assets, ARCHETYPES_FILE, "the built-in archetypes") {
+            data.archetypes = archetypes;
+        }
+        if let Some(clans) = Self::read(assets, CLANS_FILE, "the built-in clans") {
+            data.clans = clans;
+        }
+        if let Some(shelters) = Self::read(assets, SHELTERS_FILE, "the built-in shelters") {
+            data.shelters = shelters;
+        }
+        data
+    }
+
+    fn read<T: serde::de::DeserializeOwned + Validate>(
+        assets: &mut AssetManager,
+        name: &str,
+        fallback: &str,
+    ) -> Option<T> {
+        let bytes = assets.read(name, fallback)?;
+        match Self::parse(&bytes) {
+            Ok(table) => Some(table),
+            Err(error) => {
+                assets.mark_invalid(name, error);
+                None
+            }
+        }
+    }
+
+    /// Parse and check one table
+    pub fn parse<T: serde::de::DeserializeOwned + Validate>(
+        bytes: &[u8],
+    ) -> Result<T, WorldDataError> {
+        let table: T = serde_json::from_slice(bytes)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Make these the tables the world is built from. Only the first call
+    /// counts; returns whether this one did.
+    pub fn install(self) -> bool {
+        WORLD_DATA.set(self).is_ok()
+    }
+
+    /// The tables in use: those installed at startup, or the built-in ones
+    pub fn current() -> &'static WorldData {
+        WORLD_DATA.get_or_init(Self::embedded)
+    }
+
+    /// The definition of a clan by name
+    pub fn clan(&self, clan_name: &str) -> Option<&ClanDef> {
+        self.clans.iter().find(|clan| clan.name == clan_name)
+    }
+}
+
+/// A table that can be rejected after it parses
+pub trait Validate {
+    fn validate(&self) -> Result<(), WorldDataError> {
+        Ok(())
+    }
+}
+
+impl Validate for Archetypes {}
+impl Validate for ShelterTable {}
+
+impl Validate for Vec<ClanDef> {
+    fn validate(&self) -> Result<(), WorldDataError> {
+        if self.is_empty() {
+            return Err(WorldDataError::NoClans);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::prelude::{BEIGE, BLUE};
+    use std::fs;
+
+    #[test]
+    fn test_embedded_tables_match_the_original_world_and_mods_override_them() {
+        let data = WorldData::embedded();
+        assert_eq!(data.archetypes.infected.count, 8);
+        assert_eq!(data.archetypes.animal.count, 12);
+        assert_eq!(
+            data.archetypes
+                .clan_leader
+                .combat
+                .as_ref()
+                .unwrap()
+                .stats()
+                .poise,
+            0.6
+        );
+        assert_eq!(data.clans.len(), 3);
+        assert_eq!(data.clan("Bone-Eaters").unwrap().leader_color(), BEIGE);
+        assert_eq!(data.clan("Night-Bloods").unwrap().member_color(), BLUE);
+        assert_eq!(data.shelters.wild.len(), 19);
+        assert_eq!(data.shelters.households.len(), 2);
+
+        // A modded clans file replaces the built-in one; a broken archetypes
+        // file is reported and the built-in archetypes kept
+        let dir = std::env::temp_dir().join(format!("vampire_world_data_{}", std::process::id()));
+        fs::create_dir_all(dir.join("world")).unwrap();
+        let mut clans = data.clans.clone();
+        clans.truncate(1);
+        clans[0].members = 40;
+        fs::write(dir.join(CLANS_FILE), serde_json::to_string(&clans).unwrap()).unwrap();
+        fs::write(dir.join(ARCHETYPES_FILE), "{ \"player\": 3 }").unwrap();
+
+        let mut assets = AssetManager::new(vec![dir.clone()]);
+        let modded = WorldData::load(&mut assets);
+        assert_eq!(modded.clans, clans);
+        assert_eq!(modded.archetypes, data.archetypes);
+        assert_eq!(modded.shelters, data.shelters);
+        assert_eq!(assets.problems().count(), 2);
+        assert!(WorldData::parse::<Vec<ClanDef>>(b"[]").is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
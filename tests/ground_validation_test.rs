@@ -66,8 +66,9 @@ fn test_world_system_ground_validation() {
 #[test]
 fn test_generate_random_ground_position() {
     // Test random ground position generation
+    let rng = macroquad::rand::RandGenerator::new();
     for _ in 0..10 {
-        let (x, y) = systems::world::WorldSystem::generate_random_ground_position();
+        let (x, y) = systems::world::WorldSystem::generate_random_ground_position(&rng);
         assert!(x >= 64.0); // Should be within padded bounds
         assert!(x <= 1536.0); // Should be within padded bounds
         assert!(y >= 704.0); // Should be at ground level + padding (640 + 64)
@@ -111,12 +112,12 @@ fn test_is_relocatable_to_ground() {
 #[test]
 fn test_safe_shelter_spawning() {
     let mut entities = Vec::new();
-    let mut next_id = 0;
+    let mut ids = EntityAllocator::default();
 
     // Test spawning on valid ground
     let result = systems::shelter::ShelterSystem::spawn_shelter_safe(
         &mut entities,
-        &mut next_id,
+        &mut ids,
         components::shelter::ShelterType::Cave,
         500.0,
         700.0,
@@ -129,7 +130,7 @@ fn test_safe_shelter_spawning() {
     // Test spawning on invalid ground (should fail)
     let result = systems::shelter::ShelterSystem::spawn_shelter_safe(
         &mut entities,
-        &mut next_id,
+        &mut ids,
         components::shelter::ShelterType::Cave,
         500.0,
         400.0, // Above ground level
@@ -195,7 +196,7 @@ fn test_world_initialization_with_ground_validation() {
     let mut stars = Vec::new();
     let mut moon = components::environment::Moon::new();
     let mut ground_tiles = Vec::new();
-    let mut next_entity_id = 0;
+    let mut entity_ids = EntityAllocator::default();
 
     // Initialize the world
     let player_id = systems::world::WorldSystem::initialize_world(
@@ -204,7 +205,9 @@ fn test_world_initialization_with_ground_validation() {
         &mut stars,
         &mut moon,
         &mut ground_tiles,
-        &mut next_entity_id,
+        &mut entity_ids,
+        Difficulty::Normal,
+        7,
     );
 
     // Check that ground tiles were created
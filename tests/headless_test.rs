@@ -0,0 +1,134 @@
+//! Headless simulation tests
+//!
+//! Whole days of play run without a window through `HeadlessGame`.
+
+use macroquad::prelude::KeyCode;
+use vampire_rpg::components::{AIState, Species, Stagger, TerrainChunks, WorldBounds};
+use vampire_rpg::systems::WorldSystem;
+use vampire_rpg::{HeadlessGame, InputScript, NewGameSettings};
+
+/// A game with nothing alive but the player, hiding in an open shelter
+fn alone_in_a_shelter(script: InputScript) -> HeadlessGame {
+    let settings = NewGameSettings {
+        max_hostiles: 0,
+        ..NewGameSettings::default()
+    };
+    let mut game = HeadlessGame::new(settings).with_script(script);
+
+    let state = &mut game.game_state;
+    let player_id = state.player_id;
+    state
+        .entities
+        .retain(|entity| entity.id == player_id || entity.shelter.is_some());
+    let shelter = state
+        .entities
+        .iter()
+        .find(|entity| {
+            entity
+                .shelter
+                .as_ref()
+                .is_some_and(|shelter| !shelter.locked && !shelter.bars_vampires())
+        })
+        .map(|entity| entity.position)
+        .expect("the world has an open shelter");
+    if let Some(player) = state.entities.iter_mut().find(|e| e.id == player_id) {
+        player.position = shelter;
+    }
+    game
+}
+
+#[test]
+fn test_player_starves_without_feeding() {
+    let mut game = alone_in_a_shelter(InputScript::new());
+    let ticks = game.simulate_days(3);
+
+    assert!(game.is_over(), "still alive after {} ticks", ticks);
+    assert!(game.game_state.time.day_count() < 3);
+    assert_eq!(game.game_state.feeding_count, 0);
+    let player = game.player().unwrap();
+    assert_eq!(player.blood_meter.as_ref().unwrap().current, 0.0);
+    assert_eq!(player.health.as_ref().unwrap().current, 0.0);
+}
+
+#[test]
+fn test_walking_east_streams_in_new_ground() {
+    let settings = NewGameSettings {
+        max_hostiles: 0,
+        ..NewGameSettings::default()
+    };
+    let script = InputScript::new().hold(0, 900, KeyCode::D);
+    let mut game = HeadlessGame::new(settings).with_script(script);
+    let player_id = game.game_state.player_id;
+    game.game_state
+        .entities
+        .retain(|entity| entity.id == player_id);
+    let start_area = WorldBounds::default();
+    assert!(!game
+        .game_state
+        .terrain_chunks
+        .is_loaded(TerrainChunks::coord_of(start_area.max_x + 600.0, 900.0)));
+
+    game.simulate_ticks(900);
+
+    // The player walks off the edge of the starting area rather than
+    // stopping at it, and the ground ahead of them is streamed in
+    let player = game.player().unwrap().position;
+    assert!(
+        player.x > start_area.max_x + 200.0,
+        "stopped at {:?}",
+        player
+    );
+    let state = &game.game_state;
+    assert!(state.terrain_chunks.is_loaded(TerrainChunks::coord_of(
+        player.x + TerrainChunks::SIZE,
+        player.y
+    )));
+    assert!(state.world_bounds.max_x > player.x + TerrainChunks::SIZE / 2.0);
+    assert!(state
+        .ground_tiles
+        .iter()
+        .any(|tile| tile.x > start_area.max_x + TerrainChunks::SIZE));
+}
+
+#[test]
+fn test_holding_feed_in_a_shelter_hibernates_without_draining() {
+    // F takes the player inside; R is then held well past the drain hold
+    // and on to the hibernate hold
+    let script = InputScript::new()
+        .press(0, KeyCode::F)
+        .hold(10, 100, KeyCode::R);
+    let mut game = alone_in_a_shelter(script);
+    let state = &mut game.game_state;
+    state.time.set_time(12.0);
+    let player = state
+        .entities
+        .iter()
+        .find(|entity| entity.id == state.player_id)
+        .unwrap()
+        .position;
+    let deer = WorldSystem::spawn_animal(
+        &mut state.entities,
+        &mut state.entity_ids,
+        Species::Deer,
+        player.x + 10.0,
+        player.y,
+    );
+    // Dazed in place, so it stays within reach but can still be drained
+    if let Some(entity) = state.entities.iter_mut().find(|entity| entity.id == deer) {
+        entity.ai_state = AIState::Staggered(Stagger::new(60.0, false, AIState::Idle));
+    }
+    state.spatial_grid.rebuild(&state.entities);
+
+    game.simulate_ticks(100);
+
+    let state = &game.game_state;
+    assert!(state.hibernation.is_some(), "never fell asleep");
+    assert_eq!(state.feeding_count, 0);
+    let deer = state
+        .entities
+        .iter()
+        .find(|entity| entity.id == deer)
+        .unwrap();
+    let health = deer.health.as_ref().unwrap();
+    assert_eq!(health.current, health.max);
+}
@@ -4,7 +4,8 @@
 //! and that the optimizations don't break existing functionality.
 
 use vampire_rpg::components::*;
-use vampire_rpg::{GameState, Renderer};
+use vampire_rpg::headless::HeadlessGame;
+use vampire_rpg::{GameState, NewGameSettings, Renderer};
 
 #[test]
 fn test_ground_tile_texture_data_generation() {
@@ -139,6 +140,32 @@ fn test_performance_optimizations_dont_break_game_state() {
         .contains(&"Performance test message".to_string()));
 }
 
+#[test]
+fn test_thousand_entity_stress_scenario() {
+    // The scenario `examples/stress_benchmark.rs` times; here it only has to
+    // hold together, since debug-build timings say nothing
+    let mut game = HeadlessGame::new(NewGameSettings::default());
+    game.populate(1000);
+    assert_eq!(game.game_state.entities.len(), 1000);
+
+    game.simulate_ticks(60);
+
+    // Collision keeps the crowd apart: no two live creatures share a spot
+    let mut bodies: Vec<_> = game
+        .game_state
+        .entities
+        .iter()
+        .filter(|entity| matches!(entity.collider, Some(Collider::Circle { .. })))
+        .filter(|entity| entity.health.as_ref().is_some_and(Health::is_alive))
+        .map(|entity| (entity.position.x as i32, entity.position.y as i32))
+        .collect();
+    let count = bodies.len();
+    bodies.sort_unstable();
+    bodies.dedup();
+    assert!(bodies.len() as f32 > count as f32 * 0.95);
+    assert!(game.game_state.entities.len() > 900);
+}
+
 #[test]
 fn test_dirt_spot_generation_bounds() {
     // Test that dirt spots are generated within reasonable bounds
@@ -7,6 +7,7 @@ use macroquad::prelude::*;
 use vampire_rpg::components::*;
 use vampire_rpg::input::InputHandler;
 use vampire_rpg::systems::shelter::ShelterSystem;
+use vampire_rpg::systems::spatial::SpatialGrid;
 
 #[test]
 fn test_input_handler_recognizes_f_key() {
@@ -43,10 +44,28 @@ fn test_shelter_interaction_with_no_shelters() {
         shelter: None,
         shelter_occupancy: Some(shelter::ShelterOccupancy::new()),
         color: RED,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(player);
 
-    let result = ShelterSystem::handle_player_shelter_interaction(&mut entities, player_id, 0.0);
+    let grid = SpatialGrid::from_entities(&entities);
+    let result = ShelterSystem::handle_player_shelter_interaction(
+        &mut entities,
+        &grid,
+        player_id,
+        0.0,
+        true,
+    );
 
     // Should return a message indicating no shelters found
     assert!(result.is_some());
@@ -73,6 +92,17 @@ fn test_shelter_interaction_with_nearby_shelter() {
         shelter: None,
         shelter_occupancy: Some(shelter::ShelterOccupancy::new()),
         color: RED,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(player);
 
@@ -91,10 +121,28 @@ fn test_shelter_interaction_with_nearby_shelter() {
         shelter: Some(shelter::Shelter::new(shelter::ShelterType::Cave)), // Cave has 40.0 discovery range
         shelter_occupancy: None,
         color: BROWN,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(shelter_entity);
 
-    let result = ShelterSystem::handle_player_shelter_interaction(&mut entities, player_id, 100.0);
+    let grid = SpatialGrid::from_entities(&entities);
+    let result = ShelterSystem::handle_player_shelter_interaction(
+        &mut entities,
+        &grid,
+        player_id,
+        100.0,
+        true,
+    );
 
     // Should successfully enter the shelter
     assert!(result.is_some());
@@ -143,6 +191,17 @@ fn test_shelter_exit_interaction() {
         shelter: None,
         shelter_occupancy: Some(player_occupancy),
         color: RED,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(player);
 
@@ -163,10 +222,28 @@ fn test_shelter_exit_interaction() {
         shelter: Some(shelter),
         shelter_occupancy: None,
         color: GRAY,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(shelter_entity);
 
-    let result = ShelterSystem::handle_player_shelter_interaction(&mut entities, player_id, 100.0);
+    let grid = SpatialGrid::from_entities(&entities);
+    let result = ShelterSystem::handle_player_shelter_interaction(
+        &mut entities,
+        &grid,
+        player_id,
+        100.0,
+        true,
+    );
 
     // Should successfully exit the shelter
     assert!(result.is_some());
@@ -205,6 +282,17 @@ fn test_shelter_interaction_too_far() {
         shelter: None,
         shelter_occupancy: Some(shelter::ShelterOccupancy::new()),
         color: RED,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(player);
 
@@ -223,10 +311,28 @@ fn test_shelter_interaction_too_far() {
         shelter: Some(shelter::Shelter::new(shelter::ShelterType::Cave)), // Cave has 40.0 discovery range
         shelter_occupancy: None,
         color: BROWN,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(shelter_entity);
 
-    let result = ShelterSystem::handle_player_shelter_interaction(&mut entities, player_id, 100.0);
+    let grid = SpatialGrid::from_entities(&entities);
+    let result = ShelterSystem::handle_player_shelter_interaction(
+        &mut entities,
+        &grid,
+        player_id,
+        100.0,
+        true,
+    );
 
     // Should indicate no shelters nearby
     assert!(result.is_some());
@@ -254,6 +360,17 @@ fn test_shelter_full_capacity() {
         shelter: None,
         shelter_occupancy: Some(shelter::ShelterOccupancy::new()),
         color: RED,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(player);
 
@@ -276,10 +393,28 @@ fn test_shelter_full_capacity() {
         shelter: Some(shelter),
         shelter_occupancy: None,
         color: BROWN,
+        visual_state: VisualState::default(),
+        corpse: None,
+        inventory: None,
+        pickup: None,
+        schedule: None,
+        wander: None,
+        collider: None,
+        hunger: None,
+        buffs: None,
+        progression: None,
+        species: None,
     };
     entities.push(shelter_entity);
 
-    let result = ShelterSystem::handle_player_shelter_interaction(&mut entities, player_id, 100.0);
+    let grid = SpatialGrid::from_entities(&entities);
+    let result = ShelterSystem::handle_player_shelter_interaction(
+        &mut entities,
+        &grid,
+        player_id,
+        100.0,
+        true,
+    );
 
     // Should indicate shelter is full
     assert!(result.is_some());